@@ -1,34 +1,46 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use std::hint::black_box;
-use seal_kit::{HybridRsaKyber, PostQuantumKyber, AsymmetricQSealEngine, TraditionalRsa};
+use seal_kit::{HybridRsaKyber, PostQuantumKyber, AsymmetricQSealEngine, PreparedPublicKey, TraditionalRsa};
 use seal_kit::asymmetric::traits::AsymmetricCryptographicSystem;
 use seal_kit::ConfigManager;
 use std::sync::Arc;
 use std::io::Cursor;
 use std::fs;
-use rsa::{Pkcs1v15Encrypt, RsaPrivateKey};
-use rsa::pkcs8::DecodePrivateKey;
 use criterion::SamplingMode;
 use seal_kit::asymmetric::traits::AsymmetricSyncStreamingSystem;
 use seal_kit::common::streaming::StreamingConfig;
-use seal_kit::common::utils::{from_base64, CryptoConfig};
+use seal_kit::common::utils::CryptoConfig;
+use seal_kit::bench::{SymmetricAlgorithm, SymmetricBench};
 
 fn bench_rsa(c: &mut Criterion) {
     let mut config = CryptoConfig::default();
     config.rsa_key_bits = 2048;
     let (pk, sk) = TraditionalRsa::generate_keypair(&config).unwrap();
-    let data = vec![0u8; 245];
-    c.bench_function("TraditionalRsa encrypt 245B", |b| {
+    let data = vec![0u8; 190];
+    c.bench_function("TraditionalRsa encrypt 190B", |b| {
         b.iter(|| TraditionalRsa::encrypt(black_box(&pk), black_box(&data), None).unwrap());
     });
     let ciphertext_str = TraditionalRsa::encrypt(&pk, &data, None).unwrap().to_string();
-    let ciphertext_bytes = from_base64(&ciphertext_str).unwrap();
-    let rsa_priv = RsaPrivateKey::from_pkcs8_der(&sk.0).unwrap();
-    c.bench_function("TraditionalRsa raw decrypt 245B", |b| {
-        b.iter(|| rsa_priv.decrypt(Pkcs1v15Encrypt, black_box(&ciphertext_bytes)).unwrap());
+    c.bench_function("TraditionalRsa decrypt 190B", |b| {
+        b.iter(|| TraditionalRsa::decrypt(black_box(&sk), black_box(ciphertext_str.as_str()), None).unwrap());
     });
 }
 
+fn bench_prepared_rsa(c: &mut Criterion) {
+    let config = CryptoConfig { rsa_key_bits: 2048, ..CryptoConfig::default() };
+    let (pk, _sk) = TraditionalRsa::generate_keypair(&config).unwrap();
+    let prepared = PreparedPublicKey::from_rsa(&pk).unwrap();
+    let data = vec![0u8; 190];
+    let mut group = c.benchmark_group("PreparedPublicKey");
+    group.bench_function("TraditionalRsa encrypt 190B", |b| {
+        b.iter(|| TraditionalRsa::encrypt(black_box(&pk), black_box(&data), None).unwrap());
+    });
+    group.bench_function("PreparedPublicKey<Rsa> encrypt 190B", |b| {
+        b.iter(|| prepared.encrypt(black_box(&data), None).unwrap());
+    });
+    group.finish();
+}
+
 fn bench_kyber(c: &mut Criterion) {
     let config = CryptoConfig::default();
     let (pk, sk) = PostQuantumKyber::generate_keypair(&config).unwrap();
@@ -236,9 +248,21 @@ fn bench_stream_hybrid_decrypt(c: &mut Criterion) {
     });
 }
 
+fn bench_symmetric(c: &mut Criterion) {
+    let bench = SymmetricBench::new(SymmetricAlgorithm::Aes256Gcm, 64 * 1024).unwrap();
+    c.bench_function("AES-256-GCM encrypt 64KB", |b| {
+        b.iter(|| bench.encrypt_once().unwrap());
+    });
+    c.bench_function("AES-256-GCM decrypt 64KB", |b| {
+        b.iter(|| bench.decrypt_once().unwrap());
+    });
+}
+
 criterion_group!(
     base,
+    bench_symmetric,
     bench_rsa,
+    bench_prepared_rsa,
     bench_kyber,
     bench_hybrid,
     bench_engine,
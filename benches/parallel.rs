@@ -8,6 +8,9 @@ use std::io::Cursor;
 use seal_kit::asymmetric::traits::AsymmetricCryptographicSystem;
 use seal_kit::common::streaming::StreamingConfig;
 use seal_kit::common::utils::CryptoConfig;
+use seal_kit::symmetric::primitives::streaming as symmetric_streaming;
+use seal_kit::symmetric::systems::aes_gcm::AesGcmSystem;
+use seal_kit::symmetric::traits::{SymmetricCryptographicSystem, SymmetricSyncStreamingSystem};
 
 fn bench_stream_rsa_encrypt_parallel(c: &mut Criterion) {
     let mut config = CryptoConfig::default();
@@ -113,8 +116,29 @@ fn bench_stream_hybrid_decrypt_parallel(c: &mut Criterion) {
     });
 }
 
+fn bench_stream_aes_gcm_serial_vs_parallel(c: &mut Criterion) {
+    let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+    let data = vec![0u8; 16 * 1024 * 1024];
+    let scfg = StreamingConfig::default().with_buffer_size(64 * 1024);
+    let mut group = c.benchmark_group("AesGcm encrypt_stream 16MB");
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            let mut writer = Vec::new();
+            AesGcmSystem::encrypt_stream(&key, Cursor::new(&data), &mut writer, &scfg, None).unwrap();
+        });
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            let mut writer = Vec::new();
+            symmetric_streaming::encrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(&data), &mut writer, &scfg, None).unwrap();
+        });
+    });
+    group.finish();
+}
+
 criterion_group!(
     parallel_benches,
+    bench_stream_aes_gcm_serial_vs_parallel,
     bench_stream_rsa_encrypt_parallel,
     bench_stream_rsa_decrypt_parallel,
     bench_stream_kyber_encrypt_parallel,
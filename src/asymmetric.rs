@@ -1,4 +1,11 @@
+pub mod compact;
+pub mod dynamic;
 pub mod engines;
+pub mod identify;
+pub mod key_kind;
+#[cfg(feature = "aes-gcm-feature")]
+pub mod multi_recipient;
+pub mod prepared;
 pub mod primitives;
 pub mod systems;
 pub mod traits;
@@ -0,0 +1,191 @@
+//! 适合二维码与人工核对的紧凑公钥文本
+//!
+//! 格式为 `前缀:BASE32(公钥字节 || CRC32)`：前缀标识算法，Base32 使用 RFC 4648 大写字母表且不带填充，
+//! 整串只包含二维码字母数字模式支持的字符。CRC32 覆盖前缀与公钥字节，
+//! 用于发现扫描或抄写错误；它不是签名，配对时仍需通过其他渠道确认公钥来源。
+
+use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+use crate::common::errors::Error;
+use crate::common::utils::crc32;
+#[cfg(feature = "post-quantum")]
+use crate::asymmetric::systems::post_quantum::kyber::{KyberCryptoSystem, KyberPublicKeyWrapper};
+#[cfg(feature = "traditional")]
+use crate::asymmetric::systems::traditional::rsa::{RsaCryptoSystem, RsaPublicKeyWrapper};
+
+/// RFC 4648 Base32 字母表
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// 校验和长度
+const CHECKSUM_SIZE: usize = 4;
+
+/// 支持紧凑文本形式导入导出公钥的系统
+pub trait CompactPublicKey: AsymmetricCryptographicSystem {
+    /// 标识算法的短前缀
+    const COMPACT_PREFIX: &'static str;
+
+    /// 公钥的二进制编码
+    fn public_key_bytes(public_key: &Self::PublicKey) -> Vec<u8>;
+
+    /// 从二进制编码恢复并校验公钥
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error>;
+
+    /// 导出为紧凑文本
+    fn export_compact(public_key: &Self::PublicKey) -> String {
+        let mut payload = Self::public_key_bytes(public_key);
+        let checksum = compact_checksum(Self::COMPACT_PREFIX, &payload);
+        payload.extend_from_slice(&checksum.to_be_bytes());
+        format!("{}:{}", Self::COMPACT_PREFIX, base32_encode(&payload))
+    }
+
+    /// 从紧凑文本导入公钥，前缀或校验和不符时返回 `Error::Format`
+    ///
+    /// 忽略大小写与空白，便于导入人工输入的文本。
+    fn import_compact(text: &str) -> Result<Self::PublicKey, Error> {
+        let normalized: String = text.chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let (prefix, encoded) = normalized.split_once(':')
+            .ok_or_else(|| Error::Format("紧凑公钥缺少算法前缀".to_string()))?;
+        if prefix != Self::COMPACT_PREFIX {
+            return Err(Error::Format(format!(
+                "紧凑公钥算法前缀不匹配: 期望 {}, 实际 {}", Self::COMPACT_PREFIX, prefix
+            )));
+        }
+
+        let payload = base32_decode(encoded)?;
+        if payload.len() <= CHECKSUM_SIZE {
+            return Err(Error::Format("紧凑公钥过短".to_string()));
+        }
+        let (key_bytes, checksum) = payload.split_at(payload.len() - CHECKSUM_SIZE);
+        if checksum != compact_checksum(prefix, key_bytes).to_be_bytes() {
+            return Err(Error::Format("紧凑公钥校验和错误，请检查是否扫描或输入有误".to_string()));
+        }
+        Self::public_key_from_bytes(key_bytes)
+    }
+}
+
+#[cfg(feature = "traditional")]
+impl CompactPublicKey for RsaCryptoSystem {
+    const COMPACT_PREFIX: &'static str = "RSA";
+
+    fn public_key_bytes(public_key: &RsaPublicKeyWrapper) -> Vec<u8> {
+        public_key.0.clone()
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<RsaPublicKeyWrapper, Error> {
+        use rsa::pkcs8::DecodePublicKey;
+        rsa::RsaPublicKey::from_public_key_der(bytes)
+            .map_err(|e| Error::Key(format!("导入RSA公钥失败: {}", e)))?;
+        Ok(RsaPublicKeyWrapper(bytes.to_vec()))
+    }
+}
+
+#[cfg(feature = "post-quantum")]
+impl CompactPublicKey for KyberCryptoSystem {
+    const COMPACT_PREFIX: &'static str = "KYB";
+
+    fn public_key_bytes(public_key: &KyberPublicKeyWrapper) -> Vec<u8> {
+        public_key.0.clone()
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<KyberPublicKeyWrapper, Error> {
+        KyberCryptoSystem::public_key_from_vec(bytes.to_vec())
+    }
+}
+
+fn compact_checksum(prefix: &str, key_bytes: &[u8]) -> u32 {
+    let mut data = Vec::with_capacity(prefix.len() + 1 + key_bytes.len());
+    data.extend_from_slice(prefix.as_bytes());
+    data.push(b':');
+    data.extend_from_slice(key_bytes);
+    crc32(&data)
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+    }
+    output
+}
+
+fn base32_decode(text: &str) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)
+            .ok_or_else(|| Error::Format(format!("紧凑公钥包含无效字符: {:?}", c as char)))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    // 剩余位只能是编码时补齐的零
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(Error::Format("紧凑公钥的 Base32 长度无效".to_string()));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32_rfc4648_vectors() {
+        for (plain, encoded) in [("", ""), ("f", "MY"), ("fo", "MZXQ"), ("foo", "MZXW6"), ("foob", "MZXW6YQ"), ("fooba", "MZXW6YTB"), ("foobar", "MZXW6YTBOI")] {
+            assert_eq!(base32_encode(plain.as_bytes()), encoded);
+            assert_eq!(base32_decode(encoded).unwrap(), plain.as_bytes());
+        }
+        assert!(base32_decode("MZXW6Y1").is_err());
+        assert!(base32_decode("M").is_err());
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_kyber_compact_roundtrip_and_checksum() {
+        use crate::common::utils::CryptoConfig;
+
+        let (pk, _) = KyberCryptoSystem::generate_keypair(&CryptoConfig::default()).unwrap();
+        let compact = KyberCryptoSystem::export_compact(&pk);
+        assert!(compact.starts_with("KYB:"));
+        assert!(compact.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b':'));
+        assert_eq!(KyberCryptoSystem::import_compact(&compact).unwrap().0, pk.0);
+        assert_eq!(KyberCryptoSystem::import_compact(&compact.to_ascii_lowercase()).unwrap().0, pk.0);
+
+        // 修改任意一个字符都会被校验和发现
+        let mut corrupted = compact.into_bytes();
+        let i = corrupted.len() / 2;
+        corrupted[i] = if corrupted[i] == b'A' { b'B' } else { b'A' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        assert!(matches!(KyberCryptoSystem::import_compact(&corrupted), Err(Error::Format(_))));
+    }
+
+    #[cfg(feature = "traditional")]
+    #[test]
+    fn test_rsa_compact_roundtrip_and_prefix_check() {
+        use crate::common::utils::CryptoConfig;
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, _) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let compact = RsaCryptoSystem::export_compact(&pk);
+        assert!(compact.starts_with("RSA:"));
+        assert_eq!(RsaCryptoSystem::import_compact(&compact).unwrap().0, pk.0);
+
+        let truncated = &compact[..compact.len() - 8];
+        assert!(RsaCryptoSystem::import_compact(truncated).is_err());
+        assert!(RsaCryptoSystem::import_compact(&compact.replacen("RSA", "KYB", 1)).is_err());
+        assert!(RsaCryptoSystem::import_compact(compact.trim_start_matches("RSA:")).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! 对象安全的非对称加密外观
+//!
+//! `AsymmetricCryptographicSystem` 依赖关联类型，无法作为 trait object 使用。
+//! `DynAsymmetricSystem` 将密钥统一为导出后的字符串、密文统一为 `String`，
+//! 从而可以把不同算法放入 `Vec<Box<dyn DynAsymmetricSystem>>` 并在运行时选择。
+
+use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+use crate::common::errors::Error;
+use crate::common::utils::CryptoConfig;
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+use crate::asymmetric::systems::hybrid::rsa_kyber::RsaKyberCryptoSystem;
+#[cfg(feature = "post-quantum")]
+use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+#[cfg(feature = "traditional")]
+use crate::asymmetric::systems::traditional::rsa::RsaCryptoSystem;
+
+/// 对象安全的非对称加密系统接口
+///
+/// 公钥与私钥使用各系统 `export_*_key` 的标准格式字符串传递。
+pub trait DynAsymmetricSystem: Send + Sync {
+    /// 算法名称
+    fn algorithm_name(&self) -> &'static str;
+
+    /// 生成密钥对，返回导出后的 (公钥, 私钥)
+    fn generate_keypair_dyn(&self, config: &CryptoConfig) -> Result<(String, String), Error>;
+
+    /// 使用导出格式的公钥加密数据
+    fn encrypt_dyn(&self, public_key: &str, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<String, Error>;
+
+    /// 使用导出格式的私钥解密数据
+    fn decrypt_dyn(&self, private_key: &str, ciphertext: &str, additional_data: Option<&[u8]>) -> Result<Vec<u8>, Error>;
+}
+
+macro_rules! impl_dyn_asymmetric_system {
+    ($system:ty, $name:expr) => {
+        impl DynAsymmetricSystem for $system {
+            fn algorithm_name(&self) -> &'static str {
+                $name
+            }
+
+            fn generate_keypair_dyn(&self, config: &CryptoConfig) -> Result<(String, String), Error> {
+                let (public_key, private_key) = <$system as AsymmetricCryptographicSystem>::generate_keypair(config)?;
+                Ok((
+                    <$system as AsymmetricCryptographicSystem>::export_public_key(&public_key)?,
+                    <$system as AsymmetricCryptographicSystem>::export_private_key(&private_key)?,
+                ))
+            }
+
+            fn encrypt_dyn(&self, public_key: &str, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<String, Error> {
+                let public_key = <$system as AsymmetricCryptographicSystem>::import_public_key(public_key)?;
+                let ciphertext = <$system as AsymmetricCryptographicSystem>::encrypt(&public_key, plaintext, additional_data)?;
+                Ok(ciphertext.to_string())
+            }
+
+            fn decrypt_dyn(&self, private_key: &str, ciphertext: &str, additional_data: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+                let private_key = <$system as AsymmetricCryptographicSystem>::import_private_key(private_key)?;
+                <$system as AsymmetricCryptographicSystem>::decrypt(&private_key, ciphertext, additional_data)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "traditional")]
+impl_dyn_asymmetric_system!(RsaCryptoSystem, "RSA");
+#[cfg(feature = "post-quantum")]
+impl_dyn_asymmetric_system!(KyberCryptoSystem, "Kyber");
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+impl_dyn_asymmetric_system!(RsaKyberCryptoSystem, "RSA-Kyber");
+
+#[cfg(all(test, feature = "traditional", feature = "post-quantum"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_systems_in_vec() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let systems: Vec<Box<dyn DynAsymmetricSystem>> = vec![
+            Box::new(RsaCryptoSystem),
+            Box::new(KyberCryptoSystem),
+            Box::new(RsaKyberCryptoSystem),
+        ];
+        let message = b"runtime algorithm selection";
+
+        for system in &systems {
+            let (public_key, private_key) = system.generate_keypair_dyn(&config).unwrap();
+            let ciphertext = system.encrypt_dyn(&public_key, message, None).unwrap();
+            let decrypted = system.decrypt_dyn(&private_key, &ciphertext, None).unwrap();
+            assert_eq!(decrypted, message, "算法 {} 往返失败", system.algorithm_name());
+        }
+    }
+
+    #[test]
+    fn test_dyn_select_by_name() {
+        let config = CryptoConfig::default();
+        let systems: Vec<Box<dyn DynAsymmetricSystem>> = vec![
+            Box::new(KyberCryptoSystem),
+            Box::new(RsaKyberCryptoSystem),
+        ];
+        let kyber = systems.iter().find(|s| s.algorithm_name() == "Kyber").unwrap();
+
+        let (public_key, private_key) = kyber.generate_keypair_dyn(&config).unwrap();
+        let ciphertext = kyber.encrypt_dyn(&public_key, b"aad bound", Some(b"aad")).unwrap();
+        assert!(kyber.decrypt_dyn(&private_key, &ciphertext, Some(b"other")).is_err());
+        assert_eq!(kyber.decrypt_dyn(&private_key, &ciphertext, Some(b"aad")).unwrap(), b"aad bound");
+    }
+
+    #[test]
+    fn test_dyn_invalid_key() {
+        let system: Box<dyn DynAsymmetricSystem> = Box::new(KyberCryptoSystem);
+        assert!(system.encrypt_dyn("not a key", b"data", None).is_err());
+    }
+}
@@ -3,11 +3,17 @@
 use arc_swap::ArcSwapOption;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
+use std::time::SystemTime;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::common::utils::CryptoConfig;
+use crate::common::utils::{attach_fingerprint, attach_headers, attach_key_id, attach_key_version, encode_headers, encode_not_before, key_fingerprint, CiphertextEnvelope, CryptoConfig, Profile};
+use crate::common::audit::{algorithm_name, AuditEvent, AuditSink};
+use crate::common::clock::{Clock, SystemClock};
+use crate::common::metrics::Direction;
+use crate::common::inspect::{config_digest_hex, ALGORITHM_HEADER, CONFIG_DIGEST_HEADER, RESERVED_HEADERS};
 use crate::common::config::ConfigManager;
 use crate::common::errors::Error;
 use crate::rotation::{KeyMetadata, KeyStorage, RotationPolicy};
@@ -16,7 +22,10 @@ use crate::common::traits::AuthenticatedCryptoSystem;
 use crate::common::streaming::StreamingResult;
 use crate::common::streaming::StreamingConfig;
 use tokio::io::{AsyncRead, AsyncWrite};
-use crate::asymmetric::traits::{AsyncStreamingSystem, AsymmetricCryptographicSystem};
+use crate::asymmetric::traits::{AsyncStreamingSystem, AsymmetricCryptographicSystem, KeyProvider};
+
+/// 共享的加密系统 `C` 公私钥对及其元数据
+type SharedKeyEntry<C> = Arc<(<C as AsymmetricCryptographicSystem>::PublicKey, <C as AsymmetricCryptographicSystem>::PrivateKey, KeyMetadata)>;
 
 /// 并发版 QSeal 引擎，支持多线程同时调用
 pub struct AsymmetricQSealEngineAsync<C: AsymmetricCryptographicSystem + AsyncStreamingSystem + Send + Sync + 'static>
@@ -31,7 +40,15 @@ where
     /// 主密钥原子存储
     primary: ArcSwapOption<(C::PublicKey, C::PrivateKey, KeyMetadata)>,
     /// 次要密钥并发存储
-    secondary: DashMap<String, (C::PublicKey, C::PrivateKey, KeyMetadata)>,
+    secondary: DashMap<String, SharedKeyEntry<C>>,
+    /// 调用方提供的密钥来源，未设置时在主密钥与次要密钥中查找
+    key_provider: Option<Arc<dyn KeyProvider<C> + Send + Sync>>,
+    /// 校验密文生效时间使用的时钟
+    clock: Arc<dyn Clock>,
+    /// 拒绝解密的已吊销公钥指纹
+    revoked_fingerprints: HashSet<String>,
+    /// 接收审计事件的目标
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 /// 密钥序列化结构，用于读取存储的 key_data
@@ -49,6 +66,11 @@ where
 {
     /// 创建并初始化并发版引擎
     pub fn new(config: Arc<ConfigManager>, key_prefix: &str) -> Result<Self, Error> {
+        // 异步引擎不安装确定性RNG，设置了种子时拒绝创建，而不是悄然产生随机输出
+        #[cfg(feature = "test-determinism")]
+        if config.get_crypto_config().deterministic_seed.is_some() {
+            return Err(Error::Operation("异步引擎不支持 deterministic_seed，请使用同步引擎".to_string()));
+        }
         let storage_config = config.get_storage_config();
         let key_storage = Arc::new(KeyFileStorage::new(&storage_config.key_storage_dir)?);
         let rotation_policy = config.get_rotation_policy();
@@ -60,6 +82,10 @@ where
             key_prefix: prefix.clone(),
             primary: ArcSwapOption::new(None),
             secondary: DashMap::new(),
+            key_provider: None,
+            clock: Arc::new(SystemClock),
+            revoked_fingerprints: HashSet::new(),
+            audit_sink: None,
         };
         engine.initialize(&config.get_crypto_config())?;
         Ok(engine)
@@ -83,7 +109,7 @@ where
                     }
                     crate::common::traits::KeyStatus::Rotating => {
                         let (pubk, privk) = Self::deserialize(&data)?;
-                        self.secondary.insert(name, (pubk, privk, meta));
+                        self.secondary.insert(name, Arc::new((pubk, privk, meta)));
                     }
                     crate::common::traits::KeyStatus::Expired => {
                         let _ = self.key_storage.delete_key(&name);
@@ -160,7 +186,7 @@ where
             let key_name = format!("{}-{}", self.key_prefix, om.id);
             let data = Self::serialize(&_opk, &_osk)?;
             self.key_storage.save_key(&key_name, &om, &data)?;
-            self.secondary.insert(key_name.clone(), (_opk, _osk, om));
+            self.secondary.insert(key_name.clone(), Arc::new((_opk, _osk, om)));
         }
         let metadata = KeyMetadata { id: id.clone(), created_at: now.clone(), expires_at: Some(exp), usage_count: 0, status: crate::common::traits::KeyStatus::Active, version, algorithm: format!("{}", std::any::type_name::<C>()) };
         let key_name = format!("{}-{}", self.key_prefix, id);
@@ -185,28 +211,300 @@ where
 
     /// 加密
     pub fn encrypt(&self, plaintext: &[u8]) -> Result<String, Error> {
+        self.encrypt_with_key_id(plaintext, None)
+    }
+
+    /// 加密，并在密文信封中附加调用方自定义的密钥ID
+    ///
+    /// 密钥ID可通过 [`CiphertextEnvelope::key_id`] 在不解密的情况下读取，供下游路由使用。
+    pub fn encrypt_with_key_id(&self, plaintext: &[u8], key_id: Option<String>) -> Result<String, Error> {
+        let result = self.encrypt_inner(plaintext, key_id.as_deref(), &BTreeMap::new(), None, false);
+        self.record(Direction::Encrypt, self.primary_fingerprint(), result, |_| plaintext.len() as u64)
+    }
+
+    /// 加密，并在密文信封中以明文保存 `headers`（如内容类型、时间戳）
+    ///
+    /// 头部可通过 [`CiphertextEnvelope::headers`] 在不解密的情况下读取，
+    /// 同时作为附加认证数据参与加密，任何修改都会导致解密失败。
+    /// 底层系统不支持附加数据（如RSA）时返回错误；[`RESERVED_HEADERS`] 中的字段由引擎保留。
+    pub fn encrypt_with_headers(&self, plaintext: &[u8], headers: &BTreeMap<String, String>) -> Result<String, Error> {
+        let result = match headers.keys().find(|key| RESERVED_HEADERS.contains(&key.as_str())) {
+            Some(key) => Err(Error::Operation(format!("头部字段 {} 由引擎保留", key))),
+            None => self.encrypt_inner(plaintext, None, headers, None, false),
+        };
+        self.record(Direction::Encrypt, self.primary_fingerprint(), result, |_| plaintext.len() as u64)
+    }
+
+    /// 加密，并在密文信封的认证头部中记录当前配置的摘要，与同步引擎的 `encrypt_with_config_digest` 相同：
+    /// 主密钥的实际参数与当前配置不一致时返回 `Error::Key`
+    pub fn encrypt_with_config_digest(&self, plaintext: &[u8]) -> Result<String, Error> {
+        let digest = config_digest_hex(&self.config.get_crypto_config());
+        let result = self.encrypt_inner(plaintext, None, &BTreeMap::from([(CONFIG_DIGEST_HEADER.to_string(), digest)]), None, true);
+        self.record(Direction::Encrypt, self.primary_fingerprint(), result, |_| plaintext.len() as u64)
+    }
+
+    /// 解密由 `expected_profile` 的预设配置加密的密文，与同步引擎的 `decrypt_requiring_profile` 相同
+    pub fn decrypt_requiring_profile(&self, ciphertext: &str, expected_profile: Profile) -> Result<Vec<u8>, Error> {
+        self.decrypt_requiring_config(ciphertext, &CryptoConfig::preset(expected_profile))
+    }
+
+    /// 解密密文，并要求其认证头部记录的配置摘要与 `expected` 一致，与同步引擎的 `decrypt_requiring_config` 相同
+    pub fn decrypt_requiring_config(&self, ciphertext: &str, expected: &CryptoConfig) -> Result<Vec<u8>, Error> {
+        let result = CiphertextEnvelope::parse(ciphertext).and_then(|envelope| match envelope.headers().get(CONFIG_DIGEST_HEADER) {
+            Some(digest) if *digest == config_digest_hex(expected) => self.decrypt_inner(ciphertext),
+            Some(_) => Err(Error::Operation("密文不是由预期的配置加密的".to_string())),
+            None => Err(Error::Operation("密文未记录配置摘要".to_string())),
+        });
+        self.record_decrypt(result)
+    }
+
+    /// 加密，并在密文信封中写入生效时间，早于该时间的解密请求返回 `Error::NotYetValid`
+    ///
+    /// 与同步引擎的 `encrypt_with_not_before` 相同，这只是软性限制而非密码学时间锁；
+    /// 底层系统不支持附加数据（如RSA）时返回错误。
+    pub fn encrypt_with_not_before(&self, plaintext: &[u8], not_before: SystemTime) -> Result<String, Error> {
+        let result = if C::SUPPORTS_ADDITIONAL_DATA {
+            encode_not_before(not_before)
+                .and_then(|encoded| self.encrypt_inner(plaintext, None, &BTreeMap::new(), Some(&encoded), false))
+        } else {
+            Err(Error::Operation("当前加密系统不支持附加认证数据，无法保护生效时间".to_string()))
+        };
+        self.record(Direction::Encrypt, self.primary_fingerprint(), result, |_| plaintext.len() as u64)
+    }
+
+    /// 替换校验密文生效时间所用的时钟，默认读取系统时间
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// 设置或移除接收审计事件的目标，事件内容与同步引擎的 `set_audit_sink` 相同
+    ///
+    /// 加密与流式解密事件记录主公钥的指纹，块解密事件记录实际完成解密的公钥指纹，解密失败时不记录指纹。
+    pub fn set_audit_sink(&mut self, sink: Option<Arc<dyn AuditSink>>) {
+        self.audit_sink = sink;
+    }
+
+    /// 设置了审计目标时记录事件；写入失败时原本成功的操作改为返回该错误
+    fn record<T>(&self, direction: Direction, fingerprint: Option<String>, result: Result<T, Error>, bytes: impl Fn(&T) -> u64) -> Result<T, Error> {
+        let Some(sink) = &self.audit_sink else {
+            return result;
+        };
+        let event = AuditEvent::new(direction, algorithm_name::<C>(), fingerprint, result.as_ref().map(&bytes), self.clock.now());
+        let recorded = sink.record(&event);
+        result.and_then(|value| recorded.map(|_| value))
+    }
+
+    /// 记录一次块解密：成功时事件携带实际解密所用公钥的指纹
+    fn record_decrypt(&self, result: Result<(Vec<u8>, Option<String>), Error>) -> Result<Vec<u8>, Error> {
+        let (result, fingerprint) = match result {
+            Ok((plaintext, fingerprint)) => (Ok(plaintext), fingerprint),
+            Err(e) => (Err(e), None),
+        };
+        self.record(Direction::Decrypt, fingerprint, result, |plaintext| plaintext.len() as u64)
+    }
+
+    /// 审计事件中记录的公钥指纹；未设置审计目标时不计算
+    fn audited_fingerprint(&self, public_key: &C::PublicKey) -> Option<String> {
+        self.audit_sink.as_ref()?;
+        C::export_public_key(public_key).ok().map(|exported| key_fingerprint(&exported))
+    }
+
+    fn primary_fingerprint(&self) -> Option<String> {
+        self.primary.load_full().and_then(|arc| self.audited_fingerprint(&arc.0))
+    }
+
+    /// 替换解密带指纹或密钥版本的密文时使用的密钥来源，与同步引擎的 `set_key_provider` 相同
+    pub fn set_key_provider(&mut self, provider: Arc<dyn KeyProvider<C> + Send + Sync>) {
+        self.key_provider = Some(provider);
+    }
+
+    /// 设置已吊销的公钥指纹，之后解密嵌入了其中任一指纹的密文时返回 `Error::KeyRevoked`
+    ///
+    /// 与同步引擎的 `set_revoked_fingerprints` 相同：传入空集合即关闭检查，吊销的密钥同时被排除在
+    /// 逐个尝试的候选之外，去掉指纹前缀的密文也无法借吊销的私钥解密。
+    pub fn set_revoked_fingerprints(&mut self, revoked: HashSet<String>) {
+        self.revoked_fingerprints = revoked;
+    }
+
+    /// 公钥已被吊销时返回其指纹；吊销集合为空时不计算指纹。无法导出的公钥按已吊销处理
+    fn revoked_fingerprint(&self, public_key: &C::PublicKey) -> Option<String> {
+        if self.revoked_fingerprints.is_empty() {
+            return None;
+        }
+        match C::export_public_key(public_key) {
+            Ok(exported) => Some(key_fingerprint(&exported)).filter(|fp| self.revoked_fingerprints.contains(fp)),
+            Err(_) => Some(String::new()),
+        }
+    }
+
+    /// `check_key_params` 为真时要求主密钥的实际参数与当前配置一致
+    fn encrypt_inner(
+        &self,
+        plaintext: &[u8],
+        key_id: Option<&str>,
+        headers: &BTreeMap<String, String>,
+        not_before: Option<&str>,
+        check_key_params: bool,
+    ) -> Result<String, Error> {
+        self.config.get_crypto_config().check_oneshot_size(plaintext.len())?;
         if self.needs_rotation() {
             self.start_rotation(&self.config.get_crypto_config())?;
         }
         let arc = self.primary.load_full().ok_or_else(|| Error::Key("没有可用主密钥".to_string()))?;
-        let (pk, _, _) = &*arc;
+        let (pk, _, metadata) = &*arc;
+        let cfg = self.config.get_crypto_config();
+        if check_key_params && !C::public_key_matches_config(pk, &cfg)? {
+            return Err(Error::Key("主密钥参数与当前配置不一致，请先轮换密钥".to_string()));
+        }
+        let fields = Self::authenticated_fields(&cfg, headers, not_before)?;
         self.increment_usage_count()?;
-        let ct = C::encrypt(pk, plaintext, None)?;
-        Ok(ct.to_string())
+        let ct = C::encrypt_with_config(pk, plaintext, fields.as_deref().map(str::as_bytes), &cfg)?;
+        Self::finish_output(&cfg, pk, metadata.version, key_id, fields.as_deref(), cfg.base64_variant.encode(ct.as_ref()))
+    }
+
+    /// 组装信封中受认证字段的文本，与同步引擎相同：头部在前（按配置加入算法标识），已编码的生效时间在后
+    fn authenticated_fields(cfg: &CryptoConfig, headers: &BTreeMap<String, String>, not_before: Option<&str>) -> Result<Option<String>, Error> {
+        if !C::SUPPORTS_ADDITIONAL_DATA {
+            if !headers.is_empty() {
+                return Err(Error::Operation("当前加密系统不支持附加认证数据，无法保护明文头部".to_string()));
+            }
+            return Ok(not_before.map(str::to_string));
+        }
+        let mut headers = headers.clone();
+        if cfg.embed_algorithm_id && let Some(algorithm) = C::ALGORITHM_ID {
+            headers.insert(ALGORITHM_HEADER.to_string(), algorithm.to_string());
+        }
+        let encoded = if headers.is_empty() { None } else { Some(encode_headers(&headers)?) };
+        let fields: Vec<String> = encoded.into_iter().chain(not_before.map(str::to_string)).collect();
+        Ok((!fields.is_empty()).then(|| fields.join(".")))
+    }
+
+    /// 信封头部对应的附加认证数据；底层系统无法认证头部时拒绝带头部的密文
+    fn associated_data<'a>(envelope: &CiphertextEnvelope<'a>) -> Result<Option<&'a [u8]>, Error> {
+        match envelope.associated_data() {
+            Some(_) if !C::SUPPORTS_ADDITIONAL_DATA => {
+                Err(Error::Operation("当前加密系统不支持附加认证数据，无法校验明文头部".to_string()))
+            }
+            aad => Ok(aad),
+        }
+    }
+
+    /// 按配置在Base64密文前嵌入加密公钥的指纹与密钥版本，并附加可选的头部与自定义密钥ID
+    fn finish_output(cfg: &CryptoConfig, pk: &C::PublicKey, key_version: u32, key_id: Option<&str>, headers: Option<&str>, encoded: String) -> Result<String, Error> {
+        let output = if cfg.embed_key_fingerprint {
+            let fingerprint = key_fingerprint(&C::export_public_key(pk)?);
+            attach_fingerprint(&fingerprint, &encoded)
+        } else {
+            encoded
+        };
+        let output = if cfg.embed_key_version {
+            attach_key_version(key_version, &output)
+        } else {
+            output
+        };
+        let output = match headers {
+            Some(headers) => attach_headers(headers, &output),
+            None => output,
+        };
+        Ok(match key_id {
+            Some(key_id) => attach_key_id(key_id, &output),
+            None => output,
+        })
+    }
+
+    /// 按元数据版本号与公钥指纹在主密钥与次要密钥中查找，语义与同步引擎的密钥轮换管理器相同
+    ///
+    /// 指纹不匹配时返回 `Error::KeyMismatch`，其中 `provided` 为第一个候选密钥（未指定版本时即主密钥）的指纹。
+    fn find_key(&self, fingerprint: Option<&str>, key_version: Option<u32>) -> Result<SharedKeyEntry<C>, Error> {
+        let candidates = self.primary.load_full().into_iter()
+            .chain(self.secondary.iter().map(|entry| entry.value().clone()))
+            .filter(|arc| key_version.is_none_or(|version| arc.2.version == version));
+        let mut primary_fingerprint = None;
+        for arc in candidates {
+            let Some(expected) = fingerprint else {
+                return Ok(arc);
+            };
+            let actual = key_fingerprint(&C::export_public_key(&arc.0)?);
+            if actual == expected {
+                return Ok(arc);
+            }
+            primary_fingerprint.get_or_insert(actual);
+        }
+        match (fingerprint, key_version) {
+            (Some(expected), _) => Err(Error::KeyMismatch {
+                expected: expected.to_string(),
+                provided: primary_fingerprint.unwrap_or_default(),
+            }),
+            (None, Some(version)) => Err(Error::Key(format!("没有版本为 {} 的密钥", version))),
+            (None, None) => Err(Error::Key("没有可用的密钥".to_string())),
+        }
+    }
+
+    /// 密文信封带有指纹或密钥版本时，用对应的密钥对执行 `operation`；二者都没有时返回 `None`
+    ///
+    /// 密钥来自 [`set_key_provider`](Self::set_key_provider) 设置的来源或引擎保存的密钥，均以借用传入，不复制私钥。
+    /// 取得的密钥已被吊销时返回 `Error::KeyRevoked`。
+    fn with_routed_key<T>(
+        &self,
+        envelope: &CiphertextEnvelope,
+        operation: impl FnOnce(&C::PublicKey, &C::PrivateKey) -> Result<T, Error>,
+    ) -> Result<Option<T>, Error> {
+        let (fingerprint, key_version) = (envelope.fingerprint(), envelope.key_version());
+        if fingerprint.is_none() && key_version.is_none() {
+            return Ok(None);
+        }
+        let stored;
+        let (pk, sk) = match &self.key_provider {
+            Some(provider) => provider.key_pair(fingerprint, key_version)?,
+            None => {
+                stored = self.find_key(fingerprint, key_version)?;
+                (&stored.0, &stored.1)
+            }
+        };
+        if let Some(revoked) = self.revoked_fingerprint(pk) {
+            return Err(Error::KeyRevoked(revoked));
+        }
+        operation(pk, sk).map(Some)
     }
 
     /// 解密
+    ///
+    /// 若密文嵌入了密钥指纹或密钥版本，则直接使用匹配的密钥解密（见 [`KeyProvider`]），无匹配时立即返回错误。
+    /// 自定义密钥ID仅作路由用途，解密时会被忽略。
     pub fn decrypt(&self, ciphertext: &str) -> Result<Vec<u8>, Error> {
-        if let Some(arc) = self.primary.load_full() {
-            let (_, sk, _) = &*arc;
-            if let Ok(pt) = C::decrypt(sk, ciphertext, None) {
-                return Ok(pt);
+        let result = self.decrypt_inner(ciphertext);
+        self.record_decrypt(result)
+    }
+
+    /// 成功时同时返回实际解密所用公钥的指纹（仅在设置了审计目标时计算）
+    fn decrypt_inner(&self, ciphertext: &str) -> Result<(Vec<u8>, Option<String>), Error> {
+        let envelope = CiphertextEnvelope::parse(ciphertext)?;
+        envelope.check_not_before(self.clock.now())?;
+        envelope.check_revoked(&self.revoked_fingerprints)?;
+        let ciphertext = envelope.body();
+        let aad = Self::associated_data(&envelope)?;
+        let cfg = self.config.get_crypto_config();
+        let routed = self.with_routed_key(&envelope, |pk, sk| {
+            let plaintext = C::decrypt_with_config(sk, ciphertext, aad, &cfg)?;
+            Ok((plaintext, self.audited_fingerprint(pk)))
+        })?;
+        if let Some(decrypted) = routed {
+            return Ok(decrypted);
+        }
+        // 逐个尝试时跳过已吊销的密钥
+        if let Some(arc) = self.primary.load_full().filter(|arc| self.revoked_fingerprint(&arc.0).is_none()) {
+            let (pk, sk, _) = &*arc;
+            if let Ok(pt) = C::decrypt_with_config(sk, ciphertext, aad, &cfg) {
+                return Ok((pt, self.audited_fingerprint(pk)));
             }
         }
         for entry in self.secondary.iter() {
-            let (_pk, sk, _) = entry.value();
-            if let Ok(pt) = C::decrypt(sk, ciphertext, None) {
-                return Ok(pt);
+            let (pk, sk, _) = &**entry.value();
+            if self.revoked_fingerprint(pk).is_some() {
+                continue;
+            }
+            if let Ok(pt) = C::decrypt_with_config(sk, ciphertext, aad, &cfg) {
+                return Ok((pt, self.audited_fingerprint(pk)));
             }
         }
         Err(Error::Operation("解密失败".to_string()))
@@ -216,37 +514,79 @@ where
     pub fn encrypt_authenticated(&self, plaintext: &[u8]) -> Result<String, Error>
     where C: AuthenticatedCryptoSystem + Send + Sync + 'static
     {
+        self.encrypt_authenticated_with_key_id(plaintext, None)
+    }
+
+    /// 带认证加密，并在密文信封中附加调用方自定义的密钥ID
+    pub fn encrypt_authenticated_with_key_id(&self, plaintext: &[u8], key_id: Option<String>) -> Result<String, Error>
+    where C: AuthenticatedCryptoSystem + Send + Sync + 'static
+    {
+        let result = self.encrypt_authenticated_inner(plaintext, key_id.as_deref());
+        self.record(Direction::Encrypt, self.primary_fingerprint(), result, |_| plaintext.len() as u64)
+    }
+
+    fn encrypt_authenticated_inner(&self, plaintext: &[u8], key_id: Option<&str>) -> Result<String, Error>
+    where C: AuthenticatedCryptoSystem + Send + Sync + 'static
+    {
+        self.config.get_crypto_config().check_oneshot_size(plaintext.len())?;
         if self.needs_rotation() {
             self.start_rotation(&self.config.get_crypto_config())?;
         }
         let arc = self.primary.load_full().ok_or_else(|| Error::Key("没有可用主密钥".to_string()))?;
-        let (pk, sk, _) = &*arc;
-        self.increment_usage_count()?;
+        let (pk, sk, metadata) = &*arc;
         let cfg = self.config.get_crypto_config();
+        let fields = Self::authenticated_fields(&cfg, &BTreeMap::new(), None)?;
+        self.increment_usage_count()?;
         let signer = if cfg.use_authenticated_encryption { Some(sk) } else { None };
-        let auth_ct = C::encrypt_authenticated(pk, plaintext, None, signer)
-            .map_err(Into::into)?;
-        Ok(auth_ct.to_string())
+        let auth_ct = C::encrypt_authenticated(pk, plaintext, fields.as_deref().map(str::as_bytes), signer)?;
+        Self::finish_output(&cfg, pk, metadata.version, key_id, fields.as_deref(), cfg.base64_variant.encode(auth_ct.as_ref()))
     }
 
     /// 带认证解密
+    ///
+    /// 密文嵌入密钥指纹或密钥版本时的处理方式与 [`decrypt`](Self::decrypt) 相同。
     pub fn decrypt_authenticated(&self, ciphertext: &str) -> Result<Vec<u8>, Error>
     where C: AuthenticatedCryptoSystem + Send + Sync + 'static
+    {
+        let result = self.decrypt_authenticated_inner(ciphertext);
+        self.record_decrypt(result)
+    }
+
+    fn decrypt_authenticated_inner(&self, ciphertext: &str) -> Result<(Vec<u8>, Option<String>), Error>
+    where C: AuthenticatedCryptoSystem + Send + Sync + 'static
     {
         let cfg = self.config.get_crypto_config();
 
-        if let Some(arc) = self.primary.load_full() {
+        let envelope = CiphertextEnvelope::parse(ciphertext)?;
+        envelope.check_not_before(self.clock.now())?;
+        envelope.check_revoked(&self.revoked_fingerprints)?;
+        let ciphertext = envelope.body();
+        let aad = Self::associated_data(&envelope)?;
+        let routed = self.with_routed_key(&envelope, |pk, sk| {
+            let verifier = if cfg.auto_verify_signatures { Some(pk) } else { None };
+            let plaintext = C::decrypt_authenticated(sk, ciphertext, aad, verifier)?;
+            Ok((plaintext, self.audited_fingerprint(pk)))
+        })?;
+        if let Some(decrypted) = routed {
+            return Ok(decrypted);
+        }
+
+        // 逐个尝试时跳过已吊销的密钥
+        if let Some(arc) = self.primary.load_full().filter(|arc| self.revoked_fingerprint(&arc.0).is_none()) {
             let (pk, sk, _) = &*arc;
             let verifier = if cfg.auto_verify_signatures { Some(pk) } else { None };
-            if let Ok(pt) = C::decrypt_authenticated(sk, ciphertext, None, verifier) {
-                return Ok(pt);
+            if let Ok(pt) = C::decrypt_authenticated(sk, ciphertext, aad, verifier) {
+                return Ok((pt, self.audited_fingerprint(pk)));
             }
         }
 
         for entry in self.secondary.iter() {
-            let (_pk, sk, _) = entry.value();
-            if let Ok(pt) = C::decrypt_authenticated(sk, ciphertext, None, None) {
-                return Ok(pt);
+            let (pk, sk, _) = &**entry.value();
+            if self.revoked_fingerprint(pk).is_some() {
+                continue;
+            }
+            if let Ok(pt) = C::decrypt_authenticated(sk, ciphertext, aad, None) {
+                return Ok((pt, self.audited_fingerprint(pk)));
             }
         }
 
@@ -254,12 +594,25 @@ where
     }
 
     /// 异步流式加密
+    ///
+    /// 通过 [`StreamingConfig::with_cancellation`] 传入的令牌被取消后，在下一帧开始前返回 [`Error::Cancelled`]，
+    /// 已写出的都是完整的帧。
     pub async fn encrypt_stream<R, W>(
         &self,
         reader: R,
         writer: W,
         config: &StreamingConfig,
     ) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        C::PublicKey: Send + Sync,
+    {
+        let result = self.encrypt_stream_inner(reader, writer, config).await;
+        self.record(Direction::Encrypt, self.primary_fingerprint(), result, |r| r.bytes_processed)
+    }
+
+    async fn encrypt_stream_inner<R, W>(&self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error>
     where
         R: AsyncRead + Unpin + Send,
         W: AsyncWrite + Unpin + Send,
@@ -277,20 +630,33 @@ where
             .map_err(Into::into)
     }
 
-    /// 异步流式解密
+    /// 异步流式解密，取消语义与 [`encrypt_stream`](Self::encrypt_stream) 相同
     pub async fn decrypt_stream<R, W>(
         &self,
         reader: R,
         writer: W,
         config: &StreamingConfig,
     ) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        C::PrivateKey: Send + Sync,
+    {
+        let result = self.decrypt_stream_inner(reader, writer, config).await;
+        self.record(Direction::Decrypt, self.primary_fingerprint(), result, |r| r.bytes_processed)
+    }
+
+    async fn decrypt_stream_inner<R, W>(&self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error>
     where
         R: AsyncRead + Unpin + Send,
         W: AsyncWrite + Unpin + Send,
         C::PrivateKey: Send + Sync,
     {
         let arc = self.primary.load_full().ok_or_else(|| Error::Key("没有可用主密钥".to_string()))?;
-        let (_pk, sk, _) = &*arc;
+        let (pk, sk, _) = &*arc;
+        if let Some(revoked) = self.revoked_fingerprint(pk) {
+            return Err(Error::KeyRevoked(revoked));
+        }
         C::decrypt_stream_async(sk, reader, writer, config, None).await
     }
 }
@@ -303,6 +669,8 @@ where
     C::Error: Send,
 {
     /// 批量加密（并行）。需要公钥和私钥可安全在线程间共享。
+    ///
+    /// 每个输入的结果相互独立，按输入顺序返回；单个条目失败不会中止整个批次。
     pub fn encrypt_batch<T>(&self, inputs: &[T]) -> Vec<Result<String, Error>>
     where
         Self: Sync,
@@ -322,6 +690,8 @@ where
     C::Error: Send,
 {
     /// 批量加密（顺序执行）
+    ///
+    /// 每个输入的结果相互独立，按输入顺序返回；单个条目失败不会中止整个批次。
     pub fn encrypt_batch<T>(&self, inputs: &[T]) -> Vec<Result<String, Error>>
     where
         T: AsRef<[u8]>,
@@ -418,6 +788,8 @@ mod tests {
     use crate::rotation::RotationPolicy;
     use std::io::Cursor;
     use tempfile::tempdir;
+    use crate::common::streaming::CancellationToken;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use tokio::io::BufReader;
 
     type TestEngine = AsymmetricQSealEngineAsync<RsaKyberCryptoSystem>;
@@ -464,6 +836,98 @@ mod tests {
         assert_eq!(plaintext.as_ref(), decrypted.as_slice());
     }
 
+    #[tokio::test]
+    async fn test_async_engine_encrypt_batch_partial_failure() {
+        use crate::asymmetric::systems::traditional::rsa::RsaCryptoSystem;
+        use crate::common::utils::CryptoConfig;
+
+        let dir = tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig {
+                key_storage_dir: dir.path().to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            rotation: RotationPolicy::default(),
+            crypto: CryptoConfig { rsa_key_bits: 2048, ..Default::default() },
+        };
+        let engine = AsymmetricQSealEngineAsync::<RsaCryptoSystem>::new(
+            Arc::new(ConfigManager::from_config_file(config)),
+            "async_batch_partial",
+        )
+        .unwrap();
+
+        // RSA-2048 OAEP 单次最多加密 190 字节，索引 1 和 3 的条目超长
+        let inputs: Vec<Vec<u8>> = vec![
+            b"first".to_vec(),
+            vec![0u8; 4096],
+            b"third".to_vec(),
+            vec![1u8; 1024],
+            b"fifth".to_vec(),
+        ];
+        let results = engine.encrypt_batch(&inputs);
+        assert_eq!(results.len(), inputs.len());
+
+        let failed: Vec<usize> = results.iter().enumerate()
+            .filter_map(|(i, r)| r.is_err().then_some(i))
+            .collect();
+        assert_eq!(failed, vec![1, 3]);
+
+        for (input, result) in inputs.iter().zip(&results) {
+            if let Ok(ciphertext) = result {
+                assert_eq!(&engine.decrypt(ciphertext).unwrap(), input);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_embedded_fingerprint() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "async_fingerprint");
+        let mut cfg = engine.config.get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config.update_crypto_config(cfg).unwrap();
+
+        let ciphertext = engine.encrypt_authenticated(b"fingerprinted").unwrap();
+        assert!(ciphertext.contains('.'));
+        assert_eq!(engine.decrypt_authenticated(&ciphertext).unwrap(), b"fingerprinted");
+
+        let other_dir = tempdir().unwrap();
+        let other = setup_test_engine(other_dir.path(), "async_fingerprint");
+        assert!(matches!(other.decrypt_authenticated(&ciphertext), Err(Error::KeyMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_key_id_roundtrip() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "async_key_id");
+
+        let ciphertext = engine.encrypt_with_key_id(b"routed", Some("tenant-a".to_string())).unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&ciphertext).unwrap().key_id(), Some("tenant-a"));
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"routed");
+
+        let authenticated = engine.encrypt_authenticated_with_key_id(b"signed", Some("tenant-b".to_string())).unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&authenticated).unwrap().key_id(), Some("tenant-b"));
+        assert_eq!(engine.decrypt_authenticated(&authenticated).unwrap(), b"signed");
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_header_tampering_fails() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "async_headers");
+
+        let headers = BTreeMap::from([("content-type".to_string(), "application/json".to_string())]);
+        let ciphertext = engine.encrypt_with_headers(b"{}", &headers).unwrap();
+        let mut written = headers.clone();
+        written.insert(ALGORITHM_HEADER.to_string(), "RSA-Kyber".to_string());
+        assert_eq!(CiphertextEnvelope::parse(&ciphertext).unwrap().headers(), &written);
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"{}");
+
+        let (_, rest) = ciphertext.split_once('.').unwrap();
+        let forged = BTreeMap::from([("content-type".to_string(), "text/html".to_string())]);
+        assert!(engine.decrypt(&attach_headers(&encode_headers(&forged).unwrap(), rest)).is_err());
+        assert!(engine.decrypt(rest).is_err());
+    }
+
     #[tokio::test]
     async fn test_async_engine_decrypt_with_rotated_key() {
         let dir = tempdir().unwrap();
@@ -536,4 +1000,256 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_cancelled_decrypt_stream_stops_at_frame_boundary() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "async_streaming_cancel");
+        let original_data: Vec<u8> = (0..=255u8).cycle().take(32 * 5).collect();
+        let streaming_config = StreamingConfig::default().with_buffer_size(32);
+
+        let mut encrypted_dest = Vec::new();
+        engine
+            .encrypt_stream(BufReader::new(Cursor::new(original_data.clone())), &mut encrypted_dest, &streaming_config)
+            .await
+            .unwrap();
+
+        // 解密完 2 帧后请求取消
+        let token = CancellationToken::new();
+        let trigger = token.clone();
+        let frames_done = Arc::new(AtomicUsize::new(0));
+        let cancel_config = streaming_config
+            .with_cancellation(token)
+            .with_progress_callback(Arc::new(move |_, _| {
+                if frames_done.fetch_add(1, Ordering::SeqCst) + 1 == 2 {
+                    trigger.cancel();
+                }
+            }));
+
+        let mut decrypted_dest = Vec::new();
+        let result = engine
+            .decrypt_stream(BufReader::new(Cursor::new(encrypted_dest)), &mut decrypted_dest, &cancel_config)
+            .await;
+        assert!(matches!(result, Err(Error::Cancelled)));
+        assert_eq!(decrypted_dest, &original_data[..64]);
+    }
+
+    struct MockClock(std::sync::Mutex<SystemTime>);
+
+    impl Clock for MockClock {
+        fn now(&self) -> SystemTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_not_before() {
+        use std::time::Duration;
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "async_not_before");
+        let now = SystemTime::now();
+        let clock = Arc::new(MockClock(std::sync::Mutex::new(now)));
+        engine.set_clock(clock.clone());
+
+        let ciphertext = engine.encrypt_with_not_before(b"later", now + Duration::from_secs(60)).unwrap();
+        assert!(matches!(engine.decrypt(&ciphertext), Err(Error::NotYetValid(_))));
+        *clock.0.lock().unwrap() = now + Duration::from_secs(61);
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"later");
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_authenticated_oneshot_size_limit() {
+        use crate::common::utils::CryptoConfig;
+
+        let dir = tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig {
+                key_storage_dir: dir.path().to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            rotation: RotationPolicy::default(),
+            crypto: CryptoConfig { rsa_key_bits: 2048, max_oneshot_size: 64, ..Default::default() },
+        };
+        let engine = TestEngine::new(Arc::new(ConfigManager::from_config_file(config)), "async_auth_oneshot_limit").unwrap();
+
+        let data = [0x5au8; 65];
+        let ciphertext = engine.encrypt_authenticated(&data[..64]).unwrap();
+        assert_eq!(engine.decrypt_authenticated(&ciphertext).unwrap(), &data[..64]);
+        assert!(matches!(engine.encrypt(&data), Err(Error::PayloadTooLarge { size: 65, limit: 64 })));
+        assert!(matches!(engine.encrypt_authenticated(&data), Err(Error::PayloadTooLarge { size: 65, limit: 64 })));
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_refuses_revoked_key_ciphertext() {
+        use crate::common::utils::CryptoConfig;
+
+        let dir = tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig {
+                key_storage_dir: dir.path().to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            rotation: RotationPolicy::default(),
+            crypto: CryptoConfig { rsa_key_bits: 2048, embed_key_fingerprint: true, ..Default::default() },
+        };
+        let mut engine = TestEngine::new(Arc::new(ConfigManager::from_config_file(config)), "async_revoked").unwrap();
+        let ciphertext = engine.encrypt(b"before revocation").unwrap();
+        let authenticated = engine.encrypt_authenticated(b"signed").unwrap();
+        let mut encrypted = Vec::new();
+        engine.encrypt_stream(Cursor::new(b"stream".to_vec()), &mut encrypted, &StreamingConfig::default()).await.unwrap();
+        let fingerprint = CiphertextEnvelope::parse(&ciphertext).unwrap().fingerprint().unwrap().to_string();
+
+        engine.set_revoked_fingerprints(HashSet::from([fingerprint.clone()]));
+        assert!(matches!(engine.decrypt(&ciphertext), Err(Error::KeyRevoked(f)) if f == fingerprint));
+        assert!(matches!(engine.decrypt_authenticated(&authenticated), Err(Error::KeyRevoked(_))));
+
+        // 去掉未经认证的指纹前缀后，吊销的密钥也不会参与逐个尝试
+        let stripped = ciphertext.replacen(&format!("{}.", fingerprint), "", 1);
+        assert!(engine.decrypt(&stripped).is_err());
+        assert!(engine.decrypt_authenticated(&authenticated.replacen(&format!("{}.", fingerprint), "", 1)).is_err());
+        let mut decrypted = Vec::new();
+        assert!(matches!(
+            engine.decrypt_stream(Cursor::new(encrypted), &mut decrypted, &StreamingConfig::default()).await,
+            Err(Error::KeyRevoked(_))
+        ));
+
+        engine.set_revoked_fingerprints(HashSet::new());
+        assert_eq!(engine.decrypt(&stripped).unwrap(), b"before revocation");
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_config_digest_requires_matching_key() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "async_config_digest");
+        let approved = engine.encrypt_with_config_digest(b"approved").unwrap();
+        assert_eq!(engine.decrypt_requiring_profile(&approved, Profile::Balanced).unwrap(), b"approved");
+        assert!(engine.decrypt_requiring_profile(&approved, Profile::CnsaSuite).is_err());
+        assert!(engine.decrypt_requiring_profile(&engine.encrypt(b"plain").unwrap(), Profile::Balanced).is_err());
+
+        let cnsa_digest = BTreeMap::from([(CONFIG_DIGEST_HEADER.to_string(), config_digest_hex(&CryptoConfig::preset(Profile::CnsaSuite)))]);
+        assert!(engine.encrypt_with_headers(b"forged", &cnsa_digest).is_err());
+        engine.config.update_crypto_config(CryptoConfig::preset(Profile::CnsaSuite)).unwrap();
+        assert!(matches!(engine.encrypt_with_config_digest(b"mismatch"), Err(Error::Key(_))));
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_embedded_key_version_selects_key() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "async_key_version");
+        let mut cfg = engine.config.get_crypto_config();
+        cfg.embed_key_version = true;
+        engine.config.update_crypto_config(cfg).unwrap();
+
+        let v1_ciphertext = engine.encrypt(b"version one").unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&v1_ciphertext).unwrap().key_version(), Some(1));
+
+        // 超过使用次数上限后轮换到版本 2
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let v2_ciphertext = engine.encrypt(b"version two").unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&v2_ciphertext).unwrap().key_version(), Some(2));
+        let v2_authenticated = engine.encrypt_authenticated(b"signed two").unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&v2_authenticated).unwrap().key_version(), Some(2));
+
+        assert_eq!(engine.decrypt(&v1_ciphertext).unwrap(), b"version one");
+        assert_eq!(engine.decrypt(&v2_ciphertext).unwrap(), b"version two");
+        assert_eq!(engine.decrypt_authenticated(&v2_authenticated).unwrap(), b"signed two");
+
+        // 版本号决定所用的密钥：改写版本号后不再回退尝试其他密钥
+        let swapped = v1_ciphertext.replacen("ver:1.", "ver:2.", 1);
+        assert!(engine.decrypt(&swapped).is_err());
+        let unknown = v1_ciphertext.replacen("ver:1.", "ver:99.", 1);
+        assert!(matches!(engine.decrypt(&unknown), Err(Error::Key(_))));
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_custom_key_provider_receives_fingerprint_and_version() {
+        use std::sync::Mutex;
+
+        type PublicKey = <RsaKyberCryptoSystem as AsymmetricCryptographicSystem>::PublicKey;
+        type PrivateKey = <RsaKyberCryptoSystem as AsymmetricCryptographicSystem>::PrivateKey;
+
+        /// 按版本号保存密钥对，并记录每次收到的查询
+        struct VersionedKeys {
+            keys: Vec<(u32, (PublicKey, PrivateKey))>,
+            requests: Mutex<Vec<(Option<String>, Option<u32>)>>,
+        }
+
+        impl KeyProvider<RsaKyberCryptoSystem> for VersionedKeys {
+            fn key_pair(&self, fingerprint: Option<&str>, key_version: Option<u32>) -> Result<(&PublicKey, &PrivateKey), Error> {
+                self.requests.lock().unwrap().push((fingerprint.map(str::to_string), key_version));
+                self.keys.iter()
+                    .find(|(version, _)| Some(*version) == key_version)
+                    .map(|(_, (pk, sk))| (pk, sk))
+                    .ok_or_else(|| Error::Key("unknown version".to_string()))
+            }
+        }
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "async_key_provider");
+        let mut cfg = engine.config.get_crypto_config();
+        cfg.embed_key_version = true;
+        cfg.embed_key_fingerprint = true;
+        engine.config.update_crypto_config(cfg).unwrap();
+
+        let v1_ciphertext = engine.encrypt(b"version one").unwrap();
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let v2_ciphertext = engine.encrypt(b"version two").unwrap();
+
+        let keys = engine.primary.load_full().into_iter()
+            .chain(engine.secondary.iter().map(|entry| entry.value().clone()))
+            .map(|arc| (arc.2.version, (arc.0.clone(), arc.1.clone())))
+            .collect();
+        let provider = Arc::new(VersionedKeys { keys, requests: Mutex::new(Vec::new()) });
+        engine.set_key_provider(provider.clone());
+
+        assert_eq!(engine.decrypt(&v1_ciphertext).unwrap(), b"version one");
+        assert_eq!(engine.decrypt(&v2_ciphertext).unwrap(), b"version two");
+
+        let requests = provider.requests.lock().unwrap();
+        let expected: Vec<_> = [&v1_ciphertext, &v2_ciphertext].iter().map(|ciphertext| {
+            let envelope = CiphertextEnvelope::parse(ciphertext).unwrap();
+            (envelope.fingerprint().map(str::to_string), envelope.key_version())
+        }).collect();
+        assert_eq!(*requests, expected);
+        assert_eq!(requests[0].1, Some(1));
+        assert_eq!(requests[1].1, Some(2));
+        assert!(requests.iter().all(|(fingerprint, _)| fingerprint.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_audit_records_decrypting_key() {
+        use crate::common::audit::{AuditOperation, AuditOutcome, MemoryAuditSink};
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "async_audit");
+        let mut cfg = engine.config.get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config.update_crypto_config(cfg).unwrap();
+        let sink = Arc::new(MemoryAuditSink::new());
+        engine.set_audit_sink(Some(sink.clone()));
+
+        let ciphertext = engine.encrypt(b"audited").unwrap();
+        let fingerprint = CiphertextEnvelope::parse(&ciphertext).unwrap().fingerprint().unwrap().to_string();
+        // 指纹前缀未经认证：去掉后经逐个尝试解密，事件记录的是实际完成解密的密钥
+        let stripped = ciphertext.replacen(&format!("{}.", fingerprint), "", 1);
+        assert_eq!(engine.decrypt(&stripped).unwrap(), b"audited");
+        let forged = ciphertext.replacen(&fingerprint, "0000000000000000", 1);
+        assert!(engine.decrypt(&forged).is_err());
+        let mut encrypted = Vec::new();
+        engine.encrypt_stream(Cursor::new(b"stream".to_vec()), &mut encrypted, &StreamingConfig::default()).await.unwrap();
+
+        let events = sink.events();
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|event| event.algorithm == "RsaKyberCryptoSystem" && event.timestamp > 0));
+        assert_eq!((events[0].op, events[0].bytes, &events[0].fingerprint), (AuditOperation::Encrypt, 7, &Some(fingerprint.clone())));
+        assert_eq!((events[1].op, events[1].bytes, &events[1].fingerprint), (AuditOperation::Decrypt, 7, &Some(fingerprint.clone())));
+        assert_eq!((events[2].op, &events[2].fingerprint), (AuditOperation::Decrypt, &None));
+        assert!(matches!(events[2].outcome, AuditOutcome::Failure { .. }));
+        assert_eq!((events[3].op, events[3].bytes, &events[3].outcome), (AuditOperation::Encrypt, 6, &AuditOutcome::Success));
+    }
 } 
\ No newline at end of file
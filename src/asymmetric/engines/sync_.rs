@@ -3,15 +3,66 @@
 //! 该模块封装了密钥管理、轮换、加解密等复杂性，为用户提供一个简洁的入口。
 
 use crate::asymmetric::rotation::KeyRotationManager;
-use crate::asymmetric::traits::{AsymmetricCryptographicSystem, AsymmetricSyncStreamingSystem};
+use crate::asymmetric::traits::{AsymmetricCryptographicSystem, AsymmetricSyncStreamingSystem, KeyProvider};
+use crate::common::audit::{algorithm_name, AuditEvent, AuditSink};
+use crate::common::clock::{Clock, SystemClock};
 use crate::common::config::ConfigManager;
+use crate::common::inspect::{config_digest_hex, ALGORITHM_HEADER, CONFIG_DIGEST_HEADER, RESERVED_HEADERS};
 use crate::common::errors::Error;
+use crate::common::metrics::{Direction, EngineMetrics, MetricsRecorder};
+use crate::common::rng::EngineRng;
 use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::common::utils::{CryptoConfig, Profile};
 use crate::common::traits::AuthenticatedCryptoSystem;
+use crate::common::utils::{attach_fingerprint, attach_headers, attach_key_id, attach_key_version, encode_headers, encode_not_before, key_fingerprint, select_first_success_constant_time, CiphertextEnvelope};
 use crate::storage::KeyFileStorage;
+use std::collections::{BTreeMap, HashSet};
 use std::io::{Read, Write};
 use std::path::Path;
 use std::sync::Arc;
+use std::time::SystemTime;
+#[cfg(feature = "secure-storage")]
+use crate::common::config::ConfigFile;
+#[cfg(feature = "secure-storage")]
+use crate::common::traits::{KeyMetadata, SecureKeyStorage};
+#[cfg(feature = "secure-storage")]
+use crate::common::utils::{from_base64, to_base64};
+#[cfg(feature = "secure-storage")]
+use crate::rotation::KeyStorage;
+#[cfg(feature = "secure-storage")]
+use crate::storage::EncryptedKeyContainer;
+#[cfg(feature = "secure-storage")]
+use secrecy::SecretString;
+#[cfg(feature = "secure-storage")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "secure-storage")]
+use zeroize::Zeroizing;
+
+/// 借用的加密系统 `C` 公私钥对
+type KeyPairRef<'a, C> = (&'a <C as AsymmetricCryptographicSystem>::PublicKey, &'a <C as AsymmetricCryptographicSystem>::PrivateKey);
+
+/// 引擎状态文件中 `EncryptedKeyContainer` 使用的算法标识符
+#[cfg(feature = "secure-storage")]
+const ENGINE_STATE_ALGORITHM_ID: &str = "seal-kit-engine-state-v1";
+
+/// 引擎完整状态快照，序列化后由 `EncryptedKeyContainer` 加密保存
+#[cfg(feature = "secure-storage")]
+#[derive(Serialize, Deserialize)]
+struct EngineState {
+    config: ConfigFile,
+    key_prefix: String,
+    keys: Vec<EngineKeyRecord>,
+}
+
+/// 单个密钥的存储记录
+#[cfg(feature = "secure-storage")]
+#[derive(Serialize, Deserialize)]
+struct EngineKeyRecord {
+    name: String,
+    metadata: KeyMetadata,
+    /// Base64编码的序列化密钥对
+    key_data: String,
+}
 
 /// Q-Seal核心引擎
 ///
@@ -26,6 +77,18 @@ where
     config: Arc<ConfigManager>,
     /// 单线程模式下直接持有轮换管理器
     key_manager: KeyRotationManager<C>,
+    /// 引擎的RNG作用域（确定性测试模式下持有种子RNG状态）
+    rng: EngineRng,
+    /// 操作指标计数器
+    metrics: MetricsRecorder,
+    /// 校验密文生效时间使用的时钟
+    clock: Arc<dyn Clock>,
+    /// 解密带指纹或密钥版本的密文时使用的密钥来源，为 `None` 时使用轮换管理器中的密钥
+    key_provider: Option<Arc<dyn KeyProvider<C> + Send + Sync>>,
+    /// 接收审计事件的目标，为 `None` 时不产生审计事件
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    /// 拒绝解密的已吊销公钥指纹
+    revoked_fingerprints: HashSet<String>,
 }
 
 impl<C: AsymmetricCryptographicSystem + AsymmetricSyncStreamingSystem> AsymmetricQSealEngine<C>
@@ -55,14 +118,40 @@ where
             rotation_policy,
             key_prefix
         );
-        key_manager.initialize(&config_manager.get_crypto_config())?;
+        let crypto_config = config_manager.get_crypto_config();
+        #[cfg(feature = "test-determinism")]
+        if crypto_config.deterministic_seed.is_some() && !C::SUPPORTS_DETERMINISTIC_RNG {
+            return Err(Error::Operation(format!(
+                "{} 的密钥生成与封装使用外部库内部的RNG，无法由 deterministic_seed 固定", algorithm_name::<C>()
+            )));
+        }
+        let rng = EngineRng::from_config(&crypto_config);
+        rng.run(|| key_manager.initialize(&crypto_config))?;
         
         Ok(Self {
             config: config_manager,
             key_manager,
+            rng,
+            metrics: MetricsRecorder::default(),
+            clock: Arc::new(SystemClock),
+            key_provider: None,
+            audit_sink: None,
+            revoked_fingerprints: HashSet::new(),
         })
     }
     
+    /// 与 [`new`](Self::new) 相同，但在发现弱配置时拒绝创建引擎
+    ///
+    /// 校验在生成或加载任何密钥之前进行，存在问题时返回 `Error::InsecureParameter`，
+    /// 其中列出所有不安全的设置（见 [`CryptoConfig::insecure_settings`]）。
+    pub fn new_strict(config_manager: Arc<ConfigManager>, key_prefix: &str) -> Result<Self, Error> {
+        let findings = config_manager.get_crypto_config().insecure_settings();
+        if !findings.is_empty() {
+            return Err(Error::InsecureParameter(findings));
+        }
+        Self::new(config_manager, key_prefix)
+    }
+
     /// 从配置文件路径创建一个新的引擎实例
     ///
     /// # 参数
@@ -93,6 +182,195 @@ where
     ///
     /// 自动处理密钥选择、使用计数更新和必要的密钥轮换。
     pub fn encrypt(&mut self, data: &[u8]) -> Result<String, Error> {
+        self.encrypt_with_key_id(data, None)
+    }
+
+    /// 加密数据，并在密文信封中附加调用方自定义的密钥ID
+    ///
+    /// 密钥ID可通过 [`CiphertextEnvelope::key_id`] 在不解密的情况下读取，供下游路由使用。
+    pub fn encrypt_with_key_id(&mut self, data: &[u8], key_id: Option<String>) -> Result<String, Error> {
+        let rng = self.rng.clone();
+        let result = rng.run(|| self.encrypt_inner(data, key_id.as_deref(), &BTreeMap::new(), None, false));
+        self.record(Direction::Encrypt, result, |_| data.len() as u64)
+    }
+
+    /// 加密数据，并在密文信封中以明文保存 `headers`（如内容类型、时间戳）
+    ///
+    /// 头部可通过 [`CiphertextEnvelope::headers`] 在不解密的情况下读取，
+    /// 同时作为附加认证数据参与加密，任何修改都会导致解密失败。
+    /// 底层系统不支持附加数据（如RSA）时返回错误，而不是输出未受保护的头部。
+    /// [`RESERVED_HEADERS`] 中的字段由引擎写入：算法标识见 [`CryptoConfig::embed_algorithm_id`]，
+    /// 配置摘要只能经 [`encrypt_with_config_digest`](Self::encrypt_with_config_digest) 写入。
+    pub fn encrypt_with_headers(&mut self, data: &[u8], headers: &BTreeMap<String, String>) -> Result<String, Error> {
+        let result = match headers.keys().find(|key| RESERVED_HEADERS.contains(&key.as_str())) {
+            Some(key) => Err(Error::Operation(format!("头部字段 {} 由引擎保留", key))),
+            None => self.encrypt_with_header_map(data, headers, false),
+        };
+        self.record(Direction::Encrypt, result, |_| data.len() as u64)
+    }
+
+    /// 加密数据，并在密文信封的认证头部中记录当前配置的摘要（[`CONFIG_DIGEST_HEADER`]）
+    ///
+    /// 写入摘要前确认主密钥的实际参数（RSA位数、Kyber级别等）与当前配置一致，不一致时返回 `Error::Key`，
+    /// 例如配置已更新而密钥尚未轮换。解密方应使用 [`decrypt_requiring_profile`](Self::decrypt_requiring_profile)
+    /// 在认证解密的同时检查摘要。底层系统不支持附加数据（如RSA）时返回错误。
+    pub fn encrypt_with_config_digest(&mut self, data: &[u8]) -> Result<String, Error> {
+        let digest = config_digest_hex(&self.config.get_crypto_config());
+        let result = self.encrypt_with_header_map(data, &BTreeMap::from([(CONFIG_DIGEST_HEADER.to_string(), digest)]), true);
+        self.record(Direction::Encrypt, result, |_| data.len() as u64)
+    }
+
+    fn encrypt_with_header_map(&mut self, data: &[u8], headers: &BTreeMap<String, String>, check_key_params: bool) -> Result<String, Error> {
+        let rng = self.rng.clone();
+        rng.run(|| self.encrypt_inner(data, None, headers, None, check_key_params))
+    }
+
+    /// 解密由 `expected_profile` 的预设配置（[`CryptoConfig::preset`]）加密的密文，见 [`decrypt_requiring_config`](Self::decrypt_requiring_config)
+    pub fn decrypt_requiring_profile(&mut self, ciphertext: &str, expected_profile: Profile) -> Result<Vec<u8>, Error> {
+        self.decrypt_requiring_config(ciphertext, &CryptoConfig::preset(expected_profile))
+    }
+
+    /// 解密密文，并要求其认证头部记录的配置摘要与 `expected` 一致
+    ///
+    /// 摘要缺失或不一致时返回 `Error::Operation`；头部经 AEAD 认证，被改写的摘要会使解密失败。
+    /// 与只读取头部的 [`verify_produced_with`](crate::common::inspect::verify_produced_with) 不同，本方法的结果可作为策略判断的依据。
+    pub fn decrypt_requiring_config(&mut self, ciphertext: &str, expected: &CryptoConfig) -> Result<Vec<u8>, Error> {
+        let result = Self::check_config_digest(ciphertext, expected).and_then(|_| self.decrypt_inner(ciphertext));
+        self.record_decrypt(result)
+    }
+
+    /// 信封头部记录的配置摘要与 `expected` 不一致时返回 `Error::Operation`
+    fn check_config_digest(ciphertext: &str, expected: &CryptoConfig) -> Result<(), Error> {
+        let envelope = CiphertextEnvelope::parse(ciphertext)?;
+        match envelope.headers().get(CONFIG_DIGEST_HEADER) {
+            Some(digest) if *digest == config_digest_hex(expected) => Ok(()),
+            Some(_) => Err(Error::Operation("密文不是由预期的配置加密的".to_string())),
+            None => Err(Error::Operation("密文未记录配置摘要".to_string())),
+        }
+    }
+
+    /// 加密数据，并在密文信封中写入生效时间，早于该时间的解密请求返回 `Error::NotYetValid`
+    ///
+    /// 生效时间以秒为精度明文保存，可通过 [`CiphertextEnvelope::not_before`] 读取，并作为附加认证数据参与加密。
+    /// 这只是引擎自行执行的软性限制，不是密码学时间锁：持有私钥者可以绕过引擎直接解密。
+    /// 底层系统不支持附加数据（如RSA）时返回错误。
+    pub fn encrypt_with_not_before(&mut self, data: &[u8], not_before: SystemTime) -> Result<String, Error> {
+        let rng = self.rng.clone();
+        let result = Self::encoded_not_before(not_before)
+            .and_then(|encoded| rng.run(|| self.encrypt_inner(data, None, &BTreeMap::new(), Some(&encoded), false)));
+        self.record(Direction::Encrypt, result, |_| data.len() as u64)
+    }
+
+    /// 编码生效时间；底层系统无法认证该字段时拒绝
+    fn encoded_not_before(not_before: SystemTime) -> Result<String, Error> {
+        if !C::SUPPORTS_ADDITIONAL_DATA {
+            return Err(Error::Operation("当前加密系统不支持附加认证数据，无法保护生效时间".to_string()));
+        }
+        encode_not_before(not_before)
+    }
+
+    /// 替换校验密文生效时间所用的时钟，默认读取系统时间
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// 替换解密带指纹或密钥版本的密文时使用的密钥来源，默认使用引擎轮换管理器中的密钥
+    ///
+    /// 适用于调用方自行维护同一逻辑密钥多个版本的场景；不带路由信息的密文仍按轮换管理器中的密钥逐个尝试。
+    pub fn set_key_provider(&mut self, provider: Arc<dyn KeyProvider<C> + Send + Sync>) {
+        self.key_provider = Some(provider);
+    }
+
+    /// 设置已吊销的公钥指纹，之后解密嵌入了其中任一指纹的密文时返回 `Error::KeyRevoked`
+    ///
+    /// 检查在任何解密运算之前进行；传入空集合即关闭检查。信封中的指纹未经认证，因此吊销的密钥同时被排除在
+    /// 解密候选之外：去掉或改写指纹前缀的密文只会在其余密钥上尝试，无法借吊销的私钥解密。
+    pub fn set_revoked_fingerprints(&mut self, revoked: HashSet<String>) {
+        self.revoked_fingerprints = revoked;
+    }
+
+    /// 设置或移除接收审计事件的目标
+    ///
+    /// 设置后每次加解密（含流式与认证加解密）完成时产生一条 [`AuditEvent`]，时间取自引擎的时钟。
+    /// 加密与流式解密事件记录主公钥的指纹；块解密事件记录实际完成解密的公钥指纹，
+    /// 与密文中未经认证的指纹前缀无关，解密失败时不记录指纹。
+    pub fn set_audit_sink(&mut self, sink: Option<Arc<dyn AuditSink>>) {
+        self.audit_sink = sink;
+    }
+
+    /// 更新指标，并在设置了审计目标时以主公钥的指纹记录事件
+    fn record<T>(&self, direction: Direction, result: Result<T, Error>, bytes: impl Fn(&T) -> u64) -> Result<T, Error> {
+        let primary = self.key_manager.get_primary_key().map(|(public_key, _)| public_key);
+        self.record_with_key(direction, primary, result, bytes)
+    }
+
+    /// 记录一次块解密：成功时事件携带实际解密所用公钥的指纹
+    fn record_decrypt(&self, result: Result<(Vec<u8>, &C::PublicKey), Error>) -> Result<Vec<u8>, Error> {
+        let (result, public_key) = match result {
+            Ok((plaintext, public_key)) => (Ok(plaintext), Some(public_key)),
+            Err(e) => (Err(e), None),
+        };
+        self.record_with_key(Direction::Decrypt, public_key, result, |plaintext| plaintext.len() as u64)
+    }
+
+    fn record_with_key<T>(
+        &self,
+        direction: Direction,
+        public_key: Option<&C::PublicKey>,
+        result: Result<T, Error>,
+        bytes: impl Fn(&T) -> u64,
+    ) -> Result<T, Error> {
+        let result = self.metrics.record(direction, result, &bytes);
+        let Some(sink) = &self.audit_sink else {
+            return result;
+        };
+        let fingerprint = public_key
+            .and_then(|public_key| C::export_public_key(public_key).ok())
+            .map(|exported| key_fingerprint(&exported));
+        let event = AuditEvent::new(direction, algorithm_name::<C>(), fingerprint, result.as_ref().map(&bytes), self.clock.now());
+        let recorded = sink.record(&event);
+        result.and_then(|value| recorded.map(|_| value))
+    }
+
+    /// 组装信封中受认证字段的文本：头部在前（按配置加入算法标识），已编码的生效时间在后，二者都没有时为 `None`
+    ///
+    /// 底层系统无法认证头部时拒绝调用方提供的头部，也不写入算法标识。
+    fn authenticated_fields(cfg: &CryptoConfig, headers: &BTreeMap<String, String>, not_before: Option<&str>) -> Result<Option<String>, Error> {
+        if !C::SUPPORTS_ADDITIONAL_DATA {
+            if !headers.is_empty() {
+                return Err(Error::Operation("当前加密系统不支持附加认证数据，无法保护明文头部".to_string()));
+            }
+            return Ok(not_before.map(str::to_string));
+        }
+        let mut headers = headers.clone();
+        if cfg.embed_algorithm_id && let Some(algorithm) = C::ALGORITHM_ID {
+            headers.insert(ALGORITHM_HEADER.to_string(), algorithm.to_string());
+        }
+        let encoded = if headers.is_empty() { None } else { Some(encode_headers(&headers)?) };
+        let fields: Vec<String> = encoded.into_iter().chain(not_before.map(str::to_string)).collect();
+        Ok((!fields.is_empty()).then(|| fields.join(".")))
+    }
+
+    /// 信封头部对应的附加认证数据；底层系统无法认证头部时拒绝带头部的密文
+    fn associated_data<'a>(envelope: &CiphertextEnvelope<'a>) -> Result<Option<&'a [u8]>, Error> {
+        match envelope.associated_data() {
+            Some(_) if !C::SUPPORTS_ADDITIONAL_DATA => {
+                Err(Error::Operation("当前加密系统不支持附加认证数据，无法校验明文头部".to_string()))
+            }
+            aad => Ok(aad),
+        }
+    }
+
+    /// `check_key_params` 为真时要求主密钥的实际参数与当前配置一致，见 [`AsymmetricCryptographicSystem::public_key_matches_config`]
+    fn encrypt_inner(
+        &mut self,
+        data: &[u8],
+        key_id: Option<&str>,
+        headers: &BTreeMap<String, String>,
+        not_before: Option<&str>,
+        check_key_params: bool,
+    ) -> Result<String, Error> {
+        self.config.get_crypto_config().check_oneshot_size(data.len())?;
         let manager = &mut self.key_manager;
         
         // 检查是否需要轮换
@@ -105,32 +383,124 @@ where
             .map(|(pk, _)| pk.clone())
             .ok_or_else(|| Error::Key("没有可用的主加密密钥".to_string()))?;
             
+        let cfg = self.config.get_crypto_config();
+        if check_key_params && !C::public_key_matches_config(&public_key, &cfg)? {
+            return Err(Error::Key("主密钥参数与当前配置不一致，请先轮换密钥".to_string()));
+        }
+        let fields = Self::authenticated_fields(&cfg, headers, not_before)?;
+
         // 现在可以安全地对manager进行可变借用
         manager.increment_usage_count()?;
         
         // 使用克隆的密钥执行加密
-        let ciphertext = C::encrypt(&public_key, data, None)?;
+        let ciphertext = C::encrypt_with_config(&public_key, data, fields.as_deref().map(str::as_bytes), &cfg)?;
         
-        Ok(ciphertext.to_string())
+        let key_version = self.key_manager.get_primary_key_metadata().map(|metadata| metadata.version);
+        Self::finish_output(&cfg, &public_key, key_version, key_id, fields.as_deref(), cfg.base64_variant.encode(ciphertext.as_ref()))
+    }
+
+    /// 按配置在Base64密文前嵌入加密公钥的指纹与密钥版本，并附加可选的头部与自定义密钥ID
+    fn finish_output(
+        cfg: &CryptoConfig,
+        public_key: &C::PublicKey,
+        key_version: Option<u32>,
+        key_id: Option<&str>,
+        headers: Option<&str>,
+        encoded: String,
+    ) -> Result<String, Error> {
+        let output = if cfg.embed_key_fingerprint {
+            let fingerprint = key_fingerprint(&C::export_public_key(public_key)?);
+            attach_fingerprint(&fingerprint, &encoded)
+        } else {
+            encoded
+        };
+        let output = match key_version {
+            Some(key_version) if cfg.embed_key_version => attach_key_version(key_version, &output),
+            _ => output,
+        };
+        let output = match headers {
+            Some(headers) => attach_headers(headers, &output),
+            None => output,
+        };
+        Ok(match key_id {
+            Some(key_id) => attach_key_id(key_id, &output),
+            None => output,
+        })
+    }
+
+    /// 密文信封带有指纹或密钥版本时，从密钥来源中取得对应的密钥对；二者都没有时返回 `None`
+    ///
+    /// 取得的密钥已被吊销时返回 `Error::KeyRevoked`。
+    fn routed_key_pair(&self, envelope: &CiphertextEnvelope) -> Result<Option<KeyPairRef<'_, C>>, Error> {
+        let (fingerprint, key_version) = (envelope.fingerprint(), envelope.key_version());
+        if fingerprint.is_none() && key_version.is_none() {
+            return Ok(None);
+        }
+        let (public_key, private_key) = match &self.key_provider {
+            Some(provider) => provider.key_pair(fingerprint, key_version),
+            None => self.key_manager.key_pair(fingerprint, key_version),
+        }?;
+        if let Some(revoked) = self.revoked_fingerprint(public_key) {
+            return Err(Error::KeyRevoked(revoked));
+        }
+        Ok(Some((public_key, private_key)))
+    }
+
+    /// 公钥已被吊销时返回其指纹；吊销集合为空时不计算指纹。无法导出的公钥按已吊销处理
+    fn revoked_fingerprint(&self, public_key: &C::PublicKey) -> Option<String> {
+        if self.revoked_fingerprints.is_empty() {
+            return None;
+        }
+        match C::export_public_key(public_key) {
+            Ok(exported) => Some(key_fingerprint(&exported)).filter(|fp| self.revoked_fingerprints.contains(fp)),
+            Err(_) => Some(String::new()),
+        }
+    }
+
+    /// 逐个尝试解密时的候选密钥，主密钥在前并标记为 `true`；已吊销的密钥不参与尝试
+    fn candidate_keys(&self) -> Vec<(bool, &C::PublicKey, &C::PrivateKey)> {
+        let manager = &self.key_manager;
+        manager.get_primary_key().map(|(pk, sk)| (true, pk, sk)).into_iter()
+            .chain(manager.get_secondary_keys().into_iter().map(|(pk, sk, _)| (false, pk, sk)))
+            .filter(|(_, pk, _)| self.revoked_fingerprint(pk).is_none())
+            .collect()
     }
     
     /// 解密数据
     ///
-    /// 自动尝试使用主密钥和所有次要密钥进行解密，直到成功为止。
+    /// 若密文嵌入了密钥指纹或密钥版本，则直接使用匹配的密钥解密（见 [`KeyProvider`]），无匹配时立即返回错误；
+    /// 否则自动尝试使用主密钥和所有次要密钥进行解密，直到成功为止。
+    /// 自定义密钥ID仅作路由用途，解密时会被忽略。
     pub fn decrypt(&mut self, ciphertext: &str) -> Result<Vec<u8>, Error> {
-        let manager = &mut self.key_manager;
-        
-        // 首先尝试使用主密钥解密
-        if let Some((_, private_key)) = manager.get_primary_key() {
-            if let Ok(plaintext) = C::decrypt(private_key, ciphertext, None) {
-                return Ok(plaintext);
-            }
+        let result = self.decrypt_inner(ciphertext);
+        self.record_decrypt(result)
+    }
+
+    /// 成功时同时返回实际解密所用的公钥
+    fn decrypt_inner(&self, ciphertext: &str) -> Result<(Vec<u8>, &C::PublicKey), Error> {
+        let envelope = CiphertextEnvelope::parse(ciphertext)?;
+        envelope.check_not_before(self.clock.now())?;
+        envelope.check_revoked(&self.revoked_fingerprints)?;
+        let ciphertext = envelope.body();
+        let aad = Self::associated_data(&envelope)?;
+        let cfg = self.config.get_crypto_config();
+        if let Some((public_key, private_key)) = self.routed_key_pair(&envelope)? {
+            let plaintext = C::decrypt_with_config(private_key, ciphertext, aad, &cfg)?;
+            return Ok((plaintext, public_key));
         }
-        
-        // 如果主密钥失败，遍历次要密钥尝试解密
-        for (_, private_key, _) in manager.get_secondary_keys() {
-            if let Ok(plaintext) = C::decrypt(private_key, ciphertext, None) {
-                return Ok(plaintext);
+
+        let candidates = self.candidate_keys();
+        if cfg.ct_key_selection {
+            return select_first_success_constant_time(candidates, |(_, pk, sk)| {
+                C::decrypt_with_config(sk, ciphertext, aad, &cfg).ok().map(|plaintext| (plaintext, pk))
+            })
+            .ok_or_else(|| Error::Operation("解密失败：所有可用密钥都无法解密该密文".to_string()));
+        }
+
+        // 先尝试主密钥，失败后遍历次要密钥
+        for (_, public_key, private_key) in candidates {
+            if let Ok(plaintext) = C::decrypt_with_config(private_key, ciphertext, aad, &cfg) {
+                return Ok((plaintext, public_key));
             }
         }
         
@@ -143,6 +513,17 @@ where
         reader: R,
         writer: W,
         config: &StreamingConfig,
+    ) -> Result<StreamingResult, Error> {
+        let rng = self.rng.clone();
+        let result = rng.run(|| self.encrypt_stream_inner(reader, writer, config));
+        self.record(Direction::Encrypt, result, |r| r.bytes_processed)
+    }
+
+    fn encrypt_stream_inner<R: Read, W: Write>(
+        &mut self,
+        reader: R,
+        writer: W,
+        config: &StreamingConfig,
     ) -> Result<StreamingResult, Error> {
         let manager = &mut self.key_manager;
         if manager.needs_rotation() {
@@ -164,24 +545,106 @@ where
         writer: W,
         config: &StreamingConfig,
     ) -> Result<StreamingResult, Error> {
-        let manager = &mut self.key_manager;
+        let manager = &self.key_manager;
 
         // 注意：流式解密无法像块解密一样轻易地"尝试"多个密钥。
         // 一个简单的实现是只使用主密钥。
         // 更复杂的实现需要协议层支持，比如在流的开头包含密钥ID。
-        // 这里我们选择只用主密钥进行解密。
+        // 这里我们选择只用主密钥进行解密，主密钥已被吊销时拒绝解密。
         let private_key = manager.get_primary_key()
-            .map(|(_, sk)| sk.clone())
-            .ok_or_else(|| Error::Key("没有可用的主解密密钥".to_string()))?;
+            .ok_or_else(|| Error::Key("没有可用的主解密密钥".to_string()))
+            .and_then(|(pk, sk)| match self.revoked_fingerprint(pk) {
+                Some(revoked) => Err(Error::KeyRevoked(revoked)),
+                None => Ok(sk.clone()),
+            });
 
-        C::decrypt_stream(&private_key, reader, writer, config, None)
-            .map_err(Into::into)
+        let result = private_key.and_then(|private_key| {
+            C::decrypt_stream(&private_key, reader, writer, config, None)
+        });
+        self.record(Direction::Decrypt, result, |r| r.bytes_processed)
     }
 
     /// 获取当前的配置管理器
     pub fn config(&self) -> Arc<ConfigManager> {
         Arc::clone(&self.config)
     }
+
+    /// 返回引擎自创建以来的操作指标快照（仅含计数与明文字节数）
+    pub fn metrics(&self) -> EngineMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// 将引擎的完整状态（配置、轮换策略、密钥前缀及全部密钥）以密码加密保存到单个文件
+    #[cfg(feature = "secure-storage")]
+    pub fn save_engine<P: AsRef<Path>>(&self, path: P, password: &SecretString) -> Result<(), Error> {
+        let keys = self.key_manager.export_key_records()?
+            .into_iter()
+            .map(|(name, metadata, key_data)| EngineKeyRecord {
+                name,
+                metadata,
+                key_data: to_base64(&Zeroizing::new(key_data)),
+            })
+            .collect();
+        let state = EngineState {
+            config: self.config.to_config_file(),
+            key_prefix: self.key_manager.key_prefix().to_string(),
+            keys,
+        };
+        let state_json = Zeroizing::new(serde_json::to_vec(&state)
+            .map_err(|e| Error::Serialization(format!("序列化引擎状态失败: {}", e)))?);
+
+        let container = EncryptedKeyContainer::new_with_config(
+            password,
+            state_json.as_slice(),
+            ENGINE_STATE_ALGORITHM_ID,
+            &state.config.crypto,
+        )?;
+        std::fs::write(path, container.to_json()?)?;
+        Ok(())
+    }
+
+    /// 从 [`save_engine`](Self::save_engine) 生成的文件恢复引擎
+    ///
+    /// 密钥会写回 `storage_dir`（为 `None` 时使用保存时配置中的存储目录），随后以保存的配置构建引擎。
+    /// 若目标目录中已存在同一前缀、但不属于该快照的密钥，则返回错误而不是覆盖。
+    #[cfg(feature = "secure-storage")]
+    pub fn load_engine<P: AsRef<Path>>(
+        path: P,
+        password: &SecretString,
+        storage_dir: Option<&str>,
+    ) -> Result<Self, Error> {
+        let json = std::fs::read_to_string(path)?;
+        let container = EncryptedKeyContainer::from_json(&json)?;
+        if container.algorithm_id() != ENGINE_STATE_ALGORITHM_ID {
+            return Err(Error::Format(format!(
+                "不是引擎状态文件，算法标识符为: {}", container.algorithm_id()
+            )));
+        }
+        let state_json = Zeroizing::new(container.decrypt_key(password)?);
+        let mut state: EngineState = serde_json::from_slice(&state_json)
+            .map_err(|e| Error::Serialization(format!("解析引擎状态失败: {}", e)))?;
+
+        if let Some(dir) = storage_dir {
+            state.config.storage.key_storage_dir = dir.to_string();
+        }
+        let key_storage = KeyFileStorage::new(&state.config.storage.key_storage_dir)?;
+
+        let conflicting = key_storage.list_keys()?
+            .into_iter()
+            .find(|name| name.starts_with(&state.key_prefix)
+                && !state.keys.iter().any(|record| &record.name == name));
+        if let Some(name) = conflicting {
+            return Err(Error::KeyStorage(format!("目标目录中已存在不属于该快照的密钥: {}", name)));
+        }
+
+        for record in &state.keys {
+            let key_data = Zeroizing::new(from_base64(&record.key_data)?);
+            key_storage.save_key(&record.name, &record.metadata, &key_data)?;
+        }
+
+        let config_manager = Arc::new(ConfigManager::from_config_file(state.config));
+        Self::new(config_manager, &state.key_prefix)
+    }
 }
 
 impl<C: AuthenticatedCryptoSystem> AsymmetricQSealEngine<C>
@@ -191,6 +654,18 @@ where
 {
     /// 带认证的加密: 根据配置执行必要的轮换并可选签名
     pub fn encrypt_authenticated(&mut self, plaintext: &[u8]) -> Result<String, Error> {
+        self.encrypt_authenticated_with_key_id(plaintext, None)
+    }
+
+    /// 带认证的加密，并在密文信封中附加调用方自定义的密钥ID
+    pub fn encrypt_authenticated_with_key_id(&mut self, plaintext: &[u8], key_id: Option<String>) -> Result<String, Error> {
+        let rng = self.rng.clone();
+        let result = rng.run(|| self.encrypt_authenticated_inner(plaintext, key_id.as_deref()));
+        self.record(Direction::Encrypt, result, |_| plaintext.len() as u64)
+    }
+
+    fn encrypt_authenticated_inner(&mut self, plaintext: &[u8], key_id: Option<&str>) -> Result<String, Error> {
+        self.config.get_crypto_config().check_oneshot_size(plaintext.len())?;
         let manager = &mut self.key_manager;
         
         // 检查并执行轮换
@@ -203,44 +678,59 @@ where
             .map(|(pk, sk)| (pk.clone(), sk.clone()))
             .ok_or_else(|| Error::Key("没有可用的主加密密钥".to_string()))?;
         
+        let cfg = self.config.get_crypto_config();
+        let fields = Self::authenticated_fields(&cfg, &BTreeMap::new(), None)?;
+
         // 更新使用计数
         manager.increment_usage_count()?;
         
         // 根据配置决定是否签名
-        let cfg = self.config.get_crypto_config();
         let signer = if cfg.use_authenticated_encryption {
             Some(&private_key)
         } else {
             None
         };
-        let auth_output = C::encrypt_authenticated(&public_key, plaintext, None, signer)
-            .map_err(Into::into)?;
-        Ok(auth_output.to_string())
+        let auth_output = C::encrypt_authenticated(&public_key, plaintext, fields.as_deref().map(str::as_bytes), signer)?;
+        let key_version = self.key_manager.get_primary_key_metadata().map(|metadata| metadata.version);
+        Self::finish_output(&cfg, &public_key, key_version, key_id, fields.as_deref(), cfg.base64_variant.encode(auth_output.as_ref()))
     }
 
     /// 带认证的解密: 根据配置执行必要的轮换并可选校验签名
+    ///
+    /// 密文嵌入密钥指纹或密钥版本时的处理方式与 [`decrypt`](Self::decrypt) 相同。
     pub fn decrypt_authenticated(&mut self, ciphertext: &str) -> Result<Vec<u8>, Error> {
-        let manager = &mut self.key_manager;
-        
+        let result = self.decrypt_authenticated_inner(ciphertext);
+        self.record_decrypt(result)
+    }
+
+    fn decrypt_authenticated_inner(&self, ciphertext: &str) -> Result<(Vec<u8>, &C::PublicKey), Error> {
         // 获取配置
         let cfg = self.config.get_crypto_config();
-        
-        // 首先尝试使用主密钥解密并可选验证签名
-        if let Some((public_key, private_key)) = manager.get_primary_key() {
-            let verifier = if cfg.auto_verify_signatures {
-                Some(public_key)
-            } else {
-                None
-            };
-            if let Ok(plaintext) = C::decrypt_authenticated(private_key, ciphertext, None, verifier) {
-                return Ok(plaintext);
-            }
+
+        let envelope = CiphertextEnvelope::parse(ciphertext)?;
+        envelope.check_not_before(self.clock.now())?;
+        envelope.check_revoked(&self.revoked_fingerprints)?;
+        let ciphertext = envelope.body();
+        let aad = Self::associated_data(&envelope)?;
+        if let Some((public_key, private_key)) = self.routed_key_pair(&envelope)? {
+            let verifier = if cfg.auto_verify_signatures { Some(public_key) } else { None };
+            let plaintext = C::decrypt_authenticated(private_key, ciphertext, aad, verifier)?;
+            return Ok((plaintext, public_key));
         }
-        
-        // 如果主密钥失败，遍历次要密钥尝试解密（不验证签名）
-        for (_, private_key, _) in manager.get_secondary_keys() {
-            if let Ok(plaintext) = C::decrypt_authenticated(private_key, ciphertext, None, None) {
-                return Ok(plaintext);
+
+        // 主密钥按配置验证签名，次要密钥不验证签名
+        let candidates = self.candidate_keys().into_iter()
+            .map(|(primary, pk, sk)| (pk, sk, (primary && cfg.auto_verify_signatures).then_some(pk)));
+        if cfg.ct_key_selection {
+            return select_first_success_constant_time(candidates, |(pk, sk, verifier)| {
+                C::decrypt_authenticated(sk, ciphertext, aad, verifier).ok().map(|plaintext| (plaintext, pk))
+            })
+            .ok_or_else(|| Error::Operation("解密失败：所有可用密钥都无法解密该密文".to_string()));
+        }
+
+        for (public_key, private_key, verifier) in candidates {
+            if let Ok(plaintext) = C::decrypt_authenticated(private_key, ciphertext, aad, verifier) {
+                return Ok((plaintext, public_key));
             }
         }
         
@@ -408,6 +898,486 @@ mod tests {
         assert_eq!(plaintext2.as_ref(), decrypted2.as_slice());
     }
     
+    #[test]
+    #[cfg(feature = "secure-storage")]
+    fn test_save_load_engine_roundtrip() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "state");
+        let password = SecretString::new(Box::from("engine-state-password"));
+
+        // 超过使用次数上限触发轮换，使快照同时包含主密钥与次要密钥
+        let old_ciphertext = engine.encrypt(b"before rotation").unwrap();
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let new_ciphertext = engine.encrypt(b"after rotation").unwrap();
+
+        let state_path = dir.path().join("engine.state");
+        engine.save_engine(&state_path, &password).unwrap();
+
+        let restore_dir = tempdir().unwrap();
+        let mut restored = TestEngine::load_engine(
+            &state_path, &password, restore_dir.path().to_str(),
+        ).unwrap();
+
+        assert_eq!(restored.decrypt(&old_ciphertext).unwrap(), b"before rotation");
+        assert_eq!(restored.decrypt(&new_ciphertext).unwrap(), b"after rotation");
+        assert_eq!(restored.config().get_rotation_policy().max_usage_count, Some(5));
+
+        let fresh = restored.encrypt(b"after restore").unwrap();
+        assert_eq!(restored.decrypt(&fresh).unwrap(), b"after restore");
+    }
+
+    #[test]
+    #[cfg(feature = "secure-storage")]
+    fn test_load_engine_wrong_password_and_conflicts() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "state_err");
+        let state_path = dir.path().join("engine.state");
+        engine.save_engine(&state_path, &SecretString::new(Box::from("right"))).unwrap();
+
+        let restore_dir = tempdir().unwrap();
+        let result = TestEngine::load_engine(
+            &state_path, &SecretString::new(Box::from("wrong")), restore_dir.path().to_str(),
+        );
+        assert!(result.is_err());
+
+        // 目标目录中已有同前缀的其他密钥时拒绝恢复
+        let _other = setup_test_engine(restore_dir.path(), "state_err");
+        let result = TestEngine::load_engine(
+            &state_path, &SecretString::new(Box::from("right")), restore_dir.path().to_str(),
+        );
+        assert!(matches!(result, Err(Error::KeyStorage(_))));
+    }
+
+    #[test]
+    fn test_engine_embedded_fingerprint_selects_key() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "fingerprint");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let old_ciphertext = engine.encrypt(b"old key").unwrap();
+        let fingerprint = CiphertextEnvelope::parse(&old_ciphertext).unwrap().fingerprint().unwrap();
+        assert_eq!(fingerprint.len(), 16);
+
+        // 轮换后旧密文仍可通过指纹直接定位到次要密钥
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let new_ciphertext = engine.encrypt(b"new key").unwrap();
+        assert_ne!(CiphertextEnvelope::parse(&new_ciphertext).unwrap().fingerprint().unwrap(), fingerprint);
+        assert_eq!(engine.decrypt(&old_ciphertext).unwrap(), b"old key");
+        assert_eq!(engine.decrypt(&new_ciphertext).unwrap(), b"new key");
+
+        let authenticated = engine.encrypt_authenticated(b"signed").unwrap();
+        assert_eq!(engine.decrypt_authenticated(&authenticated).unwrap(), b"signed");
+    }
+
+    #[test]
+    fn test_engine_embedded_key_version_selects_key() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "key_version");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_version = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let v1_ciphertext = engine.encrypt(b"version one").unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&v1_ciphertext).unwrap().key_version(), Some(1));
+
+        // 超过使用次数上限后轮换到版本 2
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let v2_ciphertext = engine.encrypt(b"version two").unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&v2_ciphertext).unwrap().key_version(), Some(2));
+        let v2_authenticated = engine.encrypt_authenticated(b"signed two").unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&v2_authenticated).unwrap().key_version(), Some(2));
+
+        assert_eq!(engine.decrypt(&v1_ciphertext).unwrap(), b"version one");
+        assert_eq!(engine.decrypt(&v2_ciphertext).unwrap(), b"version two");
+        assert_eq!(engine.decrypt_authenticated(&v2_authenticated).unwrap(), b"signed two");
+
+        // 版本号决定所用的密钥：改写版本号后不再回退尝试其他密钥
+        let swapped = v1_ciphertext.replacen("ver:1.", "ver:2.", 1);
+        assert!(engine.decrypt(&swapped).is_err());
+        let unknown = v1_ciphertext.replacen("ver:1.", "ver:99.", 1);
+        assert!(matches!(engine.decrypt(&unknown), Err(Error::Key(_))));
+    }
+
+    #[test]
+    fn test_engine_custom_key_provider_receives_fingerprint_and_version() {
+        use std::sync::Mutex;
+
+        type PublicKey = <RsaKyberCryptoSystem as AsymmetricCryptographicSystem>::PublicKey;
+        type PrivateKey = <RsaKyberCryptoSystem as AsymmetricCryptographicSystem>::PrivateKey;
+
+        /// 按版本号保存密钥对，并记录每次收到的查询
+        struct VersionedKeys {
+            keys: Vec<(u32, (PublicKey, PrivateKey))>,
+            requests: Mutex<Vec<(Option<String>, Option<u32>)>>,
+        }
+
+        impl KeyProvider<RsaKyberCryptoSystem> for VersionedKeys {
+            fn key_pair(&self, fingerprint: Option<&str>, key_version: Option<u32>) -> Result<(&PublicKey, &PrivateKey), Error> {
+                self.requests.lock().unwrap().push((fingerprint.map(str::to_string), key_version));
+                self.keys.iter()
+                    .find(|(version, _)| Some(*version) == key_version)
+                    .map(|(_, (pk, sk))| (pk, sk))
+                    .ok_or_else(|| Error::Key("unknown version".to_string()))
+            }
+        }
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "key_provider");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_version = true;
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let v1_ciphertext = engine.encrypt(b"version one").unwrap();
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let v2_ciphertext = engine.encrypt(b"version two").unwrap();
+
+        let mut keys = Vec::new();
+        let manager = &engine.key_manager;
+        let (pk, sk) = manager.get_primary_key().unwrap();
+        keys.push((manager.get_primary_key_metadata().unwrap().version, (pk.clone(), sk.clone())));
+        for (pk, sk, metadata) in manager.get_secondary_keys() {
+            keys.push((metadata.version, (pk.clone(), sk.clone())));
+        }
+        let provider = Arc::new(VersionedKeys { keys, requests: Mutex::new(Vec::new()) });
+        engine.set_key_provider(provider.clone());
+
+        assert_eq!(engine.decrypt(&v1_ciphertext).unwrap(), b"version one");
+        assert_eq!(engine.decrypt(&v2_ciphertext).unwrap(), b"version two");
+
+        let requests = provider.requests.lock().unwrap();
+        let expected: Vec<_> = [&v1_ciphertext, &v2_ciphertext].iter().map(|ciphertext| {
+            let envelope = CiphertextEnvelope::parse(ciphertext).unwrap();
+            (envelope.fingerprint().map(str::to_string), envelope.key_version())
+        }).collect();
+        assert_eq!(*requests, expected);
+        assert_eq!(requests[0].1, Some(1));
+        assert_eq!(requests[1].1, Some(2));
+        assert!(requests.iter().all(|(fingerprint, _)| fingerprint.is_some()));
+    }
+
+    #[test]
+    fn test_engine_ct_key_selection_tries_every_key() {
+        use crate::common::utils::CT_SELECTION_ATTEMPTS;
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "ct_selection");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.ct_key_selection = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let old_ciphertext = engine.encrypt(b"old key").unwrap();
+        let old_authenticated = engine.encrypt_authenticated(b"old signed").unwrap();
+        for _ in 0..5 {
+            engine.encrypt(b"filler").unwrap();
+        }
+        let new_ciphertext = engine.encrypt(b"new key").unwrap();
+        let key_count = 1 + engine.key_manager.get_secondary_keys().len();
+        assert!(key_count >= 2);
+
+        for (ciphertext, expected) in [(&new_ciphertext, b"new key".as_slice()), (&old_ciphertext, b"old key")] {
+            let before = CT_SELECTION_ATTEMPTS.with(|count| count.get());
+            assert_eq!(engine.decrypt(ciphertext).unwrap(), expected);
+            assert_eq!(CT_SELECTION_ATTEMPTS.with(|count| count.get()) - before, key_count);
+        }
+        let before = CT_SELECTION_ATTEMPTS.with(|count| count.get());
+        assert_eq!(engine.decrypt_authenticated(&old_authenticated).unwrap(), b"old signed");
+        assert_eq!(CT_SELECTION_ATTEMPTS.with(|count| count.get()) - before, key_count);
+    }
+
+    #[test]
+    fn test_engine_metrics_count_operations_and_failures() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "metrics");
+
+        let ciphertext = engine.encrypt(b"abcd").unwrap();
+        let authenticated = engine.encrypt_authenticated(b"abcdefgh").unwrap();
+        engine.encrypt(&[0u8; 1000]).unwrap();
+        engine.decrypt(&ciphertext).unwrap();
+        engine.decrypt_authenticated(&authenticated).unwrap();
+        assert!(engine.decrypt("garbage").is_err());
+
+        let metrics = engine.metrics();
+        assert_eq!(metrics.encrypt_operations, 3);
+        assert_eq!(metrics.decrypt_operations, 3);
+        assert_eq!(metrics.bytes_decrypted, 12);
+        assert_eq!(metrics.decrypt_failures, 1);
+        assert_eq!(metrics.bytes_encrypted, 1012);
+        assert_eq!(metrics.encrypt_failures, 0);
+    }
+
+    #[test]
+    fn test_engine_audit_events_for_success_and_failure() {
+        use crate::common::audit::{AuditOperation, AuditOutcome, AuditSink, MemoryAuditSink};
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "audit");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+        let sink = Arc::new(MemoryAuditSink::new());
+        engine.set_audit_sink(Some(sink.clone()));
+
+        let ciphertext = engine.encrypt(b"audited").unwrap();
+        let fingerprint = CiphertextEnvelope::parse(&ciphertext).unwrap().fingerprint().map(str::to_string);
+        engine.decrypt(&ciphertext).unwrap();
+        let error = engine.decrypt("garbage").unwrap_err();
+
+        let events = sink.events();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event.algorithm == "RsaKyberCryptoSystem" && event.timestamp > 0));
+        assert_eq!((events[0].op, events[0].bytes, &events[0].outcome), (AuditOperation::Encrypt, 7, &AuditOutcome::Success));
+        assert_eq!(events[0].fingerprint, fingerprint);
+        assert_eq!((events[1].op, events[1].bytes, &events[1].outcome), (AuditOperation::Decrypt, 7, &AuditOutcome::Success));
+        assert_eq!(events[1].fingerprint, fingerprint);
+        assert_eq!(events[2].op, AuditOperation::Decrypt);
+        assert_eq!((events[2].bytes, &events[2].fingerprint), (0, &None));
+        assert_eq!(events[2].outcome, AuditOutcome::Failure { error: error.code() });
+
+        // 去掉指纹前缀后经逐个尝试解密，事件记录的仍是实际完成解密的密钥
+        let stripped = ciphertext.replacen(&format!("{}.", fingerprint.as_deref().unwrap()), "", 1);
+        assert_eq!(engine.decrypt(&stripped).unwrap(), b"audited");
+        assert_eq!(sink.events()[3].fingerprint, fingerprint);
+
+        // 审计写入失败时不交出操作结果
+        struct RejectingSink;
+        impl AuditSink for RejectingSink {
+            fn record(&self, _event: &AuditEvent) -> Result<(), Error> {
+                Err(Error::Operation("审计存储不可用".to_string()))
+            }
+        }
+        engine.set_audit_sink(Some(Arc::new(RejectingSink)));
+        assert!(matches!(engine.encrypt(b"unrecorded"), Err(Error::Operation(_))));
+        engine.set_audit_sink(None);
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"audited");
+        assert_eq!(sink.events().len(), 4);
+    }
+
+    #[test]
+    fn test_engine_key_id_readable_without_decryption() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "key_id");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let ciphertext = engine.encrypt_with_key_id(b"tenant data", Some("tenant-42.primary".to_string())).unwrap();
+        let envelope = CiphertextEnvelope::parse(&ciphertext).unwrap();
+        assert_eq!(envelope.key_id(), Some("tenant-42.primary"));
+        assert_eq!(envelope.fingerprint().map(str::len), Some(16));
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"tenant data");
+
+        let authenticated = engine.encrypt_authenticated_with_key_id(b"signed", Some("tenant-7".to_string())).unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&authenticated).unwrap().key_id(), Some("tenant-7"));
+        assert_eq!(engine.decrypt_authenticated(&authenticated).unwrap(), b"signed");
+
+        let untagged = engine.encrypt_with_key_id(b"no id", None).unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&untagged).unwrap().key_id(), None);
+    }
+
+    #[test]
+    fn test_engine_refuses_revoked_key_ciphertext() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "revoked");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let ciphertext = engine.encrypt(b"before revocation").unwrap();
+        let authenticated = engine.encrypt_authenticated(b"signed").unwrap();
+        let fingerprint = CiphertextEnvelope::parse(&ciphertext).unwrap().fingerprint().unwrap().to_string();
+        let revoked = HashSet::from([fingerprint.clone()]);
+        assert!(crate::common::inspect::is_revoked(&ciphertext, &revoked));
+
+        engine.set_revoked_fingerprints(revoked);
+        assert!(matches!(engine.decrypt(&ciphertext), Err(Error::KeyRevoked(f)) if f == fingerprint));
+        assert!(matches!(engine.decrypt_authenticated(&authenticated), Err(Error::KeyRevoked(_))));
+
+        // 去掉未经认证的指纹前缀后，吊销的密钥也不会参与逐个尝试
+        let stripped = ciphertext.replacen(&format!("{}.", fingerprint), "", 1);
+        let stripped_authenticated = authenticated.replacen(&format!("{}.", fingerprint), "", 1);
+        for ct_key_selection in [false, true] {
+            let mut cfg = engine.config().get_crypto_config();
+            cfg.ct_key_selection = ct_key_selection;
+            engine.config().update_crypto_config(cfg).unwrap();
+            assert!(engine.decrypt(&stripped).is_err());
+            assert!(engine.decrypt_authenticated(&stripped_authenticated).is_err());
+        }
+        let mut encrypted = Vec::new();
+        engine.encrypt_stream(Cursor::new(b"stream"), &mut encrypted, &StreamingConfig::default()).unwrap();
+        assert!(matches!(
+            engine.decrypt_stream(Cursor::new(encrypted), Vec::new(), &StreamingConfig::default()),
+            Err(Error::KeyRevoked(_))
+        ));
+
+        // 清空吊销列表后恢复解密
+        engine.set_revoked_fingerprints(HashSet::new());
+        assert_eq!(engine.decrypt(&stripped).unwrap(), b"before revocation");
+        engine.set_revoked_fingerprints(HashSet::new());
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"before revocation");
+    }
+
+    #[test]
+    fn test_engine_headers_authenticated_in_cleartext() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "headers");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+
+        let headers = BTreeMap::from([
+            ("content-type".to_string(), "text/plain".to_string()),
+            ("timestamp".to_string(), "1700000000".to_string()),
+        ]);
+        let ciphertext = engine.encrypt_with_headers(b"with headers", &headers).unwrap();
+        let mut written = headers.clone();
+        written.insert(ALGORITHM_HEADER.to_string(), "RSA-Kyber".to_string());
+        assert_eq!(CiphertextEnvelope::parse(&ciphertext).unwrap().headers(), &written);
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"with headers");
+
+        // 修改头部
+        let (_, rest) = ciphertext.split_once('.').unwrap();
+        let mut forged = written.clone();
+        forged.insert("timestamp".to_string(), "1800000000".to_string());
+        let tampered = attach_headers(&encode_headers(&forged).unwrap(), rest);
+        assert_eq!(CiphertextEnvelope::parse(&tampered).unwrap().headers(), &forged);
+        assert!(engine.decrypt(&tampered).is_err());
+        // 删除头部
+        assert!(engine.decrypt(rest).is_err());
+    }
+
+    #[test]
+    fn test_engine_config_digest_verifies_profile() {
+        use crate::common::inspect::{verify_produced_with, CONFIG_DIGEST_HEADER};
+
+        // 默认配置生成的密钥为 RSA-3072 + Kyber-768，与 Balanced 预设一致
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "config_digest");
+        engine.config().update_crypto_config(CryptoConfig::preset(Profile::Balanced)).unwrap();
+        let approved = engine.encrypt_with_config_digest(b"approved").unwrap();
+        assert!(verify_produced_with(&approved, Profile::Balanced));
+        assert!(!verify_produced_with(&approved, Profile::CnsaSuite));
+        assert_eq!(engine.decrypt_requiring_profile(&approved, Profile::Balanced).unwrap(), b"approved");
+        assert!(engine.decrypt_requiring_profile(&approved, Profile::CnsaSuite).is_err());
+        let plain = engine.encrypt(b"plain").unwrap();
+        assert!(engine.decrypt_requiring_profile(&plain, Profile::Balanced).is_err());
+
+        // 配置改为 CNSA 而密钥仍是 Kyber-768 时拒绝写入摘要，也不能经自定义头部伪造
+        engine.config().update_crypto_config(CryptoConfig::preset(Profile::CnsaSuite)).unwrap();
+        assert!(matches!(engine.encrypt_with_config_digest(b"mismatch"), Err(Error::Key(_))));
+        let cnsa_digest = BTreeMap::from([(CONFIG_DIGEST_HEADER.to_string(), config_digest_hex(&CryptoConfig::preset(Profile::CnsaSuite)))]);
+        assert!(engine.encrypt_with_headers(b"forged", &cnsa_digest).is_err());
+
+        // 把摘要头部拼接到其他密文上可以骗过只读头部的检查，但无法通过认证解密
+        let (_, rest) = approved.split_once('.').unwrap();
+        let forged = attach_headers(&encode_headers(&cnsa_digest).unwrap(), rest);
+        assert!(verify_produced_with(&forged, Profile::CnsaSuite));
+        assert!(engine.decrypt_requiring_profile(&forged, Profile::CnsaSuite).is_err());
+    }
+
+    /// 可手动推进的模拟时钟
+    struct MockClock(std::sync::Mutex<SystemTime>);
+
+    impl Clock for MockClock {
+        fn now(&self) -> SystemTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn test_engine_not_before_enforced_with_mock_clock() {
+        use crate::common::utils::encode_not_before;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "not_before");
+        let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let clock = Arc::new(MockClock(std::sync::Mutex::new(start)));
+        engine.set_clock(clock.clone());
+
+        let not_before = start + Duration::from_secs(3600);
+        let ciphertext = engine.encrypt_with_not_before(b"time locked", not_before).unwrap();
+        assert_eq!(CiphertextEnvelope::parse(&ciphertext).unwrap().not_before(), Some(not_before));
+        assert!(matches!(engine.decrypt(&ciphertext), Err(Error::NotYetValid(_))));
+
+        *clock.0.lock().unwrap() = not_before - Duration::from_secs(1);
+        assert!(matches!(engine.decrypt(&ciphertext), Err(Error::NotYetValid(_))));
+        *clock.0.lock().unwrap() = not_before;
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"time locked");
+
+        // 生效时间受认证：提前或删除该字段都会导致解密失败
+        let field = encode_not_before(not_before).unwrap();
+        let earlier = ciphertext.replacen(&field, &encode_not_before(start).unwrap(), 1);
+        assert_eq!(CiphertextEnvelope::parse(&earlier).unwrap().not_before(), Some(start));
+        assert!(engine.decrypt(&earlier).is_err());
+        assert!(engine.decrypt(&ciphertext.replacen(&format!("{}.", field), "", 1)).is_err());
+    }
+
+    #[test]
+    fn test_engine_headers_rejected_without_aad_support() {
+        use crate::asymmetric::systems::traditional::rsa::RsaCryptoSystem;
+
+        let dir = tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig {
+                key_storage_dir: dir.path().to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            rotation: RotationPolicy::default(),
+            crypto: CryptoConfig { rsa_key_bits: 2048, ..Default::default() },
+        };
+        let mut engine = AsymmetricQSealEngine::<RsaCryptoSystem>::new(
+            Arc::new(ConfigManager::from_config_file(config)),
+            "rsa_headers",
+        ).unwrap();
+
+        let headers = BTreeMap::from([("content-type".to_string(), "text/plain".to_string())]);
+        assert!(engine.encrypt_with_headers(b"rsa", &headers).is_err());
+        assert!(engine.encrypt_with_not_before(b"rsa", SystemTime::now()).is_err());
+
+        // 带头部的RSA密文无法被认证，解密时同样拒绝
+        let ciphertext = engine.encrypt(b"rsa").unwrap();
+        let with_headers = attach_headers(&encode_headers(&headers).unwrap(), &ciphertext);
+        assert!(engine.decrypt(&with_headers).is_err());
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"rsa");
+    }
+
+    #[test]
+    fn test_engine_fingerprint_mismatch_fails_fast() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "fingerprint_mismatch");
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_key_fingerprint = true;
+        engine.config().update_crypto_config(cfg).unwrap();
+        let ciphertext = engine.encrypt(b"data").unwrap();
+
+        let other_dir = tempdir().unwrap();
+        let mut other = setup_test_engine(other_dir.path(), "fingerprint_mismatch");
+        match other.decrypt(&ciphertext) {
+            Err(Error::KeyMismatch { expected, provided }) => {
+                assert_eq!(expected, CiphertextEnvelope::parse(&ciphertext).unwrap().fingerprint().unwrap());
+                assert_ne!(expected, provided);
+            }
+            other => panic!("期望 KeyMismatch，实际为 {:?}", other),
+        }
+        assert!(matches!(other.decrypt_authenticated(&ciphertext), Err(Error::KeyMismatch { .. })));
+
+        // 未嵌入指纹的密文仍按原方式逐个尝试密钥
+        let fingerprint = CiphertextEnvelope::parse(&ciphertext).unwrap().fingerprint().unwrap().to_string();
+        let plain = ciphertext.replacen(&format!("{}.", fingerprint), "", 1);
+        assert_eq!(engine.decrypt(&plain).unwrap(), b"data");
+    }
+
     #[test]
     fn test_engine_streaming_roundtrip() {
         let dir = tempdir().unwrap();
@@ -426,6 +1396,35 @@ mod tests {
         assert_eq!(original_data.as_ref(), decrypted_dest.into_inner().as_slice());
     }
 
+    #[test]
+    fn test_new_strict_rejects_weak_config() {
+        let dir = tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig {
+                key_storage_dir: dir.path().to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            rotation: RotationPolicy::default(),
+            crypto: CryptoConfig { rsa_key_bits: 2048, auto_verify_signatures: false, ..Default::default() },
+        };
+        let config_manager = Arc::new(ConfigManager::from_config_file(config));
+
+        match TestEngine::new_strict(config_manager.clone(), "strict") {
+            Err(Error::InsecureParameter(findings)) => {
+                assert_eq!(findings.len(), 2, "{:?}", findings);
+                assert!(findings[0].contains("rsa_key_bits = 2048"));
+                assert!(findings[1].contains("auto_verify_signatures"));
+            }
+            other => panic!("弱配置应被拒绝: {:?}", other.map(|_| ())),
+        }
+        // 拒绝发生在生成密钥之前
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let mut engine = TestEngine::new(config_manager, "strict").unwrap();
+        let ciphertext = engine.encrypt(b"permissive").unwrap();
+        assert_eq!(engine.decrypt(&ciphertext).unwrap(), b"permissive");
+    }
+
     #[test]
     #[should_panic]
     fn test_streaming_decrypt_with_rotated_key_fails() {
@@ -449,4 +1448,24 @@ mod tests {
         let mut decrypted_dest = Cursor::new(Vec::new());
         engine.decrypt_stream(&mut encrypted_source, &mut decrypted_dest, &streaming_config).unwrap();
     }
+
+    #[test]
+    fn test_engine_authenticated_oneshot_size_limit() {
+        let dir = tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig {
+                key_storage_dir: dir.path().to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            rotation: RotationPolicy::default(),
+            crypto: CryptoConfig { rsa_key_bits: 2048, max_oneshot_size: 64, ..Default::default() },
+        };
+        let mut engine = TestEngine::new(Arc::new(ConfigManager::from_config_file(config)), "auth_oneshot_limit").unwrap();
+
+        let data = [0x5au8; 65];
+        let ciphertext = engine.encrypt_authenticated(&data[..64]).unwrap();
+        assert_eq!(engine.decrypt_authenticated(&ciphertext).unwrap(), &data[..64]);
+        assert!(matches!(engine.encrypt(&data), Err(Error::PayloadTooLarge { size: 65, limit: 64 })));
+        assert!(matches!(engine.encrypt_authenticated(&data), Err(Error::PayloadTooLarge { size: 65, limit: 64 })));
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,180 @@
+//! 在不解密的情况下识别 seal-kit 密文
+//!
+//! 目前的密文格式没有统一的版本/算法前缀，因此识别基于各格式的结构特征：
+//! 引擎信封（自定义密钥ID、公钥指纹）、Kyber 密文首字节的变体ID与长度、混合密文的分隔结构。
+//! RSA 与对称 AEAD 的裸密文与随机数据不可区分，只有带引擎信封时才能被识别为 seal-kit 密文，
+//! 此时算法为 [`DetectedAlgorithm::Unknown`]。所有判断都只检查结构，任意输入都不会引发错误。
+
+use crate::common::utils::{from_base64, CiphertextEnvelope};
+#[cfg(feature = "post-quantum")]
+use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+use crate::asymmetric::systems::hybrid::rsa_kyber;
+
+/// AEAD 认证标签长度
+#[cfg(feature = "post-quantum")]
+const AEAD_TAG_SIZE: usize = 16;
+/// 混合密文 DEM 的 Nonce 长度
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+const HYBRID_NONCE_SIZE: usize = 12;
+/// 混合密文各部分之间的分隔符
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+const HYBRID_DELIMITER: &[u8] = b"::";
+
+/// 识别出的算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedAlgorithm {
+    /// Kyber KEM-DEM 密文（含密封盒），`parameter_k` 为 512/768/1024
+    Kyber { parameter_k: usize },
+    /// RSA-Kyber 混合密文，`signed` 表示附带 RSA 签名
+    RsaKyberHybrid { parameter_k: usize, signed: bool },
+    /// 带引擎信封但载荷结构无法区分的密文（如 RSA）
+    Unknown,
+}
+
+/// 识别结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgorithmInfo {
+    /// 识别出的算法
+    pub algorithm: DetectedAlgorithm,
+    /// 引擎信封中的公钥指纹
+    pub key_fingerprint: Option<String>,
+    /// 引擎信封中的自定义密钥ID
+    pub key_id: Option<String>,
+}
+
+/// 判断 `bytes` 是否可能为 seal-kit 密文（Base64 文本，可带引擎信封），并报告算法
+///
+/// 不尝试解密；无法识别时返回 `None`。
+pub fn identify(bytes: &[u8]) -> Option<AlgorithmInfo> {
+    let text = std::str::from_utf8(bytes).ok()?.trim();
+    let envelope = CiphertextEnvelope::parse(text).ok()?;
+    let has_envelope = envelope.key_id().is_some() || envelope.fingerprint().is_some();
+    if envelope.fingerprint().is_some_and(|fp| fp.len() != 16 || !fp.bytes().all(|b| b.is_ascii_hexdigit())) {
+        return None;
+    }
+
+    let decoded = from_base64(envelope.body()).ok()?;
+    let algorithm = match detect_payload(&decoded) {
+        Some(algorithm) => algorithm,
+        None if has_envelope && !decoded.is_empty() => DetectedAlgorithm::Unknown,
+        None => return None,
+    };
+    Some(AlgorithmInfo {
+        algorithm,
+        key_fingerprint: envelope.fingerprint().map(str::to_string),
+        key_id: envelope.key_id().map(str::to_string),
+    })
+}
+
+#[allow(unused_variables)]
+fn detect_payload(decoded: &[u8]) -> Option<DetectedAlgorithm> {
+    #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+    if let Some(algorithm) = detect_hybrid(decoded) {
+        return Some(algorithm);
+    }
+    #[cfg(feature = "post-quantum")]
+    if let Some(parameter_k) = detect_kyber(decoded) {
+        return Some(DetectedAlgorithm::Kyber { parameter_k });
+    }
+    None
+}
+
+/// Kyber 密文：`变体ID || KEM密文 || [Nonce] || AEAD密文`
+#[cfg(feature = "post-quantum")]
+fn detect_kyber(decoded: &[u8]) -> Option<usize> {
+    let (&variant_id, rest) = decoded.split_first()?;
+    let (parameter_k, kem_len) = KyberCryptoSystem::variant_layout(variant_id)?;
+    (rest.len() >= kem_len + AEAD_TAG_SIZE).then_some(parameter_k)
+}
+
+/// 混合密文：`[版本] Base64(Kyber密文) :: Nonce :: DEM密文 [:: Base64(签名)]`，旧格式没有版本字节
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+fn detect_hybrid(decoded: &[u8]) -> Option<DetectedAlgorithm> {
+    let decoded = decoded.strip_prefix(&[rsa_kyber::FORMAT_VERSION]).unwrap_or(decoded);
+    let kem_end = decoded.windows(HYBRID_DELIMITER.len()).position(|w| w == HYBRID_DELIMITER)?;
+    let parameter_k = detect_kyber(&from_base64(std::str::from_utf8(&decoded[..kem_end]).ok()?).ok()?)?;
+
+    let rest = &decoded[kem_end + HYBRID_DELIMITER.len()..];
+    let dem_start = HYBRID_NONCE_SIZE + HYBRID_DELIMITER.len();
+    if rest.len() < dem_start + AEAD_TAG_SIZE || &rest[HYBRID_NONCE_SIZE..dem_start] != HYBRID_DELIMITER {
+        return None;
+    }
+
+    // 签名为追加在末尾的 Base64 文本；DEM 密文中偶然出现的分隔符不会恰好跟随一段合法的签名
+    let dem_and_signature = &rest[dem_start..];
+    let signed = dem_and_signature.windows(HYBRID_DELIMITER.len())
+        .rposition(|w| w == HYBRID_DELIMITER)
+        .and_then(|pos| std::str::from_utf8(&dem_and_signature[pos + HYBRID_DELIMITER.len()..]).ok())
+        .and_then(|signature| from_base64(signature).ok())
+        .is_some_and(|signature| (128..=1024).contains(&signature.len()));
+    Some(DetectedAlgorithm::RsaKyberHybrid { parameter_k, signed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_identify_kyber_levels() {
+        use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+        use crate::common::utils::CryptoConfig;
+
+        for k in [512, 768, 1024] {
+            let config = CryptoConfig { kyber_parameter_k: k, ..Default::default() };
+            let (pk, _) = KyberCryptoSystem::generate_keypair(&config).unwrap();
+            let ciphertext = KyberCryptoSystem::encrypt(&pk, b"identify me", None).unwrap();
+            let info = identify(ciphertext.to_string().as_bytes()).unwrap();
+            assert_eq!(info.algorithm, DetectedAlgorithm::Kyber { parameter_k: k });
+
+            let sealed = KyberCryptoSystem::seal_anonymous(&pk, b"").unwrap();
+            assert_eq!(identify(sealed.to_string().as_bytes()).unwrap().algorithm, DetectedAlgorithm::Kyber { parameter_k: k });
+        }
+    }
+
+    #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_identify_hybrid_and_envelopes() {
+        use crate::asymmetric::systems::hybrid::rsa_kyber::RsaKyberCryptoSystem;
+        use crate::asymmetric::systems::traditional::rsa::RsaCryptoSystem;
+        use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+        use crate::common::traits::AuthenticatedCryptoSystem;
+        use crate::common::utils::{attach_fingerprint, attach_key_id, CryptoConfig};
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let unsigned = RsaKyberCryptoSystem::encrypt(&pk, b"hybrid", None).unwrap().to_string();
+        let signed = RsaKyberCryptoSystem::encrypt_authenticated(&pk, b"hybrid", None, Some(&sk)).unwrap().to_string();
+        assert_eq!(identify(unsigned.as_bytes()).unwrap().algorithm, DetectedAlgorithm::RsaKyberHybrid { parameter_k: 768, signed: false });
+        assert_eq!(identify(signed.as_bytes()).unwrap().algorithm, DetectedAlgorithm::RsaKyberHybrid { parameter_k: 768, signed: true });
+
+        let tagged = attach_key_id("tenant", &attach_fingerprint("0123456789abcdef", &unsigned));
+        let info = identify(tagged.as_bytes()).unwrap();
+        assert_eq!(info.key_id.as_deref(), Some("tenant"));
+        assert_eq!(info.key_fingerprint.as_deref(), Some("0123456789abcdef"));
+
+        // RSA 裸密文无结构特征，仅在带引擎信封时被识别
+        let (rsa_pk, _) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let rsa = RsaCryptoSystem::encrypt(&rsa_pk, b"rsa", None).unwrap().to_string();
+        let rsa_info = identify(attach_fingerprint("0123456789abcdef", &rsa).as_bytes()).unwrap();
+        assert_eq!(rsa_info.algorithm, DetectedAlgorithm::Unknown);
+    }
+
+    #[test]
+    fn test_identify_rejects_arbitrary_input() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for len in [0usize, 1, 15, 64, 1000, 4096] {
+            let random: Vec<u8> = (0..len).map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            }).collect();
+            assert_eq!(identify(&random), None, "len {}", len);
+        }
+        for text in ["", "hello world", "kid:", "kid:!!.abc", "zz.Zm9v", "0123456789abcdef.", "Zm9vYmFy"] {
+            assert_eq!(identify(text.as_bytes()), None, "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,577 @@
+//! 多接收方信封
+//!
+//! 载荷只用随机生成的数据加密密钥（DEK）以 AES-256-GCM 加密一次，DEK 再分别用每个接收方的公钥封装。
+//! 每个封装条目附带接收方的能力策略（如是否允许转授），策略与接收方公钥指纹一起作为封装时的附加认证数据。
+//!
+//! 持有 DEK 的任何一方都能为任意公钥封装新条目，因此条目还须由授予方签名：初始条目由发送方签名，
+//! 之后添加的条目由可转授的接收方签名。签名覆盖载荷摘要、接收方公钥、策略、封装的 DEK 与授予方指纹，
+//! 解封时沿授予链逐级验证，直到调用方信任的发送方公钥为止。只读接收方签出的条目无法通过验证，
+//! 因而不能为自己或他人提升权限。
+//!
+//! 签名依赖 [`AuthenticatedCryptoSystem`]，封装依赖附加认证数据，目前适用于 RSA-Kyber 混合系统。
+//! 签名只约束条目的添加，不阻止有写权限的一方删除条目。
+
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+use crate::common::errors::Error;
+use crate::common::traits::AuthenticatedCryptoSystem;
+use crate::common::utils::{aad_segments, from_base64, key_fingerprint, to_base64, CryptoConfig};
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 封装 DEK 时附加认证数据的域分隔标签
+const WRAP_AAD_LABEL: &[u8] = b"seal-kit-recipient-v1";
+/// 加密载荷时附加认证数据的域分隔标签
+const PAYLOAD_AAD_LABEL: &[u8] = b"seal-kit-multi-recipient-v1";
+/// 授予方签名数据的域分隔标签
+const GRANT_SIGNATURE_LABEL: &[u8] = b"seal-kit-recipient-grant-v1";
+
+/// 接收方的能力策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecipientPolicy {
+    /// 只能读取载荷
+    ReadOnly,
+    /// 可以读取载荷，并可将访问权转授给其他接收方
+    Reshare,
+}
+
+impl RecipientPolicy {
+    /// 是否允许转授
+    pub fn can_reshare(self) -> bool {
+        self == RecipientPolicy::Reshare
+    }
+
+    fn as_byte(self) -> u8 {
+        match self {
+            RecipientPolicy::ReadOnly => 0,
+            RecipientPolicy::Reshare => 1,
+        }
+    }
+}
+
+/// 单个接收方的封装条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecipientEntry {
+    key_fingerprint: String,
+    public_key: String,
+    policy: RecipientPolicy,
+    wrapped_key: String,
+    /// 授予方（发送方或可转授的接收方）的公钥指纹
+    granted_by: String,
+    /// 授予方对 [`signed_data`](Self::signed_data) 的签名，Base64 编码
+    signature: String,
+}
+
+impl RecipientEntry {
+    fn wrap_aad(key_fingerprint: &str, policy: RecipientPolicy) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(WRAP_AAD_LABEL.len() + key_fingerprint.len() + 1);
+        aad.extend_from_slice(WRAP_AAD_LABEL);
+        aad.extend_from_slice(key_fingerprint.as_bytes());
+        aad.push(policy.as_byte());
+        aad
+    }
+
+    /// 授予方签名的数据：各字段经 [`aad_segments`] 规范拼接
+    fn signed_data(&self, payload_digest: &[u8]) -> Vec<u8> {
+        aad_segments(&[
+            GRANT_SIGNATURE_LABEL,
+            payload_digest,
+            self.key_fingerprint.as_bytes(),
+            self.public_key.as_bytes(),
+            &[self.policy.as_byte()],
+            self.wrapped_key.as_bytes(),
+            self.granted_by.as_bytes(),
+        ])
+    }
+}
+
+/// 接收方解封得到的 DEK 及其策略
+#[derive(Debug)]
+pub struct UnwrappedKey {
+    key: AesGcmKey,
+    policy: RecipientPolicy,
+    key_fingerprint: String,
+    /// 解封来源信封的载荷摘要，用于确认 DEK 只用于修改同一信封
+    payload_digest: [u8; 32],
+}
+
+impl UnwrappedKey {
+    /// 数据加密密钥
+    pub fn key(&self) -> &AesGcmKey {
+        &self.key
+    }
+
+    /// 该接收方经过授予链签名验证的策略
+    pub fn policy(&self) -> RecipientPolicy {
+        self.policy
+    }
+
+    /// 该接收方的公钥指纹
+    pub fn key_fingerprint(&self) -> &str {
+        &self.key_fingerprint
+    }
+}
+
+/// 多接收方信封的构建器
+pub struct MultiRecipientBuilder<'a, S: AuthenticatedCryptoSystem> {
+    recipients: Vec<(&'a S::PublicKey, RecipientPolicy)>,
+    _system: PhantomData<S>,
+}
+
+impl<'a, S> MultiRecipientBuilder<'a, S>
+where
+    S: AuthenticatedCryptoSystem,
+    Error: From<S::Error>,
+{
+    /// 创建空的构建器
+    pub fn new() -> Self {
+        Self { recipients: Vec::new(), _system: PhantomData }
+    }
+
+    /// 添加只读接收方
+    pub fn add_recipient(self, public_key: &'a S::PublicKey) -> Self {
+        self.add_recipient_with_policy(public_key, RecipientPolicy::ReadOnly)
+    }
+
+    /// 添加带指定策略的接收方
+    pub fn add_recipient_with_policy(mut self, public_key: &'a S::PublicKey, policy: RecipientPolicy) -> Self {
+        self.recipients.push((public_key, policy));
+        self
+    }
+
+    /// 生成随机 DEK 加密载荷，为每个接收方封装 DEK，并以发送方私钥 `sender` 签名每个条目
+    ///
+    /// 接收方解封时须提供 `sender` 对应的公钥。
+    pub fn seal(self, sender: &S::PrivateKey, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<MultiRecipientEnvelope, Error> {
+        if !S::SUPPORTS_ADDITIONAL_DATA {
+            return Err(Error::Operation("多接收方信封要求非对称系统支持附加认证数据".to_string()));
+        }
+        if self.recipients.is_empty() {
+            return Err(Error::Operation("多接收方信封至少需要一个接收方".to_string()));
+        }
+
+        let sender_fingerprint = private_key_fingerprint::<S>(sender)?;
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default())?;
+        let payload = AesGcmSystem::encrypt(&key, plaintext, Some(&payload_aad(additional_data)))?.to_string();
+        let payload_digest = Sha256::digest(payload.as_bytes());
+        let mut envelope = MultiRecipientEnvelope { recipients: Vec::with_capacity(self.recipients.len()), payload };
+        for (public_key, policy) in self.recipients {
+            let entry = wrap_key::<S>(&key, public_key, policy, (sender, &sender_fingerprint), &payload_digest)?;
+            envelope.recipients.push(entry);
+        }
+        Ok(envelope)
+    }
+}
+
+impl<S> Default for MultiRecipientBuilder<'_, S>
+where
+    S: AuthenticatedCryptoSystem,
+    Error: From<S::Error>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 多接收方信封：一份载荷密文与每个接收方各自的 DEK 封装条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiRecipientEnvelope {
+    recipients: Vec<RecipientEntry>,
+    payload: String,
+}
+
+impl MultiRecipientEnvelope {
+    /// 接收方数量
+    pub fn recipient_count(&self) -> usize {
+        self.recipients.len()
+    }
+
+    /// 各接收方的公钥指纹，顺序与添加顺序一致
+    pub fn recipient_fingerprints(&self) -> Vec<&str> {
+        self.recipients.iter().map(|entry| entry.key_fingerprint.as_str()).collect()
+    }
+
+    /// 使用接收方私钥解封 DEK，并返回经过认证的策略
+    ///
+    /// 能解封的条目还须通过授予链签名验证，链的起点必须是 `sender` 签出的条目。
+    /// 私钥不属于任何接收方或对应条目被篡改时返回 `Error::DecryptionFailed`；
+    /// 条目可以解封但签名无法追溯到 `sender` 时返回 `Error::Operation`。
+    pub fn unwrap_key<S>(&self, private_key: &S::PrivateKey, sender: &S::PublicKey) -> Result<UnwrappedKey, Error>
+    where
+        S: AuthenticatedCryptoSystem,
+        Error: From<S::Error>,
+    {
+        let sender_fingerprint = key_fingerprint(&S::export_public_key(sender)?);
+        let payload_digest = Sha256::digest(self.payload.as_bytes()).into();
+        let mut unverified = None;
+        for (index, entry) in self.recipients.iter().enumerate() {
+            let aad = RecipientEntry::wrap_aad(&entry.key_fingerprint, entry.policy);
+            let Ok(key_bytes) = S::decrypt(private_key, &entry.wrapped_key, Some(&aad)).map(Zeroizing::new) else {
+                continue;
+            };
+            // 签不出有效授予链的条目可能是持有 DEK 的一方伪造的，跳过后继续寻找真实条目
+            if let Err(e) = self.verify_grant_chain::<S>(index, sender, &sender_fingerprint) {
+                unverified = Some(e);
+                continue;
+            }
+            let key_bytes: [u8; 32] = key_bytes.as_slice().try_into()
+                .map_err(|_| Error::DecryptionFailed("封装的数据加密密钥长度无效".to_string()))?;
+            return Ok(UnwrappedKey {
+                key: AesGcmKey::from_derived(key_bytes),
+                policy: entry.policy,
+                key_fingerprint: entry.key_fingerprint.clone(),
+                payload_digest,
+            });
+        }
+        Err(unverified.unwrap_or_else(|| {
+            Error::DecryptionFailed("私钥不属于该信封的任何接收方，或其封装条目已被篡改".to_string())
+        }))
+    }
+
+    /// 使用接收方私钥解密载荷，返回明文与该接收方的策略；`sender` 的含义见 [`unwrap_key`](Self::unwrap_key)
+    pub fn open<S>(
+        &self,
+        private_key: &S::PrivateKey,
+        sender: &S::PublicKey,
+        additional_data: Option<&[u8]>,
+    ) -> Result<(Vec<u8>, RecipientPolicy), Error>
+    where
+        S: AuthenticatedCryptoSystem,
+        Error: From<S::Error>,
+    {
+        let unwrapped = self.unwrap_key::<S>(private_key, sender)?;
+        let plaintext = AesGcmSystem::decrypt(unwrapped.key(), &self.payload, Some(&payload_aad(additional_data)))?;
+        Ok((plaintext, unwrapped.policy))
+    }
+
+    /// 为新的接收方封装 DEK 并以授予方私钥签名，载荷密文保持不变
+    ///
+    /// `granter` 必须是通过 [`unwrap_key`](Self::unwrap_key) 从本信封取得、且策略允许转授的 DEK，
+    /// `granter_key` 是该接收方的私钥；公钥已是接收方时返回 `Error::Operation`。
+    pub fn add_recipient<S>(
+        &mut self,
+        granter: &UnwrappedKey,
+        granter_key: &S::PrivateKey,
+        public_key: &S::PublicKey,
+        policy: RecipientPolicy,
+    ) -> Result<(), Error>
+    where
+        S: AuthenticatedCryptoSystem,
+        Error: From<S::Error>,
+    {
+        if !S::SUPPORTS_ADDITIONAL_DATA {
+            return Err(Error::Operation("多接收方信封要求非对称系统支持附加认证数据".to_string()));
+        }
+        self.check_granter(granter)?;
+        if private_key_fingerprint::<S>(granter_key)? != granter.key_fingerprint() {
+            return Err(Error::Operation("签名私钥与转授的接收方不符".to_string()));
+        }
+        let payload_digest = Sha256::digest(self.payload.as_bytes());
+        let entry = wrap_key::<S>(granter.key(), public_key, policy, (granter_key, granter.key_fingerprint()), &payload_digest)?;
+        if self.position(&entry.key_fingerprint).is_some() {
+            return Err(Error::Operation(format!("公钥 {} 已是该信封的接收方", entry.key_fingerprint)));
+        }
+        self.recipients.push(entry);
+        Ok(())
+    }
+
+    /// 删除指纹为 `key_fingerprint` 的接收方的封装条目，载荷密文保持不变
+    ///
+    /// `granter` 的要求与 [`add_recipient`](Self::add_recipient) 相同；不能删除最后一个接收方，
+    /// 也不能删除仍有经其转授的接收方的条目，否则这些接收方的授予链将无法验证。
+    /// 注意：被删除的接收方若此前已取得 DEK 或保存了旧信封，仍可解密这份载荷；需要彻底撤销时应重新加密载荷。
+    pub fn remove_recipient(&mut self, granter: &UnwrappedKey, key_fingerprint: &str) -> Result<(), Error> {
+        self.check_granter(granter)?;
+        let index = self.position(key_fingerprint)
+            .ok_or_else(|| Error::Operation(format!("公钥 {} 不是该信封的接收方", key_fingerprint)))?;
+        if self.recipients.len() == 1 {
+            return Err(Error::Operation("多接收方信封至少需要一个接收方".to_string()));
+        }
+        if self.recipients.iter().any(|entry| entry.granted_by == key_fingerprint) {
+            return Err(Error::Operation(format!("公钥 {} 仍有经其转授的接收方", key_fingerprint)));
+        }
+        self.recipients.remove(index);
+        Ok(())
+    }
+
+    fn position(&self, key_fingerprint: &str) -> Option<usize> {
+        self.recipients.iter().position(|entry| entry.key_fingerprint == key_fingerprint)
+    }
+
+    /// 确认 DEK 是从本信封解封的、其接收方仍在列表中，且该接收方可以转授
+    ///
+    /// 同一接收方可能出现在多个信封中，只比对指纹无法区分 DEK 的来源，因此还须比对载荷摘要。
+    fn check_granter(&self, granter: &UnwrappedKey) -> Result<(), Error> {
+        let payload_digest: [u8; 32] = Sha256::digest(self.payload.as_bytes()).into();
+        if granter.payload_digest != payload_digest || self.position(granter.key_fingerprint()).is_none() {
+            return Err(Error::Operation("数据加密密钥不是从该信封解封得到的".to_string()));
+        }
+        if !granter.policy().can_reshare() {
+            return Err(Error::Operation("只读接收方不能修改信封的接收方列表".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 从第 `index` 个条目沿授予方逐级验证签名，直到由 `sender` 签名的条目；中间的授予方必须可以转授
+    fn verify_grant_chain<S>(&self, index: usize, sender: &S::PublicKey, sender_fingerprint: &str) -> Result<(), Error>
+    where
+        S: AuthenticatedCryptoSystem,
+        Error: From<S::Error>,
+    {
+        let invalid = || Error::Operation("接收方条目的授予签名验证失败".to_string());
+        let payload_digest = Sha256::digest(self.payload.as_bytes());
+        let mut entry = &self.recipients[index];
+        // 每一步上移一级授予方，合法的链不会长于条目数
+        for _ in 0..self.recipients.len() {
+            if key_fingerprint(&entry.public_key) != entry.key_fingerprint {
+                return Err(invalid());
+            }
+            let signature = from_base64(&entry.signature).map_err(|_| invalid())?;
+            let data = entry.signed_data(&payload_digest);
+            if entry.granted_by == sender_fingerprint {
+                return if S::verify(sender, &data, &signature).unwrap_or(false) { Ok(()) } else { Err(invalid()) };
+            }
+            let granter = self.recipients.iter()
+                .find(|granter| granter.key_fingerprint == entry.granted_by && granter.policy.can_reshare())
+                .ok_or_else(invalid)?;
+            let granter_key = S::import_public_key(&granter.public_key).map_err(|_| invalid())?;
+            if !S::verify(&granter_key, &data, &signature).unwrap_or(false) {
+                return Err(invalid());
+            }
+            entry = granter;
+        }
+        Err(invalid())
+    }
+}
+
+/// 私钥对应公钥的指纹
+fn private_key_fingerprint<S>(private_key: &S::PrivateKey) -> Result<String, Error>
+where
+    S: AsymmetricCryptographicSystem,
+    Error: From<S::Error>,
+{
+    Ok(key_fingerprint(&S::export_public_key(&S::public_key_from_private(private_key)?)?))
+}
+
+/// 为 `public_key` 封装 DEK，并以授予方 `(私钥, 指纹)` 签名条目
+fn wrap_key<S>(
+    key: &AesGcmKey,
+    public_key: &S::PublicKey,
+    policy: RecipientPolicy,
+    (granter_key, granter_fingerprint): (&S::PrivateKey, &str),
+    payload_digest: &[u8],
+) -> Result<RecipientEntry, Error>
+where
+    S: AuthenticatedCryptoSystem,
+    Error: From<S::Error>,
+{
+    let exported = S::export_public_key(public_key)?;
+    let key_fingerprint = key_fingerprint(&exported);
+    let aad = RecipientEntry::wrap_aad(&key_fingerprint, policy);
+    let wrapped_key = S::encrypt(public_key, key.as_bytes(), Some(&aad))?.to_string();
+    let mut entry = RecipientEntry {
+        key_fingerprint,
+        public_key: exported,
+        policy,
+        wrapped_key,
+        granted_by: granter_fingerprint.to_string(),
+        signature: String::new(),
+    };
+    entry.signature = to_base64(&S::sign(granter_key, &entry.signed_data(payload_digest))?);
+    Ok(entry)
+}
+
+fn payload_aad(additional_data: Option<&[u8]>) -> Vec<u8> {
+    let additional_data = additional_data.unwrap_or_default();
+    let mut aad = Vec::with_capacity(PAYLOAD_AAD_LABEL.len() + additional_data.len());
+    aad.extend_from_slice(PAYLOAD_AAD_LABEL);
+    aad.extend_from_slice(additional_data);
+    aad
+}
+
+#[cfg(all(test, feature = "traditional", feature = "post-quantum"))]
+mod tests {
+    use super::*;
+
+    use crate::asymmetric::systems::hybrid::rsa_kyber::{RsaKyberCryptoSystem, RsaKyberPrivateKey, RsaKyberPublicKey};
+
+    fn hybrid_keys(count: usize) -> Vec<(RsaKyberPublicKey, RsaKyberPrivateKey)> {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        (0..count).map(|_| RsaKyberCryptoSystem::generate_keypair(&config).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_recipients_read_their_own_policy() {
+        let keys = hybrid_keys(4);
+        let (sender_pk, sender_sk) = &keys[3];
+        let envelope = MultiRecipientBuilder::<RsaKyberCryptoSystem>::new()
+            .add_recipient(&keys[0].0)
+            .add_recipient_with_policy(&keys[1].0, RecipientPolicy::Reshare)
+            .add_recipient_with_policy(&keys[2].0, RecipientPolicy::ReadOnly)
+            .seal(sender_sk, b"shared document", Some(b"doc-42"))
+            .unwrap();
+        assert_eq!(envelope.recipient_count(), 3);
+
+        let expected = [RecipientPolicy::ReadOnly, RecipientPolicy::Reshare, RecipientPolicy::ReadOnly];
+        for ((_, sk), policy) in keys.iter().zip(expected) {
+            let (plaintext, read_policy) = envelope.open::<RsaKyberCryptoSystem>(sk, sender_pk, Some(b"doc-42")).unwrap();
+            assert_eq!(plaintext, b"shared document");
+            assert_eq!(read_policy, policy);
+        }
+        assert!(envelope.unwrap_key::<RsaKyberCryptoSystem>(&keys[1].1, sender_pk).unwrap().policy().can_reshare());
+        assert!(envelope.open::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk, Some(b"doc-43")).is_err());
+        // 不是发送方签出的信封不被接受
+        assert!(matches!(envelope.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, &keys[0].0), Err(Error::Operation(_))));
+        assert!(matches!(envelope.unwrap_key::<RsaKyberCryptoSystem>(sender_sk, sender_pk), Err(Error::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_tampered_policy_breaks_unwrap() {
+        let keys = hybrid_keys(3);
+        let (sender_pk, sender_sk) = &keys[2];
+        let envelope = MultiRecipientBuilder::<RsaKyberCryptoSystem>::new()
+            .add_recipient(&keys[0].0)
+            .add_recipient_with_policy(&keys[1].0, RecipientPolicy::Reshare)
+            .seal(sender_sk, b"payload", None)
+            .unwrap();
+
+        // 只读接收方把自己的策略改为可转授
+        let json = serde_json::to_string(&envelope).unwrap();
+        let tampered = json.replacen("\"ReadOnly\"", "\"Reshare\"", 1);
+        assert_ne!(json, tampered);
+        let tampered: MultiRecipientEnvelope = serde_json::from_str(&tampered).unwrap();
+        assert!(tampered.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).is_err());
+        // 其他接收方不受影响
+        assert_eq!(tampered.open::<RsaKyberCryptoSystem>(&keys[1].1, sender_pk, None).unwrap().1, RecipientPolicy::Reshare);
+
+        // 把条目改挂到其他指纹上同样会失败
+        let mut relabelled = envelope.clone();
+        relabelled.recipients[0].key_fingerprint = relabelled.recipients[1].key_fingerprint.clone();
+        assert!(relabelled.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).is_err());
+    }
+
+    #[test]
+    fn test_read_only_recipient_cannot_upgrade_itself() {
+        let keys = hybrid_keys(3);
+        let (sender_pk, sender_sk) = &keys[2];
+        let envelope = MultiRecipientBuilder::<RsaKyberCryptoSystem>::new()
+            .add_recipient(&keys[0].0)
+            .seal(sender_sk, b"payload", None)
+            .unwrap();
+        let reader = envelope.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).unwrap();
+        let reader_fingerprint = reader.key_fingerprint().to_string();
+        let payload_digest = Sha256::digest(envelope.payload.as_bytes());
+
+        // 只读接收方用自己的私钥为自己签出可转授的条目，或冒充发送方的指纹
+        for granted_by in [reader_fingerprint.clone(), envelope.recipients[0].granted_by.clone()] {
+            let forged = wrap_key::<RsaKyberCryptoSystem>(
+                reader.key(), &keys[0].0, RecipientPolicy::Reshare, (&keys[0].1, &granted_by), &payload_digest,
+            ).unwrap();
+
+            // 伪造条目排在真实条目之前时被跳过，读到的仍是发送方授予的只读策略
+            let mut prepended = envelope.clone();
+            prepended.recipients.insert(0, forged.clone());
+            assert_eq!(prepended.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).unwrap().policy(), RecipientPolicy::ReadOnly);
+
+            // 伪造条目替换真实条目后无法解封
+            let mut replaced = envelope.clone();
+            replaced.recipients[0] = forged;
+            assert!(matches!(replaced.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk), Err(Error::Operation(_))));
+        }
+
+        // 只读接收方签出的条目也不能让第三方通过验证
+        let mut extended = envelope.clone();
+        extended.recipients.push(wrap_key::<RsaKyberCryptoSystem>(
+            reader.key(), &keys[1].0, RecipientPolicy::ReadOnly, (&keys[0].1, &reader_fingerprint), &payload_digest,
+        ).unwrap());
+        assert!(extended.open::<RsaKyberCryptoSystem>(&keys[1].1, sender_pk, None).is_err());
+    }
+
+    #[test]
+    fn test_add_and_remove_recipients() {
+        let keys = hybrid_keys(5);
+        let (sender_pk, sender_sk) = &keys[4];
+        let mut envelope = MultiRecipientBuilder::<RsaKyberCryptoSystem>::new()
+            .add_recipient_with_policy(&keys[0].0, RecipientPolicy::Reshare)
+            .add_recipient(&keys[1].0)
+            .add_recipient(&keys[2].0)
+            .seal(sender_sk, b"shared document", Some(b"doc-7"))
+            .unwrap();
+        let payload = envelope.payload.clone();
+        assert!(envelope.open::<RsaKyberCryptoSystem>(&keys[3].1, sender_pk, Some(b"doc-7")).is_err());
+
+        // 只读接收方不能修改接收方列表
+        let reader = envelope.unwrap_key::<RsaKyberCryptoSystem>(&keys[1].1, sender_pk).unwrap();
+        assert!(matches!(
+            envelope.add_recipient::<RsaKyberCryptoSystem>(&reader, &keys[1].1, &keys[3].0, RecipientPolicy::ReadOnly),
+            Err(Error::Operation(_))
+        ));
+
+        // 签名私钥必须属于转授的接收方
+        let owner = envelope.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).unwrap();
+        assert!(envelope.add_recipient::<RsaKyberCryptoSystem>(&owner, &keys[1].1, &keys[3].0, RecipientPolicy::ReadOnly).is_err());
+
+        envelope.add_recipient::<RsaKyberCryptoSystem>(&owner, &keys[0].1, &keys[3].0, RecipientPolicy::ReadOnly).unwrap();
+        assert_eq!(envelope.recipient_count(), 4);
+        assert_eq!(envelope.payload, payload);
+        let (plaintext, policy) = envelope.open::<RsaKyberCryptoSystem>(&keys[3].1, sender_pk, Some(b"doc-7")).unwrap();
+        assert_eq!((plaintext.as_slice(), policy), (b"shared document".as_ref(), RecipientPolicy::ReadOnly));
+        assert!(envelope.add_recipient::<RsaKyberCryptoSystem>(&owner, &keys[0].1, &keys[3].0, RecipientPolicy::Reshare).is_err());
+
+        // 仍有经其转授的接收方时不能删除授予方
+        let owner_fingerprint = owner.key_fingerprint().to_string();
+        assert!(envelope.remove_recipient(&owner, &owner_fingerprint).is_err());
+
+        let removed = envelope.recipient_fingerprints()[2].to_string();
+        envelope.remove_recipient(&owner, &removed).unwrap();
+        assert_eq!(envelope.recipient_count(), 3);
+        assert_eq!(envelope.payload, payload);
+        assert!(envelope.open::<RsaKyberCryptoSystem>(&keys[2].1, sender_pk, Some(b"doc-7")).is_err());
+        for index in [0, 1, 3] {
+            assert!(envelope.open::<RsaKyberCryptoSystem>(&keys[index].1, sender_pk, Some(b"doc-7")).is_ok());
+        }
+        assert!(envelope.remove_recipient(&owner, &removed).is_err());
+
+        // 其他信封的 DEK 不能用于修改本信封
+        let other = MultiRecipientBuilder::<RsaKyberCryptoSystem>::new()
+            .add_recipient_with_policy(&keys[2].0, RecipientPolicy::Reshare)
+            .seal(sender_sk, b"other", None)
+            .unwrap();
+        let foreign = other.unwrap_key::<RsaKyberCryptoSystem>(&keys[2].1, sender_pk).unwrap();
+        assert!(envelope.add_recipient::<RsaKyberCryptoSystem>(&foreign, &keys[2].1, &keys[2].0, RecipientPolicy::ReadOnly).is_err());
+    }
+
+    #[test]
+    fn test_dek_from_another_envelope_is_rejected() {
+        let keys = hybrid_keys(3);
+        let (sender_pk, sender_sk) = &keys[2];
+        let seal = |plaintext: &[u8]| {
+            MultiRecipientBuilder::<RsaKyberCryptoSystem>::new()
+                .add_recipient_with_policy(&keys[0].0, RecipientPolicy::Reshare)
+                .seal(sender_sk, plaintext, None)
+                .unwrap()
+        };
+        let envelope_a = seal(b"document a");
+        let mut envelope_b = seal(b"document b");
+
+        // 同一接收方在两个信封中都可转授，但 A 的 DEK 不能用于修改 B
+        let from_a = envelope_a.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).unwrap();
+        assert!(matches!(
+            envelope_b.add_recipient::<RsaKyberCryptoSystem>(&from_a, &keys[0].1, &keys[1].0, RecipientPolicy::ReadOnly),
+            Err(Error::Operation(_))
+        ));
+        let owner_fingerprint = from_a.key_fingerprint().to_string();
+        assert!(matches!(envelope_b.remove_recipient(&from_a, &owner_fingerprint), Err(Error::Operation(_))));
+        assert_eq!(envelope_b.recipient_count(), 1);
+
+        // 从 B 自身解封的 DEK 可以正常转授
+        let from_b = envelope_b.unwrap_key::<RsaKyberCryptoSystem>(&keys[0].1, sender_pk).unwrap();
+        envelope_b.add_recipient::<RsaKyberCryptoSystem>(&from_b, &keys[0].1, &keys[1].0, RecipientPolicy::ReadOnly).unwrap();
+        assert_eq!(envelope_b.open::<RsaKyberCryptoSystem>(&keys[1].1, sender_pk, None).unwrap().0, b"document b");
+    }
+}
@@ -0,0 +1,200 @@
+//! 预解析公钥
+//!
+//! 对同一接收方反复加密时，每次调用 `encrypt` 都会重新解析DER/Base64编码的公钥。
+//! `PreparedPublicKey` 只解析一次并缓存解析结果，之后的加密直接复用。
+//! 输出的密文与对应系统的 `encrypt` 完全兼容，可用原有的 `decrypt` 解密。
+
+use crate::common::errors::Error;
+use crate::common::utils::Base64String;
+#[cfg(feature = "traditional")]
+use crate::common::utils::RsaPadding;
+#[cfg(feature = "post-quantum")]
+use crate::asymmetric::systems::post_quantum::kyber::{KyberCryptoSystem, KyberPublicKeyWrapper, ParsedKyberPublicKey};
+#[cfg(feature = "traditional")]
+use crate::asymmetric::systems::traditional::rsa::{RsaCryptoSystem, RsaPublicKeyWrapper};
+#[cfg(all(feature = "traditional", feature = "post-quantum"))]
+use crate::asymmetric::systems::hybrid::rsa_kyber::{RsaKyberCryptoSystem, RsaKyberPublicKey};
+
+#[derive(Clone)]
+enum PreparedInner {
+    #[cfg(feature = "traditional")]
+    Rsa(rsa::RsaPublicKey, RsaPadding),
+    #[cfg(feature = "post-quantum")]
+    Kyber(ParsedKyberPublicKey),
+    #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+    RsaKyber(ParsedKyberPublicKey),
+}
+
+/// 已解析并缓存的公钥，用于对同一接收方的重复加密
+#[derive(Clone)]
+pub struct PreparedPublicKey {
+    inner: PreparedInner,
+}
+
+impl PreparedPublicKey {
+    /// 从RSA公钥创建预解析公钥，以 OAEP 填充加密，与 `RsaCryptoSystem::encrypt` 一致
+    #[cfg(feature = "traditional")]
+    pub fn from_rsa(public_key: &RsaPublicKeyWrapper) -> Result<Self, Error> {
+        Self::from_rsa_with_padding(public_key, RsaPadding::Oaep)
+    }
+
+    /// 从RSA公钥创建按 `padding` 加密的预解析公钥
+    ///
+    /// 引擎按 `CryptoConfig::rsa_padding` 加密；传入 `config.rsa_padding` 即可得到与
+    /// `RsaCryptoSystem::encrypt_with_config` 相同格式的密文。PKCS#1 v1.5 的安全风险见 [`RsaPadding`]。
+    #[cfg(feature = "traditional")]
+    pub fn from_rsa_with_padding(public_key: &RsaPublicKeyWrapper, padding: RsaPadding) -> Result<Self, Error> {
+        let parsed = RsaCryptoSystem::parse_public_key(public_key)?;
+        Ok(Self { inner: PreparedInner::Rsa(parsed, padding) })
+    }
+
+    /// 从Kyber公钥创建预解析公钥
+    #[cfg(feature = "post-quantum")]
+    pub fn from_kyber(public_key: &KyberPublicKeyWrapper) -> Result<Self, Error> {
+        let parsed = KyberCryptoSystem::parse_public_key(public_key)?;
+        Ok(Self { inner: PreparedInner::Kyber(parsed) })
+    }
+
+    /// 从RSA-Kyber混合公钥创建预解析公钥
+    ///
+    /// 混合系统的无签名加密只使用Kyber部分，因此只缓存Kyber公钥。
+    #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+    pub fn from_rsa_kyber(public_key: &RsaKyberPublicKey) -> Result<Self, Error> {
+        let parsed = KyberCryptoSystem::parse_public_key(&public_key.kyber_public_key)?;
+        Ok(Self { inner: PreparedInner::RsaKyber(parsed) })
+    }
+
+    /// 使用缓存的公钥加密数据
+    ///
+    /// RSA 不支持附加认证数据，传入的 `additional_data` 会被忽略，
+    /// 与 `RsaCryptoSystem::encrypt` 的行为一致；填充方案取创建时指定的值。
+    pub fn encrypt(&self, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<Base64String, Error> {
+        match &self.inner {
+            #[cfg(feature = "traditional")]
+            PreparedInner::Rsa(key, padding) => {
+                let _ = additional_data;
+                RsaCryptoSystem::encrypt_with_parsed_key(key, plaintext, *padding)
+            }
+            #[cfg(feature = "post-quantum")]
+            PreparedInner::Kyber(key) => {
+                KyberCryptoSystem::encrypt_with_parsed_key(key, plaintext, additional_data)
+            }
+            #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+            PreparedInner::RsaKyber(key) => {
+                RsaKyberCryptoSystem::encrypt_with_parsed_key(key, plaintext, additional_data)
+            }
+        }
+    }
+
+    /// 返回缓存公钥所属的算法名称
+    pub fn algorithm(&self) -> &'static str {
+        match &self.inner {
+            #[cfg(feature = "traditional")]
+            PreparedInner::Rsa(..) => "RSA",
+            #[cfg(feature = "post-quantum")]
+            PreparedInner::Kyber(_) => "Kyber",
+            #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+            PreparedInner::RsaKyber(_) => "RSA-Kyber",
+        }
+    }
+}
+
+impl std::fmt::Debug for PreparedPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreparedPublicKey")
+            .field("algorithm", &self.algorithm())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+    use crate::common::utils::CryptoConfig;
+
+    #[test]
+    #[cfg(feature = "traditional")]
+    fn test_prepared_rsa_roundtrip() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let prepared = PreparedPublicKey::from_rsa(&pk).unwrap();
+        assert_eq!(prepared.algorithm(), "RSA");
+
+        let ciphertext = prepared.encrypt(b"prepared rsa", None).unwrap();
+        let decrypted = RsaCryptoSystem::decrypt(&sk, &ciphertext.to_string(), None).unwrap();
+        assert_eq!(decrypted, b"prepared rsa");
+    }
+
+    #[test]
+    #[cfg(feature = "post-quantum")]
+    fn test_prepared_kyber_roundtrip() {
+        let config = CryptoConfig::default();
+        let (pk, sk) = KyberCryptoSystem::generate_keypair(&config).unwrap();
+        let prepared = PreparedPublicKey::from_kyber(&pk).unwrap();
+
+        let aad = b"header";
+        let ciphertext = prepared.encrypt(b"prepared kyber", Some(aad)).unwrap();
+        let decrypted = KyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), Some(aad)).unwrap();
+        assert_eq!(decrypted, b"prepared kyber");
+
+        // 错误的附加数据应导致认证失败
+        assert!(KyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), Some(b"other")).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+    fn test_prepared_rsa_kyber_roundtrip() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let prepared = PreparedPublicKey::from_rsa_kyber(&pk).unwrap();
+
+        let ciphertext = prepared.encrypt(b"prepared hybrid", Some(b"aad")).unwrap();
+        let decrypted = RsaKyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), Some(b"aad")).unwrap();
+        assert_eq!(decrypted, b"prepared hybrid");
+    }
+
+    #[test]
+    #[cfg(feature = "traditional")]
+    fn test_prepared_rsa_invalid_key() {
+        let invalid = RsaPublicKeyWrapper(vec![0u8; 16]);
+        assert!(PreparedPublicKey::from_rsa(&invalid).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "traditional")]
+    fn test_prepared_rsa_repeated_encryption() {
+        // 对同一公钥重复加密，预解析的公钥每次都产生可解密的新密文
+        const ROUNDS: usize = 50;
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let message = b"repeated message";
+
+        let prepared = PreparedPublicKey::from_rsa(&pk).unwrap();
+        let ciphertexts: Vec<String> = (0..ROUNDS).map(|_| prepared.encrypt(message, None).unwrap().to_string()).collect();
+        assert_ne!(ciphertexts[0], ciphertexts[1]);
+        for ciphertext in [&ciphertexts[0], &ciphertexts[ROUNDS - 1]] {
+            assert_eq!(RsaCryptoSystem::decrypt(&sk, ciphertext, None).unwrap(), message);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "traditional")]
+    fn test_prepared_rsa_honours_configured_padding() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+
+        for padding in [RsaPadding::Oaep, RsaPadding::Pkcs1v15] {
+            let config = CryptoConfig { rsa_padding: padding, ..config.clone() };
+            let prepared = PreparedPublicKey::from_rsa_with_padding(&pk, config.rsa_padding).unwrap();
+            let ciphertext = prepared.encrypt(b"padded", None).unwrap().to_string();
+
+            // 与 encrypt_with_config 的密文格式相同，按配置的填充解密
+            let expected = RsaCryptoSystem::encrypt_with_config(&pk, b"padded", None, &config).unwrap().to_string();
+            assert_eq!(ciphertext.len(), expected.len(), "{:?}", padding);
+            assert_eq!(RsaCryptoSystem::decrypt_with_config(&sk, &ciphertext, None, &config).unwrap(), b"padded");
+            let other = if padding == RsaPadding::Oaep { RsaPadding::Pkcs1v15 } else { RsaPadding::Oaep };
+            assert!(RsaCryptoSystem::decrypt_with_padding(&sk, &ciphertext, other).is_err(), "{:?}", padding);
+        }
+    }
+}
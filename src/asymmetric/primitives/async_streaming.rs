@@ -1,7 +1,7 @@
 #![cfg(feature = "async-engine")]
 
 use crate::common::errors::Error;
-use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::common::streaming::{StreamingConfig, StreamingResult, MAX_FRAME_PREALLOC};
 use crate::asymmetric::traits::AsymmetricCryptographicSystem;
 use std::marker::PhantomData;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
@@ -60,6 +60,7 @@ where
         };
 
         loop {
+            self.config.check_cancelled()?;
             let read_bytes = self.reader.read(&mut buffer).await.map_err(Error::Io)?;
             if read_bytes == 0 {
                 break;
@@ -163,12 +164,18 @@ where
         };
 
         while self.reader.read_exact(&mut length_buffer).await.is_ok() {
+            self.config.check_cancelled()?;
             let length = u32::from_le_bytes(length_buffer) as usize;
-            let mut ciphertext_buffer = vec![0u8; length];
-            self.reader
-                .read_exact(&mut ciphertext_buffer)
+            // 缓冲区随实际读到的数据增长，伪造的长度前缀不会触发巨大分配
+            let mut ciphertext_buffer = Vec::with_capacity(length.min(MAX_FRAME_PREALLOC));
+            (&mut self.reader)
+                .take(length as u64)
+                .read_to_end(&mut ciphertext_buffer)
                 .await
                 .map_err(Error::Io)?;
+            if ciphertext_buffer.len() != length {
+                return Err(Error::Io(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "帧数据不完整")));
+            }
 
             let ciphertext = String::from_utf8(ciphertext_buffer)
                 .map_err(|e| Error::Format(format!("Invalid UTF-8 ciphertext: {}", e)))?;
@@ -5,7 +5,7 @@ use crate::common::errors::Error;
 use std::sync::Arc;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
-use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::common::streaming::{read_frame, StreamingConfig, StreamingResult};
 
 /// 流式加密器，将输入流数据加密并写入输出流
 pub struct StreamingEncryptor<'a, C: AsymmetricCryptographicSystem, R: Read, W: Write> 
@@ -193,8 +193,7 @@ where
             let block_size = u32::from_le_bytes(length_buffer) as usize;
             
             // 读取密文块
-            let mut ciphertext_buffer = vec![0u8; block_size];
-            self.reader.read_exact(&mut ciphertext_buffer)
+            let ciphertext_buffer = read_frame(&mut self.reader, block_size)
                 .map_err(Error::Io)?;
                 
             // 将字节解析为字符串
@@ -361,8 +360,7 @@ where
             Err(e) => return Err(Error::Io(e)),
         }
         let size = u32::from_le_bytes(len_buf) as usize;
-        let mut cipher_buf = vec![0u8; size];
-        reader.read_exact(&mut cipher_buf).map_err(Error::Io)?;
+        let cipher_buf = read_frame(&mut reader, size).map_err(Error::Io)?;
         let ciphertext = String::from_utf8(cipher_buf)
             .map_err(|e| Error::Format(format!("无效的UTF-8密文: {}", e)))?;
         chunks.push(ciphertext);
@@ -532,4 +530,16 @@ mod tests {
         assert_eq!(dec_result.bytes_processed, 0);
         assert_eq!(decrypted_dest.into_inner().as_slice(), b"");
     }
+
+    #[test]
+    fn test_encrypt_to_sink_roundtrip() {
+        let (pk, sk, _) = get_test_keys_and_config();
+        let mut sink = Vec::new();
+        let written = RsaKyberCryptoSystem::encrypt_to(&pk, b"hybrid pipeline", Some(b"aad"), &mut sink).unwrap();
+        assert_eq!(written, sink.len());
+        let returned = RsaKyberCryptoSystem::encrypt(&pk, b"hybrid pipeline", Some(b"aad")).unwrap().to_string();
+        assert_eq!(sink.len(), returned.len());
+        let encoded = std::str::from_utf8(&sink).unwrap();
+        assert_eq!(RsaKyberCryptoSystem::decrypt(&sk, encoded, Some(b"aad")).unwrap(), b"hybrid pipeline");
+    }
 } 
\ No newline at end of file
@@ -4,7 +4,10 @@ use uuid::Uuid;
 use crate::{AsymmetricCryptographicSystem, Error};
 use crate::common::utils::CryptoConfig;
 use crate::common::traits::KeyStatus;
-use crate::rotation::{KeyMetadata, KeyPairData, KeyStorage, RotationPolicy};
+use crate::rotation::{verify_audit_chain, AuditLog, KeyMetadata, KeyPairData, KeyStorage, RotationEvent, RotationEventKind, RotationPolicy};
+use crate::common::traits::AuthenticatedCryptoSystem;
+use crate::common::utils::key_fingerprint;
+use crate::asymmetric::traits::KeyProvider;
 
 /// 密钥轮换管理器
 pub struct KeyRotationManager<T: AsymmetricCryptographicSystem> {
@@ -18,6 +21,8 @@ pub struct KeyRotationManager<T: AsymmetricCryptographicSystem> {
     rotation_policy: RotationPolicy,
     /// 密钥名称前缀
     key_prefix: String,
+    /// 轮换审计日志（仅保存在内存中）
+    audit_log: AuditLog,
 }
 
 impl<T: AsymmetricCryptographicSystem> KeyRotationManager<T> 
@@ -36,6 +41,7 @@ where
             key_storage,
             rotation_policy,
             key_prefix: key_prefix.to_string(),
+            audit_log: AuditLog::default(),
         }
     }
     
@@ -145,6 +151,7 @@ where
         };
         
         // 更新现有主密钥状态为轮换中
+        let previous_key_id = self.primary_key.as_ref().map(|(_, _, old_metadata)| old_metadata.id.clone());
         if let Some((pub_key, priv_key, mut old_metadata)) = self.primary_key.take() {
             old_metadata.status = KeyStatus::Rotating;
             
@@ -161,6 +168,7 @@ where
         let key_name = format!("{}-{}", self.key_prefix, metadata.id);
         let key_data = self.serialize_key_pair(&new_public_key, &new_private_key)?;
         self.key_storage.save_key(&key_name, &metadata, &key_data)?;
+        self.audit_log.record(RotationEventKind::RotationStarted, &metadata, previous_key_id)?;
         
         // 设置新主密钥
         self.primary_key = Some((new_public_key, new_private_key, metadata));
@@ -186,6 +194,7 @@ where
             let key_name = format!("{}-{}", self.key_prefix, metadata.id);
             // 删除已过期密钥文件
             let _ = self.key_storage.delete_key(&key_name);
+            self.audit_log.record(RotationEventKind::RotationCompleted, &metadata, None)?;
         }
         
         Ok(())
@@ -226,6 +235,40 @@ where
         self.secondary_keys.iter().map(|(pub_key, priv_key, metadata)| (pub_key, priv_key, metadata)).collect()
     }
     
+    /// 本管理器记录的轮换审计事件，按发生顺序排列
+    pub fn audit_log(&self) -> Vec<RotationEvent> {
+        self.audit_log.events().to_vec()
+    }
+
+    /// 校验审计日志的哈希链是否完整，历史事件被修改、删除或重排时返回 `Ok(false)`
+    pub fn verify_audit_chain(&self) -> Result<bool, Error> {
+        Ok(verify_audit_chain(self.audit_log.events()))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn audit_events_mut(&mut self) -> &mut Vec<RotationEvent> {
+        self.audit_log.events_mut()
+    }
+
+    /// 获取密钥名称前缀
+    #[cfg(feature = "secure-storage")]
+    pub(crate) fn key_prefix(&self) -> &str {
+        &self.key_prefix
+    }
+
+    /// 导出当前持有的全部密钥记录：(存储名称, 元数据, 序列化的密钥对)
+    #[cfg(feature = "secure-storage")]
+    pub(crate) fn export_key_records(&self) -> Result<Vec<(String, KeyMetadata, Vec<u8>)>, Error> {
+        self.primary_key.iter()
+            .chain(self.secondary_keys.iter())
+            .map(|(pub_key, priv_key, metadata)| {
+                let key_name = format!("{}-{}", self.key_prefix, metadata.id);
+                let key_data = self.serialize_key_pair(pub_key, priv_key)?;
+                Ok((key_name, metadata.clone(), key_data))
+            })
+            .collect()
+    }
+    
     // 私有方法
     
     /// 创建新的主密钥
@@ -258,6 +301,7 @@ where
         let key_name = format!("{}-{}", self.key_prefix, id);
         let key_data = self.serialize_key_pair(&public_key, &private_key)?;
         self.key_storage.save_key(&key_name, &metadata, &key_data)?;
+        self.audit_log.record(RotationEventKind::KeyCreated, &metadata, None)?;
         
         // 设置为主密钥
         self.primary_key = Some((public_key, private_key, metadata));
@@ -326,4 +370,70 @@ where
             
         Ok((public_key, private_key))
     }
-}
\ No newline at end of file
+}
+
+impl<T> KeyRotationManager<T>
+where
+    T: AuthenticatedCryptoSystem,
+    T::Error: std::error::Error + 'static,
+    T::PrivateKey: Send + Sync + 'static,
+{
+    /// 使用 `signing_key` 对之后记录的每条审计事件签名
+    pub fn with_audit_signer(mut self, signing_key: T::PrivateKey) -> Self {
+        self.audit_log.set_signer(Box::new(move |data| {
+            T::sign(&signing_key, data).map_err(|e| Error::Operation(format!("签名审计事件失败: {}", e)))
+        }));
+        self
+    }
+
+    /// 校验哈希链，并确认每条事件都带有 `verifier` 可验证的签名
+    pub fn verify_audit_signatures(&self, verifier: &T::PublicKey) -> Result<bool, Error> {
+        if !verify_audit_chain(self.audit_log.events()) {
+            return Ok(false);
+        }
+        for event in self.audit_log.events() {
+            let Some(signature) = &event.signature else {
+                return Ok(false);
+            };
+            let signature = crate::common::utils::from_base64(signature)?;
+            let valid = T::verify(verifier, event.hash.as_bytes(), &signature)
+                .map_err(|e| Error::Operation(format!("验证审计事件签名失败: {}", e)))?;
+            if !valid {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<T: AsymmetricCryptographicSystem> KeyProvider<T> for KeyRotationManager<T>
+where
+    Error: From<T::Error>,
+{
+    /// 在主密钥与次要密钥中按元数据版本号与公钥指纹查找
+    ///
+    /// 指纹不匹配时返回 `Error::KeyMismatch`，其中 `provided` 为第一个候选密钥（未指定版本时即主密钥）的指纹。
+    fn key_pair(&self, fingerprint: Option<&str>, key_version: Option<u32>) -> Result<(&T::PublicKey, &T::PrivateKey), Error> {
+        let candidates = self.primary_key.iter().chain(&self.secondary_keys)
+            .filter(|(_, _, metadata)| key_version.is_none_or(|version| metadata.version == version));
+        let mut primary_fingerprint = None;
+        for (public_key, private_key, _) in candidates {
+            let Some(expected) = fingerprint else {
+                return Ok((public_key, private_key));
+            };
+            let actual = key_fingerprint(&T::export_public_key(public_key)?);
+            if actual == expected {
+                return Ok((public_key, private_key));
+            }
+            primary_fingerprint.get_or_insert(actual);
+        }
+        match (fingerprint, key_version) {
+            (Some(expected), _) => Err(Error::KeyMismatch {
+                expected: expected.to_string(),
+                provided: primary_fingerprint.unwrap_or_default(),
+            }),
+            (None, Some(version)) => Err(Error::Key(format!("没有版本为 {} 的密钥", version))),
+            (None, None) => Err(Error::Key("没有可用的密钥".to_string())),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 //! 算法系统集合
 //!
 //! 包含传统、后量子和混合加密算法实现
+//!
+//! 匿名发送方的密封盒（`seal_anonymous`/`open_anonymous`）由 X25519 与 Kyber 系统提供。
 #[cfg(feature = "traditional")]
 pub mod traditional;
 #[cfg(feature = "post-quantum")]
@@ -4,7 +4,8 @@
 
 use aes_gcm::KeyInit;
 use crate::common::errors::Error;
-use crate::asymmetric::systems::post_quantum::kyber::{KyberCryptoSystem, KyberPrivateKeyWrapper, KyberPublicKeyWrapper};
+use crate::asymmetric::key_kind::{expect_key_kind, KeyKind};
+use crate::asymmetric::systems::post_quantum::kyber::{KyberCryptoSystem, KyberPrivateKeyWrapper, KyberPublicKeyWrapper, ParsedKyberPublicKey};
 use crate::asymmetric::systems::traditional::rsa::{RsaCryptoSystem, RsaPrivateKeyWrapper, RsaPublicKeyWrapper};
 use crate::common::traits::AuthenticatedCryptoSystem;
 use aes_gcm::aead::Aead;
@@ -14,17 +15,21 @@ use aes_gcm::{Aes256Gcm, Nonce};
 #[cfg(feature = "chacha")]
 #[allow(unused_imports)]
 use chacha20poly1305::{aead::generic_array::GenericArray, aead::{Aead as ChaAead, AeadCore as ChaAeadCore, KeyInit as ChaKeyInit}, ChaCha20Poly1305, Nonce as ChaNonce};
-use rsa::rand_core::{OsRng, RngCore};
+use rsa::rand_core::RngCore;
+use crate::common::rng::SystemRng;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 #[cfg(feature = "async-engine")]
 use crate::asymmetric::traits::AsyncStreamingSystem;
-#[cfg(feature = "async-engine")]
-use crate::common::streaming::StreamingConfig;
+use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+use crate::symmetric::traits::SymmetricSyncStreamingSystem;
+use std::io::{Read, Write};
 #[cfg(feature = "async-engine")]
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use crate::asymmetric::traits::AsymmetricCryptographicSystem;
-use crate::common::utils::{from_base64, to_base64, Base64String, CryptoConfig};
+use crate::common::utils::{from_base64, to_base64, Base64String, CryptoConfig, RsaPadding};
+use zeroize::{Zeroize, Zeroizing};
 // --- 密钥结构 ---
 
 /// 混合公钥，包含用于签名的RSA公钥和用于密钥封装的Kyber公钥。
@@ -34,6 +39,13 @@ pub struct RsaKyberPublicKey {
     pub kyber_public_key: KyberPublicKeyWrapper,
 }
 
+impl RsaKyberPublicKey {
+    /// 估算混合公钥的经典安全强度（比特），取RSA与Kyber两部分中较弱的一方
+    pub fn security_bits(&self) -> Result<u32, Error> {
+        Ok(self.rsa_public_key.security_bits()?.min(self.kyber_public_key.security_bits()?))
+    }
+}
+
 /// 混合私钥，包含用于签名的RSA私钥和用于密钥封装的Kyber私钥。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RsaKyberPrivateKey {
@@ -41,65 +53,169 @@ pub struct RsaKyberPrivateKey {
     pub kyber_private_key: KyberPrivateKeyWrapper,
 }
 
-// --- 加密系统实现 ---
-
-/// RSA-Kyber混合加密系统。
-pub struct RsaKyberCryptoSystem;
-
-impl AsymmetricCryptographicSystem for RsaKyberCryptoSystem {
-    type PublicKey = RsaKyberPublicKey;
-    type PrivateKey = RsaKyberPrivateKey;
-    type Error = Error;
-    type CiphertextOutput = Base64String;
+impl RsaKyberPrivateKey {
+    /// 立即清零并丢弃两半私钥，不必等到离开作用域
+    pub fn zeroize_now(mut self) {
+        self.zeroize();
+    }
 
-    fn generate_keypair(config: &CryptoConfig) -> Result<(Self::PublicKey, Self::PrivateKey), Self::Error> {
-        let (rsa_pk, rsa_sk) = RsaCryptoSystem::generate_keypair(config)?;
-        let (kyber_pk, kyber_sk) = KyberCryptoSystem::generate_keypair(config)?;
-        
-        let public_key = Self::PublicKey { rsa_public_key: rsa_pk, kyber_public_key: kyber_pk };
-        let private_key = Self::PrivateKey { rsa_private_key: rsa_sk, kyber_private_key: kyber_sk };
-        
-        Ok((public_key, private_key))
+    /// 任一半私钥被清零后整把混合私钥即不可用
+    pub fn is_zeroized(&self) -> bool {
+        self.rsa_private_key.is_zeroized() || self.kyber_private_key.is_zeroized()
     }
+}
 
-    fn export_public_key(pk: &Self::PublicKey) -> Result<String, Self::Error> {
-        serde_json::to_string(pk).map_err(Into::into)
+impl Zeroize for RsaKyberPrivateKey {
+    fn zeroize(&mut self) {
+        self.rsa_private_key.zeroize();
+        self.kyber_private_key.zeroize();
     }
+}
 
-    fn export_private_key(sk: &Self::PrivateKey) -> Result<String, Self::Error> {
-        serde_json::to_string(sk).map_err(Into::into)
+/// 重随机化外层使用的关联数据标签，用于与调用方的明文层区分
+const RERANDOMIZE_LAYER_AAD: &[u8] = b"seal-kit-rsa-kyber-rerandomize-layer-v1";
+
+/// 密文格式版本字节：该格式的 DEM 密钥由传输密钥与KEM密文派生
+///
+/// 旧格式没有版本字节，直接以KEM密文的Base64文本开头，DEM 密钥就是传输密钥本身；
+/// 版本字节不是Base64字符，两种格式不会混淆。
+pub(crate) const FORMAT_VERSION: u8 = 0x02;
+
+/// DEM 密钥派生的域分隔标签
+const DEM_KEY_LABEL: &[u8] = b"seal-kit-rsa-kyber-dem-key-v1";
+
+/// DEM 使用的 Nonce 长度（AES-GCM 与 ChaCha20-Poly1305 均为12字节）
+const DEM_NONCE_SIZE: usize = 12;
+
+/// 解密时允许剥离的最大重随机化层数
+const MAX_RERANDOMIZE_LAYERS: usize = 32;
+
+/// 单次KEM流式格式的魔数与版本
+const STREAM_MAGIC: &[u8; 4] = b"SKHS";
+const STREAM_VERSION: u8 = 1;
+
+/// 流式 DEM 密钥派生的域分隔标签，与单条消息的 DEM 密钥互不相同
+const STREAM_DEM_KEY_LABEL: &[u8] = b"seal-kit-rsa-kyber-stream-dem-key-v1";
+
+/// 流头部中KEM密文的长度上限，防止伪造的长度字段触发超大分配
+const MAX_STREAM_KEM_SIZE: usize = 64 * 1024;
+
+/// 单层KEM-DEM解密的结果
+enum DecryptedLayer {
+    /// 调用方的明文
+    Plaintext(Vec<u8>),
+    /// 重随机化外层包裹的内层密文
+    Inner(Vec<u8>),
+}
+
+/// 原始密文的组成部分：(是否带版本字节, KEM密文, Nonce, DEM密文)
+type CiphertextParts<'a> = (bool, &'a [u8], &'a [u8], &'a [u8]);
+
+/// 将原始密文拆分为 (是否带版本字节, KEM密文, Nonce, DEM密文)，同时接受没有版本字节的旧格式
+fn split_ciphertext(combined: &[u8]) -> Result<CiphertextParts<'_>, Error> {
+    let delim = b"::";
+    let (versioned, combined) = match combined.split_first() {
+        Some((&FORMAT_VERSION, rest)) => (true, rest),
+        _ => (false, combined),
+    };
+    // 查找第一个分隔符，将KEM部分与Nonce部分分开
+    let first_pos = combined.windows(delim.len())
+        .position(|window| window == delim)
+        .ok_or_else(|| Error::Format("密文格式错误：缺少KEM-Nonce分隔符".to_string()))?;
+    let kem_part = &combined[..first_pos];
+    // 跳过第一个分隔符；Nonce 为定长随机字节，可能包含分隔符，因此按长度切分
+    let rest = &combined[first_pos + delim.len()..];
+    if rest.len() < DEM_NONCE_SIZE + delim.len() || &rest[DEM_NONCE_SIZE..DEM_NONCE_SIZE + delim.len()] != delim {
+        return Err(Error::Format("密文格式错误：缺少Nonce-DEM分隔符".to_string()));
     }
+    Ok((versioned, kem_part, &rest[..DEM_NONCE_SIZE], &rest[DEM_NONCE_SIZE + delim.len()..]))
+}
 
-    fn import_public_key(pk_str: &str) -> Result<Self::PublicKey, Self::Error> {
-        serde_json::from_str(pk_str).map_err(Into::into)
+/// 由Kyber传输的密钥与KEM密文本身派生DEM密钥
+///
+/// DEM 密钥同时依赖解封得到的密钥与完整的KEM密文，缺失、置零或替换KEM部分都会得到不同的密钥，
+/// AEAD 认证随之失败，不存在绕过后量子层的降级解密路径。
+fn derive_dem_key(transported_key: &[u8], kem_part: &[u8]) -> Zeroizing<[u8; 32]> {
+    derive_dem_key_with_label(DEM_KEY_LABEL, transported_key, kem_part)
+}
+
+fn derive_dem_key_with_label(label: &[u8], transported_key: &[u8], kem_part: &[u8]) -> Zeroizing<[u8; 32]> {
+    let digest = Sha256::new()
+        .chain_update(label)
+        .chain_update((transported_key.len() as u64).to_be_bytes())
+        .chain_update(transported_key)
+        .chain_update(kem_part)
+        .finalize();
+    Zeroizing::new(digest.into())
+}
+
+// --- 加密系统实现 ---
+
+/// RSA-Kyber混合加密系统。
+pub struct RsaKyberCryptoSystem;
+
+impl RsaKyberCryptoSystem {
+    /// 将已有的RSA公钥与Kyber公钥组合为混合公钥，仅用于加密与验签。
+    pub fn compose_public_key(
+        rsa_public_key: RsaPublicKeyWrapper,
+        kyber_public_key: KyberPublicKeyWrapper,
+    ) -> Result<RsaKyberPublicKey, Error> {
+        RsaCryptoSystem::parse_public_key(&rsa_public_key)?;
+        KyberCryptoSystem::parse_public_key(&kyber_public_key)?;
+        Ok(RsaKyberPublicKey { rsa_public_key, kyber_public_key })
     }
 
-    fn import_private_key(sk_str: &str) -> Result<Self::PrivateKey, Self::Error> {
-        serde_json::from_str(sk_str).map_err(Into::into)
+    /// 将已有的RSA密钥对与Kyber密钥对组合为混合密钥对。
+    ///
+    /// 会校验RSA公钥与私钥匹配，并通过一次封装/解封确认Kyber公私钥匹配。
+    pub fn compose_keypair(
+        rsa_public_key: RsaPublicKeyWrapper,
+        rsa_private_key: RsaPrivateKeyWrapper,
+        kyber_public_key: KyberPublicKeyWrapper,
+        kyber_private_key: KyberPrivateKeyWrapper,
+    ) -> Result<(RsaKyberPublicKey, RsaKyberPrivateKey), Error> {
+        let public_key = Self::compose_public_key(rsa_public_key, kyber_public_key)?;
+
+        if RsaCryptoSystem::derive_public_key(&rsa_private_key)? != public_key.rsa_public_key {
+            return Err(Error::Key("RSA公钥与私钥不匹配".to_string()));
+        }
+
+        let mut probe = [0u8; 32];
+        SystemRng.fill_bytes(&mut probe);
+        let probe_ciphertext = KyberCryptoSystem::encrypt(&public_key.kyber_public_key, &probe, None)?;
+        match KyberCryptoSystem::decrypt(&kyber_private_key, &probe_ciphertext.to_string(), None) {
+            Ok(decrypted) if decrypted == probe => {}
+            _ => return Err(Error::Key("Kyber公钥与私钥不匹配".to_string())),
+        }
+
+        let private_key = RsaKyberPrivateKey { rsa_private_key, kyber_private_key };
+        Ok((public_key, private_key))
     }
 
-    /// 执行无签名的KEM-DEM加密。
-    fn encrypt(
-        public_key: &Self::PublicKey,
+    /// 使用已解析的Kyber公钥执行无签名的KEM-DEM加密。
+    pub(crate) fn encrypt_with_parsed_key(
+        kyber_public_key: &ParsedKyberPublicKey,
         plaintext: &[u8],
         additional_data: Option<&[u8]>,
-    ) -> Result<Self::CiphertextOutput, Self::Error> {
-        // 1. 生成一个一次性的AES-256密钥。
-        let mut aes_key = [0u8; 32];
-        OsRng.fill_bytes(&mut aes_key);
+    ) -> Result<Base64String, Error> {
+        // 1. 生成一个一次性的传输密钥。
+        let mut transported_key = Zeroizing::new([0u8; 32]);
+        SystemRng.fill_bytes(transported_key.as_mut());
 
-        // 2. KEM: 使用Kyber公钥封装（加密）AES密钥。
-        let kem_ciphertext = KyberCryptoSystem::encrypt(&public_key.kyber_public_key, &aes_key, None)?;
+        // 2. KEM: 使用Kyber公钥封装（加密）传输密钥，并由其与KEM密文派生DEM密钥。
+        let kem_ciphertext = KyberCryptoSystem::encrypt_with_parsed_key(kyber_public_key, transported_key.as_ref(), None)?;
+        let kem_str = kem_ciphertext.to_string();
+        let dem_key = derive_dem_key(transported_key.as_ref(), kem_str.as_bytes());
 
-        // 3. DEM: 使用AES密钥加密实际数据。
+        // 3. DEM: 使用派生的密钥加密实际数据。
         #[cfg(feature = "chacha")]
-        let cipher = ChaCha20Poly1305::new((&aes_key).into());
+        let cipher = ChaCha20Poly1305::new(dem_key.as_ref().into());
         #[cfg(not(feature = "chacha"))]
-        let cipher = Aes256Gcm::new(&aes_key.into());
+        let cipher = Aes256Gcm::new(dem_key.as_ref().into());
         #[cfg(feature = "chacha")]
-        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut SystemRng);
         #[cfg(not(feature = "chacha"))]
-        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let nonce = Aes256Gcm::generate_nonce(&mut SystemRng);
 
         use aes_gcm::aead::Payload;
         let payload = Payload {
@@ -109,9 +225,9 @@ impl AsymmetricCryptographicSystem for RsaKyberCryptoSystem {
         let dem_ciphertext = cipher.encrypt(&nonce, payload)
             .map_err(|e| Error::Operation(format!("AEAD 加密失败: {}", e)))?;
         
-        // 4. 将 KEM 密文的 Base64 ASCII 与 DEM 密文及 Nonce 组合
-        let kem_str = kem_ciphertext.to_string();
+        // 4. 将版本字节、KEM 密文的 Base64 ASCII 与 DEM 密文及 Nonce 组合
         let combined = [
+            &[FORMAT_VERSION][..],
             kem_str.as_bytes(),
             b"::",
             nonce.as_slice(),
@@ -122,39 +238,143 @@ impl AsymmetricCryptographicSystem for RsaKyberCryptoSystem {
         Ok(Base64String::from(combined))
     }
 
-    /// 执行无签名的KEM-DEM解密。
-    fn decrypt(
-        private_key: &Self::PrivateKey,
+    /// 重随机化无签名的混合密文，使同一消息得到无法关联的新密文，无需私钥。
+    ///
+    /// KEM-DEM 结构无法在不知道 DEM 密钥的情况下替换其随机性，因此这里以全新的
+    /// KEM 封装与 DEM 密钥将整个原密文再包裹一层；解密时会自动逐层剥离。
+    /// 注意：每次重随机化都会使密文长度增加一个 KEM 密文与 AEAD 开销，长度本身仍可能泄露层数。
+    /// 带签名的密文请先去除签名再重随机化，签名本身即可关联发送方。
+    pub fn rerandomize(public_key: &RsaKyberPublicKey, ciphertext: &str) -> Result<Base64String, Error> {
+        let inner = from_base64(ciphertext)?;
+        split_ciphertext(&inner)?;
+        let kyber_public_key = KyberCryptoSystem::parse_public_key(&public_key.kyber_public_key)?;
+        Self::encrypt_with_parsed_key(&kyber_public_key, &inner, Some(RERANDOMIZE_LAYER_AAD))
+    }
+
+    /// 仅轮换混合密文的DEM（数据加密）密钥，无需持有或分发公钥
+    ///
+    /// 解封原DEM密钥并解密载荷，随后以新生成的DEM密钥重新加密，并用私钥中内嵌的Kyber公钥重新封装。
+    /// 由于DEM密钥与KEM密文绑定，重新封装仍需一次Kyber封装运算，但不涉及RSA操作，也不改变接收方密钥。
+    /// 带重随机化外层的密文会被整体剥离，输出为单层密文；带签名的密文请先去除签名，轮换后再重新签名。
+    pub fn rotate_dem_key(
+        private_key: &RsaKyberPrivateKey,
         ciphertext: &str,
         additional_data: Option<&[u8]>,
-    ) -> Result<Vec<u8>, Self::Error> {
-        // 解码Base64得到原始字节
-        let combined = from_base64(ciphertext)?;
-        let delim = b"::";
-        // 查找第一个分隔符，将KEM部分与Nonce部分分开
-        let first_pos = combined.windows(delim.len())
-            .position(|window| window == delim)
-            .ok_or_else(|| Error::Format("密文格式错误：缺少KEM-Nonce分隔符".to_string()))?;
-        let kem_part = &combined[..first_pos];
-        // 跳过第一个分隔符
-        let rest = &combined[first_pos + delim.len()..];
-        // 查找第二个分隔符，将Nonce与AES密文分开
-        let second_pos = rest.windows(delim.len())
-            .position(|window| window == delim)
-            .ok_or_else(|| Error::Format("密文格式错误：缺少Nonce-DEM分隔符".to_string()))?;
-        let nonce_part = &rest[..second_pos];
-        let dem_part = &rest[second_pos + delim.len()..];
-
-        // 1. KEM: 使用Kyber私钥解封AES密钥。
-        let kem_ciphertext_str = String::from_utf8(kem_part.to_vec())
+    ) -> Result<Base64String, Error> {
+        let plaintext = Zeroizing::new(Self::decrypt(private_key, ciphertext, additional_data)?);
+        let kyber_public_key = KyberPublicKeyWrapper(
+            KyberCryptoSystem::embedded_public_key(&private_key.kyber_private_key)?.to_vec(),
+        );
+        let kyber_public_key = KyberCryptoSystem::parse_public_key(&kyber_public_key)?;
+        Self::encrypt_with_parsed_key(&kyber_public_key, &plaintext, additional_data)
+    }
+
+    /// 为仅支持RSA的旧接收方显式降级：解密混合密文，再以 `legacy_recipient` 按 `padding` 重新加密为纯RSA密文
+    ///
+    /// 必须持有混合私钥才能完成解密，因此降级只能由接收方主动执行，攻击者无法通过篡改密文诱发。
+    /// 输出与 [`RsaCryptoSystem`] 的密文格式相同，只有经典安全性；RSA单块加密限制了明文长度
+    /// （2048 位密钥配合 OAEP 约 190 字节），超出时返回 `Error::Traditional`。
+    pub fn extract_classical(
+        private_key: &RsaKyberPrivateKey,
+        hybrid_ciphertext: &str,
+        additional_data: Option<&[u8]>,
+        legacy_recipient: &RsaPublicKeyWrapper,
+        padding: RsaPadding,
+    ) -> Result<Base64String, Error> {
+        let plaintext = Zeroizing::new(Self::decrypt(private_key, hybrid_ciphertext, additional_data)?);
+        RsaCryptoSystem::encrypt_with_padding(legacy_recipient, &plaintext, padding)
+    }
+
+    /// 流式加密任意大小的数据，整个流只执行一次KEM封装
+    ///
+    /// 输出为 `"SKHS" || 版本(1字节) || KEM密文长度(u32 LE) || KEM密文`，随后是以派生的DEM密钥
+    /// 按 AES-GCM 对称流格式逐帧加密的数据，帧元数据绑定与完整性尾部遵循 `config`。
+    /// 与 [`AsymmetricSyncStreamingSystem`](crate::asymmetric::traits::AsymmetricSyncStreamingSystem)
+    /// 为每个数据块单独封装的通用格式不兼容，须使用 [`decrypt_stream_single_kem`](Self::decrypt_stream_single_kem) 解密。
+    pub fn encrypt_stream_single_kem<R: Read, W: Write>(
+        public_key: &RsaKyberPublicKey,
+        reader: R,
+        mut writer: W,
+        config: &StreamingConfig,
+        additional_data: Option<&[u8]>,
+    ) -> Result<StreamingResult, Error> {
+        let kyber_public_key = KyberCryptoSystem::parse_public_key(&public_key.kyber_public_key)?;
+        let mut transported_key = Zeroizing::new([0u8; 32]);
+        SystemRng.fill_bytes(transported_key.as_mut());
+        let kem_str = KyberCryptoSystem::encrypt_with_parsed_key(&kyber_public_key, transported_key.as_ref(), None)?
+            .to_string();
+        let dem_key = derive_dem_key_with_label(STREAM_DEM_KEY_LABEL, transported_key.as_ref(), kem_str.as_bytes());
+
+        writer.write_all(STREAM_MAGIC)?;
+        writer.write_all(&[STREAM_VERSION])?;
+        writer.write_all(&(kem_str.len() as u32).to_le_bytes())?;
+        writer.write_all(kem_str.as_bytes())?;
+        AesGcmSystem::encrypt_stream(&AesGcmKey::from_derived(*dem_key), reader, writer, config, additional_data)
+    }
+
+    /// 解密 [`encrypt_stream_single_kem`](Self::encrypt_stream_single_kem) 的输出：先读取头部并解封一次DEM密钥，再逐帧解密
+    ///
+    /// 内存占用只取决于帧大小，与数据总量无关。`config` 须与加密时的帧元数据绑定、完整性尾部设置一致。
+    pub fn decrypt_stream_single_kem<R: Read, W: Write>(
+        private_key: &RsaKyberPrivateKey,
+        mut reader: R,
+        writer: W,
+        config: &StreamingConfig,
+        additional_data: Option<&[u8]>,
+    ) -> Result<StreamingResult, Error> {
+        let mut header = [0u8; 9];
+        reader.read_exact(&mut header)
+            .map_err(|e| Error::Format(format!("混合流头部不完整: {}", e)))?;
+        if &header[..4] != STREAM_MAGIC || header[4] != STREAM_VERSION {
+            return Err(Error::Format("不是受支持的混合流格式".to_string()));
+        }
+        let kem_len = u32::from_le_bytes(header[5..].try_into().expect("长度字段固定为4字节")) as usize;
+        if kem_len > MAX_STREAM_KEM_SIZE {
+            return Err(Error::Format(format!("混合流头部中的KEM密文过长: {} 字节", kem_len)));
+        }
+        let mut kem_part = vec![0u8; kem_len];
+        reader.read_exact(&mut kem_part)
+            .map_err(|e| Error::Format(format!("混合流头部不完整: {}", e)))?;
+
+        let kem_str = std::str::from_utf8(&kem_part)
             .map_err(|e| Error::Format(format!("无效的PQ Base64密文: {}", e)))?;
-        let aes_key_bytes = KyberCryptoSystem::decrypt(&private_key.kyber_private_key, &kem_ciphertext_str, None)?;
+        let transported_key = Zeroizing::new(
+            KyberCryptoSystem::decrypt(&private_key.kyber_private_key, kem_str, None)?,
+        );
+        let dem_key = derive_dem_key_with_label(STREAM_DEM_KEY_LABEL, &transported_key, &kem_part);
+        AesGcmSystem::decrypt_stream(&AesGcmKey::from_derived(*dem_key), reader, writer, config, additional_data)
+    }
+
+    /// 解密一层KEM-DEM密文：先以调用方的关联数据尝试，失败后再尝试重随机化外层标签
+    fn decrypt_layer(
+        private_key: &RsaKyberPrivateKey,
+        combined: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<DecryptedLayer, Error> {
+        let (versioned, kem_part, nonce_part, dem_part) = split_ciphertext(combined)?;
 
-        // 2. DEM: 使用AES密钥和Nonce解密数据。
+        // 1. KEM: 使用Kyber私钥解封传输密钥，并与KEM密文一起派生DEM密钥；旧格式直接以传输密钥作为DEM密钥。
+        let kem_ciphertext_str = std::str::from_utf8(kem_part)
+            .map_err(|e| Error::Format(format!("无效的PQ Base64密文: {}", e)))?;
+        let transported_key = Zeroizing::new(
+            KyberCryptoSystem::decrypt(&private_key.kyber_private_key, kem_ciphertext_str, None)?,
+        );
+        let dem_key = if versioned {
+            derive_dem_key(&transported_key, kem_part)
+        } else {
+            let mut raw = Zeroizing::new([0u8; 32]);
+            if transported_key.len() != raw.len() {
+                return Err(Error::Format(format!("旧格式的传输密钥长度应为 32 字节，实际 {}", transported_key.len())));
+            }
+            raw.copy_from_slice(&transported_key);
+            raw
+        };
+
+        // 2. DEM: 使用派生的密钥和Nonce解密数据。
         #[cfg(feature = "chacha")]
-        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&aes_key_bytes));
+        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(dem_key.as_ref()));
         #[cfg(not(feature = "chacha"))]
-        let cipher = Aes256Gcm::new_from_slice(&aes_key_bytes)
+        let cipher = Aes256Gcm::new_from_slice(dem_key.as_ref())
             .map_err(|_| Error::Key("无效的对称密钥".to_string()))?;
         #[cfg(feature = "chacha")]
         let nonce = ChaNonce::from_slice(nonce_part);
@@ -166,11 +386,101 @@ impl AsymmetricCryptographicSystem for RsaKyberCryptoSystem {
             msg: dem_part,
             aad: additional_data.unwrap_or_default(),
         };
-        cipher.decrypt(&nonce, payload)
+        if let Ok(plaintext) = cipher.decrypt(nonce, payload) {
+            return Ok(DecryptedLayer::Plaintext(plaintext));
+        }
+        let layer_payload = Payload {
+            msg: dem_part,
+            aad: RERANDOMIZE_LAYER_AAD,
+        };
+        cipher.decrypt(nonce, layer_payload)
+            .map(DecryptedLayer::Inner)
             .map_err(|_| Error::Operation("AEAD 解密或认证失败".to_string()))
     }
 }
 
+impl AsymmetricCryptographicSystem for RsaKyberCryptoSystem {
+    type PublicKey = RsaKyberPublicKey;
+    type PrivateKey = RsaKyberPrivateKey;
+    type Error = Error;
+    type CiphertextOutput = Base64String;
+    const SUPPORTS_ADDITIONAL_DATA: bool = true;
+    const ALGORITHM_ID: Option<&'static str> = Some("RSA-Kyber");
+    const SUPPORTS_DETERMINISTIC_RNG: bool = false;
+
+    fn generate_keypair(config: &CryptoConfig) -> Result<(Self::PublicKey, Self::PrivateKey), Self::Error> {
+        let (rsa_pk, rsa_sk) = RsaCryptoSystem::generate_keypair(config)?;
+        let (kyber_pk, kyber_sk) = KyberCryptoSystem::generate_keypair(config)?;
+        
+        let public_key = Self::PublicKey { rsa_public_key: rsa_pk, kyber_public_key: kyber_pk };
+        let private_key = Self::PrivateKey { rsa_private_key: rsa_sk, kyber_private_key: kyber_sk };
+        
+        Ok((public_key, private_key))
+    }
+
+    fn export_public_key(pk: &Self::PublicKey) -> Result<String, Self::Error> {
+        serde_json::to_string(pk).map_err(Into::into)
+    }
+
+    fn public_key_from_private(sk: &Self::PrivateKey) -> Result<Self::PublicKey, Error> {
+        Ok(RsaKyberPublicKey {
+            rsa_public_key: RsaCryptoSystem::public_key_from_private(&sk.rsa_private_key)?,
+            kyber_public_key: KyberCryptoSystem::public_key_from_private(&sk.kyber_private_key)?,
+        })
+    }
+
+    fn public_key_matches_config(pk: &Self::PublicKey, config: &CryptoConfig) -> Result<bool, Error> {
+        Ok(RsaCryptoSystem::public_key_matches_config(&pk.rsa_public_key, config)?
+            && KyberCryptoSystem::public_key_matches_config(&pk.kyber_public_key, config)?)
+    }
+
+    fn export_private_key(sk: &Self::PrivateKey) -> Result<String, Self::Error> {
+        if sk.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
+        serde_json::to_string(sk).map_err(Into::into)
+    }
+
+    fn import_public_key(pk_str: &str) -> Result<Self::PublicKey, Self::Error> {
+        expect_key_kind(pk_str, KeyKind::Public)?;
+        serde_json::from_str(pk_str).map_err(Into::into)
+    }
+
+    fn import_private_key(sk_str: &str) -> Result<Self::PrivateKey, Self::Error> {
+        expect_key_kind(sk_str, KeyKind::Private)?;
+        serde_json::from_str(sk_str).map_err(Into::into)
+    }
+
+    /// 执行无签名的KEM-DEM加密。
+    fn encrypt(
+        public_key: &Self::PublicKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        let kyber_public_key = KyberCryptoSystem::parse_public_key(&public_key.kyber_public_key)?;
+        Self::encrypt_with_parsed_key(&kyber_public_key, plaintext, additional_data)
+    }
+
+    /// 执行无签名的KEM-DEM解密。
+    ///
+    /// 自动剥离由 [`RsaKyberCryptoSystem::rerandomize`] 添加的外层。
+    fn decrypt(
+        private_key: &Self::PrivateKey,
+        ciphertext: &str,
+        additional_data: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        // 解码Base64得到原始字节
+        let mut combined = from_base64(ciphertext)?;
+        for _ in 0..=MAX_RERANDOMIZE_LAYERS {
+            match Self::decrypt_layer(private_key, &combined, additional_data)? {
+                DecryptedLayer::Plaintext(plaintext) => return Ok(plaintext),
+                DecryptedLayer::Inner(inner) => combined = inner,
+            }
+        }
+        Err(Error::Format(format!("重随机化层数超过上限 {}", MAX_RERANDOMIZE_LAYERS)))
+    }
+}
+
 impl AuthenticatedCryptoSystem for RsaKyberCryptoSystem {
     type AuthenticatedOutput = Base64String;
 
@@ -256,6 +566,7 @@ impl AsyncStreamingSystem for RsaKyberCryptoSystem {
         let mut output_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
 
         loop {
+            config.check_cancelled()?;
             let read_bytes = reader.read(&mut buffer).await.map_err(Error::Io)?;
             if read_bytes == 0 {
                 break;
@@ -313,6 +624,7 @@ impl AsyncStreamingSystem for RsaKyberCryptoSystem {
         let mut output_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
 
         while reader.read_exact(&mut length_buffer).await.is_ok() {
+            config.check_cancelled()?;
             let length = u32::from_le_bytes(length_buffer) as usize;
             let mut ciphertext_buffer = vec![0u8; length];
             reader.read_exact(&mut ciphertext_buffer).await.map_err(Error::Io)?;
@@ -355,6 +667,120 @@ impl AsyncStreamingSystem for RsaKyberCryptoSystem {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compose_keypair_roundtrip() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (rsa_pk, rsa_sk) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let (kyber_pk, kyber_sk) = KyberCryptoSystem::generate_keypair(&config).unwrap();
+
+        let (pk, sk) = RsaKyberCryptoSystem::compose_keypair(rsa_pk.clone(), rsa_sk, kyber_pk.clone(), kyber_sk).unwrap();
+        let plaintext = b"composed from existing keys";
+
+        let ciphertext = RsaKyberCryptoSystem::encrypt_authenticated(&pk, plaintext, None, Some(&sk)).unwrap();
+        let decrypted = RsaKyberCryptoSystem::decrypt_authenticated(&sk, &ciphertext.to_string(), None, Some(&pk)).unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        // 仅公钥的组合可用于加密
+        let public_only = RsaKyberCryptoSystem::compose_public_key(rsa_pk, kyber_pk).unwrap();
+        assert_eq!(public_only, pk);
+        let ciphertext = RsaKyberCryptoSystem::encrypt(&public_only, plaintext, None).unwrap();
+        assert_eq!(RsaKyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), None).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_hybrid_security_bits_is_weaker_component() {
+        let config = CryptoConfig { rsa_key_bits: 2048, kyber_parameter_k: 1024, ..Default::default() };
+        let (pk, _) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        assert_eq!(pk.rsa_public_key.security_bits().unwrap(), 112);
+        assert_eq!(pk.kyber_public_key.security_bits().unwrap(), 256);
+        assert_eq!(pk.security_bits().unwrap(), 112);
+    }
+
+    #[test]
+    fn test_compose_keypair_mismatch_fails() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (rsa_pk, rsa_sk) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let (other_rsa_pk, _) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let (kyber_pk, kyber_sk) = KyberCryptoSystem::generate_keypair(&config).unwrap();
+        let (other_kyber_pk, _) = KyberCryptoSystem::generate_keypair(&config).unwrap();
+
+        let result = RsaKyberCryptoSystem::compose_keypair(other_rsa_pk, rsa_sk.clone(), kyber_pk, kyber_sk.clone());
+        assert!(matches!(result, Err(Error::Key(_))));
+
+        let result = RsaKyberCryptoSystem::compose_keypair(rsa_pk.clone(), rsa_sk, other_kyber_pk, kyber_sk);
+        assert!(matches!(result, Err(Error::Key(_))));
+
+        let result = RsaKyberCryptoSystem::compose_public_key(rsa_pk, KyberPublicKeyWrapper(vec![0u8; 10]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rerandomize_is_unlinkable_and_decrypts() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let plaintext = b"relay me without linking";
+        let aad = b"context";
+
+        let original = RsaKyberCryptoSystem::encrypt(&pk, plaintext, Some(aad)).unwrap();
+        let once = RsaKyberCryptoSystem::rerandomize(&pk, &original.to_string()).unwrap();
+        let twice = RsaKyberCryptoSystem::rerandomize(&pk, &original.to_string()).unwrap();
+        assert_ne!(once.as_bytes(), original.as_bytes());
+        assert_ne!(once.as_bytes(), twice.as_bytes());
+        assert!(!once.as_bytes().windows(original.as_bytes().len()).any(|w| w == original.as_bytes()));
+
+        for ciphertext in [&once, &twice] {
+            let decrypted = RsaKyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), Some(aad)).unwrap();
+            assert_eq!(decrypted, plaintext);
+        }
+        assert!(RsaKyberCryptoSystem::decrypt(&sk, &once.to_string(), Some(b"other")).is_err());
+
+        // 可以多次叠加重随机化
+        let nested = RsaKyberCryptoSystem::rerandomize(&pk, &once.to_string()).unwrap();
+        assert_eq!(RsaKyberCryptoSystem::decrypt(&sk, &nested.to_string(), Some(aad)).unwrap(), plaintext);
+
+        assert!(RsaKyberCryptoSystem::rerandomize(&pk, &to_base64(b"not a ciphertext")).is_err());
+    }
+
+    #[test]
+    fn test_rotate_dem_key_rewraps_and_decrypts() {
+        let config = CryptoConfig::default();
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let plaintext = b"long-lived archive payload";
+        let aad = b"archive-7";
+
+        let original = RsaKyberCryptoSystem::encrypt(&pk, plaintext, Some(aad)).unwrap();
+        let rotated = RsaKyberCryptoSystem::rotate_dem_key(&sk, &original.to_string(), Some(aad)).unwrap();
+        assert_eq!(RsaKyberCryptoSystem::decrypt(&sk, &rotated.to_string(), Some(aad)).unwrap(), plaintext);
+
+        // 封装DEM密钥的KEM部分与DEM密文均已更换
+        let (_, old_kem, _, old_dem) = split_ciphertext(original.as_bytes()).unwrap();
+        let (_, new_kem, _, new_dem) = split_ciphertext(rotated.as_bytes()).unwrap();
+        assert_ne!(old_kem, new_kem);
+        assert_ne!(old_dem, new_dem);
+
+        // 重随机化的密文轮换后变回单层，关联数据不匹配时拒绝轮换
+        let layered = RsaKyberCryptoSystem::rerandomize(&pk, &original.to_string()).unwrap();
+        let flattened = RsaKyberCryptoSystem::rotate_dem_key(&sk, &layered.to_string(), Some(aad)).unwrap();
+        assert_eq!(flattened.as_bytes().len(), rotated.as_bytes().len());
+        assert!(RsaKyberCryptoSystem::rotate_dem_key(&sk, &original.to_string(), Some(b"other")).is_err());
+    }
+
+    #[test]
+    fn test_hybrid_url_safe_ciphertext_auto_detected() {
+        use crate::common::utils::Base64Variant;
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let plaintext = b"hybrid ciphertext embedded in a URL";
+
+        let ciphertext = RsaKyberCryptoSystem::encrypt_authenticated(&pk, plaintext, None, Some(&sk)).unwrap();
+        let url_safe = ciphertext.encode(Base64Variant::UrlSafeNoPad);
+        assert!(!url_safe.contains(['+', '/', '=']));
+
+        let decrypted = RsaKyberCryptoSystem::decrypt_authenticated(&sk, &url_safe, None, Some(&pk)).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
     #[test]
     fn test_hybrid_roundtrip_unauthenticated() {
         let config = CryptoConfig::default();
@@ -436,6 +862,29 @@ mod tests {
         assert!(result.is_err(), "解密被篡改的KEM部分应该失败");
     }
 
+    #[test]
+    fn test_hybrid_stripped_or_zeroed_kyber_component_fails() {
+        let config = CryptoConfig::default();
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let ciphertext = RsaKyberCryptoSystem::encrypt(&pk, b"downgrade target", None).unwrap();
+        let combined = from_base64(&ciphertext.to_string()).unwrap();
+        let (_, kem_part, nonce_part, dem_part) = split_ciphertext(&combined).unwrap();
+
+        // 将Kyber密文置零（保留变体ID与长度），或整体移除
+        let mut kem_bytes = from_base64(std::str::from_utf8(kem_part).unwrap()).unwrap();
+        kem_bytes[1..].fill(0);
+        let zeroed_kem = to_base64(&kem_bytes);
+        for forged_kem in [zeroed_kem.as_bytes(), b"".as_slice()] {
+            let forged = [&[FORMAT_VERSION][..], forged_kem, b"::", nonce_part, b"::", dem_part].concat();
+            let forged_b64 = to_base64(&forged);
+            assert!(RsaKyberCryptoSystem::decrypt(&sk, &forged_b64, None).is_err());
+            assert!(RsaKyberCryptoSystem::decrypt_authenticated(&sk, &forged_b64, None, None).is_err());
+        }
+
+        // 同一传输密钥配合不同的KEM密文会派生出不同的DEM密钥
+        assert_ne!(*derive_dem_key(&[7u8; 32], kem_part), *derive_dem_key(&[7u8; 32], zeroed_kem.as_bytes()));
+    }
+
     #[test]
     fn test_hybrid_decrypt_with_wrong_key_fails() {
         let config = CryptoConfig::default();
@@ -474,6 +923,142 @@ mod tests {
         let decrypted = RsaKyberCryptoSystem::decrypt(&imported_sk, ciphertext.to_string().as_ref(), None).unwrap();
         assert_eq!(plaintext.as_slice(), decrypted.as_slice());
     }
+
+    #[test]
+    fn test_hybrid_import_swapped_key_kind_fails() {
+        let config = CryptoConfig::default();
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let pk_str = RsaKyberCryptoSystem::export_public_key(&pk).unwrap();
+        let sk_str = RsaKyberCryptoSystem::export_private_key(&sk).unwrap();
+
+        assert!(matches!(RsaKyberCryptoSystem::import_public_key(&sk_str), Err(Error::KeyImportFailed(_))));
+        assert!(matches!(RsaKyberCryptoSystem::import_private_key(&pk_str), Err(Error::KeyImportFailed(_))));
+    }
+
+    #[test]
+    fn test_hybrid_stream_large_payload_roundtrip() {
+        use std::io::Cursor;
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let payload: Vec<u8> = (0..(1 << 20) + 123).map(|i| (i % 251) as u8).collect();
+        let streaming_config = StreamingConfig::default().with_buffer_size(64 * 1024).with_integrity_footer(true);
+
+        let mut encrypted = Vec::new();
+        RsaKyberCryptoSystem::encrypt_stream_single_kem(&pk, Cursor::new(&payload), &mut encrypted, &streaming_config, Some(b"aad")).unwrap();
+        assert_eq!(&encrypted[..4], STREAM_MAGIC);
+        // 整个流只有头部中的一份KEM密文，每帧仅增加长度前缀与AEAD开销
+        let kem_len = u32::from_le_bytes(encrypted[5..9].try_into().unwrap()) as usize;
+        let frames = payload.len().div_ceil(64 * 1024);
+        assert!(encrypted.len() < 9 + kem_len + payload.len() * 4 / 3 + frames * 64 + 128);
+
+        let mut decrypted = Vec::new();
+        let result = RsaKyberCryptoSystem::decrypt_stream_single_kem(&sk, Cursor::new(&encrypted), &mut decrypted, &streaming_config, Some(b"aad")).unwrap();
+        assert_eq!(decrypted, payload);
+        assert!(result.bytes_processed > 0);
+
+        assert!(RsaKyberCryptoSystem::decrypt_stream_single_kem(&sk, Cursor::new(&encrypted), &mut Vec::new(), &streaming_config, Some(b"other")).is_err());
+
+        // 截断、篡改KEM部分或使用错误的私钥都无法解密
+        let truncated = &encrypted[..encrypted.len() - 10];
+        assert!(RsaKyberCryptoSystem::decrypt_stream_single_kem(&sk, Cursor::new(truncated), &mut Vec::new(), &streaming_config, Some(b"aad")).is_err());
+        let mut tampered = encrypted.clone();
+        tampered[9 + kem_len / 2] ^= 1;
+        assert!(RsaKyberCryptoSystem::decrypt_stream_single_kem(&sk, Cursor::new(&tampered), &mut Vec::new(), &streaming_config, Some(b"aad")).is_err());
+        let (_, other_sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        assert!(RsaKyberCryptoSystem::decrypt_stream_single_kem(&other_sk, Cursor::new(&encrypted), &mut Vec::new(), &streaming_config, Some(b"aad")).is_err());
+    }
+
+    #[test]
+    fn test_hybrid_stream_rejects_malformed_header() {
+        use std::io::Cursor;
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let streaming_config = StreamingConfig::default();
+        let mut encrypted = Vec::new();
+        RsaKyberCryptoSystem::encrypt_stream_single_kem(&pk, Cursor::new(b""), &mut encrypted, &streaming_config, None).unwrap();
+        let mut decrypted = Vec::new();
+        RsaKyberCryptoSystem::decrypt_stream_single_kem(&sk, Cursor::new(&encrypted), &mut decrypted, &streaming_config, None).unwrap();
+        assert!(decrypted.is_empty());
+
+        let mut wrong_magic = encrypted.clone();
+        wrong_magic[0] = b'X';
+        let mut huge_kem = encrypted.clone();
+        huge_kem[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        for malformed in [&wrong_magic[..], &huge_kem[..], &encrypted[..6]] {
+            let result = RsaKyberCryptoSystem::decrypt_stream_single_kem(&sk, Cursor::new(malformed), &mut Vec::new(), &streaming_config, None);
+            assert!(matches!(result, Err(Error::Format(_))), "{:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_zeroized_private_key_is_rejected() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (public_key, private_key) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let ciphertext = RsaKyberCryptoSystem::encrypt(&public_key, b"session", None).unwrap().to_string();
+
+        let mut wiped = private_key.clone();
+        wiped.kyber_private_key.zeroize();
+        assert!(wiped.is_zeroized() && !private_key.is_zeroized());
+        assert!(matches!(RsaKyberCryptoSystem::decrypt(&wiped, &ciphertext, None), Err(Error::KeyZeroized)));
+        assert!(matches!(RsaKyberCryptoSystem::export_private_key(&wiped), Err(Error::KeyZeroized)));
+        assert!(matches!(RsaKyberCryptoSystem::public_key_from_private(&wiped), Err(Error::KeyZeroized)));
+        assert_eq!(RsaKyberCryptoSystem::public_key_from_private(&private_key).unwrap(), public_key);
+
+        assert_eq!(RsaKyberCryptoSystem::decrypt(&private_key, &ciphertext, None).unwrap(), b"session");
+        private_key.zeroize_now();
+    }
+
+    #[test]
+    fn test_extract_classical_for_legacy_recipient() {
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let (legacy_pk, legacy_sk) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        let hybrid = RsaKyberCryptoSystem::encrypt(&pk, b"transition period", Some(b"aad")).unwrap().to_string();
+
+        for padding in [RsaPadding::Oaep, RsaPadding::Pkcs1v15] {
+            let classical = RsaKyberCryptoSystem::extract_classical(&sk, &hybrid, Some(b"aad"), &legacy_pk, padding).unwrap();
+            assert_eq!(RsaCryptoSystem::decrypt_with_padding(&legacy_sk, &classical.to_string(), padding).unwrap(), b"transition period");
+        }
+
+        // 没有正确的混合私钥或关联数据就无法降级
+        let (_, other_sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        assert!(RsaKyberCryptoSystem::extract_classical(&other_sk, &hybrid, Some(b"aad"), &legacy_pk, RsaPadding::Oaep).is_err());
+        assert!(RsaKyberCryptoSystem::extract_classical(&sk, &hybrid, None, &legacy_pk, RsaPadding::Oaep).is_err());
+        let mut wiped = sk.clone();
+        wiped.zeroize();
+        let result = RsaKyberCryptoSystem::extract_classical(&wiped, &hybrid, Some(b"aad"), &legacy_pk, RsaPadding::Oaep);
+        assert!(matches!(result, Err(Error::KeyZeroized)));
+
+        // 超出RSA单块容量的明文无法降级
+        let long = RsaKyberCryptoSystem::encrypt(&pk, &[7u8; 512], None).unwrap().to_string();
+        let result = RsaKyberCryptoSystem::extract_classical(&sk, &long, None, &legacy_pk, RsaPadding::Oaep);
+        assert!(matches!(result, Err(Error::Traditional(_))));
+    }
+
+    #[cfg(not(feature = "chacha"))]
+    #[test]
+    fn test_legacy_unversioned_ciphertext_still_decrypts() {
+        let (pk, sk) = RsaKyberCryptoSystem::generate_keypair(&CryptoConfig::default()).unwrap();
+        let current = from_base64(&RsaKyberCryptoSystem::encrypt(&pk, b"current", None).unwrap().to_string()).unwrap();
+        assert_eq!(current[0], FORMAT_VERSION);
+
+        // 旧格式：没有版本字节，传输密钥直接作为 AES-256-GCM 的 DEM 密钥
+        let parsed = KyberCryptoSystem::parse_public_key(&pk.kyber_public_key).unwrap();
+        let transported_key = [0x5au8; 32];
+        let kem_str = KyberCryptoSystem::encrypt_with_parsed_key(&parsed, &transported_key, None).unwrap().to_string();
+        let nonce = Aes256Gcm::generate_nonce(&mut SystemRng);
+        let payload = aes_gcm::aead::Payload { msg: &b"legacy"[..], aad: &b"aad"[..] };
+        let dem = Aes256Gcm::new(&transported_key.into()).encrypt(&nonce, payload).unwrap();
+        let legacy = [kem_str.as_bytes(), b"::", nonce.as_slice(), b"::", &dem].concat();
+        assert_eq!(RsaKyberCryptoSystem::decrypt(&sk, &to_base64(&legacy), Some(b"aad")).unwrap(), b"legacy");
+
+        // 给旧密文加上版本字节，或去掉新密文的版本字节，都会改变DEM密钥的派生方式而无法解密
+        let upgraded = [&[FORMAT_VERSION][..], &legacy].concat();
+        assert!(RsaKyberCryptoSystem::decrypt(&sk, &to_base64(&upgraded), Some(b"aad")).is_err());
+        assert!(RsaKyberCryptoSystem::decrypt(&sk, &to_base64(&current[1..]), None).is_err());
+    }
 }
 
 #[cfg(all(test, feature = "async-engine"))]
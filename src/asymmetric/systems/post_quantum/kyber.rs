@@ -1,14 +1,16 @@
 use pqcrypto_kyber::{kyber1024, kyber512, kyber768};
 use pqcrypto_traits::kem::{Ciphertext, PublicKey, SecretKey, SharedSecret};
 use serde::{Deserialize, Serialize};
+use crate::asymmetric::key_kind::{expect_key_kind, KeyKind};
 use crate::asymmetric::traits::AsymmetricCryptographicSystem;
 #[cfg(feature = "async-engine")]
 use crate::asymmetric::traits::AsyncStreamingSystem;
 use crate::common::errors::Error;
+use crate::common::rng::SystemRng;
 use aes_gcm::aead::{AeadCore, KeyInit};
 #[cfg(not(feature = "chacha"))]
 use aes_gcm::{
-    aead::{Aead, OsRng}, Aes256Gcm, Nonce
+    aead::Aead, Aes256Gcm, Nonce
 };
 #[cfg(feature = "chacha")]
 use chacha20poly1305::{
@@ -16,8 +18,6 @@ use chacha20poly1305::{
     ChaCha20Poly1305,
     Nonce as ChaNonce
 };
-#[cfg(feature = "chacha")]
-use rsa::rand_core::OsRng;
 
 #[cfg(feature = "async-engine")]
 use crate::common::streaming::StreamingConfig;
@@ -25,16 +25,64 @@ use crate::common::streaming::StreamingConfig;
 use crate::common::streaming::StreamingResult;
 #[cfg(feature = "async-engine")]
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
-use crate::common::utils::{from_base64, to_base64, Base64String, CryptoConfig, ZeroizingVec};
+use crate::common::utils::{constant_time_eq, from_base64, to_base64, Base64String, CryptoConfig, ZeroizingVec};
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
 
 /// Kyber公钥包装器
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KyberPublicKeyWrapper(pub Vec<u8>);
 
+impl KyberPublicKeyWrapper {
+    /// 按公钥长度识别的安全级别估算经典安全强度（比特）
+    ///
+    /// Kyber512/768/1024 分别对应 NIST 安全类别 1/3/5，即与 AES-128/192/256 相当的 128/192/256 比特。
+    pub fn security_bits(&self) -> Result<u32, Error> {
+        match self.0.len() {
+            KYBER512_PUBLICKEYBYTES => Ok(128),
+            KYBER768_PUBLICKEYBYTES => Ok(192),
+            KYBER1024_PUBLICKEYBYTES => Ok(256),
+            len => Err(Error::PostQuantum(format!("无效的Kyber公钥长度: {}", len))),
+        }
+    }
+
+    /// 按公钥长度识别的安全级别参数 k（512、768 或 1024）
+    pub fn parameter_k(&self) -> Result<usize, Error> {
+        match self.0.len() {
+            KYBER512_PUBLICKEYBYTES => Ok(512),
+            KYBER768_PUBLICKEYBYTES => Ok(768),
+            KYBER1024_PUBLICKEYBYTES => Ok(1024),
+            len => Err(Error::PostQuantum(format!("无效的Kyber公钥长度: {}", len))),
+        }
+    }
+}
+
 /// Kyber私钥包装器
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KyberPrivateKeyWrapper(pub ZeroizingVec);
 
+impl KyberPrivateKeyWrapper {
+    /// 立即清零并丢弃私钥，不必等到离开作用域
+    ///
+    /// 只清零这一份；此前 `clone` 出的副本不受影响。需要让仍被其他结构持有的私钥失效时，
+    /// 对其调用 [`Zeroize::zeroize`]，之后任何使用私钥的运算都返回 `Error::KeyZeroized`。
+    pub fn zeroize_now(mut self) {
+        self.zeroize();
+    }
+
+    /// 私钥是否已被清零
+    pub fn is_zeroized(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Zeroize for KyberPrivateKeyWrapper {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// Kyber后量子加密系统实现
 /// 
 /// 使用Kyber进行密钥封装，然后用AES-GCM进行数据加密
@@ -53,80 +101,82 @@ const KYBER1024_PUBLICKEYBYTES: usize = kyber1024::public_key_bytes();
 const KYBER1024_SECRETKEYBYTES: usize = kyber1024::secret_key_bytes();
 const KYBER1024_CIPHERTEXTBYTES: usize = kyber1024::ciphertext_bytes();
 
-impl AsymmetricCryptographicSystem for KyberCryptoSystem {
-    type PublicKey = KyberPublicKeyWrapper;
-    type PrivateKey = KyberPrivateKeyWrapper;
-    type CiphertextOutput = Base64String;
-    type Error = Error;
-    
-    fn generate_keypair(config: &CryptoConfig) -> Result<(Self::PublicKey, Self::PrivateKey), Self::Error> {
-        let (public_key_vec, private_key_vec) = match config.kyber_parameter_k {
-            512 => {
-                let (pk, sk) = kyber512::keypair();
-                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
-            }
-            768 => {
-                let (pk, sk) = kyber768::keypair();
-                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
-            }
-            1024 => {
-                let (pk, sk) = kyber1024::keypair();
-                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
-            }
-            k => return Err(Error::PostQuantum(format!("不支持的Kyber安全级别: {}", k))),
-        };
+/// 密封盒密钥派生的域分隔标签
+const SEALED_BOX_KEY_LABEL: &[u8] = b"seal-kit-kyber-sealed-box-key-v1";
+/// 密封盒 Nonce 派生的域分隔标签
+const SEALED_BOX_NONCE_LABEL: &[u8] = b"seal-kit-kyber-sealed-box-nonce-v1";
 
-        Ok(
-            (
-                KyberPublicKeyWrapper(public_key_vec),
-                KyberPrivateKeyWrapper(ZeroizingVec(private_key_vec)),
-            )
-        )
+/// KEM-DEM 密钥派生使用的 HKDF-SHA256 salt
+///
+/// 按 FIPS 203 的建议，KEM 共享密钥不直接用作数据加密密钥，而是经带域分隔标签的 KDF 派生：
+/// `DEM密钥 = HKDF-SHA256(salt = KYBER_DEM_KDF_SALT, IKM = 共享密钥, info = DEM标签, L = 32)`。
+/// 其他实现按这些常量即可派生出相同的密钥。
+pub const KYBER_DEM_KDF_SALT: &[u8] = b"seal-kit-kyber-dem-salt-v2";
+/// DEM 为 AES-256-GCM 时的 HKDF `info` 标签
+pub const KYBER_DEM_AES_256_GCM_LABEL: &[u8] = b"seal-kit-kyber-dem-aes-256-gcm-key-v2";
+/// DEM 为 ChaCha20-Poly1305（`chacha` 特性）时的 HKDF `info` 标签
+pub const KYBER_DEM_CHACHA20_POLY1305_LABEL: &[u8] = b"seal-kit-kyber-dem-chacha20-poly1305-key-v2";
+
+/// 当前 DEM 对应的派生标签
+#[cfg(not(feature = "chacha"))]
+const KYBER_DEM_LABEL: &[u8] = KYBER_DEM_AES_256_GCM_LABEL;
+#[cfg(feature = "chacha")]
+const KYBER_DEM_LABEL: &[u8] = KYBER_DEM_CHACHA20_POLY1305_LABEL;
+
+/// 变体ID中标记"DEM 密钥经标签化 HKDF 派生"的位；未置位的旧密文直接以共享密钥作为 DEM 密钥
+const LABELED_KDF_FLAG: u8 = 0x10;
+
+/// 已解析的Kyber公钥，按安全级别区分
+#[derive(Clone)]
+pub(crate) enum ParsedKyberPublicKey {
+    Kyber512(Box<kyber512::PublicKey>),
+    Kyber768(Box<kyber768::PublicKey>),
+    Kyber1024(Box<kyber1024::PublicKey>),
+}
+
+impl KyberCryptoSystem {
+    /// 根据公钥长度判断Kyber安全级别并解析公钥
+    pub(crate) fn parse_public_key(public_key: &KyberPublicKeyWrapper) -> Result<ParsedKyberPublicKey, Error> {
+        let pk_bytes = &public_key.0;
+        match pk_bytes.len() {
+            KYBER512_PUBLICKEYBYTES => kyber512::PublicKey::from_bytes(pk_bytes)
+                .map(|pk| ParsedKyberPublicKey::Kyber512(Box::new(pk)))
+                .map_err(|_| Error::PostQuantum("无效的Kyber512公钥格式".to_string())),
+            KYBER768_PUBLICKEYBYTES => kyber768::PublicKey::from_bytes(pk_bytes)
+                .map(|pk| ParsedKyberPublicKey::Kyber768(Box::new(pk)))
+                .map_err(|_| Error::PostQuantum("无效的Kyber768公钥格式".to_string())),
+            KYBER1024_PUBLICKEYBYTES => kyber1024::PublicKey::from_bytes(pk_bytes)
+                .map(|pk| ParsedKyberPublicKey::Kyber1024(Box::new(pk)))
+                .map_err(|_| Error::PostQuantum("无效的Kyber1024公钥格式".to_string())),
+            len => Err(Error::PostQuantum(format!("无效的Kyber公钥长度: {}", len))),
+        }
     }
-    
-    fn encrypt(
-        public_key: &Self::PublicKey,
+
+    /// 使用已解析的Kyber公钥执行KEM+DEM加密
+    pub(crate) fn encrypt_with_parsed_key(
+        public_key: &ParsedKyberPublicKey,
         plaintext: &[u8],
-        _additional_data: Option<&[u8]>, // AAD在KEM+DEM中不由KEM部分直接处理
-    ) -> Result<Self::CiphertextOutput, Self::Error> {
-        let pk_bytes = &public_key.0;
-        let (variant_id, shared_secret_bytes, kyber_ciphertext_bytes) = match pk_bytes.len() {
-            KYBER512_PUBLICKEYBYTES => {
-                let pk = kyber512::PublicKey::from_bytes(pk_bytes)
-                    .map_err(|_| Error::PostQuantum("无效的Kyber512公钥格式".to_string()))?;
-                let (ss, ct) = kyber512::encapsulate(&pk);
-                (1u8, ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
-            }
-            KYBER768_PUBLICKEYBYTES => {
-                let pk = kyber768::PublicKey::from_bytes(pk_bytes)
-                    .map_err(|_| Error::PostQuantum("无效的Kyber768公钥格式".to_string()))?;
-                let (ss, ct) = kyber768::encapsulate(&pk);
-                (2u8, ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
-            }
-            KYBER1024_PUBLICKEYBYTES => {
-                let pk = kyber1024::PublicKey::from_bytes(pk_bytes)
-                    .map_err(|_| Error::PostQuantum("无效的Kyber1024公钥格式".to_string()))?;
-                let (ss, ct) = kyber1024::encapsulate(&pk);
-                (3u8, ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
-            }
-            len => return Err(Error::PostQuantum(format!("无效的Kyber公钥长度: {}", len))),
-        };
+        additional_data: Option<&[u8]>,
+    ) -> Result<Base64String, Error> {
+        let (variant_id, shared_secret_bytes, kyber_ciphertext_bytes) = Self::encapsulate(public_key);
+        let dem_key = Self::derive_dem_key(&shared_secret_bytes);
+        let variant_id = variant_id | LABELED_KDF_FLAG;
 
-        // 使用共享密钥执行AEAD加密
+        // 使用派生的DEM密钥执行AEAD加密
         #[cfg(feature = "chacha")]
-        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&shared_secret_bytes));
+        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(dem_key.as_ref()));
         #[cfg(not(feature = "chacha"))]
-        let cipher = Aes256Gcm::new_from_slice(&shared_secret_bytes)
+        let cipher = Aes256Gcm::new_from_slice(dem_key.as_ref())
             .map_err(|e| Error::Operation(format!("创建AEAD加密器失败: {}", e)))?;
         #[cfg(feature = "chacha")]
-        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut SystemRng);
         #[cfg(not(feature = "chacha"))]
-        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let nonce = Aes256Gcm::generate_nonce(&mut SystemRng);
 
         use aes_gcm::aead::Payload;
         let payload = Payload {
             msg: plaintext,
-            aad: _additional_data.unwrap_or_default(),
+            aad: additional_data.unwrap_or_default(),
         };
 
         // 加密数据
@@ -141,24 +191,222 @@ impl AsymmetricCryptographicSystem for KyberCryptoSystem {
         
         Ok(Base64String::from(combined))
     }
-    
-    fn decrypt(
-        private_key: &Self::PrivateKey,
-        ciphertext: &str,
-        additional_data: Option<&[u8]>, // AAD在KEM+DEM中不由KEM部分直接处理
-    ) -> Result<Vec<u8>, Self::Error> {
-        // 解码Base64
-        let combined = from_base64(ciphertext)?;
-        
-        if combined.is_empty() {
-            return Err(Error::Format("密文为空".to_string()));
+
+    /// 匿名发送方的"密封盒"加密
+    ///
+    /// 每条消息都执行一次新的Kyber封装，其KEM密文即一次性的临时公开值，发送方不持有也不暴露任何长期密钥。
+    /// 对称密钥由 `SHA-256(标签 || 共享密钥 || KEM密文 || 接收方公钥)` 派生，Nonce 同样由KEM密文与接收方公钥派生，
+    /// 因此密文与接收方绑定。输出格式为 `变体ID(1字节) || KEM密文 || AEAD密文`。
+    ///
+    /// X25519 系统提供同样的接口（`X25519CryptoSystem::seal_anonymous`）；RSA 与 RSA+Kyber 混合系统没有对应接口。
+    pub fn seal_anonymous(recipient: &KyberPublicKeyWrapper, plaintext: &[u8]) -> Result<Base64String, Error> {
+        let public_key = Self::parse_public_key(recipient)?;
+        let (variant_id, shared_secret, kem_ciphertext) = Self::encapsulate(&public_key);
+        let shared_secret = Zeroizing::new(shared_secret);
+
+        let sealed = Self::sealed_box_aead(&shared_secret, &kem_ciphertext, &recipient.0, plaintext, true)?;
+
+        let mut combined = vec![variant_id];
+        combined.extend_from_slice(&kem_ciphertext);
+        combined.extend_from_slice(&sealed);
+        Ok(Base64String::from(combined))
+    }
+
+    /// 打开 [`seal_anonymous`](Self::seal_anonymous) 生成的密封盒
+    ///
+    /// Kyber私钥中内嵌了对应的公钥，因此只需接收方私钥即可完成密钥派生。
+    pub fn open_anonymous(recipient: &KyberPrivateKeyWrapper, sealed: &str) -> Result<Vec<u8>, Error> {
+        let combined = from_base64(sealed)?;
+        let (&variant_id, rest) = combined.split_first()
+            .ok_or_else(|| Error::Format("密文为空".to_string()))?;
+        let (kem_len, shared_secret) = Self::decapsulate(recipient, variant_id, rest)?;
+        let shared_secret = Zeroizing::new(shared_secret);
+        let (kem_ciphertext, sealed_body) = rest.split_at(kem_len);
+
+        Self::sealed_box_aead(&shared_secret, kem_ciphertext, Self::embedded_public_key(recipient)?, sealed_body, false)
+    }
+
+    /// 从Kyber私钥中取出内嵌的公钥（私钥布局为 `s || pk || H(pk) || z`）
+    pub(crate) fn embedded_public_key(private_key: &KyberPrivateKeyWrapper) -> Result<&[u8], Error> {
+        if private_key.is_zeroized() {
+            return Err(Error::KeyZeroized);
         }
+        let sk = private_key.0.as_ref();
+        let pk_len = match sk.len() {
+            KYBER512_SECRETKEYBYTES => KYBER512_PUBLICKEYBYTES,
+            KYBER768_SECRETKEYBYTES => KYBER768_PUBLICKEYBYTES,
+            KYBER1024_SECRETKEYBYTES => KYBER1024_PUBLICKEYBYTES,
+            len => return Err(Error::Key(format!("无效的Kyber私钥大小: {}字节", len))),
+        };
+        let end = sk.len() - 64;
+        Ok(&sk[end - pk_len..end])
+    }
 
-        // 提取变体ID
-        let variant_id = combined[0];
-        let rest = &combined[1..];
+    /// 由共享密钥派生密封盒的对称密钥与 Nonce，并执行AEAD加密（`seal` 为真）或解密
+    fn sealed_box_aead(
+        shared_secret: &[u8],
+        kem_ciphertext: &[u8],
+        recipient_public_key: &[u8],
+        data: &[u8],
+        seal: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let key = Zeroizing::new(
+            Sha256::new()
+                .chain_update(SEALED_BOX_KEY_LABEL)
+                .chain_update(shared_secret)
+                .chain_update(kem_ciphertext)
+                .chain_update(recipient_public_key)
+                .finalize()
+                .to_vec(),
+        );
+        let nonce_digest = Sha256::new()
+            .chain_update(SEALED_BOX_NONCE_LABEL)
+            .chain_update(kem_ciphertext)
+            .chain_update(recipient_public_key)
+            .finalize();
 
-        let (kyber_ct_len, shared_secret_bytes) = match variant_id {
+        #[cfg(feature = "chacha")]
+        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&key));
+        #[cfg(not(feature = "chacha"))]
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| Error::Operation(format!("创建AEAD加密器失败: {}", e)))?;
+        #[cfg(feature = "chacha")]
+        let nonce = ChaNonce::from_slice(&nonce_digest[..12]);
+        #[cfg(not(feature = "chacha"))]
+        let nonce = Nonce::from_slice(&nonce_digest[..12]);
+
+        if seal {
+            cipher.encrypt(nonce, data)
+                .map_err(|e| Error::PostQuantum(format!("AEAD加密失败: {}", e)))
+        } else {
+            cipher.decrypt(nonce, data)
+                .map_err(|e| Error::PostQuantum(format!("AEAD解密失败: {}", e)))
+        }
+    }
+
+    /// 由KEM共享密钥派生DEM密钥：`HKDF-SHA256(KYBER_DEM_KDF_SALT, 共享密钥, 当前DEM标签)`
+    ///
+    /// 标签随 DEM 算法不同，AES-256-GCM 使用 [`KYBER_DEM_AES_256_GCM_LABEL`]，
+    /// ChaCha20-Poly1305 使用 [`KYBER_DEM_CHACHA20_POLY1305_LABEL`]。
+    pub fn derive_dem_key(shared_secret: &[u8]) -> Zeroizing<[u8; 32]> {
+        let mut dem_key = Zeroizing::new([0u8; 32]);
+        Hkdf::<Sha256>::new(Some(KYBER_DEM_KDF_SALT), shared_secret)
+            .expand(KYBER_DEM_LABEL, dem_key.as_mut())
+            .expect("32 字节输出远小于 HKDF-SHA256 的上限");
+        dem_key
+    }
+
+    /// 由密文首字节的变体ID得到 (安全级别k, KEM密文长度)
+    ///
+    /// 同时接受标签化派生（带 `LABELED_KDF_FLAG`）与旧格式的变体ID。
+    pub(crate) fn variant_layout(variant_id: u8) -> Option<(usize, usize)> {
+        match variant_id & !LABELED_KDF_FLAG {
+            1 => Some((512, KYBER512_CIPHERTEXTBYTES)),
+            2 => Some((768, KYBER768_CIPHERTEXTBYTES)),
+            3 => Some((1024, KYBER1024_CIPHERTEXTBYTES)),
+            _ => None,
+        }
+    }
+
+    /// 在 `parameter_k` 级别下加密 `plaintext_len` 字节明文时的密文字节数（Base64 编码前）
+    pub(crate) fn ciphertext_len(parameter_k: usize, plaintext_len: usize) -> Result<usize, Error> {
+        let kem_len = (1..=3)
+            .filter_map(Self::variant_layout)
+            .find_map(|(k, kem_len)| (k == parameter_k).then_some(kem_len))
+            .ok_or_else(|| Error::PostQuantum(format!("不支持的Kyber安全级别: {}", parameter_k)))?;
+        // 变体ID + KEM密文 + Nonce + AEAD密文（含16字节标签）
+        Ok(1 + kem_len + 12 + plaintext_len + 16)
+    }
+
+    /// 使用Kyber公钥执行密钥封装，返回 (变体ID, 共享密钥, KEM密文)
+    fn encapsulate(public_key: &ParsedKyberPublicKey) -> (u8, Vec<u8>, Vec<u8>) {
+        match public_key {
+            ParsedKyberPublicKey::Kyber512(pk) => {
+                let (ss, ct) = kyber512::encapsulate(pk);
+                (1u8, ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
+            }
+            ParsedKyberPublicKey::Kyber768(pk) => {
+                let (ss, ct) = kyber768::encapsulate(pk);
+                (2u8, ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
+            }
+            ParsedKyberPublicKey::Kyber1024(pk) => {
+                let (ss, ct) = kyber1024::encapsulate(pk);
+                (3u8, ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
+            }
+        }
+    }
+
+    /// 检查分别导入的公钥与私钥是否属于同一密钥对
+    ///
+    /// 先比较私钥内嵌的公钥，再以公钥执行一次封装并用私钥解封，确认双方得到相同的共享密钥；
+    /// 任一密钥无法解析时返回 `false`。只返回布尔结果，不暴露私钥材料。
+    pub fn key_pair_matches(public_key: &KyberPublicKeyWrapper, private_key: &KyberPrivateKeyWrapper) -> bool {
+        if Self::embedded_public_key(private_key).ok() != Some(public_key.0.as_slice()) {
+            return false;
+        }
+        let Ok(parsed) = Self::parse_public_key(public_key) else {
+            return false;
+        };
+        let (variant_id, shared_secret, kem_ciphertext) = Self::encapsulate(&parsed);
+        let shared_secret = Zeroizing::new(shared_secret);
+        Self::decapsulate(private_key, variant_id, &kem_ciphertext)
+            .is_ok_and(|(_, decapsulated)| constant_time_eq(&Zeroizing::new(decapsulated), &shared_secret))
+    }
+
+    /// 以规范定义的原始字节编码导入密钥对（即 NIST KAT 中的 `pk`/`sk`）
+    ///
+    /// 原始编码与本库 Base64 导出解码后的字节相同；私钥内嵌的公钥必须与 `public_key` 一致。
+    pub fn import_raw(public_key: &[u8], private_key: &[u8]) -> Result<(KyberPublicKeyWrapper, KyberPrivateKeyWrapper), Error> {
+        let public_key = Self::public_key_from_vec(public_key.to_vec())?;
+        let private_key = Self::private_key_from_vec(private_key.to_vec())?;
+        if Self::embedded_public_key(&private_key)? != public_key.0.as_slice() {
+            return Err(Error::Key("Kyber公钥与私钥不匹配".to_string()));
+        }
+        Ok((public_key, private_key))
+    }
+
+    /// 导出规范定义的原始字节编码 (公钥, 私钥)
+    pub fn export_raw(public_key: &KyberPublicKeyWrapper, private_key: &KyberPrivateKeyWrapper) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+        (public_key.0.clone(), Zeroizing::new(private_key.0.to_vec()))
+    }
+
+    pub(crate) fn public_key_from_vec(bytes: Vec<u8>) -> Result<KyberPublicKeyWrapper, Error> {
+        match bytes.len() {
+            KYBER512_PUBLICKEYBYTES | KYBER768_PUBLICKEYBYTES | KYBER1024_PUBLICKEYBYTES => {},
+            len => return Err(Error::Key(format!(
+                "无效的Kyber公钥大小: {}字节, 预期值为 {}, {} 或 {}",
+                len, KYBER512_PUBLICKEYBYTES, KYBER768_PUBLICKEYBYTES, KYBER1024_PUBLICKEYBYTES
+            ))),
+        }
+        Ok(KyberPublicKeyWrapper(bytes))
+    }
+
+    /// 按原始字节长度判断是 Kyber 公钥还是私钥，两者在各安全级别下的长度互不重叠
+    pub(crate) fn key_kind_for_len(len: usize) -> Option<KeyKind> {
+        match len {
+            KYBER512_PUBLICKEYBYTES | KYBER768_PUBLICKEYBYTES | KYBER1024_PUBLICKEYBYTES => Some(KeyKind::Public),
+            KYBER512_SECRETKEYBYTES | KYBER768_SECRETKEYBYTES | KYBER1024_SECRETKEYBYTES => Some(KeyKind::Private),
+            _ => None,
+        }
+    }
+
+    fn private_key_from_vec(bytes: Vec<u8>) -> Result<KyberPrivateKeyWrapper, Error> {
+        match bytes.len() {
+            KYBER512_SECRETKEYBYTES | KYBER768_SECRETKEYBYTES | KYBER1024_SECRETKEYBYTES => {},
+            len => return Err(Error::Key(format!(
+                "无效的Kyber私钥大小: {}字节, 预期值为 {}, {} 或 {}",
+                len, KYBER512_SECRETKEYBYTES, KYBER768_SECRETKEYBYTES, KYBER1024_SECRETKEYBYTES
+            ))),
+        }
+        Ok(KyberPrivateKeyWrapper(ZeroizingVec(bytes)))
+    }
+
+    /// 按变体ID解封 `rest` 开头的KEM密文，返回 (KEM密文长度, 共享密钥)
+    fn decapsulate(private_key: &KyberPrivateKeyWrapper, variant_id: u8, rest: &[u8]) -> Result<(usize, Vec<u8>), Error> {
+        if private_key.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
+        let result = match variant_id {
             1 => { // Kyber512
                 if private_key.0.len() != KYBER512_SECRETKEYBYTES {
                     return Err(Error::Key("私钥与密文的Kyber级别不匹配".to_string()));
@@ -206,6 +454,79 @@ impl AsymmetricCryptographicSystem for KyberCryptoSystem {
             },
             _ => return Err(Error::PostQuantum("未知的Kyber变体ID".to_string())),
         };
+        Ok(result)
+    }
+}
+
+impl AsymmetricCryptographicSystem for KyberCryptoSystem {
+    type PublicKey = KyberPublicKeyWrapper;
+    type PrivateKey = KyberPrivateKeyWrapper;
+    type CiphertextOutput = Base64String;
+    type Error = Error;
+    const SUPPORTS_ADDITIONAL_DATA: bool = true;
+    const ALGORITHM_ID: Option<&'static str> = Some("Kyber");
+    const SUPPORTS_DETERMINISTIC_RNG: bool = false;
+    
+    fn generate_keypair(config: &CryptoConfig) -> Result<(Self::PublicKey, Self::PrivateKey), Self::Error> {
+        let (public_key_vec, private_key_vec) = match config.kyber_parameter_k {
+            512 => {
+                let (pk, sk) = kyber512::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            768 => {
+                let (pk, sk) = kyber768::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            1024 => {
+                let (pk, sk) = kyber1024::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            k => return Err(Error::PostQuantum(format!("不支持的Kyber安全级别: {}", k))),
+        };
+
+        Ok(
+            (
+                KyberPublicKeyWrapper(public_key_vec),
+                KyberPrivateKeyWrapper(ZeroizingVec(private_key_vec)),
+            )
+        )
+    }
+    
+    fn encrypt(
+        public_key: &Self::PublicKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>, // AAD在KEM+DEM中不由KEM部分直接处理
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        let public_key = Self::parse_public_key(public_key)?;
+        Self::encrypt_with_parsed_key(&public_key, plaintext, additional_data)
+    }
+    
+    fn decrypt(
+        private_key: &Self::PrivateKey,
+        ciphertext: &str,
+        additional_data: Option<&[u8]>, // AAD在KEM+DEM中不由KEM部分直接处理
+    ) -> Result<Vec<u8>, Self::Error> {
+        // 解码Base64
+        let combined = from_base64(ciphertext)?;
+        
+        if combined.is_empty() {
+            return Err(Error::Format("密文为空".to_string()));
+        }
+
+        // 提取变体ID
+        let variant_id = combined[0];
+        let rest = &combined[1..];
+
+        let labeled_kdf = variant_id & LABELED_KDF_FLAG != 0;
+        let (kyber_ct_len, shared_secret_bytes) = Self::decapsulate(private_key, variant_id & !LABELED_KDF_FLAG, rest)?;
+        // 旧格式密文直接以共享密钥作为DEM密钥
+        let dem_key = if labeled_kdf {
+            Self::derive_dem_key(&shared_secret_bytes)
+        } else {
+            let mut raw = Zeroizing::new([0u8; 32]);
+            raw.copy_from_slice(&shared_secret_bytes);
+            raw
+        };
 
         // 提取nonce和AEAD密文
         if rest.len() < kyber_ct_len + 12 {
@@ -214,11 +535,11 @@ impl AsymmetricCryptographicSystem for KyberCryptoSystem {
         let nonce_bytes = &rest[kyber_ct_len..kyber_ct_len + 12];
         let aes_ciphertext = &rest[kyber_ct_len + 12..];
 
-        // 使用共享密钥执行AEAD解密
+        // 使用DEM密钥执行AEAD解密
         #[cfg(feature = "chacha")]
-        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&shared_secret_bytes));
+        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(dem_key.as_ref()));
         #[cfg(not(feature = "chacha"))]
-        let cipher = Aes256Gcm::new_from_slice(&shared_secret_bytes)
+        let cipher = Aes256Gcm::new_from_slice(dem_key.as_ref())
             .map_err(|e| Error::Operation(format!("创建AEAD解密器失败: {}", e)))?;
         #[cfg(feature = "chacha")]
         let nonce = ChaNonce::from_slice(nonce_bytes);
@@ -239,36 +560,30 @@ impl AsymmetricCryptographicSystem for KyberCryptoSystem {
         Ok(to_base64(&public_key.0))
     }
     
+    /// Kyber私钥中内嵌了对应的公钥，直接取出即可
+    fn public_key_from_private(private_key: &Self::PrivateKey) -> Result<Self::PublicKey, Error> {
+        Ok(KyberPublicKeyWrapper(Self::embedded_public_key(private_key)?.to_vec()))
+    }
+
+    fn public_key_matches_config(public_key: &Self::PublicKey, config: &CryptoConfig) -> Result<bool, Error> {
+        Ok(public_key.parameter_k()? == config.kyber_parameter_k)
+    }
+
     fn export_private_key(private_key: &Self::PrivateKey) -> Result<String, Self::Error> {
+        if private_key.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
         Ok(to_base64(private_key.0.as_ref()))
     }
     
     fn import_public_key(key_data: &str) -> Result<Self::PublicKey, Self::Error> {
-        let decoded = from_base64(key_data)?;
-        
-        match decoded.len() {
-            KYBER512_PUBLICKEYBYTES | KYBER768_PUBLICKEYBYTES | KYBER1024_PUBLICKEYBYTES => {},
-            len => return Err(Error::Key(format!(
-                "无效的Kyber公钥大小: {}字节, 预期值为 {}, {} 或 {}",
-                len, KYBER512_PUBLICKEYBYTES, KYBER768_PUBLICKEYBYTES, KYBER1024_PUBLICKEYBYTES
-            ))),
-        }
-        
-        Ok(KyberPublicKeyWrapper(decoded))
+        expect_key_kind(key_data, KeyKind::Public)?;
+        Self::public_key_from_vec(from_base64(key_data)?)
     }
     
     fn import_private_key(key_data: &str) -> Result<Self::PrivateKey, Self::Error> {
-        let decoded = from_base64(key_data)?;
-        
-        match decoded.len() {
-            KYBER512_SECRETKEYBYTES | KYBER768_SECRETKEYBYTES | KYBER1024_SECRETKEYBYTES => {},
-            len => return Err(Error::Key(format!(
-                "无效的Kyber私钥大小: {}字节, 预期值为 {}, {} 或 {}",
-                len, KYBER512_SECRETKEYBYTES, KYBER768_SECRETKEYBYTES, KYBER1024_SECRETKEYBYTES
-            ))),
-        }
-        
-        Ok(KyberPrivateKeyWrapper(ZeroizingVec(decoded)))
+        expect_key_kind(key_data, KeyKind::Private)?;
+        Self::private_key_from_vec(from_base64(key_data)?)
     }
 }
 
@@ -291,6 +606,7 @@ impl AsyncStreamingSystem for KyberCryptoSystem {
         let mut output_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
 
         loop {
+            config.check_cancelled()?;
             let read_bytes = reader.read(&mut buffer).await.map_err(Error::Io)?;
             if read_bytes == 0 {
                 break;
@@ -325,7 +641,7 @@ impl AsyncStreamingSystem for KyberCryptoSystem {
         private_key: &Self::PrivateKey,
         mut reader: R,
         mut writer: W,
-        _config: &StreamingConfig,
+        config: &StreamingConfig,
         additional_data: Option<&[u8]>,
     ) -> Result<StreamingResult, Error>
     where
@@ -334,9 +650,10 @@ impl AsyncStreamingSystem for KyberCryptoSystem {
     {
         let mut length_buffer = [0u8; 4];
         let mut bytes_processed = 0;
-        let mut output_buffer = if _config.keep_in_memory { Some(Vec::new()) } else { None };
+        let mut output_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
 
         while reader.read_exact(&mut length_buffer).await.is_ok() {
+            config.check_cancelled()?;
             let length = u32::from_le_bytes(length_buffer) as usize;
             let mut ciphertext_buffer = vec![0u8; length];
             reader.read_exact(&mut ciphertext_buffer).await.map_err(Error::Io)?;
@@ -367,13 +684,74 @@ impl AsyncStreamingSystem for KyberCryptoSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::utils::CryptoConfig;
+    use crate::common::utils::{key_fingerprint, CryptoConfig};
 
     fn setup_keys(k: usize) -> (KyberPublicKeyWrapper, KyberPrivateKeyWrapper) {
         let config = CryptoConfig { kyber_parameter_k: k, ..Default::default() };
         KyberCryptoSystem::generate_keypair(&config).unwrap()
     }
 
+    #[test]
+    fn test_kyber_security_bits() {
+        for (k, expected) in [(512, 128), (768, 192), (1024, 256)] {
+            let (pk, _) = setup_keys(k);
+            assert_eq!(pk.security_bits().unwrap(), expected, "Kyber{}", k);
+        }
+        assert!(KyberPublicKeyWrapper(vec![0u8; 100]).security_bits().is_err());
+    }
+
+    #[test]
+    fn test_kyber_owned_aad_matches_borrowed() {
+        let (pk, sk) = setup_keys(768);
+        let owned_aad = b"kyber aad".to_vec();
+
+        let ciphertext = KyberCryptoSystem::encrypt_owned_aad(&pk, b"owned", Some(owned_aad.clone())).unwrap();
+        let borrowed = KyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), Some(&owned_aad)).unwrap();
+        let owned = KyberCryptoSystem::decrypt_owned_aad(&sk, &ciphertext.to_string(), Some(owned_aad)).unwrap();
+        assert_eq!(borrowed, b"owned");
+        assert_eq!(borrowed, owned);
+    }
+
+    #[test]
+    fn test_sealed_box_roundtrip_all_levels() {
+        for &k in &[512, 768, 1024] {
+            let (public_key, private_key) = setup_keys(k);
+            let sealed = KyberCryptoSystem::seal_anonymous(&public_key, b"anonymous message").unwrap();
+            let opened = KyberCryptoSystem::open_anonymous(&private_key, &sealed.to_string()).unwrap();
+            assert_eq!(opened, b"anonymous message");
+            assert_eq!(KyberCryptoSystem::embedded_public_key(&private_key).unwrap(), public_key.0.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_sealed_box_uses_fresh_ephemeral_per_message() {
+        let (public_key, private_key) = setup_keys(768);
+        let first = KyberCryptoSystem::seal_anonymous(&public_key, b"same").unwrap();
+        let second = KyberCryptoSystem::seal_anonymous(&public_key, b"same").unwrap();
+
+        let kem_range = 1..1 + KYBER768_CIPHERTEXTBYTES;
+        assert_ne!(first.as_bytes()[kem_range.clone()], second.as_bytes()[kem_range]);
+        assert_ne!(first.as_bytes(), second.as_bytes());
+
+        for sealed in [&first, &second] {
+            assert_eq!(KyberCryptoSystem::open_anonymous(&private_key, &sealed.to_string()).unwrap(), b"same");
+        }
+    }
+
+    #[test]
+    fn test_sealed_box_wrong_recipient_or_tamper_fails() {
+        let (public_key, _) = setup_keys(768);
+        let (_, other_private_key) = setup_keys(768);
+        let sealed = KyberCryptoSystem::seal_anonymous(&public_key, b"for someone else").unwrap();
+        assert!(KyberCryptoSystem::open_anonymous(&other_private_key, &sealed.to_string()).is_err());
+
+        let (_, private_key_512) = setup_keys(512);
+        assert!(matches!(
+            KyberCryptoSystem::open_anonymous(&private_key_512, &sealed.to_string()),
+            Err(Error::Key(_))
+        ));
+    }
+
     #[test]
     fn test_kyber_roundtrip_all_levels() {
         for &k in &[512, 768, 1024] {
@@ -435,6 +813,35 @@ mod tests {
         assert!(result_dem_tamper.is_err());
     }
     
+    #[test]
+    fn test_kyber_malformed_ciphertext_returns_error() {
+        let (public_key, private_key) = setup_keys(512);
+        let valid = from_base64(&KyberCryptoSystem::encrypt(&public_key, b"fuzz", None).unwrap().to_string()).unwrap();
+        let sealed = from_base64(&KyberCryptoSystem::seal_anonymous(&public_key, b"fuzz").unwrap().to_string()).unwrap();
+
+        // 截断到任意长度（含只剩变体ID、KEM密文不完整、缺少 Nonce 或标签）
+        for len in 0..valid.len() {
+            let truncated = to_base64(&valid[..len]);
+            assert!(KyberCryptoSystem::decrypt(&private_key, &truncated, None).is_err(), "len {}", len);
+        }
+        for len in 0..sealed.len() {
+            assert!(KyberCryptoSystem::open_anonymous(&private_key, &to_base64(&sealed[..len])).is_err(), "len {}", len);
+        }
+
+        // 任意变体ID
+        for variant_id in 0..=u8::MAX {
+            let mut forged = valid.clone();
+            forged[0] = variant_id;
+            let result = KyberCryptoSystem::decrypt(&private_key, &to_base64(&forged), None);
+            assert_eq!(result.is_ok(), variant_id == valid[0]);
+        }
+
+        for text in ["", "@@@", "AA", "😀"] {
+            assert!(KyberCryptoSystem::decrypt(&private_key, text, None).is_err());
+            assert!(KyberCryptoSystem::open_anonymous(&private_key, text).is_err());
+        }
+    }
+
     #[test]
     fn test_kyber_decrypt_wrong_key_fails() {
         let (public_key_512, _) = setup_keys(512);
@@ -465,6 +872,32 @@ mod tests {
         assert_eq!(private_key, imported_priv);
     }
 
+    #[test]
+    fn test_kyber_import_swapped_key_kind_fails() {
+        for k in [512, 768, 1024] {
+            let (public_key, private_key) = setup_keys(k);
+            let exported_pub = KyberCryptoSystem::export_public_key(&public_key).unwrap();
+            let exported_priv = KyberCryptoSystem::export_private_key(&private_key).unwrap();
+
+            assert!(matches!(KyberCryptoSystem::import_public_key(&exported_priv), Err(Error::KeyImportFailed(_))));
+            assert!(matches!(KyberCryptoSystem::import_private_key(&exported_pub), Err(Error::KeyImportFailed(_))));
+        }
+    }
+
+    #[test]
+    fn test_kyber_key_pair_matches() {
+        for k in [512, 768, 1024] {
+            let (public_key, private_key) = setup_keys(k);
+            let (other_public_key, other_private_key) = setup_keys(k);
+            assert!(KyberCryptoSystem::key_pair_matches(&public_key, &private_key));
+            assert!(!KyberCryptoSystem::key_pair_matches(&public_key, &other_private_key));
+            assert!(!KyberCryptoSystem::key_pair_matches(&other_public_key, &private_key));
+        }
+        let (pk512, _) = setup_keys(512);
+        let (_, sk768) = setup_keys(768);
+        assert!(!KyberCryptoSystem::key_pair_matches(&pk512, &sk768));
+    }
+
     #[test]
     fn test_kyber_import_invalid_key_fails() {
         let invalid_b64_key = "not_a_valid_base64_string";
@@ -475,6 +908,107 @@ mod tests {
         assert!(KyberCryptoSystem::import_public_key(&wrong_size_key).is_err());
         assert!(KyberCryptoSystem::import_private_key(&wrong_size_key).is_err());
     }
+
+    /// 解析 NIST KAT `.rsp` 片段中 `name = HEX` 形式的字段
+    fn kat_field(rsp: &str, name: &str) -> Vec<u8> {
+        let hex = rsp.lines()
+            .find_map(|line| line.strip_prefix(name)?.trim_start().strip_prefix('='))
+            .unwrap_or_else(|| panic!("KAT 缺少字段 {}", name))
+            .trim();
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_dem_key_derivation_vector() {
+        // 固定共享密钥 00 01 .. 1f 下的期望 DEM 密钥，由 HKDF-SHA256 与公开的标签常量独立计算
+        let shared_secret: Vec<u8> = (0u8..32).collect();
+        #[cfg(not(feature = "chacha"))]
+        let expected = "ebe2351737b4eeaa426468111d18077ff46f141e6d4fe04351f9a9b2493ddbc6";
+        #[cfg(feature = "chacha")]
+        let expected = "632bddbe381ebcd6c4c5091c607386d22e08e1771041d0732534784bf778890c";
+        let derived: String = KyberCryptoSystem::derive_dem_key(&shared_secret).iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(derived, expected);
+        assert_ne!(KyberCryptoSystem::derive_dem_key(&[0u8; 32]).as_ref(), KyberCryptoSystem::derive_dem_key(&shared_secret).as_ref());
+    }
+
+    #[cfg(not(feature = "chacha"))]
+    #[test]
+    fn test_labeled_kdf_flag_and_legacy_ciphertext() {
+        let (public_key, private_key) = setup_keys(768);
+        let ciphertext = from_base64(&KyberCryptoSystem::encrypt(&public_key, b"new", None).unwrap().to_string()).unwrap();
+        assert_eq!(ciphertext[0], 2 | LABELED_KDF_FLAG);
+
+        // 旧格式：变体ID不带标记位，共享密钥直接作为 AES-256-GCM 密钥
+        let parsed = KyberCryptoSystem::parse_public_key(&public_key).unwrap();
+        let (variant_id, shared_secret, kem_ciphertext) = KyberCryptoSystem::encapsulate(&parsed);
+        let nonce = Aes256Gcm::generate_nonce(&mut SystemRng);
+        let body = Aes256Gcm::new_from_slice(&shared_secret).unwrap().encrypt(&nonce, &b"legacy"[..]).unwrap();
+        let legacy = [&[variant_id][..], &kem_ciphertext, &nonce, &body].concat();
+        assert_eq!(KyberCryptoSystem::decrypt(&private_key, &to_base64(&legacy), None).unwrap(), b"legacy");
+
+        // 把旧密文标记为标签化派生后无法解密
+        let mut relabeled = legacy;
+        relabeled[0] |= LABELED_KDF_FLAG;
+        assert!(KyberCryptoSystem::decrypt(&private_key, &to_base64(&relabeled), None).is_err());
+    }
+
+    #[test]
+    fn test_kyber512_nist_kat_raw_import_and_decapsulate() {
+        let rsp = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/kyber512_kat0.rsp"));
+        let (pk_bytes, sk_bytes) = (kat_field(rsp, "pk"), kat_field(rsp, "sk"));
+
+        let (pk, sk) = KyberCryptoSystem::import_raw(&pk_bytes, &sk_bytes).unwrap();
+        let (kem_len, shared_secret) = KyberCryptoSystem::decapsulate(&sk, 1, &kat_field(rsp, "ct")).unwrap();
+        assert_eq!(kem_len, KYBER512_CIPHERTEXTBYTES);
+        assert_eq!(shared_secret, kat_field(rsp, "ss"));
+
+        let (exported_pk, exported_sk) = KyberCryptoSystem::export_raw(&pk, &sk);
+        assert_eq!(exported_pk, pk_bytes);
+        assert_eq!(exported_sk.as_slice(), sk_bytes.as_slice());
+
+        // 导入的 KAT 密钥同样可用于本库的加解密
+        let ciphertext = KyberCryptoSystem::encrypt(&pk, b"kat", None).unwrap();
+        assert_eq!(KyberCryptoSystem::decrypt(&sk, &ciphertext.to_string(), None).unwrap(), b"kat");
+    }
+
+    #[test]
+    fn test_import_raw_rejects_invalid_keys() {
+        let (pk, sk) = setup_keys(512);
+        let (_, other_sk) = setup_keys(512);
+        assert!(KyberCryptoSystem::import_raw(&pk.0[1..], &sk.0).is_err());
+        assert!(KyberCryptoSystem::import_raw(&pk.0, &sk.0[1..]).is_err());
+        assert!(KyberCryptoSystem::import_raw(&pk.0, &other_sk.0).is_err());
+        assert!(KyberCryptoSystem::import_raw(&pk.0, &sk.0).is_ok());
+    }
+
+    #[test]
+    fn test_kyber_generate_vanity_key() {
+        let config = CryptoConfig { kyber_parameter_k: 512, ..Default::default() };
+        let (public_key, private_key) = KyberCryptoSystem::generate_vanity_key(&config, "f", 1024).unwrap();
+        assert!(key_fingerprint(&KyberCryptoSystem::export_public_key(&public_key).unwrap()).starts_with('f'));
+        let ciphertext = KyberCryptoSystem::encrypt(&public_key, b"vanity", None).unwrap();
+        assert_eq!(KyberCryptoSystem::decrypt(&private_key, &ciphertext.to_string(), None).unwrap(), b"vanity");
+
+        let result = KyberCryptoSystem::generate_vanity_key(&config, "fedcba9876543210", 4);
+        assert!(matches!(result, Err(Error::Operation(_))));
+    }
+
+    #[test]
+    fn test_zeroized_private_key_is_rejected() {
+        let (public_key, private_key) = setup_keys(768);
+        let ciphertext = KyberCryptoSystem::encrypt(&public_key, b"session", None).unwrap().to_string();
+        let sealed = KyberCryptoSystem::seal_anonymous(&public_key, b"session").unwrap().to_string();
+
+        let mut wiped = private_key.clone();
+        wiped.zeroize();
+        assert!(wiped.is_zeroized() && !private_key.is_zeroized());
+        assert!(matches!(KyberCryptoSystem::decrypt(&wiped, &ciphertext, None), Err(Error::KeyZeroized)));
+        assert!(matches!(KyberCryptoSystem::open_anonymous(&wiped, &sealed), Err(Error::KeyZeroized)));
+        assert!(matches!(KyberCryptoSystem::export_private_key(&wiped), Err(Error::KeyZeroized)));
+
+        assert_eq!(KyberCryptoSystem::decrypt(&private_key, &ciphertext, None).unwrap(), b"session");
+        private_key.zeroize_now();
+    }
 }
 
 #[cfg(all(test, feature = "async-engine"))]
@@ -1,8 +1,13 @@
 //! 传统加密算法模块
 //!
-//! 本模块包含RSA等传统加密算法的实现，提供符合CryptographicSystem特征的接口
+//! 本模块包含RSA等传统加密算法的实现，提供符合CryptographicSystem特征的接口；
+//! X25519 仅用于匿名密封盒。
 
 pub mod rsa;
+pub mod x25519;
+#[cfg(feature = "age-interop")]
+pub mod age;
 
 // 重新导出RSA系统，方便用户使用
-pub use rsa::RsaCryptoSystem; 
\ No newline at end of file
+pub use rsa::RsaCryptoSystem;
+pub use x25519::X25519CryptoSystem; 
\ No newline at end of file
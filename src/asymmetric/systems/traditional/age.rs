@@ -0,0 +1,419 @@
+//! `age` v1 文件格式互操作（`age-interop` 特性）
+//!
+//! 只支持 X25519 接收方：接收方编码为 `age1...`，身份（私钥）编码为 `AGE-SECRET-KEY-1...`，
+//! 密文为二进制 `age` 文件，头部含 `-> X25519` stanza 与 HMAC，载荷为 64 KiB 分块的 ChaCha20-Poly1305 STREAM。
+//! 生成的文件可由 `age`/`rage` 解密，反之亦然；不支持 ASCII 装甲与 scrypt 等其他 stanza 类型的解包。
+
+use base64::engine::general_purpose::STANDARD_NO_PAD;
+use base64::Engine as _;
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32, Hrp};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+use super::x25519::{X25519CryptoSystem, X25519PrivateKeyWrapper, X25519PublicKeyWrapper, X25519_KEY_BYTES};
+use crate::common::errors::Error;
+use crate::common::rng::{fill_random, SystemRng};
+use crate::common::utils::ZeroizingVec;
+
+/// `age` v1 文件的版本行
+const AGE_VERSION_LINE: &[u8] = b"age-encryption.org/v1\n";
+/// X25519 接收方的 bech32 HRP
+const AGE_RECIPIENT_HRP: &str = "age";
+/// X25519 身份的 bech32 HRP（编码时使用大写）
+const AGE_IDENTITY_HRP: &str = "age-secret-key-";
+/// X25519 stanza 包装密钥的 HKDF `info` 标签
+const AGE_X25519_LABEL: &[u8] = b"age-encryption.org/v1/X25519";
+/// stanza 正文每行的 Base64 字符数
+const AGE_COLUMNS: usize = 64;
+/// 文件密钥长度
+const AGE_FILE_KEY_BYTES: usize = 16;
+/// 载荷分块的明文大小
+const AGE_CHUNK_SIZE: usize = 64 * 1024;
+/// ChaCha20-Poly1305 标签长度
+const AGE_TAG_BYTES: usize = 16;
+
+impl X25519PublicKeyWrapper {
+    /// 编码为 `age` 接收方字符串（`age1...`）
+    pub fn to_age_recipient(&self) -> Result<String, Error> {
+        self.to_dalek()?;
+        bech32::encode_lower::<Bech32>(Hrp::parse_unchecked(AGE_RECIPIENT_HRP), &self.0)
+            .map_err(|e| Error::KeyExportFailed(format!("age接收方编码失败: {}", e)))
+    }
+
+    /// 解析 `age` 接收方字符串（`age1...`）
+    pub fn from_age_recipient(recipient: &str) -> Result<Self, Error> {
+        let bytes = decode_bech32(recipient, AGE_RECIPIENT_HRP)?;
+        let public_key = Self(bytes);
+        public_key.to_dalek()?;
+        Ok(public_key)
+    }
+}
+
+impl X25519PrivateKeyWrapper {
+    /// 编码为 `age` 身份字符串（`AGE-SECRET-KEY-1...`）
+    pub fn to_age_identity(&self) -> Result<Zeroizing<String>, Error> {
+        let secret = self.to_dalek()?;
+        let bytes = Zeroizing::new(secret.to_bytes());
+        bech32::encode_upper::<Bech32>(Hrp::parse_unchecked(AGE_IDENTITY_HRP), bytes.as_ref())
+            .map(Zeroizing::new)
+            .map_err(|e| Error::KeyExportFailed(format!("age身份编码失败: {}", e)))
+    }
+
+    /// 解析 `age` 身份字符串（`AGE-SECRET-KEY-1...`）
+    pub fn from_age_identity(identity: &str) -> Result<Self, Error> {
+        let private_key = Self(ZeroizingVec(decode_bech32(identity, AGE_IDENTITY_HRP)?));
+        private_key.to_dalek()?;
+        Ok(private_key)
+    }
+}
+
+/// 解码 bech32 字符串并检查 HRP（不区分大小写）
+fn decode_bech32(encoded: &str, expected_hrp: &str) -> Result<Vec<u8>, Error> {
+    let checked = CheckedHrpstring::new::<Bech32>(encoded)
+        .map_err(|e| Error::KeyImportFailed(format!("无效的bech32编码: {}", e)))?;
+    if checked.hrp().to_lowercase() != expected_hrp {
+        return Err(Error::KeyImportFailed(format!("bech32前缀应为 {}", expected_hrp)));
+    }
+    Ok(checked.byte_iter().collect())
+}
+
+/// 头部中的一个 stanza：`-> 类型 参数...` 与 Base64 正文
+struct Stanza<'a> {
+    args: Vec<&'a str>,
+    body: Vec<u8>,
+}
+
+/// 解析后的 `age` 头部
+struct Header<'a> {
+    stanzas: Vec<Stanza<'a>>,
+    /// 版本行至 `---` 为止的字节，即 HMAC 的输入
+    mac_input: &'a [u8],
+    mac: Vec<u8>,
+    /// 头部之后的载荷
+    payload: &'a [u8],
+}
+
+impl X25519CryptoSystem {
+    /// 以 `age` v1 文件格式加密给单个 X25519 接收方
+    ///
+    /// 与 [`seal_anonymous`](Self::seal_anonymous) 一样，每个文件使用新的临时密钥对，发送方保持匿名；
+    /// 但密钥派生与封装遵循 `age` 规范，两种格式的密文互不兼容。
+    pub fn to_age_format(recipient: &X25519PublicKeyWrapper, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let recipient_key = recipient.to_dalek()?;
+        let mut file_key = Zeroizing::new([0u8; AGE_FILE_KEY_BYTES]);
+        fill_random(file_key.as_mut());
+
+        let ephemeral_secret = StaticSecret::random_from_rng(SystemRng);
+        let ephemeral_public = PublicKey::from(&ephemeral_secret);
+        let shared_secret = ephemeral_secret.diffie_hellman(&recipient_key);
+        if !shared_secret.was_contributory() {
+            return Err(Error::Key("X25519接收方公钥为低阶点".to_string()));
+        }
+        let wrap_key = derive_wrap_key(shared_secret.as_bytes(), ephemeral_public.as_bytes(), recipient_key.as_bytes());
+        let wrapped = ChaCha20Poly1305::new(Key::from_slice(wrap_key.as_ref()))
+            .encrypt(&Nonce::default(), file_key.as_ref())
+            .map_err(|e| Error::EncryptionFailed(format!("age文件密钥封装失败: {}", e)))?;
+
+        let mut output = AGE_VERSION_LINE.to_vec();
+        output.extend_from_slice(b"-> X25519 ");
+        output.extend_from_slice(STANDARD_NO_PAD.encode(ephemeral_public.as_bytes()).as_bytes());
+        output.push(b'\n');
+        let body = STANDARD_NO_PAD.encode(&wrapped);
+        // 正文按 64 列折行，最后一行必须短于 64 列（必要时为空行）
+        for line in body.as_bytes().chunks(AGE_COLUMNS) {
+            output.extend_from_slice(line);
+            output.push(b'\n');
+        }
+        if body.len() % AGE_COLUMNS == 0 {
+            output.push(b'\n');
+        }
+        output.extend_from_slice(b"---");
+        let mac = header_mac(file_key.as_ref(), &output).finalize().into_bytes();
+        output.push(b' ');
+        output.extend_from_slice(STANDARD_NO_PAD.encode(mac).as_bytes());
+        output.push(b'\n');
+
+        let mut payload_nonce = [0u8; 16];
+        fill_random(&mut payload_nonce);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(derive_payload_key(file_key.as_ref(), &payload_nonce).as_ref()));
+        output.extend_from_slice(&payload_nonce);
+
+        let chunk_count = plaintext.len().div_ceil(AGE_CHUNK_SIZE).max(1);
+        for index in 0..chunk_count {
+            let start = index * AGE_CHUNK_SIZE;
+            let end = (start + AGE_CHUNK_SIZE).min(plaintext.len());
+            let nonce = chunk_nonce(index as u64, index + 1 == chunk_count)?;
+            let chunk = cipher.encrypt(&nonce, &plaintext[start..end])
+                .map_err(|e| Error::EncryptionFailed(format!("age载荷加密失败: {}", e)))?;
+            output.extend_from_slice(&chunk);
+        }
+        Ok(output)
+    }
+
+    /// 解密 `age` v1 文件
+    ///
+    /// 依次尝试头部中的每个 X25519 stanza，其他类型的 stanza 被忽略；格式错误的 X25519 stanza 直接报错。
+    /// 头部 HMAC 与每个载荷分块都经过校验，截断或被篡改的文件会被拒绝。
+    pub fn from_age_format(identity: &X25519PrivateKeyWrapper, age_file: &[u8]) -> Result<Vec<u8>, Error> {
+        let header = parse_header(age_file)?;
+        let secret = identity.to_dalek()?;
+        let identity_public = PublicKey::from(&secret);
+
+        let mut file_key = None;
+        for stanza in header.stanzas.iter().filter(|s| s.args[0] == "X25519") {
+            let share = match stanza.args.as_slice() {
+                [_, share] => decode_base64(share)?,
+                _ => return Err(Error::Format("X25519 stanza 应只有一个参数".to_string())),
+            };
+            let share: [u8; X25519_KEY_BYTES] = share.as_slice().try_into()
+                .map_err(|_| Error::Format("X25519 stanza 的临时公钥长度无效".to_string()))?;
+            if stanza.body.len() != AGE_FILE_KEY_BYTES + AGE_TAG_BYTES {
+                return Err(Error::Format("X25519 stanza 的正文长度无效".to_string()));
+            }
+
+            let ephemeral_public = PublicKey::from(share);
+            let shared_secret = secret.diffie_hellman(&ephemeral_public);
+            if !shared_secret.was_contributory() {
+                return Err(Error::Format("X25519 stanza 的临时公钥为低阶点".to_string()));
+            }
+            let wrap_key = derive_wrap_key(shared_secret.as_bytes(), &share, identity_public.as_bytes());
+            if let Ok(key) = ChaCha20Poly1305::new(Key::from_slice(wrap_key.as_ref()))
+                .decrypt(&Nonce::default(), stanza.body.as_slice())
+            {
+                file_key = Some(Zeroizing::new(key));
+                break;
+            }
+        }
+        let file_key = file_key.ok_or_else(|| Error::Key("age文件中没有与该身份匹配的 X25519 stanza".to_string()))?;
+
+        header_mac(&file_key, header.mac_input)
+            .verify_slice(&header.mac)
+            .map_err(|_| Error::Format("age头部 MAC 校验失败".to_string()))?;
+
+        let (payload_nonce, chunks) = header.payload.split_at_checked(16)
+            .ok_or_else(|| Error::StreamIntegrity("age载荷缺少 Nonce".to_string()))?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(derive_payload_key(&file_key, payload_nonce).as_ref()));
+        if chunks.is_empty() {
+            return Err(Error::StreamIntegrity("age载荷缺少最后一个分块".to_string()));
+        }
+
+        let chunk_count = chunks.len().div_ceil(AGE_CHUNK_SIZE + AGE_TAG_BYTES);
+        let mut plaintext = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.chunks(AGE_CHUNK_SIZE + AGE_TAG_BYTES).enumerate() {
+            let last = index + 1 == chunk_count;
+            let nonce = chunk_nonce(index as u64, last)?;
+            let decrypted = cipher.decrypt(&nonce, chunk)
+                .map_err(|_| Error::DecryptionFailed(format!("age载荷第 {} 个分块认证失败", index)))?;
+            if last && decrypted.is_empty() && index > 0 {
+                return Err(Error::Format("age载荷的最后一个分块不能为空".to_string()));
+            }
+            plaintext.extend_from_slice(&decrypted);
+        }
+        Ok(plaintext)
+    }
+}
+
+/// 由 X25519 共享密钥派生 stanza 包装密钥：`HKDF-SHA256(salt = 临时公钥 || 接收方公钥, IKM = 共享密钥, info = 标签)`
+fn derive_wrap_key(shared_secret: &[u8], ephemeral_public: &[u8], recipient_public: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut salt = [0u8; 2 * X25519_KEY_BYTES];
+    salt[..X25519_KEY_BYTES].copy_from_slice(ephemeral_public);
+    salt[X25519_KEY_BYTES..].copy_from_slice(recipient_public);
+    hkdf_expand(Some(&salt), shared_secret, AGE_X25519_LABEL)
+}
+
+/// 由文件密钥与载荷 Nonce 派生载荷密钥
+fn derive_payload_key(file_key: &[u8], payload_nonce: &[u8]) -> Zeroizing<[u8; 32]> {
+    hkdf_expand(Some(payload_nonce), file_key, b"payload")
+}
+
+/// 以文件密钥派生的 HMAC 密钥计算头部 MAC
+fn header_mac(file_key: &[u8], header: &[u8]) -> Hmac<Sha256> {
+    let mac_key = hkdf_expand(None, file_key, b"header");
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(mac_key.as_ref())
+        .expect("HMAC 接受任意长度的密钥");
+    mac.update(header);
+    mac
+}
+
+fn hkdf_expand(salt: Option<&[u8]>, ikm: &[u8], info: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut okm = Zeroizing::new([0u8; 32]);
+    Hkdf::<Sha256>::new(salt, ikm)
+        .expand(info, okm.as_mut())
+        .expect("32 字节输出远小于 HKDF-SHA256 的上限");
+    okm
+}
+
+/// STREAM 分块 Nonce：11 字节大端计数器 || 末块标志
+fn chunk_nonce(index: u64, last: bool) -> Result<Nonce, Error> {
+    if index >= 1 << 40 {
+        return Err(Error::NonceExhausted);
+    }
+    let mut nonce = Nonce::default();
+    nonce[3..11].copy_from_slice(&index.to_be_bytes());
+    nonce[11] = last as u8;
+    Ok(nonce)
+}
+
+/// 解码规范的无填充 Base64（`age` 拒绝非规范编码）
+fn decode_base64(encoded: &str) -> Result<Vec<u8>, Error> {
+    STANDARD_NO_PAD.decode(encoded)
+        .map_err(|e| Error::Format(format!("age头部中的Base64无效: {}", e)))
+}
+
+/// 读取一行（不含换行符），返回该行与其后的剩余数据
+fn split_line(data: &[u8]) -> Result<(&str, &[u8]), Error> {
+    let end = data.iter().position(|&b| b == b'\n')
+        .ok_or_else(|| Error::Format("age头部意外结束".to_string()))?;
+    let line = core::str::from_utf8(&data[..end])
+        .map_err(|_| Error::Format("age头部包含非UTF-8数据".to_string()))?;
+    Ok((line, &data[end + 1..]))
+}
+
+fn parse_header(data: &[u8]) -> Result<Header<'_>, Error> {
+    let mut rest = data.strip_prefix(AGE_VERSION_LINE)
+        .ok_or_else(|| Error::Format("不是 age v1 文件".to_string()))?;
+    let mut stanzas = Vec::new();
+    loop {
+        let line_start = data.len() - rest.len();
+        let (line, after) = split_line(rest)?;
+        if let Some(mac) = line.strip_prefix("--- ") {
+            if stanzas.is_empty() {
+                return Err(Error::Format("age头部没有任何 stanza".to_string()));
+            }
+            return Ok(Header {
+                stanzas,
+                mac_input: &data[..line_start + 3],
+                mac: decode_base64(mac)?,
+                payload: after,
+            });
+        }
+
+        let args: Vec<&str> = line.strip_prefix("-> ")
+            .ok_or_else(|| Error::Format(format!("无效的 age 头部行: {:?}", line)))?
+            .split(' ')
+            .collect();
+        if args.iter().any(|arg| arg.is_empty() || !arg.bytes().all(|b| b.is_ascii_graphic())) {
+            return Err(Error::Format("age stanza 的参数无效".to_string()));
+        }
+
+        let mut body = String::new();
+        rest = after;
+        loop {
+            let (line, after) = split_line(rest)?;
+            rest = after;
+            if line.len() > AGE_COLUMNS {
+                return Err(Error::Format("age stanza 正文行超过 64 列".to_string()));
+            }
+            body.push_str(line);
+            if line.len() < AGE_COLUMNS {
+                break;
+            }
+        }
+        stanzas.push(Stanza { args, body: decode_base64(&body)? });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 替换文件中唯一出现的一段字节
+    fn replace(file: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+        let at = file.windows(from.len()).position(|w| w == from).unwrap();
+        [&file[..at], to, &file[at + from.len()..]].concat()
+    }
+
+    #[test]
+    fn test_age_format_roundtrip() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        for len in [0, 1, 1000, AGE_CHUNK_SIZE, AGE_CHUNK_SIZE + 1, 2 * AGE_CHUNK_SIZE + 7] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let file = X25519CryptoSystem::to_age_format(&public_key, &plaintext).unwrap();
+            assert!(file.starts_with(b"age-encryption.org/v1\n-> X25519 "));
+            assert_eq!(X25519CryptoSystem::from_age_format(&private_key, &file).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_age_recipient_and_identity_roundtrip() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        let recipient = public_key.to_age_recipient().unwrap();
+        let identity = private_key.to_age_identity().unwrap();
+        assert!(recipient.starts_with("age1"));
+        assert!(identity.starts_with("AGE-SECRET-KEY-1"));
+
+        assert_eq!(X25519PublicKeyWrapper::from_age_recipient(&recipient).unwrap(), public_key);
+        assert_eq!(X25519PrivateKeyWrapper::from_age_identity(&identity).unwrap(), private_key);
+        assert!(X25519PublicKeyWrapper::from_age_recipient(&identity).is_err());
+        assert!(X25519PrivateKeyWrapper::from_age_identity(&recipient).is_err());
+    }
+
+    #[test]
+    fn test_age_format_decrypts_reference_file() {
+        // 由 age 的 Rust 实现（rage）针对下列身份加密 "hello from age\n" 生成，头部另含一个 grease stanza
+        let identity = X25519PrivateKeyWrapper::from_age_identity(
+            "AGE-SECRET-KEY-1KQH5ZXC8U7577P65M4Q0K83XLSQT0QQM3MNUJTUHYP5YS3Q2J8XQRHYJ6E",
+        ).unwrap();
+        let file = base64::engine::general_purpose::STANDARD.decode(REFERENCE_AGE_FILE).unwrap();
+        assert_eq!(X25519CryptoSystem::from_age_format(&identity, &file).unwrap(), b"hello from age\n");
+    }
+
+    #[test]
+    fn test_age_format_rejects_malformed_stanza() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        let file = X25519CryptoSystem::to_age_format(&public_key, b"stanza").unwrap();
+        let (_, after_version) = split_line(&file).unwrap();
+        let (stanza_line, _) = split_line(after_version).unwrap();
+        let share = stanza_line.strip_prefix("-> X25519 ").unwrap();
+
+        let malformed = [
+            replace(&file, stanza_line.as_bytes(), b"-> X25519"),
+            replace(&file, stanza_line.as_bytes(), format!("{} extra", stanza_line).as_bytes()),
+            replace(&file, share.as_bytes(), &share.as_bytes()[..40]),
+            replace(&file, share.as_bytes(), format!("{}=", share).as_bytes()),
+            replace(&file, b"-> X25519 ", b"X25519 "),
+            replace(&file, b"-> X25519 ", b"->  X25519 "),
+        ];
+        for file in &malformed {
+            assert!(matches!(X25519CryptoSystem::from_age_format(&private_key, file), Err(Error::Format(_))));
+        }
+
+        let (_, after_stanza) = split_line(after_version).unwrap();
+        let (body_line, _) = split_line(after_stanza).unwrap();
+        let overlong = replace(&file, format!("{}\n", body_line).as_bytes(), format!("{}\n", "A".repeat(65)).as_bytes());
+        let wrong_length = replace(&file, format!("{}\n", body_line).as_bytes(), b"AAAA\n");
+        for file in [&overlong, &wrong_length] {
+            assert!(matches!(X25519CryptoSystem::from_age_format(&private_key, file), Err(Error::Format(_))));
+        }
+    }
+
+    #[test]
+    fn test_age_format_rejects_wrong_identity_tampering_and_truncation() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        let (_, other_private_key) = X25519CryptoSystem::generate_keypair();
+        let plaintext = vec![7u8; AGE_CHUNK_SIZE + 10];
+        let file = X25519CryptoSystem::to_age_format(&public_key, &plaintext).unwrap();
+
+        assert!(matches!(X25519CryptoSystem::from_age_format(&other_private_key, &file), Err(Error::Key(_))));
+
+        let mut payload_tampered = file.clone();
+        *payload_tampered.last_mut().unwrap() ^= 1;
+        assert!(X25519CryptoSystem::from_age_format(&private_key, &payload_tampered).is_err());
+
+        let header_end = file.windows(4).position(|w| w == b"--- ").unwrap();
+        let mut header_tampered = file.clone();
+        header_tampered[header_end + 5] ^= 1;
+        assert!(X25519CryptoSystem::from_age_format(&private_key, &header_tampered).is_err());
+
+        // 去掉最后一个分块后，剩下的完整分块不带末块标志，必须认证失败
+        let truncated = &file[..file.len() - (10 + AGE_TAG_BYTES)];
+        assert!(X25519CryptoSystem::from_age_format(&private_key, truncated).is_err());
+    }
+
+    const REFERENCE_AGE_FILE: &str = "YWdlLWVuY3J5cHRpb24ub3JnL3YxCi0+IFgyNTUxOSAwTTlmcWpaWDIzUmtjL3NlNFRCanJMaTRIVXhNbjJ0SkJWTlJqUUZZOVFFCmo5eHpKaDZ3b2cxdXdYSENMT3k1YktIS0dHc1pYQmlHb04xeEpmQ1ROWGMKLT4gR35fLWdyZWFzZSBEZkVDOTEgayRjCjRzYUNHaWsrekNqbFc4akJ0cStJa1NTZDNVeko5NTlXeTVCc1M2bE5XWEtQSS9YbjNoQzZPQQotLS0gZ1RKMjBCbUdaaHAvRmxSd0tvcFlQc0prSmRGVElWYWR0a2RQZXFYYnBPNAohekDAB56TFGhLYljwsG0FRDxscrgjZAQgRLT3fHT5o12E1SPvp+Fo5MiLeXbnEg==";
+}
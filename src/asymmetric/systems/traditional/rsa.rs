@@ -1,21 +1,27 @@
-use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
+use rsa::{Oaep, Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
+use rsa::traits::PublicKeyParts;
 use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey};
 use rsa::pss::{SigningKey, VerifyingKey};
 use rsa::signature::{RandomizedSigner, SignatureEncoding, Verifier};
 use sha2::Sha256;
-use rsa::rand_core::OsRng as RsaOsRng;
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+use crate::asymmetric::key_kind::{expect_key_kind, KeyKind};
 use crate::asymmetric::traits::AsymmetricCryptographicSystem;
 #[cfg(feature = "async-engine")]
 use crate::asymmetric::traits::AsyncStreamingSystem;
 use crate::common::errors::Error;
+use crate::common::rng::SystemRng;
 #[cfg(feature = "async-engine")]
 use crate::common::streaming::StreamingConfig;
 #[cfg(feature = "async-engine")]
 use crate::common::streaming::StreamingResult;
 #[cfg(feature = "async-engine")]
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
-use crate::common::utils::{from_base64, Base64String, CryptoConfig, ZeroizingVec};
+use crate::common::utils::{constant_time_eq, from_base64, Base64String, Base64Variant, CryptoConfig, RsaPadding, ZeroizingVec};
+
+/// OAEP 密文前记录填充方案的标记字节；PKCS#1 v1.5 密文保持原有的裸格式，既有密文无需迁移
+const OAEP_PADDING_TAG: u8 = 0x4f;
 
 /// RSA公钥包装器，提供序列化支持
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -30,6 +36,45 @@ impl RsaPublicKeyWrapper {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// 按 RFC 7638 计算 JWK 指纹，可直接用作 JWK/JWT 中的 `kid`
+    ///
+    /// 对规范 JWK `{"e":..,"kty":"RSA","n":..}`（成员按字典序排列、无空白，`n`/`e` 为去除前导零的
+    /// base64url 编码）取 SHA-256，结果以无填充 base64url 表示。
+    pub fn jwk_thumbprint(&self) -> Result<String, Error> {
+        use rsa::traits::PublicKeyParts;
+        use sha2::Digest;
+
+        let public_key = RsaCryptoSystem::parse_public_key(self)?;
+        let encode = |value: &rsa::BigUint| Base64Variant::UrlSafeNoPad.encode(&value.to_bytes_be());
+        let canonical = format!(
+            r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#,
+            encode(public_key.e()),
+            encode(public_key.n()),
+        );
+        Ok(Base64Variant::UrlSafeNoPad.encode(&Sha256::digest(canonical.as_bytes())))
+    }
+
+    /// 估算此公钥的经典安全强度（比特），见 [`rsa_security_bits`]
+    pub fn security_bits(&self) -> Result<u32, Error> {
+        Ok(rsa_security_bits(self.modulus_bits()?))
+    }
+
+    /// 模数位数
+    pub fn modulus_bits(&self) -> Result<usize, Error> {
+        let public_key = RsaCryptoSystem::parse_public_key(self)?;
+        Ok(public_key.n().bits())
+    }
+}
+
+/// 按 NIST SP 800-57 第1部分表2 估算 `modulus_bits` 位RSA模数的经典安全强度，介于两档之间时向下取整
+///
+/// 1024 → 80、2048 → 112、3072 → 128、7680 → 192、15360 → 256；不足 1024 位时返回 0。
+pub fn rsa_security_bits(modulus_bits: usize) -> u32 {
+    const LEVELS: [(usize, u32); 5] = [(15360, 256), (7680, 192), (3072, 128), (2048, 112), (1024, 80)];
+    LEVELS.iter()
+        .find(|&&(bits, _)| modulus_bits >= bits)
+        .map_or(0, |&(_, security)| security)
 }
 
 /// RSA私钥包装器，提供序列化和安全擦除支持
@@ -45,6 +90,25 @@ impl RsaPrivateKeyWrapper {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// 立即清零并丢弃私钥，不必等到离开作用域
+    ///
+    /// 只清零这一份；此前 `clone` 出的副本不受影响。需要让仍被其他结构持有的私钥失效时，
+    /// 对其调用 [`Zeroize::zeroize`]，之后任何使用私钥的运算都返回 `Error::KeyZeroized`。
+    pub fn zeroize_now(mut self) {
+        self.zeroize();
+    }
+
+    /// 私钥是否已被清零
+    pub fn is_zeroized(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Zeroize for RsaPrivateKeyWrapper {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
 }
 
 /// RSA加密系统实现
@@ -62,11 +126,10 @@ impl RsaCryptoSystem {
     /// # 返回
     /// 成功时返回签名的字节向量
     pub fn sign(private_key: &RsaPrivateKeyWrapper, data: &[u8]) -> Result<Vec<u8>, Error> {
-        let rsa_private_key = RsaPrivateKey::from_pkcs8_der(&private_key.0)
-            .map_err(|e| Error::Traditional(format!("解析RSA私钥失败: {}", e)))?;
+        let rsa_private_key = Self::parse_private_key(private_key)?;
         
         let signing_key = SigningKey::<Sha256>::new(rsa_private_key);
-        let mut rng = RsaOsRng;
+        let mut rng = SystemRng;
         let signature = signing_key.sign_with_rng(&mut rng, data);
         Ok(signature.to_vec())
     }
@@ -97,6 +160,124 @@ impl RsaCryptoSystem {
             Err(_) => Ok(false),
         }
     }
+
+    /// 检查分别导入的公钥与私钥是否属于同一密钥对
+    ///
+    /// 先比较模数与公钥指数，再以随机探测数据执行一次加解密往返；任一密钥无法解析时返回 `false`。
+    /// 只返回布尔结果，不暴露私钥指数等任何私钥材料。
+    pub fn key_pair_matches(public_key: &RsaPublicKeyWrapper, private_key: &RsaPrivateKeyWrapper) -> bool {
+        use rsa::rand_core::RngCore;
+        use rsa::traits::PublicKeyParts;
+
+        let (Ok(public_key), Ok(private_key)) = (
+            Self::parse_public_key(public_key),
+            Self::parse_private_key(private_key),
+        ) else {
+            return false;
+        };
+        if public_key.n() != private_key.n() || public_key.e() != private_key.e() {
+            return false;
+        }
+
+        let mut probe = zeroize::Zeroizing::new([0u8; 32]);
+        SystemRng.fill_bytes(probe.as_mut());
+        public_key.encrypt(&mut SystemRng, Pkcs1v15Encrypt, probe.as_ref())
+            .and_then(|ciphertext| private_key.decrypt(Pkcs1v15Encrypt, &ciphertext))
+            .is_ok_and(|decrypted| decrypted.as_slice() == probe.as_ref())
+    }
+
+    /// 从RSA私钥推导对应的公钥
+    pub(crate) fn derive_public_key(private_key: &RsaPrivateKeyWrapper) -> Result<RsaPublicKeyWrapper, Error> {
+        let rsa_private_key = Self::parse_private_key(private_key)?;
+        let public_der = RsaPublicKey::from(&rsa_private_key).to_public_key_der()
+            .map_err(|e| Error::Traditional(format!("导出RSA公钥DER失败: {}", e)))?;
+        Ok(RsaPublicKeyWrapper(public_der.as_bytes().to_vec()))
+    }
+
+    /// 将PKCS#8 DER编码的私钥解析为可直接使用的RSA私钥
+    ///
+    /// 存储的 DER 包含 `p, q, dp, dq, qinv`，但 `rsa` 库不接受外部提供的CRT值：解析时总是由 `p, q`
+    /// 重新计算，解密与签名始终走CRT路径。存储的CRT字段只在导入时用于一致性校验，不会被直接复用。
+    pub(crate) fn parse_private_key(private_key: &RsaPrivateKeyWrapper) -> Result<RsaPrivateKey, Error> {
+        if private_key.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
+        RsaPrivateKey::from_pkcs8_der(&private_key.0)
+            .map_err(|e| Error::Traditional(format!("解析RSA私钥失败: {}", e)))
+    }
+
+    /// 两份PKCS#8私钥中的PKCS#1私钥结构是否逐字节相同，以常数时间比较
+    ///
+    /// 导入时用于确认外部提供的 `dp, dq, qinv` 与由素因子重新计算的值一致：其余字段直接取自输入，
+    /// 只有CRT字段来自重新计算。
+    fn same_private_key_fields(stored: &[u8], recomputed: &[u8]) -> bool {
+        let pkcs1 = |der| rsa::pkcs8::PrivateKeyInfo::try_from(der).map(|info| info.private_key);
+        matches!((pkcs1(stored), pkcs1(recomputed)), (Ok(stored), Ok(recomputed)) if constant_time_eq(stored, recomputed))
+    }
+
+    /// 将DER编码的公钥解析为可直接使用的RSA公钥
+    pub(crate) fn parse_public_key(public_key: &RsaPublicKeyWrapper) -> Result<RsaPublicKey, Error> {
+        RsaPublicKey::from_public_key_der(&public_key.0)
+            .map_err(|e| Error::Traditional(format!("解析RSA公钥失败: {}", e)))
+    }
+
+    /// 使用已解析的RSA公钥以指定填充方案加密数据
+    pub(crate) fn encrypt_with_parsed_key(public_key: &RsaPublicKey, plaintext: &[u8], padding: RsaPadding) -> Result<Base64String, Error> {
+        match padding {
+            RsaPadding::Pkcs1v15 => {
+                let ciphertext = public_key.encrypt(&mut SystemRng, Pkcs1v15Encrypt, plaintext)
+                    .map_err(|e| Error::Traditional(format!("RSA加密失败: {}", e)))?;
+                Ok(Base64String::from(ciphertext))
+            }
+            RsaPadding::Oaep => {
+                let block = public_key.encrypt(&mut SystemRng, Oaep::new::<Sha256>(), plaintext)
+                    .map_err(|e| Error::Traditional(format!("RSA加密失败: {}", e)))?;
+                let mut ciphertext = Vec::with_capacity(1 + block.len());
+                ciphertext.push(OAEP_PADDING_TAG);
+                ciphertext.extend_from_slice(&block);
+                Ok(Base64String::from(ciphertext))
+            }
+        }
+    }
+
+    /// 以指定填充方案加密，OAEP 密文前附加 1 字节标记，解密时据此识别填充
+    ///
+    /// PKCS#1 v1.5 的安全风险见 [`RsaPadding`]。
+    pub fn encrypt_with_padding(public_key: &RsaPublicKeyWrapper, plaintext: &[u8], padding: RsaPadding) -> Result<Base64String, Error> {
+        let public_key = Self::parse_public_key(public_key)?;
+        Self::encrypt_with_parsed_key(&public_key, plaintext, padding)
+    }
+
+    /// 解密，并要求密文记录的填充方案与 `padding` 一致
+    ///
+    /// 填充不符的密文在任何私钥运算之前即被拒绝，因此只接受 OAEP 的调用方不会暴露 PKCS#1 v1.5 解密。
+    pub fn decrypt_with_padding(private_key: &RsaPrivateKeyWrapper, ciphertext: &str, padding: RsaPadding) -> Result<Vec<u8>, Error> {
+        let private_key = Self::parse_private_key(private_key)?;
+        let ciphertext_bytes = from_base64(ciphertext)?;
+        let (recorded, block) = Self::split_padding(&private_key, &ciphertext_bytes)?;
+        if recorded != padding {
+            return Err(Error::Traditional(format!("密文使用 {:?} 填充，与要求的 {:?} 不符", recorded, padding)));
+        }
+        Self::decrypt_block(&private_key, block, padding)
+    }
+
+    /// 识别密文记录的填充方案：长度等于模数为 PKCS#1 v1.5，带标记字节且多出 1 字节为 OAEP
+    fn split_padding<'a>(private_key: &RsaPrivateKey, ciphertext: &'a [u8]) -> Result<(RsaPadding, &'a [u8]), Error> {
+        let modulus_len = private_key.size();
+        match ciphertext {
+            _ if ciphertext.len() == modulus_len => Ok((RsaPadding::Pkcs1v15, ciphertext)),
+            [OAEP_PADDING_TAG, block @ ..] if block.len() == modulus_len => Ok((RsaPadding::Oaep, block)),
+            _ => Err(Error::Traditional(format!("RSA密文长度 {} 字节与 {} 字节的模数不符", ciphertext.len(), modulus_len))),
+        }
+    }
+
+    fn decrypt_block(private_key: &RsaPrivateKey, block: &[u8], padding: RsaPadding) -> Result<Vec<u8>, Error> {
+        match padding {
+            RsaPadding::Oaep => private_key.decrypt(Oaep::new::<Sha256>(), block),
+            RsaPadding::Pkcs1v15 => private_key.decrypt(Pkcs1v15Encrypt, block),
+        }
+        .map_err(|e| Error::Traditional(format!("RSA解密失败: {}", e)))
+    }
 }
 
 impl AsymmetricCryptographicSystem for RsaCryptoSystem {
@@ -104,10 +285,12 @@ impl AsymmetricCryptographicSystem for RsaCryptoSystem {
     type PrivateKey = RsaPrivateKeyWrapper;
     type CiphertextOutput = Base64String;
     type Error = Error;
+
+    const ALGORITHM_ID: Option<&'static str> = Some("RSA");
     
     fn generate_keypair(config: &CryptoConfig) -> Result<(Self::PublicKey, Self::PrivateKey), Self::Error> {
         let bits = config.rsa_key_bits;
-        let mut rsa_rng = RsaOsRng;
+        let mut rsa_rng = SystemRng;
         
         let private_key = RsaPrivateKey::new(&mut rsa_rng, bits)
             .map_err(|e| Error::Traditional(format!("生成RSA密钥失败: {}", e)))?;
@@ -128,36 +311,46 @@ impl AsymmetricCryptographicSystem for RsaCryptoSystem {
         )
     }
     
+    /// 不携带配置的加密使用默认的 OAEP 填充；
+    /// 引擎经由 [`encrypt_with_config`](AsymmetricCryptographicSystem::encrypt_with_config) 按 `rsa_padding` 加密
     fn encrypt(
         public_key: &Self::PublicKey,
         plaintext: &[u8],
-        _additional_data: Option<&[u8]> // RSA PKCS#1 v1.5不使用附加数据
+        _additional_data: Option<&[u8]> // RSA不使用附加数据
     ) -> Result<Self::CiphertextOutput, Self::Error> {
-        // 从DER数据恢复公钥
-        let public_key = RsaPublicKey::from_public_key_der(&public_key.0)
-            .map_err(|e| Error::Traditional(format!("解析RSA公钥失败: {}", e)))?;
-        
-        let mut rng = RsaOsRng;
-        let ciphertext = public_key.encrypt(&mut rng, Pkcs1v15Encrypt, plaintext)
-            .map_err(|e| Error::Traditional(format!("RSA加密失败: {}", e)))?;
-        
-        Ok(Base64String::from(ciphertext))
+        Self::encrypt_with_padding(public_key, plaintext, RsaPadding::Oaep)
+    }
+
+    fn encrypt_with_config(
+        public_key: &Self::PublicKey,
+        plaintext: &[u8],
+        _additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        Self::encrypt_with_padding(public_key, plaintext, config.rsa_padding)
     }
     
+    /// 只接受 OAEP 密文
+    ///
+    /// 按密文长度猜测填充会把 PKCS#1 v1.5 解密暴露为填充预言，因此 v1.5 密文须通过
+    /// [`decrypt_with_config`](AsymmetricCryptographicSystem::decrypt_with_config)（`rsa_padding = Pkcs1v15`）
+    /// 或 [`decrypt_with_padding`](RsaCryptoSystem::decrypt_with_padding) 显式启用。
     fn decrypt(
         private_key: &Self::PrivateKey,
         ciphertext: &str,
-        _additional_data: Option<&[u8]> // RSA PKCS#1 v1.5不使用附加数据
+        _additional_data: Option<&[u8]> // RSA不使用附加数据
     ) -> Result<Vec<u8>, Self::Error> {
-        // 从DER数据恢复私钥
-        let private_key = RsaPrivateKey::from_pkcs8_der(&private_key.0)
-            .map_err(|e| Error::Traditional(format!("解析RSA私钥失败: {}", e)))?;
-        
-        // 使用公共函数解码Base64
-        let ciphertext_bytes = from_base64(ciphertext)?;
-        
-        private_key.decrypt(Pkcs1v15Encrypt, &ciphertext_bytes)
-            .map_err(|e| Error::Traditional(format!("RSA解密失败: {}", e)))
+        Self::decrypt_with_padding(private_key, ciphertext, RsaPadding::Oaep)
+    }
+
+    /// 只按 `config.rsa_padding` 解密，填充不符的密文被拒绝
+    fn decrypt_with_config(
+        private_key: &Self::PrivateKey,
+        ciphertext: &str,
+        _additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+    ) -> Result<Vec<u8>, Self::Error> {
+        Self::decrypt_with_padding(private_key, ciphertext, config.rsa_padding)
     }
     
     fn export_public_key(public_key: &Self::PublicKey) -> Result<String, Self::Error> {
@@ -170,10 +363,17 @@ impl AsymmetricCryptographicSystem for RsaCryptoSystem {
         Ok(pem)
     }
     
+    fn public_key_from_private(private_key: &Self::PrivateKey) -> Result<Self::PublicKey, Error> {
+        Self::derive_public_key(private_key)
+    }
+
+    fn public_key_matches_config(public_key: &Self::PublicKey, config: &CryptoConfig) -> Result<bool, Error> {
+        Ok(public_key.modulus_bits()? == config.rsa_key_bits)
+    }
+
     fn export_private_key(private_key: &Self::PrivateKey) -> Result<String, Self::Error> {
         // 从DER数据恢复私钥
-        let private_key = RsaPrivateKey::from_pkcs8_der(&private_key.0)
-            .map_err(|e| Error::Traditional(format!("解析RSA私钥失败: {}", e)))?;
+        let private_key = Self::parse_private_key(private_key)?;
         
         let pem = private_key.to_pkcs8_pem(rsa::pkcs8::LineEnding::LF)
             .map_err(|e| Error::Serialization(format!("RSA私钥导出失败: {}", e)))?
@@ -182,6 +382,7 @@ impl AsymmetricCryptographicSystem for RsaCryptoSystem {
     }
     
     fn import_public_key(key_data: &str) -> Result<Self::PublicKey, Self::Error> {
+        expect_key_kind(key_data, KeyKind::Public)?;
         let public_key = RsaPublicKey::from_public_key_pem(key_data)
             .map_err(|e| Error::Key(format!("导入RSA公钥失败: {}", e)))?;
             
@@ -192,11 +393,17 @@ impl AsymmetricCryptographicSystem for RsaCryptoSystem {
     }
     
     fn import_private_key(key_data: &str) -> Result<Self::PrivateKey, Self::Error> {
-        let private_key = RsaPrivateKey::from_pkcs8_pem(key_data)
+        expect_key_kind(key_data, KeyKind::Private)?;
+        let (_, document) = rsa::pkcs8::SecretDocument::from_pem(key_data)
+            .map_err(|e| Error::Key(format!("导入RSA私钥失败: {}", e)))?;
+        let private_key = RsaPrivateKey::from_pkcs8_der(document.as_bytes())
             .map_err(|e| Error::Key(format!("导入RSA私钥失败: {}", e)))?;
             
         let private_der = private_key.to_pkcs8_der()
             .map_err(|e| Error::Traditional(format!("导出RSA私钥DER失败: {}", e)))?;
+        if !Self::same_private_key_fields(document.as_bytes(), private_der.as_bytes()) {
+            return Err(Error::Key("导入RSA私钥失败: CRT参数 (dp, dq, qinv) 与素因子不一致".to_string()));
+        }
             
         Ok(RsaPrivateKeyWrapper(ZeroizingVec(private_der.as_bytes().to_vec())))
     }
@@ -221,6 +428,7 @@ impl AsyncStreamingSystem for RsaCryptoSystem {
         let mut output_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
 
         loop {
+            config.check_cancelled()?;
             let read_bytes = reader.read(&mut buffer).await.map_err(Error::Io)?;
             if read_bytes == 0 {
                 break;
@@ -255,7 +463,7 @@ impl AsyncStreamingSystem for RsaCryptoSystem {
         private_key: &Self::PrivateKey,
         mut reader: R,
         mut writer: W,
-        _config: &StreamingConfig,
+        config: &StreamingConfig,
         additional_data: Option<&[u8]>,
     ) -> Result<StreamingResult, Error>
     where
@@ -264,9 +472,10 @@ impl AsyncStreamingSystem for RsaCryptoSystem {
     {
         let mut length_buffer = [0u8; 4];
         let mut bytes_processed = 0;
-        let mut output_buffer = if _config.keep_in_memory { Some(Vec::new()) } else { None };
+        let mut output_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
 
         while reader.read_exact(&mut length_buffer).await.is_ok() {
+            config.check_cancelled()?;
             let length = u32::from_le_bytes(length_buffer) as usize;
             let mut ciphertext_buffer = vec![0u8; length];
             reader.read_exact(&mut ciphertext_buffer).await.map_err(Error::Io)?;
@@ -351,6 +560,50 @@ mod tests {
         assert!(!is_valid);
     }
 
+    #[test]
+    fn test_rsa_crt_params_preserved_on_import() {
+        let (public_key, private_key) = setup_keys();
+
+        // 导出再导入后，存储的编码（含 dp、dq、qinv）逐字节不变
+        let pem = RsaCryptoSystem::export_private_key(&private_key).unwrap();
+        let imported = RsaCryptoSystem::import_private_key(&pem).unwrap();
+        assert_eq!(imported.0.as_ref(), private_key.0.as_ref());
+
+        // 解析后的私钥带有CRT值，且与存储的 qinv 相同
+        let info = rsa::pkcs8::PrivateKeyInfo::try_from(imported.0.as_ref()).unwrap();
+        let stored = rsa::pkcs1::RsaPrivateKey::try_from(info.private_key).unwrap();
+        let parsed = RsaCryptoSystem::parse_private_key(&imported).unwrap();
+        assert_eq!(
+            parsed.crt_coefficient().unwrap(),
+            rsa::BigUint::from_bytes_be(stored.coefficient.as_bytes())
+        );
+
+        // 使用CRT参数解密的结果与原始私钥一致
+        let ciphertext = RsaCryptoSystem::encrypt(&public_key, b"crt", None).unwrap().to_string();
+        assert_eq!(RsaCryptoSystem::decrypt(&imported, &ciphertext, None).unwrap(), b"crt");
+        assert_eq!(
+            RsaCryptoSystem::decrypt(&imported, &ciphertext, None).unwrap(),
+            RsaCryptoSystem::decrypt(&private_key, &ciphertext, None).unwrap()
+        );
+
+        // PKCS#1 编码的最后一个字段是 qinv，篡改后导入被拒绝
+        let mut corrupted = private_key.0.as_ref().to_vec();
+        *corrupted.last_mut().unwrap() ^= 1;
+        let corrupted_pem = rsa::pkcs8::der::pem::encode_string("PRIVATE KEY", rsa::pkcs8::LineEnding::LF, &corrupted).unwrap();
+        assert!(matches!(RsaCryptoSystem::import_private_key(&corrupted_pem), Err(Error::Key(_))));
+    }
+
+    #[test]
+    fn test_rsa_key_pair_matches() {
+        let (public_key, private_key) = setup_keys();
+        let (other_public_key, other_private_key) = setup_keys();
+        assert!(RsaCryptoSystem::key_pair_matches(&public_key, &private_key));
+        assert!(RsaCryptoSystem::key_pair_matches(&other_public_key, &other_private_key));
+        assert!(!RsaCryptoSystem::key_pair_matches(&public_key, &other_private_key));
+        assert!(!RsaCryptoSystem::key_pair_matches(&other_public_key, &private_key));
+        assert!(!RsaCryptoSystem::key_pair_matches(&RsaPublicKeyWrapper(vec![0u8; 16]), &private_key));
+    }
+
     #[test]
     fn test_rsa_decrypt_wrong_key_fails() {
         let (public_key, _) = setup_keys();
@@ -394,6 +647,16 @@ mod tests {
         assert_eq!(private_key, imported_priv);
     }
 
+    #[test]
+    fn test_rsa_import_swapped_key_kind_fails() {
+        let (public_key, private_key) = setup_keys();
+        let exported_pub = RsaCryptoSystem::export_public_key(&public_key).unwrap();
+        let exported_priv = RsaCryptoSystem::export_private_key(&private_key).unwrap();
+
+        assert!(matches!(RsaCryptoSystem::import_public_key(&exported_priv), Err(Error::KeyImportFailed(_))));
+        assert!(matches!(RsaCryptoSystem::import_private_key(&exported_pub), Err(Error::KeyImportFailed(_))));
+    }
+
     #[test]
     fn test_rsa_import_invalid_key_fails() {
         let invalid_pem = "-----BEGIN PUBLIC KEY-----\nINVALID_KEY_DATA\n-----END PUBLIC KEY-----";
@@ -404,6 +667,110 @@ mod tests {
         let priv_result = RsaCryptoSystem::import_private_key(invalid_priv_pem);
         assert!(priv_result.is_err());
     }
+
+    #[test]
+    fn test_jwk_thumbprint_rfc7638_vector() {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        use rsa::BigUint;
+
+        // RFC 7638 第 3.1 节示例密钥
+        let n = "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw";
+        let public_key = RsaPublicKey::new(
+            BigUint::from_bytes_be(&URL_SAFE_NO_PAD.decode(n).unwrap()),
+            BigUint::from_bytes_be(&URL_SAFE_NO_PAD.decode("AQAB").unwrap()),
+        ).unwrap();
+        let wrapper = RsaPublicKeyWrapper(public_key.to_public_key_der().unwrap().as_bytes().to_vec());
+        assert_eq!(wrapper.jwk_thumbprint().unwrap(), "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs");
+
+        let (public_key, _) = setup_keys();
+        let (other_public_key, _) = setup_keys();
+        assert_eq!(public_key.jwk_thumbprint().unwrap(), public_key.clone().jwk_thumbprint().unwrap());
+        assert_ne!(public_key.jwk_thumbprint().unwrap(), other_public_key.jwk_thumbprint().unwrap());
+        assert!(RsaPublicKeyWrapper(vec![1, 2, 3]).jwk_thumbprint().is_err());
+    }
+
+    #[test]
+    fn test_rsa_security_bits() {
+        let (public_key, _) = setup_keys();
+        assert_eq!(public_key.security_bits().unwrap(), 112);
+
+        for (bits, expected) in [(512, 0), (1024, 80), (2047, 80), (2048, 112), (3072, 128), (4096, 128), (7680, 192), (15360, 256), (16384, 256)] {
+            assert_eq!(rsa_security_bits(bits), expected, "bits = {}", bits);
+        }
+        assert!(RsaPublicKeyWrapper(vec![1, 2, 3]).security_bits().is_err());
+    }
+
+    #[test]
+    fn test_rsa_padding_roundtrip() {
+        let (public_key, private_key) = setup_keys();
+        let plaintext = b"padding choice";
+
+        for padding in [RsaPadding::Oaep, RsaPadding::Pkcs1v15] {
+            let ciphertext = RsaCryptoSystem::encrypt_with_padding(&public_key, plaintext, padding).unwrap().to_string();
+            assert_eq!(RsaCryptoSystem::decrypt_with_padding(&private_key, &ciphertext, padding).unwrap(), plaintext);
+            let config = CryptoConfig { rsa_padding: padding, ..Default::default() };
+            assert_eq!(RsaCryptoSystem::decrypt_with_config(&private_key, &ciphertext, None, &config).unwrap(), plaintext);
+        }
+
+        // 引擎使用的配置路径默认采用 OAEP
+        let ciphertext = RsaCryptoSystem::encrypt_with_config(&public_key, plaintext, None, &CryptoConfig::default()).unwrap();
+        assert_eq!(ciphertext.as_ref().len(), 1 + 256);
+        assert_eq!(RsaCryptoSystem::decrypt_with_padding(&private_key, &ciphertext.to_string(), RsaPadding::Oaep).unwrap(), plaintext);
+
+        // 不携带配置的加解密同样使用 OAEP
+        let default = RsaCryptoSystem::encrypt(&public_key, plaintext, None).unwrap();
+        assert_eq!(default.as_ref()[0], OAEP_PADDING_TAG);
+        assert_eq!(RsaCryptoSystem::decrypt(&private_key, &default.to_string(), None).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_rsa_pkcs1v15_decryption_requires_explicit_opt_in() {
+        let (public_key, private_key) = setup_keys();
+        let legacy = RsaCryptoSystem::encrypt_with_padding(&public_key, b"legacy", RsaPadding::Pkcs1v15).unwrap().to_string();
+
+        // 默认配置与不携带配置的解密都不会对 v1.5 密文执行私钥运算
+        assert!(RsaCryptoSystem::decrypt(&private_key, &legacy, None).is_err());
+        assert!(RsaCryptoSystem::decrypt_with_config(&private_key, &legacy, None, &CryptoConfig::default()).is_err());
+
+        let legacy_config = CryptoConfig { rsa_padding: RsaPadding::Pkcs1v15, ..Default::default() };
+        assert_eq!(RsaCryptoSystem::decrypt_with_config(&private_key, &legacy, None, &legacy_config).unwrap(), b"legacy");
+        let oaep = RsaCryptoSystem::encrypt(&public_key, b"modern", None).unwrap().to_string();
+        assert!(RsaCryptoSystem::decrypt_with_config(&private_key, &oaep, None, &legacy_config).is_err());
+    }
+
+    #[test]
+    fn test_rsa_cross_padding_fails() {
+        let (public_key, private_key) = setup_keys();
+        let plaintext = b"padding choice";
+
+        let oaep = RsaCryptoSystem::encrypt_with_padding(&public_key, plaintext, RsaPadding::Oaep).unwrap();
+        let pkcs1 = RsaCryptoSystem::encrypt_with_padding(&public_key, plaintext, RsaPadding::Pkcs1v15).unwrap();
+        assert!(RsaCryptoSystem::decrypt_with_padding(&private_key, &oaep.to_string(), RsaPadding::Pkcs1v15).is_err());
+        assert!(RsaCryptoSystem::decrypt_with_padding(&private_key, &pkcs1.to_string(), RsaPadding::Oaep).is_err());
+
+        // 去掉标记字节冒充 v1.5 密文，或给 v1.5 密文加上标记，都无法解密
+        let stripped = Base64String::from(oaep.as_ref()[1..].to_vec()).to_string();
+        assert!(RsaCryptoSystem::decrypt(&private_key, &stripped, None).is_err());
+        let mut tagged = vec![OAEP_PADDING_TAG];
+        tagged.extend_from_slice(pkcs1.as_ref());
+        assert!(RsaCryptoSystem::decrypt(&private_key, &Base64String::from(tagged).to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_zeroized_private_key_is_rejected() {
+        let (public_key, private_key) = setup_keys();
+        let ciphertext = RsaCryptoSystem::encrypt(&public_key, b"session", None).unwrap().to_string();
+
+        let mut wiped = private_key.clone();
+        wiped.zeroize();
+        assert!(wiped.is_zeroized() && !private_key.is_zeroized());
+        assert!(matches!(RsaCryptoSystem::decrypt(&wiped, &ciphertext, None), Err(Error::KeyZeroized)));
+        assert!(matches!(RsaCryptoSystem::sign(&wiped, b"session"), Err(Error::KeyZeroized)));
+        assert!(matches!(RsaCryptoSystem::export_private_key(&wiped), Err(Error::KeyZeroized)));
+
+        assert_eq!(RsaCryptoSystem::decrypt(&private_key, &ciphertext, None).unwrap(), b"session");
+        private_key.zeroize_now();
+    }
 }
 
 #[cfg(all(test, feature = "async-engine"))]
@@ -0,0 +1,238 @@
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::{Zeroize, Zeroizing};
+use crate::common::errors::Error;
+use crate::common::rng::SystemRng;
+use crate::common::utils::{from_base64, Base64String, ZeroizingVec};
+
+/// X25519 公钥与私钥的字节长度
+pub const X25519_KEY_BYTES: usize = 32;
+
+/// 密封盒密钥与 Nonce 派生的 HKDF `info` 标签
+const SEALED_BOX_LABEL: &[u8] = b"seal-kit-x25519-sealed-box-v1";
+
+/// X25519公钥包装器
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct X25519PublicKeyWrapper(pub Vec<u8>);
+
+impl X25519PublicKeyWrapper {
+    /// 获取公钥字节
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub(crate) fn to_dalek(&self) -> Result<PublicKey, Error> {
+        let bytes: [u8; X25519_KEY_BYTES] = self.0.as_slice().try_into()
+            .map_err(|_| Error::Key(format!("无效的X25519公钥长度: {}", self.0.len())))?;
+        Ok(PublicKey::from(bytes))
+    }
+}
+
+/// X25519私钥包装器
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct X25519PrivateKeyWrapper(pub ZeroizingVec);
+
+impl X25519PrivateKeyWrapper {
+    /// 立即清零并丢弃私钥，不必等到离开作用域
+    pub fn zeroize_now(mut self) {
+        self.zeroize();
+    }
+
+    /// 私钥是否已被清零
+    pub fn is_zeroized(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 由私钥计算对应的公钥
+    pub fn public_key(&self) -> Result<X25519PublicKeyWrapper, Error> {
+        let secret = self.to_dalek()?;
+        Ok(X25519PublicKeyWrapper(PublicKey::from(&secret).as_bytes().to_vec()))
+    }
+
+    pub(crate) fn to_dalek(&self) -> Result<StaticSecret, Error> {
+        if self.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
+        let mut bytes: [u8; X25519_KEY_BYTES] = self.0.as_ref().try_into()
+            .map_err(|_| Error::Key(format!("无效的X25519私钥长度: {}", self.0.len())))?;
+        let secret = StaticSecret::from(bytes);
+        bytes.zeroize();
+        Ok(secret)
+    }
+}
+
+impl Zeroize for X25519PrivateKeyWrapper {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// X25519 密钥协商系统
+///
+/// 仅提供匿名发送方的密封盒；X25519 不能直接加密数据，因此不实现 `AsymmetricCryptographicSystem`。
+pub struct X25519CryptoSystem;
+
+impl X25519CryptoSystem {
+    /// 生成X25519密钥对
+    pub fn generate_keypair() -> (X25519PublicKeyWrapper, X25519PrivateKeyWrapper) {
+        let secret = StaticSecret::random_from_rng(SystemRng);
+        let public_key = PublicKey::from(&secret);
+        (
+            X25519PublicKeyWrapper(public_key.as_bytes().to_vec()),
+            X25519PrivateKeyWrapper(ZeroizingVec(secret.to_bytes().to_vec())),
+        )
+    }
+
+    /// 匿名发送方的"密封盒"加密
+    ///
+    /// 每条消息生成一个新的临时X25519密钥对，与接收方公钥协商出共享密钥后立即丢弃临时私钥，
+    /// 发送方不持有也不暴露任何长期密钥。ChaCha20-Poly1305 的密钥与 Nonce 由
+    /// `HKDF-SHA256(salt = 临时公钥 || 接收方公钥, IKM = 共享密钥, info = 标签)` 派生，
+    /// 因此密文与接收方绑定。输出格式为 `临时公钥(32字节) || AEAD密文`。
+    pub fn seal_anonymous(recipient: &X25519PublicKeyWrapper, plaintext: &[u8]) -> Result<Base64String, Error> {
+        let recipient_key = recipient.to_dalek()?;
+        let ephemeral_secret = StaticSecret::random_from_rng(SystemRng);
+        let ephemeral_public = PublicKey::from(&ephemeral_secret);
+        let shared_secret = ephemeral_secret.diffie_hellman(&recipient_key);
+        if !shared_secret.was_contributory() {
+            return Err(Error::Key("X25519接收方公钥为低阶点".to_string()));
+        }
+
+        let sealed = Self::sealed_box_aead(
+            shared_secret.as_bytes(),
+            ephemeral_public.as_bytes(),
+            recipient_key.as_bytes(),
+            plaintext,
+            true,
+        )?;
+
+        let mut combined = ephemeral_public.as_bytes().to_vec();
+        combined.extend_from_slice(&sealed);
+        Ok(Base64String::from(combined))
+    }
+
+    /// 打开 [`seal_anonymous`](Self::seal_anonymous) 生成的密封盒
+    pub fn open_anonymous(recipient: &X25519PrivateKeyWrapper, sealed: &str) -> Result<Vec<u8>, Error> {
+        let combined = from_base64(sealed)?;
+        if combined.len() < X25519_KEY_BYTES + 16 {
+            return Err(Error::Format("X25519密封盒过短".to_string()));
+        }
+        let (ephemeral_bytes, sealed_body) = combined.split_at(X25519_KEY_BYTES);
+        let ephemeral_public = X25519PublicKeyWrapper(ephemeral_bytes.to_vec()).to_dalek()?;
+
+        let secret = recipient.to_dalek()?;
+        let recipient_public = PublicKey::from(&secret);
+        let shared_secret = secret.diffie_hellman(&ephemeral_public);
+        if !shared_secret.was_contributory() {
+            return Err(Error::Format("X25519临时公钥为低阶点".to_string()));
+        }
+
+        Self::sealed_box_aead(
+            shared_secret.as_bytes(),
+            ephemeral_public.as_bytes(),
+            recipient_public.as_bytes(),
+            sealed_body,
+            false,
+        )
+    }
+
+    /// 由共享密钥派生密封盒的对称密钥与 Nonce，并执行AEAD加密（`seal` 为真）或解密
+    fn sealed_box_aead(
+        shared_secret: &[u8],
+        ephemeral_public: &[u8],
+        recipient_public: &[u8],
+        data: &[u8],
+        seal: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let mut salt = [0u8; 2 * X25519_KEY_BYTES];
+        salt[..X25519_KEY_BYTES].copy_from_slice(ephemeral_public);
+        salt[X25519_KEY_BYTES..].copy_from_slice(recipient_public);
+
+        // 32 字节密钥 || 12 字节 Nonce
+        let mut okm = Zeroizing::new([0u8; 44]);
+        Hkdf::<Sha256>::new(Some(&salt), shared_secret)
+            .expand(SEALED_BOX_LABEL, okm.as_mut())
+            .expect("44 字节输出远小于 HKDF-SHA256 的上限");
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&okm[..32]));
+        let nonce = Nonce::from_slice(&okm[32..]);
+        if seal {
+            cipher.encrypt(nonce, data)
+                .map_err(|e| Error::Traditional(format!("AEAD加密失败: {}", e)))
+        } else {
+            cipher.decrypt(nonce, data)
+                .map_err(|e| Error::Traditional(format!("AEAD解密失败: {}", e)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_box_roundtrip() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        assert_eq!(private_key.public_key().unwrap(), public_key);
+
+        let sealed = X25519CryptoSystem::seal_anonymous(&public_key, b"anonymous message").unwrap();
+        let opened = X25519CryptoSystem::open_anonymous(&private_key, &sealed.to_string()).unwrap();
+        assert_eq!(opened, b"anonymous message");
+
+        let empty = X25519CryptoSystem::seal_anonymous(&public_key, b"").unwrap();
+        assert!(X25519CryptoSystem::open_anonymous(&private_key, &empty.to_string()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sealed_box_uses_fresh_ephemeral_per_message() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        let first = X25519CryptoSystem::seal_anonymous(&public_key, b"same").unwrap();
+        let second = X25519CryptoSystem::seal_anonymous(&public_key, b"same").unwrap();
+
+        let ephemeral = 0..X25519_KEY_BYTES;
+        assert_ne!(first.as_bytes()[ephemeral.clone()], second.as_bytes()[ephemeral.clone()]);
+        assert_ne!(first.as_bytes()[ephemeral], public_key.0[..]);
+        assert_ne!(first.as_bytes(), second.as_bytes());
+
+        for sealed in [&first, &second] {
+            assert_eq!(X25519CryptoSystem::open_anonymous(&private_key, &sealed.to_string()).unwrap(), b"same");
+        }
+    }
+
+    #[test]
+    fn test_sealed_box_wrong_recipient_or_tamper_fails() {
+        let (public_key, private_key) = X25519CryptoSystem::generate_keypair();
+        let (_, other_private_key) = X25519CryptoSystem::generate_keypair();
+        let sealed = X25519CryptoSystem::seal_anonymous(&public_key, b"for someone else").unwrap();
+        assert!(X25519CryptoSystem::open_anonymous(&other_private_key, &sealed.to_string()).is_err());
+
+        let mut tampered = sealed.as_bytes().to_vec();
+        *tampered.last_mut().unwrap() ^= 1;
+        let tampered = Base64String::from(tampered);
+        assert!(X25519CryptoSystem::open_anonymous(&private_key, &tampered.to_string()).is_err());
+
+        let truncated = Base64String::from(sealed.as_bytes()[..X25519_KEY_BYTES].to_vec());
+        assert!(matches!(
+            X25519CryptoSystem::open_anonymous(&private_key, &truncated.to_string()),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_sealed_box_rejects_low_order_and_zeroized_keys() {
+        let low_order = X25519PublicKeyWrapper(vec![0u8; X25519_KEY_BYTES]);
+        assert!(matches!(X25519CryptoSystem::seal_anonymous(&low_order, b"x"), Err(Error::Key(_))));
+
+        let (public_key, mut private_key) = X25519CryptoSystem::generate_keypair();
+        let sealed = X25519CryptoSystem::seal_anonymous(&public_key, b"x").unwrap();
+        private_key.zeroize();
+        assert!(matches!(
+            X25519CryptoSystem::open_anonymous(&private_key, &sealed.to_string()),
+            Err(Error::KeyZeroized)
+        ));
+    }
+}
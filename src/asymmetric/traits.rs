@@ -21,6 +21,21 @@ pub trait AsymmetricCryptographicSystem: Sized {
     
     /// 错误类型
     type Error: std::error::Error;
+
+    /// `encrypt`/`decrypt` 是否使用并认证附加数据；不支持的系统会忽略传入的附加数据
+    ///
+    /// 默认为 `false`，确实认证附加数据的系统须显式声明，避免忽略附加数据的实现被当作受保护的。
+    const SUPPORTS_ADDITIONAL_DATA: bool = false;
+
+    /// 引擎写入密文信封认证头部 [`ALGORITHM_HEADER`](crate::common::inspect::ALGORITHM_HEADER) 的算法标识
+    ///
+    /// 默认为 `None`，此时引擎不写入该字段；只有支持附加数据的系统才能写入受认证的标识。
+    const ALGORITHM_ID: Option<&'static str> = None;
+
+    /// 密钥生成与加密是否只使用本库的RNG，从而能由 `CryptoConfig::deterministic_seed` 固定
+    ///
+    /// 依赖外部库内部RNG的系统须声明为 `false`，设置了种子时引擎拒绝创建，避免金样测试悄然失去确定性。
+    const SUPPORTS_DETERMINISTIC_RNG: bool = true;
     
     /// 生成密钥对
     fn generate_keypair(config: &CryptoConfig) -> Result<(Self::PublicKey, Self::PrivateKey), Self::Error>;
@@ -38,6 +53,50 @@ pub trait AsymmetricCryptographicSystem: Sized {
         ciphertext: &str, 
         additional_data: Option<&[u8]>
     ) -> Result<Vec<u8>, Self::Error>;
+
+    /// 按加密配置加密数据，默认忽略配置、等同于 [`encrypt`](Self::encrypt)
+    ///
+    /// 引擎经由此方法加密，使具体系统能够响应与算法相关的配置项（如RSA的 `rsa_padding`）。
+    fn encrypt_with_config(
+        public_key: &Self::PublicKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        let _ = config;
+        Self::encrypt(public_key, plaintext, additional_data)
+    }
+
+    /// 按加密配置解密数据，默认忽略配置、等同于 [`decrypt`](Self::decrypt)
+    ///
+    /// 引擎经由此方法解密，使具体系统只接受配置所选的方案（如RSA只按 `rsa_padding` 解密）。
+    fn decrypt_with_config(
+        private_key: &Self::PrivateKey,
+        ciphertext: &str,
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let _ = config;
+        Self::decrypt(private_key, ciphertext, additional_data)
+    }
+
+    /// 使用公钥加密数据，附加认证数据以所有权形式传入
+    fn encrypt_owned_aad(
+        public_key: &Self::PublicKey,
+        plaintext: &[u8],
+        additional_data: Option<Vec<u8>>
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        Self::encrypt(public_key, plaintext, additional_data.as_deref())
+    }
+
+    /// 使用私钥解密数据，附加认证数据以所有权形式传入
+    fn decrypt_owned_aad(
+        private_key: &Self::PrivateKey,
+        ciphertext: &str,
+        additional_data: Option<Vec<u8>>
+    ) -> Result<Vec<u8>, Self::Error> {
+        Self::decrypt(private_key, ciphertext, additional_data.as_deref())
+    }
     
     /// 将公钥导出为标准格式
     fn export_public_key(public_key: &Self::PublicKey) -> Result<String, Self::Error>;
@@ -50,6 +109,43 @@ pub trait AsymmetricCryptographicSystem: Sized {
     
     /// 从标准格式导入私钥
     fn import_private_key(key_data: &str) -> Result<Self::PrivateKey, Self::Error>;
+
+    /// 由私钥推导对应的公钥；无法推导的系统返回 `Error::Operation`
+    fn public_key_from_private(_private_key: &Self::PrivateKey) -> Result<Self::PublicKey, Error>
+    where
+        Error: From<Self::Error>,
+    {
+        Err(Error::Operation("该加密系统不支持由私钥推导公钥".to_string()))
+    }
+
+    /// 公钥的实际参数（如RSA位数、Kyber级别）是否与 `config` 一致
+    ///
+    /// 引擎在头部记录配置摘要前据此确认摘要如实反映所用密钥；无法从公钥推导参数的系统返回 `Error::Operation`。
+    fn public_key_matches_config(_public_key: &Self::PublicKey, _config: &CryptoConfig) -> Result<bool, Error>
+    where
+        Error: From<Self::Error>,
+    {
+        Err(Error::Operation("该加密系统无法从公钥推导密钥参数".to_string()))
+    }
+
+    /// 反复生成密钥对，直到公钥指纹（见 [`key_fingerprint`](crate::common::utils::key_fingerprint)）以十六进制 `prefix` 开头
+    ///
+    /// 期望尝试次数为 16 的前缀长度次方，便于得到人眼可辨认的密钥标识；
+    /// `max_attempts` 次内未找到或前缀无效时返回 `Error::Operation`。
+    fn generate_vanity_key(
+        config: &CryptoConfig,
+        prefix: &str,
+        max_attempts: u64,
+    ) -> Result<(Self::PublicKey, Self::PrivateKey), Error>
+    where
+        Error: From<Self::Error>,
+    {
+        crate::common::utils::search_vanity_key(prefix, max_attempts, || {
+            let (public_key, private_key) = Self::generate_keypair(config)?;
+            let fingerprint = crate::common::utils::key_fingerprint(&Self::export_public_key(&public_key)?);
+            Ok(((public_key, private_key), fingerprint))
+        })
+    }
 }
 
 /// 同步流式加密系统扩展
@@ -74,6 +170,33 @@ where
         config: &StreamingConfig,
         additional_data: Option<&[u8]>,
     ) -> Result<StreamingResult, Error>;
+
+    /// 加密并把序列化后的密文直接写入 `sink`，返回写入的字节数
+    ///
+    /// 写出的内容与 `encrypt(...).to_string()` 相同，但边编码边写出，不分配中间的 Base64 字符串。
+    /// `sink` 写入失败时返回 `Error::Io`，此时可能已写出部分内容。
+    fn encrypt_to(
+        public_key: &Self::PublicKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        sink: &mut dyn Write,
+    ) -> Result<usize, Error> {
+        let ciphertext = Self::encrypt(public_key, plaintext, additional_data)?;
+        Ok(crate::common::utils::write_base64(ciphertext.as_ref(), sink)?)
+    }
+}
+
+/// 按密文信封中的路由信息提供解密所用的密钥对
+///
+/// 引擎解密嵌入了密钥指纹或密钥版本（见 [`CiphertextEnvelope`](crate::common::utils::CiphertextEnvelope)）的密文时，
+/// 通过此接口直接取得对应的密钥对，而不是逐个尝试。默认由引擎自身保存的密钥提供，
+/// 也可通过 `AsymmetricQSealEngine::set_key_provider`（或并发版引擎的同名方法）替换为调用方自己的多版本密钥来源。
+pub trait KeyProvider<C: AsymmetricCryptographicSystem> {
+    /// 返回与 `fingerprint`、`key_version` 同时匹配的密钥对，值为 `None` 的条件不参与匹配
+    ///
+    /// 返回借用而非副本，每次解密都不会复制私钥。
+    /// 没有匹配的密钥时应返回错误：指纹不匹配时为 `Error::KeyMismatch`，只有版本不匹配时为 `Error::Key`。
+    fn key_pair(&self, fingerprint: Option<&str>, key_version: Option<u32>) -> Result<(&C::PublicKey, &C::PrivateKey), Error>;
 }
 
 /// 异步流式加密系统扩展
@@ -107,4 +230,36 @@ where
     where
         R: AsyncRead + Unpin + Send,
         W: AsyncWrite + Unpin + Send;
+
+    /// 异步流式加密，附加认证数据以所有权形式传入，便于跨 `.await` 传递
+    async fn encrypt_stream_async_owned_aad<R, W>(
+        public_key: &Self::PublicKey,
+        reader: R,
+        writer: W,
+        config: &StreamingConfig,
+        additional_data: Option<Vec<u8>>,
+    ) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        Self::PublicKey: Sync,
+    {
+        Self::encrypt_stream_async(public_key, reader, writer, config, additional_data.as_deref()).await
+    }
+
+    /// 异步流式解密，附加认证数据以所有权形式传入，便于跨 `.await` 传递
+    async fn decrypt_stream_async_owned_aad<R, W>(
+        private_key: &Self::PrivateKey,
+        reader: R,
+        writer: W,
+        config: &StreamingConfig,
+        additional_data: Option<Vec<u8>>,
+    ) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        Self::PrivateKey: Sync,
+    {
+        Self::decrypt_stream_async(private_key, reader, writer, config, additional_data.as_deref()).await
+    }
 }
\ No newline at end of file
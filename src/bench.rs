@@ -0,0 +1,297 @@
+//! 运行时基准测试
+//!
+//! 在目标机器上测量核心对称操作的耗时与吞吐量，供下游在自己的 CI 中做性能回归或运行时自检。
+//! [`run_symmetric`] 直接返回统计结果；需要接入 criterion 等框架时，用 [`SymmetricBench::new`]
+//! 准备好密钥与输入，再在框架的计时闭包中调用 [`SymmetricBench::encrypt_once`] / [`SymmetricBench::decrypt_once`]
+//! （`benches/base.rs` 中的 `bench_symmetric` 即是这样接入 criterion 的）。
+//!
+//! [`select_fastest`] 在本机上实测全部可用的对称算法，按加密吞吐量选出最快的一个，
+//! 是对 [`crate::cpu::has_aes_acceleration`] 这类特性检测的经验补充；
+//! [`AutoSymmetricEngine::auto_tuned`](crate::symmetric::engines::AutoSymmetricEngine::auto_tuned) 以选中的算法加密数据。
+//!
+//! 测量走的都是引擎实际使用的实现：AES-256-GCM 经由 [`AesGcmSystem`]，ChaCha20-Poly1305 经由
+//! [`ChaCha20Poly1305System`]（需启用 `chacha` 特性）。
+
+use std::time::{Duration, Instant};
+
+use crate::common::errors::Error;
+use crate::common::utils::CryptoConfig;
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+#[cfg(feature = "chacha")]
+use crate::symmetric::systems::chacha20_poly1305::{ChaCha20Poly1305Key, ChaCha20Poly1305System};
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+pub use crate::symmetric::systems::SymmetricAlgorithm;
+
+/// [`run_symmetric`] 每项操作的计时次数
+const DEFAULT_ITERATIONS: u32 = 32;
+
+/// [`SymmetricAlgorithm::auto_tuned`] 测量时每次处理的明文字节数
+const AUTO_TUNE_SIZE: usize = 64 * 1024;
+/// [`SymmetricAlgorithm::auto_tuned`] 测量时每项操作的计时次数
+const AUTO_TUNE_ITERATIONS: u32 = 8;
+
+/// 一项操作重复执行的计时统计
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    /// 计时次数
+    pub iterations: u32,
+    /// 每次处理的明文字节数
+    pub bytes_per_iteration: usize,
+    /// 总耗时
+    pub total: Duration,
+    /// 单次最短耗时
+    pub min: Duration,
+    /// 单次最长耗时
+    pub max: Duration,
+}
+
+impl BenchStats {
+    /// 单次平均耗时
+    pub fn mean(&self) -> Duration {
+        self.total / self.iterations.max(1)
+    }
+
+    /// 吞吐量（明文字节/秒）；总耗时为零时返回 0
+    pub fn throughput_bytes_per_sec(&self) -> f64 {
+        let seconds = self.total.as_secs_f64();
+        if seconds == 0.0 {
+            return 0.0;
+        }
+        self.bytes_per_iteration as f64 * f64::from(self.iterations) / seconds
+    }
+}
+
+/// 对称算法的加密与解密统计
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymmetricBenchReport {
+    /// 被测算法
+    pub algorithm: SymmetricAlgorithm,
+    /// 加密统计
+    pub encrypt: BenchStats,
+    /// 解密统计
+    pub decrypt: BenchStats,
+}
+
+/// [`select_fastest`] 的结果：选中的算法与各算法的测量报告
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendSelection {
+    /// 加密吞吐量最高的算法
+    pub selected: SymmetricAlgorithm,
+    /// 各算法的测量报告，顺序与 [`SymmetricAlgorithm::available`] 相同
+    pub reports: Vec<SymmetricBenchReport>,
+}
+
+/// 测量全部可用算法加密、解密 `size` 字节明文的耗时，选出加密吞吐量最高的算法
+///
+/// 吞吐量相同时优先选择靠前的 AES-256-GCM。测量结果受当时的系统负载影响，
+/// 选择应在启动时做一次并记录下来，而不是每次加密前重新测量。
+pub fn select_fastest(size: usize, iterations: u32) -> Result<BackendSelection, Error> {
+    let reports = SymmetricAlgorithm::available()
+        .into_iter()
+        .map(|algorithm| run_symmetric_with_iterations(algorithm, size, iterations))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let fastest = reports
+        .iter()
+        .reduce(|best, report| {
+            if report.encrypt.throughput_bytes_per_sec() > best.encrypt.throughput_bytes_per_sec() { report } else { best }
+        })
+        .expect("至少有一种可用算法");
+    Ok(BackendSelection { selected: fastest.algorithm, reports })
+}
+
+impl SymmetricAlgorithm {
+    /// 在本机上简短测量后选出加密最快的数据加密算法，返回选择及其依据
+    ///
+    /// 与按 CPU 特性选择的 [`SymmetricAlgorithm::auto`] 互为补充。
+    pub fn auto_tuned() -> Result<BackendSelection, Error> {
+        select_fastest(AUTO_TUNE_SIZE, AUTO_TUNE_ITERATIONS)
+    }
+}
+
+/// 以默认次数测量 `algorithm` 加密、解密 `size` 字节明文的耗时
+pub fn run_symmetric(algorithm: SymmetricAlgorithm, size: usize) -> Result<SymmetricBenchReport, Error> {
+    run_symmetric_with_iterations(algorithm, size, DEFAULT_ITERATIONS)
+}
+
+/// 与 [`run_symmetric`] 相同，但指定每项操作的计时次数（至少 1 次）
+pub fn run_symmetric_with_iterations(
+    algorithm: SymmetricAlgorithm,
+    size: usize,
+    iterations: u32,
+) -> Result<SymmetricBenchReport, Error> {
+    let bench = SymmetricBench::new(algorithm, size)?;
+    Ok(SymmetricBenchReport {
+        algorithm,
+        encrypt: measure(iterations, size, || bench.encrypt_once())?,
+        decrypt: measure(iterations, size, || bench.decrypt_once())?,
+    })
+}
+
+/// 重复执行 `operation` 并统计耗时；任一次失败时立即返回该错误
+///
+/// 先执行一次不计时的预热，避免首次调用的初始化开销混入统计。
+pub fn measure<F: FnMut() -> Result<(), Error>>(
+    iterations: u32,
+    bytes_per_iteration: usize,
+    mut operation: F,
+) -> Result<BenchStats, Error> {
+    let iterations = iterations.max(1);
+    operation()?;
+    let mut stats = BenchStats {
+        iterations,
+        bytes_per_iteration,
+        total: Duration::ZERO,
+        min: Duration::MAX,
+        max: Duration::ZERO,
+    };
+    for _ in 0..iterations {
+        let start = Instant::now();
+        operation()?;
+        let elapsed = start.elapsed();
+        stats.total += elapsed;
+        stats.min = stats.min.min(elapsed);
+        stats.max = stats.max.max(elapsed);
+    }
+    Ok(stats)
+}
+
+/// 测量所需的密钥
+enum BenchKey {
+    Aes256Gcm(AesGcmKey),
+    #[cfg(feature = "chacha")]
+    ChaCha20Poly1305(ChaCha20Poly1305Key),
+}
+
+/// 预先准备好密钥、明文与密文的单项对称操作，便于接入外部基准框架
+///
+/// 加密每次都生成新的随机 Nonce，与实际使用的开销一致；解密重复处理构造时生成的同一份密文。
+pub struct SymmetricBench {
+    algorithm: SymmetricAlgorithm,
+    key: BenchKey,
+    plaintext: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl SymmetricBench {
+    /// 为 `algorithm` 生成随机密钥，并准备 `size` 字节的明文及其密文
+    pub fn new(algorithm: SymmetricAlgorithm, size: usize) -> Result<Self, Error> {
+        let key = match algorithm {
+            SymmetricAlgorithm::Aes256Gcm => BenchKey::Aes256Gcm(AesGcmSystem::generate_key(&CryptoConfig::default())?),
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => {
+                BenchKey::ChaCha20Poly1305(ChaCha20Poly1305System::generate_key(&CryptoConfig::default())?)
+            }
+        };
+        let mut bench = Self { algorithm, key, plaintext: vec![0xa5; size], ciphertext: Vec::new() };
+        bench.ciphertext = bench.encrypt()?;
+        Ok(bench)
+    }
+
+    /// 被测算法
+    pub fn algorithm(&self) -> SymmetricAlgorithm {
+        self.algorithm
+    }
+
+    /// 每次操作处理的明文字节数
+    pub fn size(&self) -> usize {
+        self.plaintext.len()
+    }
+
+    /// 加密一次明文
+    pub fn encrypt_once(&self) -> Result<(), Error> {
+        self.encrypt().map(drop)
+    }
+
+    /// 解密一次密文
+    pub fn decrypt_once(&self) -> Result<(), Error> {
+        match &self.key {
+            BenchKey::Aes256Gcm(key) => {
+                let mut out = Vec::with_capacity(self.plaintext.len());
+                AesGcmSystem::decrypt_from_bytes(key, &self.ciphertext, None, &CryptoConfig::default(), &mut out)
+            }
+            #[cfg(feature = "chacha")]
+            BenchKey::ChaCha20Poly1305(key) => {
+                let mut out = Vec::with_capacity(self.plaintext.len());
+                ChaCha20Poly1305System::decrypt_from_bytes(key, &self.ciphertext, None, &CryptoConfig::default(), &mut out)
+            }
+        }
+    }
+
+    /// 加密明文，返回 `Nonce || 密文 || 标签`
+    fn encrypt(&self) -> Result<Vec<u8>, Error> {
+        match &self.key {
+            BenchKey::Aes256Gcm(key) => {
+                let mut out = Vec::new();
+                AesGcmSystem::encrypt_to_buffer(key, &self.plaintext, None, &CryptoConfig::default(), &mut out)?;
+                Ok(out)
+            }
+            #[cfg(feature = "chacha")]
+            BenchKey::ChaCha20Poly1305(key) => {
+                let mut out = Vec::new();
+                ChaCha20Poly1305System::encrypt_to_buffer(key, &self.plaintext, None, &CryptoConfig::default(), &mut out)?;
+                Ok(out)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_symmetric_reports_throughput() {
+        let report = run_symmetric_with_iterations(SymmetricAlgorithm::Aes256Gcm, 16 * 1024, 4).unwrap();
+        assert_eq!(report.algorithm.name(), "AES-256-GCM");
+        for stats in [report.encrypt, report.decrypt] {
+            assert_eq!((stats.iterations, stats.bytes_per_iteration), (4, 16 * 1024));
+            assert!(stats.total > Duration::ZERO);
+            assert!(stats.min <= stats.mean() && stats.mean() <= stats.max);
+            assert!(stats.throughput_bytes_per_sec() > 0.0);
+            assert!(stats.throughput_bytes_per_sec().is_finite());
+        }
+        assert!(run_symmetric(SymmetricAlgorithm::Aes256Gcm, 1024).is_ok());
+    }
+
+    #[cfg(feature = "chacha")]
+    #[test]
+    fn test_chacha_bench_roundtrip() {
+        let bench = SymmetricBench::new(SymmetricAlgorithm::ChaCha20Poly1305, 4096).unwrap();
+        assert_eq!(bench.size(), 4096);
+        bench.encrypt_once().unwrap();
+        bench.decrypt_once().unwrap();
+        let report = run_symmetric_with_iterations(SymmetricAlgorithm::ChaCha20Poly1305, 4096, 2).unwrap();
+        assert!(report.encrypt.throughput_bytes_per_sec() > 0.0);
+    }
+
+    #[test]
+    fn test_measure_propagates_errors() {
+        let mut calls = 0;
+        let result = measure(3, 1, || {
+            calls += 1;
+            if calls == 2 { Err(Error::Operation("boom".to_string())) } else { Ok(()) }
+        });
+        assert!(matches!(result, Err(Error::Operation(_))));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_select_fastest_records_every_backend() {
+        let selection = select_fastest(4096, 2).unwrap();
+        let measured: Vec<_> = selection.reports.iter().map(|report| report.algorithm).collect();
+        assert_eq!(measured, SymmetricAlgorithm::available());
+        let best = selection.reports.iter().map(|report| report.encrypt.throughput_bytes_per_sec()).fold(0.0, f64::max);
+        let selected = selection.reports.iter().find(|report| report.algorithm == selection.selected).unwrap();
+        assert_eq!(selected.encrypt.throughput_bytes_per_sec(), best);
+
+        // 选中的算法可以直接使用
+        let bench = SymmetricBench::new(selection.selected, 1024).unwrap();
+        bench.encrypt_once().unwrap();
+        bench.decrypt_once().unwrap();
+
+        let selection = SymmetricAlgorithm::auto_tuned().unwrap();
+        assert!(SymmetricAlgorithm::available().contains(&selection.selected));
+        assert_eq!(selection.reports.len(), SymmetricAlgorithm::available().len());
+    }
+}
@@ -1,10 +1,31 @@
 //! 基础工具模块，提供 Base64 编解码、ZeroizingVec、安全比较等工具
 
+#[cfg(feature = "std")]
 pub mod streaming;
 pub mod utils;
+#[cfg(feature = "std")]
 pub mod traits;
 pub mod errors;
+#[cfg(feature = "std")]
 pub mod config;
+pub mod rng;
+pub mod nonce;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod buffer_pool;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(all(feature = "std", any(feature = "aes-gcm-feature", feature = "traditional")))]
+pub mod sizing;
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub mod inspect;
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub mod records;
+#[cfg(feature = "aes-gcm-feature")]
+pub mod key_derivation;
 
 #[cfg(any(feature = "traditional", feature = "post-quantum"))]
 pub use crate::asymmetric::primitives::streaming::*;
@@ -0,0 +1,199 @@
+//! 面向合规留存的操作审计事件
+//!
+//! 与调试用的日志不同，审计事件是结构化的记录：每次加解密完成（无论成败）后，引擎把一条 [`AuditEvent`]
+//! 交给设置的 [`AuditSink`]。事件只包含操作类型、算法名称、密钥标识、明文字节数、结果与时间，
+//! 不包含任何密钥、明文或错误详情；失败只记录稳定的错误代码（见 [`Error::code`]）。
+//!
+//! [`JsonLinesAuditSink`] 把事件逐行以 JSON 追加到文件，[`MemoryAuditSink`] 把事件保存在内存中，供测试检查。
+
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::common::errors::Error;
+use crate::common::metrics::Direction;
+
+/// 审计的操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    /// 加密（含流式加密）
+    Encrypt,
+    /// 解密（含流式解密）
+    Decrypt,
+}
+
+impl From<Direction> for AuditOperation {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Encrypt => AuditOperation::Encrypt,
+            Direction::Decrypt => AuditOperation::Decrypt,
+        }
+    }
+}
+
+/// 操作结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum AuditOutcome {
+    /// 操作成功
+    Success,
+    /// 操作失败，只记录错误代码
+    Failure {
+        /// [`Error::code`] 给出的错误代码
+        error: &'static str,
+    },
+}
+
+/// 一条审计事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditEvent {
+    /// 操作类型
+    pub op: AuditOperation,
+    /// 加密系统名称，如 `RsaKyberCryptoSystem`
+    pub algorithm: &'static str,
+    /// 密钥标识：非对称引擎为公钥指纹，对称引擎为主密钥ID；无法确定使用的密钥时为 `None`
+    pub fingerprint: Option<String>,
+    /// 成功处理的明文字节数，失败时为 0
+    pub bytes: u64,
+    /// 操作结果
+    pub outcome: AuditOutcome,
+    /// 操作完成时间，自 Unix 纪元起的秒数
+    pub timestamp: u64,
+}
+
+impl AuditEvent {
+    /// 由操作结果构造事件；`outcome` 成功时携带明文字节数
+    pub(crate) fn new(
+        direction: Direction,
+        algorithm: &'static str,
+        fingerprint: Option<String>,
+        outcome: Result<u64, &Error>,
+        now: SystemTime,
+    ) -> Self {
+        let (bytes, outcome) = match outcome {
+            Ok(bytes) => (bytes, AuditOutcome::Success),
+            Err(e) => (0, AuditOutcome::Failure { error: e.code() }),
+        };
+        Self {
+            op: direction.into(),
+            algorithm,
+            fingerprint,
+            bytes,
+            outcome,
+            timestamp: now.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        }
+    }
+}
+
+/// 审计事件的接收方
+///
+/// 引擎在每次加解密完成后同步调用 [`record`](Self::record)。写入失败时，原本成功的操作改为返回该错误，
+/// 保证未被记录的操作不会把结果交给调用方；原本失败的操作仍返回原来的错误。
+pub trait AuditSink: Send + Sync {
+    /// 记录一条事件
+    fn record(&self, event: &AuditEvent) -> Result<(), Error>;
+}
+
+/// 以 JSON Lines 格式把事件追加到文件的审计接收方
+#[derive(Debug)]
+pub struct JsonLinesAuditSink {
+    file: Mutex<File>,
+}
+
+impl JsonLinesAuditSink {
+    /// 以追加方式打开（不存在时创建）审计文件
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+
+impl AuditSink for JsonLinesAuditSink {
+    fn record(&self, event: &AuditEvent) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        // 整行一次写入，并发写入的事件不会交错
+        let mut file = self.file.lock().map_err(|_| Error::Operation("审计文件锁已中毒".to_string()))?;
+        file.write_all(&line)?;
+        Ok(())
+    }
+}
+
+/// 把事件保存在内存中的审计接收方
+#[derive(Debug, Default)]
+pub struct MemoryAuditSink {
+    events: Mutex<Vec<AuditEvent>>,
+}
+
+impl MemoryAuditSink {
+    /// 创建空的接收方
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 已记录事件的副本，按记录顺序排列
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.lock().map(|events| events.clone()).unwrap_or_default()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, event: &AuditEvent) -> Result<(), Error> {
+        self.events
+            .lock()
+            .map_err(|_| Error::Operation("审计事件锁已中毒".to_string()))?
+            .push(event.clone());
+        Ok(())
+    }
+}
+
+/// 加密系统类型的简短名称，用作事件中的算法名称
+pub(crate) fn algorithm_name<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let base = name.split('<').next().unwrap_or(name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_json_lines_sink_appends_one_event_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let events = [
+            AuditEvent::new(Direction::Encrypt, "AesGcmSystem", Some("key-1".to_string()), Ok(12), now),
+            AuditEvent::new(Direction::Decrypt, "AesGcmSystem", None, Err(&Error::DecryptionFailed("tag".to_string())), now),
+        ];
+        for event in &events {
+            JsonLinesAuditSink::open(&path).unwrap().record(event).unwrap();
+        }
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["op"], "encrypt");
+        assert_eq!(lines[0]["outcome"]["status"], "success");
+        assert_eq!(lines[0]["bytes"], 12);
+        assert_eq!(lines[0]["timestamp"], 1_700_000_000u64);
+        assert_eq!(lines[1]["op"], "decrypt");
+        assert_eq!(lines[1]["fingerprint"], serde_json::Value::Null);
+        assert_eq!(lines[1]["outcome"]["error"], "decryption_failed");
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("tag"));
+    }
+
+    #[test]
+    fn test_algorithm_name_strips_path_and_generics() {
+        assert_eq!(algorithm_name::<MemoryAuditSink>(), "MemoryAuditSink");
+        assert_eq!(algorithm_name::<Vec<u8>>(), "Vec");
+    }
+}
@@ -0,0 +1,149 @@
+//! 可复用的暂存缓冲区池
+//!
+//! 高并发下每次加解密都为 Nonce 与密文的拼接分配新缓冲区，分配器争用会成为瓶颈。
+//! [`BufferPool`] 保存一组已分配的 `Vec<u8>`，借出时长度为 0、容量保留；归还前整个容量都会被清零，
+//! 因此池中闲置的缓冲区不会残留任何明文或密文。
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use zeroize::Zeroize;
+
+/// 线程安全的暂存缓冲区池，可通过 `Arc` 在多个引擎之间共享
+pub struct BufferPool {
+    buffers: Mutex<Vec<Vec<u8>>>,
+    max_pooled: usize,
+}
+
+impl fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("available", &self.available())
+            .field("max_pooled", &self.max_pooled)
+            .finish()
+    }
+}
+
+impl BufferPool {
+    /// 创建缓冲区池，最多保留 `max_pooled` 个闲置缓冲区，超出的部分在归还时清零后直接释放
+    pub fn new(max_pooled: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::with_capacity(max_pooled)),
+            max_pooled,
+        }
+    }
+
+    /// 借出一个空缓冲区，池中没有闲置缓冲区时新分配一个
+    pub fn acquire(&self) -> PooledBuffer<'_> {
+        let mut buffer = self.lock().pop().unwrap_or_default();
+        buffer.clear();
+        PooledBuffer { pool: self, buffer }
+    }
+
+    /// 当前闲置的缓冲区数量
+    pub fn available(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn release(&self, mut buffer: Vec<u8>) {
+        // 连同未使用的容量一起清零，使闲置缓冲区保持全零
+        buffer.resize(buffer.capacity(), 0);
+        buffer.as_mut_slice().zeroize();
+        let mut buffers = self.lock();
+        if buffers.len() < self.max_pooled {
+            buffers.push(buffer);
+        }
+    }
+
+    /// 池中只保存已清零的缓冲区，锁中毒时其中的数据仍然一致，可以继续使用
+    fn lock(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        self.buffers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// 从 [`BufferPool`] 借出的缓冲区，离开作用域时清零并归还
+pub struct PooledBuffer<'a> {
+    pool: &'a BufferPool,
+    buffer: Vec<u8>,
+}
+
+impl Deref for PooledBuffer<'_> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+}
+
+impl DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        self.pool.release(std::mem::take(&mut self.buffer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn pooled_buffers_are_zeroed(pool: &BufferPool) -> bool {
+        pool.lock().iter().all(|buffer| buffer.len() == buffer.capacity() && buffer.iter().all(|&b| b == 0))
+    }
+
+    #[test]
+    fn test_buffers_are_reused_and_zeroized() {
+        let pool = BufferPool::new(2);
+        {
+            let mut buffer = pool.acquire();
+            buffer.extend_from_slice(b"secret plaintext");
+            buffer.truncate(6);
+        }
+        assert_eq!(pool.available(), 1);
+        assert!(pooled_buffers_are_zeroed(&pool));
+
+        let buffer = pool.acquire();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 16);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test]
+    fn test_pool_keeps_at_most_max_pooled_buffers() {
+        let pool = BufferPool::new(2);
+        let buffers: Vec<_> = (0..4).map(|i| {
+            let mut buffer = pool.acquire();
+            buffer.push(i);
+            buffer
+        }).collect();
+        drop(buffers);
+        assert_eq!(pool.available(), 2);
+        assert!(pooled_buffers_are_zeroed(&pool));
+        assert!(format!("{:?}", pool).contains("available: 2"));
+    }
+
+    #[test]
+    fn test_concurrent_acquire_and_release() {
+        let pool = Arc::new(BufferPool::new(4));
+        let handles: Vec<_> = (0..8u8).map(|i| {
+            let pool = Arc::clone(&pool);
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    let mut buffer = pool.acquire();
+                    assert!(buffer.is_empty());
+                    buffer.extend_from_slice(&[i; 64]);
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(pool.available() <= 4);
+        assert!(pooled_buffers_are_zeroed(&pool));
+    }
+}
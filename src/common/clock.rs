@@ -0,0 +1,21 @@
+//! 可注入的时间来源
+//!
+//! 引擎通过 [`Clock`] 读取当前时间（如校验密文的生效时间），测试中可替换为可调的模拟时钟。
+
+use std::time::SystemTime;
+
+/// 时间来源
+pub trait Clock: Send + Sync {
+    /// 当前时间
+    fn now(&self) -> SystemTime;
+}
+
+/// 读取系统时间的默认时钟
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
@@ -306,13 +306,7 @@ impl ConfigManager {
     
     /// 保存配置到文件
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
-        let state = self.state.load_full();
-        
-        let config = ConfigFile {
-            crypto: state.crypto.clone(),
-            rotation: state.rotation.clone(),
-            storage: state.storage.clone(),
-        };
+        let config = self.to_config_file();
         
         let json = serde_json::to_string_pretty(&config)
             .map_err(|e| Error::Serialization(format!("序列化配置失败: {}", e)))?;
@@ -323,6 +317,16 @@ impl ConfigManager {
         Ok(())
     }
     
+    /// 获取当前配置的完整快照
+    pub fn to_config_file(&self) -> ConfigFile {
+        let state = self.state.load_full();
+        ConfigFile {
+            crypto: state.crypto.clone(),
+            rotation: state.rotation.clone(),
+            storage: state.storage.clone(),
+        }
+    }
+    
     /// 添加配置变更监听器
     pub fn add_listener<F>(&self, listener: F)
     where
@@ -1,5 +1,8 @@
-use std::fmt;
-use std::error::Error as StdError;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error as StdError;
+use core::fmt;
 
 /// 加密操作可能遇到的错误类型
 #[derive(Debug)]
@@ -17,6 +20,7 @@ pub enum Error {
     Serialization(String),
     
     /// 输入/输出错误
+    #[cfg(feature = "std")]
     Io(std::io::Error),
     
     /// 数据格式错误
@@ -35,6 +39,92 @@ pub enum Error {
     KeyImportFailed(String),
     /// 密钥导出失败
     KeyExportFailed(String),
+    /// 随机数生成器失败 (例如系统熵源暂时不可用，调用方可重试)
+    RngFailure(String),
+    /// 流完整性校验失败：完整性尾部缺失或与实际帧不符（如传输中被截断）
+    StreamIntegrity(String),
+    /// 配置中存在不安全的参数，列出每一项问题
+    InsecureParameter(Vec<String>),
+    /// 一次性加密的载荷超过 `max_oneshot_size`，应改用流式接口
+    PayloadTooLarge {
+        /// 载荷字节数
+        size: usize,
+        /// 配置的上限
+        limit: usize,
+    },
+    /// 调用方提供的输出缓冲区不足以容纳结果
+    BufferTooSmall {
+        /// 所需字节数
+        required: usize,
+        /// 提供的缓冲区字节数
+        provided: usize,
+    },
+    /// 密钥指纹不匹配：密文要求的密钥与提供的密钥不同，未执行任何解密运算
+    KeyMismatch {
+        /// 密文中嵌入的密钥指纹
+        expected: String,
+        /// 提供的密钥指纹
+        provided: String,
+    },
+    /// 操作被调用方通过取消令牌中止，已写出的数据只包含完整的帧
+    Cancelled,
+    /// 密文设置了生效时间（not-before），当前时间早于该时间，未执行任何解密运算
+    NotYetValid(String),
+    /// 密文由已吊销的密钥加密（携带密文中的密钥指纹），未执行任何解密运算
+    KeyRevoked(String),
+    /// 基于计数器的 Nonce 序列已用尽，继续使用将导致 Nonce 复用，必须更换密钥
+    NonceExhausted,
+    /// 密钥已被显式清零（见各密钥包装器的 `zeroize_now`），不能再用于任何运算
+    KeyZeroized,
+    /// 解密失败，并附带可能原因的诊断信息，供界面引导用户排查
+    AuthenticationFailed(DecryptDiagnostics),
+}
+
+/// 解密失败的可能原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecryptFailureCause {
+    /// 使用了错误的密钥
+    WrongKey,
+    /// 密文在传输或存储中被篡改
+    Tampered,
+    /// 附加认证数据与加密时不一致
+    WrongAad,
+    /// 密文编码或结构无效（如 Base64 错误、长度不足）
+    CorruptFormat,
+}
+
+impl DecryptFailureCause {
+    /// 机器可读的原因代码，如 `"wrong_key"`
+    pub fn code(&self) -> &'static str {
+        match self {
+            DecryptFailureCause::WrongKey => "wrong_key",
+            DecryptFailureCause::Tampered => "tampered",
+            DecryptFailureCause::WrongAad => "wrong_aad",
+            DecryptFailureCause::CorruptFormat => "corrupt_format",
+        }
+    }
+}
+
+/// 解密失败的诊断信息
+///
+/// 原因由已通过的检查推断：密文格式无效时只可能是 `CorruptFormat`；格式有效但认证标签校验失败时，
+/// AEAD 本身无法区分错误的密钥、被篡改的密文与不一致的附加认证数据，三者都会列出。
+/// 诊断只用于提示，不能作为安全判断的依据。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecryptDiagnostics {
+    /// 密文格式是否通过了解析
+    pub format_valid: bool,
+    /// 可能的原因，按可能性从高到低排列
+    pub possible_causes: Vec<DecryptFailureCause>,
+    /// 底层错误的描述
+    pub detail: String,
+}
+
+impl DecryptDiagnostics {
+    /// 所有可能原因的机器可读代码
+    pub fn cause_codes(&self) -> Vec<&'static str> {
+        self.possible_causes.iter().map(DecryptFailureCause::code).collect()
+    }
 }
 
 impl fmt::Display for Error {
@@ -44,6 +134,7 @@ impl fmt::Display for Error {
             Error::PostQuantum(msg) => write!(f, "后量子加密错误: {}", msg),
             Error::KeyStorage(msg) => write!(f, "密钥存储错误: {}", msg),
             Error::Serialization(msg) => write!(f, "序列化错误: {}", msg),
+            #[cfg(feature = "std")]
             Error::Io(e) => write!(f, "I/O 错误: {}", e),
             Error::Format(msg) => write!(f, "格式错误: {}", msg),
             Error::Key(msg) => write!(f, "密钥错误: {}", msg),
@@ -52,6 +143,26 @@ impl fmt::Display for Error {
             Error::DecryptionFailed(msg) => write!(f, "解密失败: {}", msg),
             Error::KeyImportFailed(msg) => write!(f, "密钥导入失败: {}", msg),
             Error::KeyExportFailed(msg) => write!(f, "密钥导出失败: {}", msg),
+            Error::RngFailure(msg) => write!(f, "随机数生成失败: {}", msg),
+            Error::StreamIntegrity(msg) => write!(f, "流完整性校验失败: {}", msg),
+            Error::InsecureParameter(findings) => write!(f, "不安全的参数: {}", findings.join("; ")),
+            Error::PayloadTooLarge { size, limit } => {
+                write!(f, "载荷过大: {} 字节超过一次性加密上限 {} 字节，请改用流式接口", size, limit)
+            }
+            Error::BufferTooSmall { required, provided } => {
+                write!(f, "输出缓冲区过小: 需要 {} 字节, 实际 {} 字节", required, provided)
+            }
+            Error::KeyMismatch { expected, provided } => {
+                write!(f, "密钥指纹不匹配: 密文需要 {}, 提供的是 {}", expected, provided)
+            }
+            Error::Cancelled => write!(f, "操作已取消"),
+            Error::NotYetValid(msg) => write!(f, "密文尚未生效: {}", msg),
+            Error::KeyRevoked(fingerprint) => write!(f, "密文使用的密钥已吊销: {}", fingerprint),
+            Error::NonceExhausted => write!(f, "Nonce 序列已耗尽，必须更换密钥"),
+            Error::KeyZeroized => write!(f, "密钥已被清零，不能再使用"),
+            Error::AuthenticationFailed(diagnostics) => write!(
+                f, "解密失败: {} (可能原因: {})", diagnostics.detail, diagnostics.cause_codes().join(", ")
+            ),
         }
     }
 }
@@ -59,24 +170,124 @@ impl fmt::Display for Error {
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
+            #[cfg(feature = "std")]
             Error::Io(e) => Some(e),
             _ => None,
         }
     }
 }
 
+/// 解开由 [`Error`] 转换而来的 `io::Error`，恢复原始错误；其他 I/O 错误包装为 `Error::Io`
+#[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
+        if err.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            let inner = err.into_inner().expect("已确认存在内部错误");
+            return *inner.downcast::<Error>().expect("已确认内部错误类型");
+        }
         Error::Io(err)
     }
 }
 
+impl Error {
+    /// 解密失败的诊断信息，仅 `Error::AuthenticationFailed` 携带
+    pub fn diagnostics(&self) -> Option<&DecryptDiagnostics> {
+        match self {
+            Error::AuthenticationFailed(diagnostics) => Some(diagnostics),
+            _ => None,
+        }
+    }
+
+    /// 稳定的错误代码，不含任何错误详情，可写入审计记录等长期留存的位置
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Traditional(_) => "traditional",
+            Error::PostQuantum(_) => "post_quantum",
+            Error::KeyStorage(_) => "key_storage",
+            Error::Serialization(_) => "serialization",
+            #[cfg(feature = "std")]
+            Error::Io(_) => "io",
+            Error::Format(_) => "format",
+            Error::Key(_) => "key",
+            Error::Operation(_) => "operation",
+            Error::EncryptionFailed(_) => "encryption_failed",
+            Error::DecryptionFailed(_) => "decryption_failed",
+            Error::KeyImportFailed(_) => "key_import_failed",
+            Error::KeyExportFailed(_) => "key_export_failed",
+            Error::RngFailure(_) => "rng_failure",
+            Error::StreamIntegrity(_) => "stream_integrity",
+            Error::InsecureParameter(_) => "insecure_parameter",
+            Error::PayloadTooLarge { .. } => "payload_too_large",
+            Error::BufferTooSmall { .. } => "buffer_too_small",
+            Error::KeyMismatch { .. } => "key_mismatch",
+            Error::Cancelled => "cancelled",
+            Error::NotYetValid(_) => "not_yet_valid",
+            Error::KeyRevoked(_) => "key_revoked",
+            Error::NonceExhausted => "nonce_exhausted",
+            Error::KeyZeroized => "key_zeroized",
+            Error::AuthenticationFailed(_) => "authentication_failed",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error {
+    /// 该错误对应的 `io::ErrorKind`
+    ///
+    /// 认证失败与数据格式问题为 `InvalidData`，调用方传入的密钥、参数或缓冲区问题为 `InvalidInput`，
+    /// 尚未生效的密文为 `PermissionDenied`。取消映射为 `Other` 而非 `Interrupted`，
+    /// 因为标准库的 `read_exact` 等方法会自动重试 `Interrupted`。
+    pub fn io_kind(&self) -> std::io::ErrorKind {
+        use std::io::ErrorKind;
+        match self {
+            Error::Io(e) => e.kind(),
+            Error::DecryptionFailed(_)
+            | Error::AuthenticationFailed(_)
+            | Error::Format(_)
+            | Error::Serialization(_)
+            | Error::StreamIntegrity(_) => ErrorKind::InvalidData,
+            Error::Key(_)
+            | Error::KeyImportFailed(_)
+            | Error::KeyMismatch { .. }
+            | Error::InsecureParameter(_)
+            | Error::KeyZeroized
+            | Error::PayloadTooLarge { .. }
+            | Error::BufferTooSmall { .. } => ErrorKind::InvalidInput,
+            Error::NotYetValid(_) | Error::KeyRevoked(_) => ErrorKind::PermissionDenied,
+            Error::Traditional(_)
+            | Error::PostQuantum(_)
+            | Error::KeyStorage(_)
+            | Error::Operation(_)
+            | Error::EncryptionFailed(_)
+            | Error::KeyExportFailed(_)
+            | Error::RngFailure(_)
+            | Error::Cancelled
+            | Error::NonceExhausted => ErrorKind::Other,
+        }
+    }
+}
+
+/// 便于在返回 `io::Result` 的代码中直接用 `?` 传播加密错误
+///
+/// `Error::Io` 还原为内部的 I/O 错误；其他错误按 [`Error::io_kind`] 选择类别并保留原错误，
+/// 可通过 `get_ref` 向下转型或再转换回 [`Error`] 取得。
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Io(e) => e,
+            other => std::io::Error::new(other.io_kind(), other),
+        }
+    }
+}
+
 impl From<base64::DecodeError> for Error {
     fn from(err: base64::DecodeError) -> Self {
         Error::Format(format!("Base64解码错误: {}", err))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
         Error::Serialization(format!("JSON错误: {}", err))
@@ -84,8 +295,82 @@ impl From<serde_json::Error> for Error {
 }
 
 // 可以添加更多转换实现，方便错误处理
-impl From<std::string::FromUtf8Error> for Error {
-    fn from(err: std::string::FromUtf8Error) -> Self {
+impl From<alloc::string::FromUtf8Error> for Error {
+    fn from(err: alloc::string::FromUtf8Error) -> Self {
         Error::Format(format!("UTF-8转换错误: {}", err))
     }
-} 
\ No newline at end of file
+} 
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_error_maps_to_io_kind() {
+        let cases = [
+            (Error::DecryptionFailed("tag".into()), ErrorKind::InvalidData),
+            (Error::AuthenticationFailed(DecryptDiagnostics {
+                format_valid: true,
+                possible_causes: vec![DecryptFailureCause::WrongKey],
+                detail: "tag".into(),
+            }), ErrorKind::InvalidData),
+            (Error::StreamIntegrity("truncated".into()), ErrorKind::InvalidData),
+            (Error::Format("bad".into()), ErrorKind::InvalidData),
+            (Error::Key("short".into()), ErrorKind::InvalidInput),
+            (Error::BufferTooSmall { required: 2, provided: 1 }, ErrorKind::InvalidInput),
+            (Error::NotYetValid("later".into()), ErrorKind::PermissionDenied),
+            (Error::KeyRevoked("0011223344556677".into()), ErrorKind::PermissionDenied),
+            (Error::Cancelled, ErrorKind::Other),
+        ];
+        for (error, kind) in cases {
+            let io_error: std::io::Error = error.into();
+            assert_eq!(io_error.kind(), kind);
+        }
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let io_error: std::io::Error = Error::DecryptionFailed("tag mismatch".into()).into();
+        assert!(io_error.to_string().contains("tag mismatch"));
+        assert!(matches!(Error::from(io_error), Error::DecryptionFailed(msg) if msg == "tag mismatch"));
+
+        // 原生 I/O 错误往返后保持类别不变，且不会被多包一层
+        let original = std::io::Error::new(ErrorKind::UnexpectedEof, "eof");
+        let error = Error::from(original);
+        assert!(matches!(&error, Error::Io(e) if e.kind() == ErrorKind::UnexpectedEof));
+        let back: std::io::Error = error.into();
+        assert_eq!(back.kind(), ErrorKind::UnexpectedEof);
+        assert!(back.get_ref().is_some_and(|inner| !inner.is::<Error>()));
+    }
+
+    #[cfg(feature = "aes-gcm-feature")]
+    #[test]
+    fn test_streaming_errors_propagate_as_io_errors() {
+        use crate::common::streaming::StreamingConfig;
+        use crate::common::utils::CryptoConfig;
+        use crate::symmetric::systems::aes_gcm::AesGcmSystem;
+        use crate::symmetric::traits::{SymmetricCryptographicSystem, SymmetricSyncStreamingSystem};
+        use std::io::Cursor;
+
+        fn decrypt(key: &<AesGcmSystem as SymmetricCryptographicSystem>::Key, encrypted: &[u8], config: &StreamingConfig) -> std::io::Result<Vec<u8>> {
+            let mut plaintext = Vec::new();
+            AesGcmSystem::decrypt_stream(key, Cursor::new(encrypted), &mut plaintext, config, None)?;
+            Ok(plaintext)
+        }
+
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let config = StreamingConfig::default().with_buffer_size(16).with_integrity_footer(true);
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(b"io adapters want io errors"), &mut encrypted, &config, None).unwrap();
+        assert_eq!(decrypt(&key, &encrypted, &config).unwrap(), b"io adapters want io errors");
+
+        let mut tampered = encrypted.clone();
+        tampered[10] ^= 1;
+        assert_eq!(decrypt(&key, &tampered, &config).unwrap_err().kind(), ErrorKind::InvalidData);
+
+        let truncated = &encrypted[..encrypted.len() - 4];
+        let error = decrypt(&key, truncated, &config).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(matches!(Error::from(error), Error::StreamIntegrity(_)));
+    }
+}
@@ -0,0 +1,249 @@
+//! 无需密钥读取加密数据块的描述信息
+//!
+//! 面向只负责按算法路由、不持有任何密钥的存储层：[`inspect`] 只解析数据块的明文头部，
+//! 从不尝试解密，也不会因输入畸形而 panic。可识别的格式：
+//!
+//! - 引擎输出的密文信封（见 [`CiphertextEnvelope`]）：读取密钥ID、公钥指纹、生效时间，
+//!   以及受认证头部中的 [`ALGORITHM_HEADER`] 与 [`CREATED_AT_HEADER`] 字段。非对称引擎默认写入算法标识
+//!   （见 [`CryptoConfig::embed_algorithm_id`]），但RSA无法认证头部，其输出不含该字段；对称引擎输出裸密文，没有信封；
+//! - 口令加密数据块（`secure-storage`，见 [`crate::symmetric::password`]）：读取 Argon2 参数；
+//! - `EncryptedKeyContainer` 的 JSON（`secure-storage`）：读取算法标识、创建时间与 KDF 参数。
+//!
+//! 除信封头部与口令数据块头部外，这些信息都没有经过认证，只能用于路由，不能作为安全决策的依据。
+//!
+//! [`verify_produced_with`] 检查信封头部中 [`CONFIG_DIGEST_HEADER`] 记录的配置摘要，只适合路由与预筛，
+//! 作为策略依据时应使用引擎的 `decrypt_requiring_profile`；
+//! [`is_revoked`] 检查信封中的密钥指纹是否在调用方维护的吊销列表中。
+
+use std::collections::HashSet;
+
+use crate::common::errors::Error;
+use crate::common::utils::{CiphertextEnvelope, CryptoConfig, Profile};
+
+/// 信封头部中记录算法名称的字段，由非对称引擎按 [`CryptoConfig::embed_algorithm_id`] 写入
+pub const ALGORITHM_HEADER: &str = "alg";
+/// 信封头部中记录创建时间的字段，由调用方在 `encrypt_with_headers` 时写入
+pub const CREATED_AT_HEADER: &str = "created_at";
+
+/// 信封头部中记录 [`CryptoConfig::config_digest`]（十六进制）的字段，由引擎的 `encrypt_with_config_digest` 写入
+pub const CONFIG_DIGEST_HEADER: &str = "config_digest";
+
+/// 由引擎写入、调用方不能经 `encrypt_with_headers` 提供的头部字段
+pub const RESERVED_HEADERS: [&str; 2] = [ALGORITHM_HEADER, CONFIG_DIGEST_HEADER];
+
+/// 配置摘要在信封头部中的十六进制表示
+pub(crate) fn config_digest_hex(config: &CryptoConfig) -> String {
+    config.config_digest().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 数据块是否由 `expected_profile` 的预设配置（[`CryptoConfig::preset`]）加密
+///
+/// 见 [`verify_produced_with_config`]。
+pub fn verify_produced_with(blob: &str, expected_profile: Profile) -> bool {
+    verify_produced_with_config(blob, &CryptoConfig::preset(expected_profile))
+}
+
+/// 数据块信封头部记录的配置摘要是否与 `expected` 的摘要一致
+///
+/// 摘要覆盖配置的全部字段，加密时的配置与 `expected` 有任何差异都不会通过；没有记录摘要或信封无法解析时返回 `false`。
+/// 头部参与 AEAD 认证，但本函数不解密：被篡改头部的密文同样能通过检查。需要据此做策略判断时，
+/// 使用引擎的 `decrypt_requiring_profile`/`decrypt_requiring_config`，它们在认证解密的同时检查摘要。
+pub fn verify_produced_with_config(blob: &str, expected: &CryptoConfig) -> bool {
+    CiphertextEnvelope::parse(blob.trim()).is_ok_and(|envelope| {
+        envelope.headers().get(CONFIG_DIGEST_HEADER).is_some_and(|digest| *digest == config_digest_hex(expected))
+    })
+}
+
+/// 数据块是否由 `revoked` 中列出指纹的公钥加密
+///
+/// 只读取信封中的密钥指纹（见 [`crate::common::utils::CryptoConfig::embed_key_fingerprint`]），不解密。
+/// 未嵌入指纹或无法解析的数据块返回 `false`，因此吊销检查要求加密时启用指纹嵌入。
+pub fn is_revoked(blob: &str, revoked: &HashSet<String>) -> bool {
+    CiphertextEnvelope::parse(blob.trim()).is_ok_and(|envelope| envelope.check_revoked(revoked).is_err())
+}
+
+/// 从数据块明文头部读取的描述信息，格式中不存在的字段为 `None`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BlobInfo {
+    /// 算法名称
+    pub algorithm: Option<String>,
+    /// 加密所用公钥的指纹
+    pub key_fingerprint: Option<String>,
+    /// 密钥ID
+    pub key_id: Option<String>,
+    /// 创建时间
+    pub created_at: Option<String>,
+    /// 算法参数摘要，如 Argon2 参数或生效时间
+    pub param_summary: Option<String>,
+}
+
+/// 读取数据块的描述信息，不需要任何密钥
+///
+/// 头部损坏（如无法解码的密钥ID、非法的头部 JSON、不完整的口令数据块头部）时返回 `Error::Format`
+/// 或 `Error::Serialization`；没有任何可识别头部的裸密文返回所有字段均为 `None` 的 [`BlobInfo`]。
+pub fn inspect(blob: &str) -> Result<BlobInfo, Error> {
+    let blob = blob.trim();
+    if blob.starts_with('{') {
+        return inspect_key_container(blob);
+    }
+
+    let envelope = CiphertextEnvelope::parse(blob)?;
+    #[cfg(feature = "secure-storage")]
+    if let Ok(bytes) = crate::common::utils::from_base64(envelope.body())
+        && let Some((memory_cost, time_cost, parallelism)) = crate::symmetric::password::header_params(&bytes)?
+    {
+        return Ok(BlobInfo {
+            algorithm: Some("Argon2id/AES-256-GCM".to_string()),
+            param_summary: Some(format!("m={},t={},p={}", memory_cost, time_cost, parallelism)),
+            ..BlobInfo::default()
+        });
+    }
+
+    let headers = envelope.headers();
+    Ok(BlobInfo {
+        algorithm: headers.get(ALGORITHM_HEADER).cloned(),
+        key_fingerprint: envelope.fingerprint().map(str::to_string),
+        key_id: envelope.key_id().map(str::to_string),
+        created_at: headers.get(CREATED_AT_HEADER).cloned(),
+        param_summary: envelope.not_before()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|since_epoch| format!("nbf={}", since_epoch.as_secs())),
+    })
+}
+
+#[cfg(feature = "secure-storage")]
+fn inspect_key_container(json: &str) -> Result<BlobInfo, Error> {
+    use crate::common::traits::SecureKeyStorage;
+    use crate::storage::container::EncryptedKeyContainer;
+
+    let container = EncryptedKeyContainer::from_json(json)?;
+    Ok(BlobInfo {
+        algorithm: Some(container.algorithm_id().to_string()),
+        created_at: Some(container.created_at().to_string()),
+        param_summary: Some(container.kdf_summary()),
+        ..BlobInfo::default()
+    })
+}
+
+#[cfg(not(feature = "secure-storage"))]
+fn inspect_key_container(_json: &str) -> Result<BlobInfo, Error> {
+    Err(Error::Format("解析密钥容器需要启用 secure-storage 特性".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_inspect_engine_envelope() {
+        use crate::asymmetric::engines::AsymmetricQSealEngine;
+        use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+        use crate::common::config::{ConfigFile, ConfigManager, StorageConfig};
+        use std::collections::BTreeMap;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = ConfigFile {
+            storage: StorageConfig { key_storage_dir: dir.path().to_str().unwrap().to_string(), ..Default::default() },
+            rotation: Default::default(),
+            crypto: CryptoConfig { embed_key_fingerprint: true, ..Default::default() },
+        };
+        let mut engine = AsymmetricQSealEngine::<KyberCryptoSystem>::new(Arc::new(ConfigManager::from_config_file(config)), "inspect").unwrap();
+
+        // 默认输出即带有受认证的算法标识
+        let blob = engine.encrypt(b"routed").unwrap();
+        let info = inspect(&blob).unwrap();
+        assert_eq!(info.algorithm.as_deref(), Some("Kyber"));
+        let fingerprint = info.key_fingerprint.unwrap();
+        assert_eq!(engine.decrypt(&blob).unwrap(), b"routed");
+
+        let headers = BTreeMap::from([(CREATED_AT_HEADER.to_string(), "2025-01-01T00:00:00Z".to_string())]);
+        let info = inspect(&engine.encrypt_with_headers(b"routed", &headers).unwrap()).unwrap();
+        assert_eq!(info.algorithm.as_deref(), Some("Kyber"));
+        assert_eq!(info.created_at.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(info.key_fingerprint.as_deref(), Some(fingerprint.as_str()));
+
+        let not_before = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_800_000_000);
+        let info = inspect(&engine.encrypt_with_not_before(b"routed", not_before).unwrap()).unwrap();
+        assert_eq!(info.algorithm.as_deref(), Some("Kyber"));
+        assert_eq!(info.param_summary.as_deref(), Some("nbf=1800000000"));
+
+        let info = inspect(&engine.encrypt_with_key_id(b"routed", Some("tenant-a".to_string())).unwrap()).unwrap();
+        assert_eq!(info.key_id.as_deref(), Some("tenant-a"));
+        assert_eq!(info.algorithm.as_deref(), Some("Kyber"));
+
+        // 调用方不能伪造算法标识
+        let forged = BTreeMap::from([(ALGORITHM_HEADER.to_string(), "RSA".to_string())]);
+        assert!(engine.encrypt_with_headers(b"routed", &forged).is_err());
+
+        // 关闭后不写入算法标识；没有信封字段的裸密文不报错
+        let mut cfg = engine.config().get_crypto_config();
+        cfg.embed_algorithm_id = false;
+        cfg.embed_key_fingerprint = false;
+        engine.config().update_crypto_config(cfg).unwrap();
+        let bare = engine.encrypt(b"routed").unwrap();
+        assert_eq!(inspect(&bare).unwrap(), BlobInfo::default());
+        assert_eq!(engine.decrypt(&bare).unwrap(), b"routed");
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_is_revoked_matches_embedded_fingerprint() {
+        use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+        use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+        use crate::common::utils::{attach_fingerprint, attach_key_id, key_fingerprint};
+
+        let (public_key, _) = KyberCryptoSystem::generate_keypair(&CryptoConfig::default()).unwrap();
+        let fingerprint = key_fingerprint(&KyberCryptoSystem::export_public_key(&public_key).unwrap());
+        let body = KyberCryptoSystem::encrypt(&public_key, b"revocable", None).unwrap().to_string();
+        let blob = attach_key_id("tenant-a", &attach_fingerprint(&fingerprint, &body));
+
+        let mut revoked = HashSet::from(["0000000000000000".to_string()]);
+        assert!(!is_revoked(&blob, &revoked));
+        revoked.insert(fingerprint);
+        assert!(is_revoked(&blob, &revoked));
+        assert!(is_revoked(&format!("{blob}\n"), &revoked));
+
+        // 没有指纹或无法解析时无法判断
+        assert!(!is_revoked(&body, &revoked));
+        assert!(!is_revoked("not an envelope", &revoked));
+    }
+
+    #[cfg(feature = "secure-storage")]
+    #[test]
+    fn test_inspect_password_blob_and_key_container() {
+        use crate::common::traits::SecureKeyStorage;
+        use crate::common::utils::CryptoConfig;
+        use crate::storage::container::EncryptedKeyContainer;
+        use secrecy::SecretString;
+
+        let password = SecretString::from("routing needs no password");
+        let config = CryptoConfig::default();
+        let blob = crate::symmetric::password::encrypt_with_password(b"data", &password, &config).unwrap();
+        let info = inspect(&blob).unwrap();
+        assert_eq!(info.algorithm.as_deref(), Some("Argon2id/AES-256-GCM"));
+        assert_eq!(info.param_summary, Some(format!("m={},t={},p=1", config.argon2_memory_cost, config.argon2_time_cost)));
+
+        let container = EncryptedKeyContainer::new(&password, b"key bytes", "AES-256-GCM").unwrap();
+        let info = inspect(&container.to_json().unwrap()).unwrap();
+        assert_eq!(info.algorithm.as_deref(), Some("AES-256-GCM"));
+        assert_eq!(info.created_at.as_deref(), Some(container.created_at()));
+        assert!(info.param_summary.unwrap().starts_with("Argon2id m="));
+        assert!(info.key_fingerprint.is_none());
+    }
+
+    #[test]
+    fn test_inspect_rejects_corrupted_headers() {
+        assert!(inspect("kid:not-terminated").is_err());
+        assert!(inspect("hdr:bm90IGpzb24.Y2lwaGVy").is_err());
+        assert!(inspect("{ not json").is_err());
+        // 以口令数据块魔数开头但头部被截断
+        #[cfg(feature = "secure-storage")]
+        assert!(inspect(&crate::common::utils::to_base64(b"SKPW\x01\x00")).is_err());
+        // 任意输入都不会 panic
+        for input in ["", ".", "kid:.", "hdr:.", "nbf:x.", "{}", "\u{0}\u{ff}"] {
+            let _ = inspect(input);
+        }
+    }
+}
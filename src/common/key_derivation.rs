@@ -0,0 +1,272 @@
+//! 可插拔的密钥派生函数
+//!
+//! 不同部署对 KDF 的要求各不相同（HKDF、Argon2、scrypt、PBKDF2）。[`KeyDerivation`] 统一了它们的调用方式，
+//! 口令密钥容器与子密钥派生都可以替换为调用方提供的实现。内置实现：
+//!
+//! - [`HkdfSha256`]：RFC 5869，用于从高熵密钥材料派生子密钥（子密钥派生的默认值）；
+//! - [`Pbkdf2Sha256`]：RFC 8018 PBKDF2-HMAC-SHA256；
+//! - [`Scrypt`]：RFC 7914；
+//! - [`Argon2id`]（`secure-storage`）：RFC 9106，口令密钥容器的默认值。
+//!
+//! 口令类 KDF 没有 `info` 输入，盐值与 `info` 各自加上长度前缀后拼接为实际的盐值参与派生，
+//! 因此不同的 (盐值, `info`) 组合不会得到相同的密钥。
+//! PBKDF2 与 scrypt 分别由 RustCrypto 的 `pbkdf2`、`scrypt` crate 实现。
+
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::common::errors::Error;
+
+/// 密钥派生函数
+pub trait KeyDerivation: Send + Sync {
+    /// 算法标识，记录在密钥容器中，解密时用于确认使用了相同的 KDF
+    fn algorithm(&self) -> &str;
+
+    /// 从输入密钥材料 `ikm`、盐值与上下文信息派生 `out_len` 字节密钥
+    fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<Zeroizing<Vec<u8>>, Error>;
+}
+
+/// HKDF-SHA256，空盐值等价于 RFC 5869 中的默认盐值
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HkdfSha256;
+
+impl KeyDerivation for HkdfSha256 {
+    fn algorithm(&self) -> &str {
+        "HKDF-SHA256"
+    }
+
+    fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let mut okm = Zeroizing::new(vec![0u8; out_len]);
+        Hkdf::<Sha256>::new(Some(salt), ikm)
+            .expand(info, okm.as_mut())
+            .map_err(|_| Error::Key(format!("HKDF-SHA256 输出长度过大: {} 字节", out_len)))?;
+        Ok(okm)
+    }
+}
+
+/// 口令类 KDF 实际使用的盐值：`u32 LE 盐值长度 || 盐值 || u32 LE info 长度 || info`
+fn context_salt(salt: &[u8], info: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut encoded = Vec::with_capacity(8 + salt.len() + info.len());
+    for part in [salt, info] {
+        let len = u32::try_from(part.len())
+            .map_err(|_| Error::Key(format!("盐值或 info 长度 {} 字节超出 u32 长度前缀的上限", part.len())))?;
+        encoded.extend_from_slice(&len.to_le_bytes());
+        encoded.extend_from_slice(part);
+    }
+    Ok(encoded)
+}
+
+/// PBKDF2-HMAC-SHA256
+#[derive(Debug, Clone, Copy)]
+pub struct Pbkdf2Sha256 {
+    /// 迭代次数，至少为 1
+    pub iterations: u32,
+}
+
+impl Default for Pbkdf2Sha256 {
+    /// OWASP 建议的 600000 次迭代
+    fn default() -> Self {
+        Self { iterations: 600_000 }
+    }
+}
+
+impl KeyDerivation for Pbkdf2Sha256 {
+    fn algorithm(&self) -> &str {
+        "PBKDF2-HMAC-SHA256"
+    }
+
+    fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if self.iterations == 0 {
+            return Err(Error::Key("PBKDF2 迭代次数不能为 0".into()));
+        }
+        let mut okm = Zeroizing::new(vec![0u8; out_len]);
+        pbkdf2::pbkdf2_hmac::<Sha256>(ikm, &context_salt(salt, info)?, self.iterations, okm.as_mut());
+        Ok(okm)
+    }
+}
+
+/// scrypt，内存开销约为 `128 * r * 2^log_n` 字节，上限为 [`SCRYPT_MAX_MEMORY`]
+#[derive(Debug, Clone, Copy)]
+pub struct Scrypt {
+    /// CPU/内存开销参数 N 的以 2 为底的对数
+    pub log_n: u8,
+    /// 块大小参数，不超过 [`SCRYPT_MAX_R`]
+    pub r: u32,
+    /// 并行度参数，不超过 [`SCRYPT_MAX_P`]
+    pub p: u32,
+}
+
+impl Default for Scrypt {
+    /// RFC 7914 建议的交互式参数 N = 2^15, r = 8, p = 1
+    fn default() -> Self {
+        Self { log_n: 15, r: 8, p: 1 }
+    }
+}
+
+/// scrypt 接受的最大 `log_n`
+const SCRYPT_MAX_LOG_N: u8 = 20;
+/// scrypt 接受的最大块大小参数 `r`
+pub const SCRYPT_MAX_R: u32 = 32;
+/// scrypt 接受的最大并行度参数 `p`
+pub const SCRYPT_MAX_P: u32 = 16;
+/// scrypt 派生时允许占用的最大内存（1 GiB），即 `r = 8` 时 `log_n = 20`
+pub const SCRYPT_MAX_MEMORY: u64 = 1 << 30;
+
+impl Scrypt {
+    /// 派生所需的内存字节数 `128 * r * 2^log_n`，`log_n` 超出上限时返回 `None`
+    pub fn memory_cost(&self) -> Option<u64> {
+        (self.log_n <= SCRYPT_MAX_LOG_N).then(|| (128 * u64::from(self.r)) << self.log_n)
+    }
+}
+
+impl KeyDerivation for Scrypt {
+    fn algorithm(&self) -> &str {
+        "scrypt"
+    }
+
+    fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let invalid = || Error::Key(format!("scrypt参数无效 (log_n = {}, r = {}, p = {})", self.log_n, self.r, self.p));
+        if self.log_n == 0 || self.r > SCRYPT_MAX_R || self.p > SCRYPT_MAX_P
+            || self.memory_cost().is_none_or(|memory| memory > SCRYPT_MAX_MEMORY)
+        {
+            return Err(invalid());
+        }
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, out_len).map_err(|_| invalid())?;
+        let mut okm = Zeroizing::new(vec![0u8; out_len]);
+        scrypt::scrypt(ikm, &context_salt(salt, info)?, &params, okm.as_mut())
+            .map_err(|_| Error::Key(format!("scrypt 输出长度无效: {} 字节", out_len)))?;
+        Ok(okm)
+    }
+}
+
+/// Argon2id，参数含义与 `CryptoConfig` 中的 `argon2_memory_cost`、`argon2_time_cost` 相同
+#[cfg(feature = "secure-storage")]
+#[derive(Debug, Clone, Copy)]
+pub struct Argon2id {
+    /// 内存开销（KB）
+    pub memory_cost: u32,
+    /// 迭代次数
+    pub time_cost: u32,
+    /// 并行度
+    pub parallelism: u32,
+}
+
+#[cfg(feature = "secure-storage")]
+impl Default for Argon2id {
+    fn default() -> Self {
+        let config = crate::common::utils::CryptoConfig::default();
+        Self { memory_cost: config.argon2_memory_cost, time_cost: config.argon2_time_cost, parallelism: 1 }
+    }
+}
+
+#[cfg(feature = "secure-storage")]
+impl KeyDerivation for Argon2id {
+    fn algorithm(&self) -> &str {
+        "Argon2id"
+    }
+
+    fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        use argon2::{Algorithm, Argon2, Params, Version};
+
+        let params = Params::new(self.memory_cost, self.time_cost, self.parallelism, Some(out_len))
+            .map_err(|e| Error::Key(format!("Argon2参数无效: {}", e)))?;
+        let mut okm = Zeroizing::new(vec![0u8; out_len]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(ikm, &context_salt(salt, info)?, okm.as_mut())
+            .map_err(|e| Error::Key(format!("Argon2 派生密钥失败: {}", e)))?;
+        Ok(okm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hkdf_sha256_rfc5869_vector() {
+        // RFC 5869 测试用例 1
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        let okm = HkdfSha256.derive(&[0x0b; 22], &salt, &info, 42).unwrap();
+        assert_eq!(hex(&okm), "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865");
+        assert!(HkdfSha256.derive(b"ikm", b"", b"", 255 * 32 + 1).is_err());
+    }
+
+    #[test]
+    fn test_context_salt_is_length_prefixed() {
+        assert_eq!(context_salt(b"salt", b"").unwrap(), b"\x04\0\0\0salt\0\0\0\0");
+        assert_eq!(context_salt(b"sa", b"lt").unwrap(), b"\x02\0\0\0sa\x02\0\0\0lt");
+    }
+
+    #[test]
+    fn test_pbkdf2_sha256_vectors() {
+        // RFC 7914 第 11 节
+        let mut okm = [0u8; 64];
+        pbkdf2::pbkdf2_hmac::<Sha256>(b"passwd", b"salt", 1, &mut okm);
+        assert_eq!(hex(&okm), "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783");
+        let derived = Pbkdf2Sha256 { iterations: 1 }.derive(b"passwd", b"salt", b"ctx", 64).unwrap();
+        pbkdf2::pbkdf2_hmac::<Sha256>(b"passwd", &context_salt(b"salt", b"ctx").unwrap(), 1, &mut okm);
+        assert_eq!(derived.as_slice(), okm);
+
+        // 盐值与 info 的边界不同则密钥不同
+        assert_ne!(
+            Pbkdf2Sha256 { iterations: 2 }.derive(b"pw", b"sa", b"lt", 16).unwrap(),
+            Pbkdf2Sha256 { iterations: 2 }.derive(b"pw", b"salt", b"", 16).unwrap()
+        );
+        assert!(Pbkdf2Sha256 { iterations: 0 }.derive(b"pw", b"salt", b"", 16).is_err());
+    }
+
+    #[test]
+    fn test_scrypt_rfc7914_vectors() {
+        let mut okm = [0u8; 64];
+        scrypt::scrypt(b"", b"", &scrypt::Params::new(4, 1, 1, 64).unwrap(), &mut okm).unwrap();
+        assert_eq!(hex(&okm), "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906");
+        scrypt::scrypt(b"password", b"NaCl", &scrypt::Params::new(10, 8, 16, 64).unwrap(), &mut okm).unwrap();
+        assert_eq!(hex(&okm), "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640");
+
+        // 派生结果即以编码后的盐值调用 scrypt
+        let derived = Scrypt { log_n: 4, r: 1, p: 1 }.derive(b"pw", b"NaCl", b"ctx", 64).unwrap();
+        scrypt::scrypt(b"pw", &context_salt(b"NaCl", b"ctx").unwrap(), &scrypt::Params::new(4, 1, 1, 64).unwrap(), &mut okm).unwrap();
+        assert_eq!(derived.as_slice(), okm);
+        assert_ne!(
+            Scrypt { log_n: 4, r: 1, p: 1 }.derive(b"pw", b"sa", b"lt", 16).unwrap(),
+            Scrypt { log_n: 4, r: 1, p: 1 }.derive(b"pw", b"salt", b"", 16).unwrap()
+        );
+        assert!(Scrypt { log_n: 0, r: 8, p: 1 }.derive(b"pw", b"salt", b"", 32).is_err());
+        assert!(Scrypt { log_n: 4, r: 0, p: 1 }.derive(b"pw", b"salt", b"", 32).is_err());
+    }
+
+    #[test]
+    fn test_scrypt_memory_is_capped() {
+        // 超过上限的参数在分配内存之前就被拒绝
+        assert!(Scrypt { log_n: 4, r: SCRYPT_MAX_R + 1, p: 1 }.derive(b"pw", b"salt", b"", 32).is_err());
+        assert!(Scrypt { log_n: 20, r: 16, p: 1 }.derive(b"pw", b"salt", b"", 32).is_err());
+        assert!(Scrypt { log_n: 21, r: 1, p: 1 }.derive(b"pw", b"salt", b"", 32).is_err());
+        assert!(Scrypt { log_n: 4, r: 32, p: SCRYPT_MAX_P + 1 }.derive(b"pw", b"salt", b"", 32).is_err());
+        assert_eq!(Scrypt { log_n: 20, r: 8, p: 1 }.memory_cost(), Some(SCRYPT_MAX_MEMORY));
+        assert_eq!(Scrypt { log_n: 14, r: 8, p: 1 }.memory_cost(), Some((128 * 8) << 14));
+    }
+
+    #[cfg(feature = "secure-storage")]
+    #[test]
+    fn test_argon2id_matches_direct_derivation() {
+        use argon2::{Algorithm, Argon2, Params, Version};
+
+        let kdf = Argon2id { memory_cost: 64, time_cost: 1, parallelism: 1 };
+        let okm = kdf.derive(b"password", b"somesalt", b"", 32).unwrap();
+        let mut expected = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, Params::new(64, 1, 1, Some(32)).unwrap())
+            .hash_password_into(b"password", &context_salt(b"somesalt", b"").unwrap(), &mut expected)
+            .unwrap();
+        assert_eq!(okm.as_slice(), expected);
+        assert!(Argon2id { memory_cost: 1, time_cost: 1, parallelism: 1 }.derive(b"pw", b"somesalt", b"", 32).is_err());
+    }
+}
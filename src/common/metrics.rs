@@ -0,0 +1,101 @@
+//! 引擎运行指标
+//!
+//! 以原子计数器记录操作次数、处理字节数与失败次数，开销低且线程安全。
+//! 指标只包含计数与明文长度，不包含任何密钥、明文或由其派生的值。
+
+use crate::common::errors::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 引擎指标的时间点快照
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EngineMetrics {
+    /// 加密操作次数（含失败）
+    pub encrypt_operations: u64,
+    /// 解密操作次数（含失败）
+    pub decrypt_operations: u64,
+    /// 成功加密的明文字节总数
+    pub bytes_encrypted: u64,
+    /// 成功解密的明文字节总数
+    pub bytes_decrypted: u64,
+    /// 加密失败次数
+    pub encrypt_failures: u64,
+    /// 解密失败次数
+    pub decrypt_failures: u64,
+}
+
+/// 操作方向
+#[derive(Clone, Copy)]
+pub(crate) enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// 引擎内部持有的原子计数器
+#[derive(Debug, Default)]
+pub(crate) struct MetricsRecorder {
+    encrypt_operations: AtomicU64,
+    decrypt_operations: AtomicU64,
+    bytes_encrypted: AtomicU64,
+    bytes_decrypted: AtomicU64,
+    encrypt_failures: AtomicU64,
+    decrypt_failures: AtomicU64,
+}
+
+impl MetricsRecorder {
+    /// 记录一次操作的结果并原样返回；`bytes` 仅在成功时用于计算明文字节数
+    pub(crate) fn record<T>(
+        &self,
+        direction: Direction,
+        result: Result<T, Error>,
+        bytes: impl FnOnce(&T) -> u64,
+    ) -> Result<T, Error> {
+        let (operations, processed, failures) = match direction {
+            Direction::Encrypt => (&self.encrypt_operations, &self.bytes_encrypted, &self.encrypt_failures),
+            Direction::Decrypt => (&self.decrypt_operations, &self.bytes_decrypted, &self.decrypt_failures),
+        };
+        operations.fetch_add(1, Ordering::Relaxed);
+        match &result {
+            Ok(value) => {
+                processed.fetch_add(bytes(value), Ordering::Relaxed);
+            }
+            Err(_) => {
+                failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        result
+    }
+
+    /// 读取当前快照
+    pub(crate) fn snapshot(&self) -> EngineMetrics {
+        EngineMetrics {
+            encrypt_operations: self.encrypt_operations.load(Ordering::Relaxed),
+            decrypt_operations: self.decrypt_operations.load(Ordering::Relaxed),
+            bytes_encrypted: self.bytes_encrypted.load(Ordering::Relaxed),
+            bytes_decrypted: self.bytes_decrypted.load(Ordering::Relaxed),
+            encrypt_failures: self.encrypt_failures.load(Ordering::Relaxed),
+            decrypt_failures: self.decrypt_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_counts_success_and_failure() {
+        let recorder = MetricsRecorder::default();
+        let ok: Result<Vec<u8>, Error> = Ok(vec![0u8; 10]);
+        assert!(recorder.record(Direction::Encrypt, ok, |v| v.len() as u64).is_ok());
+        let err: Result<Vec<u8>, Error> = Err(Error::Operation("失败".to_string()));
+        assert!(recorder.record(Direction::Decrypt, err, |v| v.len() as u64).is_err());
+
+        assert_eq!(recorder.snapshot(), EngineMetrics {
+            encrypt_operations: 1,
+            bytes_encrypted: 10,
+            decrypt_operations: 1,
+            decrypt_failures: 1,
+            ..Default::default()
+        });
+    }
+}
@@ -0,0 +1,98 @@
+//! 定长 Nonce 类型
+//!
+//! 以类型参数固定 Nonce 长度，构造时校验长度，释放时自动清零。
+
+use alloc::format;
+#[cfg(feature = "aes-gcm-feature")]
+use alloc::string::ToString;
+use core::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::common::errors::Error;
+
+/// 长度为 `N` 字节的 Nonce
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Nonce<const N: usize>([u8; N]);
+
+impl<const N: usize> Nonce<N> {
+    /// Nonce 的字节长度
+    pub const LEN: usize = N;
+
+    /// 从字节切片创建 Nonce，长度必须恰好为 `N`
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let array: [u8; N] = bytes.try_into()
+            .map_err(|_| Error::Format(format!("无效的Nonce长度: 期望 {} 字节, 实际 {} 字节", N, bytes.len())))?;
+        Ok(Self(array))
+    }
+
+    /// 使用系统RNG生成随机 Nonce
+    #[cfg(feature = "aes-gcm-feature")]
+    pub fn random() -> Result<Self, Error> {
+        Self::random_from(&mut crate::common::rng::SystemRng)
+    }
+
+    /// 使用指定的随机数生成器生成 Nonce
+    #[cfg(feature = "aes-gcm-feature")]
+    pub(crate) fn random_from<R: rand_core::TryRngCore + ?Sized>(rng: &mut R) -> Result<Self, Error> {
+        let mut bytes = [0u8; N];
+        rng.try_fill_bytes(&mut bytes)
+            .map_err(|e| Error::RngFailure(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// 获取 Nonce 字节
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Nonce<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// 以十六进制显示（Nonce 本身不是机密数据）
+impl<const N: usize> fmt::Display for Nonce<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Nonce<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Nonce<{}>({})", N, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_slice_correct_length() {
+        let nonce = Nonce::<12>::from_slice(&[1u8; 12]).unwrap();
+        assert_eq!(nonce.as_bytes(), &[1u8; 12]);
+        assert_eq!(Nonce::<12>::LEN, 12);
+        assert_eq!(nonce.to_string(), "01".repeat(12));
+    }
+
+    #[test]
+    fn test_from_slice_wrong_length() {
+        assert!(matches!(Nonce::<12>::from_slice(&[0u8; 11]), Err(Error::Format(_))));
+        assert!(matches!(Nonce::<12>::from_slice(&[0u8; 13]), Err(Error::Format(_))));
+        assert!(Nonce::<24>::from_slice(&[]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "aes-gcm-feature")]
+    fn test_random_uniqueness() {
+        let nonces: std::collections::HashSet<[u8; 12]> = (0..1000)
+            .map(|_| *Nonce::<12>::random().unwrap().as_bytes())
+            .collect();
+        assert_eq!(nonces.len(), 1000);
+    }
+}
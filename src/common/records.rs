@@ -0,0 +1,160 @@
+//! 多条独立密文拼接存放时的记录分帧
+//!
+//! 引擎输出的密文信封（见 [`CiphertextEnvelope`]）是不带长度的文本，多条直接拼接后无法再分开。
+//! [`write_record`] 为每条信封加上 `u32 LE 长度 || 信封文本` 的帧，[`RecordReader`] 从任意 `Read`
+//! 中逐条取回，调用方可以一次只解密一条记录，而不必把整个文件读入内存。
+//!
+//! 在记录边界处读到文件结尾表示正常结束；长度前缀或记录正文只读到一部分时返回 `Error::StreamIntegrity`。
+
+use std::io::{Read, Write};
+
+use crate::common::errors::Error;
+use crate::common::utils::CiphertextEnvelope;
+
+/// 写出一条记录：`u32 LE 长度 || 信封文本`
+pub fn write_record<W: Write>(mut writer: W, record: &str) -> Result<(), Error> {
+    let len = u32::try_from(record.len())
+        .map_err(|_| Error::Format(format!("记录长度 {} 字节超出 u32 长度前缀的上限", record.len())))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(record.as_bytes())?;
+    Ok(())
+}
+
+/// 逐条读取 [`write_record`] 写出的记录
+///
+/// 每次迭代返回一条完整的信封文本，读取时已确认其可按 [`CiphertextEnvelope::parse`] 解析，
+/// 可直接交给引擎的 `decrypt`。遇到截断或畸形的记录时返回一次错误，之后迭代结束。
+pub struct RecordReader<R: Read> {
+    reader: R,
+    index: u64,
+    finished: bool,
+}
+
+impl<R: Read> RecordReader<R> {
+    /// 从 `reader` 的当前位置开始读取
+    pub fn new(reader: R) -> Self {
+        Self { reader, index: 0, finished: false }
+    }
+
+    /// 已成功读取的记录数
+    pub fn records_read(&self) -> u64 {
+        self.index
+    }
+
+    /// 取回内部的读取器
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_record(&mut self) -> Result<Option<String>, Error> {
+        let mut len_buf = [0u8; 4];
+        let prefix_len = read_up_to(&mut self.reader, &mut len_buf)?;
+        if prefix_len == 0 {
+            return Ok(None);
+        }
+        if prefix_len < len_buf.len() {
+            return Err(self.truncated(format!("长度前缀只有 {} 字节", prefix_len)));
+        }
+
+        let len = u32::from_le_bytes(len_buf) as usize;
+        // 按实际读到的数据增长缓冲区，伪造的长度前缀不会触发超大分配
+        let mut body = Vec::new();
+        (&mut self.reader).take(len as u64).read_to_end(&mut body)?;
+        if body.len() < len {
+            return Err(self.truncated(format!("声明 {} 字节，实际只有 {} 字节", len, body.len())));
+        }
+
+        let record = String::from_utf8(body)
+            .map_err(|e| Error::Format(format!("第 {} 条记录不是有效的UTF-8: {}", self.index, e)))?;
+        CiphertextEnvelope::parse(&record)?;
+        self.index += 1;
+        Ok(Some(record))
+    }
+
+    fn truncated(&self, detail: String) -> Error {
+        Error::StreamIntegrity(format!("第 {} 条记录被截断: {}", self.index, detail))
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.read_record().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+/// 尽量读满 `buf`，返回实际读到的字节数；只有在文件结尾时才会少于 `buf.len()`
+fn read_up_to<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(all(test, feature = "post-quantum"))]
+mod tests {
+    use super::*;
+    use std::io::{Seek, SeekFrom};
+    use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+    use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+    use crate::common::utils::{attach_key_version, CryptoConfig};
+
+    #[test]
+    fn test_reads_concatenated_records_one_at_a_time() {
+        let (public_key, private_key) = KyberCryptoSystem::generate_keypair(&CryptoConfig::default()).unwrap();
+        let messages: [&[u8]; 3] = [b"first", b"", b"third record"];
+        let mut file = tempfile::tempfile().unwrap();
+        for (i, message) in messages.iter().enumerate() {
+            let ciphertext = KyberCryptoSystem::encrypt(&public_key, message, None).unwrap().to_string();
+            write_record(&mut file, &attach_key_version(i as u32, &ciphertext)).unwrap();
+        }
+        file.seek(SeekFrom::Start(0)).unwrap();
+
+        let mut reader = RecordReader::new(file);
+        for (i, message) in messages.iter().enumerate() {
+            let record = reader.next().unwrap().unwrap();
+            let envelope = CiphertextEnvelope::parse(&record).unwrap();
+            assert_eq!(envelope.key_version(), Some(i as u32));
+            assert_eq!(KyberCryptoSystem::decrypt(&private_key, envelope.body(), None).unwrap(), *message);
+        }
+        assert!(reader.next().is_none());
+        assert_eq!(reader.records_read(), 3);
+    }
+
+    #[test]
+    fn test_truncated_final_record_errors() {
+        let (public_key, _) = KyberCryptoSystem::generate_keypair(&CryptoConfig::default()).unwrap();
+        let mut stream = Vec::new();
+        for message in [b"one", b"two"] {
+            let ciphertext = KyberCryptoSystem::encrypt(&public_key, message, None).unwrap().to_string();
+            write_record(&mut stream, &ciphertext).unwrap();
+        }
+        let first_len = 4 + u32::from_le_bytes(stream[..4].try_into().unwrap()) as usize;
+
+        // 截断在最后一条记录的正文中或长度前缀中
+        for cut in [stream.len() - 1, first_len + 5, first_len + 2] {
+            let mut reader = RecordReader::new(&stream[..cut]);
+            assert!(reader.next().unwrap().is_ok());
+            assert!(matches!(reader.next(), Some(Err(Error::StreamIntegrity(_)))), "cut at {cut}");
+            assert!(reader.next().is_none());
+        }
+
+        // 恰好在记录边界结束不是错误
+        assert_eq!(RecordReader::new(&stream[..first_len]).count(), 1);
+        assert_eq!(RecordReader::new(&[][..]).count(), 0);
+    }
+}
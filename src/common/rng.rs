@@ -0,0 +1,244 @@
+//! 随机数来源
+//!
+//! 库内部的密钥生成与 Nonce 生成统一通过 `SystemRng` 获取随机数，默认直接使用操作系统RNG。
+//! 启用 `test-determinism` 特性后，可以在当前线程内临时切换为由种子派生的确定性RNG，
+//! 用于下游的金样（golden-file）测试。该特性切勿在生产环境中启用。
+
+#[cfg(all(feature = "std", any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha")))]
+use crate::common::utils::CryptoConfig;
+#[cfg(feature = "test-determinism")]
+use std::cell::RefCell;
+#[cfg(feature = "test-determinism")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "aes-gcm-feature")]
+use aes_gcm::aead::rand_core as rand_core_06;
+#[cfg(all(feature = "traditional", not(feature = "aes-gcm-feature")))]
+use rsa::rand_core as rand_core_06;
+
+/// 由种子派生的确定性RNG（SHA-256 计数器模式）
+///
+/// 仅用于测试，输出完全由种子决定。
+#[cfg(feature = "test-determinism")]
+#[derive(Debug)]
+pub struct DeterministicRng {
+    seed: [u8; 8],
+    counter: u64,
+    block: [u8; 32],
+    offset: usize,
+}
+
+#[cfg(feature = "test-determinism")]
+impl DeterministicRng {
+    /// 使用种子创建确定性RNG
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed: seed.to_le_bytes(),
+            counter: 0,
+            block: [0u8; 32],
+            offset: 32,
+        }
+    }
+
+    /// 填充随机字节
+    pub fn fill(&mut self, dest: &mut [u8]) {
+        use sha2::{Digest, Sha256};
+
+        for byte in dest.iter_mut() {
+            if self.offset == self.block.len() {
+                let mut hasher = Sha256::new();
+                hasher.update(b"seal-kit-test-determinism");
+                hasher.update(self.seed);
+                hasher.update(self.counter.to_le_bytes());
+                self.block.copy_from_slice(&hasher.finalize());
+                self.counter += 1;
+                self.offset = 0;
+            }
+            *byte = self.block[self.offset];
+            self.offset += 1;
+        }
+    }
+}
+
+#[cfg(feature = "test-determinism")]
+thread_local! {
+    static ACTIVE_RNG: RefCell<Option<Arc<Mutex<DeterministicRng>>>> = const { RefCell::new(None) };
+}
+
+/// 若当前线程安装了确定性RNG，则用其填充并返回 `true`
+#[cfg(all(feature = "test-determinism", any(feature = "traditional", feature = "aes-gcm-feature")))]
+fn fill_deterministic(dest: &mut [u8]) -> bool {
+    ACTIVE_RNG.with(|active| match active.borrow().as_ref() {
+        Some(rng) => {
+            rng.lock().unwrap_or_else(|e| e.into_inner()).fill(dest);
+            true
+        }
+        None => false,
+    })
+}
+
+#[cfg(all(not(feature = "test-determinism"), any(feature = "traditional", feature = "aes-gcm-feature")))]
+#[inline]
+fn fill_deterministic(_dest: &mut [u8]) -> bool {
+    false
+}
+
+/// 引擎持有的RNG作用域
+///
+/// 未启用 `test-determinism` 时为零大小类型，`run` 直接执行闭包。
+/// 启用后，若配置中设置了种子，则每个引擎拥有独立的确定性RNG状态，
+/// 并在执行操作期间安装到当前线程。
+#[cfg(all(feature = "std", any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha")))]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EngineRng {
+    #[cfg(feature = "test-determinism")]
+    rng: Option<Arc<Mutex<DeterministicRng>>>,
+}
+
+#[cfg(all(feature = "std", any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha")))]
+impl EngineRng {
+    /// 根据加密配置创建RNG作用域
+    pub(crate) fn from_config(config: &CryptoConfig) -> Self {
+        #[cfg(feature = "test-determinism")]
+        {
+            Self {
+                rng: config.deterministic_seed
+                    .map(|seed| Arc::new(Mutex::new(DeterministicRng::from_seed(seed)))),
+            }
+        }
+        #[cfg(not(feature = "test-determinism"))]
+        {
+            let _ = config;
+            Self {}
+        }
+    }
+
+    /// 在此RNG作用域内执行操作
+    pub(crate) fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "test-determinism")]
+        if let Some(rng) = &self.rng {
+            let previous = ACTIVE_RNG.with(|active| active.replace(Some(Arc::clone(rng))));
+            let _restore = RestoreActiveRng(previous);
+            return f();
+        }
+        f()
+    }
+}
+
+/// 离开作用域时恢复之前安装的RNG，即使闭包发生 panic 也不会残留种子RNG
+#[cfg(all(feature = "std", feature = "test-determinism", any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha")))]
+struct RestoreActiveRng(Option<Arc<Mutex<DeterministicRng>>>);
+
+#[cfg(all(feature = "std", feature = "test-determinism", any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha")))]
+impl Drop for RestoreActiveRng {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        ACTIVE_RNG.with(|active| *active.borrow_mut() = previous);
+    }
+}
+
+/// 库内部使用的加密安全RNG
+///
+/// 默认委托给操作系统RNG；在确定性测试模式下委托给当前线程安装的种子RNG。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemRng;
+
+#[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+impl rand_core_06::RngCore for SystemRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core_06::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core_06::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if !fill_deterministic(dest) {
+            rand_core_06::OsRng.fill_bytes(dest);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core_06::Error> {
+        if !fill_deterministic(dest) {
+            rand_core_06::OsRng.try_fill_bytes(dest)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+impl rand_core_06::CryptoRng for SystemRng {}
+
+#[cfg(feature = "aes-gcm-feature")]
+impl rand_core::TryRngCore for SystemRng {
+    type Error = rand_core::OsError;
+
+    fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+        let mut bytes = [0u8; 4];
+        self.try_fill_bytes(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+        let mut bytes = [0u8; 8];
+        self.try_fill_bytes(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Self::Error> {
+        if !fill_deterministic(dest) {
+            rand_core::OsRng.try_fill_bytes(dest)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "aes-gcm-feature")]
+impl rand_core::TryCryptoRng for SystemRng {}
+
+/// 使用 [`SystemRng`] 填充随机字节
+#[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+pub(crate) fn fill_random(dest: &mut [u8]) {
+    rand_core_06::RngCore::fill_bytes(&mut SystemRng, dest);
+}
+
+#[cfg(all(test, feature = "test-determinism", any(feature = "traditional", feature = "aes-gcm-feature")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_rng_is_reproducible() {
+        let mut a = DeterministicRng::from_seed(42);
+        let mut b = DeterministicRng::from_seed(42);
+        let mut c = DeterministicRng::from_seed(43);
+        let (mut out_a, mut out_b, mut out_c) = ([0u8; 100], [0u8; 100], [0u8; 100]);
+        a.fill(&mut out_a);
+        b.fill(&mut out_b);
+        c.fill(&mut out_c);
+        assert_eq!(out_a, out_b);
+        assert_ne!(out_a, out_c);
+    }
+
+    #[test]
+    fn test_engine_rng_scope_is_restored() {
+        let config = CryptoConfig::test_deterministic(7);
+        let scope = EngineRng::from_config(&config);
+        let mut inside = [0u8; 16];
+        scope.run(|| assert!(fill_deterministic(&mut inside)));
+        assert!(!fill_deterministic(&mut [0u8; 16]));
+    }
+
+    #[test]
+    fn test_engine_rng_restored_after_panic() {
+        let scope = EngineRng::from_config(&CryptoConfig::test_deterministic(7));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            scope.run(|| {
+                assert!(fill_deterministic(&mut [0u8; 4]));
+                panic!("操作中途失败");
+            })
+        }));
+        assert!(result.is_err());
+        assert!(!fill_deterministic(&mut [0u8; 4]));
+    }
+}
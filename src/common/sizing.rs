@@ -0,0 +1,134 @@
+//! 加密前预测密文大小
+//!
+//! 各算法的密文长度只取决于参数与明文长度，可在加密前精确算出，便于预先分配网络缓冲区。
+//! 预测值对应系统层 `encrypt` 输出的标准 Base64 文本（`CiphertextOutput::to_string()`）；
+//! 引擎输出的信封前缀（密钥ID、公钥指纹、头部）不计算在内，使用其他 Base64 变体时可用
+//! [`Base64Variant::encoded_len`](crate::common::utils::Base64Variant::encoded_len) 换算。
+
+use crate::common::errors::Error;
+use crate::common::utils::Base64Variant;
+#[cfg(feature = "post-quantum")]
+use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+#[cfg(feature = "aes-gcm-feature")]
+use crate::symmetric::systems::aes_gcm::AesGcmSystem;
+
+/// OAEP（SHA-256）加密填充的开销：两倍摘要长度加 2 字节
+#[cfg(feature = "traditional")]
+const OAEP_SHA256_PADDING_SIZE: usize = 2 * 32 + 2;
+
+/// 可预测密文大小的算法及其参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputAlgorithm {
+    /// AES-256-GCM 一次性加密，`tag_length` 与 `CryptoConfig::tag_length` 含义相同
+    #[cfg(feature = "aes-gcm-feature")]
+    Aes256Gcm { tag_length: usize },
+    /// Kyber KEM-DEM，`parameter_k` 为 512/768/1024
+    #[cfg(feature = "post-quantum")]
+    Kyber { parameter_k: usize },
+    /// RSA OAEP（`RsaCryptoSystem::encrypt` 的默认填充），`key_bits` 为模数位数
+    #[cfg(feature = "traditional")]
+    Rsa { key_bits: usize },
+}
+
+/// 预测以 `algorithm` 加密 `plaintext_len` 字节明文得到的 Base64 密文长度
+///
+/// 附加认证数据只参与认证、不随密文传输，因此 `aad_len` 不影响结果。
+/// 参数不受支持（如标签短于安全下限、未知的 Kyber 级别）或明文超过 RSA 单次加密上限时，
+/// 返回与实际加密相同类型的错误。
+#[allow(unused_variables)]
+pub fn predict_output_size(algorithm: OutputAlgorithm, plaintext_len: usize, aad_len: usize) -> Result<usize, Error> {
+    let raw_len = match algorithm {
+        #[cfg(feature = "aes-gcm-feature")]
+        OutputAlgorithm::Aes256Gcm { tag_length } => AesGcmSystem::ciphertext_len(plaintext_len, tag_length)?,
+        #[cfg(feature = "post-quantum")]
+        OutputAlgorithm::Kyber { parameter_k } => KyberCryptoSystem::ciphertext_len(parameter_k, plaintext_len)?,
+        #[cfg(feature = "traditional")]
+        OutputAlgorithm::Rsa { key_bits } => {
+            let modulus_len = key_bits.div_ceil(8);
+            if plaintext_len + OAEP_SHA256_PADDING_SIZE > modulus_len {
+                return Err(Error::Traditional(format!(
+                    "明文 {} 字节超过 {} 位RSA密钥单次加密的上限 {} 字节",
+                    plaintext_len, key_bits, modulus_len.saturating_sub(OAEP_SHA256_PADDING_SIZE)
+                )));
+            }
+            // 模数长度的密文块前带 1 字节填充标记
+            1 + modulus_len
+        }
+    };
+    Ok(Base64Variant::Standard.encoded_len(raw_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::utils::to_base64;
+
+    #[cfg(feature = "aes-gcm-feature")]
+    const SIZES: [usize; 7] = [0, 1, 2, 3, 16, 100, 4097];
+
+    #[test]
+    fn test_base64_encoded_len() {
+        for len in 0..20 {
+            let data = vec![0xfbu8; len];
+            assert_eq!(Base64Variant::Standard.encoded_len(len), to_base64(&data).len());
+            assert_eq!(Base64Variant::UrlSafeNoPad.encoded_len(len), Base64Variant::UrlSafeNoPad.encode(&data).len());
+        }
+    }
+
+    #[cfg(feature = "aes-gcm-feature")]
+    #[test]
+    fn test_predict_aes_gcm_matches_output() {
+        use crate::common::utils::CryptoConfig;
+        use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        for tag_length in [12, 14, 16] {
+            for len in SIZES {
+                let plaintext = vec![0x11u8; len];
+                let actual = AesGcmSystem::encrypt_with_tag_length(&key, &plaintext, Some(b"aad"), tag_length).unwrap();
+                let predicted = predict_output_size(OutputAlgorithm::Aes256Gcm { tag_length }, len, 3).unwrap();
+                assert_eq!(predicted, actual.to_string().len(), "tag_length = {}, len = {}", tag_length, len);
+            }
+        }
+        assert!(matches!(
+            predict_output_size(OutputAlgorithm::Aes256Gcm { tag_length: 8 }, 10, 0),
+            Err(Error::InsecureParameter(_))
+        ));
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_predict_kyber_matches_output() {
+        use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+        use crate::common::utils::CryptoConfig;
+
+        for parameter_k in [512, 768, 1024] {
+            let config = CryptoConfig { kyber_parameter_k: parameter_k, ..Default::default() };
+            let (pk, _) = KyberCryptoSystem::generate_keypair(&config).unwrap();
+            for len in SIZES {
+                let actual = KyberCryptoSystem::encrypt(&pk, &vec![0x22u8; len], None).unwrap();
+                let predicted = predict_output_size(OutputAlgorithm::Kyber { parameter_k }, len, 0).unwrap();
+                assert_eq!(predicted, actual.to_string().len(), "k = {}, len = {}", parameter_k, len);
+            }
+        }
+        assert!(predict_output_size(OutputAlgorithm::Kyber { parameter_k: 256 }, 10, 0).is_err());
+    }
+
+    #[cfg(feature = "traditional")]
+    #[test]
+    fn test_predict_rsa_matches_output() {
+        use crate::asymmetric::systems::traditional::rsa::RsaCryptoSystem;
+        use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+        use crate::common::utils::CryptoConfig;
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (pk, _) = RsaCryptoSystem::generate_keypair(&config).unwrap();
+        for len in [0, 1, 100, 190] {
+            let actual = RsaCryptoSystem::encrypt(&pk, &vec![0x33u8; len], None).unwrap();
+            let predicted = predict_output_size(OutputAlgorithm::Rsa { key_bits: 2048 }, len, 0).unwrap();
+            assert_eq!(predicted, actual.to_string().len(), "len = {}", len);
+        }
+        assert!(RsaCryptoSystem::encrypt(&pk, &[0u8; 191], None).is_err());
+        assert!(predict_output_size(OutputAlgorithm::Rsa { key_bits: 2048 }, 191, 0).is_err());
+    }
+}
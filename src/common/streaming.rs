@@ -1,4 +1,17 @@
+#[cfg(any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha"))]
+use std::io::Read;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "async-engine")]
+use std::time::Duration;
+
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+use crate::common::errors::Error;
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+use hmac::{Hmac, Mac};
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+use sha2::Sha256;
+use zeroize::Zeroizing;
 
 /// 流式处理返回结果
 #[derive(Debug)]
@@ -9,9 +22,110 @@ pub struct StreamingResult {
     pub buffer: Option<Vec<u8>>,
 }
 
+/// 流式处理的取消令牌，可克隆后交给其他任务，任一副本调用 [`cancel`](Self::cancel) 即对所有副本生效
+///
+/// 流式加解密在每帧开始前检查令牌，已取消时返回 `Error::Cancelled`。检查只发生在帧与帧之间，
+/// 因此输出中不会出现写了一半的帧。直接丢弃正在执行的 future 无法提供这一保证。
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// 创建尚未取消的令牌
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 请求取消，正在进行的流式处理会在下一帧开始前停止
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// 是否已请求取消
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
 /// 默认缓冲区大小（64KB）
 const DEFAULT_BUFFER_SIZE: usize = 65536;
 
+/// 自适应分块：从较小的帧开始，写出顺畅时逐步增大，直到 `max_frame_size`
+///
+/// 固定的大帧在慢速网络上会让接收方等待整帧到达后才能解密出第一个字节。自适应分块先发送
+/// `initial_size` 字节的小帧，某帧从读取到写出的耗时不超过 `target_latency` 时下一帧长度翻倍，
+/// 超过时减半（不低于 `initial_size`），从而让写入端的背压直接决定帧长度。每帧仍带长度前缀，
+/// 解密无需任何设置。仅异步对称流式加密支持，且不能与帧元数据绑定同时使用（总帧数无法预先确定）。
+#[cfg(feature = "async-engine")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdaptiveChunking {
+    /// 第一帧及收缩下限的明文长度
+    pub initial_size: usize,
+    /// 帧明文长度上限
+    pub max_frame_size: usize,
+    /// 单帧读取与写出的目标耗时
+    pub target_latency: Duration,
+}
+
+#[cfg(feature = "async-engine")]
+impl Default for AdaptiveChunking {
+    fn default() -> Self {
+        Self { initial_size: 4096, max_frame_size: DEFAULT_BUFFER_SIZE, target_latency: Duration::from_millis(10) }
+    }
+}
+
+#[cfg(feature = "async-engine")]
+impl AdaptiveChunking {
+    /// 以 `initial_size` 起步、最大为 `max_frame_size` 的自适应分块，目标耗时取默认值
+    pub fn new(initial_size: usize, max_frame_size: usize) -> Self {
+        Self { initial_size, max_frame_size, ..Self::default() }
+    }
+
+    /// 设置单帧目标耗时
+    pub fn with_target_latency(mut self, latency: Duration) -> Self {
+        self.target_latency = latency;
+        self
+    }
+
+    /// 检查参数并创建帧长度调节器
+    pub(crate) fn sizer(&self) -> Result<ChunkSizer, crate::common::errors::Error> {
+        if self.initial_size == 0 || self.initial_size > self.max_frame_size {
+            return Err(crate::common::errors::Error::Format(format!(
+                "自适应分块参数无效: 初始帧长 {} 字节, 最大帧长 {} 字节", self.initial_size, self.max_frame_size
+            )));
+        }
+        Ok(ChunkSizer { config: *self, current: self.initial_size })
+    }
+}
+
+/// 按上一帧的耗时调节下一帧的明文长度
+#[cfg(feature = "async-engine")]
+pub(crate) struct ChunkSizer {
+    config: AdaptiveChunking,
+    current: usize,
+}
+
+#[cfg(feature = "async-engine")]
+impl ChunkSizer {
+    /// 下一帧的明文长度
+    pub(crate) fn next_len(&self) -> usize {
+        self.current
+    }
+
+    /// 记录一帧从读取到写出的耗时
+    pub(crate) fn observe(&mut self, elapsed: Duration) {
+        self.current = if elapsed <= self.config.target_latency {
+            self.current.saturating_mul(2).min(self.config.max_frame_size)
+        } else {
+            (self.current / 2).max(self.config.initial_size)
+        };
+    }
+}
+
+/// 加密时生成每帧附加元数据的函数，参数为帧序号与该帧明文
+pub type ChunkMetadataProvider = Arc<dyn Fn(u64, &[u8]) -> Vec<u8> + Send + Sync>;
+/// 解密时接收每帧元数据的回调，参数为帧序号与元数据
+pub type ChunkMetadataCallback = Arc<dyn Fn(u64, &[u8]) + Send + Sync>;
+
 /// 流式加密配置
 #[derive(Clone)]
 pub struct StreamingConfig {
@@ -29,6 +143,65 @@ pub struct StreamingConfig {
 
     /// 可选的总字节数，用于进度计算
     pub total_bytes: Option<u64>,
+
+    /// 是否将帧序号与总帧数自动绑定为附加认证数据，默认启用
+    ///
+    /// 启用后流以8字节的总帧数头部开始，每帧的AAD为 `frame_index || total_frames || 用户AAD`，
+    /// 即使没有用户AAD也能防止帧被重排、截断或篡改声明的总帧数。
+    /// 加密时设置了 `total_bytes` 则预先声明总帧数；否则（或使用内容定义分块、自适应分块时）头部声明总帧数未知，
+    /// 各帧绑定该未知值，流以一个带标记的空末帧结束，末帧绑定实际的总帧数。
+    pub bind_frame_metadata: bool,
+
+    /// 是否将上一帧密文的 SHA-256 链入每帧的附加认证数据
+    ///
+    /// 启用后两条以同一密钥加密的流即使帧序号相同也无法互相拼接：替换任一帧都会使其后一帧认证失败，
+    /// 被换入的帧本身也因前一帧不同而认证失败。首帧链接的是全零值，因此仍需配合不同的用户AAD区分各条流的首帧。
+    /// 仅同步对称流式处理、并行解密与增量解密支持。
+    pub frame_chaining: bool,
+
+    /// 是否在流末尾写入完整性尾部（总帧数与全部帧内容的 CRC32）
+    ///
+    /// 每帧按写入流的原样（可选的元数据前缀与编码后的密文，其中包含 AEAD 认证标签）参与 CRC，不含长度前缀。
+    ///
+    /// 尾部不具备密码学强度，仅用于廉价地发现传输中的意外截断：
+    /// 解密时缺少尾部或尾部与实际帧不符会返回 `Error::StreamIntegrity`。
+    pub integrity_footer: bool,
+
+    /// 可选的流全局认证密钥，设置后完整性尾部附带覆盖全部帧内容的 HMAC-SHA256
+    ///
+    /// 单帧标签只能证明每一帧本身未被篡改；全局标签按顺序覆盖每一帧的长度与完整内容（因而包括各帧的 AEAD 标签）以及总帧数，
+    /// 一次校验即可发现帧被重排、删除或复制，且无法像 CRC 那样被攻击者重新计算。
+    /// 设置后总会写入并要求完整性尾部；该密钥应与加密密钥相互独立并同样保密。
+    pub stream_mac_key: Option<Zeroizing<Vec<u8>>>,
+
+    /// 可选的取消令牌，异步流式加解密在每帧开始前检查
+    pub cancellation: Option<CancellationToken>,
+
+    /// 加密时为每帧生成附加元数据（如时间戳），参数为帧序号与该帧明文
+    ///
+    /// 设置后每帧内容变为 `元数据长度(u16 LE) || 元数据 || 密文`，元数据以明文传输并绑定到该帧的附加认证数据中，
+    /// 单帧元数据不得超过 65535 字节；帧元数据绑定结束流时写入的空末帧同样生成元数据。仅对称流式处理支持。
+    pub chunk_metadata_provider: Option<ChunkMetadataProvider>,
+
+    /// 解密时接收每帧通过认证的元数据，参数为帧序号与元数据
+    ///
+    /// 解密带元数据的流时必须设置（不关心元数据时可传入空操作），否则无法解析帧格式。
+    pub chunk_metadata_callback: Option<ChunkMetadataCallback>,
+
+    /// 加密时按内容定义的边界分块，为 `None` 时按 `buffer_size` 固定分块
+    ///
+    /// 插入或删除数据只影响附近的块，适合需要对加密备份去重的场景。仅同步与并行对称流式加密支持，
+    /// 总帧数无法预先确定，帧元数据绑定按总帧数未知处理；解密无需任何设置。
+    #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+    pub content_defined_chunking: Option<crate::symmetric::chunking::ContentDefinedChunker>,
+
+    /// 加密时按写出速度自适应调节帧长度，为 `None` 时按 `buffer_size` 固定分块；仅异步对称流式加密支持
+    #[cfg(feature = "async-engine")]
+    pub adaptive_chunking: Option<AdaptiveChunking>,
+
+    /// 并行流式处理使用的线程池，为 `None` 时使用 Rayon 全局线程池
+    #[cfg(feature = "parallel")]
+    pub thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl Default for StreamingConfig {
@@ -39,6 +212,19 @@ impl Default for StreamingConfig {
             keep_in_memory: false,
             progress_callback: None,
             total_bytes: None,
+            bind_frame_metadata: true,
+            frame_chaining: false,
+            integrity_footer: false,
+            stream_mac_key: None,
+            cancellation: None,
+            chunk_metadata_provider: None,
+            chunk_metadata_callback: None,
+            #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+            content_defined_chunking: None,
+            #[cfg(feature = "async-engine")]
+            adaptive_chunking: None,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
         }
     }
 }
@@ -70,4 +256,435 @@ impl StreamingConfig {
         self.progress_callback = Some(callback);
         self
     }
-}
\ No newline at end of file
+    /// 设置是否将帧元数据自动绑定为附加认证数据
+    pub fn with_frame_metadata_binding(mut self, bind: bool) -> Self {
+        self.bind_frame_metadata = bind;
+        self
+    }
+    /// 设置是否将上一帧密文的摘要链入每帧的附加认证数据
+    pub fn with_frame_chaining(mut self, chaining: bool) -> Self {
+        self.frame_chaining = chaining;
+        self
+    }
+    /// 设置是否写入并校验流完整性尾部
+    pub fn with_integrity_footer(mut self, footer: bool) -> Self {
+        self.integrity_footer = footer;
+        self
+    }
+    /// 设置流全局认证密钥，同时启用完整性尾部
+    pub fn with_stream_mac_key(mut self, key: &[u8]) -> Self {
+        self.stream_mac_key = Some(Zeroizing::new(key.to_vec()));
+        self
+    }
+    /// 设置取消令牌
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// 设置加密时每帧附加元数据的生成函数
+    pub fn with_chunk_metadata(mut self, provider: ChunkMetadataProvider) -> Self {
+        self.chunk_metadata_provider = Some(provider);
+        self
+    }
+    /// 设置解密时接收每帧元数据的回调
+    pub fn with_chunk_metadata_callback(mut self, callback: ChunkMetadataCallback) -> Self {
+        self.chunk_metadata_callback = Some(callback);
+        self
+    }
+
+    /// 设置加密时使用内容定义分块
+    #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+    pub fn with_content_defined_chunking(mut self, chunker: crate::symmetric::chunking::ContentDefinedChunker) -> Self {
+        self.content_defined_chunking = Some(chunker);
+        self
+    }
+
+    /// 设置加密时使用自适应分块
+    #[cfg(feature = "async-engine")]
+    pub fn with_adaptive_chunking(mut self, adaptive: AdaptiveChunking) -> Self {
+        self.adaptive_chunking = Some(adaptive);
+        self
+    }
+
+    /// 加密时生成第 `frame_index` 帧的元数据，未设置生成函数时为 `None`
+    #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+    pub(crate) fn chunk_metadata(&self, frame_index: u64, plaintext: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let Some(provider) = &self.chunk_metadata_provider else {
+            return Ok(None);
+        };
+        let metadata = provider(frame_index, plaintext);
+        if metadata.len() > MAX_CHUNK_METADATA_SIZE {
+            return Err(Error::Format(format!(
+                "第 {} 帧的元数据长度 {} 字节超过上限 {} 字节", frame_index, metadata.len(), MAX_CHUNK_METADATA_SIZE
+            )));
+        }
+        Ok(Some(metadata))
+    }
+
+    /// 解密时将通过认证的元数据交给回调
+    #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+    pub(crate) fn deliver_chunk_metadata(&self, frame_index: u64, metadata: Option<&[u8]>) {
+        if let (Some(callback), Some(metadata)) = (&self.chunk_metadata_callback, metadata) {
+            callback(frame_index, metadata);
+        }
+    }
+
+    /// 取消令牌已被触发时返回 `Error::Cancelled`
+    #[cfg(feature = "async-engine")]
+    pub(crate) fn check_cancelled(&self) -> Result<(), crate::common::errors::Error> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(crate::common::errors::Error::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// 设置并行流式处理使用的线程池
+    #[cfg(feature = "parallel")]
+    pub fn with_thread_pool(mut self, pool: Arc<rayon::ThreadPool>) -> Self {
+        self.thread_pool = Some(pool);
+        self
+    }
+
+    /// 根据 `total_bytes` 与 `buffer_size` 计算加密时应产生的总帧数，无法预先确定时为 [`OPEN_ENDED_FRAME_COUNT`]
+    #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+    pub(crate) fn declared_frame_count(&self) -> Result<u64, Error> {
+        #[cfg(feature = "async-engine")]
+        let adaptive = self.adaptive_chunking.is_some();
+        #[cfg(not(feature = "async-engine"))]
+        let adaptive = false;
+        let Some(total) = self.total_bytes.filter(|_| self.content_defined_chunking.is_none() && !adaptive) else {
+            return Ok(OPEN_ENDED_FRAME_COUNT);
+        };
+        if self.buffer_size == 0 {
+            return Err(Error::Format("缓冲区大小不能为0".to_string()));
+        }
+        Ok(total.div_ceil(self.buffer_size as u64))
+    }
+}
+
+/// 帧元数据头部长度（总帧数，u64 小端）
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const FRAME_HEADER_SIZE: usize = 8;
+/// 总帧数未知时头部声明的值
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const OPEN_ENDED_FRAME_COUNT: u64 = u64::MAX;
+/// 总帧数未知时写在末帧长度前缀之前的标记（真实帧长度不会达到该值）
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const FINAL_FRAME_MARKER: u32 = u32::MAX - 1;
+
+/// 帧元数据绑定：头部声明的总帧数，以及据此构造的每帧附加认证数据
+///
+/// 声明了总帧数时每帧都绑定该值；总帧数未知时各帧绑定 [`OPEN_ENDED_FRAME_COUNT`]，
+/// 末帧（以 [`FINAL_FRAME_MARKER`] 标记）改为绑定实际的总帧数，因此丢弃末帧或把普通帧标记为末帧都会认证失败。
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FrameBinding {
+    declared: u64,
+}
+
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+impl FrameBinding {
+    /// 加密时按配置确定声明的总帧数；未启用帧元数据绑定时返回 `None`
+    pub(crate) fn for_encryption(config: &StreamingConfig) -> Result<Option<Self>, Error> {
+        if !config.bind_frame_metadata {
+            return Ok(None);
+        }
+        Ok(Some(Self { declared: config.declared_frame_count()? }))
+    }
+
+    /// 由解密时读到的头部恢复
+    pub(crate) fn from_header(header: [u8; FRAME_HEADER_SIZE]) -> Self {
+        Self { declared: u64::from_le_bytes(header) }
+    }
+
+    /// 写在流开头的头部
+    pub(crate) fn header(&self) -> [u8; FRAME_HEADER_SIZE] {
+        self.declared.to_le_bytes()
+    }
+
+    /// 总帧数是否未知，此时流以带标记的末帧结束
+    pub(crate) fn is_open_ended(&self) -> bool {
+        self.declared == OPEN_ENDED_FRAME_COUNT
+    }
+
+    /// 第 `frame_index` 帧的附加认证数据；`is_final` 只在总帧数未知时参与绑定
+    pub(crate) fn aad(&self, frame_index: u64, is_final: bool, user_aad: Option<&[u8]>) -> Vec<u8> {
+        let total = if self.is_open_ended() && is_final { frame_index + 1 } else { self.declared };
+        frame_aad(frame_index, total, user_aad)
+    }
+
+    /// 加密结束时确认实际帧数与声明相符
+    pub(crate) fn check_encrypted(&self, frames: u64) -> Result<(), Error> {
+        if !self.is_open_ended() && self.declared != frames {
+            return Err(Error::Format(format!("输入长度与声明的总帧数不符: 声明 {} 帧, 实际 {} 帧", self.declared, frames)));
+        }
+        Ok(())
+    }
+
+    /// 解密结束时确认流未被截断：帧数与声明相符，或总帧数未知时已读到末帧
+    pub(crate) fn check_decrypted(&self, frames: u64, final_seen: bool) -> Result<(), Error> {
+        if self.is_open_ended() {
+            if !final_seen {
+                return Err(Error::StreamIntegrity(format!("在第 {} 帧之后缺少末帧，流可能被截断", frames)));
+            }
+        } else if self.declared != frames {
+            return Err(Error::Format(format!("流帧数与声明不符: 声明 {} 帧, 实际 {} 帧", self.declared, frames)));
+        }
+        Ok(())
+    }
+
+    /// 末帧之后又读到帧时的错误
+    pub(crate) fn frame_after_final(&self) -> Error {
+        Error::StreamIntegrity("末帧之后存在多余的帧".to_string())
+    }
+}
+
+/// 完整性尾部的标记，写在帧长度前缀的位置（真实帧长度不会达到该值）
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const FOOTER_MARKER: u32 = u32::MAX;
+/// 完整性尾部内容长度：总帧数（u64 小端）|| 帧内容CRC32（u32 小端）
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const FOOTER_SIZE: usize = 12;
+/// 流全局认证标签（HMAC-SHA256）长度
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const STREAM_MAC_SIZE: usize = 32;
+/// 流全局认证标签的域分隔标签
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+const STREAM_MAC_LABEL: &[u8] = b"seal-kit-stream-mac-v1";
+
+/// 累计已处理的帧数与帧内容CRC，用于生成和校验完整性尾部
+///
+/// 配置了流全局认证密钥时同时按顺序累计各帧长度与内容的 HMAC。
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+#[derive(Default)]
+pub(crate) struct FrameTally {
+    frames: u64,
+    crc: u32,
+    mac: Option<Hmac<Sha256>>,
+}
+
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+impl FrameTally {
+    /// 按配置创建计数器；既未启用完整性尾部也未设置全局认证密钥时返回 `None`
+    pub(crate) fn for_config(config: &StreamingConfig) -> Option<Self> {
+        if !config.integrity_footer && config.stream_mac_key.is_none() {
+            return None;
+        }
+        let mac = config.stream_mac_key.as_ref().map(|key| {
+            let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC 接受任意长度的密钥");
+            mac.update(STREAM_MAC_LABEL);
+            mac
+        });
+        Some(Self { mac, ..Self::default() })
+    }
+
+    /// 记录一帧内容（不含长度前缀）
+    ///
+    /// 整帧参与CRC与HMAC：帧中的密文是编码后的文本，AEAD 标签不落在固定的字节位置上。
+    pub(crate) fn record(&mut self, frame: &[u8]) {
+        self.frames += 1;
+        self.crc = crate::common::utils::crc32_update(self.crc, frame);
+        if let Some(mac) = self.mac.as_mut() {
+            mac.update(&(frame.len() as u32).to_le_bytes());
+            mac.update(frame);
+        }
+    }
+
+    /// 已记录的帧数
+    pub(crate) fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// 尾部内容长度（不含标记）
+    pub(crate) fn footer_len(&self) -> usize {
+        FOOTER_SIZE + if self.mac.is_some() { STREAM_MAC_SIZE } else { 0 }
+    }
+
+    /// 对已记录的全部帧计算全局认证标签，总帧数一并参与认证
+    fn global_mac(&self) -> Option<Hmac<Sha256>> {
+        self.mac.clone().map(|mut mac| {
+            mac.update(&self.frames.to_le_bytes());
+            mac
+        })
+    }
+
+    /// 编码完整性尾部：`标记 || 总帧数 || CRC32 [|| 全局认证标签]`
+    pub(crate) fn footer(&self) -> Vec<u8> {
+        let mut footer = Vec::with_capacity(4 + self.footer_len());
+        footer.extend_from_slice(&FOOTER_MARKER.to_le_bytes());
+        footer.extend_from_slice(&self.frames.to_le_bytes());
+        footer.extend_from_slice(&self.crc.to_le_bytes());
+        if let Some(mac) = self.global_mac() {
+            footer.extend_from_slice(&mac.finalize().into_bytes());
+        }
+        footer
+    }
+
+    /// 将读取到的尾部内容（不含标记，长度为 `footer_len`）与已处理的帧比对
+    pub(crate) fn verify(&self, footer: &[u8]) -> Result<(), Error> {
+        if footer.len() != self.footer_len() {
+            return Err(Error::StreamIntegrity("完整性尾部长度无效".to_string()));
+        }
+        let frames = u64::from_le_bytes(footer[..8].try_into().expect("尾部长度固定"));
+        let crc = u32::from_le_bytes(footer[8..FOOTER_SIZE].try_into().expect("尾部长度固定"));
+        if frames != self.frames {
+            return Err(Error::StreamIntegrity(format!(
+                "尾部记录 {} 帧, 实际收到 {} 帧", frames, self.frames
+            )));
+        }
+        if crc != self.crc {
+            return Err(Error::StreamIntegrity("帧内容CRC与尾部记录不符".to_string()));
+        }
+        if let Some(mac) = self.global_mac() {
+            mac.verify_slice(&footer[FOOTER_SIZE..])
+                .map_err(|_| Error::StreamIntegrity("流全局认证标签校验失败，帧可能被重排、删除或伪造".to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// 读取到达流末尾却没有遇到尾部时的错误
+    pub(crate) fn missing_footer(&self) -> Error {
+        Error::StreamIntegrity(format!("在第 {} 帧之后缺少完整性尾部，流可能被截断", self.frames))
+    }
+
+    /// 读取帧内容时遇到的I/O错误；帧不完整视为截断
+    pub(crate) fn frame_read_error(&self, e: std::io::Error) -> Error {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            Error::StreamIntegrity(format!("第 {} 帧不完整，流被截断", self.frames + 1))
+        } else {
+            e.into()
+        }
+    }
+}
+
+/// 读取完整性尾部并校验，尾部之后不允许再有数据
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn read_footer<R: Read>(reader: &mut R, tally: &FrameTally) -> Result<(), Error> {
+    let mut footer = vec![0u8; tally.footer_len()];
+    reader.read_exact(&mut footer).map_err(|e| tally.frame_read_error(e))?;
+    tally.verify(&footer)?;
+    if read_full(reader, &mut [0u8; 1])? != 0 {
+        return Err(Error::StreamIntegrity("完整性尾部之后存在多余数据".to_string()));
+    }
+    Ok(())
+}
+
+/// 单帧附加元数据的最大长度（长度字段为 u16）
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+const MAX_CHUNK_METADATA_SIZE: usize = u16::MAX as usize;
+/// 帧元数据附加认证数据的域分隔标签
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+const CHUNK_METADATA_AAD_LABEL: &[u8] = b"seal-kit-chunk-metadata-v1";
+
+/// 将帧元数据并入该帧原有的附加认证数据，各字段带长度前缀以消除歧义
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn chunk_metadata_aad(aad: Option<&[u8]>, metadata: &[u8]) -> Vec<u8> {
+    crate::common::utils::aad_segments(&[CHUNK_METADATA_AAD_LABEL, aad.unwrap_or_default(), metadata])
+}
+
+/// 帧链接附加认证数据的域分隔标签
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+const FRAME_CHAIN_AAD_LABEL: &[u8] = b"seal-kit-frame-chain-v1";
+
+/// 帧链接状态：记录上一帧密文（不含长度前缀）的 SHA-256，首帧之前为全零
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+#[derive(Clone, Default)]
+pub(crate) struct FrameChain {
+    previous: [u8; 32],
+}
+
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+impl FrameChain {
+    /// 按配置创建链接状态；未启用帧链接时返回 `None`
+    pub(crate) fn for_config(config: &StreamingConfig) -> Option<Self> {
+        config.frame_chaining.then(Self::default)
+    }
+
+    /// 将上一帧摘要并入该帧原有的附加认证数据，各字段带长度前缀以消除歧义
+    pub(crate) fn aad(&self, aad: Option<&[u8]>) -> Vec<u8> {
+        crate::common::utils::aad_segments(&[FRAME_CHAIN_AAD_LABEL, &self.previous, aad.unwrap_or_default()])
+    }
+
+    /// 记录刚写出或读入的一帧
+    pub(crate) fn advance(&mut self, frame: &[u8]) {
+        use sha2::Digest;
+        self.previous = Sha256::digest(frame).into();
+    }
+}
+
+/// 组装帧内容：有元数据时为 `元数据长度(u16 LE) || 元数据 || 密文`，否则只有密文
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn encode_chunk_frame(metadata: Option<&[u8]>, ciphertext: String) -> Vec<u8> {
+    let Some(metadata) = metadata else {
+        return ciphertext.into_bytes();
+    };
+    let mut frame = Vec::with_capacity(2 + metadata.len() + ciphertext.len());
+    frame.extend_from_slice(&(metadata.len() as u16).to_le_bytes());
+    frame.extend_from_slice(metadata);
+    frame.extend_from_slice(ciphertext.as_bytes());
+    frame
+}
+
+/// 拆分帧内容为 (元数据, UTF-8 密文)，`with_metadata` 为假时整帧都是密文
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn decode_chunk_frame(frame: &[u8], with_metadata: bool) -> Result<(Option<&[u8]>, &str), Error> {
+    let (metadata, ciphertext) = if with_metadata {
+        let len = frame.get(..2)
+            .map(|prefix| u16::from_le_bytes([prefix[0], prefix[1]]) as usize)
+            .filter(|&len| 2 + len <= frame.len())
+            .ok_or_else(|| Error::Format("帧元数据长度无效".to_string()))?;
+        (Some(&frame[2..2 + len]), &frame[2 + len..])
+    } else {
+        (None, frame)
+    };
+    let ciphertext = std::str::from_utf8(ciphertext)
+        .map_err(|e| Error::Format(format!("无效的UTF-8密文: {}", e)))?;
+    Ok((metadata, ciphertext))
+}
+
+/// 构造帧的附加认证数据：`frame_index(u64 LE) || total_frames(u64 LE) || 用户AAD`
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn frame_aad(frame_index: u64, total_frames: u64, user_aad: Option<&[u8]>) -> Vec<u8> {
+    let user_aad = user_aad.unwrap_or_default();
+    let mut aad = Vec::with_capacity(16 + user_aad.len());
+    aad.extend_from_slice(&frame_index.to_le_bytes());
+    aad.extend_from_slice(&total_frames.to_le_bytes());
+    aad.extend_from_slice(user_aad);
+    aad
+}
+
+/// 尽可能填满缓冲区，仅在遇到EOF时返回较少的字节数
+///
+/// 帧元数据绑定要求帧边界与 `buffer_size` 对齐，不能依赖单次 `read` 的返回长度。
+#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
+}
+
+/// 帧缓冲区的最大预分配长度
+#[cfg(any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) const MAX_FRAME_PREALLOC: usize = 1 << 20;
+
+/// 读取长度前缀声明的 `len` 字节帧内容
+///
+/// 缓冲区随实际读到的数据增长，伪造的超大长度前缀不会触发一次性的巨大分配；
+/// 数据不足 `len` 字节时返回 `UnexpectedEof`。
+#[cfg(any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha"))]
+pub(crate) fn read_frame<R: Read>(reader: &mut R, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut frame = Vec::with_capacity(len.min(MAX_FRAME_PREALLOC));
+    reader.take(len as u64).read_to_end(&mut frame)?;
+    if frame.len() != len {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "帧数据不完整"));
+    }
+    Ok(frame)
+}
@@ -1,17 +1,158 @@
 use zeroize::{Zeroize, ZeroizeOnDrop};
-use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD as BASE64, URL_SAFE_NO_PAD};
+use base64::engine::DecodePaddingMode;
 use serde::{Deserialize, Serialize};
-use std::ops::{Deref, DerefMut};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
 use base64::Engine;
 
+/// 解码时对填充宽容的配置：带或不带 `=` 填充均可接受
+const LENIENT_DECODE_CONFIG: GeneralPurposeConfig =
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+/// 宽容解码的标准字母表引擎
+const LENIENT_STANDARD: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, LENIENT_DECODE_CONFIG);
+/// 宽容解码的URL安全字母表引擎
+const LENIENT_URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, LENIENT_DECODE_CONFIG);
+
 /// 将字节数组转换为Base64字符串
 pub fn to_base64(data: &[u8]) -> String {
     BASE64.encode(data)
 }
 
+/// 将字节数组按 [`to_base64`] 相同的格式编码后直接写入 `sink`，不分配中间字符串；返回写入的字节数
+#[cfg(feature = "std")]
+pub fn write_base64(data: &[u8], sink: &mut dyn std::io::Write) -> std::io::Result<usize> {
+    use std::io::Write;
+
+    let mut encoder = base64::write::EncoderWriter::new(sink, &BASE64);
+    encoder.write_all(data)?;
+    encoder.finish()?;
+    Ok(base64::encoded_len(data.len(), true).expect("长度来自内存中的数据，不会溢出"))
+}
+
 /// 从Base64字符串解码为字节数组
+///
+/// 自动识别标准Base64与URL安全Base64（带或不带填充）。
+/// 两种字母表仅在 `+`/`/` 与 `-`/`_` 上不同，因此识别结果不存在歧义。
+/// 为兼容邮件、配置文件中被折行的密文，解码前会忽略其中的空白字符（空格、制表符与换行）；
+/// 编码端（[`to_base64`]、[`Base64Variant::encode`]）仍输出严格格式。
 pub fn from_base64(encoded: &str) -> Result<Vec<u8>, base64::DecodeError> {
-    BASE64.decode(encoded)
+    let mut decoded = Vec::new();
+    from_base64_into(encoded, &mut decoded)?;
+    Ok(decoded)
+}
+
+/// 与 [`from_base64`] 规则相同，但把解码结果追加到调用方提供的缓冲区；失败时缓冲区保持原长度
+pub fn from_base64_into(encoded: &str, out: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+    let encoded: Cow<'_, str> = if encoded.bytes().any(|b| b.is_ascii_whitespace()) {
+        Cow::Owned(encoded.chars().filter(|c| !c.is_ascii_whitespace()).collect())
+    } else {
+        Cow::Borrowed(encoded)
+    };
+    let start = out.len();
+    LENIENT_STANDARD.decode_vec(encoded.as_bytes(), out)
+        .or_else(|e| {
+            out.truncate(start);
+            LENIENT_URL_SAFE.decode_vec(encoded.as_bytes(), out).map_err(|_| e)
+        })
+        .inspect_err(|_| out.truncate(start))
+}
+
+/// Base64编码变体
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Base64Variant {
+    /// 标准Base64（`+`/`/`，带填充）
+    #[default]
+    Standard,
+    /// URL安全Base64（`-`/`_`，无填充），适合嵌入URL与文件名
+    UrlSafeNoPad,
+}
+
+impl Base64Variant {
+    /// 按此变体编码字节数组
+    pub fn encode(&self, data: &[u8]) -> String {
+        match self {
+            Base64Variant::Standard => BASE64.encode(data),
+            Base64Variant::UrlSafeNoPad => URL_SAFE_NO_PAD.encode(data),
+        }
+    }
+
+    /// 按此变体编码 `len` 字节数据得到的字符数
+    pub fn encoded_len(&self, len: usize) -> usize {
+        match self {
+            Base64Variant::Standard => len.div_ceil(3) * 4,
+            Base64Variant::UrlSafeNoPad => len / 3 * 4 + [0, 2, 3][len % 3],
+        }
+    }
+}
+
+/// RSA 加密填充方案
+///
+/// 默认的 OAEP（SHA-256）具有可证明的安全性。PKCS#1 v1.5 加密填充易受 Bleichenbacher 填充预言攻击：
+/// 只要攻击者能观察到解密是否成功（错误信息、响应耗时），就能通过大量查询解出任意密文。
+/// 仅应在与只支持 v1.5 的旧系统互通时选用，并确保解密失败不会以任何形式暴露给对端。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RsaPadding {
+    /// RSAES-OAEP，摘要与 MGF1 均为 SHA-256
+    #[default]
+    Oaep,
+    /// RSAES-PKCS1-v1_5，仅为兼容旧系统保留
+    Pkcs1v15,
+}
+
+/// 密钥容器保护密钥数据所用的 AEAD 算法
+///
+/// 早期版本创建的容器没有记录该字段，按 AES-256-GCM 处理。ChaCha20-Poly1305 需要启用 `chacha` 特性。
+/// 本设置只决定存储的密钥如何被封装；数据加密算法的选择见 `symmetric::systems::SymmetricAlgorithm`。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyProtection {
+    /// AES-256-GCM
+    #[default]
+    Aes256Gcm,
+    /// ChaCha20-Poly1305，适合没有 AES 硬件加速的平台
+    ChaCha20Poly1305,
+}
+
+impl KeyProtection {
+    /// 按 CPU 是否具备 AES 硬件加速选择密钥容器的保护算法：有加速时用 AES-256-GCM，否则用 ChaCha20-Poly1305
+    ///
+    /// 未启用 `chacha` 特性时总是返回 AES-256-GCM。
+    pub fn select(aes_accelerated: bool) -> Self {
+        if aes_accelerated || cfg!(not(feature = "chacha")) {
+            KeyProtection::Aes256Gcm
+        } else {
+            KeyProtection::ChaCha20Poly1305
+        }
+    }
+
+    /// 检测当前 CPU 后调用 [`KeyProtection::select`]，结果只用于密钥容器的保护
+    #[cfg(feature = "std")]
+    pub fn auto() -> Self {
+        Self::select(crate::cpu::has_aes_acceleration())
+    }
+}
+
+/// AES-GCM 加密时 Nonce 的生成方式
+///
+/// 默认每次加密随机生成 Nonce，相同明文的密文互不相同。`SyntheticFromPlaintext` 以
+/// `HMAC-SHA256(由密钥派生的子密钥, 附加认证数据 || 明文)` 的前 12 字节作为 Nonce（类似 SIV），
+/// 相同密钥、明文与附加认证数据总是得到相同的密文，可用于按密文去重，而无需引入 AES-GCM-SIV。
+///
+/// 该模式下 Nonce 只在输入完全相同时才会重复，而此时密文本身也相同，不会泄露额外信息；
+/// 不同输入得到相同 Nonce 需要 96 位的 HMAC 碰撞。代价是确定性加密会暴露“两段密文的明文是否相同”，
+/// 只应在需要这一性质时选用。密文格式与随机 Nonce 相同，解密无需任何改动。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NonceStrategy {
+    /// 每次加密随机生成 Nonce
+    #[default]
+    Random,
+    /// 由密钥、附加认证数据与明文确定性地合成 Nonce
+    SyntheticFromPlaintext,
 }
 
 /// Base64编码的字符串类型
@@ -22,6 +163,11 @@ impl Base64String {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// 按指定的Base64变体编码
+    pub fn encode(&self, variant: Base64Variant) -> String {
+        variant.encode(&self.0)
+    }
 }
 
 impl AsRef<[u8]> for Base64String {
@@ -58,6 +204,344 @@ pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     result == 0
 }
 
+/// 计算 CRC-32（IEEE 802.3，与 zlib/PNG 相同）校验值
+///
+/// 仅用于检测传输与抄写错误，不提供任何抗篡改能力。
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
+
+/// 在已有的 CRC-32 值上继续累加数据，满足 `crc32_update(crc32(a), b) == crc32(a || b)`
+#[cfg(all(feature = "std", any(feature = "traditional", feature = "post-quantum", feature = "aes-gcm-feature", feature = "chacha")))]
+pub(crate) fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// 生成 `len` 字节的加密安全随机数，适用于盐值、挑战值等非密钥用途
+///
+/// 随机数统一取自 [`SystemRng`](crate::common::rng::SystemRng)，默认即操作系统RNG。
+#[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    crate::common::rng::fill_random(&mut bytes);
+    bytes
+}
+
+/// 生成由 `len` 字节随机数编码而成的 URL 安全 Base64（无填充）令牌，可直接用作会话ID等
+#[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+pub fn random_token(len: usize) -> String {
+    let mut bytes = random_bytes(len);
+    let token = Base64Variant::UrlSafeNoPad.encode(&bytes);
+    bytes.zeroize();
+    token
+}
+
+/// 将多个附加认证数据字段规范地拼接为一个 AAD：每个字段前加 8 字节大端长度
+///
+/// 长度前缀消除了字段边界的歧义，`["ab", "c"]` 与 `["a", "bc"]` 得到不同的结果；字段数量与顺序同样被认证。
+pub fn aad_segments(segments: &[&[u8]]) -> Vec<u8> {
+    let total: usize = segments.iter().map(|segment| 8 + segment.len()).sum();
+    let mut aad = Vec::with_capacity(total);
+    for segment in segments {
+        aad.extend_from_slice(&(segment.len() as u64).to_be_bytes());
+        aad.extend_from_slice(segment);
+    }
+    aad
+}
+
+#[cfg(test)]
+thread_local! {
+    /// 测试钩子：记录当前线程中常数时间密钥选择执行的尝试次数
+    pub(crate) static CT_SELECTION_ATTEMPTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// 对所有候选依次执行 `attempt`，不因某个候选成功而提前返回，并以不依赖成功位置的方式选出第一个成功结果
+///
+/// 用于在多个保留密钥间回退解密时隐藏匹配的是第几个密钥。尝试次数恒等于候选数量；
+/// 选择下标通过掩码运算得出，不含依赖结果的分支。各次尝试本身的耗时差异（如失败时提前终止的解析）
+/// 仍可能泄露信息，因此只能视为尽力而为的防护。
+#[cfg(feature = "std")]
+pub(crate) fn select_first_success_constant_time<K, T>(
+    candidates: impl IntoIterator<Item = K>,
+    mut attempt: impl FnMut(K) -> Option<T>,
+) -> Option<T> {
+    let mut results: Vec<Option<T>> = candidates.into_iter()
+        .map(|candidate| {
+            #[cfg(test)]
+            CT_SELECTION_ATTEMPTS.with(|count| count.set(count.get() + 1));
+            attempt(candidate)
+        })
+        .collect();
+
+    let mut selected = 0usize;
+    let mut found = 0usize;
+    for (index, result) in results.iter().enumerate() {
+        // success 为全1或全0掩码；仅在此前尚未找到成功结果时记录下标
+        let success = (result.is_some() as usize).wrapping_neg();
+        let take = core::hint::black_box(success & !found);
+        selected |= index & take;
+        found |= success;
+    }
+    if found == 0 {
+        return None;
+    }
+    results.swap_remove(selected)
+}
+
+/// 密文中密钥指纹与Base64密文之间的分隔符（不属于任何Base64字母表）
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+const FINGERPRINT_SEPARATOR: char = '.';
+
+/// 计算公钥指纹：导出公钥字符串的 SHA-256 摘要前 8 字节（十六进制）
+pub fn key_fingerprint(exported_public_key: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(exported_public_key.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// 反复调用 `attempt` 生成密钥，直到其指纹以十六进制 `prefix` 开头（不区分大小写），最多尝试 `max_attempts` 次
+///
+/// `attempt` 返回密钥及其指纹。`prefix` 不是十六进制或长于指纹时返回 `Error::Operation`，
+/// 预算内未找到时同样返回 `Error::Operation`。
+pub(crate) fn search_vanity_key<T>(
+    prefix: &str,
+    max_attempts: u64,
+    mut attempt: impl FnMut() -> Result<(T, String), crate::common::errors::Error>,
+) -> Result<T, crate::common::errors::Error> {
+    use crate::common::errors::Error;
+
+    if !prefix.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Error::Operation(format!("指纹前缀 {:?} 不是十六进制", prefix)));
+    }
+    if prefix.len() > 16 {
+        return Err(Error::Operation(format!("指纹前缀长度 {} 超过指纹长度 16", prefix.len())));
+    }
+    let prefix = prefix.to_ascii_lowercase();
+    for _ in 0..max_attempts {
+        let (key, fingerprint) = attempt()?;
+        if fingerprint.starts_with(&prefix) {
+            return Ok(key);
+        }
+    }
+    Err(Error::Operation(format!("尝试 {} 次后仍未找到指纹以 {} 开头的密钥", max_attempts, prefix)))
+}
+
+/// 在Base64密文前附加密钥指纹，格式为 `指纹.密文`
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn attach_fingerprint(fingerprint: &str, ciphertext: &str) -> String {
+    format!("{}{}{}", fingerprint, FINGERPRINT_SEPARATOR, ciphertext)
+}
+
+/// 拆分可能带有密钥指纹的密文，返回 (指纹, Base64密文)
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn split_fingerprint(ciphertext: &str) -> (Option<&str>, &str) {
+    match ciphertext.split_once(FINGERPRINT_SEPARATOR) {
+        Some((fingerprint, rest)) => (Some(fingerprint), rest),
+        None => (None, ciphertext),
+    }
+}
+
+/// 密文中调用方自定义密钥ID的前缀（`:` 不属于任何Base64字母表）
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+const KEY_ID_PREFIX: &str = "kid:";
+
+/// 在密文前附加调用方自定义的密钥ID，格式为 `kid:<URL安全Base64(密钥ID)>.密文`
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn attach_key_id(key_id: &str, ciphertext: &str) -> String {
+    format!("{}{}{}{}", KEY_ID_PREFIX, URL_SAFE_NO_PAD.encode(key_id), FINGERPRINT_SEPARATOR, ciphertext)
+}
+
+/// 密文中明文认证头部的前缀
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+const HEADERS_PREFIX: &str = "hdr:";
+
+/// 将明文头部编码为信封中的 `hdr:<URL安全Base64(JSON)>` 字段，返回该字段的文本
+///
+/// 字段文本同时作为AEAD附加认证数据，因此对头部的任何修改都会导致解密失败。
+/// `BTreeMap` 保证相同的头部总是得到相同的编码。
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn encode_headers(headers: &std::collections::BTreeMap<String, String>) -> Result<String, crate::common::errors::Error> {
+    let json = serde_json::to_vec(headers)
+        .map_err(|e| crate::common::errors::Error::Serialization(format!("头部序列化失败: {}", e)))?;
+    Ok(format!("{}{}", HEADERS_PREFIX, URL_SAFE_NO_PAD.encode(json)))
+}
+
+/// 在密文前附加 [`encode_headers`] 生成的头部字段
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn attach_headers(encoded_headers: &str, ciphertext: &str) -> String {
+    format!("{}{}{}", encoded_headers, FINGERPRINT_SEPARATOR, ciphertext)
+}
+
+/// 密文中生效时间字段的前缀
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+const NOT_BEFORE_PREFIX: &str = "nbf:";
+
+/// 将生效时间编码为信封中的 `nbf:<Unix秒数>` 字段；不足一秒的部分向上取整，保证不会提前生效
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn encode_not_before(not_before: std::time::SystemTime) -> Result<String, crate::common::errors::Error> {
+    let since_epoch = not_before.duration_since(std::time::UNIX_EPOCH)
+        .map_err(|_| crate::common::errors::Error::Operation("生效时间早于 Unix 纪元".to_string()))?;
+    let seconds = since_epoch.as_secs() + u64::from(since_epoch.subsec_nanos() > 0);
+    Ok(format!("{}{}", NOT_BEFORE_PREFIX, seconds))
+}
+
+/// 密文中密钥版本字段的前缀
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+const KEY_VERSION_PREFIX: &str = "ver:";
+
+/// 在密文前附加加密所用密钥的版本号，格式为 `ver:<版本>.密文`
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) fn attach_key_version(key_version: u32, ciphertext: &str) -> String {
+    format!("{}{}{}{}", KEY_VERSION_PREFIX, key_version, FINGERPRINT_SEPARATOR, ciphertext)
+}
+
+/// 引擎输出密文的外层信封，无需解密即可读取其中的路由信息
+///
+/// 格式为 `[kid:<密钥ID>.][hdr:<头部>.][nbf:<生效时间>.][ver:<密钥版本>.][指纹.]Base64密文`。密钥ID、密钥版本与指纹均为明文元数据，不受AEAD认证保护，
+/// 仅应用于路由与查找密钥，不能作为授权依据。头部与生效时间同样以明文保存、可直接读取，
+/// 但会被并入AEAD附加认证数据：被篡改、删除或添加这些字段的密文无法解密。
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiphertextEnvelope<'a> {
+    key_id: Option<String>,
+    headers: std::collections::BTreeMap<String, String>,
+    not_before: Option<std::time::SystemTime>,
+    /// 受认证字段（头部与生效时间）的原始文本
+    authenticated_fields: Option<&'a str>,
+    key_version: Option<u32>,
+    fingerprint: Option<&'a str>,
+    body: &'a str,
+}
+
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+impl<'a> CiphertextEnvelope<'a> {
+    /// 解析引擎输出的密文
+    pub fn parse(ciphertext: &'a str) -> Result<Self, crate::common::errors::Error> {
+        use crate::common::errors::Error;
+
+        let (key_id, rest) = match ciphertext.strip_prefix(KEY_ID_PREFIX) {
+            Some(tagged) => {
+                let (encoded, rest) = tagged.split_once(FINGERPRINT_SEPARATOR)
+                    .ok_or_else(|| Error::Format("密文格式错误：密钥ID缺少分隔符".to_string()))?;
+                let bytes = URL_SAFE_NO_PAD.decode(encoded)
+                    .map_err(|e| Error::Format(format!("密钥ID编码无效: {}", e)))?;
+                let key_id = String::from_utf8(bytes)
+                    .map_err(|e| Error::Format(format!("密钥ID不是有效的UTF-8: {}", e)))?;
+                (Some(key_id), rest)
+            }
+            None => (None, ciphertext),
+        };
+        let authenticated_start = rest;
+        let (headers, rest) = if rest.starts_with(HEADERS_PREFIX) {
+            let (encoded, rest) = rest.split_once(FINGERPRINT_SEPARATOR)
+                .ok_or_else(|| Error::Format("密文格式错误：头部缺少分隔符".to_string()))?;
+            let json = URL_SAFE_NO_PAD.decode(&encoded[HEADERS_PREFIX.len()..])
+                .map_err(|e| Error::Format(format!("头部编码无效: {}", e)))?;
+            let headers = serde_json::from_slice(&json)
+                .map_err(|e| Error::Format(format!("头部格式无效: {}", e)))?;
+            (headers, rest)
+        } else {
+            (Default::default(), rest)
+        };
+        let (not_before, rest) = match rest.strip_prefix(NOT_BEFORE_PREFIX) {
+            Some(tagged) => {
+                let (seconds, rest) = tagged.split_once(FINGERPRINT_SEPARATOR)
+                    .ok_or_else(|| Error::Format("密文格式错误：生效时间缺少分隔符".to_string()))?;
+                let seconds = seconds.parse()
+                    .map_err(|e| Error::Format(format!("生效时间无效: {}", e)))?;
+                let not_before = std::time::UNIX_EPOCH.checked_add(std::time::Duration::from_secs(seconds))
+                    .ok_or_else(|| Error::Format(format!("生效时间超出范围: {}", seconds)))?;
+                (Some(not_before), rest)
+            }
+            None => (None, rest),
+        };
+        // 受认证字段在文本中连续出现，去掉末尾分隔符即为加密时使用的附加认证数据
+        let authenticated_len = authenticated_start.len() - rest.len();
+        let authenticated_fields = (authenticated_len > 0).then(|| &authenticated_start[..authenticated_len - 1]);
+        let (key_version, rest) = match rest.strip_prefix(KEY_VERSION_PREFIX) {
+            Some(tagged) => {
+                let (version, rest) = tagged.split_once(FINGERPRINT_SEPARATOR)
+                    .ok_or_else(|| Error::Format("密文格式错误：密钥版本缺少分隔符".to_string()))?;
+                let version = version.parse()
+                    .map_err(|e| Error::Format(format!("密钥版本无效: {}", e)))?;
+                (Some(version), rest)
+            }
+            None => (None, rest),
+        };
+        let (fingerprint, body) = split_fingerprint(rest);
+        Ok(Self { key_id, headers, not_before, authenticated_fields, key_version, fingerprint, body })
+    }
+
+    /// 加密时附加的自定义密钥ID
+    pub fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+
+    /// 明文认证头部；读取不需要密钥，但在解密成功之前其内容不可信
+    pub fn headers(&self) -> &std::collections::BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// 加密时设置的生效时间；解密成功之前其内容不可信
+    pub fn not_before(&self) -> Option<std::time::SystemTime> {
+        self.not_before
+    }
+
+    /// `now` 早于生效时间时返回 `Error::NotYetValid`
+    ///
+    /// 这只是由库自行执行的软性限制，持有私钥者总能绕过它，不提供任何密码学意义上的时间锁。
+    pub(crate) fn check_not_before(&self, now: std::time::SystemTime) -> Result<(), crate::common::errors::Error> {
+        match self.not_before {
+            Some(not_before) if now < not_before => {
+                let seconds = not_before.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+                Err(crate::common::errors::Error::NotYetValid(format!("生效时间为 Unix 时间 {} 秒", seconds)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 嵌入的密钥指纹在 `revoked` 中时返回 `Error::KeyRevoked`；未嵌入指纹的密文无法判断，总是通过
+    pub(crate) fn check_revoked(&self, revoked: &std::collections::HashSet<String>) -> Result<(), crate::common::errors::Error> {
+        match self.fingerprint {
+            Some(fingerprint) if revoked.contains(fingerprint) => {
+                Err(crate::common::errors::Error::KeyRevoked(fingerprint.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 解密时应使用的附加认证数据：头部与生效时间字段的原始文本，二者都没有时为 `None`
+    pub(crate) fn associated_data(&self) -> Option<&'a [u8]> {
+        self.authenticated_fields.map(str::as_bytes)
+    }
+
+    /// 加密时嵌入的密钥版本（对应密钥元数据中的 `version`）
+    pub fn key_version(&self) -> Option<u32> {
+        self.key_version
+    }
+
+    /// 加密时嵌入的公钥指纹
+    pub fn fingerprint(&self) -> Option<&'a str> {
+        self.fingerprint
+    }
+
+    /// 去除信封后的Base64密文
+    pub fn body(&self) -> &'a str {
+        self.body
+    }
+}
+
 /// 安全字节容器，提供自动内存擦除
 /// 
 /// 当对象离开作用域时，自动清除内存中的敏感数据
@@ -125,6 +609,41 @@ impl AsRef<[u8]> for SecureBytes {
     }
 }
 
+/// 常见合规要求对应的配置预设，配合 [`CryptoConfig::preset`] 使用
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Profile {
+    /// NSA CNSA 套件：RSA-3072 以上、Kyber-1024（ML-KEM-1024）、AES-256-GCM
+    CnsaSuite,
+    /// NIST 128 位安全强度（SP 800-57 / FIPS 203 第1类）：RSA-3072、Kyber-512
+    Nist128,
+    /// NIST 192 位安全强度（SP 800-57 / FIPS 203 第3类）：RSA-7680、Kyber-768
+    Nist192,
+    /// 安全与性能的折中，与 [`CryptoConfig::default`] 的参数一致：RSA-3072、Kyber-768
+    Balanced,
+}
+
+/// 检查原始密钥字节是否明显不是随机生成的：全零、所有字节相同，或不同字节值少于密钥长度的一半
+///
+/// 只是粗略的启发式，通过检查并不代表密钥足够随机。32 字节的随机密钥平均约含 30 种不同字节值，
+/// 少于一半的概率可以忽略，因此正常生成的密钥几乎不会被误判。发现问题时返回 `Error::InsecureParameter`。
+pub fn check_weak_key(key: &[u8]) -> Result<(), crate::common::errors::Error> {
+    let mut seen = [false; 256];
+    for &byte in key {
+        seen[byte as usize] = true;
+    }
+    let distinct = seen.iter().filter(|&&present| present).count();
+    let finding = if key.iter().all(|&byte| byte == 0) {
+        "密钥全为零".to_string()
+    } else if distinct <= 1 {
+        "密钥的所有字节都相同".to_string()
+    } else if distinct < key.len() / 2 {
+        format!("密钥只含 {} 种不同字节值（共 {} 字节），熵过低", distinct, key.len())
+    } else {
+        return Ok(());
+    };
+    Err(crate::common::errors::Error::InsecureParameter(vec![finding]))
+}
+
 /// 加密系统配置
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CryptoConfig {
@@ -146,6 +665,68 @@ pub struct CryptoConfig {
     pub argon2_memory_cost: u32,
     /// Argon2时间成本（默认2）
     pub argon2_time_cost: u32,
+    /// 引擎输出密文时使用的Base64变体（解密时自动识别）
+    #[serde(default)]
+    pub base64_variant: Base64Variant,
+    /// 非对称引擎是否在密文前嵌入加密所用公钥的指纹，解密时据此直接选择密钥
+    #[serde(default)]
+    pub embed_key_fingerprint: bool,
+    /// 非对称引擎是否在密文前嵌入加密所用密钥的版本号，解密时据此直接选择对应版本的密钥
+    #[serde(default)]
+    pub embed_key_version: bool,
+    /// 非对称引擎是否在密文信封的认证头部写入算法标识（默认开启），供 [`crate::common::inspect::inspect`] 路由；
+    /// 底层系统不支持附加数据（如RSA）时无法认证该字段，因而不写入
+    #[serde(default = "default_embed_algorithm_id")]
+    pub embed_algorithm_id: bool,
+    /// 引擎回退解密时是否尝试所有保留密钥，并以常数时间选择成功结果，避免泄露匹配的密钥位置
+    #[serde(default)]
+    pub ct_key_selection: bool,
+    /// 引擎一次性 `encrypt` 接受的最大明文字节数（默认 64 MiB），更大的载荷应使用流式接口
+    #[serde(default = "default_max_oneshot_size")]
+    pub max_oneshot_size: usize,
+    /// 对称引擎 AES-GCM 认证标签的字节长度（12–16，默认16）；低于12字节的配置在加密时被拒绝，
+    /// 引擎解密时只接受以该长度加密的密文。密钥容器的 ChaCha20-Poly1305 保护固定使用16字节标签，不受此项影响
+    #[serde(default = "default_tag_length")]
+    pub tag_length: usize,
+    /// 导入对称密钥时是否拒绝全零或熵明显过低的密钥（见 [`check_weak_key`]），默认关闭以免误报；
+    /// 对称引擎与轮换管理器从存储加载密钥时同样按此检查
+    #[serde(default)]
+    pub reject_weak_keys: bool,
+    /// 引擎进行RSA加解密时使用的填充方案（默认 OAEP），解密只接受以该方案加密的密文；PKCS#1 v1.5 存在填充预言风险，见 [`RsaPadding`]
+    #[serde(default)]
+    pub rsa_padding: RsaPadding,
+    /// 新建密钥容器时保护密钥数据的 AEAD 算法（默认 AES-256-GCM）
+    #[serde(default)]
+    pub key_protection: KeyProtection,
+    /// 对称引擎 AES-GCM 加密时 Nonce 的生成方式（默认随机），见 [`NonceStrategy`]
+    #[serde(default)]
+    pub nonce_strategy: NonceStrategy,
+    /// 确定性测试模式的RNG种子（仅 `test-determinism` 特性可用）
+    #[cfg(feature = "test-determinism")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deterministic_seed: Option<u64>,
+}
+
+/// 长期数据可接受的最小RSA位数
+const MIN_LONG_TERM_RSA_BITS: usize = 3072;
+/// Argon2 的最低内存成本（KB）与时间成本
+const MIN_ARGON2_MEMORY_COST: u32 = 19456;
+const MIN_ARGON2_TIME_COST: u32 = 2;
+/// 一次性加密的默认载荷上限
+const DEFAULT_MAX_ONESHOT_SIZE: usize = 64 * 1024 * 1024;
+/// AES-GCM 完整认证标签长度
+const DEFAULT_TAG_LENGTH: usize = 16;
+
+fn default_max_oneshot_size() -> usize {
+    DEFAULT_MAX_ONESHOT_SIZE
+}
+
+fn default_tag_length() -> usize {
+    DEFAULT_TAG_LENGTH
+}
+
+fn default_embed_algorithm_id() -> bool {
+    true
 }
 
 impl Default for CryptoConfig {
@@ -160,6 +741,110 @@ impl Default for CryptoConfig {
             default_signature_algorithm: "RSA-PSS-SHA256".to_string(),
             argon2_memory_cost: 19456, // 19MB
             argon2_time_cost: 2,
+            base64_variant: Base64Variant::Standard,
+            embed_key_fingerprint: false,
+            embed_key_version: false,
+            embed_algorithm_id: true,
+            ct_key_selection: false,
+            max_oneshot_size: DEFAULT_MAX_ONESHOT_SIZE,
+            tag_length: DEFAULT_TAG_LENGTH,
+            reject_weak_keys: false,
+            rsa_padding: RsaPadding::Oaep,
+            key_protection: KeyProtection::Aes256Gcm,
+            nonce_strategy: NonceStrategy::Random,
+            #[cfg(feature = "test-determinism")]
+            deterministic_seed: None,
+        }
+    }
+}
+
+impl CryptoConfig {
+    /// 按合规预设生成配置：同时启用传统与后量子算法以及认证加密，并设置对应的RSA位数与Kyber级别
+    ///
+    /// 对称部分统一为 AES-256-GCM 密钥容器保护与 16 字节认证标签：CNSA 只认可 AES，其余预设与之保持一致。
+    /// 数据加密的对称算法由引擎的类型参数选择，应同样选用 AES-256-GCM。其余字段（Argon2 参数、Base64 变体等）保持默认值。
+    pub fn preset(profile: Profile) -> Self {
+        let (rsa_key_bits, kyber_parameter_k) = match profile {
+            Profile::CnsaSuite => (3072, 1024),
+            Profile::Nist128 => (3072, 512),
+            Profile::Nist192 => (7680, 768),
+            Profile::Balanced => (3072, 768),
+        };
+        Self {
+            use_traditional: true,
+            use_post_quantum: true,
+            rsa_key_bits,
+            kyber_parameter_k,
+            use_authenticated_encryption: true,
+            auto_verify_signatures: true,
+            key_protection: KeyProtection::Aes256Gcm,
+            tag_length: 16,
+            ..Self::default()
+        }
+    }
+
+    /// 一次性加密前检查载荷大小，超过 `max_oneshot_size` 时返回 `Error::PayloadTooLarge`
+    #[cfg(feature = "std")]
+    pub(crate) fn check_oneshot_size(&self, size: usize) -> Result<(), crate::common::errors::Error> {
+        if size > self.max_oneshot_size {
+            return Err(crate::common::errors::Error::PayloadTooLarge { size, limit: self.max_oneshot_size });
+        }
+        Ok(())
+    }
+
+    /// 列出不适合生产环境（长期保存数据）的设置，返回值为空表示未发现弱配置
+    pub fn insecure_settings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+        if self.use_traditional && self.rsa_key_bits < MIN_LONG_TERM_RSA_BITS {
+            findings.push(format!(
+                "rsa_key_bits = {}，低于长期数据所需的 {} 位", self.rsa_key_bits, MIN_LONG_TERM_RSA_BITS
+            ));
+        }
+        if self.use_traditional && self.rsa_padding == RsaPadding::Pkcs1v15 {
+            findings.push("rsa_padding = Pkcs1v15，RSA加密易受 Bleichenbacher 填充预言攻击".to_string());
+        }
+        if !self.use_authenticated_encryption {
+            findings.push("use_authenticated_encryption = false，密文不绑定发送方身份".to_string());
+        } else if !self.auto_verify_signatures {
+            findings.push("auto_verify_signatures = false，解密时不校验签名".to_string());
+        }
+        if self.argon2_memory_cost < MIN_ARGON2_MEMORY_COST || self.argon2_time_cost < MIN_ARGON2_TIME_COST {
+            findings.push(format!(
+                "Argon2 参数 (m = {} KB, t = {}) 低于基线 (m = {} KB, t = {})",
+                self.argon2_memory_cost, self.argon2_time_cost, MIN_ARGON2_MEMORY_COST, MIN_ARGON2_TIME_COST
+            ));
+        }
+        #[cfg(feature = "test-determinism")]
+        if self.deterministic_seed.is_some() {
+            findings.push("deterministic_seed 已设置，随机数可被预测".to_string());
+        }
+        findings
+    }
+
+    /// 配置摘要：按字段声明顺序序列化的 JSON 的 SHA-256
+    ///
+    /// 可与密文一同保存，审计时用于确认产生密文的配置；任一字段变化都会得到不同的摘要。
+    /// 新版本增加字段后，旧配置的摘要也会随之改变，比较摘要时应使用同一版本计算。
+    #[cfg(feature = "std")]
+    pub fn config_digest(&self) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        let canonical = serde_json::to_vec(self).expect("CryptoConfig 总能序列化为 JSON");
+        Sha256::digest(canonical).into()
+    }
+}
+
+#[cfg(feature = "test-determinism")]
+impl CryptoConfig {
+    /// 创建确定性测试配置：引擎的密钥生成和 Nonce 生成都将使用由 `seed` 派生的RNG
+    ///
+    /// 相同种子构建的引擎对相同输入产生逐字节相同的密文，便于金样测试。
+    /// Kyber 与 RSA-Kyber 的密钥生成与封装使用 pqcrypto 内部的系统RNG，无法被种子固定，
+    /// 以该配置创建这两种系统的引擎或任何异步非对称引擎时返回 `Error::Operation`。
+    pub fn test_deterministic(seed: u64) -> Self {
+        Self {
+            deterministic_seed: Some(seed),
+            ..Self::default()
         }
     }
 }
@@ -168,7 +853,7 @@ impl Default for CryptoConfig {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct ZeroizingVec(#[serde(with = "serde_bytes")] pub Vec<u8>);
 
-impl std::ops::Deref for ZeroizingVec {
+impl core::ops::Deref for ZeroizingVec {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -183,7 +868,7 @@ impl AsRef<[u8]> for ZeroizingVec {
 
 #[cfg(test)]
 mod tests {
-    use crate::common::utils::{constant_time_eq, from_base64, to_base64, Base64String, CryptoConfig, SecureBytes};
+    use crate::common::utils::{aad_segments, check_weak_key, constant_time_eq, from_base64, key_fingerprint, to_base64, Base64String, Base64Variant, CryptoConfig, SecureBytes};
 
     #[test]
     fn test_base64_roundtrip() {
@@ -193,6 +878,24 @@ mod tests {
         assert_eq!(decoded, original);
     }
     
+    #[test]
+    fn test_from_base64_tolerates_whitespace_and_missing_padding() {
+        let data = b"word-wrapped ciphertext?";
+        let encoded = to_base64(&data[..23]);
+        assert!(encoded.ends_with('='));
+
+        let unpadded = encoded.trim_end_matches('=');
+        assert_eq!(from_base64(unpadded).unwrap(), &data[..23]);
+
+        let wrapped: String = encoded.as_bytes().chunks(8)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect::<Vec<_>>()
+            .join("\r\n  ");
+        assert_eq!(from_base64(&format!(" {}\n", wrapped)).unwrap(), &data[..23]);
+
+        assert!(from_base64("not base64!").is_err());
+    }
+
     #[test]
     fn test_base64string_traits() {
         let data = vec![1, 2, 3, 4, 5];
@@ -208,6 +911,211 @@ mod tests {
         assert_eq!(b64_string.to_string(), to_base64(&b64_string.0));
     }
     
+    #[test]
+    fn test_base64_variants_auto_detect() {
+        // 0xfb 0xff 会在标准编码中产生 '+' 与 '/'
+        let data = vec![0xfb, 0xff, 0xfe, 0x00, 0x01];
+        let b64_string = Base64String::from(data.clone());
+
+        let standard = b64_string.encode(Base64Variant::Standard);
+        let url_safe = b64_string.encode(Base64Variant::UrlSafeNoPad);
+        assert_eq!(standard, b64_string.to_string());
+        assert!(!url_safe.contains(['+', '/', '=']));
+
+        assert_eq!(from_base64(&standard).unwrap(), data);
+        assert_eq!(from_base64(&url_safe).unwrap(), data);
+        assert!(from_base64("not*base64").is_err());
+    }
+
+    #[test]
+    fn test_aad_segments_boundaries() {
+        let left = aad_segments(&[&b"ab"[..], b"c"]);
+        let right = aad_segments(&[&b"a"[..], b"bc"]);
+        // 仅拼接时两者相同，长度前缀使其区分开
+        assert_eq!([&b"ab"[..], b"c"].concat(), [&b"a"[..], b"bc"].concat());
+        assert_ne!(left, right);
+        assert_eq!(left, [&2u64.to_be_bytes()[..], b"ab", &1u64.to_be_bytes(), b"c"].concat());
+        assert_ne!(aad_segments(&[&b""[..], b"abc"]), aad_segments(&[&b"abc"[..]]));
+        assert!(aad_segments(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_key_fingerprint() {
+        let fingerprint = key_fingerprint("public-key");
+        assert_eq!(fingerprint.len(), 16);
+        assert!(fingerprint.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(fingerprint, key_fingerprint("public-key"));
+        assert_ne!(fingerprint, key_fingerprint("other-public-key"));
+    }
+
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_ciphertext_envelope_parsing() {
+        use crate::common::utils::{attach_fingerprint, attach_key_id, CiphertextEnvelope};
+
+        let tagged = attach_key_id("tenant-a/key.1", &attach_fingerprint("0011223344556677", "Y2lwaGVy"));
+        let envelope = CiphertextEnvelope::parse(&tagged).unwrap();
+        assert_eq!(envelope.key_id(), Some("tenant-a/key.1"));
+        assert_eq!(envelope.fingerprint(), Some("0011223344556677"));
+        assert_eq!(envelope.body(), "Y2lwaGVy");
+
+        let plain = CiphertextEnvelope::parse("Y2lwaGVy").unwrap();
+        assert_eq!((plain.key_id(), plain.fingerprint(), plain.body()), (None, None, "Y2lwaGVy"));
+
+        assert!(CiphertextEnvelope::parse("kid:not-terminated").is_err());
+        assert!(CiphertextEnvelope::parse("kid:@@@.Y2lwaGVy").is_err());
+    }
+
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_ciphertext_envelope_headers() {
+        use crate::common::utils::{attach_fingerprint, attach_headers, attach_key_id, encode_headers, CiphertextEnvelope};
+        use std::collections::BTreeMap;
+
+        let headers = BTreeMap::from([
+            ("content-type".to_string(), "application/json".to_string()),
+            ("timestamp".to_string(), "1700000000".to_string()),
+        ]);
+        let encoded = encode_headers(&headers).unwrap();
+        let tagged = attach_key_id("k", &attach_headers(&encoded, &attach_fingerprint("0011223344556677", "Y2lwaGVy")));
+        let envelope = CiphertextEnvelope::parse(&tagged).unwrap();
+        assert_eq!(envelope.headers(), &headers);
+        assert_eq!(envelope.associated_data(), Some(encoded.as_bytes()));
+        assert_eq!((envelope.key_id(), envelope.fingerprint(), envelope.body()), (Some("k"), Some("0011223344556677"), "Y2lwaGVy"));
+
+        let plain = CiphertextEnvelope::parse("Y2lwaGVy").unwrap();
+        assert!(plain.headers().is_empty());
+        assert_eq!(plain.associated_data(), None);
+
+        assert!(CiphertextEnvelope::parse("hdr:e30").is_err());
+        assert!(CiphertextEnvelope::parse("hdr:bm90IGpzb24.Y2lwaGVy").is_err());
+    }
+
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_ciphertext_envelope_key_version() {
+        use crate::common::utils::{attach_fingerprint, attach_headers, attach_key_id, attach_key_version, encode_headers, CiphertextEnvelope};
+        use std::collections::BTreeMap;
+
+        let encoded = encode_headers(&BTreeMap::from([("a".to_string(), "b".to_string())])).unwrap();
+        let body = attach_key_version(7, &attach_fingerprint("0011223344556677", "Y2lwaGVy"));
+        let tagged = attach_key_id("k", &attach_headers(&encoded, &body));
+        let envelope = CiphertextEnvelope::parse(&tagged).unwrap();
+        assert_eq!(envelope.key_version(), Some(7));
+        assert_eq!((envelope.fingerprint(), envelope.body()), (Some("0011223344556677"), "Y2lwaGVy"));
+        // 密钥版本与指纹一样只用于路由，不属于附加认证数据
+        assert_eq!(envelope.associated_data(), Some(encoded.as_bytes()));
+
+        let versioned = attach_key_version(u32::MAX, "Y2lwaGVy");
+        let without_fingerprint = CiphertextEnvelope::parse(&versioned).unwrap();
+        assert_eq!((without_fingerprint.key_version(), without_fingerprint.fingerprint()), (Some(u32::MAX), None));
+        assert_eq!(CiphertextEnvelope::parse("Y2lwaGVy").unwrap().key_version(), None);
+
+        assert!(CiphertextEnvelope::parse("ver:1").is_err());
+        assert!(CiphertextEnvelope::parse("ver:-1.Y2lwaGVy").is_err());
+        assert!(CiphertextEnvelope::parse("ver:4294967296.Y2lwaGVy").is_err());
+    }
+
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_ciphertext_envelope_malformed_input() {
+        use crate::common::utils::CiphertextEnvelope;
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+        // 密钥ID解码后不是 UTF-8
+        assert!(CiphertextEnvelope::parse(&format!("kid:{}.Y2lwaGVy", URL_SAFE_NO_PAD.encode([0xff, 0xfe]))).is_err());
+        // 头部为空、不是对象或值不是字符串
+        for json in ["", "[1]", "{\"a\":1}", "{\"a\":"] {
+            let text = format!("hdr:{}.Y2lwaGVy", URL_SAFE_NO_PAD.encode(json));
+            assert!(CiphertextEnvelope::parse(&text).is_err(), "{:?}", json);
+        }
+
+        // 各结构片段的任意组合（含多字节字符）都不会引发恐慌
+        let pieces = ["kid:", "hdr:", "ver:", ".", ":", "e30", "__4", "Y2lwaGVy", "é", "😀", ""];
+        for a in pieces {
+            for b in pieces {
+                for c in pieces {
+                    let _ = CiphertextEnvelope::parse(&[a, b, c, b, a].concat());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crypto_config_presets() {
+        use crate::common::utils::{KeyProtection, Profile};
+
+        let expected = [
+            (Profile::CnsaSuite, 3072, 1024),
+            (Profile::Nist128, 3072, 512),
+            (Profile::Nist192, 7680, 768),
+            (Profile::Balanced, 3072, 768),
+        ];
+        for (profile, rsa_key_bits, kyber_parameter_k) in expected {
+            let config = CryptoConfig::preset(profile);
+            assert_eq!(config.rsa_key_bits, rsa_key_bits, "{:?}", profile);
+            assert_eq!(config.kyber_parameter_k, kyber_parameter_k, "{:?}", profile);
+            assert!(config.use_traditional && config.use_post_quantum);
+            assert!(config.use_authenticated_encryption && config.auto_verify_signatures);
+            assert_eq!((config.key_protection, config.tag_length), (KeyProtection::Aes256Gcm, 16), "{:?}", profile);
+        }
+        assert_eq!(CryptoConfig::preset(Profile::Balanced), CryptoConfig::default());
+    }
+
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_crc32_check_value() {
+        use crate::common::utils::crc32;
+
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crate::common::utils::crc32_update(crc32(b"1234"), b"56789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_insecure_settings() {
+        use crate::common::utils::{Profile, RsaPadding};
+
+        assert!(CryptoConfig::default().insecure_settings().is_empty());
+        for profile in [Profile::CnsaSuite, Profile::Nist128, Profile::Nist192, Profile::Balanced] {
+            assert!(CryptoConfig::preset(profile).insecure_settings().is_empty(), "{:?}", profile);
+        }
+
+        let weak = CryptoConfig {
+            rsa_key_bits: 2048,
+            use_authenticated_encryption: false,
+            argon2_memory_cost: 4096,
+            ..Default::default()
+        };
+        let findings = weak.insecure_settings();
+        assert_eq!(findings.len(), 3, "{:?}", findings);
+        assert!(findings[0].contains("rsa_key_bits"));
+        assert!(findings[1].contains("use_authenticated_encryption"));
+        assert!(findings[2].contains("Argon2"));
+
+        // 不使用RSA时不检查RSA位数
+        let pq_only = CryptoConfig { use_traditional: false, rsa_key_bits: 1024, ..Default::default() };
+        assert!(pq_only.insecure_settings().is_empty());
+
+        let legacy_padding = CryptoConfig { rsa_padding: RsaPadding::Pkcs1v15, ..Default::default() };
+        assert!(legacy_padding.insecure_settings()[0].contains("Pkcs1v15"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_select_first_success_constant_time() {
+        use crate::common::utils::{select_first_success_constant_time, CT_SELECTION_ATTEMPTS};
+
+        for matching in 0..4 {
+            let before = CT_SELECTION_ATTEMPTS.with(|count| count.get());
+            let selected = select_first_success_constant_time(0..4, |i| (i >= matching).then_some(i * 10));
+            assert_eq!(selected, Some(matching * 10));
+            assert_eq!(CT_SELECTION_ATTEMPTS.with(|count| count.get()) - before, 4);
+        }
+        assert_eq!(select_first_success_constant_time(0..3, |_| None::<u8>), None);
+        assert_eq!(select_first_success_constant_time(Vec::<u8>::new(), Some), None);
+    }
+
     #[test]
     fn test_constant_time_eq() {
         let a = b"sensitive data";
@@ -247,4 +1155,89 @@ mod tests {
         assert!(config.auto_verify_signatures);
         assert_eq!(config.default_signature_algorithm, "RSA-PSS-SHA256");
     }
+
+    #[test]
+    fn test_check_weak_key_heuristic() {
+        use crate::common::errors::Error;
+
+        for weak in [vec![0u8; 32], vec![0x41u8; 32], b"abababababababababababababababab".to_vec(), (0u8..8).cycle().take(32).collect()] {
+            assert!(matches!(check_weak_key(&weak), Err(Error::InsecureParameter(_))), "{:?}", weak);
+        }
+        let err = check_weak_key(&[0u8; 32]).unwrap_err();
+        assert!(err.to_string().contains("全为零"), "{}", err);
+
+        let random: Vec<u8> = (0..32u8).map(|i| i.wrapping_mul(73).wrapping_add(11)).collect();
+        assert!(check_weak_key(&random).is_ok());
+        assert!(check_weak_key(&(0u8..64).collect::<Vec<_>>()).is_ok());
+    }
+
+    #[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+    #[test]
+    fn test_random_bytes_and_token_lengths() {
+        use crate::common::utils::{random_bytes, random_token, Base64Variant};
+        use base64::Engine;
+
+        for len in [0, 1, 16, 31, 32, 64] {
+            assert_eq!(random_bytes(len).len(), len);
+            let token = random_token(len);
+            assert_eq!(token.len(), Base64Variant::UrlSafeNoPad.encoded_len(len));
+            assert!(token.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'), "{}", token);
+            assert_eq!(super::URL_SAFE_NO_PAD.decode(&token).unwrap().len(), len);
+        }
+    }
+
+    #[cfg(any(feature = "traditional", feature = "aes-gcm-feature"))]
+    #[test]
+    fn test_random_tokens_are_unique() {
+        use crate::common::utils::{random_bytes, random_token};
+
+        let tokens: std::collections::HashSet<String> = (0..1000).map(|_| random_token(16)).collect();
+        assert_eq!(tokens.len(), 1000);
+        assert_ne!(random_bytes(32), random_bytes(32));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_config_digest_tracks_every_field() {
+        use crate::common::utils::{KeyProtection, NonceStrategy, RsaPadding};
+
+        let base = CryptoConfig::default();
+        assert_eq!(base.config_digest(), CryptoConfig::default().config_digest());
+        let restored: CryptoConfig = serde_json::from_str(&serde_json::to_string(&base).unwrap()).unwrap();
+        assert_eq!(restored, base);
+        assert_eq!(restored.config_digest(), base.config_digest());
+
+        let mutations: Vec<fn(&mut CryptoConfig)> = vec![
+            |c| c.use_traditional = false,
+            |c| c.use_post_quantum = false,
+            |c| c.rsa_key_bits = 4096,
+            |c| c.kyber_parameter_k = 1024,
+            |c| c.use_authenticated_encryption = false,
+            |c| c.auto_verify_signatures = false,
+            |c| c.default_signature_algorithm = "Ed25519".to_string(),
+            |c| c.argon2_memory_cost += 1,
+            |c| c.argon2_time_cost += 1,
+            |c| c.base64_variant = Base64Variant::UrlSafeNoPad,
+            |c| c.embed_key_fingerprint = true,
+            |c| c.embed_key_version = true,
+            |c| c.embed_algorithm_id = false,
+            |c| c.ct_key_selection = true,
+            |c| c.max_oneshot_size += 1,
+            |c| c.tag_length = 12,
+            |c| c.reject_weak_keys = true,
+            |c| c.rsa_padding = RsaPadding::Pkcs1v15,
+            |c| c.key_protection = KeyProtection::ChaCha20Poly1305,
+            |c| c.nonce_strategy = NonceStrategy::SyntheticFromPlaintext,
+        ];
+        let mut digests = vec![base.config_digest()];
+        for mutate in mutations {
+            let mut config = base.clone();
+            mutate(&mut config);
+            digests.push(config.config_digest());
+        }
+        #[cfg(feature = "test-determinism")]
+        digests.push(CryptoConfig::test_deterministic(1).config_digest());
+        let distinct: std::collections::HashSet<_> = digests.iter().collect();
+        assert_eq!(distinct.len(), digests.len());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+//! CPU 能力检测
+//!
+//! 没有 AES 硬件指令（x86 的 AES-NI、ARMv8 的 AES 扩展）时，AES-GCM 只能以常数时间的软件实现运行，
+//! 速度明显慢于 ChaCha20-Poly1305。[`SymmetricAlgorithm::auto`](crate::symmetric::systems::SymmetricAlgorithm::auto)
+//! 据此为数据加密在两者之间选择，调用方再以选中的系统创建对称引擎；
+//! [`KeyProtection::auto`](crate::common::utils::KeyProtection::auto) 以同样的规则选择密钥容器的保护算法。
+
+/// 当前 CPU 是否支持 AES 硬件加速（运行时检测，结果由标准库缓存）
+pub fn has_aes_acceleration() -> bool {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        std::arch::is_x86_feature_detected!("aes") && std::arch::is_x86_feature_detected!("pclmulqdq")
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        std::arch::is_aarch64_feature_detected!("aes") && std::arch::is_aarch64_feature_detected!("pmull")
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        false
+    }
+}
+
+/// AES 将以软件实现运行时返回提示信息，有硬件加速时返回 `None`
+///
+/// 适合在服务启动时写入日志，提醒运维人员在该平台上优先选用 ChaCha20-Poly1305。
+pub fn software_fallback_notice() -> Option<&'static str> {
+    software_fallback_notice_for(has_aes_acceleration())
+}
+
+fn software_fallback_notice_for(aes_accelerated: bool) -> Option<&'static str> {
+    (!aes_accelerated).then_some("当前 CPU 不支持 AES 硬件加速，AES-GCM 将以较慢的软件实现运行；建议改用 ChaCha20-Poly1305")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_fallback_notice_follows_capability() {
+        assert!(software_fallback_notice_for(false).is_some());
+        assert!(software_fallback_notice_for(true).is_none());
+        assert_eq!(software_fallback_notice().is_none(), has_aes_acceleration());
+    }
+}
@@ -4,39 +4,87 @@
 //! 以及安全的密钥存储功能。
 //!
 //! 新版本添加了混合加密系统，同时使用RSA和Kyber提供双重安全保障。
+//!
+//! 关闭默认的 `std` 特性后，库以 `no_std + alloc` 方式编译，仅保留对称加密核心
+//! （`symmetric::systems` 与 `SymmetricCryptographicSystem`），供嵌入式目标使用。
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod storage;
 pub mod common;
+#[cfg(feature = "std")]
 pub mod rotation;
+#[cfg(feature = "std")]
+pub mod cpu;
 #[cfg(any(feature = "traditional", feature = "post-quantum"))]
 pub mod asymmetric;
 #[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
 pub mod symmetric;
+#[cfg(feature = "test-vectors")]
+pub mod testvectors;
+#[cfg(all(feature = "std", feature = "aes-gcm-feature"))]
+pub mod bench;
 
 #[cfg(any(feature = "traditional", feature = "post-quantum"))]
-pub use asymmetric::traits::AsymmetricCryptographicSystem;
+pub use asymmetric::traits::{AsymmetricCryptographicSystem, KeyProvider};
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use asymmetric::prepared::PreparedPublicKey;
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use asymmetric::dynamic::DynAsymmetricSystem;
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use asymmetric::identify::{identify, AlgorithmInfo, DetectedAlgorithm};
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use asymmetric::key_kind::{detect_key_kind, KeyKind};
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use asymmetric::compact::CompactPublicKey;
+#[cfg(all(any(feature = "traditional", feature = "post-quantum"), feature = "aes-gcm-feature"))]
+pub use asymmetric::multi_recipient::{MultiRecipientBuilder, MultiRecipientEnvelope, RecipientPolicy};
 #[cfg(feature = "secure-storage")]
 pub use common::traits::SecureKeyStorage;
 #[cfg(any(feature = "traditional", feature = "post-quantum"))]
 pub use common::traits::AuthenticatedCryptoSystem;
 pub use common::errors::Error;
+#[cfg(all(feature = "std", any(feature = "aes-gcm-feature", feature = "traditional")))]
+pub use common::sizing::{predict_output_size, OutputAlgorithm};
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use common::inspect::{inspect, is_revoked, verify_produced_with, verify_produced_with_config, BlobInfo};
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub use common::records::{write_record, RecordReader};
+#[cfg(feature = "aes-gcm-feature")]
+pub use common::key_derivation::KeyDerivation;
+#[cfg(feature = "aes-gcm-feature")]
+pub use symmetric::codec::CiphertextCodec;
+#[cfg(feature = "aes-gcm-feature")]
+pub use symmetric::split::{KeyShare, SplitKey};
 #[cfg(all(feature = "traditional", feature = "post-quantum"))]
 pub use asymmetric::systems::hybrid::rsa_kyber::RsaKyberCryptoSystem;
 #[cfg(any(feature = "traditional", feature = "post-quantum"))]
 pub use asymmetric::rotation::KeyRotationManager;
+#[cfg(feature = "std")]
 pub use common::config::ConfigManager;
+#[cfg(feature = "std")]
+pub use common::audit::{AuditEvent, AuditOperation, AuditOutcome, AuditSink, JsonLinesAuditSink, MemoryAuditSink};
 #[cfg(any(feature = "traditional", feature = "post-quantum"))]
 pub use asymmetric::engines::AsymmetricQSealEngine;
 #[cfg(all(feature = "async-engine", any(feature = "traditional", feature = "post-quantum")))]
 pub use asymmetric::engines::AsymmetricQSealEngineAsync;
-#[cfg(any(feature = "aes-gcm-feature", feature = "chacha"))]
+#[cfg(all(feature = "std", any(feature = "aes-gcm-feature", feature = "chacha")))]
 pub use symmetric::engines::SymmetricQSealEngine;
+#[cfg(all(feature = "std", feature = "aes-gcm-feature"))]
+pub use symmetric::engines::AutoSymmetricEngine;
 
 // 条件编译特性
 /// 传统RSA加密系统别名
 #[cfg(feature = "traditional")]
 pub use asymmetric::systems::traditional::rsa::RsaCryptoSystem as TraditionalRsa;
 
+/// X25519 匿名密封盒系统
+#[cfg(feature = "traditional")]
+pub use asymmetric::systems::traditional::x25519::X25519CryptoSystem;
+
 /// 后量子Kyber加密系统别名
 #[cfg(feature = "post-quantum")]
 pub use asymmetric::systems::post_quantum::kyber::KyberCryptoSystem as PostQuantumKyber;
@@ -93,4 +141,9 @@ mod tests {
             assert!(result, "Failed with system: {}", system);
         }
     }
-}
\ No newline at end of file
+
+    // 只有确实认证附加数据的系统声明支持
+    const _: () = assert!(!TraditionalRsa::SUPPORTS_ADDITIONAL_DATA);
+    const _: () = assert!(PostQuantumKyber::SUPPORTS_ADDITIONAL_DATA);
+    const _: () = assert!(HybridRsaKyber::SUPPORTS_ADDITIONAL_DATA);
+}
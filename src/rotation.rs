@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
 pub(crate) use crate::common::traits::KeyMetadata;
 use crate::common::errors::Error;
 
+/// 审计链哈希的域分隔标签
+const AUDIT_HASH_LABEL: &[u8] = b"seal-kit-rotation-audit-v1";
+/// 审计链首个事件引用的前驱哈希
+const AUDIT_GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
 
 /// 密钥轮换策略
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -40,6 +47,234 @@ pub trait KeyStorage: Send + Sync {
     
     /// 删除密钥
     fn delete_key(&self, name: &str) -> Result<(), Error>;
+
+    /// 将一个密钥连同元数据复制到另一个存储后端
+    ///
+    /// 中转的密钥明文只保存在 `Zeroizing` 缓冲区中，无论写入成功与否都会被擦除；
+    /// 源后端中的密钥保持不变，确认迁移成功后可自行调用 `delete_key` 删除。
+    ///
+    /// 迁移定义在 `KeyStorage` 而不是 `SecureKeyStorage` 上：
+    /// 后者描述的是单个口令加密的密钥容器，没有密钥名称与存储后端的概念，而按名称保存多个密钥的后端都实现本特征。
+    fn migrate_to(&self, dest: &dyn KeyStorage, name: &str) -> Result<(), Error> {
+        let (metadata, key_data) = self.load_key(name)?;
+        let key_data = Zeroizing::new(key_data);
+        dest.save_key(name, &metadata, &key_data)
+    }
+
+    /// 删除密钥，并尽可能擦除其在存储介质上的内容
+    ///
+    /// 默认实现等同于 `delete_key`；能够在删除前覆写底层数据的后端（如文件存储）应重写此方法。
+    fn purge_key(&self, name: &str) -> Result<(), Error> {
+        self.delete_key(name)
+    }
+
+    /// 擦除存储中所有指纹为 `fingerprint` 的密钥，返回逐条的处理结果
+    ///
+    /// 指纹的计算方式见 [`stored_key_fingerprint`]。同一密钥可能以多个名称存储（如轮换前后的副本），
+    /// 所有匹配的条目都会经 `purge_key` 擦除。个别条目读取或擦除失败时继续处理其余条目，
+    /// 失败的条目记录在 [`PurgeReport::failures`] 中，调用方须检查 [`PurgeReport::is_complete`]；
+    /// 只有列出密钥本身失败时才返回错误。本方法定义在 `KeyStorage` 上的原因同 [`migrate_to`](Self::migrate_to)。
+    fn purge_by_fingerprint(&self, fingerprint: &str) -> Result<PurgeReport, Error> {
+        let mut report = PurgeReport::default();
+        for name in self.list_keys()? {
+            let key_data = match self.load_key(&name) {
+                Ok((_, key_data)) => Zeroizing::new(key_data),
+                Err(e) => {
+                    report.failures.push((name, e));
+                    continue;
+                }
+            };
+            if stored_key_fingerprint(&key_data).as_deref() == Some(fingerprint) {
+                match self.purge_key(&name) {
+                    Ok(()) => report.purged.push(name),
+                    Err(e) => report.failures.push((name, e)),
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// [`KeyStorage::purge_by_fingerprint`] 的处理结果
+#[derive(Debug, Default)]
+pub struct PurgeReport {
+    /// 已擦除的密钥名称
+    pub purged: Vec<String>,
+    /// 无法读取或擦除的密钥名称及对应错误；无法读取的条目可能也匹配该指纹
+    pub failures: Vec<(String, Error)>,
+}
+
+impl PurgeReport {
+    /// 是否找到并擦除了至少一个匹配的密钥
+    pub fn found(&self) -> bool {
+        !self.purged.is_empty()
+    }
+
+    /// 是否所有条目都处理成功，没有遗漏可能匹配的密钥
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// 计算 `KeyStorage` 中一条密钥数据的指纹
+///
+/// 非对称密钥对取其导出公钥的 [`key_fingerprint`]，与引擎嵌入密文的指纹一致；
+/// 对称密钥取导出密钥字符串的 [`key_fingerprint`]。数据既不是密钥对也不是文本时返回 `None`。
+///
+/// [`key_fingerprint`]: crate::common::utils::key_fingerprint
+pub fn stored_key_fingerprint(key_data: &[u8]) -> Option<String> {
+    use crate::common::utils::key_fingerprint;
+
+    /// 只反序列化密钥对的公钥部分，私钥不会被复制出来
+    #[derive(Deserialize)]
+    struct PublicHalf {
+        public_key: String,
+    }
+
+    if let Ok(pair) = serde_json::from_slice::<PublicHalf>(key_data) {
+        return Some(key_fingerprint(&pair.public_key));
+    }
+    std::str::from_utf8(key_data).ok().map(key_fingerprint)
+}
+
+/// 轮换审计事件的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotationEventKind {
+    /// 创建了首个主密钥
+    KeyCreated,
+    /// 开始轮换：新主密钥启用，旧主密钥转为轮换中
+    RotationStarted,
+    /// 完成轮换：轮换中的旧密钥被删除
+    RotationCompleted,
+}
+
+impl RotationEventKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            RotationEventKind::KeyCreated => "key-created",
+            RotationEventKind::RotationStarted => "rotation-started",
+            RotationEventKind::RotationCompleted => "rotation-completed",
+        }
+    }
+}
+
+/// 轮换审计日志中的一条事件
+///
+/// 每条事件的 `hash` 覆盖前一条事件的 `hash` 与本事件的全部字段（签名除外），构成哈希链：
+/// 修改、删除或重排任意历史事件都会使之后的链校验失败。配置了签名者时，`signature` 是对 `hash` 的签名。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationEvent {
+    /// 事件序号，从0开始连续递增
+    pub sequence: u64,
+    /// 事件时间 (RFC 3339)
+    pub timestamp: String,
+    /// 事件类型
+    pub kind: RotationEventKind,
+    /// 事件涉及的密钥ID（开始轮换时为新主密钥）
+    pub key_id: String,
+    /// 该密钥的版本
+    pub key_version: u32,
+    /// 开始轮换时被替换的旧主密钥ID
+    pub previous_key_id: Option<String>,
+    /// 前一条事件的哈希（十六进制）
+    pub previous_hash: String,
+    /// 本事件的哈希（十六进制）
+    pub hash: String,
+    /// 对 `hash` 的签名（Base64），未配置签名者时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl RotationEvent {
+    /// 按事件字段与前驱哈希重新计算哈希
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(AUDIT_HASH_LABEL);
+        let sequence = self.sequence.to_string();
+        let key_version = self.key_version.to_string();
+        let fields = [
+            self.previous_hash.as_str(),
+            &sequence,
+            &self.timestamp,
+            self.kind.as_str(),
+            &self.key_id,
+            &key_version,
+            self.previous_key_id.as_deref().unwrap_or_default(),
+        ];
+        for field in fields {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+/// 审计事件签名函数：输入事件哈希，返回签名
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+pub(crate) type AuditSigner = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, Error> + Send + Sync>;
+
+/// 只追加的轮换审计日志
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+#[derive(Default)]
+pub(crate) struct AuditLog {
+    events: Vec<RotationEvent>,
+    signer: Option<AuditSigner>,
+}
+
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+impl AuditLog {
+    pub(crate) fn set_signer(&mut self, signer: AuditSigner) {
+        self.signer = Some(signer);
+    }
+
+    /// 追加一条事件，链接到当前最后一条事件
+    pub(crate) fn record(
+        &mut self,
+        kind: RotationEventKind,
+        metadata: &KeyMetadata,
+        previous_key_id: Option<String>,
+    ) -> Result<(), Error> {
+        let mut event = RotationEvent {
+            sequence: self.events.len() as u64,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind,
+            key_id: metadata.id.clone(),
+            key_version: metadata.version,
+            previous_key_id,
+            previous_hash: self.events.last().map_or_else(|| AUDIT_GENESIS_HASH.to_string(), |last| last.hash.clone()),
+            hash: String::new(),
+            signature: None,
+        };
+        event.hash = event.compute_hash();
+        if let Some(signer) = &self.signer {
+            event.signature = Some(crate::common::utils::to_base64(&signer(event.hash.as_bytes())?));
+        }
+        self.events.push(event);
+        Ok(())
+    }
+
+    pub(crate) fn events(&self) -> &[RotationEvent] {
+        &self.events
+    }
+
+    #[cfg(test)]
+    pub(crate) fn events_mut(&mut self) -> &mut Vec<RotationEvent> {
+        &mut self.events
+    }
+}
+
+/// 校验审计事件序列的哈希链：序号连续、每条事件引用前一条的哈希且自身哈希与内容一致
+///
+/// 只校验链本身，不校验签名；可用于校验导出后保存的审计日志。
+pub fn verify_audit_chain(events: &[RotationEvent]) -> bool {
+    let mut previous_hash = AUDIT_GENESIS_HASH;
+    for (index, event) in events.iter().enumerate() {
+        if event.sequence != index as u64 || event.previous_hash != previous_hash || event.compute_hash() != event.hash {
+            return false;
+        }
+        previous_hash = &event.hash;
+    }
+    true
 }
 
 /// 密钥对序列化数据
@@ -189,4 +424,69 @@ mod tests {
         let (meta2, _) = storage.load_key(name).unwrap();
         assert_eq!(meta2.usage_count, 1);
     }
+
+    #[test]
+    fn test_audit_chain_verifies_and_detects_tampering() {
+        let storage = Arc::new(InMemoryStorage::new());
+        let mut mgr = KeyRotationManager::<DummySystem>::new(storage, RotationPolicy::default(), "audit");
+        mgr.initialize(&CryptoConfig::default()).unwrap();
+        for _ in 0..3 {
+            mgr.start_rotation(&CryptoConfig::default()).unwrap();
+            mgr.complete_rotation().unwrap();
+        }
+
+        let log = mgr.audit_log();
+        let kinds: Vec<_> = log.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds[0], RotationEventKind::KeyCreated);
+        assert_eq!(&kinds[1..3], &[RotationEventKind::RotationStarted, RotationEventKind::RotationCompleted]);
+        assert_eq!(log.len(), 7);
+        assert_eq!(log[1].previous_key_id.as_deref(), Some(log[0].key_id.as_str()));
+        assert_eq!(log[2].key_id, log[0].key_id);
+        assert_eq!(log.last().unwrap().key_version, 3);
+        assert!(mgr.verify_audit_chain().unwrap());
+        assert!(verify_audit_chain(&log));
+
+        // 修改历史事件的内容
+        mgr.audit_events_mut()[2].key_version = 9;
+        assert!(!mgr.verify_audit_chain().unwrap());
+        mgr.audit_events_mut()[2] = log[2].clone();
+        assert!(mgr.verify_audit_chain().unwrap());
+
+        // 修改后重新计算该事件的哈希，之后一条事件的链接仍会断开
+        let mut rewritten = log.clone();
+        rewritten[2].key_id = "forged".to_string();
+        rewritten[2].hash = rewritten[2].compute_hash();
+        assert!(!verify_audit_chain(&rewritten));
+
+        // 删除或重排事件
+        let mut truncated = log.clone();
+        truncated.remove(1);
+        assert!(!verify_audit_chain(&truncated));
+        let mut reordered = log.clone();
+        reordered.swap(3, 4);
+        assert!(!verify_audit_chain(&reordered));
+    }
+
+    #[cfg(all(feature = "traditional", feature = "post-quantum"))]
+    #[test]
+    fn test_signed_audit_log() {
+        use crate::asymmetric::systems::hybrid::rsa_kyber::RsaKyberCryptoSystem;
+
+        let config = CryptoConfig { rsa_key_bits: 2048, ..Default::default() };
+        let (auditor_pk, auditor_sk) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let (other_pk, _) = RsaKyberCryptoSystem::generate_keypair(&config).unwrap();
+        let storage = Arc::new(InMemoryStorage::new());
+        let mut mgr = KeyRotationManager::<RsaKyberCryptoSystem>::new(storage, RotationPolicy::default(), "signed")
+            .with_audit_signer(auditor_sk);
+        mgr.initialize(&config).unwrap();
+        mgr.start_rotation(&config).unwrap();
+
+        assert!(mgr.audit_log().iter().all(|event| event.signature.is_some()));
+        assert!(mgr.verify_audit_signatures(&auditor_pk).unwrap());
+        assert!(!mgr.verify_audit_signatures(&other_pk).unwrap());
+
+        mgr.audit_events_mut()[1].signature = mgr.audit_log()[0].signature.clone();
+        assert!(mgr.verify_audit_chain().unwrap());
+        assert!(!mgr.verify_audit_signatures(&auditor_pk).unwrap());
+    }
 } 
\ No newline at end of file
@@ -5,7 +5,9 @@
 #[cfg(feature = "secure-storage")]
 pub mod container;
 pub mod file;
+pub mod memory;
 
 #[cfg(feature = "secure-storage")]
 pub use container::EncryptedKeyContainer;
-pub use file::KeyFileStorage; 
\ No newline at end of file
+pub use file::KeyFileStorage;
+pub use memory::MemoryKeyStorage; 
\ No newline at end of file
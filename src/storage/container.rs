@@ -14,10 +14,82 @@ use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Nonce
 };
-use argon2::password_hash::rand_core::{OsRng, RngCore};
+use argon2::password_hash::rand_core::RngCore;
+use crate::common::rng::SystemRng;
 use crate::common::traits::SecureKeyStorage;
 use crate::common::errors::Error;
-use crate::common::utils::{from_base64, to_base64, CryptoConfig};
+#[cfg(any(feature = "traditional", feature = "post-quantum"))]
+use crate::asymmetric::traits::AsymmetricCryptographicSystem;
+use crate::common::key_derivation::KeyDerivation;
+use crate::common::utils::{constant_time_eq, from_base64, to_base64, CryptoConfig, KeyProtection};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// 密码校验值的域分隔标签
+const VERIFIER_LABEL: &[u8] = b"seal-kit-container-password-verifier-v1";
+/// 使用自定义 KDF 派生 KEK 时传入的 `info`
+const KEK_INFO: &[u8] = b"seal-kit-container-kek-v1";
+
+#[cfg(test)]
+thread_local! {
+    /// 测试钩子：记录当前线程解密密钥条目的次数
+    static KEY_DECRYPTIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    /// 测试钩子：记录当前线程执行 Argon2 派生的次数
+    static KEY_DERIVATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// 会话内缓存的派生密钥（KEK）及对应密码的摘要
+struct CachedKek {
+    password_digest: Zeroizing<[u8; 32]>,
+    kek: Zeroizing<Vec<u8>>,
+}
+
+/// KEK 缓存：不参与序列化，克隆容器时不复制缓存内容；缓存的密钥在清除或丢弃时自动清零
+#[derive(Default)]
+struct KekCache(Mutex<Option<CachedKek>>);
+
+impl KekCache {
+    fn slot(&self) -> std::sync::MutexGuard<'_, Option<CachedKek>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 密码与缓存时的密码一致时返回缓存的 KEK
+    fn get(&self, password: &SecretString) -> Option<Zeroizing<Vec<u8>>> {
+        let digest = password_digest(password);
+        self.slot().as_ref()
+            .filter(|cached| constant_time_eq(cached.password_digest.as_ref(), digest.as_ref()))
+            .map(|cached| cached.kek.clone())
+    }
+
+    fn store(&self, password: &SecretString, kek: Zeroizing<Vec<u8>>) {
+        *self.slot() = Some(CachedKek { password_digest: password_digest(password), kek });
+    }
+
+    fn clear(&self) {
+        self.slot().take();
+    }
+
+    fn is_cached(&self) -> bool {
+        self.slot().is_some()
+    }
+}
+
+impl Clone for KekCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for KekCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KekCache").field("cached", &self.is_cached()).finish()
+    }
+}
+
+fn password_digest(password: &SecretString) -> Zeroizing<[u8; 32]> {
+    Zeroizing::new(Sha256::digest(password.expose_secret().as_bytes()).into())
+}
 
 /// 加密的密钥容器，实现了SecureKeyStorage特征
 /// 提供密码保护的密钥存储功能
@@ -45,6 +117,25 @@ pub struct EncryptedKeyContainer {
     /// Argon2时间成本参数（迭代次数）
     #[serde(default = "default_time_cost")]
     time_cost: u32,
+
+    /// 保护密钥数据的 AEAD 算法；早期版本的容器没有该字段，按 AES-256-GCM 处理
+    #[serde(default)]
+    protection: KeyProtection,
+
+    /// 自定义 KDF 的算法标识；为空时使用 Argon2id 与上面的参数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<String>,
+
+    /// 密码校验值：SHA-256(派生密钥 || 标签)，用于在不解密密钥的情况下校验密码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verifier: Option<String>,
+
+    /// 是否在会话内缓存派生的 KEK；关闭时每次访问都重新执行 Argon2 派生
+    #[serde(skip)]
+    kek_caching: bool,
+
+    #[serde(skip)]
+    kek_cache: KekCache,
 }
 
 fn default_memory_cost() -> u32 {
@@ -75,11 +166,29 @@ impl EncryptedKeyContainer {
         Self::encrypt_key_with_config(password, key_data, algorithm_id, config)
     }
     
+    /// 使用自定义 KDF 生成新的密钥容器
+    ///
+    /// 容器只记录 KDF 的算法标识而不记录其参数，解密时必须通过 [`Self::get_key_with_kdf`]
+    /// 传入参数相同的 KDF；`get_key` 与 `verify_password` 对这类容器返回错误。
+    pub fn new_with_kdf<K: AsRef<[u8]>, D: KeyDerivation>(
+        password: &SecretString,
+        key_data: K,
+        algorithm_id: &str,
+        kdf: &D
+    ) -> Result<Self, Error> {
+        Self::seal(password, key_data.as_ref(), algorithm_id, &CryptoConfig::default(), Some(kdf))
+    }
+
     /// 从密钥容器中提取密钥
     pub fn get_key(&self, password: &SecretString) -> Result<Vec<u8>, Error> {
         let secure_bytes = self.decrypt_key(password)?;
         Ok(secure_bytes.to_vec())
     }
+
+    /// 使用创建容器时的自定义 KDF 提取密钥，KDF 的算法标识与容器记录的不一致时返回错误
+    pub fn get_key_with_kdf<D: KeyDerivation>(&self, password: &SecretString, kdf: &D) -> Result<Vec<u8>, Error> {
+        self.open(password, Some(kdf))
+    }
     
     /// 使用自定义参数加密密钥
     pub fn encrypt_key_with_config<K: AsRef<[u8]>>(
@@ -87,47 +196,30 @@ impl EncryptedKeyContainer {
         key_data: K,
         algorithm_id: &str,
         config: &CryptoConfig
+    ) -> Result<Self, Error> {
+        Self::seal(password, key_data.as_ref(), algorithm_id, config, None)
+    }
+
+    /// 加密密钥数据；`kdf` 为空时使用 Argon2id 与 `config` 中的参数
+    fn seal(
+        password: &SecretString,
+        key_data: &[u8],
+        algorithm_id: &str,
+        config: &CryptoConfig,
+        kdf: Option<&dyn KeyDerivation>
     ) -> Result<Self, Error> {
         // 生成随机盐值用于密钥派生
-        let salt = SaltString::generate(&mut OsRng);
-        
-        // 使用配置的参数创建Argon2实例
-        let mut params_builder = ParamsBuilder::new();
-        params_builder
-            .m_cost(config.argon2_memory_cost)
-            .t_cost(config.argon2_time_cost)
-            .p_cost(1) // 并行度参数
-            .output_len(32); // 输出长度为32字节（256位）
-            
-        let params = params_builder
-            .build()
-            .map_err(|e| Error::KeyStorage(format!("Argon2参数无效: {}", e)))?;
-            
-        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+        let salt = SaltString::generate(&mut SystemRng);
         
-        // 使用Argon2派生加密密钥
-        let password_bytes = password.expose_secret().as_bytes();
-        let password_hash = argon2
-            .hash_password(password_bytes, &salt)
-            .map_err(|e| Error::KeyStorage(format!("密码哈希失败: {}", e)))?;
-        
-        // 安全地获取哈希值
-        let hash = password_hash.hash
-            .ok_or_else(|| Error::KeyStorage("无法生成密码哈希".to_string()))?;
-        let derived_key = hash.as_bytes();
-        
-        // 创建AES-GCM加密器
-        let cipher = Aes256Gcm::new_from_slice(derived_key)
-            .map_err(|e| Error::KeyStorage(format!("创建加密器失败: {}", e)))?;
+        let derived_key = match kdf {
+            Some(kdf) => Self::derive_custom_key(kdf, password, &salt)?,
+            None => Self::derive_key(password, &salt, config.argon2_memory_cost, config.argon2_time_cost)?,
+        };
         
         // 生成随机nonce并加密数据
         let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        
-        let nonce = Nonce::from_slice(&nonce_bytes);
-        let ciphertext = cipher
-            .encrypt(nonce, key_data.as_ref())
-            .map_err(|e| Error::KeyStorage(format!("加密密钥失败: {}", e)))?;
+        SystemRng.fill_bytes(&mut nonce_bytes);
+        let ciphertext = seal_key_data(config.key_protection, &derived_key, &nonce_bytes, key_data)?;
         
         Ok(Self {
             encrypted_data: to_base64(&ciphertext),
@@ -137,38 +229,158 @@ impl EncryptedKeyContainer {
             created_at: Utc::now().to_rfc3339(),
             memory_cost: config.argon2_memory_cost,
             time_cost: config.argon2_time_cost,
+            protection: config.key_protection,
+            kdf: kdf.map(|kdf| kdf.algorithm().to_string()),
+            verifier: Some(Self::compute_verifier(&derived_key)),
+            kek_caching: false,
+            kek_cache: KekCache::default(),
         })
     }
-}
 
-impl SecureKeyStorage for EncryptedKeyContainer {
-    type Error = Error;
-    
-    fn encrypt_key<K: AsRef<[u8]>>(
-        password: &SecretString, 
-        key_data: K,
-        algorithm_id: &str
-    ) -> Result<Self, Self::Error> {
-        // 使用默认配置
-        Self::encrypt_key_with_config(
-            password,
-            key_data, 
-            algorithm_id,
-            &CryptoConfig::default()
-        )
+    /// 按新配置重新保护容器中的密钥
+    ///
+    /// 以 `password` 解密密钥数据后，使用新的盐值与 Nonce、`new_config` 中的 Argon2 参数与
+    /// [`key_protection`](CryptoConfig::key_protection) 算法重新加密。新的保护完全建立后才替换当前内容，
+    /// 任何一步失败（包括密码错误）都不会改变容器。算法标识与创建时间保持不变；使用自定义 KDF 的容器不能迁移。
+    pub fn migrate_protection(&mut self, password: &SecretString, new_config: &CryptoConfig) -> Result<(), Error> {
+        let key_data = Zeroizing::new(self.decrypt_key(password)?);
+        let mut migrated = Self::seal(password, &key_data, &self.algorithm_id, new_config, None)?;
+        migrated.created_at = self.created_at.clone();
+        migrated.kek_caching = self.kek_caching;
+        *self = migrated;
+        Ok(())
     }
-    
-    fn decrypt_key(&self, password: &SecretString) -> Result<Vec<u8>, Self::Error> {
-        // 重建盐值和派生密钥
-        let salt_str = &self.salt;
-        let salt = SaltString::from_b64(salt_str)
+
+    /// 保护密钥数据所用的 AEAD 算法
+    pub fn protection(&self) -> KeyProtection {
+        self.protection
+    }
+
+    /// 设置是否缓存派生的 KEK
+    ///
+    /// 开启后，首次以正确密码解密时缓存 KEK，之后使用相同密码的访问跳过 Argon2 派生；
+    /// 关闭（默认）时每次访问都重新派生，KEK 在内存中的暴露时间最短。关闭时立即清除已缓存的 KEK。
+    pub fn with_kek_caching(mut self, enabled: bool) -> Self {
+        self.kek_caching = enabled;
+        if !enabled {
+            self.kek_cache.clear();
+        }
+        self
+    }
+
+    /// 是否启用了 KEK 缓存
+    pub fn kek_caching(&self) -> bool {
+        self.kek_caching
+    }
+
+    /// 清零并丢弃缓存的 KEK，之后的访问需要重新派生
+    pub fn lock(&self) {
+        self.kek_cache.clear();
+    }
+
+    /// 校验密码是否正确，不解密任何密钥数据
+    ///
+    /// 仅执行 Argon2 派生并以常量时间比较存储的校验值。
+    /// 对于早期版本创建的、没有校验值的容器，退回为试解密，解密结果会立即清零丢弃。
+    pub fn verify_password(&self, password: &SecretString) -> Result<bool, Error> {
+        let Some(verifier) = &self.verifier else {
+            return match self.decrypt_key(password) {
+                Ok(key) => {
+                    drop(Zeroizing::new(key));
+                    Ok(true)
+                }
+                Err(Error::KeyStorage(_)) => Ok(false),
+                Err(e) => Err(e),
+            };
+        };
+
+        let derived_key = self.derive_kek(password, None)?;
+        let expected = Self::compute_verifier(&derived_key);
+        Ok(constant_time_eq(verifier.as_bytes(), expected.as_bytes()))
+    }
+
+    /// 导出容器中私钥对应的公钥
+    ///
+    /// 容器保存的须是 `T::export_private_key` 的输出。私钥只在本调用内临时解密、导入并推导公钥，
+    /// 解密出的字节与导入的私钥在返回前即被清零丢弃，返回值为 `T::export_public_key` 格式的公钥，
+    /// 可以交给加密方而无需在别处保留私钥明文。
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    pub fn export_public<T: AsymmetricCryptographicSystem>(&self, password: &SecretString) -> Result<String, Error>
+    where
+        Error: From<T::Error>,
+    {
+        let key_data = Zeroizing::new(self.decrypt_key(password)?);
+        let encoded = std::str::from_utf8(&key_data)
+            .map_err(|_| Error::Key(format!("容器 {} 中保存的不是文本格式的私钥", self.algorithm_id)))?;
+        let private_key = T::import_private_key(encoded)?;
+        let public_key = T::public_key_from_private(&private_key);
+        drop(private_key);
+        Ok(T::export_public_key(&public_key?)?)
+    }
+
+    /// 派生 KEK 所用 KDF 的描述，如 `Argon2id m=19456,t=2`
+    #[cfg(any(feature = "traditional", feature = "post-quantum"))]
+    pub(crate) fn kdf_summary(&self) -> String {
+        match &self.kdf {
+            Some(kdf) => kdf.clone(),
+            None => format!("Argon2id m={},t={}", self.memory_cost, self.time_cost),
+        }
+    }
+
+    /// 以容器记录的 KDF 重新派生 KEK；`kdf` 必须与创建容器时使用的 KDF 一致
+    fn derive_kek(&self, password: &SecretString, kdf: Option<&dyn KeyDerivation>) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.check_kdf(kdf)?;
+        match kdf {
+            None => Self::derive_key(password, &self.parse_salt()?, self.memory_cost, self.time_cost),
+            Some(kdf) => Self::derive_custom_key(kdf, password, &self.parse_salt()?),
+        }
+    }
+
+    /// 确认提供的 KDF 与容器记录的一致
+    fn check_kdf(&self, kdf: Option<&dyn KeyDerivation>) -> Result<(), Error> {
+        match (self.kdf.as_deref(), kdf) {
+            (None, None) => Ok(()),
+            (Some(expected), Some(kdf)) if expected == kdf.algorithm() => Ok(()),
+            (expected, kdf) => Err(Error::KeyStorage(format!(
+                "容器使用 {} 派生密钥，但提供的是 {}",
+                expected.unwrap_or("Argon2id"),
+                kdf.map_or("Argon2id", |kdf| kdf.algorithm())
+            ))),
+        }
+    }
+
+    /// 以自定义 KDF 从密码与盐值派生 32 字节密钥
+    fn derive_custom_key(kdf: &dyn KeyDerivation, password: &SecretString, salt: &SaltString) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let mut salt_bytes = [0u8; 64];
+        let salt_bytes = salt.as_salt().decode_b64(&mut salt_bytes)
             .map_err(|e| Error::KeyStorage(format!("无效的盐值: {}", e)))?;
-        
-        // 使用存储的参数重新创建Argon2实例
+        let derived_key = kdf.derive(password.expose_secret().as_bytes(), salt_bytes, KEK_INFO, 32)?;
+        if derived_key.len() != 32 {
+            return Err(Error::KeyStorage(format!("{} 返回的密钥长度不是 32 字节", kdf.algorithm())));
+        }
+        Ok(derived_key)
+    }
+
+    /// 解析存储的盐值
+    fn parse_salt(&self) -> Result<SaltString, Error> {
+        SaltString::from_b64(&self.salt)
+            .map_err(|e| Error::KeyStorage(format!("无效的盐值: {}", e)))
+    }
+
+    /// 使用Argon2id从密码派生32字节密钥
+    fn derive_key(
+        password: &SecretString,
+        salt: &SaltString,
+        memory_cost: u32,
+        time_cost: u32,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        #[cfg(test)]
+        KEY_DERIVATIONS.with(|count| count.set(count.get() + 1));
+
         let mut params_builder = ParamsBuilder::new();
         params_builder
-            .m_cost(self.memory_cost)
-            .t_cost(self.time_cost)
+            .m_cost(memory_cost)
+            .t_cost(time_cost)
             .p_cost(1) // 并行度参数
             .output_len(32); // 输出长度为32字节（256位）
             
@@ -180,35 +392,114 @@ impl SecureKeyStorage for EncryptedKeyContainer {
         
         let password_bytes = password.expose_secret().as_bytes();
         let password_hash = argon2
-            .hash_password(password_bytes, &salt)
+            .hash_password(password_bytes, salt)
             .map_err(|e| Error::KeyStorage(format!("密码哈希失败: {}", e)))?;
         
         // 安全地获取哈希值
         let hash = password_hash.hash
             .ok_or_else(|| Error::KeyStorage("无法生成密码哈希".to_string()))?;
-        let derived_key = hash.as_bytes();
-        
-        // 创建AES-GCM解密器
-        let cipher = Aes256Gcm::new_from_slice(derived_key)
-            .map_err(|e| Error::KeyStorage(format!("创建解密器失败: {}", e)))?;
+        Ok(Zeroizing::new(hash.as_bytes().to_vec()))
+    }
+
+    /// 解密密钥数据；`kdf` 为空时使用 Argon2id
+    fn open(&self, password: &SecretString, kdf: Option<&dyn KeyDerivation>) -> Result<Vec<u8>, Error> {
+        #[cfg(test)]
+        KEY_DECRYPTIONS.with(|count| count.set(count.get() + 1));
+
+        // KDF 不匹配时无论缓存状态如何都拒绝；之后优先使用缓存的 KEK，否则以存储的盐值与参数重新派生
+        self.check_kdf(kdf)?;
+        let cached_key = if self.kek_caching { self.kek_cache.get(password) } else { None };
+        let derived_key = match cached_key {
+            Some(key) => key,
+            None => self.derive_kek(password, kdf)?,
+        };
         
         // 解码nonce和密文
         let nonce_bytes = from_base64(&self.nonce)?;
         let ciphertext = from_base64(&self.encrypted_data)?;
-        
-        // 解密密钥数据
-        let nonce = Nonce::from_slice(&nonce_bytes);
-        let decrypted = cipher
-            .decrypt(nonce, ciphertext.as_ref())
-            .map_err(|e| Error::KeyStorage(format!("解密密钥失败，密码可能不正确: {}", e)))?;
-        
+        let decrypted = open_key_data(self.protection, &derived_key, &nonce_bytes, &ciphertext)?;
+
+        // 只缓存经解密验证过的 KEK，错误密码不会覆盖缓存
+        if self.kek_caching {
+            self.kek_cache.store(password, derived_key);
+        }
         Ok(decrypted)
     }
+
+    /// 由派生密钥计算密码校验值
+    fn compute_verifier(derived_key: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(derived_key);
+        hasher.update(VERIFIER_LABEL);
+        to_base64(&hasher.finalize())
+    }
+}
+
+/// 以 KEK 加密密钥数据
+fn seal_key_data(protection: KeyProtection, kek: &[u8], nonce: &[u8; 12], key_data: &[u8]) -> Result<Vec<u8>, Error> {
+    match protection {
+        KeyProtection::Aes256Gcm => Aes256Gcm::new_from_slice(kek)
+            .map_err(|e| Error::KeyStorage(format!("创建加密器失败: {}", e)))?
+            .encrypt(Nonce::from_slice(nonce), key_data)
+            .map_err(|e| Error::KeyStorage(format!("加密密钥失败: {}", e))),
+        #[cfg(feature = "chacha")]
+        KeyProtection::ChaCha20Poly1305 => chacha20poly1305::ChaCha20Poly1305::new_from_slice(kek)
+            .map_err(|e| Error::KeyStorage(format!("创建加密器失败: {}", e)))?
+            .encrypt(chacha20poly1305::Nonce::from_slice(nonce), key_data)
+            .map_err(|e| Error::KeyStorage(format!("加密密钥失败: {}", e))),
+        #[cfg(not(feature = "chacha"))]
+        KeyProtection::ChaCha20Poly1305 => Err(chacha_unavailable()),
+    }
+}
+
+/// 以 KEK 解密密钥数据
+fn open_key_data(protection: KeyProtection, kek: &[u8], nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+    if nonce.len() != 12 {
+        return Err(Error::KeyStorage(format!("无效的nonce长度: {}", nonce.len())));
+    }
+    let decrypted = match protection {
+        KeyProtection::Aes256Gcm => Aes256Gcm::new_from_slice(kek)
+            .map_err(|e| Error::KeyStorage(format!("创建解密器失败: {}", e)))?
+            .decrypt(Nonce::from_slice(nonce), ciphertext),
+        #[cfg(feature = "chacha")]
+        KeyProtection::ChaCha20Poly1305 => chacha20poly1305::ChaCha20Poly1305::new_from_slice(kek)
+            .map_err(|e| Error::KeyStorage(format!("创建解密器失败: {}", e)))?
+            .decrypt(chacha20poly1305::Nonce::from_slice(nonce), ciphertext),
+        #[cfg(not(feature = "chacha"))]
+        KeyProtection::ChaCha20Poly1305 => return Err(chacha_unavailable()),
+    };
+    decrypted.map_err(|e| Error::KeyStorage(format!("解密密钥失败，密码可能不正确: {}", e)))
+}
+
+#[cfg(not(feature = "chacha"))]
+fn chacha_unavailable() -> Error {
+    Error::KeyStorage("ChaCha20-Poly1305 保护需要启用 chacha 特性".to_string())
+}
+
+impl SecureKeyStorage for EncryptedKeyContainer {
+    type Error = Error;
+    
+    fn encrypt_key<K: AsRef<[u8]>>(
+        password: &SecretString, 
+        key_data: K,
+        algorithm_id: &str
+    ) -> Result<Self, Self::Error> {
+        // 使用默认配置
+        Self::encrypt_key_with_config(
+            password,
+            key_data, 
+            algorithm_id,
+            &CryptoConfig::default()
+        )
+    }
+    
+    fn decrypt_key(&self, password: &SecretString) -> Result<Vec<u8>, Self::Error> {
+        self.open(password, None)
+    }
     
     fn algorithm_id(&self) -> &str {
         &self.algorithm_id
     }
-    
     fn created_at(&self) -> &str {
         &self.created_at
     }
@@ -279,6 +570,38 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    fn verify_password_does_not_decrypt_keys() {
+        let password = SecretString::new(Box::from("correct-password"));
+        let wrong_password = SecretString::new(Box::from("wrong-password"));
+        let container = EncryptedKeyContainer::encrypt_key(&password, b"secret-key", "test").unwrap();
+
+        let before = KEY_DECRYPTIONS.with(|count| count.get());
+        assert!(container.verify_password(&password).unwrap());
+        assert!(!container.verify_password(&wrong_password).unwrap());
+        assert_eq!(KEY_DECRYPTIONS.with(|count| count.get()), before);
+    }
+
+    #[test]
+    fn verify_password_survives_json_roundtrip() {
+        let password = SecretString::new(Box::from("correct-password"));
+        let container = EncryptedKeyContainer::encrypt_key(&password, b"secret-key", "test").unwrap();
+        let restored = EncryptedKeyContainer::from_json(&container.to_json().unwrap()).unwrap();
+        assert!(restored.verify_password(&password).unwrap());
+        assert!(!restored.verify_password(&SecretString::new(Box::from("nope"))).unwrap());
+    }
+
+    #[test]
+    fn verify_password_legacy_container_falls_back_to_decryption() {
+        let password = SecretString::new(Box::from("correct-password"));
+        let mut container = EncryptedKeyContainer::encrypt_key(&password, b"secret-key", "test").unwrap();
+        container.verifier = None;
+
+        assert!(!container.to_json().unwrap().contains("verifier"));
+        assert!(container.verify_password(&password).unwrap());
+        assert!(!container.verify_password(&SecretString::new(Box::from("wrong"))).unwrap());
+    }
+
     #[test]
     fn custom_config_works() {
         let password = SecretString::new(Box::from("secure-password"));
@@ -305,4 +628,262 @@ mod tests {
         let decrypted = container.decrypt_key(&password).unwrap();
         assert_eq!(&decrypted, key_data);
     }
+
+    #[test]
+    fn kek_caching_modes() {
+        let password = SecretString::new(Box::from("session-password"));
+        let wrong_password = SecretString::new(Box::from("wrong-password"));
+        let container = EncryptedKeyContainer::encrypt_key(&password, b"secret-key", "test").unwrap();
+        let derivations = || KEY_DERIVATIONS.with(|count| count.get());
+
+        // 默认不缓存：每次访问都重新派生
+        assert!(!container.kek_caching());
+        let before = derivations();
+        for _ in 0..2 {
+            assert_eq!(container.decrypt_key(&password).unwrap(), b"secret-key");
+        }
+        assert_eq!(derivations() - before, 2);
+        assert!(!container.kek_cache.is_cached());
+
+        // 缓存模式：只在首次访问时派生
+        let cached = container.clone().with_kek_caching(true);
+        let before = derivations();
+        for _ in 0..3 {
+            assert_eq!(cached.decrypt_key(&password).unwrap(), b"secret-key");
+        }
+        assert_eq!(derivations() - before, 1);
+        assert!(cached.kek_cache.is_cached());
+
+        // 错误密码不会命中缓存，也不会覆盖缓存
+        assert!(cached.decrypt_key(&wrong_password).is_err());
+        assert_eq!(derivations() - before, 2);
+        assert_eq!(cached.decrypt_key(&password).unwrap(), b"secret-key");
+        assert_eq!(derivations() - before, 2);
+
+        // 克隆与序列化都不携带缓存
+        assert!(!cached.clone().kek_cache.is_cached());
+        assert!(!EncryptedKeyContainer::from_json(&cached.to_json().unwrap()).unwrap().kek_cache.is_cached());
+    }
+
+    #[test]
+    fn kek_cache_cleared_on_lock() {
+        let password = SecretString::new(Box::from("session-password"));
+        let container = EncryptedKeyContainer::encrypt_key(&password, b"secret-key", "test").unwrap()
+            .with_kek_caching(true);
+        container.decrypt_key(&password).unwrap();
+        assert!(container.kek_cache.is_cached());
+
+        container.lock();
+        assert!(!container.kek_cache.is_cached());
+        let before = KEY_DERIVATIONS.with(|count| count.get());
+        assert_eq!(container.decrypt_key(&password).unwrap(), b"secret-key");
+        assert_eq!(KEY_DERIVATIONS.with(|count| count.get()) - before, 1);
+
+        // 关闭缓存同样会清除已缓存的 KEK
+        let container = container.with_kek_caching(false);
+        assert!(!container.kek_cache.is_cached());
+    }
+
+    /// 调用方自行实现的 KDF：以固定轮数迭代 SHA-256
+    struct IteratedSha256;
+
+    impl KeyDerivation for IteratedSha256 {
+        fn algorithm(&self) -> &str {
+            "iterated-sha256"
+        }
+
+        fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+            let mut digest = Sha256::new().chain_update(salt).chain_update(info).chain_update(ikm).finalize();
+            for _ in 0..1000 {
+                digest = Sha256::digest(digest);
+            }
+            Ok(Zeroizing::new(digest[..out_len].to_vec()))
+        }
+    }
+
+    #[test]
+    fn custom_kdf_container_roundtrip() {
+        use crate::common::key_derivation::{Pbkdf2Sha256, Scrypt};
+
+        let password = SecretString::new(Box::from("kdf-password"));
+        let container = EncryptedKeyContainer::new_with_kdf(&password, b"custom kdf key", "test", &IteratedSha256).unwrap();
+        let restored = EncryptedKeyContainer::from_json(&container.to_json().unwrap()).unwrap();
+        assert_eq!(restored.get_key_with_kdf(&password, &IteratedSha256).unwrap(), b"custom kdf key");
+        assert!(restored.get_key_with_kdf(&SecretString::new(Box::from("wrong")), &IteratedSha256).is_err());
+
+        // 必须使用创建容器时的 KDF
+        assert!(restored.get_key(&password).is_err());
+        assert!(restored.get_key_with_kdf(&password, &Pbkdf2Sha256 { iterations: 10 }).is_err());
+        let argon2_container = EncryptedKeyContainer::new(&password, b"argon2 key", "test").unwrap();
+        assert!(argon2_container.get_key_with_kdf(&password, &IteratedSha256).is_err());
+
+        let pbkdf2 = Pbkdf2Sha256 { iterations: 10 };
+        let container = EncryptedKeyContainer::new_with_kdf(&password, b"pbkdf2", "test", &pbkdf2).unwrap();
+        assert_eq!(container.get_key_with_kdf(&password, &pbkdf2).unwrap(), b"pbkdf2");
+        let scrypt = Scrypt { log_n: 4, r: 8, p: 1 };
+        let container = EncryptedKeyContainer::new_with_kdf(&password, b"scrypt", "test", &scrypt).unwrap();
+        assert_eq!(container.get_key_with_kdf(&password, &scrypt).unwrap(), b"scrypt");
+    }
+
+    #[test]
+    fn kdf_mismatch_rejected_even_with_cached_kek() {
+        use crate::common::key_derivation::Pbkdf2Sha256;
+
+        let password = SecretString::new(Box::from("kdf-password"));
+        let container = EncryptedKeyContainer::new_with_kdf(&password, b"custom kdf key", "test", &IteratedSha256).unwrap()
+            .with_kek_caching(true);
+        assert_eq!(container.get_key_with_kdf(&password, &IteratedSha256).unwrap(), b"custom kdf key");
+        assert!(container.kek_cache.is_cached());
+
+        // 缓存命中与否不影响 KDF 校验
+        assert!(matches!(container.get_key(&password), Err(Error::KeyStorage(_))));
+        assert!(matches!(container.get_key_with_kdf(&password, &Pbkdf2Sha256 { iterations: 10 }), Err(Error::KeyStorage(_))));
+
+        let argon2_container = EncryptedKeyContainer::new(&password, b"argon2 key", "test").unwrap().with_kek_caching(true);
+        assert_eq!(argon2_container.get_key(&password).unwrap(), b"argon2 key");
+        assert!(argon2_container.get_key_with_kdf(&password, &IteratedSha256).is_err());
+    }
+
+    #[test]
+    fn standalone_password_derivation_matches_container() {
+        use crate::symmetric::password::PasswordKdfParams;
+        use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+        use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+        let password = SecretString::new(Box::from("standalone-password"));
+        let config = CryptoConfig { argon2_memory_cost: 8192, argon2_time_cost: 1, ..CryptoConfig::default() };
+        let container = EncryptedKeyContainer::new_with_config(&password, b"container key", "test", &config).unwrap();
+
+        let salt = container.parse_salt().unwrap();
+        let mut salt_buf = [0u8; 64];
+        let salt_bytes = salt.as_salt().decode_b64(&mut salt_buf).unwrap();
+        let key = AesGcmKey::from_password(&password, salt_bytes, &PasswordKdfParams::from_config(&config)).unwrap();
+        let kek = EncryptedKeyContainer::derive_key(&password, &salt, config.argon2_memory_cost, config.argon2_time_cost).unwrap();
+        assert_eq!(key.as_bytes(), kek.as_slice());
+
+        // 独立派生的密钥可以直接打开容器中的密钥数据（`Nonce || 密文` 即 AES-GCM 密文格式）
+        let sealed = [from_base64(&container.nonce).unwrap(), from_base64(&container.encrypted_data).unwrap()].concat();
+        assert_eq!(AesGcmSystem::decrypt(&key, &to_base64(&sealed), None).unwrap(), b"container key");
+
+        // 参数或口令不同则得到不同的密钥
+        let other = PasswordKdfParams { time_cost: 2, ..PasswordKdfParams::from_config(&config) };
+        assert_ne!(AesGcmKey::from_password(&password, salt_bytes, &other).unwrap().as_bytes(), kek.as_slice());
+        let wrong = SecretString::new(Box::from("wrong"));
+        let params = PasswordKdfParams::from_config(&config);
+        assert_ne!(AesGcmKey::from_password(&wrong, salt_bytes, &params).unwrap().as_bytes(), kek.as_slice());
+        assert!(AesGcmKey::from_password(&password, b"short", &params).is_err());
+    }
+
+    #[cfg(feature = "chacha")]
+    #[test]
+    fn migrate_protection_from_aes_gcm_to_chacha() {
+        let password = SecretString::new(Box::from("migration-password"));
+        let fast = CryptoConfig { argon2_memory_cost: 8192, argon2_time_cost: 1, ..CryptoConfig::default() };
+        let mut containers: Vec<_> = (0..3u8)
+            .map(|i| EncryptedKeyContainer::new_with_config(&password, [i; 32], &format!("algo-{}", i), &fast).unwrap())
+            .collect();
+        let created_at: Vec<String> = containers.iter().map(|c| c.created_at().to_string()).collect();
+
+        let chacha = CryptoConfig { key_protection: KeyProtection::ChaCha20Poly1305, ..fast.clone() };
+        // 密码错误时迁移失败，容器保持不变
+        let before = containers[0].to_json().unwrap();
+        assert!(containers[0].migrate_protection(&SecretString::new(Box::from("wrong")), &chacha).is_err());
+        assert_eq!(containers[0].to_json().unwrap(), before);
+
+        for container in &mut containers {
+            assert_eq!(container.protection(), KeyProtection::Aes256Gcm);
+            container.migrate_protection(&password, &chacha).unwrap();
+            assert_eq!(container.protection(), KeyProtection::ChaCha20Poly1305);
+        }
+        for (i, container) in containers.iter().enumerate() {
+            let restored = EncryptedKeyContainer::from_json(&container.to_json().unwrap()).unwrap();
+            assert_eq!(restored.protection(), KeyProtection::ChaCha20Poly1305);
+            assert_eq!(restored.get_key(&password).unwrap(), [i as u8; 32]);
+            assert!(restored.verify_password(&password).unwrap());
+            assert_eq!(restored.algorithm_id(), format!("algo-{}", i));
+            assert_eq!(restored.created_at(), created_at[i]);
+        }
+
+        // 按 ChaCha 保护的密文无法再按 AES-GCM 解密
+        let mut json: serde_json::Value = serde_json::from_str(&containers[0].to_json().unwrap()).unwrap();
+        json["protection"] = serde_json::json!("Aes256Gcm");
+        let mislabeled = EncryptedKeyContainer::from_json(&json.to_string()).unwrap();
+        assert!(mislabeled.get_key(&password).is_err());
+
+        // 再迁移回 AES-GCM
+        containers[1].migrate_protection(&password, &fast).unwrap();
+        assert_eq!(containers[1].protection(), KeyProtection::Aes256Gcm);
+        assert_eq!(containers[1].get_key(&password).unwrap(), [1u8; 32]);
+    }
+
+    #[test]
+    fn auto_selected_protection_roundtrips() {
+        let password = SecretString::new(Box::from("auto-password"));
+        let fast = CryptoConfig { argon2_memory_cost: 8192, argon2_time_cost: 1, ..CryptoConfig::default() };
+        for aes_accelerated in [true, false] {
+            let protection = KeyProtection::select(aes_accelerated);
+            let expected = if aes_accelerated || cfg!(not(feature = "chacha")) {
+                KeyProtection::Aes256Gcm
+            } else {
+                KeyProtection::ChaCha20Poly1305
+            };
+            assert_eq!(protection, expected);
+            let config = CryptoConfig { key_protection: protection, ..fast.clone() };
+            let container = EncryptedKeyContainer::new_with_config(&password, b"auto-key", "auto", &config).unwrap();
+            assert_eq!(container.protection(), protection);
+            let restored = EncryptedKeyContainer::from_json(&container.to_json().unwrap()).unwrap();
+            assert_eq!(restored.get_key(&password).unwrap(), b"auto-key");
+        }
+        assert_eq!(KeyProtection::auto(), KeyProtection::select(crate::cpu::has_aes_acceleration()));
+    }
+
+    #[test]
+    fn legacy_container_without_protection_field_uses_aes_gcm() {
+        let password = SecretString::new(Box::from("legacy"));
+        let container = EncryptedKeyContainer::new(&password, b"legacy key", "test").unwrap();
+        let mut json: serde_json::Value = serde_json::from_str(&container.to_json().unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("protection");
+        let legacy = EncryptedKeyContainer::from_json(&json.to_string()).unwrap();
+        assert_eq!(legacy.protection(), KeyProtection::Aes256Gcm);
+        assert_eq!(legacy.get_key(&password).unwrap(), b"legacy key");
+    }
+
+    #[test]
+    #[cfg(feature = "post-quantum")]
+    fn export_public_encrypts_for_stored_private_key() {
+        use crate::asymmetric::systems::post_quantum::kyber::KyberCryptoSystem;
+
+        let password = SecretString::new(Box::from("export-password"));
+        let fast = CryptoConfig { argon2_memory_cost: 8192, argon2_time_cost: 1, ..CryptoConfig::default() };
+        let (_, private_key) = KyberCryptoSystem::generate_keypair(&fast).unwrap();
+        let exported_private = KyberCryptoSystem::export_private_key(&private_key).unwrap();
+        let container = EncryptedKeyContainer::new_with_config(&password, exported_private.as_bytes(), "kyber", &fast).unwrap();
+
+        let public_key = KyberCryptoSystem::import_public_key(&container.export_public::<KyberCryptoSystem>(&password).unwrap()).unwrap();
+        let ciphertext = KyberCryptoSystem::encrypt(&public_key, b"for the stored key", None).unwrap().to_string();
+        let stored = KyberCryptoSystem::import_private_key(std::str::from_utf8(&container.get_key(&password).unwrap()).unwrap()).unwrap();
+        assert_eq!(KyberCryptoSystem::decrypt(&stored, &ciphertext, None).unwrap(), b"for the stored key");
+
+        let wrong = SecretString::new(Box::from("wrong-password"));
+        assert!(matches!(container.export_public::<KyberCryptoSystem>(&wrong), Err(Error::KeyStorage(_))));
+        let not_a_key = EncryptedKeyContainer::new_with_config(&password, [0xffu8, 0xfe], "raw", &fast).unwrap();
+        assert!(not_a_key.export_public::<KyberCryptoSystem>(&password).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "traditional")]
+    fn export_public_derives_rsa_public_key() {
+        use crate::asymmetric::systems::traditional::rsa::RsaCryptoSystem;
+
+        let password = SecretString::new(Box::from("export-password"));
+        let fast = CryptoConfig { argon2_memory_cost: 8192, argon2_time_cost: 1, ..CryptoConfig::default() };
+        let (public_key, private_key) = RsaCryptoSystem::generate_keypair(&fast).unwrap();
+        let exported_private = RsaCryptoSystem::export_private_key(&private_key).unwrap();
+        let container = EncryptedKeyContainer::new_with_config(&password, exported_private.as_bytes(), "rsa", &fast).unwrap();
+
+        let exported = container.export_public::<RsaCryptoSystem>(&password).unwrap();
+        assert_eq!(exported, RsaCryptoSystem::export_public_key(&public_key).unwrap());
+        let ciphertext = RsaCryptoSystem::encrypt(&RsaCryptoSystem::import_public_key(&exported).unwrap(), b"rsa", None).unwrap().to_string();
+        assert_eq!(RsaCryptoSystem::decrypt(&private_key, &ciphertext, None).unwrap(), b"rsa");
+    }
 } 
\ No newline at end of file
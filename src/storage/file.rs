@@ -386,6 +386,36 @@ impl KeyStorage for KeyFileStorage {
         
         Ok(())
     }
+
+    /// 先以零覆写数据文件与元数据文件并同步到磁盘，再删除
+    ///
+    /// 覆写只作用于文件当前占用的数据块：写时复制或日志型文件系统、SSD 的磨损均衡以及快照仍可能保留旧数据，
+    /// 这类环境下应结合全盘加密使用。
+    fn purge_key(&self, name: &str) -> Result<(), Error> {
+        for path in [self.get_data_path(name), self.get_metadata_path(name)] {
+            if path.exists() {
+                overwrite_with_zeros(&path)?;
+            }
+        }
+        self.delete_key(name)
+    }
+}
+
+/// 以零覆写文件的全部内容并同步到磁盘
+fn overwrite_with_zeros(path: &Path) -> Result<(), Error> {
+    let io_error = |e: io::Error| Error::Io(io::Error::new(
+        e.kind(),
+        format!("覆写密钥文件失败 {}: {}", path.display(), e)
+    ));
+    let mut file = fs::OpenOptions::new().write(true).open(path).map_err(io_error)?;
+    let mut remaining = file.metadata().map_err(io_error)?.len();
+    let zeros = [0u8; 4096];
+    while remaining > 0 {
+        let len = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..len]).map_err(io_error)?;
+        remaining -= len as u64;
+    }
+    file.sync_all().map_err(io_error)
 }
 
 #[cfg(test)]
@@ -500,4 +530,88 @@ mod tests {
         let cached = storage.metadata_cache.get("test-key").unwrap();
         assert_eq!(cached.value().id, metadata.id);
     }
+
+    #[test]
+    fn purge_by_fingerprint_overwrites_and_removes_key() {
+        use crate::common::utils::key_fingerprint;
+        use crate::rotation::stored_key_fingerprint;
+
+        let temp_dir = tempdir().unwrap();
+        let storage = KeyFileStorage::new(temp_dir.path()).unwrap();
+        let metadata = KeyMetadata {
+            id: "test-id".to_string(),
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            expires_at: None,
+            usage_count: 0,
+            status: KeyStatus::Active,
+            version: 1,
+            algorithm: "TestAlgo".to_string(),
+        };
+        let pair = br#"{"public_key":"PUBLIC","private_key":"PRIVATE"}"#;
+        storage.save_key("pair-key", &metadata, pair).unwrap();
+        storage.save_key("pair-key-copy", &metadata, pair).unwrap();
+        storage.save_key("other-key", &metadata, b"c2VjcmV0").unwrap();
+
+        let fingerprint = key_fingerprint("PUBLIC");
+        assert_eq!(stored_key_fingerprint(pair), Some(fingerprint.clone()));
+        assert!(storage.purge_by_fingerprint(&fingerprint).unwrap().found());
+
+        // 所有副本都被删除，之后的读取失败，其余密钥不受影响
+        for name in ["pair-key", "pair-key-copy"] {
+            assert!(!storage.key_exists(name));
+            assert!(storage.load_key(name).is_err());
+            assert!(!storage.get_data_path(name).exists());
+            assert!(!storage.get_metadata_path(name).exists());
+        }
+        assert_eq!(storage.load_key("other-key").unwrap().1, b"c2VjcmV0");
+        assert!(!storage.purge_by_fingerprint(&fingerprint).unwrap().found());
+
+        // 对称密钥按导出字符串计算指纹
+        assert!(storage.purge_by_fingerprint(&key_fingerprint("c2VjcmV0")).unwrap().found());
+        assert!(storage.list_keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn purge_by_fingerprint_continues_past_corrupt_entries() {
+        use crate::common::utils::key_fingerprint;
+
+        let temp_dir = tempdir().unwrap();
+        let storage = KeyFileStorage::new(temp_dir.path()).unwrap();
+        let metadata = KeyMetadata {
+            id: "test-id".to_string(),
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            expires_at: None,
+            usage_count: 0,
+            status: KeyStatus::Active,
+            version: 1,
+            algorithm: "TestAlgo".to_string(),
+        };
+        let pair = br#"{"public_key":"PUBLIC","private_key":"PRIVATE"}"#;
+        for name in ["a-corrupt", "b-pair", "c-pair-copy"] {
+            storage.save_key(name, &metadata, pair).unwrap();
+        }
+        std::fs::write(storage.get_metadata_path("a-corrupt"), b"not json").unwrap();
+        storage.metadata_cache.clear();
+
+        // 损坏的条目不会阻止擦除其余匹配的副本
+        let report = storage.purge_by_fingerprint(&key_fingerprint("PUBLIC")).unwrap();
+        assert_eq!(report.purged, ["b-pair", "c-pair-copy"]);
+        assert!(report.found() && !report.is_complete());
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "a-corrupt");
+        assert!(!storage.key_exists("b-pair") && !storage.key_exists("c-pair-copy"));
+    }
+
+    #[test]
+    fn overwrite_with_zeros_clears_file_contents() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("secret.data");
+        let secret = vec![0xa5u8; 10_000];
+        fs::write(&path, &secret).unwrap();
+
+        overwrite_with_zeros(&path).unwrap();
+        let contents = fs::read(&path).unwrap();
+        assert_eq!(contents.len(), secret.len());
+        assert!(contents.iter().all(|&byte| byte == 0));
+    }
 } 
\ No newline at end of file
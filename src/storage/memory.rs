@@ -0,0 +1,300 @@
+//! 进程内密钥存储
+//!
+//! 密钥数据保存在 `Zeroizing` 缓冲区中，删除、覆盖或存储被释放时会擦除内存。
+//! 启用 `aes-gcm-feature` 时，每条密钥还以进程内随机生成的 KEK（密钥加密密钥）经 AES-256-GCM 包裹后存放，
+//! 密钥名称作为附加认证数据；KEK 可通过 [`MemoryKeyStorage::rotate_kek`] 在不停止服务的情况下更换。
+//! 适用于测试、短生命周期的服务进程，或作为迁移到其他后端之前的中转。
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use zeroize::Zeroizing;
+
+use crate::common::errors::Error;
+use crate::common::traits::KeyMetadata;
+use crate::rotation::KeyStorage;
+#[cfg(feature = "aes-gcm-feature")]
+use crate::common::utils::CryptoConfig;
+#[cfg(feature = "aes-gcm-feature")]
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+#[cfg(feature = "aes-gcm-feature")]
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 存储的一条密钥：元数据与（包裹后的）密钥数据
+type StoredKey = (KeyMetadata, Zeroizing<Vec<u8>>);
+
+/// 受同一把锁保护的存储状态，KEK 与其包裹的密钥总是一起更新
+struct Inner {
+    keys: HashMap<String, StoredKey>,
+    #[cfg(feature = "aes-gcm-feature")]
+    kek: AesGcmKey,
+}
+
+impl Inner {
+    /// 包裹一条待存储的密钥
+    #[cfg(feature = "aes-gcm-feature")]
+    fn seal(&self, name: &str, key_data: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        wrap_key(&self.kek, name, key_data)
+    }
+
+    #[cfg(not(feature = "aes-gcm-feature"))]
+    fn seal(&self, _name: &str, key_data: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        Ok(Zeroizing::new(key_data.to_vec()))
+    }
+
+    /// 取出一条已存储的密钥
+    #[cfg(feature = "aes-gcm-feature")]
+    fn open(&self, name: &str, stored: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        unwrap_key(&self.kek, name, stored)
+    }
+
+    #[cfg(not(feature = "aes-gcm-feature"))]
+    fn open(&self, _name: &str, stored: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        Ok(Zeroizing::new(stored.to_vec()))
+    }
+}
+
+/// 以 KEK 包裹密钥，密钥名称作为附加认证数据，防止存储条目被互换
+#[cfg(feature = "aes-gcm-feature")]
+fn wrap_key(kek: &AesGcmKey, name: &str, key_data: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let wrapped = AesGcmSystem::encrypt(kek, key_data, Some(name.as_bytes()))?;
+    Ok(Zeroizing::new(wrapped.as_ref().to_vec()))
+}
+
+#[cfg(feature = "aes-gcm-feature")]
+fn unwrap_key(kek: &AesGcmKey, name: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let mut key_data = Zeroizing::new(Vec::new());
+    AesGcmSystem::decrypt_from_bytes(kek, wrapped, Some(name.as_bytes()), &CryptoConfig::default(), &mut key_data)
+        .map_err(|_| Error::KeyStorage(format!("无法解开密钥: {}", name)))?;
+    Ok(key_data)
+}
+
+/// 内存密钥存储
+pub struct MemoryKeyStorage {
+    inner: RwLock<Inner>,
+}
+
+impl MemoryKeyStorage {
+    /// 创建空的内存存储
+    ///
+    /// 启用 `aes-gcm-feature` 时会随机生成 KEK，系统随机数生成器不可用时返回 `Error::RngFailure`。
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self {
+            inner: RwLock::new(Inner {
+                keys: HashMap::new(),
+                #[cfg(feature = "aes-gcm-feature")]
+                kek: AesGcmSystem::generate_key(&CryptoConfig::default())?,
+            }),
+        })
+    }
+
+    /// 生成新的 KEK 并以其重新包裹所有已存储的密钥
+    ///
+    /// 整个过程持有写锁：并发的读取要么在轮换前完成，要么在轮换后看到全部以新 KEK 包裹的密钥，
+    /// 不会观察到新旧混合的状态。任一密钥解包失败时存储保持原样；成功后旧 KEK 被清零丢弃。
+    #[cfg(feature = "aes-gcm-feature")]
+    pub fn rotate_kek(&self) -> Result<(), Error> {
+        let mut inner = self.inner.write().map_err(|_| Self::lock_error())?;
+        let new_kek = AesGcmSystem::generate_key(&CryptoConfig::default())?;
+        let mut rewrapped = HashMap::with_capacity(inner.keys.len());
+        for (name, (metadata, wrapped)) in &inner.keys {
+            let key_data = unwrap_key(&inner.kek, name, wrapped)?;
+            rewrapped.insert(name.clone(), (metadata.clone(), wrap_key(&new_kek, name, &key_data)?));
+        }
+        inner.keys = rewrapped;
+        // 旧 KEK 在此被替换并随丢弃清零
+        inner.kek = new_kek;
+        Ok(())
+    }
+
+    fn lock_error() -> Error {
+        Error::KeyStorage("内存密钥存储的锁已损坏".to_string())
+    }
+}
+
+impl std::fmt::Debug for MemoryKeyStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.inner.read().map(|inner| inner.keys.len()).unwrap_or_default();
+        f.debug_struct("MemoryKeyStorage").field("keys", &count).finish()
+    }
+}
+
+impl KeyStorage for MemoryKeyStorage {
+    fn save_key(&self, name: &str, metadata: &KeyMetadata, key_data: &[u8]) -> Result<(), Error> {
+        let mut inner = self.inner.write().map_err(|_| Self::lock_error())?;
+        let stored = inner.seal(name, key_data)?;
+        inner.keys.insert(name.to_string(), (metadata.clone(), stored));
+        Ok(())
+    }
+
+    fn load_key(&self, name: &str) -> Result<(KeyMetadata, Vec<u8>), Error> {
+        let inner = self.inner.read().map_err(|_| Self::lock_error())?;
+        let (metadata, stored) = inner.keys.get(name)
+            .ok_or_else(|| Error::KeyStorage(format!("未找到密钥: {}", name)))?;
+        let key_data = inner.open(name, stored)?;
+        Ok((metadata.clone(), key_data.to_vec()))
+    }
+
+    fn key_exists(&self, name: &str) -> bool {
+        self.inner.read().is_ok_and(|inner| inner.keys.contains_key(name))
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, Error> {
+        let inner = self.inner.read().map_err(|_| Self::lock_error())?;
+        Ok(inner.keys.keys().cloned().collect())
+    }
+
+    fn delete_key(&self, name: &str) -> Result<(), Error> {
+        let mut inner = self.inner.write().map_err(|_| Self::lock_error())?;
+        inner.keys.remove(name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::traits::KeyStatus;
+
+    fn metadata(id: &str) -> KeyMetadata {
+        KeyMetadata {
+            id: id.to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            expires_at: None,
+            usage_count: 3,
+            status: KeyStatus::Active,
+            version: 2,
+            algorithm: "AES-256-GCM".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_memory_storage_operations() {
+        let storage = MemoryKeyStorage::new().unwrap();
+        storage.save_key("a", &metadata("a"), b"key-a").unwrap();
+        assert!(storage.key_exists("a"));
+        assert_eq!(storage.load_key("a").unwrap().1, b"key-a");
+        assert_eq!(storage.list_keys().unwrap(), vec!["a".to_string()]);
+
+        storage.delete_key("a").unwrap();
+        assert!(!storage.key_exists("a"));
+        assert!(matches!(storage.load_key("a"), Err(Error::KeyStorage(_))));
+    }
+
+    #[cfg(feature = "aes-gcm-feature")]
+    #[test]
+    fn test_migrate_between_memory_and_file_backends() {
+        use crate::common::utils::{from_base64, to_base64, CryptoConfig};
+        use crate::storage::file::KeyFileStorage;
+        use crate::symmetric::systems::aes_gcm::AesGcmSystem;
+        use crate::symmetric::traits::SymmetricCryptographicSystem;
+        use tempfile::tempdir;
+
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let ciphertext = AesGcmSystem::encrypt(&key, b"migrated secret", None).unwrap().to_string();
+        let raw = Zeroizing::new(from_base64(&AesGcmSystem::export_key(&key).unwrap()).unwrap());
+
+        let memory = MemoryKeyStorage::new().unwrap();
+        memory.save_key("data-key", &metadata("data-key"), &raw).unwrap();
+
+        // 内存 -> 文件
+        let dir = tempdir().unwrap();
+        let file = KeyFileStorage::new(dir.path()).unwrap();
+        memory.migrate_to(&file, "data-key").unwrap();
+        let (migrated_metadata, migrated) = file.load_key("data-key").unwrap();
+        assert_eq!((migrated_metadata.version, migrated_metadata.usage_count), (2, 3));
+        let migrated = AesGcmSystem::import_key(&to_base64(&migrated)).unwrap();
+        assert_eq!(AesGcmSystem::decrypt(&migrated, &ciphertext, None).unwrap(), b"migrated secret");
+        assert!(memory.key_exists("data-key"));
+
+        // 文件 -> 新的内存存储
+        let restored = MemoryKeyStorage::new().unwrap();
+        file.migrate_to(&restored, "data-key").unwrap();
+        assert_eq!(restored.load_key("data-key").unwrap().1, raw.as_slice());
+
+        assert!(memory.migrate_to(&restored, "missing").is_err());
+        assert!(!restored.key_exists("missing"));
+    }
+
+    #[cfg(feature = "aes-gcm-feature")]
+    #[test]
+    fn test_rotate_kek_rewraps_all_keys() {
+        let storage = MemoryKeyStorage::new().unwrap();
+        for i in 0..8 {
+            storage.save_key(&format!("key-{}", i), &metadata("k"), format!("secret-{}", i).as_bytes()).unwrap();
+        }
+        let wrapped_before = storage.inner.read().unwrap().keys["key-0"].1.to_vec();
+        // 存储的是包裹后的数据而非明文
+        assert!(!wrapped_before.windows(8).any(|w| w == b"secret-0"));
+
+        storage.rotate_kek().unwrap();
+        assert_ne!(storage.inner.read().unwrap().keys["key-0"].1.to_vec(), wrapped_before);
+        for i in 0..8 {
+            let (loaded_metadata, data) = storage.load_key(&format!("key-{}", i)).unwrap();
+            assert_eq!(data, format!("secret-{}", i).as_bytes());
+            assert_eq!(loaded_metadata.usage_count, 3);
+        }
+
+        // 轮换后新保存的密钥同样可用；条目被互换时因名称绑定而无法解开
+        storage.save_key("after", &metadata("after"), b"after-rotation").unwrap();
+        assert_eq!(storage.load_key("after").unwrap().1, b"after-rotation");
+        {
+            let mut inner = storage.inner.write().unwrap();
+            let swapped = inner.keys["key-1"].clone();
+            inner.keys.insert("key-2".to_string(), swapped);
+        }
+        assert!(matches!(storage.load_key("key-2"), Err(Error::KeyStorage(_))));
+    }
+
+    #[cfg(feature = "aes-gcm-feature")]
+    #[test]
+    fn test_concurrent_reads_during_kek_rotation() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let storage = Arc::new(MemoryKeyStorage::new().unwrap());
+        for i in 0..16 {
+            storage.save_key(&format!("key-{}", i), &metadata("k"), format!("secret-{}", i).as_bytes()).unwrap();
+        }
+        let done = Arc::new(AtomicBool::new(false));
+        let readers: Vec<_> = (0..4).map(|_| {
+            let storage = Arc::clone(&storage);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(Ordering::Relaxed) || reads == 0 {
+                    for i in 0..16 {
+                        let (_, data) = storage.load_key(&format!("key-{}", i)).unwrap();
+                        assert_eq!(data, format!("secret-{}", i).as_bytes());
+                        reads += 1;
+                    }
+                }
+                reads
+            })
+        }).collect();
+
+        for _ in 0..50 {
+            storage.rotate_kek().unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn test_purge_by_fingerprint() {
+        use crate::common::utils::key_fingerprint;
+
+        let storage = MemoryKeyStorage::new().unwrap();
+        storage.save_key("pair", &metadata("a"), br#"{"public_key":"PUBLIC","private_key":"PRIVATE"}"#).unwrap();
+        storage.save_key("other", &metadata("b"), b"c2VjcmV0").unwrap();
+
+        assert!(storage.purge_by_fingerprint(&key_fingerprint("PUBLIC")).unwrap().found());
+        assert!(!storage.key_exists("pair"));
+        assert!(storage.load_key("pair").is_err());
+        assert!(storage.key_exists("other"));
+        assert!(!storage.purge_by_fingerprint(&key_fingerprint("PUBLIC")).unwrap().found());
+        assert!(!storage.purge_by_fingerprint("0000000000000000").unwrap().found());
+    }
+}
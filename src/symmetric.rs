@@ -1,7 +1,28 @@
 //! 对称加密核心模块
 
+#[cfg(all(feature = "std", feature = "aes-gcm-feature"))]
+pub mod channel;
+#[cfg(feature = "std")]
 pub mod engines;
+#[cfg(feature = "aes-gcm-feature")]
+pub mod kdf;
+#[cfg(feature = "std")]
+pub mod chunking;
+#[cfg(feature = "aes-gcm-feature")]
+pub mod codec;
+#[cfg(feature = "chacha")]
+pub mod poly1305;
+#[cfg(feature = "secure-storage")]
+pub mod password;
+#[cfg(feature = "std")]
 pub mod primitives;
+#[cfg(feature = "aes-gcm-feature")]
+pub mod ratchet;
+#[cfg(feature = "std")]
 pub mod rotation;
+#[cfg(all(feature = "std", feature = "aes-gcm-feature"))]
+pub mod seal;
+#[cfg(feature = "aes-gcm-feature")]
+pub mod split;
 pub mod systems;
 pub mod traits;
\ No newline at end of file
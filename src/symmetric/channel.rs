@@ -0,0 +1,590 @@
+//! 基于共享密钥的双向加密信道
+//!
+//! 通信双方持有同一个 32 字节共享密钥，并各自以不同的角色创建 [`SecureChannel`]。
+//! 每个方向维护独立的序列号：Nonce 由方向标识与序列号组成，两个方向永远不会复用 Nonce；
+//! 方向与序列号同时作为附加认证数据，因此重放的旧消息、乱序消息以及被反射回发送方的消息都会被拒绝。
+//! 序列号在回绕前即判定为耗尽并返回 `Error::NonceExhausted`，双方须通过 [`SecureChannel::rekey`] 换用新密钥。
+//!
+//! 收发业务消息前，双方可以先交换一次握手消息（[`SecureChannel::begin_handshake`] 与 [`SecureChannel::negotiate`]），
+//! 从各自支持的算法与压缩方式中商定 [`SessionParams`]。握手消息同样以共享密钥认证，并使用独立的附加认证数据标签，
+//! 中间人无法篡改对方声明的能力来诱导降级，也无法把握手消息当作业务消息投递。
+//! 本端只能声明信道实际实现的算法（见 [`CHANNEL_ALGORITHMS`]）与已注册的压缩方式（见 [`ChannelCompression`]），
+//! 协商成功后的业务消息即以商定的算法加密，并在加密前压缩、解密后解压。
+//! 握手消息始终使用 AES-256-GCM；各算法共用同一密钥，但序列号贯穿握手与业务消息，同一密钥下 Nonce 不会重复。
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::errors::Error;
+use crate::common::utils::{constant_time_eq, from_base64, Base64String};
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmNonce, AesGcmSystem};
+#[cfg(feature = "chacha")]
+use crate::symmetric::systems::chacha20_poly1305::{ChaCha20Poly1305Key, ChaCha20Poly1305System};
+use crate::symmetric::systems::SymmetricAlgorithm;
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 信道消息附加认证数据的域分隔标签
+const CHANNEL_AAD_LABEL: &[u8] = b"seal-kit-channel-v1";
+
+/// 握手消息附加认证数据的域分隔标签
+const HANDSHAKE_AAD_LABEL: &[u8] = b"seal-kit-channel-handshake-v1";
+
+/// AES-256-GCM，握手消息与未协商时的业务消息均使用该算法
+pub const CHANNEL_ALGORITHM_AES_256_GCM: &str = "AES-256-GCM";
+
+/// ChaCha20-Poly1305，启用 `chacha` 特性时可供协商
+#[cfg(feature = "chacha")]
+pub const CHANNEL_ALGORITHM_CHACHA20_POLY1305: &str = "ChaCha20-Poly1305";
+
+/// 信道实现的全部算法，[`SecureChannel::begin_handshake`] 只接受其中的名称
+pub const CHANNEL_ALGORITHMS: &[&str] = &[
+    CHANNEL_ALGORITHM_AES_256_GCM,
+    #[cfg(feature = "chacha")]
+    CHANNEL_ALGORITHM_CHACHA20_POLY1305,
+];
+
+/// 信道可插拔的压缩方式
+///
+/// 以 [`SecureChannel::with_compression`] 注册后，其 [`name`](Self::name) 才能在握手中声明；
+/// 商定后信道在加密前调用 [`compress`](Self::compress)，在认证解密后调用 [`decompress`](Self::decompress)。
+pub trait ChannelCompression: Send + Sync {
+    /// 握手中声明的名称，双方须对同一实现使用相同的名称
+    fn name(&self) -> &str;
+    /// 压缩一条业务消息
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>, Error>;
+    /// 还原 [`compress`](Self::compress) 的输出
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// 本端在握手中声明的能力，按偏好从高到低排列
+///
+/// 算法只能取 [`CHANNEL_ALGORITHMS`] 中的名称，压缩方式只能取本端已注册的 [`ChannelCompression`] 的名称；
+/// 商定的算法与压缩方式即信道此后收发业务消息所用的方式。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelCapabilities {
+    /// 支持的对称算法
+    pub algorithms: Vec<String>,
+    /// 支持的压缩方式；为空表示不压缩
+    pub compression: Vec<String>,
+}
+
+impl Default for ChannelCapabilities {
+    fn default() -> Self {
+        Self { algorithms: CHANNEL_ALGORITHMS.iter().map(|name| name.to_string()).collect(), compression: Vec::new() }
+    }
+}
+
+impl ChannelCapabilities {
+    /// 以给定的算法与压缩方式名称创建能力声明
+    pub fn new<A, C>(algorithms: A, compression: C) -> Self
+    where
+        A: IntoIterator,
+        A::Item: Into<String>,
+        C: IntoIterator,
+        C::Item: Into<String>,
+    {
+        Self {
+            algorithms: algorithms.into_iter().map(Into::into).collect(),
+            compression: compression.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// 按发起方的偏好顺序选出双方都支持的算法与压缩方式
+    ///
+    /// 没有共同算法时返回 `Error::Operation`；没有共同压缩方式时不压缩。
+    pub fn select(initiator: &Self, responder: &Self) -> Result<SessionParams, Error> {
+        let common = |offered: &[String], supported: &[String]| {
+            offered.iter().find(|name| supported.contains(name)).cloned()
+        };
+        let algorithm = common(&initiator.algorithms, &responder.algorithms).ok_or_else(|| {
+            Error::Operation(format!(
+                "双方没有共同支持的算法: 发起方 {:?}，响应方 {:?}",
+                initiator.algorithms, responder.algorithms
+            ))
+        })?;
+        Ok(SessionParams { algorithm, compression: common(&initiator.compression, &responder.compression) })
+    }
+}
+
+/// 握手商定的会话参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionParams {
+    /// 商定的算法，即信道收发消息使用的算法
+    pub algorithm: String,
+    /// 商定的压缩方式，`None` 表示不压缩
+    pub compression: Option<String>,
+}
+
+/// 信道中的角色，双方必须使用不同的角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelRole {
+    /// 发起方（如请求方）
+    Initiator,
+    /// 响应方
+    Responder,
+}
+
+impl ChannelRole {
+    /// 该角色发出的消息所属的方向标识
+    fn outbound_direction(self) -> u32 {
+        match self {
+            ChannelRole::Initiator => 0,
+            ChannelRole::Responder => 1,
+        }
+    }
+
+    /// 该角色接收的消息所属的方向标识
+    fn inbound_direction(self) -> u32 {
+        1 - self.outbound_direction()
+    }
+}
+
+/// 维护双向序列号的加密会话
+///
+/// 消息必须按发送顺序逐条打开；任何一条消息认证失败都不会推进接收序列号。
+pub struct SecureChannel {
+    key: AesGcmKey,
+    role: ChannelRole,
+    send_sequence: u64,
+    recv_sequence: u64,
+    capabilities: Option<ChannelCapabilities>,
+    session: Option<SessionParams>,
+    algorithm: SymmetricAlgorithm,
+    compressions: Vec<Arc<dyn ChannelCompression>>,
+    compression: Option<Arc<dyn ChannelCompression>>,
+}
+
+impl core::fmt::Debug for SecureChannel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecureChannel")
+            .field("role", &self.role)
+            .field("send_sequence", &self.send_sequence)
+            .field("recv_sequence", &self.recv_sequence)
+            .field("session", &self.session)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SecureChannel {
+    /// 使用共享密钥与本端角色创建信道
+    pub fn new(key: AesGcmKey, role: ChannelRole) -> Self {
+        Self {
+            key,
+            role,
+            send_sequence: 0,
+            recv_sequence: 0,
+            capabilities: None,
+            session: None,
+            algorithm: SymmetricAlgorithm::Aes256Gcm,
+            compressions: Vec::new(),
+            compression: None,
+        }
+    }
+
+    /// 注册一种压缩方式，之后可在握手能力中声明其名称；同名的压缩方式以后注册的为准
+    pub fn with_compression(mut self, compression: Arc<dyn ChannelCompression>) -> Self {
+        self.compressions.retain(|registered| registered.name() != compression.name());
+        self.compressions.push(compression);
+        self
+    }
+
+    /// 本端角色
+    pub fn role(&self) -> ChannelRole {
+        self.role
+    }
+
+    /// 下一条发出消息的序列号
+    pub fn send_sequence(&self) -> u64 {
+        self.send_sequence
+    }
+
+    /// 下一条期望接收消息的序列号
+    pub fn recv_sequence(&self) -> u64 {
+        self.recv_sequence
+    }
+
+    /// 握手商定的会话参数，尚未完成握手时为 `None`
+    pub fn session_params(&self) -> Option<&SessionParams> {
+        self.session.as_ref()
+    }
+
+    /// 生成发往对端的握手消息，声明本端能力
+    ///
+    /// 双方各自发出一条握手消息，再以收到的对端握手消息调用 [`Self::negotiate`]。
+    /// 声明的算法为空、含有信道未实现的算法或未注册的压缩方式时返回 `Error::Operation`，不发出任何消息。
+    pub fn begin_handshake(&mut self, local: &ChannelCapabilities) -> Result<Base64String, Error> {
+        if local.algorithms.is_empty() {
+            return Err(Error::Operation("握手能力至少需要声明一种算法".to_string()));
+        }
+        if let Some(unsupported) = local.algorithms.iter().find(|name| !CHANNEL_ALGORITHMS.contains(&name.as_str())) {
+            return Err(Error::Operation(format!(
+                "信道未实现算法 {}，可用的算法: {:?}", unsupported, CHANNEL_ALGORITHMS
+            )));
+        }
+        if let Some(unregistered) = local.compression.iter().find(|name| self.registered_compression(name).is_none()) {
+            return Err(Error::Operation(format!("信道未注册压缩方式 {}", unregistered)));
+        }
+        let encoded = serde_json::to_vec(local)
+            .map_err(|e| Error::Serialization(format!("序列化握手能力失败: {}", e)))?;
+        let message = self.seal(HANDSHAKE_AAD_LABEL, &encoded, SymmetricAlgorithm::Aes256Gcm)?;
+        self.capabilities = Some(local.clone());
+        Ok(message)
+    }
+
+    /// 认证对端的握手消息，并按发起方的偏好商定会话参数
+    ///
+    /// 双方以相同的规则从相同的两份能力中选择，因此得到一致的结果。本端只声明了已实现的算法与已注册的压缩方式，
+    /// 对端声明的其他名称不会被选中。成功后此后的业务消息改用商定的算法与压缩方式。
+    /// 握手消息被篡改时认证失败，没有共同算法时返回 `Error::Operation`；两种情况都不会记录会话参数。
+    pub fn negotiate(&mut self, peer_message: &str) -> Result<SessionParams, Error> {
+        let local = self.capabilities.clone()
+            .ok_or_else(|| Error::Operation("协商前必须先调用 begin_handshake 发出本端能力".to_string()))?;
+        let decoded = self.open(HANDSHAKE_AAD_LABEL, peer_message, SymmetricAlgorithm::Aes256Gcm)?;
+        let peer: ChannelCapabilities = serde_json::from_slice(&decoded)
+            .map_err(|e| Error::Format(format!("无效的握手消息: {}", e)))?;
+
+        let params = match self.role {
+            ChannelRole::Initiator => ChannelCapabilities::select(&local, &peer)?,
+            ChannelRole::Responder => ChannelCapabilities::select(&peer, &local)?,
+        };
+        self.algorithm = SymmetricAlgorithm::available().into_iter()
+            .find(|algorithm| algorithm.name() == params.algorithm)
+            .ok_or_else(|| Error::Operation(format!("信道未实现算法 {}", params.algorithm)))?;
+        self.compression = params.compression.as_deref().and_then(|name| self.registered_compression(name));
+        self.session = Some(params.clone());
+        Ok(params)
+    }
+
+    /// 换用新的共享密钥并将两个方向的序列号归零
+    ///
+    /// 双方必须在同一消息边界处以相同的新密钥调用；旧密钥下的 Nonce 不会在新密钥下构成复用。
+    /// 序列号归零后 Nonce 从头开始，因此新密钥与当前密钥相同时返回 `Error::Key` 并保持信道状态不变
+    /// （以常数时间比较）；调用方同样不得换回更早使用过的密钥。
+    pub fn rekey(&mut self, key: AesGcmKey) -> Result<(), Error> {
+        if constant_time_eq(key.as_bytes(), self.key.as_bytes()) {
+            return Err(Error::Key("新密钥与当前密钥相同，重置序列号将导致 Nonce 复用".to_string()));
+        }
+        self.key = key;
+        self.send_sequence = 0;
+        self.recv_sequence = 0;
+        Ok(())
+    }
+
+    /// 加密一条发往对端的消息，并推进发送序列号
+    ///
+    /// 握手商定了压缩方式时先压缩再加密。
+    pub fn seal_outbound(&mut self, message: &[u8]) -> Result<Base64String, Error> {
+        let compressed = self.compression.as_ref().map(|compression| compression.compress(message)).transpose()?;
+        self.seal(CHANNEL_AAD_LABEL, compressed.as_deref().unwrap_or(message), self.algorithm)
+    }
+
+    /// 解密一条来自对端的消息，仅在认证成功后推进接收序列号
+    ///
+    /// 握手商定了压缩方式时在认证解密后解压；解压失败时返回其错误，该消息的序列号已被消耗。
+    pub fn open_inbound(&mut self, message: &str) -> Result<Vec<u8>, Error> {
+        let plaintext = self.open(CHANNEL_AAD_LABEL, message, self.algorithm)?;
+        match &self.compression {
+            Some(compression) => compression.decompress(&plaintext),
+            None => Ok(plaintext),
+        }
+    }
+
+    fn registered_compression(&self, name: &str) -> Option<Arc<dyn ChannelCompression>> {
+        self.compressions.iter().find(|compression| compression.name() == name).cloned()
+    }
+
+    fn seal(&mut self, label: &[u8], message: &[u8], algorithm: SymmetricAlgorithm) -> Result<Base64String, Error> {
+        let sequence = self.send_sequence;
+        let next = sequence.checked_add(1).ok_or(Error::NonceExhausted)?;
+        let direction = self.role.outbound_direction();
+
+        let nonce = Self::nonce(direction, sequence)?;
+        let aad = Self::aad(label, direction, sequence);
+        let ciphertext = match algorithm {
+            SymmetricAlgorithm::Aes256Gcm => AesGcmSystem::encrypt_with_nonce(&self.key, &nonce, message, Some(&aad))?,
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => {
+                let key = ChaCha20Poly1305Key::from_slice(self.key.as_bytes())?;
+                ChaCha20Poly1305System::encrypt_with_nonce(&key, &nonce, message, Some(&aad))?
+            }
+        };
+        self.send_sequence = next;
+        Ok(ciphertext)
+    }
+
+    fn open(&mut self, label: &[u8], message: &str, algorithm: SymmetricAlgorithm) -> Result<Vec<u8>, Error> {
+        let sequence = self.recv_sequence;
+        let next = sequence.checked_add(1).ok_or(Error::NonceExhausted)?;
+        let direction = self.role.inbound_direction();
+
+        // 先比对 Nonce 以给出明确的错误；真正的保护来自附加认证数据
+        let decoded = from_base64(message)
+            .map_err(|e| Error::DecryptionFailed(format!("Base64 decoding failed: {}", e)))?;
+        let expected = Self::nonce(direction, sequence)?;
+        let (expected_direction, expected_sequence) = expected.as_bytes().split_at(4);
+        let nonce = decoded.get(..expected.as_bytes().len())
+            .ok_or_else(|| Error::DecryptionFailed("信道消息过短".to_string()))?;
+        if &nonce[..4] != expected_direction {
+            return Err(Error::DecryptionFailed("信道消息方向错误（疑似反射攻击）".to_string()));
+        }
+        if &nonce[4..] != expected_sequence {
+            return Err(Error::DecryptionFailed(format!("信道消息序列号不匹配，期望 {}（疑似重放或乱序）", sequence)));
+        }
+
+        let aad = Self::aad(label, direction, sequence);
+        let plaintext = match algorithm {
+            SymmetricAlgorithm::Aes256Gcm => AesGcmSystem::decrypt(&self.key, message, Some(&aad))?,
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => {
+                let key = ChaCha20Poly1305Key::from_slice(self.key.as_bytes())?;
+                ChaCha20Poly1305System::decrypt(&key, message, Some(&aad))?
+            }
+        };
+        self.recv_sequence = next;
+        Ok(plaintext)
+    }
+
+    /// Nonce 布局：`方向 (u32 BE) || 序列号 (u64 BE)`
+    fn nonce(direction: u32, sequence: u64) -> Result<AesGcmNonce, Error> {
+        let mut bytes = [0u8; 12];
+        bytes[..4].copy_from_slice(&direction.to_be_bytes());
+        bytes[4..].copy_from_slice(&sequence.to_be_bytes());
+        AesGcmNonce::from_slice(&bytes)
+    }
+
+    /// 附加认证数据：`标签 || 方向 || 序列号`
+    fn aad(label: &[u8], direction: u32, sequence: u64) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(label.len() + 12);
+        aad.extend_from_slice(label);
+        aad.extend_from_slice(&direction.to_be_bytes());
+        aad.extend_from_slice(&sequence.to_be_bytes());
+        aad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::utils::CryptoConfig;
+
+    fn channel_pair() -> (SecureChannel, SecureChannel) {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        (
+            SecureChannel::new(key.clone(), ChannelRole::Initiator),
+            SecureChannel::new(key, ChannelRole::Responder),
+        )
+    }
+
+    /// 在消息前加上自身名称的“压缩”，便于确认信道确实按商定的方式处理了消息
+    struct TaggingCompression(&'static str);
+
+    impl ChannelCompression for TaggingCompression {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn compress(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+            Ok([self.0.as_bytes(), data].concat())
+        }
+
+        fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+            data.strip_prefix(self.0.as_bytes())
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| Error::Format(format!("缺少 {} 标记", self.0)))
+        }
+    }
+
+    fn with_compressions(channel: SecureChannel) -> SecureChannel {
+        channel
+            .with_compression(Arc::new(TaggingCompression("zstd")))
+            .with_compression(Arc::new(TaggingCompression("deflate")))
+    }
+
+    #[test]
+    fn test_channel_request_response_exchange() {
+        let (mut client, mut server) = channel_pair();
+        for i in 0..5u8 {
+            let request = client.seal_outbound(&[b'q', i]).unwrap();
+            assert_eq!(server.open_inbound(&request.to_string()).unwrap(), vec![b'q', i]);
+            let response = server.seal_outbound(&[b'r', i]).unwrap();
+            assert_eq!(client.open_inbound(&response.to_string()).unwrap(), vec![b'r', i]);
+        }
+        assert_eq!((client.send_sequence(), client.recv_sequence()), (5, 5));
+        assert_eq!((server.send_sequence(), server.recv_sequence()), (5, 5));
+
+        // 相同明文在不同序列号下产生不同密文
+        let a = client.seal_outbound(b"same").unwrap();
+        let b = client.seal_outbound(b"same").unwrap();
+        assert_ne!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    fn test_channel_rejects_replay_and_reflection() {
+        let (mut client, mut server) = channel_pair();
+        let first = client.seal_outbound(b"transfer 10").unwrap().to_string();
+        assert!(server.open_inbound(&first).is_ok());
+
+        // 重放
+        assert!(server.open_inbound(&first).is_err());
+        // 反射：客户端自己发出的消息被送回客户端
+        let second = client.seal_outbound(b"transfer 20").unwrap().to_string();
+        assert!(client.open_inbound(&second).is_err());
+        assert_eq!(client.recv_sequence(), 0);
+
+        // 失败不影响后续正常消息
+        assert_eq!(server.open_inbound(&second).unwrap(), b"transfer 20");
+        let reply = server.seal_outbound(b"ok").unwrap().to_string();
+        assert_eq!(client.open_inbound(&reply).unwrap(), b"ok");
+    }
+
+    #[test]
+    fn test_channel_rejects_reordered_and_tampered_messages() {
+        let (mut client, mut server) = channel_pair();
+        let first = client.seal_outbound(b"one").unwrap().to_string();
+        let second = client.seal_outbound(b"two").unwrap().to_string();
+        assert!(server.open_inbound(&second).is_err());
+
+        let mut tampered = from_base64(&first).unwrap();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(server.open_inbound(&Base64String::from(tampered).to_string()).is_err());
+
+        assert_eq!(server.open_inbound(&first).unwrap(), b"one");
+        assert_eq!(server.open_inbound(&second).unwrap(), b"two");
+    }
+
+    #[test]
+    fn test_channel_sequence_exhaustion_requires_rekey() {
+        let (mut client, mut server) = channel_pair();
+        client.send_sequence = u64::MAX - 1;
+        server.recv_sequence = u64::MAX - 1;
+
+        // 最后一个可用的序列号
+        let last = client.seal_outbound(b"last").unwrap().to_string();
+        assert_eq!(server.open_inbound(&last).unwrap(), b"last");
+        assert_eq!(client.send_sequence(), u64::MAX);
+
+        // 序列号不会回绕到 0 而复用 Nonce
+        assert!(matches!(client.seal_outbound(b"wrapped"), Err(Error::NonceExhausted)));
+        assert_eq!(client.send_sequence(), u64::MAX);
+        assert!(matches!(server.open_inbound(&last), Err(Error::NonceExhausted)));
+
+        // 以当前密钥“换钥”会让序列号归零后复用 Nonce，必须拒绝
+        let current = client.key.clone();
+        assert!(matches!(client.rekey(current), Err(Error::Key(_))));
+        assert_eq!(client.send_sequence(), u64::MAX);
+
+        let new_key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        client.rekey(new_key.clone()).unwrap();
+        server.rekey(new_key).unwrap();
+        assert_eq!((client.send_sequence(), server.recv_sequence()), (0, 0));
+        let message = client.seal_outbound(b"after rekey").unwrap().to_string();
+        assert_eq!(server.open_inbound(&message).unwrap(), b"after rekey");
+    }
+
+    #[test]
+    fn test_handshake_negotiates_common_parameters() {
+        let (client, server) = channel_pair();
+        let (mut client, mut server) = (with_compressions(client), with_compressions(server));
+        let client_caps = ChannelCapabilities::new(["AES-256-GCM"], ["zstd", "deflate"]);
+        let server_caps = ChannelCapabilities::new(["AES-256-GCM"], ["deflate", "zstd"]);
+        let client_hello = client.begin_handshake(&client_caps).unwrap().to_string();
+        let server_hello = server.begin_handshake(&server_caps).unwrap().to_string();
+
+        // 双方都按发起方的偏好选择
+        let expected = SessionParams { algorithm: CHANNEL_ALGORITHM_AES_256_GCM.to_string(), compression: Some("zstd".to_string()) };
+        assert_eq!(server.negotiate(&client_hello).unwrap(), expected);
+        assert_eq!(client.negotiate(&server_hello).unwrap(), expected);
+        assert_eq!(client.session_params(), Some(&expected));
+
+        // 握手之后的业务消息按商定的方式压缩，对端解压后得到原文
+        let message = client.seal_outbound(b"after handshake").unwrap().to_string();
+        let raw = AesGcmSystem::decrypt(&client.key, &message, Some(&SecureChannel::aad(CHANNEL_AAD_LABEL, 0, 1))).unwrap();
+        assert_eq!(raw, b"zstdafter handshake");
+        assert_eq!(server.open_inbound(&message).unwrap(), b"after handshake");
+        let reply = server.seal_outbound(b"reply").unwrap().to_string();
+        assert_eq!(client.open_inbound(&reply).unwrap(), b"reply");
+
+        // 选择规则本身按发起方的偏好，不限于信道实现的算法；没有共同压缩方式时不压缩
+        let client_caps = ChannelCapabilities::new(["XChaCha20-Poly1305", "AES-256-GCM"], ["zstd"]);
+        let plain = ChannelCapabilities::default();
+        let params = ChannelCapabilities::select(&client_caps, &plain).unwrap();
+        assert_eq!(params, SessionParams { algorithm: CHANNEL_ALGORITHM_AES_256_GCM.to_string(), compression: None });
+    }
+
+    #[test]
+    fn test_handshake_without_common_algorithm_fails() {
+        let (mut client, mut server) = channel_pair();
+        let client_hello = client.begin_handshake(&ChannelCapabilities::new(["AES-256-GCM"], Vec::<String>::new())).unwrap().to_string();
+        // 对端（如其他实现）声明了本端未实现的算法
+        server.capabilities = Some(ChannelCapabilities::new(["XChaCha20-Poly1305"], ["zstd"]));
+        let peer_caps = serde_json::to_vec(&ChannelCapabilities::new(["XChaCha20-Poly1305"], ["zstd"])).unwrap();
+        let server_hello = server.seal(HANDSHAKE_AAD_LABEL, &peer_caps, SymmetricAlgorithm::Aes256Gcm).unwrap().to_string();
+        assert!(matches!(client.negotiate(&server_hello), Err(Error::Operation(_))));
+        assert!(matches!(server.negotiate(&client_hello), Err(Error::Operation(_))));
+        assert!(client.session_params().is_none() && server.session_params().is_none());
+
+        // 未发出本端能力时不能协商
+        let (_, mut fresh) = channel_pair();
+        assert!(matches!(fresh.negotiate(&client_hello), Err(Error::Operation(_))));
+    }
+
+    #[test]
+    fn test_handshake_tampering_is_detected() {
+        let (mut client, mut server) = channel_pair();
+        let client_hello = client.begin_handshake(&ChannelCapabilities::default()).unwrap().to_string();
+        server.begin_handshake(&ChannelCapabilities::default()).unwrap();
+
+        let mut tampered = from_base64(&client_hello).unwrap();
+        tampered[20] ^= 1;
+        assert!(server.negotiate(&Base64String::from(tampered).to_string()).is_err());
+        assert_eq!(server.recv_sequence(), 0);
+        assert!(server.session_params().is_none());
+
+        // 握手消息不能当作业务消息打开，业务消息也不能冒充握手消息
+        let (mut other_client, mut other_server) = channel_pair();
+        let hello = other_client.begin_handshake(&ChannelCapabilities::default()).unwrap().to_string();
+        assert!(other_server.open_inbound(&hello).is_err());
+        let data = client.seal_outbound(b"{}").unwrap().to_string();
+        assert!(server.negotiate(&client_hello).is_ok());
+        assert!(server.negotiate(&data).is_err());
+    }
+
+    #[test]
+    fn test_handshake_only_advertises_implemented_algorithms() {
+        let (client, _) = channel_pair();
+        let mut client = client.with_compression(Arc::new(TaggingCompression("zstd")));
+        for caps in [
+            ChannelCapabilities::new(["XChaCha20-Poly1305", "AES-256-GCM"], ["zstd"]),
+            ChannelCapabilities::new(Vec::<String>::new(), ["zstd"]),
+            ChannelCapabilities::new(["AES-256-GCM"], ["zstd", "brotli"]),
+        ] {
+            assert!(matches!(client.begin_handshake(&caps), Err(Error::Operation(_))));
+        }
+        assert_eq!(client.send_sequence(), 0);
+        assert!(client.capabilities.is_none());
+        assert_eq!(ChannelCapabilities::default().algorithms, CHANNEL_ALGORITHMS);
+    }
+
+    #[cfg(feature = "chacha")]
+    #[test]
+    fn test_handshake_agrees_on_chacha_and_uses_it() {
+        let (mut client, mut server) = channel_pair();
+        let client_caps = ChannelCapabilities::new([CHANNEL_ALGORITHM_CHACHA20_POLY1305, CHANNEL_ALGORITHM_AES_256_GCM], Vec::<String>::new());
+        let server_hello = server.begin_handshake(&ChannelCapabilities::default()).unwrap().to_string();
+        let client_hello = client.begin_handshake(&client_caps).unwrap().to_string();
+        assert_eq!(server.negotiate(&client_hello).unwrap().algorithm, CHANNEL_ALGORITHM_CHACHA20_POLY1305);
+        assert_eq!(client.negotiate(&server_hello).unwrap().algorithm, CHANNEL_ALGORITHM_CHACHA20_POLY1305);
+
+        // 业务消息以 ChaCha20-Poly1305 加密，用 AES-256-GCM 无法解开
+        let message = client.seal_outbound(b"over chacha").unwrap().to_string();
+        let aad = SecureChannel::aad(CHANNEL_AAD_LABEL, 0, 1);
+        let chacha_key = ChaCha20Poly1305Key::from_slice(client.key.as_bytes()).unwrap();
+        assert_eq!(ChaCha20Poly1305System::decrypt(&chacha_key, &message, Some(&aad)).unwrap(), b"over chacha");
+        assert!(AesGcmSystem::decrypt(&client.key, &message, Some(&aad)).is_err());
+
+        assert_eq!(server.open_inbound(&message).unwrap(), b"over chacha");
+        let reply = server.seal_outbound(b"reply").unwrap().to_string();
+        assert_eq!(client.open_inbound(&reply).unwrap(), b"reply");
+    }
+}
@@ -0,0 +1,248 @@
+//! 内容定义分块（CDC）
+//!
+//! 固定大小分块时，在数据开头插入一个字节会使之后的所有块边界整体平移，加密后的块全部改变，无法去重。
+//! [`ContentDefinedChunker`] 以 Gear 滚动哈希在数据内容上寻找切分点：切分点只取决于附近的字节，
+//! 未改动区域的块边界保持不变。配合 [`StreamingConfig::with_content_defined_chunking`] 在加密前分块，
+//! 备份软件即可按块去重；要让相同明文块产生相同密文，还需要调用方为每块使用确定性的密钥与 Nonce。
+//!
+//! [`StreamingConfig::with_content_defined_chunking`]: crate::common::streaming::StreamingConfig::with_content_defined_chunking
+
+use std::io::Read;
+
+use crate::common::errors::Error;
+
+/// Gear 哈希的字节映射表，由 SplitMix64 以固定种子生成，不同版本之间保持不变以保证块边界稳定
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0x5365_616c_4b69_7443u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// 最小块不得小于该值，过小的块会使每块的 Nonce 与认证标签开销失去意义
+const MIN_CHUNK_FLOOR: usize = 64;
+/// 最大块上限（16 MiB）
+const MAX_CHUNK_CEILING: usize = 16 * 1024 * 1024;
+
+/// 基于 Gear 滚动哈希的内容定义分块器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentDefinedChunker {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    mask: u64,
+}
+
+impl Default for ContentDefinedChunker {
+    /// 最小 16 KiB、平均 64 KiB、最大 256 KiB
+    fn default() -> Self {
+        Self::new(16 * 1024, 64 * 1024, 256 * 1024).expect("默认分块参数有效")
+    }
+}
+
+impl ContentDefinedChunker {
+    /// 创建分块器：块长度在 `[min_size, max_size]` 之间，期望长度约为 `avg_size`
+    ///
+    /// `avg_size` 必须是 2 的幂，且 `64 <= min_size <= avg_size <= max_size <= 16 MiB`，否则返回 `Error::Format`。
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, Error> {
+        if !avg_size.is_power_of_two()
+            || min_size < MIN_CHUNK_FLOOR
+            || min_size > avg_size
+            || avg_size > max_size
+            || max_size > MAX_CHUNK_CEILING
+        {
+            return Err(Error::Format(format!(
+                "无效的分块参数 (min = {}, avg = {}, max = {})", min_size, avg_size, max_size
+            )));
+        }
+        // 使用哈希的高位判断切分点：Gear 哈希每步左移一位，高位覆盖的窗口最长
+        let bits = avg_size.trailing_zeros();
+        let mask = if bits == 0 { 0 } else { u64::MAX << (64 - bits) };
+        Ok(Self { min_size, avg_size, max_size, mask })
+    }
+
+    /// 最小块长度
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// 期望的平均块长度
+    pub fn avg_size(&self) -> usize {
+        self.avg_size
+    }
+
+    /// 最大块长度
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// 返回 `data` 中第一个块的长度
+    ///
+    /// 在 `max_size` 内找不到切分点时返回 `min(data.len(), max_size)`；
+    /// 调用方须保证 `data` 至少有 `max_size` 字节，或已到达输入末尾，否则切分点可能随读取方式变化。
+    pub fn cut_point(&self, data: &[u8]) -> usize {
+        let limit = data.len().min(self.max_size);
+        if limit <= self.min_size {
+            return limit;
+        }
+        let mut hash = 0u64;
+        for (i, &byte) in data[..limit].iter().enumerate().skip(self.min_size) {
+            hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+            if hash & self.mask == 0 {
+                return i + 1;
+            }
+        }
+        limit
+    }
+
+    /// 将内存中的数据依次切分为块
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+        let chunker = *self;
+        let mut rest = data;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let (chunk, tail) = rest.split_at(chunker.cut_point(rest));
+            rest = tail;
+            Some(chunk)
+        })
+    }
+
+    /// 创建从 [`Read`] 中按内容切分读取块的读取器
+    pub(crate) fn reader(&self) -> ChunkReader {
+        ChunkReader { chunker: *self, pending: Vec::new(), eof: false }
+    }
+}
+
+/// 从输入流中按内容定义的边界逐块读取，最多缓冲 `max_size` 字节
+pub(crate) struct ChunkReader {
+    chunker: ContentDefinedChunker,
+    pending: Vec<u8>,
+    eof: bool,
+}
+
+impl ChunkReader {
+    /// 将下一块读入 `out`（覆盖原有内容），返回块长度；输入结束时返回 0
+    pub(crate) fn next_into<R: Read>(&mut self, reader: &mut R, out: &mut Vec<u8>) -> Result<usize, Error> {
+        while !self.eof && self.pending.len() < self.chunker.max_size {
+            let filled = self.pending.len();
+            self.pending.resize(self.chunker.max_size, 0);
+            let result = reader.read(&mut self.pending[filled..]);
+            self.pending.truncate(filled + *result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => self.eof = true,
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let len = self.chunker.cut_point(&self.pending);
+        out.clear();
+        out.extend(self.pending.drain(..len));
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 确定性的伪随机测试数据
+    fn sample_data(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        }).collect()
+    }
+
+    fn small_chunker() -> ContentDefinedChunker {
+        ContentDefinedChunker::new(1024, 4096, 16 * 1024).unwrap()
+    }
+
+    /// 每次最多返回 7 字节的读取器，模拟零碎的底层读取
+    struct TrickleReader<'a>(&'a [u8]);
+
+    impl Read for TrickleReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(7).min(self.0.len());
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_chunks_cover_input_within_bounds() {
+        let chunker = small_chunker();
+        let data = sample_data(256 * 1024);
+        let chunks: Vec<&[u8]> = chunker.chunks(&data).collect();
+        assert_eq!(chunks.concat(), data);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!((chunker.min_size()..=chunker.max_size()).contains(&chunk.len()));
+        }
+        // 块长度随内容变化，平均值接近期望值
+        let average = data.len() / chunks.len();
+        assert!(average > chunker.min_size() && average < chunker.max_size(), "average = {}", average);
+        assert!(chunks.iter().any(|chunk| chunk.len() != chunks[0].len()));
+    }
+
+    #[test]
+    fn test_insertion_only_changes_affected_chunks() {
+        let chunker = small_chunker();
+        let data = sample_data(256 * 1024);
+        let mut shifted = b"inserted prefix bytes".to_vec();
+        shifted.extend_from_slice(&data);
+
+        let original: Vec<&[u8]> = chunker.chunks(&data).collect();
+        let modified: Vec<&[u8]> = chunker.chunks(&shifted).collect();
+        let changed = modified.iter().filter(|chunk| !original.contains(chunk)).count();
+        assert!(changed <= 2, "{} of {} chunks changed", changed, modified.len());
+        // 第一个边界之后的所有块完全相同
+        assert_eq!(original[original.len() - 1], modified[modified.len() - 1]);
+        assert_eq!(original[2..], modified[modified.len() - (original.len() - 2)..]);
+
+        // 作为对照：固定大小分块在同样的插入后几乎所有块都会改变
+        let fixed: Vec<&[u8]> = data.chunks(4096).collect();
+        assert!(shifted.chunks(4096).filter(|chunk| fixed.contains(chunk)).count() <= 1);
+    }
+
+    #[test]
+    fn test_chunk_reader_matches_in_memory_chunking() {
+        let chunker = small_chunker();
+        let data = sample_data(100 * 1024 + 17);
+        let mut reader = chunker.reader();
+        let mut source = TrickleReader(&data);
+        let mut chunk = Vec::new();
+        let mut streamed = Vec::new();
+        while reader.next_into(&mut source, &mut chunk).unwrap() > 0 {
+            streamed.push(chunk.clone());
+        }
+        let expected: Vec<Vec<u8>> = chunker.chunks(&data).map(<[u8]>::to_vec).collect();
+        assert_eq!(streamed, expected);
+        assert_eq!(reader.next_into(&mut source, &mut chunk).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_chunker_parameters_rejected() {
+        assert!(ContentDefinedChunker::new(1024, 3000, 8192).is_err());
+        assert!(ContentDefinedChunker::new(8192, 4096, 16384).is_err());
+        assert!(ContentDefinedChunker::new(1024, 4096, 2048).is_err());
+        assert!(ContentDefinedChunker::new(16, 4096, 8192).is_err());
+        assert!(ContentDefinedChunker::new(1024, 4096, 32 * 1024 * 1024).is_err());
+        assert_eq!(ContentDefinedChunker::default().avg_size(), 64 * 1024);
+    }
+}
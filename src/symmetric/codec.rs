@@ -0,0 +1,123 @@
+//! 对称密文在 Base64、原始字节与十六进制之间的转换
+//!
+//! [`AesGcmSystem`] 的序列化密文是 `Nonce || 密文 || 标签`（截断标签格式另有 1 字节标签长度前缀）的 Base64 编码。
+//! 在不同系统之间搬运密文时，用 `from_*` 解析任一格式，再用 `to_*` 输出另一格式；
+//! 解析时校验长度足以容纳 Nonce 与认证标签，但不校验认证标签本身（那需要密钥）。
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::common::errors::Error;
+use crate::common::utils::{from_base64, to_base64};
+use crate::symmetric::systems::aes_gcm::AesGcmSystem;
+
+/// 结构已校验的 AES-GCM 密文字节
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CiphertextCodec {
+    bytes: Vec<u8>,
+}
+
+impl CiphertextCodec {
+    /// 解析 Base64 编码的序列化密文（即 `encrypt` 的输出）
+    pub fn from_base64(encoded: &str) -> Result<Self, Error> {
+        let bytes = from_base64(encoded).map_err(|e| Error::Format(format!("无效的Base64密文: {}", e)))?;
+        Self::from_raw(bytes)
+    }
+
+    /// 解析十六进制编码的密文，大小写均可
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        if !hex.len().is_multiple_of(2) {
+            return Err(Error::Format(format!("十六进制密文长度 {} 不是偶数", hex.len())));
+        }
+        let bytes = hex
+            .as_bytes()
+            .chunks_exact(2)
+            .map(|pair| Some((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| Error::Format("十六进制密文包含非法字符".to_string()))?;
+        Self::from_raw(bytes)
+    }
+
+    /// 接受原始密文字节
+    pub fn from_raw(bytes: impl Into<Vec<u8>>) -> Result<Self, Error> {
+        let bytes = bytes.into();
+        if !AesGcmSystem::has_valid_layout(&bytes) {
+            return Err(Error::Format(format!("密文长度 {} 字节不足以容纳 Nonce 与认证标签", bytes.len())));
+        }
+        Ok(Self { bytes })
+    }
+
+    /// 输出 Base64 编码的序列化密文，可直接交给 `decrypt`
+    pub fn to_base64(&self) -> String {
+        to_base64(&self.bytes)
+    }
+
+    /// 输出小写十六进制编码
+    pub fn to_hex(&self) -> String {
+        self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// 输出原始密文字节
+    pub fn to_raw(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    /// 借用原始密文字节
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::utils::CryptoConfig;
+    use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+    #[test]
+    fn test_codec_roundtrips_every_format_pairing() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let cases = [
+            (AesGcmSystem::encrypt(&key, b"codec", Some(b"aad")).unwrap().to_string(), &b"codec"[..], Some(&b"aad"[..])),
+            (AesGcmSystem::encrypt_with_tag_length(&key, b"truncated", None, 12).unwrap().to_string(), &b"truncated"[..], None),
+        ];
+        for (serialized, plaintext, aad) in cases {
+            let codec = CiphertextCodec::from_base64(&serialized).unwrap();
+            let parsed = [
+                CiphertextCodec::from_base64(&codec.to_base64()).unwrap(),
+                CiphertextCodec::from_hex(&codec.to_hex()).unwrap(),
+                CiphertextCodec::from_hex(&codec.to_hex().to_uppercase()).unwrap(),
+                CiphertextCodec::from_raw(codec.to_raw()).unwrap(),
+            ];
+            for from in &parsed {
+                assert_eq!(from, &codec);
+                assert_eq!(CiphertextCodec::from_base64(&from.to_base64()).unwrap(), codec);
+                assert_eq!(CiphertextCodec::from_hex(&from.to_hex()).unwrap(), codec);
+                assert_eq!(CiphertextCodec::from_raw(from.as_bytes()).unwrap(), codec);
+                assert_eq!(AesGcmSystem::decrypt(&key, &from.to_base64(), aad).unwrap(), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn test_codec_rejects_invalid_hex() {
+        let valid = "00".repeat(28);
+        assert!(CiphertextCodec::from_hex(&valid).is_ok());
+        for invalid in [
+            &valid[..55],                      // 奇数长度
+            &format!("zz{}", &valid[2..]),     // 非法字符
+            &"00".repeat(27),                  // 不足以容纳 Nonce 与完整标签
+            "",
+            &format!("0c{}", "00".repeat(23)), // 截断标签格式但长度不足
+        ] {
+            assert!(matches!(CiphertextCodec::from_hex(invalid), Err(Error::Format(_))), "{invalid}");
+        }
+        assert!(CiphertextCodec::from_hex(&format!("0c{}", "00".repeat(24))).is_ok());
+        assert!(CiphertextCodec::from_base64("not base64!").is_err());
+    }
+}
@@ -2,8 +2,12 @@
 
 #[cfg(feature = "async-engine")]
 mod async_;
+#[cfg(feature = "aes-gcm-feature")]
+mod auto;
 mod sync_;
 
 #[cfg(feature = "async-engine")]
 pub use async_::SymmetricQSealEngineAsync;
+#[cfg(feature = "aes-gcm-feature")]
+pub use auto::AutoSymmetricEngine;
 pub use sync_::{SymmetricQSealEngine, SymmetricQSealEngineBuilder};
\ No newline at end of file
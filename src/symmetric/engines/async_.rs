@@ -4,9 +4,13 @@ use arc_swap::ArcSwapOption;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::SystemTime;
+use crate::common::audit::{algorithm_name, AuditEvent, AuditSink};
 use crate::common::config::ConfigManager;
+use crate::common::metrics::Direction;
 use crate::common::errors::Error;
 use crate::common::streaming::StreamingResult;
+use crate::common::utils::CryptoConfig;
 use crate::rotation::{KeyMetadata, KeyStorage};
 use crate::storage::KeyFileStorage;
 use crate::common::streaming::StreamingConfig;
@@ -24,6 +28,7 @@ where
     key_prefix: String,
     primary: ArcSwapOption<(C::Key, KeyMetadata)>,
     secondary: DashMap<String, (C::Key, KeyMetadata)>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -49,17 +54,19 @@ where
             key_prefix: prefix.clone(),
             primary: ArcSwapOption::new(None),
             secondary: DashMap::new(),
+            audit_sink: None,
         };
         engine.initialize()?;
         Ok(engine)
     }
 
     fn initialize(&self) -> Result<(), Error> {
+        let crypto_config = self.config.get_crypto_config();
         let keys = self.key_storage.list_keys()?;
         for name in keys {
             if name.starts_with(&self.key_prefix) {
                 let (meta, data) = self.key_storage.load_key(&name)?;
-                let key = Self::deserialize(&data)?;
+                let key = Self::deserialize(&data, &crypto_config)?;
                 match meta.status {
                     crate::common::traits::KeyStatus::Active => {
                         self.primary.store(Some(Arc::new((key, meta))));
@@ -79,9 +86,10 @@ where
         Ok(())
     }
 
-    fn deserialize(data: &[u8]) -> Result<C::Key, Error> {
+    /// 按配置导入存储的密钥，`reject_weak_keys` 开启时拒绝弱密钥
+    fn deserialize(data: &[u8], config: &CryptoConfig) -> Result<C::Key, Error> {
         let kd: KeyData = serde_json::from_slice(data)?;
-        C::import_key(&kd.key).map_err(Into::into)
+        C::import_key_with_config(&kd.key, config).map_err(Into::into)
     }
 
     fn serialize(key: &C::Key) -> Result<Vec<u8>, Error> {
@@ -157,33 +165,59 @@ where
     }
     
     pub fn encrypt(&self, plaintext: &[u8]) -> Result<String, Error> {
+        let result = self.encrypt_inner(plaintext);
+        self.record(Direction::Encrypt, result, |_| plaintext.len() as u64)
+    }
+
+    fn encrypt_inner(&self, plaintext: &[u8]) -> Result<String, Error> {
+        let crypto_config = self.config.get_crypto_config();
+        crypto_config.check_oneshot_size(plaintext.len())?;
         if self.needs_rotation() {
             self.start_rotation()?;
         }
         let arc = self.primary.load_full().ok_or_else(|| Error::Key("没有可用主密钥".to_string()))?;
         let (key, _) = &*arc;
         self.increment_usage_count()?;
-        let ct = C::encrypt(key, plaintext, None)?;
-        Ok(ct.to_string())
+        let ct = C::encrypt_with_config(key, plaintext, None, &crypto_config)?;
+        Ok(crypto_config.base64_variant.encode(ct.as_ref()))
     }
 
     pub fn decrypt(&self, ciphertext: &str) -> Result<Vec<u8>, Error> {
+        let result = self.decrypt_inner(ciphertext);
+        self.record(Direction::Decrypt, result, |plaintext| plaintext.len() as u64)
+    }
+
+    fn decrypt_inner(&self, ciphertext: &str) -> Result<Vec<u8>, Error> {
+        let crypto_config = self.config.get_crypto_config();
         if let Some(arc) = self.primary.load_full() {
             let (key, _) = &*arc;
-            if let Ok(pt) = C::decrypt(key, ciphertext, None) {
+            if let Ok(pt) = C::decrypt_with_config(key, ciphertext, None, &crypto_config) {
                 return Ok(pt);
             }
         }
         for entry in self.secondary.iter() {
             let (key, _) = entry.value();
-            if let Ok(pt) = C::decrypt(key, ciphertext, None) {
+            if let Ok(pt) = C::decrypt_with_config(key, ciphertext, None, &crypto_config) {
                 return Ok(pt);
             }
         }
         Err(Error::Operation("解密失败".to_string()))
     }
     
+    /// 异步流式加密
+    ///
+    /// 通过 [`StreamingConfig::with_cancellation`] 传入的令牌被取消后，在下一帧开始前返回 [`Error::Cancelled`]，
+    /// 已写出的都是完整的帧。
     pub async fn encrypt_stream<R, W>(&self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        let result = self.encrypt_stream_inner(reader, writer, config).await;
+        self.record(Direction::Encrypt, result, |r| r.bytes_processed)
+    }
+
+    async fn encrypt_stream_inner<R, W>(&self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error>
     where
         R: AsyncRead + Unpin + Send,
         W: AsyncWrite + Unpin + Send,
@@ -197,14 +231,38 @@ where
         C::encrypt_stream_async(key, reader, writer, config, None).await
     }
 
+    /// 异步流式解密，取消语义与 [`encrypt_stream`](Self::encrypt_stream) 相同
     pub async fn decrypt_stream<R, W>(&self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error>
     where
         R: AsyncRead + Unpin + Send,
         W: AsyncWrite + Unpin + Send,
     {
-        let arc = self.primary.load_full().ok_or_else(|| Error::Key("没有可用主密钥".to_string()))?;
-        let (key, _) = &*arc;
-        C::decrypt_stream_async(key, reader, writer, config, None).await
+        let result = match self.primary.load_full() {
+            Some(arc) => C::decrypt_stream_async(&arc.0, reader, writer, config, None).await,
+            None => Err(Error::Key("没有可用主密钥".to_string())),
+        };
+        self.record(Direction::Decrypt, result, |r| r.bytes_processed)
+    }
+
+    /// 设置或移除接收审计事件的目标，事件内容与同步引擎的 `set_audit_sink` 相同
+    ///
+    /// 加密事件以主密钥ID作为密钥标识；解密会逐个尝试保留的密钥，不记录密钥标识。
+    pub fn set_audit_sink(&mut self, sink: Option<Arc<dyn AuditSink>>) {
+        self.audit_sink = sink;
+    }
+
+    /// 设置了审计目标时记录事件；写入失败时原本成功的操作改为返回该错误
+    fn record<R>(&self, direction: Direction, result: Result<R, Error>, bytes: impl Fn(&R) -> u64) -> Result<R, Error> {
+        let Some(sink) = &self.audit_sink else {
+            return result;
+        };
+        let key_id = match direction {
+            Direction::Encrypt => self.primary.load_full().map(|arc| arc.1.id.clone()),
+            Direction::Decrypt => None,
+        };
+        let event = AuditEvent::new(direction, algorithm_name::<C>(), key_id, result.as_ref().map(&bytes), SystemTime::now());
+        let recorded = sink.record(&event);
+        result.and_then(|value| recorded.map(|_| value))
     }
 
     #[cfg(test)]
@@ -226,6 +284,7 @@ mod tests {
     use crate::symmetric::systems::aes_gcm::AesGcmSystem;
     use std::io::Cursor;
     use tempfile::tempdir;
+    use crate::common::streaming::{CancellationToken, FRAME_HEADER_SIZE};
     use tokio::io::BufReader;
 
     type TestEngine = SymmetricQSealEngineAsync<AesGcmSystem>;
@@ -331,4 +390,114 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_leaves_only_whole_frames() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "test_async_cancel");
+        let original_data: Vec<u8> = (0..=255u8).cycle().take(16 * 10).collect();
+
+        // 第 3 帧写出后请求取消，加密应在第 4 帧开始前停止
+        let cancelling_config = || {
+            let token = CancellationToken::new();
+            let trigger = token.clone();
+            StreamingConfig::default()
+                .with_buffer_size(16)
+                .with_cancellation(token)
+                .with_progress_callback(Arc::new(move |processed, _| {
+                    if processed >= 48 {
+                        trigger.cancel();
+                    }
+                }))
+        };
+
+        let mut encrypted_dest = Vec::new();
+        let result = engine
+            .encrypt_stream(BufReader::new(Cursor::new(original_data.clone())), &mut encrypted_dest, &cancelling_config())
+            .await;
+        assert!(matches!(result, Err(Error::Cancelled)));
+
+        // 输出恰好由头部与完整的帧组成
+        let mut offset = FRAME_HEADER_SIZE;
+        let mut frames = 0;
+        while offset < encrypted_dest.len() {
+            let len = u32::from_le_bytes(encrypted_dest[offset..offset + 4].try_into().unwrap()) as usize;
+            offset += 4 + len;
+            frames += 1;
+        }
+        assert_eq!(offset, encrypted_dest.len());
+        assert_eq!(frames, 3);
+
+        // 取消的流没有末帧，解密时视为截断
+        let result = engine
+            .decrypt_stream(BufReader::new(Cursor::new(encrypted_dest)), &mut Vec::new(), &StreamingConfig::default())
+            .await;
+        assert!(matches!(result, Err(Error::StreamIntegrity(_))));
+
+        // 未绑定帧元数据时，已写出的完整帧可以解密为输入的前缀
+        let unbound_config = cancelling_config().with_frame_metadata_binding(false);
+        let mut encrypted_dest = Vec::new();
+        let result = engine
+            .encrypt_stream(BufReader::new(Cursor::new(original_data.clone())), &mut encrypted_dest, &unbound_config)
+            .await;
+        assert!(matches!(result, Err(Error::Cancelled)));
+        let mut decrypted_dest = Vec::new();
+        engine
+            .decrypt_stream(BufReader::new(Cursor::new(encrypted_dest)), &mut decrypted_dest, &StreamingConfig::default().with_frame_metadata_binding(false))
+            .await
+            .unwrap();
+        assert_eq!(decrypted_dest, &original_data[..48]);
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_audit_events() {
+        use crate::common::audit::{AuditOperation, AuditOutcome, MemoryAuditSink};
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_async_audit");
+        let sink = Arc::new(MemoryAuditSink::new());
+        engine.set_audit_sink(Some(sink.clone()));
+
+        let ciphertext = engine.encrypt(b"audited").unwrap();
+        engine.decrypt(&ciphertext).unwrap();
+        let error = engine.decrypt("bm90IGEgY2lwaGVydGV4dA==").unwrap_err();
+        let mut encrypted = Vec::new();
+        engine
+            .encrypt_stream(BufReader::new(Cursor::new(b"stream".to_vec())), &mut encrypted, &StreamingConfig::default())
+            .await
+            .unwrap();
+
+        let events = sink.events();
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|event| event.algorithm == "AesGcmSystem"));
+        let primary_id = engine.primary.load_full().map(|arc| arc.1.id.clone());
+        assert_eq!((events[0].op, events[0].bytes, &events[0].outcome), (AuditOperation::Encrypt, 7, &AuditOutcome::Success));
+        assert_eq!(events[0].fingerprint, primary_id);
+        assert_eq!((events[1].op, events[1].bytes, &events[1].fingerprint), (AuditOperation::Decrypt, 7, &None));
+        assert_eq!(events[2].outcome, AuditOutcome::Failure { error: error.code() });
+        assert_eq!((events[3].op, events[3].bytes, &events[3].fingerprint), (AuditOperation::Encrypt, 6, &primary_id));
+    }
+
+    #[test]
+    fn test_async_engine_rejects_weak_stored_key_when_configured() {
+        use crate::common::utils::to_base64;
+
+        let dir = tempdir().unwrap();
+        setup_test_engine(dir.path(), "test_async_weak_key");
+        let storage = KeyFileStorage::new(dir.path().to_str().unwrap()).unwrap();
+        let name = storage.list_keys().unwrap().into_iter().find(|name| name.starts_with("test_async_weak_key")).unwrap();
+        let (metadata, _) = storage.load_key(&name).unwrap();
+        let weak = serde_json::to_vec(&KeyData { key: to_base64(&[0u8; 32]) }).unwrap();
+        storage.save_key(&name, &metadata, &weak).unwrap();
+
+        let config = |reject_weak_keys| ConfigFile {
+            storage: StorageConfig { key_storage_dir: dir.path().to_str().unwrap().to_string(), ..Default::default() },
+            rotation: Default::default(),
+            crypto: CryptoConfig { reject_weak_keys, ..Default::default() },
+        };
+        let strict = Arc::new(ConfigManager::from_config_file(config(true)));
+        assert!(matches!(TestEngine::new(strict, "test_async_weak_key"), Err(Error::InsecureParameter(_))));
+        let lenient = Arc::new(ConfigManager::from_config_file(config(false)));
+        assert!(TestEngine::new(lenient, "test_async_weak_key").is_ok());
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,183 @@
+//! 运行时选择数据加密算法的对称引擎 `AutoSymmetricEngine`
+use std::io::{Read, Write};
+use std::sync::Arc;
+use crate::bench::BackendSelection;
+use crate::common::config::ConfigManager;
+use crate::common::errors::Error;
+use crate::common::metrics::EngineMetrics;
+use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::symmetric::engines::SymmetricQSealEngine;
+use crate::symmetric::systems::aes_gcm::AesGcmSystem;
+#[cfg(feature = "chacha")]
+use crate::symmetric::systems::chacha20_poly1305::ChaCha20Poly1305System;
+use crate::symmetric::systems::SymmetricAlgorithm;
+
+/// 按运行时选出的算法加密数据的对称引擎
+///
+/// [`SymmetricQSealEngine`] 的算法由类型参数在编译期确定；本类型在创建时按 [`SymmetricAlgorithm`]
+/// 构造对应的引擎，之后的加解密都由该引擎完成。各算法的密钥以 `key_prefix` 加算法后缀分别存储，
+/// 同一前缀下切换算法不会把一种算法的密钥用于另一种算法，但以原算法加密的数据也只能由原算法的引擎解密，
+/// 因此应记录 [`algorithm`](Self::algorithm)，之后以 [`for_algorithm`](Self::for_algorithm) 重建引擎。
+pub struct AutoSymmetricEngine {
+    backend: Backend,
+    selection: Option<BackendSelection>,
+}
+
+enum Backend {
+    Aes256Gcm(SymmetricQSealEngine<AesGcmSystem>),
+    #[cfg(feature = "chacha")]
+    ChaCha20Poly1305(SymmetricQSealEngine<ChaCha20Poly1305System>),
+}
+
+/// 在选中的引擎上执行同一表达式
+macro_rules! dispatch {
+    ($backend:expr, $engine:ident => $body:expr) => {
+        match $backend {
+            Backend::Aes256Gcm($engine) => $body,
+            #[cfg(feature = "chacha")]
+            Backend::ChaCha20Poly1305($engine) => $body,
+        }
+    };
+}
+
+impl AutoSymmetricEngine {
+    /// 以指定算法创建引擎，密钥存储在 `key_prefix` 加算法后缀之下
+    pub fn for_algorithm(algorithm: SymmetricAlgorithm, config_manager: Arc<ConfigManager>, key_prefix: &str) -> Result<Self, Error> {
+        let key_prefix = format!("{}-{}", key_prefix, storage_suffix(algorithm));
+        let backend = match algorithm {
+            SymmetricAlgorithm::Aes256Gcm => Backend::Aes256Gcm(SymmetricQSealEngine::new(config_manager, &key_prefix)?),
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => Backend::ChaCha20Poly1305(SymmetricQSealEngine::new(config_manager, &key_prefix)?),
+        };
+        Ok(Self { backend, selection: None })
+    }
+
+    /// 按 CPU 是否具备 AES 硬件加速选择算法（见 [`SymmetricAlgorithm::auto`]）后创建引擎
+    pub fn auto(config_manager: Arc<ConfigManager>, key_prefix: &str) -> Result<Self, Error> {
+        Self::for_algorithm(SymmetricAlgorithm::auto(), config_manager, key_prefix)
+    }
+
+    /// 在本机上简短测量各算法，以加密最快者创建引擎
+    ///
+    /// 测量结果可由 [`selection`](Self::selection) 取得。`config_manager` 不会被修改。
+    pub fn auto_tuned(config_manager: Arc<ConfigManager>, key_prefix: &str) -> Result<Self, Error> {
+        let selection = SymmetricAlgorithm::auto_tuned()?;
+        let mut engine = Self::for_algorithm(selection.selected, config_manager, key_prefix)?;
+        engine.selection = Some(selection);
+        Ok(engine)
+    }
+
+    /// 引擎加密数据所用的算法
+    pub fn algorithm(&self) -> SymmetricAlgorithm {
+        match self.backend {
+            Backend::Aes256Gcm(_) => SymmetricAlgorithm::Aes256Gcm,
+            #[cfg(feature = "chacha")]
+            Backend::ChaCha20Poly1305(_) => SymmetricAlgorithm::ChaCha20Poly1305,
+        }
+    }
+
+    /// 由 [`auto_tuned`](Self::auto_tuned) 创建时的测量结果；以其他方式创建时为 `None`
+    pub fn selection(&self) -> Option<&BackendSelection> {
+        self.selection.as_ref()
+    }
+
+    /// 加密一段明文
+    pub fn encrypt(&mut self, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<String, Error> {
+        dispatch!(&mut self.backend, engine => engine.encrypt(plaintext, additional_data))
+    }
+
+    /// 解密一段密文
+    pub fn decrypt(&mut self, ciphertext: &str, additional_data: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        dispatch!(&mut self.backend, engine => engine.decrypt(ciphertext, additional_data))
+    }
+
+    /// 同步流式加密
+    pub fn encrypt_stream<R: Read, W: Write>(&mut self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error> {
+        dispatch!(&mut self.backend, engine => engine.encrypt_stream(reader, writer, config))
+    }
+
+    /// 同步流式解密
+    pub fn decrypt_stream<R: Read, W: Write>(&mut self, reader: R, writer: W, config: &StreamingConfig) -> Result<StreamingResult, Error> {
+        dispatch!(&mut self.backend, engine => engine.decrypt_stream(reader, writer, config))
+    }
+
+    /// 获取当前的配置管理器
+    pub fn config(&self) -> Arc<ConfigManager> {
+        dispatch!(&self.backend, engine => engine.config())
+    }
+
+    /// 返回引擎自创建以来的操作指标快照
+    pub fn metrics(&self) -> EngineMetrics {
+        dispatch!(&self.backend, engine => engine.metrics())
+    }
+}
+
+/// 各算法密钥在存储中的前缀后缀
+fn storage_suffix(algorithm: SymmetricAlgorithm) -> &'static str {
+    match algorithm {
+        SymmetricAlgorithm::Aes256Gcm => "aes256gcm",
+        #[cfg(feature = "chacha")]
+        SymmetricAlgorithm::ChaCha20Poly1305 => "chacha20poly1305",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::{ConfigFile, StorageConfig};
+    use std::io::Cursor;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn config_manager(dir: &Path) -> Arc<ConfigManager> {
+        Arc::new(ConfigManager::from_config_file(ConfigFile {
+            storage: StorageConfig { key_storage_dir: dir.to_str().unwrap().to_string(), ..Default::default() },
+            rotation: Default::default(),
+            crypto: Default::default(),
+        }))
+    }
+
+    #[test]
+    fn test_auto_tuned_encrypts_with_selected_backend() {
+        let dir = tempdir().unwrap();
+        let config_manager = config_manager(dir.path());
+        let before = config_manager.get_crypto_config();
+        let mut engine = AutoSymmetricEngine::auto_tuned(config_manager.clone(), "tuned").unwrap();
+
+        let selection = engine.selection().unwrap();
+        assert_eq!(engine.algorithm(), selection.selected);
+        assert_eq!(selection.reports.len(), SymmetricAlgorithm::available().len());
+        assert_eq!(config_manager.get_crypto_config().key_protection, before.key_protection);
+
+        // 数据确实由选中的算法加密：同一算法重建的引擎能解密，另一种算法的系统不能
+        let ciphertext = engine.encrypt(b"tuned", Some(b"aad")).unwrap();
+        assert_eq!(engine.decrypt(&ciphertext, Some(b"aad")).unwrap(), b"tuned");
+        let mut reopened = AutoSymmetricEngine::for_algorithm(engine.algorithm(), config_manager.clone(), "tuned").unwrap();
+        assert_eq!(reopened.decrypt(&ciphertext, Some(b"aad")).unwrap(), b"tuned");
+        for other in SymmetricAlgorithm::available().into_iter().filter(|&other| other != engine.algorithm()) {
+            let mut other = AutoSymmetricEngine::for_algorithm(other, config_manager.clone(), "tuned").unwrap();
+            assert!(other.decrypt(&ciphertext, Some(b"aad")).is_err());
+        }
+        assert!(AutoSymmetricEngine::auto(config_manager, "cpu").unwrap().selection().is_none());
+    }
+
+    #[test]
+    fn test_every_algorithm_roundtrips() {
+        for algorithm in SymmetricAlgorithm::available() {
+            let dir = tempdir().unwrap();
+            let mut engine = AutoSymmetricEngine::for_algorithm(algorithm, config_manager(dir.path()), "each").unwrap();
+            assert_eq!(engine.algorithm(), algorithm);
+            let ciphertext = engine.encrypt(b"payload", None).unwrap();
+            assert_eq!(engine.decrypt(&ciphertext, None).unwrap(), b"payload", "{}", algorithm.name());
+
+            let config = StreamingConfig::default().with_buffer_size(16);
+            let data = b"streamed through the selected backend".to_vec();
+            let mut encrypted = Vec::new();
+            engine.encrypt_stream(Cursor::new(&data), &mut encrypted, &config).unwrap();
+            let mut decrypted = Vec::new();
+            engine.decrypt_stream(Cursor::new(&encrypted), &mut decrypted, &config).unwrap();
+            assert_eq!(decrypted, data);
+            assert_eq!(engine.metrics().encrypt_operations, 2);
+        }
+    }
+}
@@ -2,11 +2,23 @@
 use std::sync::Arc;
 use std::io::{Read, Write};
 use std::path::Path;
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+use crate::common::audit::{algorithm_name, AuditEvent, AuditSink};
+use crate::common::buffer_pool::BufferPool;
 use crate::common::errors::Error;
 use crate::common::config::ConfigManager;
+use crate::common::metrics::{Direction, EngineMetrics, MetricsRecorder};
+use crate::common::utils::{from_base64_into, select_first_success_constant_time, CryptoConfig};
+use crate::common::rng::EngineRng;
 use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::common::traits::KeyMetadata;
 use crate::storage::KeyFileStorage;
 use crate::symmetric::rotation::SymmetricKeyRotationManager;
+#[cfg(feature = "secure-storage")]
+use crate::symmetric::password;
+#[cfg(feature = "secure-storage")]
+use secrecy::SecretString;
 use crate::symmetric::traits::{SymmetricCryptographicSystem, SymmetricSyncStreamingSystem};
 
 /// `SymmetricQSealEngine`：一个使用对称加密算法并支持密钥自动轮换的用户友好引擎。
@@ -20,6 +32,10 @@ where
 {
     config: Arc<ConfigManager>,
     pub(crate) key_manager: SymmetricKeyRotationManager<T>,
+    rng: EngineRng,
+    metrics: MetricsRecorder,
+    buffer_pool: Option<Arc<BufferPool>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl<T: SymmetricCryptographicSystem + SymmetricSyncStreamingSystem> SymmetricQSealEngine<T>
@@ -38,11 +54,17 @@ where
             rotation_policy,
             key_prefix
         );
-        key_manager.initialize(&config_manager.get_crypto_config())?;
+        let crypto_config = config_manager.get_crypto_config();
+        let rng = EngineRng::from_config(&crypto_config);
+        rng.run(|| key_manager.initialize(&crypto_config))?;
         
         Ok(Self {
             config: config_manager,
             key_manager,
+            rng,
+            metrics: MetricsRecorder::default(),
+            buffer_pool: None,
+            audit_sink: None,
         })
     }
     
@@ -65,9 +87,17 @@ where
 
     /// 加密一段明文。
     pub fn encrypt(&mut self, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<String, Error> {
+        let rng = self.rng.clone();
+        let result = rng.run(|| self.encrypt_inner(plaintext, additional_data));
+        self.record(Direction::Encrypt, result, |_| plaintext.len() as u64)
+    }
+
+    fn encrypt_inner(&mut self, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<String, Error> {
+        let crypto_config = self.config.get_crypto_config();
+        crypto_config.check_oneshot_size(plaintext.len())?;
         let manager = &mut self.key_manager;
         if manager.needs_rotation() {
-            manager.start_rotation(&self.config.get_crypto_config())?;
+            manager.start_rotation(&crypto_config)?;
         }
 
         let key = manager.get_primary_key()
@@ -76,14 +106,26 @@ where
 
         manager.increment_usage_count()?;
 
-        let ciphertext = T::encrypt(&key, plaintext, additional_data)
+        if let Some(pool) = &self.buffer_pool {
+            let mut scratch = pool.acquire();
+            T::encrypt_to_buffer(&key, plaintext, additional_data, &crypto_config, &mut scratch)
+                .map_err(|e| Error::Operation(format!("加密失败: {}", e)))?;
+            return Ok(crypto_config.base64_variant.encode(&scratch));
+        }
+
+        let ciphertext = T::encrypt_with_config(&key, plaintext, additional_data, &crypto_config)
             .map_err(|e| Error::Operation(format!("加密失败: {}", e)))?;
 
-        Ok(ciphertext.to_string())
+        Ok(crypto_config.base64_variant.encode(ciphertext.as_ref()))
     }
 
     /// 解密一段密文。
     pub fn decrypt(&mut self, ciphertext: &str, additional_data: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        let result = self.decrypt_inner(ciphertext, additional_data);
+        self.record(Direction::Decrypt, result, |plaintext| plaintext.len() as u64)
+    }
+
+    fn decrypt_inner(&mut self, ciphertext: &str, additional_data: Option<&[u8]>) -> Result<Vec<u8>, Error> {
         let manager = &mut self.key_manager;
         
         let keys = manager.get_all_keys();
@@ -91,9 +133,19 @@ where
             return Err(Error::Operation("没有可用的密钥进行解密".to_string()));
         }
 
+        let crypto_config = self.config.get_crypto_config();
+        if let Some(pool) = &self.buffer_pool {
+            return Self::decrypt_pooled(pool, keys, ciphertext, additional_data, &crypto_config);
+        }
+
+        if crypto_config.ct_key_selection {
+            return select_first_success_constant_time(keys, |key| T::decrypt_with_config(key, ciphertext, additional_data, &crypto_config).ok())
+                .ok_or_else(|| Error::Operation("解密失败，所有可用密钥都无法解密该密文".to_string()));
+        }
+
         for key_ref in keys {
             let key = key_ref.clone(); // 克隆以避免生命周期问题
-            if let Ok(plaintext) = T::decrypt(&key, ciphertext, additional_data) {
+            if let Ok(plaintext) = T::decrypt_with_config(&key, ciphertext, additional_data, &crypto_config) {
                 return Ok(plaintext);
             }
         }
@@ -101,12 +153,52 @@ where
         Err(Error::Operation("解密失败，所有可用密钥都无法解密该密文".to_string()))
     }
 
+    /// 借用暂存缓冲区解密：Base64 只解码一次，各候选密钥在缓冲区中原地尝试
+    fn decrypt_pooled(
+        pool: &BufferPool,
+        keys: Vec<&T::Key>,
+        ciphertext: &str,
+        additional_data: Option<&[u8]>,
+        crypto_config: &CryptoConfig,
+    ) -> Result<Vec<u8>, Error> {
+        let all_failed = || Error::Operation("解密失败，所有可用密钥都无法解密该密文".to_string());
+        let mut decoded = pool.acquire();
+        from_base64_into(ciphertext, &mut decoded).map_err(|_| all_failed())?;
+
+        if crypto_config.ct_key_selection {
+            return select_first_success_constant_time(keys, |key| {
+                let mut plaintext = pool.acquire();
+                T::decrypt_from_bytes(key, &decoded, additional_data, crypto_config, &mut plaintext).ok().map(|_| plaintext.to_vec())
+            })
+            .ok_or_else(all_failed);
+        }
+
+        let mut plaintext = pool.acquire();
+        for key in keys {
+            if T::decrypt_from_bytes(key, &decoded, additional_data, crypto_config, &mut plaintext).is_ok() {
+                return Ok(plaintext.to_vec());
+            }
+        }
+        Err(all_failed())
+    }
+
     /// 同步流式加密
     pub fn encrypt_stream<R: Read, W: Write>(
         &mut self,
         reader: R,
         writer: W,
         config: &StreamingConfig,
+    ) -> Result<StreamingResult, Error> {
+        let rng = self.rng.clone();
+        let result = rng.run(|| self.encrypt_stream_inner(reader, writer, config));
+        self.record(Direction::Encrypt, result, |r| r.bytes_processed)
+    }
+
+    fn encrypt_stream_inner<R: Read, W: Write>(
+        &mut self,
+        reader: R,
+        writer: W,
+        config: &StreamingConfig,
     ) -> Result<StreamingResult, Error> {
         let manager = &mut self.key_manager;
         if manager.needs_rotation() {
@@ -133,15 +225,89 @@ where
 
         let key = manager.get_primary_key()
             .map(|k| k.clone())
-            .ok_or_else(|| Error::Key("没有可用的主密钥进行解密".to_string()))?;
-        
-        T::decrypt_stream(&key, reader, writer, config, None)
+            .ok_or_else(|| Error::Key("没有可用的主密钥进行解密".to_string()));
+        let result = key.and_then(|key| T::decrypt_stream(&key, reader, writer, config, None));
+        self.record(Direction::Decrypt, result, |r| r.bytes_processed)
+    }
+
+    /// 以口令加密数据，不使用引擎管理的密钥
+    ///
+    /// 以 Argon2id（参数取自引擎配置）和随机盐值派生 AES-256-GCM 密钥，返回包含派生参数、盐值与密文的自描述 Base64 数据块。
+    /// 格式见 [`crate::symmetric::password`]。
+    #[cfg(feature = "secure-storage")]
+    pub fn encrypt_with_password(&self, data: &[u8], password: &SecretString) -> Result<String, Error> {
+        let crypto_config = self.config.get_crypto_config();
+        let result = self.rng.run(|| password::encrypt_with_password(data, password, &crypto_config));
+        self.record(Direction::Encrypt, result, |_| data.len() as u64)
+    }
+
+    /// 解密 [`encrypt_with_password`](Self::encrypt_with_password) 生成的数据块，派生参数从数据块中读取
+    ///
+    /// 头部参数的上限取 [`password::PasswordKdfParams::DEFAULT_LIMITS`] 与引擎配置中的较大者，
+    /// 引擎总能解密自己生成的数据块，而伪造的超大参数在派生密钥之前即被拒绝。
+    #[cfg(feature = "secure-storage")]
+    pub fn decrypt_with_password(&self, blob: &str, password: &SecretString) -> Result<Vec<u8>, Error> {
+        let configured = password::PasswordKdfParams::from_config(&self.config.get_crypto_config());
+        let limits = password::PasswordKdfParams {
+            memory_cost: configured.memory_cost.max(password::DEFAULT_MAX_MEMORY_COST),
+            time_cost: configured.time_cost.max(password::DEFAULT_MAX_TIME_COST),
+        };
+        let result = password::decrypt_with_password_limited(blob, password, &limits);
+        self.record(Direction::Decrypt, result, |plaintext| plaintext.len() as u64)
     }
 
     /// 获取当前的配置管理器
     pub fn config(&self) -> Arc<ConfigManager> {
         Arc::clone(&self.config)
     }
+
+    /// 设置或移除一次性加解密使用的暂存缓冲区池
+    ///
+    /// 启用后 Nonce 与密文的拼接、Base64 解码及原地解密都在借出的缓冲区中完成，缓冲区归还前清零；
+    /// 输出格式与未启用时完全相同。
+    pub fn set_buffer_pool(&mut self, pool: Option<Arc<BufferPool>>) {
+        self.buffer_pool = pool;
+    }
+
+    /// 设置或移除接收审计事件的目标
+    ///
+    /// 设置后每次加解密（含流式与口令加解密）完成时产生一条 [`AuditEvent`]。
+    /// 加密事件以主密钥ID作为密钥标识；解密会逐个尝试保留的密钥，不记录密钥标识。
+    pub fn set_audit_sink(&mut self, sink: Option<Arc<dyn AuditSink>>) {
+        self.audit_sink = sink;
+    }
+
+    /// 更新指标，并在设置了审计目标时记录事件
+    fn record<R>(&self, direction: Direction, result: Result<R, Error>, bytes: impl Fn(&R) -> u64) -> Result<R, Error> {
+        let result = self.metrics.record(direction, result, &bytes);
+        let Some(sink) = &self.audit_sink else {
+            return result;
+        };
+        let key_id = match direction {
+            Direction::Encrypt => self.key_manager.get_primary_key_metadata().map(|metadata| metadata.id.clone()),
+            Direction::Decrypt => None,
+        };
+        let event = AuditEvent::new(direction, algorithm_name::<T>(), key_id, result.as_ref().map(&bytes), SystemTime::now());
+        let recorded = sink.record(&event);
+        result.and_then(|value| recorded.map(|_| value))
+    }
+
+    /// 返回引擎自创建以来的操作指标快照（仅含计数与明文字节数）
+    pub fn metrics(&self) -> EngineMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// 列出为解密旧数据而保留的密钥的元数据（不含当前主密钥）
+    pub fn retained_keys(&self) -> Vec<KeyMetadata> {
+        self.key_manager.get_secondary_key_metadata().into_iter().cloned().collect()
+    }
+
+    /// 删除创建时间早于 `timestamp` 的保留密钥，返回删除的数量
+    ///
+    /// 被删除的密钥同时从存储中移除并在内存中清零，此后由它们加密的数据将无法解密。
+    pub fn prune_retained_before(&mut self, timestamp: DateTime<Utc>) -> Result<usize, Error> {
+        self.key_manager.prune_secondary_keys_before(timestamp)
+    }
 }
 
 /// `SymmetricQSealEngine` 的构造器
@@ -152,6 +318,8 @@ where
 {
     config_manager: Option<Arc<ConfigManager>>,
     key_prefix: Option<String>,
+    buffer_pool: Option<Arc<BufferPool>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -165,6 +333,8 @@ where
         Self {
             config_manager: None,
             key_prefix: None,
+            buffer_pool: None,
+            audit_sink: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -188,11 +358,26 @@ where
         self
     }
     
+    /// 使用共享的暂存缓冲区池
+    pub fn with_buffer_pool(mut self, pool: Arc<BufferPool>) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
+    /// 把审计事件交给 `sink`
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
     /// 构建 `SymmetricQSealEngine`
     pub fn build(self) -> Result<SymmetricQSealEngine<T>, Error> {
         let cm = self.config_manager.unwrap_or_else(|| Arc::new(ConfigManager::new()));
         let prefix = self.key_prefix.ok_or_else(|| Error::Operation("Key prefix must be set".to_string()))?;
-        SymmetricQSealEngine::new(cm, &prefix)
+        let mut engine = SymmetricQSealEngine::new(cm, &prefix)?;
+        engine.set_buffer_pool(self.buffer_pool);
+        engine.set_audit_sink(self.audit_sink);
+        Ok(engine)
     }
 }
 
@@ -237,6 +422,153 @@ mod tests {
         assert_eq!(plaintext.as_ref(), decrypted.as_slice());
     }
 
+    #[test]
+    fn test_engine_url_safe_base64_output() {
+        use crate::common::utils::{to_base64, Base64Variant};
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_url_safe");
+        let mut crypto = engine.config().get_crypto_config();
+        crypto.base64_variant = Base64Variant::UrlSafeNoPad;
+        engine.config().update_crypto_config(crypto).unwrap();
+
+        for plaintext in [b"a".as_ref(), b"url safe ciphertext", &[0xffu8; 64]] {
+            let ciphertext = engine.encrypt(plaintext, None).unwrap();
+            assert!(!ciphertext.contains(['+', '/', '=']));
+            assert_eq!(engine.decrypt(&ciphertext, None).unwrap(), plaintext);
+
+            // 标准编码的同一密文同样可以解密
+            let standard = to_base64(&crate::common::utils::from_base64(&ciphertext).unwrap());
+            assert_eq!(engine.decrypt(&standard, None).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_engine_ct_key_selection_tries_every_key() {
+        use crate::common::utils::CT_SELECTION_ATTEMPTS;
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_ct_selection");
+        let mut crypto = engine.config().get_crypto_config();
+        crypto.ct_key_selection = true;
+        engine.config().update_crypto_config(crypto).unwrap();
+
+        let old_ciphertext = engine.encrypt(b"old key", None).unwrap();
+        for _ in 0..10 {
+            engine.encrypt(b"filler", None).unwrap();
+        }
+        let new_ciphertext = engine.encrypt(b"new key", None).unwrap();
+        let key_count = engine.key_manager.get_all_keys().len();
+        assert!(key_count >= 2);
+
+        // 无论匹配的是主密钥还是次要密钥，尝试次数都等于密钥数量
+        for (ciphertext, expected) in [(&new_ciphertext, b"new key"), (&old_ciphertext, b"old key")] {
+            let before = CT_SELECTION_ATTEMPTS.with(|count| count.get());
+            assert_eq!(engine.decrypt(ciphertext, None).unwrap(), expected);
+            assert_eq!(CT_SELECTION_ATTEMPTS.with(|count| count.get()) - before, key_count);
+        }
+        assert!(engine.decrypt(&old_ciphertext, Some(b"wrong aad")).is_err());
+    }
+
+    #[test]
+    fn test_engine_truncated_tag_length() {
+        use crate::common::utils::from_base64;
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_tag_length");
+        let full = engine.encrypt(b"reading", None).unwrap();
+
+        let mut crypto = engine.config().get_crypto_config();
+        crypto.tag_length = 12;
+        engine.config().update_crypto_config(crypto.clone()).unwrap();
+        let truncated = engine.encrypt(b"reading", None).unwrap();
+        assert_eq!(from_base64(&truncated).unwrap().len() + 3, from_base64(&full).unwrap().len());
+        assert_eq!(engine.decrypt(&truncated, None).unwrap(), b"reading");
+        // 解密只接受配置的标签长度
+        assert!(engine.decrypt(&full, None).is_err());
+        crypto.tag_length = 16;
+        engine.config().update_crypto_config(crypto.clone()).unwrap();
+        assert!(engine.decrypt(&truncated, None).is_err());
+        assert_eq!(engine.decrypt(&full, None).unwrap(), b"reading");
+
+        crypto.tag_length = 8;
+        engine.config().update_crypto_config(crypto).unwrap();
+        let err = engine.encrypt(b"reading", None).unwrap_err();
+        assert!(err.to_string().contains("tag_length = 8"), "{}", err);
+    }
+
+    #[test]
+    fn test_engine_metrics_count_operations_and_failures() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_metrics");
+        assert_eq!(engine.metrics(), EngineMetrics::default());
+
+        let first = engine.encrypt(b"12345", None).unwrap();
+        let second = engine.encrypt(b"1234567890", None).unwrap();
+        engine.decrypt(&first, None).unwrap();
+        engine.decrypt(&second, None).unwrap();
+        assert!(engine.decrypt("not a ciphertext", None).is_err());
+        assert!(engine.decrypt(&first, Some(b"wrong aad")).is_err());
+
+        let mut sealed = Vec::new();
+        engine.encrypt_stream(Cursor::new(vec![7u8; 100]), &mut sealed, &StreamingConfig::default()).unwrap();
+
+        let metrics = engine.metrics();
+        assert_eq!(metrics.encrypt_operations, 3);
+        assert_eq!(metrics.bytes_encrypted, 115);
+        assert_eq!(metrics.encrypt_failures, 0);
+        assert_eq!(metrics.decrypt_operations, 4);
+        assert_eq!(metrics.bytes_decrypted, 15);
+        assert_eq!(metrics.decrypt_failures, 2);
+    }
+
+    #[test]
+    fn test_engine_audit_events_for_success_and_failure() {
+        use crate::common::audit::{AuditOperation, AuditOutcome, MemoryAuditSink};
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_audit");
+        let sink = Arc::new(MemoryAuditSink::new());
+        engine.set_audit_sink(Some(sink.clone()));
+
+        let ciphertext = engine.encrypt(b"audited", Some(b"aad")).unwrap();
+        engine.decrypt(&ciphertext, Some(b"aad")).unwrap();
+        let error = engine.decrypt(&ciphertext, Some(b"wrong aad")).unwrap_err();
+
+        let events = sink.events();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event.algorithm == "AesGcmSystem"));
+        let primary_id = engine.key_manager.get_primary_key_metadata().map(|metadata| metadata.id.clone());
+        assert_eq!((events[0].op, events[0].bytes, &events[0].outcome), (AuditOperation::Encrypt, 7, &AuditOutcome::Success));
+        assert_eq!(events[0].fingerprint, primary_id);
+        assert_eq!((events[1].op, events[1].bytes, &events[1].outcome), (AuditOperation::Decrypt, 7, &AuditOutcome::Success));
+        assert_eq!((events[2].op, events[2].bytes), (AuditOperation::Decrypt, 0));
+        assert_eq!(events[2].outcome, AuditOutcome::Failure { error: error.code() });
+
+        // 事件不含明文、密文或错误详情
+        let serialized = serde_json::to_string(&events).unwrap();
+        assert!(!serialized.contains("audited") && !serialized.contains(&ciphertext));
+    }
+
+    #[test]
+    fn test_engine_decrypts_wrapped_and_unpadded_ciphertext() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_lenient_base64");
+        let plaintext = b"pasted into an email!";
+        let ciphertext = engine.encrypt(plaintext, None).unwrap();
+        assert!(ciphertext.ends_with('='));
+
+        let wrapped: String = ciphertext.as_bytes().chunks(16)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(engine.decrypt(&wrapped, None).unwrap(), plaintext);
+
+        let unpadded = ciphertext.trim_end_matches('=');
+        assert_eq!(engine.decrypt(unpadded, None).unwrap(), plaintext);
+        assert_eq!(engine.decrypt(&format!("  {}\r\n", unpadded), None).unwrap(), plaintext);
+    }
+
     #[test]
     fn test_engine_streaming_roundtrip() {
         let dir = tempdir().unwrap();
@@ -257,6 +589,41 @@ mod tests {
         assert_eq!(original_data.as_ref(), decrypted_dest.into_inner().as_slice());
     }
 
+    #[test]
+    fn test_engine_oneshot_size_limit() {
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_oneshot_limit");
+        let mut crypto = engine.config().get_crypto_config();
+        assert_eq!(crypto.max_oneshot_size, 64 * 1024 * 1024);
+        crypto.max_oneshot_size = 64;
+        engine.config().update_crypto_config(crypto).unwrap();
+
+        let data = [0x5au8; 200];
+        let ciphertext = engine.encrypt(&data[..64], None).unwrap();
+        assert_eq!(engine.decrypt(&ciphertext, None).unwrap(), &data[..64]);
+        assert!(matches!(
+            engine.encrypt(&data[..65], None),
+            Err(Error::PayloadTooLarge { size: 65, limit: 64 })
+        ));
+        #[cfg(feature = "secure-storage")]
+        {
+            let password = SecretString::from("oneshot");
+            assert!(engine.encrypt_with_password(&data[..64], &password).is_ok());
+            assert!(matches!(
+                engine.encrypt_with_password(&data[..65], &password),
+                Err(Error::PayloadTooLarge { size: 65, limit: 64 })
+            ));
+        }
+
+        // 流式接口不受该上限限制
+        let streaming_config = StreamingConfig::default().with_buffer_size(32);
+        let mut encrypted = Vec::new();
+        engine.encrypt_stream(Cursor::new(&data), &mut encrypted, &streaming_config).unwrap();
+        let mut decrypted = Vec::new();
+        engine.decrypt_stream(Cursor::new(encrypted), &mut decrypted, &streaming_config).unwrap();
+        assert_eq!(decrypted, data);
+    }
+
     #[test]
     fn test_decrypt_with_rotated_key() {
         let dir = tempdir().unwrap();
@@ -304,4 +671,220 @@ mod tests {
         let mut decrypted_dest = Cursor::new(Vec::new());
         engine.decrypt_stream(&mut encrypted_source, &mut decrypted_dest, &streaming_config).unwrap();
     }
+
+    #[test]
+    fn test_prune_retained_keys_by_age() {
+        use crate::rotation::KeyStorage;
+
+        let dir = tempdir().unwrap();
+        let mut engine = setup_test_engine(dir.path(), "test_prune");
+        assert!(engine.retained_keys().is_empty());
+
+        // 轮换三次，得到三把按版本递增的保留密钥，并各自加密一段数据
+        let mut ciphertexts = Vec::new();
+        for version in 1..=3u8 {
+            ciphertexts.push(engine.encrypt(&[version], None).unwrap());
+            engine.key_manager.set_usage_count(11);
+            engine.encrypt(b"trigger rotation", None).unwrap();
+        }
+        let retained = engine.retained_keys();
+        assert_eq!(retained.iter().map(|m| m.version).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let now = Utc::now();
+        for (metadata, days) in retained.iter().zip([30, 20, 10]) {
+            engine.key_manager.set_secondary_created_at(&metadata.id, now - chrono::Duration::days(days));
+        }
+
+        assert_eq!(engine.prune_retained_before(now - chrono::Duration::days(15)).unwrap(), 2);
+        let remaining = engine.retained_keys();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, retained[2].id);
+
+        // 被删除的密钥无法再解密，较新的保留密钥与主密钥不受影响
+        assert!(engine.decrypt(&ciphertexts[0], None).is_err());
+        assert!(engine.decrypt(&ciphertexts[1], None).is_err());
+        assert_eq!(engine.decrypt(&ciphertexts[2], None).unwrap(), [3]);
+        let fresh = engine.encrypt(b"fresh", None).unwrap();
+        assert_eq!(engine.decrypt(&fresh, None).unwrap(), b"fresh");
+
+        // 存储中也只剩下未被删除的密钥
+        let stored = KeyFileStorage::new(dir.path().to_str().unwrap()).unwrap().list_keys().unwrap();
+        for metadata in &retained[..2] {
+            assert!(!stored.iter().any(|name| name.ends_with(&metadata.id)));
+        }
+        assert!(stored.iter().any(|name| name.ends_with(&retained[2].id)));
+
+        assert_eq!(engine.prune_retained_before(now - chrono::Duration::days(15)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_engine_with_buffer_pool() {
+        use crate::common::buffer_pool::BufferPool;
+
+        let dir = tempdir().unwrap();
+        let pool = Arc::new(BufferPool::new(4));
+        let mut pooled = setup_test_engine(dir.path(), "test_pool");
+        let mut unpooled = setup_test_engine(dir.path(), "test_pool");
+        pooled.set_buffer_pool(Some(Arc::clone(&pool)));
+
+        let old_ciphertext = pooled.encrypt(b"before rotation", Some(b"aad")).unwrap();
+        pooled.key_manager.set_usage_count(11);
+        for plaintext in [b"".as_ref(), b"pooled buffers", &[0xa5u8; 4096]] {
+            let ciphertext = pooled.encrypt(plaintext, Some(b"aad")).unwrap();
+            assert_eq!(pooled.decrypt(&ciphertext, Some(b"aad")).unwrap(), plaintext);
+            assert!(pooled.decrypt(&ciphertext, Some(b"wrong aad")).is_err());
+        }
+        // 旧密钥加密的数据仍可通过回退密钥解密，池化与否输出格式一致
+        assert_eq!(pooled.decrypt(&old_ciphertext, Some(b"aad")).unwrap(), b"before rotation");
+        assert_eq!(unpooled.decrypt(&old_ciphertext, Some(b"aad")).unwrap(), b"before rotation");
+        let unpooled_ciphertext = unpooled.encrypt(b"from unpooled", None).unwrap();
+        assert_eq!(pooled.decrypt(&unpooled_ciphertext, None).unwrap(), b"from unpooled");
+
+        let mut crypto = pooled.config().get_crypto_config();
+        crypto.ct_key_selection = true;
+        crypto.tag_length = 12;
+        pooled.config().update_crypto_config(crypto).unwrap();
+        let truncated = pooled.encrypt(b"truncated tag", None).unwrap();
+        assert_eq!(pooled.decrypt(&truncated, None).unwrap(), b"truncated tag");
+        // 截断标签格式的短密文不足标准格式的 28 字节下限，同样可以解密
+        let short = pooled.encrypt(b"", None).unwrap();
+        assert_eq!(pooled.decrypt(&short, None).unwrap(), b"");
+        // 配置为截断标签后，16 字节标签的旧密文不再被接受
+        assert!(pooled.decrypt(&old_ciphertext, Some(b"aad")).is_err());
+        assert!(pooled.decrypt("not base64!", None).is_err());
+
+        assert!(pool.available() > 0);
+        drop(pooled);
+        let reused = pool.acquire();
+        assert!(reused.is_empty() && reused.capacity() > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "secure-storage")]
+    fn test_password_encryption_roundtrip() {
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "test_password");
+        let password = SecretString::from("correct horse battery staple");
+        let plaintext = b"encrypt this file with a password";
+
+        let first = engine.encrypt_with_password(plaintext, &password).unwrap();
+        let second = engine.encrypt_with_password(plaintext, &password).unwrap();
+        // 随机盐值与 Nonce 使两次加密结果不同
+        assert_ne!(first, second);
+        assert_eq!(engine.decrypt_with_password(&first, &password).unwrap(), plaintext);
+        assert_eq!(engine.decrypt_with_password(&second, &password).unwrap(), plaintext);
+
+        // 数据块自描述，不依赖引擎管理的密钥
+        let other_dir = tempdir().unwrap();
+        let other = setup_test_engine(other_dir.path(), "test_password_other");
+        assert_eq!(other.decrypt_with_password(&first, &password).unwrap(), plaintext);
+        assert_eq!(engine.metrics().encrypt_operations, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "secure-storage")]
+    fn test_password_encryption_rejects_wrong_password_and_tampering() {
+        use crate::common::utils::{from_base64, to_base64};
+
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "test_password_wrong");
+        let blob = engine.encrypt_with_password(b"secret", &SecretString::from("right")).unwrap();
+        assert!(matches!(
+            engine.decrypt_with_password(&blob, &SecretString::from("wrong")),
+            Err(Error::DecryptionFailed(_))
+        ));
+
+        // 降低头部中的迭代次数：派生参数受认证，解密失败
+        let mut bytes = from_base64(&blob).unwrap();
+        bytes[9] = 1;
+        assert!(engine.decrypt_with_password(&to_base64(&bytes), &SecretString::from("right")).is_err());
+        assert!(matches!(
+            engine.decrypt_with_password(&to_base64(b"not a password blob"), &SecretString::from("right")),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "secure-storage")]
+    fn test_password_decryption_caps_header_parameters() {
+        use crate::common::utils::{from_base64, to_base64};
+        use crate::symmetric::password::{decrypt_with_password_limited, PasswordKdfParams, DEFAULT_MAX_MEMORY_COST};
+
+        let dir = tempdir().unwrap();
+        let engine = setup_test_engine(dir.path(), "test_password_caps");
+        let password = SecretString::from("right");
+        let blob = engine.encrypt_with_password(b"secret", &password).unwrap();
+
+        // 伪造 4 GiB 内存开销与 64 次迭代的头部：在派生密钥之前即被拒绝
+        for (offset, value) in [(5, 4 * 1024 * 1024u32), (5, DEFAULT_MAX_MEMORY_COST + 1), (9, 64)] {
+            let mut bytes = from_base64(&blob).unwrap();
+            bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            assert!(matches!(engine.decrypt_with_password(&to_base64(&bytes), &password), Err(Error::Format(_))));
+        }
+
+        // 调用方可以给出更严格的上限
+        let crypto = engine.config().get_crypto_config();
+        let strict = PasswordKdfParams { memory_cost: crypto.argon2_memory_cost - 1, time_cost: crypto.argon2_time_cost };
+        assert!(matches!(decrypt_with_password_limited(&blob, &password, &strict), Err(Error::Format(_))));
+        let exact = PasswordKdfParams::from_config(&crypto);
+        assert_eq!(decrypt_with_password_limited(&blob, &password, &exact).unwrap(), b"secret");
+    }
+
+    #[test]
+    fn test_engine_for_selected_algorithm_roundtrips() {
+        use crate::symmetric::systems::SymmetricAlgorithm;
+
+        fn roundtrip<S>(dir: &Path, key_prefix: &str) -> Vec<u8>
+        where
+            S: SymmetricCryptographicSystem + SymmetricSyncStreamingSystem,
+            S::Error: std::error::Error + 'static,
+            Error: From<S::Error>,
+        {
+            let config = ConfigFile {
+                storage: StorageConfig { key_storage_dir: dir.to_str().unwrap().to_string(), ..Default::default() },
+                rotation: Default::default(),
+                crypto: Default::default(),
+            };
+            let mut engine = SymmetricQSealEngine::<S>::new(Arc::new(ConfigManager::from_config_file(config)), key_prefix).unwrap();
+            let ciphertext = engine.encrypt(b"selected backend", Some(b"aad")).unwrap();
+            engine.decrypt(&ciphertext, Some(b"aad")).unwrap()
+        }
+
+        // 以模拟的能力标志分别走两个分支，由选择结果决定引擎的类型参数
+        for aes_accelerated in [true, false] {
+            let dir = tempdir().unwrap();
+            let algorithm = SymmetricAlgorithm::select(aes_accelerated);
+            let decrypted = match algorithm {
+                SymmetricAlgorithm::Aes256Gcm => roundtrip::<AesGcmSystem>(dir.path(), "selected"),
+                #[cfg(feature = "chacha")]
+                SymmetricAlgorithm::ChaCha20Poly1305 => {
+                    roundtrip::<crate::symmetric::systems::chacha20_poly1305::ChaCha20Poly1305System>(dir.path(), "selected")
+                }
+            };
+            assert_eq!(decrypted, b"selected backend", "{}", algorithm.name());
+        }
+    }
+
+    #[test]
+    fn test_engine_rejects_weak_stored_key_when_configured() {
+        use crate::common::utils::to_base64;
+        use crate::rotation::KeyStorage;
+
+        let dir = tempdir().unwrap();
+        setup_test_engine(dir.path(), "test_weak_key");
+        let storage = KeyFileStorage::new(dir.path().to_str().unwrap()).unwrap();
+        let name = storage.list_keys().unwrap().into_iter().find(|name| name.starts_with("test_weak_key")).unwrap();
+        let (metadata, _) = storage.load_key(&name).unwrap();
+        storage.save_key(&name, &metadata, to_base64(&[0u8; 32]).as_bytes()).unwrap();
+
+        let config = |reject_weak_keys| ConfigFile {
+            storage: StorageConfig { key_storage_dir: dir.path().to_str().unwrap().to_string(), ..Default::default() },
+            rotation: Default::default(),
+            crypto: CryptoConfig { reject_weak_keys, ..Default::default() },
+        };
+        let strict = Arc::new(ConfigManager::from_config_file(config(true)));
+        assert!(TestEngine::new(strict, "test_weak_key").is_err());
+        let lenient = Arc::new(ConfigManager::from_config_file(config(false)));
+        assert!(TestEngine::new(lenient, "test_weak_key").is_ok());
+    }
 } 
\ No newline at end of file
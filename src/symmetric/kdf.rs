@@ -0,0 +1,112 @@
+//! 从密钥协商得到的共享密钥派生多个相互独立的对称密钥
+//!
+//! KEM 交换后通常需要多把用途不同的密钥（加密、MAC、头部保护等）。
+//! 默认使用 HKDF-SHA256：共享密钥经 HKDF-Extract 得到伪随机密钥，
+//! 再以每个标签作为 `info` 执行 HKDF-Expand，不同标签得到的密钥在密码学上相互独立。
+//! 部署要求使用其他 KDF 时，可通过 [`derive_keys_with`] 传入任意 [`KeyDerivation`] 实现。
+
+use alloc::vec::Vec;
+
+use crate::common::errors::Error;
+use crate::common::key_derivation::{HkdfSha256, KeyDerivation};
+use crate::symmetric::systems::aes_gcm::AesGcmKey;
+
+/// 以 `labels` 中的每个标签派生一把 AES-256-GCM 密钥，顺序与 `labels` 一致
+///
+/// 相同的共享密钥与标签总是得到相同的密钥；重复的标签会得到相同的密钥。
+pub fn derive_keys(shared_secret: &[u8], labels: &[&str]) -> Vec<AesGcmKey> {
+    derive_keys_with(&HkdfSha256, shared_secret, labels)
+        .expect("32 字节输出远小于 HKDF-SHA256 的上限")
+}
+
+/// 使用指定的 KDF 派生子密钥，每个标签作为 `info`，盐值为空
+pub fn derive_keys_with<D: KeyDerivation + ?Sized>(kdf: &D, shared_secret: &[u8], labels: &[&str]) -> Result<Vec<AesGcmKey>, Error> {
+    labels.iter()
+        .map(|label| {
+            let okm = kdf.derive(shared_secret, &[], label.as_bytes(), 32)?;
+            let key: [u8; 32] = okm.as_slice().try_into()
+                .map_err(|_| Error::Key(alloc::format!("{} 返回的密钥长度不是 32 字节", kdf.algorithm())))?;
+            Ok(AesGcmKey::from_derived(key))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::utils::from_base64;
+    use crate::symmetric::systems::aes_gcm::AesGcmSystem;
+    use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+    fn export(key: &AesGcmKey) -> String {
+        AesGcmSystem::export_key(key).unwrap()
+    }
+
+    #[test]
+    fn test_derive_keys_deterministic_and_independent() {
+        let shared_secret = [0x42u8; 32];
+        let labels = ["encryption", "mac", "header"];
+        let keys = derive_keys(&shared_secret, &labels);
+        let again = derive_keys(&shared_secret, &labels);
+        assert_eq!(keys.len(), 3);
+        for (a, b) in keys.iter().zip(&again) {
+            assert_eq!(export(a), export(b));
+        }
+        assert_ne!(export(&keys[0]), export(&keys[1]));
+        assert_ne!(export(&keys[1]), export(&keys[2]));
+        assert_ne!(export(&keys[0]), export(&keys[2]));
+
+        // 单独派生某个标签与批量派生的结果一致
+        assert_eq!(export(&derive_keys(&shared_secret, &["mac"])[0]), export(&keys[1]));
+        // 不同共享密钥得到不同的密钥
+        assert_ne!(export(&derive_keys(&[0x43u8; 32], &["encryption"])[0]), export(&keys[0]));
+    }
+
+    #[test]
+    fn test_derived_keys_are_not_interchangeable() {
+        let keys = derive_keys(b"kem shared secret", &["encryption", "header"]);
+        let ciphertext = AesGcmSystem::encrypt(&keys[0], b"payload", None).unwrap();
+        assert_eq!(AesGcmSystem::decrypt(&keys[0], &ciphertext.to_string(), None).unwrap(), b"payload");
+        assert!(AesGcmSystem::decrypt(&keys[1], &ciphertext.to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_derive_keys_rfc5869_prk_expand() {
+        // RFC 5869 测试用例 3（空 salt、空 info）输出的前 8 字节
+        let ikm = [0x0bu8; 22];
+        let key = derive_keys(&ikm, &[""]);
+        assert_eq!(
+            from_base64(&export(&key[0])).unwrap()[..8],
+            [0x8d, 0xa4, 0xe7, 0x75, 0xa5, 0x63, 0xc1, 0x8f]
+        );
+    }
+
+    /// 仅用于测试的自定义 KDF：把标签与共享密钥拼接后取 SHA-256
+    struct TestKdf;
+
+    impl KeyDerivation for TestKdf {
+        fn algorithm(&self) -> &str {
+            "test-sha256"
+        }
+
+        fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Result<zeroize::Zeroizing<Vec<u8>>, Error> {
+            use sha2::{Digest, Sha256};
+            let digest = Sha256::new().chain_update(info).chain_update(salt).chain_update(ikm).finalize();
+            Ok(zeroize::Zeroizing::new(digest[..out_len].to_vec()))
+        }
+    }
+
+    #[test]
+    fn test_derive_keys_with_custom_kdf() {
+        use sha2::{Digest, Sha256};
+
+        let keys = derive_keys_with(&TestKdf, b"shared", &["encryption", "mac"]).unwrap();
+        let expected = Sha256::new().chain_update(b"mac").chain_update(b"shared").finalize();
+        assert_eq!(from_base64(&export(&keys[1])).unwrap(), expected.as_slice());
+        assert_ne!(export(&keys[0]), export(&derive_keys(b"shared", &["encryption"])[0]));
+
+        // 默认实现与显式传入 HKDF-SHA256 等价
+        let explicit = derive_keys_with(&HkdfSha256, b"shared", &["encryption"]).unwrap();
+        assert_eq!(export(&explicit[0]), export(&derive_keys(b"shared", &["encryption"])[0]));
+    }
+}
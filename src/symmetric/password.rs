@@ -0,0 +1,149 @@
+//! 基于口令的一次性对称加密
+//!
+//! 以 Argon2id 从口令与随机盐值派生 AES-256-GCM 密钥，将派生参数、盐值与密文打包成一个自描述的 Base64 数据块：
+//! `魔数 "SKPW" || 版本(1) || m_cost(u32 LE) || t_cost(u32 LE) || p_cost(u32 LE) || 盐值(16) || AES-GCM 密文`。
+//! 魔数至盐值的整个头部作为附加认证数据，篡改派生参数或盐值都会导致解密失败。
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use secrecy::{ExposeSecret, SecretString};
+use zeroize::Zeroizing;
+
+use crate::common::errors::Error;
+use crate::common::rng::fill_random;
+use crate::common::utils::{from_base64, to_base64, CryptoConfig};
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 口令加密数据块的魔数
+const MAGIC: &[u8; 4] = b"SKPW";
+/// 当前数据块格式版本
+const VERSION: u8 = 1;
+/// 随机盐值长度
+const SALT_SIZE: usize = 16;
+/// 头部长度：魔数 + 版本 + 三个 u32 参数 + 盐值
+const HEADER_SIZE: usize = MAGIC.len() + 1 + 12 + SALT_SIZE;
+/// Argon2 并行度
+const PARALLELISM: u32 = 1;
+/// [`decrypt_with_password`] 默认接受的最大内存开销（KB，即 256 MiB），防止伪造的数据块耗尽内存
+pub const DEFAULT_MAX_MEMORY_COST: u32 = 256 * 1024;
+/// [`decrypt_with_password`] 默认接受的最大迭代次数，防止伪造的数据块长时间占用 CPU
+pub const DEFAULT_MAX_TIME_COST: u32 = 8;
+
+/// 以口令加密数据，派生参数取自 `config` 的 `argon2_memory_cost` 与 `argon2_time_cost`
+///
+/// 每次调用都使用新的随机盐值与 Nonce，相同口令与明文的两次加密结果互不相同。
+/// 数据超过 `config.max_oneshot_size` 时返回 `Error::PayloadTooLarge`。
+pub fn encrypt_with_password(data: &[u8], password: &SecretString, config: &CryptoConfig) -> Result<String, Error> {
+    config.check_oneshot_size(data.len())?;
+    let mut salt = [0u8; SALT_SIZE];
+    fill_random(&mut salt);
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    header.extend_from_slice(MAGIC);
+    header.push(VERSION);
+    header.extend_from_slice(&config.argon2_memory_cost.to_le_bytes());
+    header.extend_from_slice(&config.argon2_time_cost.to_le_bytes());
+    header.extend_from_slice(&PARALLELISM.to_le_bytes());
+    header.extend_from_slice(&salt);
+
+    let key = derive_key(password, &salt, config.argon2_memory_cost, config.argon2_time_cost, PARALLELISM)?;
+    let ciphertext = AesGcmSystem::encrypt(&key, data, Some(&header))?;
+    header.extend_from_slice(ciphertext.as_ref());
+    Ok(to_base64(&header))
+}
+
+/// 解密 [`encrypt_with_password`] 生成的数据块，派生参数从数据块头部读取
+///
+/// 口令错误与数据被篡改都返回 `Error::DecryptionFailed`，二者无法区分。头部参数超过
+/// [`DEFAULT_MAX_MEMORY_COST`] 或 [`DEFAULT_MAX_TIME_COST`] 时不派生密钥，直接返回 `Error::Format`；
+/// 需要其他上限时使用 [`decrypt_with_password_limited`]。
+pub fn decrypt_with_password(blob: &str, password: &SecretString) -> Result<Vec<u8>, Error> {
+    decrypt_with_password_limited(blob, password, &PasswordKdfParams::DEFAULT_LIMITS)
+}
+
+/// 与 [`decrypt_with_password`] 相同，但以 `limits` 作为头部派生参数的上限
+///
+/// 头部参数来自不可信的输入，在认证之前就决定了派生密钥的内存与时间开销，上限应贴近调用方实际使用的配置。
+pub fn decrypt_with_password_limited(
+    blob: &str,
+    password: &SecretString,
+    limits: &PasswordKdfParams,
+) -> Result<Vec<u8>, Error> {
+    let bytes = from_base64(blob)?;
+    let (memory_cost, time_cost, parallelism) = header_params(&bytes)?
+        .ok_or_else(|| Error::Format("不是口令加密的数据块".to_string()))?;
+    let (header, ciphertext) = bytes.split_at(HEADER_SIZE);
+    if memory_cost > limits.memory_cost || time_cost > limits.time_cost || parallelism != PARALLELISM {
+        return Err(Error::Format(format!(
+            "口令加密数据块的派生参数超出允许范围 (m = {} KB, t = {}, p = {})", memory_cost, time_cost, parallelism
+        )));
+    }
+
+    let key = derive_key(password, &header[HEADER_SIZE - SALT_SIZE..], memory_cost, time_cost, parallelism)?;
+    let mut plaintext = Vec::new();
+    AesGcmSystem::decrypt_from_bytes(&key, ciphertext, Some(header), &CryptoConfig::default(), &mut plaintext)
+        .map_err(|_| Error::DecryptionFailed("口令错误或数据已被篡改".to_string()))?;
+    Ok(plaintext)
+}
+
+/// 读取口令加密数据块头部中的 Argon2 参数 `(m_cost, t_cost, p_cost)`，不需要口令
+///
+/// 数据不以魔数开头时返回 `Ok(None)`；以魔数开头但头部不完整或版本未知时返回 `Error::Format`。
+pub(crate) fn header_params(bytes: &[u8]) -> Result<Option<(u32, u32, u32)>, Error> {
+    if !bytes.starts_with(MAGIC) {
+        return Ok(None);
+    }
+    if bytes.len() < HEADER_SIZE {
+        return Err(Error::Format("口令加密数据块的头部不完整".to_string()));
+    }
+    if bytes[MAGIC.len()] != VERSION {
+        return Err(Error::Format(format!("不支持的口令加密格式版本: {}", bytes[MAGIC.len()])));
+    }
+    let param = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("参数长度固定"));
+    Ok(Some((param(5), param(9), param(13))))
+}
+
+/// [`AesGcmKey::from_password`] 使用的 Argon2id 参数，并行度固定为 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PasswordKdfParams {
+    /// 内存成本（KB）
+    pub memory_cost: u32,
+    /// 时间成本（迭代次数）
+    pub time_cost: u32,
+}
+
+impl Default for PasswordKdfParams {
+    fn default() -> Self {
+        Self::from_config(&CryptoConfig::default())
+    }
+}
+
+impl PasswordKdfParams {
+    /// [`decrypt_with_password`] 默认接受的参数上限
+    pub const DEFAULT_LIMITS: Self = Self { memory_cost: DEFAULT_MAX_MEMORY_COST, time_cost: DEFAULT_MAX_TIME_COST };
+
+    /// 取 `config` 中的 `argon2_memory_cost` 与 `argon2_time_cost`
+    pub fn from_config(config: &CryptoConfig) -> Self {
+        Self { memory_cost: config.argon2_memory_cost, time_cost: config.argon2_time_cost }
+    }
+}
+
+impl AesGcmKey {
+    /// 以 Argon2id（v0x13，并行度 1，输出 32 字节）从口令与盐值派生 AES-256-GCM 密钥
+    ///
+    /// 与密钥容器派生 KEK、[`encrypt_with_password`] 派生加密密钥的算法完全相同，供不需要容器的自定义流程直接使用。
+    /// 盐值至少 8 字节，应随机生成并与密文一同保存；相同口令、盐值与参数总是得到相同的密钥。
+    pub fn from_password(password: &SecretString, salt: &[u8], kdf_params: &PasswordKdfParams) -> Result<Self, Error> {
+        derive_key(password, salt, kdf_params.memory_cost, kdf_params.time_cost, PARALLELISM)
+    }
+}
+
+/// 使用 Argon2id 从口令与盐值派生 32 字节 AES-GCM 密钥
+fn derive_key(password: &SecretString, salt: &[u8], memory_cost: u32, time_cost: u32, parallelism: u32) -> Result<AesGcmKey, Error> {
+    let params = Params::new(memory_cost, time_cost, parallelism, Some(32))
+        .map_err(|e| Error::Key(format!("Argon2参数无效: {}", e)))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.expose_secret().as_bytes(), salt, key.as_mut())
+        .map_err(|e| Error::Key(format!("口令派生密钥失败: {}", e)))?;
+    Ok(AesGcmKey::from_derived(*key))
+}
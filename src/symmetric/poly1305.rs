@@ -0,0 +1,79 @@
+//! 独立的 Poly1305 一次性消息认证码
+//!
+//! 供构建自定义认证通道时直接使用。
+//!
+//! **警告：Poly1305 密钥只能使用一次。** 同一密钥认证两条不同的消息后，攻击者即可恢复密钥并伪造任意标签。
+//! 每条消息都必须使用新的32字节密钥，通常由流密码按消息派生（例如 ChaCha20 以新 Nonce 生成的首个密钥流块）。
+//! 需要可重复使用的密钥时，请改用 AEAD 或 HMAC 等多次使用的 MAC。
+
+use alloc::format;
+use crate::common::errors::Error;
+use crate::common::utils::constant_time_eq;
+use poly1305::universal_hash::KeyInit;
+use poly1305::Poly1305;
+
+/// 一次性密钥长度（字节）
+pub const KEY_SIZE: usize = 32;
+/// 认证标签长度（字节）
+pub const TAG_SIZE: usize = 16;
+
+fn mac(one_time_key: &[u8]) -> Result<Poly1305, Error> {
+    if one_time_key.len() != KEY_SIZE {
+        return Err(Error::Key(format!(
+            "Poly1305 一次性密钥必须为{}字节，实际为{}字节",
+            KEY_SIZE,
+            one_time_key.len()
+        )));
+    }
+    Ok(Poly1305::new(one_time_key.into()))
+}
+
+/// 使用一次性密钥计算 `data` 的 Poly1305 标签
+///
+/// 同一 `one_time_key` 绝不能用于第二条消息，见模块文档。
+pub fn compute(one_time_key: &[u8], data: &[u8]) -> Result<[u8; TAG_SIZE], Error> {
+    Ok(mac(one_time_key)?.compute_unpadded(data).into())
+}
+
+/// 以常数时间比较校验 `tag` 是否为 `data` 在 `one_time_key` 下的标签
+///
+/// 标签长度不为16字节时返回 `Ok(false)`。
+pub fn verify(one_time_key: &[u8], data: &[u8], tag: &[u8]) -> Result<bool, Error> {
+    let expected = compute(one_time_key, data)?;
+    Ok(tag.len() == TAG_SIZE && constant_time_eq(&expected, tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8439 第 2.5.2 节测试向量
+    const RFC_KEY: [u8; KEY_SIZE] = [
+        0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5, 0x06, 0xa8,
+        0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf, 0x41, 0x49, 0xf5, 0x1b,
+    ];
+    const RFC_MESSAGE: &[u8] = b"Cryptographic Forum Research Group";
+    const RFC_TAG: [u8; TAG_SIZE] = [
+        0xa8, 0x06, 0x1d, 0xc1, 0x30, 0x51, 0x36, 0xc6, 0xc2, 0x2b, 0x8b, 0xaf, 0x0c, 0x01, 0x27, 0xa9,
+    ];
+
+    #[test]
+    fn test_poly1305_rfc8439_vector() {
+        assert_eq!(compute(&RFC_KEY, RFC_MESSAGE).unwrap(), RFC_TAG);
+        assert!(verify(&RFC_KEY, RFC_MESSAGE, &RFC_TAG).unwrap());
+    }
+
+    #[test]
+    fn test_poly1305_detects_tampering() {
+        let mut tampered_message = RFC_MESSAGE.to_vec();
+        tampered_message[0] ^= 0x01;
+        assert!(!verify(&RFC_KEY, &tampered_message, &RFC_TAG).unwrap());
+
+        let mut tampered_tag = RFC_TAG;
+        tampered_tag[15] ^= 0x80;
+        assert!(!verify(&RFC_KEY, RFC_MESSAGE, &tampered_tag).unwrap());
+        assert!(!verify(&RFC_KEY, RFC_MESSAGE, &RFC_TAG[..8]).unwrap());
+
+        assert!(matches!(compute(&RFC_KEY[..16], RFC_MESSAGE), Err(Error::Key(_))));
+    }
+}
@@ -1,8 +1,10 @@
 //! 对称加密实现
 #[cfg(feature = "async-engine")]
 pub mod async_streaming;
+pub mod incremental;
 pub mod streaming;
 
-pub use streaming::{SymmetricStreamingEncryptor, SymmetricStreamingDecryptor};
+pub use incremental::IncrementalDecryptor;
+pub use streaming::{verify_stream_integrity, SymmetricStreamingEncryptor, SymmetricStreamingDecryptor};
 #[cfg(feature = "async-engine")]
 pub use async_streaming::{AsyncStreamingEncryptor, AsyncStreamingDecryptor};
\ No newline at end of file
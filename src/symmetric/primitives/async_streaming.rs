@@ -1,12 +1,36 @@
 #![cfg(feature = "async-engine")]
 
 use std::marker::PhantomData;
+use std::time::Instant;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use crate::common::errors::Error;
-use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::common::streaming::{chunk_metadata_aad, decode_chunk_frame, encode_chunk_frame, FrameBinding, FrameTally, StreamingConfig, StreamingResult, FINAL_FRAME_MARKER, FOOTER_MARKER, FRAME_HEADER_SIZE, MAX_FRAME_PREALLOC};
 use crate::symmetric::traits::{SymmetricCryptographicSystem, SymmetricAsyncStreamingSystem};
 use crate::common::utils;
 
+/// 尽可能填满缓冲区，仅在遇到EOF时返回较少的字节数
+async fn read_full<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = reader.read(&mut buf[filled..]).await.map_err(Error::Io)?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+/// 读取长度前缀声明的 `len` 字节帧内容，缓冲区随实际读到的数据增长
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut frame = Vec::with_capacity(len.min(MAX_FRAME_PREALLOC));
+    reader.take(len as u64).read_to_end(&mut frame).await?;
+    if frame.len() != len {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "帧数据不完整"));
+    }
+    Ok(frame)
+}
+
 /// 异步对称流式加密器
 pub struct AsyncStreamingEncryptor<'a, C, R, W>
 where
@@ -41,26 +65,80 @@ where
     }
 
     pub async fn process(mut self) -> Result<StreamingResult, Error> {
-        let mut buffer = vec![0u8; self.config.buffer_size];
+        if self.config.content_defined_chunking.is_some() {
+            return Err(Error::Format("异步流式加密不支持内容定义分块".to_string()));
+        }
+        if self.config.frame_chaining {
+            return Err(Error::Format("异步流式加密不支持帧链接".to_string()));
+        }
+        let mut sizer = self.config.adaptive_chunking.map(|adaptive| adaptive.sizer()).transpose()?;
+        let buffer_len = self.config.adaptive_chunking.map_or(self.config.buffer_size, |adaptive| adaptive.max_frame_size);
+        let mut buffer = vec![0u8; buffer_len];
         let mut total_written = 0;
         let mut bytes_processed = 0;
 
-        while let Ok(read_bytes) = self.reader.read(&mut buffer).await {
-            if read_bytes == 0 { break; }
+        // 启用帧元数据绑定时，先写入声明的总帧数
+        let binding = FrameBinding::for_encryption(&self.config)?;
+        if let Some(binding) = &binding {
+            self.writer.write_all(&binding.header()).await.map_err(Error::Io)?;
+        }
+        let mut frame_index = 0u64;
+        let mut tally = FrameTally::for_config(&self.config);
+
+        loop {
+            self.config.check_cancelled()?;
+            let frame_started = Instant::now();
+            let read_bytes = if let Some(sizer) = &sizer {
+                read_full(&mut self.reader, &mut buffer[..sizer.next_len()]).await?
+            } else if binding.is_some() {
+                read_full(&mut self.reader, &mut buffer).await?
+            } else {
+                match self.reader.read(&mut buffer).await {
+                    Ok(n) => n,
+                    Err(_) => break,
+                }
+            };
+            // 总帧数未知时以一个空的末帧结束流
+            let is_final = read_bytes == 0 && binding.is_some_and(|binding| binding.is_open_ended());
+            if read_bytes == 0 && !is_final { break; }
             bytes_processed += read_bytes as u64;
             
             let plaintext = &buffer[..read_bytes];
-            let ciphertext_obj = C::encrypt(self.key, plaintext, self.additional_data)?;
-            let ciphertext_bytes = utils::to_base64(ciphertext_obj.as_ref()).into_bytes();
+            let bound_aad = binding.map(|binding| binding.aad(frame_index, is_final, self.additional_data));
+            let aad = bound_aad.as_deref().or(self.additional_data);
+            let metadata = self.config.chunk_metadata(frame_index, plaintext)?;
+            let metadata_aad = metadata.as_deref().map(|metadata| chunk_metadata_aad(aad, metadata));
+            let aad = metadata_aad.as_deref().or(aad);
+            let ciphertext_obj = C::encrypt(self.key, plaintext, aad)?;
+            frame_index += 1;
+            let ciphertext_bytes = encode_chunk_frame(metadata.as_deref(), utils::to_base64(ciphertext_obj.as_ref()));
             
+            if is_final {
+                self.writer.write_all(&FINAL_FRAME_MARKER.to_le_bytes()).await.map_err(Error::Io)?;
+            }
             let len = (ciphertext_bytes.len() as u32).to_le_bytes();
             self.writer.write_all(&len).await.map_err(Error::Io)?;
             self.writer.write_all(&ciphertext_bytes).await.map_err(Error::Io)?;
+            if let Some(tally) = tally.as_mut() {
+                tally.record(&ciphertext_bytes);
+            }
+            // 写入端的背压体现在本帧的耗时上，据此调节下一帧长度
+            if let Some(sizer) = sizer.as_mut() {
+                self.writer.flush().await.map_err(Error::Io)?;
+                sizer.observe(frame_started.elapsed());
+            }
             total_written += read_bytes as u64;
 
             if let Some(cb) = &self.config.progress_callback {
                 cb(bytes_processed, self.config.total_bytes);
             }
+            if is_final { break; }
+        }
+        if let Some(binding) = &binding {
+            binding.check_encrypted(frame_index)?;
+        }
+        if let Some(tally) = &tally {
+            self.writer.write_all(&tally.footer()).await.map_err(Error::Io)?;
         }
         self.writer.flush().await.map_err(Error::Io)?;
         Ok(StreamingResult { bytes_processed: total_written, buffer: None })
@@ -101,20 +179,70 @@ where
     }
 
     pub async fn process(mut self) -> Result<StreamingResult, Error> {
+        if self.config.frame_chaining {
+            return Err(Error::Format("异步流式解密不支持帧链接".to_string()));
+        }
         let mut total_written = 0;
         let mut bytes_processed = 0;
         let mut len_buf = [0u8; 4];
 
+        // 启用帧元数据绑定时，先读取声明的总帧数
+        let binding = if self.config.bind_frame_metadata {
+            let mut header = [0u8; FRAME_HEADER_SIZE];
+            self.reader.read_exact(&mut header).await.map_err(Error::Io)?;
+            bytes_processed += FRAME_HEADER_SIZE as u64;
+            Some(FrameBinding::from_header(header))
+        } else {
+            None
+        };
+        let mut frame_index = 0u64;
+        let mut tally = FrameTally::for_config(&self.config);
+        let mut footer_verified = false;
+        let mut final_seen = false;
+
         while self.reader.read_exact(&mut len_buf).await.is_ok() {
+            self.config.check_cancelled()?;
+            if let Some(tally) = tally.as_ref().filter(|_| u32::from_le_bytes(len_buf) == FOOTER_MARKER) {
+                let mut footer = vec![0u8; tally.footer_len()];
+                self.reader.read_exact(&mut footer).await.map_err(|e| tally.frame_read_error(e))?;
+                tally.verify(&footer)?;
+                if read_full(&mut self.reader, &mut [0u8; 1]).await? != 0 {
+                    return Err(Error::StreamIntegrity("完整性尾部之后存在多余数据".to_string()));
+                }
+                footer_verified = true;
+                break;
+            }
+            if let Some(binding) = binding.as_ref().filter(|_| final_seen) {
+                return Err(binding.frame_after_final());
+            }
+            let is_final = binding.is_some_and(|binding| binding.is_open_ended())
+                && u32::from_le_bytes(len_buf) == FINAL_FRAME_MARKER;
+            if is_final {
+                self.reader.read_exact(&mut len_buf).await.map_err(Error::Io)?;
+                bytes_processed += 4;
+            }
             let block_size = u32::from_le_bytes(len_buf) as usize;
-            let mut ciphertext_buffer = vec![0u8; block_size];
-            self.reader.read_exact(&mut ciphertext_buffer).await.map_err(Error::Io)?;
+            let ciphertext_buffer = match tally.as_mut() {
+                Some(tally) => {
+                    let frame = read_frame(&mut self.reader, block_size).await.map_err(|e| tally.frame_read_error(e))?;
+                    tally.record(&frame);
+                    frame
+                }
+                None => read_frame(&mut self.reader, block_size).await.map_err(Error::Io)?,
+            };
             bytes_processed += (4 + block_size) as u64;
             
-            let ciphertext_str = String::from_utf8(ciphertext_buffer)
-                .map_err(|e| Error::Format(format!("无效的UTF-8密文: {}", e)))?;
+            let with_metadata = self.config.chunk_metadata_callback.is_some();
+            let (metadata, ciphertext_str) = decode_chunk_frame(&ciphertext_buffer, with_metadata)?;
             
-            let plaintext = C::decrypt(self.key, &ciphertext_str, self.additional_data)?;
+            let bound_aad = binding.map(|binding| binding.aad(frame_index, is_final, self.additional_data));
+            let aad = bound_aad.as_deref().or(self.additional_data);
+            let metadata_aad = metadata.map(|metadata| chunk_metadata_aad(aad, metadata));
+            let aad = metadata_aad.as_deref().or(aad);
+            let plaintext = C::decrypt(self.key, ciphertext_str, aad)?;
+            self.config.deliver_chunk_metadata(frame_index, metadata);
+            frame_index += 1;
+            final_seen = is_final;
             
             self.writer.write_all(&plaintext).await.map_err(Error::Io)?;
             total_written += plaintext.len() as u64;
@@ -123,6 +251,13 @@ where
                 cb(bytes_processed, self.config.total_bytes);
             }
         }
+        // 帧数不足或缺少末帧说明流被截断
+        if let Some(binding) = &binding {
+            binding.check_decrypted(frame_index, final_seen)?;
+        }
+        if let Some(tally) = tally.filter(|_| !footer_verified) {
+            return Err(tally.missing_footer());
+        }
         self.writer.flush().await.map_err(Error::Io)?;
         Ok(StreamingResult { bytes_processed: total_written, buffer: None })
     }
@@ -169,6 +304,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::streaming::AdaptiveChunking;
     use crate::common::utils::CryptoConfig;
     use crate::symmetric::systems::aes_gcm::AesGcmSystem;
     use tokio::io::BufReader;
@@ -259,4 +395,169 @@ mod tests {
 
         assert!(result.is_err());
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_async_streaming_frame_reorder_fails() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"0123456789abcdef-second-frame-data".to_vec();
+        let config = StreamingConfig::default()
+            .with_buffer_size(16)
+            .with_total_bytes(original_data.len() as u64)
+            .with_frame_metadata_binding(true);
+
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream_async(&key, Cursor::new(original_data.clone()), &mut encrypted, &config, None)
+            .await
+            .unwrap();
+
+        // 正常解密
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream_async(&key, Cursor::new(encrypted.clone()), &mut decrypted, &config, None)
+            .await
+            .unwrap();
+        assert_eq!(original_data, decrypted);
+
+        // 交换前两帧
+        let (header, rest) = encrypted.split_at(FRAME_HEADER_SIZE);
+        let first_len = 4 + u32::from_le_bytes(rest[..4].try_into().unwrap()) as usize;
+        let (first, rest) = rest.split_at(first_len);
+        let second_len = 4 + u32::from_le_bytes(rest[..4].try_into().unwrap()) as usize;
+        let (second, tail) = rest.split_at(second_len);
+        let reordered = [header, second, first, tail].concat();
+
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream_async(&key, Cursor::new(reordered), &mut decrypted, &config, None).await;
+        assert!(result.is_err());
+
+        // 篡改声明的总帧数
+        let mut tampered = encrypted.clone();
+        tampered[0] ^= 0x01;
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream_async(&key, Cursor::new(tampered), &mut decrypted, &config, None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_async_streaming_footer_detects_truncation() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"0123456789abcdef-second-frame-data".to_vec();
+        let config = StreamingConfig::default()
+            .with_buffer_size(16)
+            .with_integrity_footer(true);
+
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream_async(&key, Cursor::new(original_data.clone()), &mut encrypted, &config, None)
+            .await
+            .unwrap();
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream_async(&key, Cursor::new(encrypted.clone()), &mut decrypted, &config, None)
+            .await
+            .unwrap();
+        assert_eq!(original_data, decrypted);
+
+        // 只保留头部与第一帧
+        let first_end = FRAME_HEADER_SIZE + 4
+            + u32::from_le_bytes(encrypted[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + 4].try_into().unwrap()) as usize;
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream_async(&key, Cursor::new(encrypted[..first_end].to_vec()), &mut decrypted, &config, None).await;
+        assert!(matches!(result, Err(Error::StreamIntegrity(_))));
+    }
+
+    #[tokio::test]
+    async fn test_async_streaming_owned_aad() {
+        let (key, config) = get_test_key_and_config();
+        let original_data = b"Owned AAD can be moved across await points.".to_vec();
+        let owned_aad = format!("session-{}", 7).into_bytes();
+
+        // 所有权AAD加密
+        let mut encrypted_dest = Vec::new();
+        let aad = owned_aad.clone();
+        AesGcmSystem::encrypt_stream_async_owned_aad(&key, Cursor::new(original_data.clone()), &mut encrypted_dest, &config, Some(aad))
+            .await
+            .unwrap();
+
+        // 借用AAD与所有权AAD解密结果一致
+        let mut borrowed_dest = Vec::new();
+        AesGcmSystem::decrypt_stream_async(&key, Cursor::new(encrypted_dest.clone()), &mut borrowed_dest, &config, Some(&owned_aad))
+            .await
+            .unwrap();
+        let mut owned_dest = Vec::new();
+        AesGcmSystem::decrypt_stream_async_owned_aad(&key, Cursor::new(encrypted_dest), &mut owned_dest, &config, Some(owned_aad))
+            .await
+            .unwrap();
+
+        assert_eq!(original_data, borrowed_dest);
+        assert_eq!(borrowed_dest, owned_dest);
+    }
+
+    #[tokio::test]
+    async fn test_async_streaming_adaptive_chunking() {
+        let (key, _) = get_test_key_and_config();
+        let original_data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let adaptive = AdaptiveChunking::new(256, 4096).with_target_latency(std::time::Duration::from_secs(60));
+        let config = StreamingConfig::default().with_adaptive_chunking(adaptive).with_integrity_footer(true);
+
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream_async(&key, Cursor::new(original_data.clone()), &mut encrypted, &config, None)
+            .await
+            .unwrap();
+
+        // 写入端没有背压时帧长度逐帧翻倍，直到上限
+        // 自适应分块的总帧数无法预先确定，流以空末帧结束
+        let mut frame_lens = Vec::new();
+        let mut rest = &encrypted[FRAME_HEADER_SIZE..];
+        loop {
+            let len = u32::from_le_bytes(rest[..4].try_into().unwrap());
+            if len == FINAL_FRAME_MARKER {
+                break;
+            }
+            let frame = std::str::from_utf8(&rest[4..4 + len as usize]).unwrap();
+            frame_lens.push(utils::from_base64(frame).unwrap().len() - 12 - 16);
+            rest = &rest[4 + len as usize..];
+        }
+        assert_eq!(&frame_lens[..5], &[256, 512, 1024, 2048, 4096]);
+        assert!(frame_lens[1..].iter().all(|&len| len > frame_lens[0]));
+        assert!(frame_lens.iter().all(|&len| len <= 4096));
+        assert_eq!(frame_lens.iter().sum::<usize>(), original_data.len());
+
+        // 解密无需自适应分块设置
+        let plain_config = StreamingConfig::default().with_integrity_footer(true);
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream_async(&key, Cursor::new(encrypted), &mut decrypted, &plain_config, None)
+            .await
+            .unwrap();
+        assert_eq!(original_data, decrypted);
+
+        // 即使设置了 total_bytes，帧元数据绑定也按总帧数未知处理
+        let declared = config.clone().with_total_bytes(original_data.len() as u64);
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream_async(&key, Cursor::new(original_data.clone()), &mut encrypted, &declared, None)
+            .await
+            .unwrap();
+        assert_eq!(encrypted[..FRAME_HEADER_SIZE], u64::MAX.to_le_bytes());
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream_async(&key, Cursor::new(encrypted), &mut decrypted, &plain_config, None)
+            .await
+            .unwrap();
+        assert_eq!(original_data, decrypted);
+
+        // 参数无效时拒绝加密
+        let invalid = StreamingConfig::default().with_adaptive_chunking(AdaptiveChunking::new(8192, 4096));
+        let result = AesGcmSystem::encrypt_stream_async(&key, Cursor::new(original_data.clone()), Vec::new(), &invalid, None).await;
+        assert!(matches!(result, Err(Error::Format(_))));
+    }
+
+    #[test]
+    fn test_chunk_sizer_shrinks_under_backpressure() {
+        let adaptive = AdaptiveChunking::new(100, 1000).with_target_latency(std::time::Duration::from_millis(5));
+        let mut sizer = adaptive.sizer().unwrap();
+        let fast = std::time::Duration::from_millis(1);
+        let slow = std::time::Duration::from_millis(50);
+        let mut lens = vec![sizer.next_len()];
+        for elapsed in [fast, fast, fast, fast, slow, slow, slow, slow] {
+            sizer.observe(elapsed);
+            lens.push(sizer.next_len());
+        }
+        assert_eq!(lens, [100, 200, 400, 800, 1000, 500, 250, 125, 100]);
+    }
+}
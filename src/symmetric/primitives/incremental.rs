@@ -0,0 +1,302 @@
+//! 推送式的增量流解密
+//!
+//! 与从 `Read` 拉取数据的 [`SymmetricStreamingDecryptor`](super::SymmetricStreamingDecryptor) 解析相同的帧格式，
+//! 但由调用方以任意大小的片段推送密文（如每次 socket 读取的结果），每凑齐一帧就解密并返回通过认证的明文。
+//! 帧元数据头部、每帧附加元数据与完整性尾部同样受支持。二者至少须启用其一，否则恰好在帧边界处被丢弃的末尾帧
+//! 无法被 `finalize` 发现，因此解密器拒绝以这样的配置创建。
+
+use std::marker::PhantomData;
+
+use crate::common::errors::Error;
+use crate::common::streaming::{chunk_metadata_aad, decode_chunk_frame, FrameBinding, FrameChain, FrameTally, StreamingConfig, FINAL_FRAME_MARKER, FOOTER_MARKER, FRAME_HEADER_SIZE};
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 解析进度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// 等待帧元数据头部
+    Header,
+    /// 逐帧解密
+    Frames,
+    /// 已校验完整性尾部，不再接受数据
+    Done,
+}
+
+/// 增量解密器：`update` 推送密文片段，`finalize` 确认流已完整结束
+///
+/// 任一步骤出错后解密器进入失败状态，之后的调用都会返回错误。
+pub struct IncrementalDecryptor<'a, C: SymmetricCryptographicSystem>
+where
+    Error: From<C::Error>,
+{
+    key: &'a C::Key,
+    config: &'a StreamingConfig,
+    additional_data: Option<&'a [u8]>,
+    pending: Vec<u8>,
+    stage: Stage,
+    binding: Option<FrameBinding>,
+    final_seen: bool,
+    frame_index: u64,
+    tally: Option<FrameTally>,
+    chain: Option<FrameChain>,
+    bytes_processed: u64,
+    poisoned: bool,
+    _phantom: PhantomData<C>,
+}
+
+impl<'a, C: SymmetricCryptographicSystem> IncrementalDecryptor<'a, C>
+where
+    Error: From<C::Error>,
+{
+    /// 创建增量解密器，`config` 须与加密时的帧元数据绑定、完整性尾部设置一致
+    ///
+    /// 既未启用帧元数据绑定，也未启用完整性尾部（或流全局认证密钥）时返回 `Error::InsecureParameter`。
+    pub fn new(key: &'a C::Key, config: &'a StreamingConfig, additional_data: Option<&'a [u8]>) -> Result<Self, Error> {
+        if !config.bind_frame_metadata && !config.integrity_footer && config.stream_mac_key.is_none() {
+            return Err(Error::InsecureParameter(vec![
+                "增量解密须启用帧元数据绑定或完整性尾部，否则无法发现在帧边界处被截断的流".to_string(),
+            ]));
+        }
+        Ok(Self {
+            key,
+            config,
+            additional_data,
+            pending: Vec::new(),
+            stage: if config.bind_frame_metadata { Stage::Header } else { Stage::Frames },
+            binding: None,
+            final_seen: false,
+            frame_index: 0,
+            tally: FrameTally::for_config(config),
+            chain: FrameChain::for_config(config),
+            bytes_processed: 0,
+            poisoned: false,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// 推送一段密文，返回其中完整帧解密得到的明文；不完整的帧留待后续数据
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.poisoned {
+            return Err(Error::Operation("增量解密器已因先前的错误失效".to_string()));
+        }
+        self.pending.extend_from_slice(data);
+        let result = self.drain_frames();
+        self.poisoned = result.is_err();
+        result
+    }
+
+    /// 结束解密，确认没有残留的不完整数据且流未被截断
+    pub fn finalize(self) -> Result<(), Error> {
+        if self.poisoned {
+            return Err(Error::Operation("增量解密器已因先前的错误失效".to_string()));
+        }
+        if self.stage == Stage::Header {
+            return Err(Error::Format("流在帧元数据头部之前结束".to_string()));
+        }
+        if !self.pending.is_empty() {
+            let message = format!("第 {} 帧不完整，流被截断", self.frame_index + 1);
+            return Err(match self.tally {
+                Some(_) => Error::StreamIntegrity(message),
+                None => Error::Format(message),
+            });
+        }
+        if let Some(binding) = &self.binding {
+            binding.check_decrypted(self.frame_index, self.final_seen)?;
+        }
+        if let Some(tally) = self.tally.as_ref().filter(|_| self.stage != Stage::Done) {
+            return Err(tally.missing_footer());
+        }
+        Ok(())
+    }
+
+    fn drain_frames(&mut self) -> Result<Vec<u8>, Error> {
+        let mut plaintext = Vec::new();
+        let mut consumed = 0;
+        loop {
+            let available = &self.pending[consumed..];
+            match self.stage {
+                Stage::Header => {
+                    let Some(header) = available.get(..FRAME_HEADER_SIZE) else { break };
+                    self.binding = Some(FrameBinding::from_header(header.try_into().expect("头部长度固定")));
+                    consumed += FRAME_HEADER_SIZE;
+                    self.bytes_processed += FRAME_HEADER_SIZE as u64;
+                    self.stage = Stage::Frames;
+                }
+                Stage::Frames => {
+                    let Some(prefix) = available.get(..4) else { break };
+                    let block_size = u32::from_le_bytes(prefix.try_into().expect("长度前缀固定为4字节"));
+                    if let Some(tally) = self.tally.as_ref().filter(|_| block_size == FOOTER_MARKER) {
+                        let footer_len = tally.footer_len();
+                        let Some(footer) = available.get(4..4 + footer_len) else { break };
+                        tally.verify(footer)?;
+                        consumed += 4 + footer_len;
+                        self.stage = Stage::Done;
+                        continue;
+                    }
+                    if let Some(binding) = self.binding.as_ref().filter(|_| self.final_seen) {
+                        return Err(binding.frame_after_final());
+                    }
+                    // 末帧标记之后才是末帧真实的长度前缀
+                    let is_final = self.binding.is_some_and(|binding| binding.is_open_ended()) && block_size == FINAL_FRAME_MARKER;
+                    let (prefix_len, block_size) = if is_final {
+                        let Some(prefix) = available.get(4..8) else { break };
+                        (8, u32::from_le_bytes(prefix.try_into().expect("长度前缀固定为4字节")))
+                    } else {
+                        (4, block_size)
+                    };
+                    let Some(frame) = available.get(prefix_len..prefix_len + block_size as usize) else { break };
+                    if let Some(tally) = self.tally.as_mut() {
+                        tally.record(frame);
+                    }
+                    let (metadata, ciphertext) = decode_chunk_frame(frame, self.config.chunk_metadata_callback.is_some())?;
+                    let bound_aad = self.binding.map(|binding| binding.aad(self.frame_index, is_final, self.additional_data));
+                    let aad = bound_aad.as_deref().or(self.additional_data);
+                    let chained_aad = self.chain.as_ref().map(|chain| chain.aad(aad));
+                    let aad = chained_aad.as_deref().or(aad);
+                    let metadata_aad = metadata.map(|metadata| chunk_metadata_aad(aad, metadata));
+                    let aad = metadata_aad.as_deref().or(aad);
+                    plaintext.extend_from_slice(&C::decrypt(self.key, ciphertext, aad)?);
+                    if let Some(chain) = self.chain.as_mut() {
+                        chain.advance(frame);
+                    }
+                    self.config.deliver_chunk_metadata(self.frame_index, metadata);
+                    self.frame_index += 1;
+                    self.final_seen = is_final;
+                    consumed += prefix_len + block_size as usize;
+                    self.bytes_processed += (prefix_len + block_size as usize) as u64;
+                    if let Some(cb) = &self.config.progress_callback {
+                        cb(self.bytes_processed, self.config.total_bytes);
+                    }
+                }
+                Stage::Done => {
+                    if !available.is_empty() {
+                        return Err(Error::StreamIntegrity("完整性尾部之后存在多余数据".to_string()));
+                    }
+                    break;
+                }
+            }
+        }
+        self.pending.drain(..consumed);
+        Ok(plaintext)
+    }
+}
+
+#[cfg(all(test, feature = "aes-gcm-feature"))]
+mod tests {
+    use super::*;
+    use crate::common::utils::CryptoConfig;
+    use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+    use crate::symmetric::traits::SymmetricSyncStreamingSystem;
+    use std::io::Cursor;
+
+    const DATA: &[u8] = b"Socket reads rarely line up with frame boundaries, so the decryptor buffers partial frames.";
+
+    fn encrypt(key: &AesGcmKey, config: &StreamingConfig) -> Vec<u8> {
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(key, Cursor::new(DATA), &mut encrypted, config, Some(b"aad")).unwrap();
+        encrypted
+    }
+
+    fn decrypt_in_pieces(key: &AesGcmKey, config: &StreamingConfig, encrypted: &[u8], piece: usize) -> Result<Vec<u8>, Error> {
+        let mut decryptor = IncrementalDecryptor::<AesGcmSystem>::new(key, config, Some(b"aad"))?;
+        let mut plaintext = Vec::new();
+        for chunk in encrypted.chunks(piece) {
+            plaintext.extend(decryptor.update(chunk)?);
+        }
+        decryptor.finalize()?;
+        Ok(plaintext)
+    }
+
+    fn configs() -> Vec<StreamingConfig> {
+        let base = StreamingConfig::default().with_buffer_size(16);
+        vec![
+            base.clone().with_frame_metadata_binding(false).with_integrity_footer(true),
+            base.clone(),
+            base.clone().with_total_bytes(DATA.len() as u64).with_frame_metadata_binding(true),
+            base.with_total_bytes(DATA.len() as u64).with_frame_metadata_binding(true).with_integrity_footer(true),
+        ]
+    }
+
+    #[test]
+    fn test_split_at_any_boundary_gives_identical_output() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let chained = StreamingConfig::default().with_buffer_size(16).with_frame_chaining(true).with_integrity_footer(true);
+        for config in configs().into_iter().chain([chained]) {
+            let encrypted = encrypt(&key, &config);
+            for piece in [1, 2, 3, 7, 16, 31, 64, encrypted.len()] {
+                assert_eq!(decrypt_in_pieces(&key, &config, &encrypted, piece).unwrap(), DATA, "piece = {}", piece);
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_emits_plaintext_per_complete_frame() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let config = StreamingConfig::default().with_buffer_size(16).with_integrity_footer(true);
+        let encrypted = encrypt(&key, &config);
+        let first_frame_len = FRAME_HEADER_SIZE + 4
+            + u32::from_le_bytes(encrypted[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + 4].try_into().unwrap()) as usize;
+
+        let mut decryptor = IncrementalDecryptor::<AesGcmSystem>::new(&key, &config, Some(b"aad")).unwrap();
+        assert!(decryptor.update(&encrypted[..first_frame_len - 1]).unwrap().is_empty());
+        assert_eq!(decryptor.update(&encrypted[first_frame_len - 1..first_frame_len]).unwrap(), &DATA[..16]);
+    }
+
+    #[test]
+    fn test_dropped_final_frame_fails_finalize() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        // 既无尾部也无帧元数据时无法发现被丢弃的末尾帧，解密器拒绝创建
+        let unbound = StreamingConfig::default().with_buffer_size(16).with_frame_metadata_binding(false);
+        let result = IncrementalDecryptor::<AesGcmSystem>::new(&key, &unbound, Some(b"aad"));
+        assert!(matches!(result, Err(Error::InsecureParameter(_))));
+
+        for config in configs() {
+            let encrypted = encrypt(&key, &config);
+            // 定位最后一个数据帧并将其删除，保留其后的尾部
+            let header_len = if config.bind_frame_metadata { FRAME_HEADER_SIZE } else { 0 };
+            let mut offset = header_len;
+            let mut frame_starts = Vec::new();
+            while offset < encrypted.len() && u32::from_le_bytes(encrypted[offset..offset + 4].try_into().unwrap()) != FOOTER_MARKER {
+                frame_starts.push(offset);
+                if u32::from_le_bytes(encrypted[offset..offset + 4].try_into().unwrap()) == FINAL_FRAME_MARKER {
+                    offset += 4;
+                }
+                offset += 4 + u32::from_le_bytes(encrypted[offset..offset + 4].try_into().unwrap()) as usize;
+            }
+            let last = *frame_starts.last().unwrap();
+            let without_last_frame = [&encrypted[..last], &encrypted[offset..]].concat();
+            let mut truncations = vec![&without_last_frame[..], &encrypted[..encrypted.len() - 3]];
+            if config.integrity_footer {
+                truncations.push(&encrypted[..offset]);
+            }
+
+            for truncated in truncations {
+                let mut decryptor = IncrementalDecryptor::<AesGcmSystem>::new(&key, &config, Some(b"aad")).unwrap();
+                let result = decryptor.update(truncated).and_then(|_| decryptor.finalize());
+                assert!(result.is_err());
+            }
+        }
+
+        // 只绑定帧元数据、未启用尾部时，帧中途截断同样被 finalize 发现
+        let config = StreamingConfig::default().with_buffer_size(16)
+            .with_total_bytes(DATA.len() as u64)
+            .with_frame_metadata_binding(true);
+        let encrypted = encrypt(&key, &config);
+        let mut decryptor = IncrementalDecryptor::<AesGcmSystem>::new(&key, &config, Some(b"aad")).unwrap();
+        decryptor.update(&encrypted[..encrypted.len() - 1]).unwrap();
+        assert!(matches!(decryptor.finalize(), Err(Error::Format(_))));
+    }
+
+    #[test]
+    fn test_tampered_frame_poisons_decryptor() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let config = StreamingConfig::default().with_buffer_size(16).with_integrity_footer(true);
+        let mut encrypted = encrypt(&key, &config);
+        encrypted[FRAME_HEADER_SIZE + 10] ^= 1;
+
+        let mut decryptor = IncrementalDecryptor::<AesGcmSystem>::new(&key, &config, Some(b"aad")).unwrap();
+        assert!(decryptor.update(&encrypted).is_err());
+        assert!(decryptor.update(&[]).is_err());
+        assert!(decryptor.finalize().is_err());
+    }
+}
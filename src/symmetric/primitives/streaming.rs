@@ -3,8 +3,14 @@ use std::io::{Read, Write};
 use std::marker::PhantomData;
 
 use crate::common::errors::Error;
-use crate::common::streaming::{StreamingConfig, StreamingResult};
+use crate::common::streaming::{chunk_metadata_aad, decode_chunk_frame, encode_chunk_frame, read_footer, read_frame, read_full, FrameBinding, FrameChain, FrameTally, StreamingConfig, StreamingResult, FINAL_FRAME_MARKER, FOOTER_MARKER, FRAME_HEADER_SIZE};
 use crate::symmetric::traits::{SymmetricCryptographicSystem, SymmetricSyncStreamingSystem};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// 并行模式下每批读取的帧数相对于线程数的倍数
+#[cfg(feature = "parallel")]
+const PARALLEL_FRAMES_PER_THREAD: usize = 4;
 /// 对称流式加密器
 pub struct SymmetricStreamingEncryptor<'a, C: SymmetricCryptographicSystem, R: Read, W: Write>
 where
@@ -44,26 +50,63 @@ where
 
     /// 执行流式加密
     pub fn process(mut self) -> Result<StreamingResult, Error> {
+        #[cfg(feature = "async-engine")]
+        if self.config.adaptive_chunking.is_some() {
+            return Err(Error::Format("同步流式加密不支持自适应分块".to_string()));
+        }
         let mut buffer = vec![0u8; self.config.buffer_size];
         let mut total_written = 0;
         let mut mem_buffer = if self.config.keep_in_memory { Some(Vec::new()) } else { None };
 
+        // 启用帧元数据绑定时，先写入声明的总帧数
+        let binding = FrameBinding::for_encryption(self.config)?;
+        if let Some(binding) = &binding {
+            self.writer.write_all(&binding.header())?;
+        }
+        let mut frame_index = 0u64;
+        let mut tally = FrameTally::for_config(self.config);
+        let mut chain = FrameChain::for_config(self.config);
+        let mut chunks = self.config.content_defined_chunking.map(|chunker| chunker.reader());
+
         loop {
-            let read_bytes = self.reader.read(&mut buffer)?;
-            if read_bytes == 0 {
+            let read_bytes = match chunks.as_mut() {
+                Some(chunks) => chunks.next_into(&mut self.reader, &mut buffer)?,
+                None if binding.is_some() => read_full(&mut self.reader, &mut buffer)?,
+                None => self.reader.read(&mut buffer)?,
+            };
+            // 总帧数未知时以一个空的末帧结束流
+            let is_final = read_bytes == 0 && binding.is_some_and(|binding| binding.is_open_ended());
+            if read_bytes == 0 && !is_final {
                 break;
             }
             self.bytes_processed += read_bytes as u64;
 
             let plaintext = &buffer[..read_bytes];
-            let ciphertext_obj = C::encrypt(self.key, plaintext, self.additional_data)?;
-            let ciphertext_str = ciphertext_obj.to_string();
-            let ciphertext_bytes = ciphertext_str.as_bytes();
+            let bound_aad = binding.map(|binding| binding.aad(frame_index, is_final, self.additional_data));
+            let aad = bound_aad.as_deref().or(self.additional_data);
+            let chained_aad = chain.as_ref().map(|chain| chain.aad(aad));
+            let aad = chained_aad.as_deref().or(aad);
+            let metadata = self.config.chunk_metadata(frame_index, plaintext)?;
+            let metadata_aad = metadata.as_deref().map(|metadata| chunk_metadata_aad(aad, metadata));
+            let aad = metadata_aad.as_deref().or(aad);
+            let ciphertext_obj = C::encrypt(self.key, plaintext, aad)?;
+            frame_index += 1;
+            let frame = encode_chunk_frame(metadata.as_deref(), ciphertext_obj.to_string());
+            let ciphertext_bytes = frame.as_slice();
             
             // 写入长度前缀和密文
+            if is_final {
+                self.writer.write_all(&FINAL_FRAME_MARKER.to_le_bytes())?;
+            }
             let len = ciphertext_bytes.len() as u32;
             self.writer.write_all(&len.to_le_bytes())?;
             self.writer.write_all(ciphertext_bytes)?;
+            if let Some(tally) = tally.as_mut() {
+                tally.record(ciphertext_bytes);
+            }
+            if let Some(chain) = chain.as_mut() {
+                chain.advance(ciphertext_bytes);
+            }
 
             total_written += read_bytes as u64; // We track original bytes processed
 
@@ -74,6 +117,16 @@ where
             if let Some(cb) = &self.config.progress_callback {
                 cb(self.bytes_processed, self.config.total_bytes);
             }
+            if is_final {
+                break;
+            }
+        }
+
+        if let Some(binding) = &binding {
+            binding.check_encrypted(frame_index)?;
+        }
+        if let Some(tally) = &tally {
+            self.writer.write_all(&tally.footer())?;
         }
 
         self.writer.flush()?;
@@ -127,22 +180,69 @@ where
         let mut mem_buffer = if self.config.keep_in_memory { Some(Vec::new()) } else { None };
         let mut len_buf = [0u8; 4];
 
+        // 启用帧元数据绑定时，先读取声明的总帧数
+        let binding = if self.config.bind_frame_metadata {
+            let mut header = [0u8; FRAME_HEADER_SIZE];
+            self.reader.read_exact(&mut header)?;
+            self.bytes_processed += FRAME_HEADER_SIZE as u64;
+            Some(FrameBinding::from_header(header))
+        } else {
+            None
+        };
+        let mut frame_index = 0u64;
+        let mut tally = FrameTally::for_config(self.config);
+        let mut chain = FrameChain::for_config(self.config);
+        let mut footer_verified = false;
+        let mut final_seen = false;
+
         loop {
             match self.reader.read_exact(&mut len_buf) {
                 Ok(_) => (),
                 Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
                 Err(e) => return Err(e.into()),
             }
+            if let Some(tally) = tally.as_ref().filter(|_| u32::from_le_bytes(len_buf) == FOOTER_MARKER) {
+                read_footer(&mut self.reader, tally)?;
+                footer_verified = true;
+                break;
+            }
+            if let Some(binding) = binding.as_ref().filter(|_| final_seen) {
+                return Err(binding.frame_after_final());
+            }
+            let is_final = binding.is_some_and(|binding| binding.is_open_ended())
+                && u32::from_le_bytes(len_buf) == FINAL_FRAME_MARKER;
+            if is_final {
+                self.reader.read_exact(&mut len_buf)?;
+                self.bytes_processed += 4;
+            }
 
             let block_size = u32::from_le_bytes(len_buf) as usize;
-            let mut ciphertext_buffer = vec![0u8; block_size];
-            self.reader.read_exact(&mut ciphertext_buffer)?;
+            let ciphertext_buffer = match tally.as_mut() {
+                Some(tally) => {
+                    let frame = read_frame(&mut self.reader, block_size).map_err(|e| tally.frame_read_error(e))?;
+                    tally.record(&frame);
+                    frame
+                }
+                None => read_frame(&mut self.reader, block_size)?,
+            };
             self.bytes_processed += (4 + block_size) as u64;
 
-            let ciphertext_str = String::from_utf8(ciphertext_buffer)
-                .map_err(|e| Error::Format(format!("无效的UTF-8密文: {}", e)))?;
+            let with_metadata = self.config.chunk_metadata_callback.is_some();
+            let (metadata, ciphertext_str) = decode_chunk_frame(&ciphertext_buffer, with_metadata)?;
 
-            let plaintext = C::decrypt(self.key, &ciphertext_str, self.additional_data)?;
+            let bound_aad = binding.map(|binding| binding.aad(frame_index, is_final, self.additional_data));
+            let aad = bound_aad.as_deref().or(self.additional_data);
+            let chained_aad = chain.as_ref().map(|chain| chain.aad(aad));
+            let aad = chained_aad.as_deref().or(aad);
+            let metadata_aad = metadata.map(|metadata| chunk_metadata_aad(aad, metadata));
+            let aad = metadata_aad.as_deref().or(aad);
+            let plaintext = C::decrypt(self.key, ciphertext_str, aad)?;
+            if let Some(chain) = chain.as_mut() {
+                chain.advance(&ciphertext_buffer);
+            }
+            self.config.deliver_chunk_metadata(frame_index, metadata);
+            frame_index += 1;
+            final_seen = is_final;
             
             self.writer.write_all(&plaintext)?;
             total_written += plaintext.len() as u64;
@@ -157,6 +257,14 @@ where
             }
         }
 
+        // 帧数不足或缺少末帧说明流被截断
+        if let Some(binding) = &binding {
+            binding.check_decrypted(frame_index, final_seen)?;
+        }
+        if let Some(tally) = tally.filter(|_| !footer_verified) {
+            return Err(tally.missing_footer());
+        }
+
         self.writer.flush()?;
         Ok(StreamingResult {
             bytes_processed: total_written,
@@ -192,9 +300,320 @@ where
     }
 }
 
+/// 在配置的线程池（或 Rayon 全局线程池）中执行并行任务
+#[cfg(feature = "parallel")]
+fn run_in_pool<T: Send>(config: &StreamingConfig, op: impl FnOnce() -> T + Send) -> T {
+    match config.thread_pool.as_deref() {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+/// 每批读取的帧数，使内存占用有界
+#[cfg(feature = "parallel")]
+fn parallel_batch_len(config: &StreamingConfig) -> usize {
+    let threads = config.thread_pool.as_deref()
+        .map_or_else(rayon::current_num_threads, |pool| pool.current_num_threads());
+    threads.max(1) * PARALLEL_FRAMES_PER_THREAD
+}
+
+/// 并行流式加密：按批读取数据块，在线程池中并行加密后按原顺序写出
+///
+/// 输出格式与 [`SymmetricSyncStreamingSystem::encrypt_stream`] 完全相同，可由串行或并行解密读取。
+/// 每帧由工作线程各自从系统RNG获取随机 Nonce，互不共享状态，因此并行不会导致 Nonce 重复；
+/// 同时这也意味着确定性测试模式的种子RNG不会作用于并行加密。
+#[cfg(feature = "parallel")]
+pub fn encrypt_stream_parallel<C, R, W>(
+    key: &C::Key,
+    mut reader: R,
+    mut writer: W,
+    config: &StreamingConfig,
+    additional_data: Option<&[u8]>,
+) -> Result<StreamingResult, Error>
+where
+    C: SymmetricCryptographicSystem,
+    C::Key: Sync,
+    C::Error: Send,
+    Error: From<C::Error>,
+    R: Read,
+    W: Write,
+{
+    #[cfg(feature = "async-engine")]
+    if config.adaptive_chunking.is_some() {
+        return Err(Error::Format("并行流式加密不支持自适应分块".to_string()));
+    }
+    if config.frame_chaining {
+        return Err(Error::Format("帧链接要求逐帧串行加密，并行流式加密不支持".to_string()));
+    }
+    let batch_len = parallel_batch_len(config);
+    let mut mem_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
+    let mut bytes_processed = 0u64;
+
+    // 启用帧元数据绑定时，先写入声明的总帧数
+    let binding = FrameBinding::for_encryption(config)?;
+    if let Some(binding) = &binding {
+        writer.write_all(&binding.header())?;
+    }
+    let mut frame_index = 0u64;
+    let mut tally = FrameTally::for_config(config);
+    let mut chunks = config.content_defined_chunking.map(|chunker| chunker.reader());
+    let mut finished = false;
+
+    while !finished {
+        let mut batch = Vec::with_capacity(batch_len);
+        while batch.len() < batch_len {
+            let mut chunk = Vec::new();
+            let read_bytes = match chunks.as_mut() {
+                Some(chunks) => chunks.next_into(&mut reader, &mut chunk)?,
+                None => {
+                    chunk.resize(config.buffer_size, 0);
+                    read_full(&mut reader, &mut chunk)?
+                }
+            };
+            if read_bytes == 0 {
+                finished = true;
+                // 总帧数未知时以一个空的末帧结束流
+                if binding.is_some_and(|binding| binding.is_open_ended()) {
+                    batch.push(Vec::new());
+                }
+                break;
+            }
+            chunk.truncate(read_bytes);
+            batch.push(chunk);
+        }
+        if batch.is_empty() {
+            break;
+        }
+        let final_offset = binding.filter(|binding| finished && binding.is_open_ended()).map(|_| batch.len() - 1);
+
+        let first_index = frame_index;
+        let ciphertexts = run_in_pool(config, || {
+            batch.par_iter()
+                .enumerate()
+                .map(|(offset, plaintext)| {
+                    let index = first_index + offset as u64;
+                    let is_final = final_offset == Some(offset);
+                    let bound_aad = binding.map(|binding| binding.aad(index, is_final, additional_data));
+                    let aad = bound_aad.as_deref().or(additional_data);
+                    let metadata = config.chunk_metadata(index, plaintext)?;
+                    let metadata_aad = metadata.as_deref().map(|metadata| chunk_metadata_aad(aad, metadata));
+                    let aad = metadata_aad.as_deref().or(aad);
+                    let ciphertext = C::encrypt(key, plaintext, aad)?;
+                    Ok(encode_chunk_frame(metadata.as_deref(), ciphertext.to_string()))
+                })
+                .collect::<Result<Vec<_>, Error>>()
+        })?;
+
+        for (offset, (plaintext, ciphertext)) in batch.iter().zip(&ciphertexts).enumerate() {
+            let ciphertext_bytes = ciphertext.as_slice();
+            if final_offset == Some(offset) {
+                writer.write_all(&FINAL_FRAME_MARKER.to_le_bytes())?;
+            }
+            writer.write_all(&(ciphertext_bytes.len() as u32).to_le_bytes())?;
+            writer.write_all(ciphertext_bytes)?;
+            if let Some(tally) = tally.as_mut() {
+                tally.record(ciphertext_bytes);
+            }
+            if let Some(ref mut buf) = mem_buffer {
+                buf.extend_from_slice(ciphertext_bytes);
+            }
+            bytes_processed += plaintext.len() as u64;
+            frame_index += 1;
+        }
+
+        if let Some(cb) = &config.progress_callback {
+            cb(bytes_processed, config.total_bytes);
+        }
+    }
+
+    if let Some(binding) = &binding {
+        binding.check_encrypted(frame_index)?;
+    }
+    if let Some(tally) = &tally {
+        writer.write_all(&tally.footer())?;
+    }
+
+    writer.flush()?;
+    Ok(StreamingResult {
+        bytes_processed,
+        buffer: mem_buffer,
+    })
+}
+
+/// 并行流式解密：按批读取密文帧，在线程池中并行解密后按原顺序写出
+#[cfg(feature = "parallel")]
+pub fn decrypt_stream_parallel<C, R, W>(
+    key: &C::Key,
+    mut reader: R,
+    mut writer: W,
+    config: &StreamingConfig,
+    additional_data: Option<&[u8]>,
+) -> Result<StreamingResult, Error>
+where
+    C: SymmetricCryptographicSystem,
+    C::Key: Sync,
+    C::Error: Send,
+    Error: From<C::Error>,
+    R: Read,
+    W: Write,
+{
+    let batch_len = parallel_batch_len(config);
+    let mut mem_buffer = if config.keep_in_memory { Some(Vec::new()) } else { None };
+    let mut bytes_read = 0u64;
+    let mut total_written = 0u64;
+
+    // 启用帧元数据绑定时，先读取声明的总帧数
+    let binding = if config.bind_frame_metadata {
+        let mut header = [0u8; FRAME_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        bytes_read += FRAME_HEADER_SIZE as u64;
+        Some(FrameBinding::from_header(header))
+    } else {
+        None
+    };
+    let mut frame_index = 0u64;
+    let mut eof = false;
+    let mut tally = FrameTally::for_config(config);
+    let mut chain = FrameChain::for_config(config);
+    let mut footer_verified = false;
+    let mut final_seen = false;
+
+    while !eof {
+        let mut batch = Vec::with_capacity(batch_len);
+        while batch.len() < batch_len {
+            let mut len_buf = [0u8; 4];
+            match reader.read_exact(&mut len_buf) {
+                Ok(_) => (),
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    eof = true;
+                    break;
+                }
+                Err(e) => return Err(e.into()),
+            }
+            if let Some(tally) = tally.as_ref().filter(|_| u32::from_le_bytes(len_buf) == FOOTER_MARKER) {
+                read_footer(&mut reader, tally)?;
+                footer_verified = true;
+                eof = true;
+                break;
+            }
+            if let Some(binding) = binding.as_ref().filter(|_| final_seen) {
+                return Err(binding.frame_after_final());
+            }
+            final_seen = binding.is_some_and(|binding| binding.is_open_ended())
+                && u32::from_le_bytes(len_buf) == FINAL_FRAME_MARKER;
+            if final_seen {
+                reader.read_exact(&mut len_buf)?;
+                bytes_read += 4;
+            }
+            let block_size = u32::from_le_bytes(len_buf) as usize;
+            let ciphertext_buffer = match tally.as_mut() {
+                Some(tally) => {
+                    let frame = read_frame(&mut reader, block_size).map_err(|e| tally.frame_read_error(e))?;
+                    tally.record(&frame);
+                    frame
+                }
+                None => read_frame(&mut reader, block_size)?,
+            };
+            bytes_read += (4 + block_size) as u64;
+            // 帧链接只依赖密文，读取时即可确定每帧链接的上一帧摘要
+            let link = chain.clone();
+            if let Some(chain) = chain.as_mut() {
+                chain.advance(&ciphertext_buffer);
+            }
+            batch.push((ciphertext_buffer, link, final_seen));
+        }
+
+        let first_index = frame_index;
+        let with_metadata = config.chunk_metadata_callback.is_some();
+        let plaintexts = run_in_pool(config, || {
+            batch.par_iter()
+                .enumerate()
+                .map(|(offset, (frame, link, is_final))| {
+                    let (metadata, ciphertext) = decode_chunk_frame(frame, with_metadata)?;
+                    let bound_aad = binding
+                        .map(|binding| binding.aad(first_index + offset as u64, *is_final, additional_data));
+                    let aad = bound_aad.as_deref().or(additional_data);
+                    let chained_aad = link.as_ref().map(|link| link.aad(aad));
+                    let aad = chained_aad.as_deref().or(aad);
+                    let metadata_aad = metadata.map(|metadata| chunk_metadata_aad(aad, metadata));
+                    let aad = metadata_aad.as_deref().or(aad);
+                    Ok((metadata, C::decrypt(key, ciphertext, aad)?))
+                })
+                .collect::<Result<Vec<_>, Error>>()
+        })?;
+
+        for (offset, (metadata, plaintext)) in plaintexts.iter().enumerate() {
+            config.deliver_chunk_metadata(first_index + offset as u64, *metadata);
+            writer.write_all(plaintext)?;
+            if let Some(ref mut buf) = mem_buffer {
+                buf.extend_from_slice(plaintext);
+            }
+            total_written += plaintext.len() as u64;
+        }
+        frame_index += plaintexts.len() as u64;
+
+        if let Some(cb) = &config.progress_callback {
+            cb(bytes_read, config.total_bytes);
+        }
+    }
+
+    // 帧数不足或缺少末帧说明流被截断
+    if let Some(binding) = &binding {
+        binding.check_decrypted(frame_index, final_seen)?;
+    }
+    if let Some(tally) = tally.filter(|_| !footer_verified) {
+        return Err(tally.missing_footer());
+    }
+
+    writer.flush()?;
+    Ok(StreamingResult {
+        bytes_processed: total_written,
+        buffer: mem_buffer,
+    })
+}
+
+/// 只检查帧结构与完整性尾部，不执行任何AEAD运算，返回流中的帧数
+///
+/// 适用于在解密前廉价地确认传输是否完整；流必须以启用 `integrity_footer` 或设置了 `stream_mac_key` 的配置生成。
+/// 设置了全局认证密钥时同时校验全局认证标签，可确认帧的顺序与数量未被改动；
+/// 但帧内容本身是否可信仍由解密完成认证。
+pub fn verify_stream_integrity<R: Read>(mut reader: R, config: &StreamingConfig) -> Result<u64, Error> {
+    let Some(mut tally) = FrameTally::for_config(config) else {
+        return Err(Error::Format("未启用完整性尾部，无法校验流完整性".to_string()));
+    };
+    let binding = if config.bind_frame_metadata {
+        let mut header = [0u8; FRAME_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        Some(FrameBinding::from_header(header))
+    } else {
+        None
+    };
+
+    let mut len_buf = [0u8; 4];
+    loop {
+        match reader.read_exact(&mut len_buf) {
+            Ok(_) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Err(tally.missing_footer()),
+            Err(e) => return Err(e.into()),
+        }
+        let mut block_size = u32::from_le_bytes(len_buf);
+        if block_size == FOOTER_MARKER {
+            read_footer(&mut reader, &tally)?;
+            return Ok(tally.frames());
+        }
+        if block_size == FINAL_FRAME_MARKER && binding.is_some_and(|binding| binding.is_open_ended()) {
+            reader.read_exact(&mut len_buf).map_err(|e| tally.frame_read_error(e))?;
+            block_size = u32::from_le_bytes(len_buf);
+        }
+        let frame = read_frame(&mut reader, block_size as usize).map_err(|e| tally.frame_read_error(e))?;
+        tally.record(&frame);
+    }
+}
+
 #[cfg(all(test, feature = "aes-gcm-feature"))]
 mod tests {
     use super::*;
+    use crate::common::streaming::{frame_aad, OPEN_ENDED_FRAME_COUNT};
     use crate::common::utils::CryptoConfig;
     use crate::symmetric::systems::aes_gcm::AesGcmSystem;
     use std::io::Cursor;
@@ -335,4 +754,571 @@ mod tests {
         assert_eq!(dec_result.bytes_processed, 0);
         assert_eq!(decrypted_dest.into_inner().as_slice(), b"");
     }
-} 
\ No newline at end of file
+
+    /// 将帧流拆分为 (头部, 各帧) 以便测试重排/篡改
+    fn split_frames(data: &[u8]) -> (Vec<u8>, Vec<Vec<u8>>) {
+        let (header, rest) = data.split_at(FRAME_HEADER_SIZE);
+        (header.to_vec(), frame_list(rest))
+    }
+
+    /// 末帧标记与其后的末帧合为一项
+    fn frame_list(mut rest: &[u8]) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        while !rest.is_empty() {
+            let marker = if u32::from_le_bytes(rest[..4].try_into().unwrap()) == FINAL_FRAME_MARKER { 4 } else { 0 };
+            let len = u32::from_le_bytes(rest[marker..marker + 4].try_into().unwrap()) as usize;
+            frames.push(rest[..marker + 4 + len].to_vec());
+            rest = &rest[marker + 4 + len..];
+        }
+        frames
+    }
+
+    fn encrypt_bound(
+        key: &<AesGcmSystem as SymmetricCryptographicSystem>::Key,
+        data: &[u8],
+        aad: Option<&[u8]>,
+    ) -> (StreamingConfig, Vec<u8>) {
+        let config = StreamingConfig::default()
+            .with_buffer_size(16)
+            .with_total_bytes(data.len() as u64)
+            .with_frame_metadata_binding(true);
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(key, Cursor::new(data), &mut encrypted, &config, aad).unwrap();
+        (config, encrypted)
+    }
+
+    #[test]
+    fn test_streaming_frame_metadata_roundtrip() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"Frame metadata is bound as AAD for every chunk of this stream.";
+
+        for aad in [None, Some(b"user aad".as_ref())] {
+            let (config, encrypted) = encrypt_bound(&key, original_data, aad);
+            let (_, frames) = split_frames(&encrypted);
+            assert_eq!(frames.len(), original_data.len().div_ceil(16));
+
+            let mut decrypted = Vec::new();
+            AesGcmSystem::decrypt_stream(&key, Cursor::new(encrypted), &mut decrypted, &config, aad).unwrap();
+            assert_eq!(decrypted, original_data);
+        }
+    }
+
+    #[test]
+    fn test_streaming_frame_reorder_fails() {
+        let (key, _) = get_test_key_and_config();
+        let (config, encrypted) = encrypt_bound(&key, b"0123456789abcdef-second-frame-data", None);
+
+        let (header, mut frames) = split_frames(&encrypted);
+        frames.swap(0, 1);
+        let reordered = [header, frames.concat()].concat();
+
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(reordered), &mut decrypted, &config, None);
+        assert!(matches!(result, Err(Error::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_streaming_frame_total_tampered_fails() {
+        let (key, _) = get_test_key_and_config();
+        let (config, encrypted) = encrypt_bound(&key, b"0123456789abcdef-second-frame-data", None);
+
+        let (header, frames) = split_frames(&encrypted);
+        let declared = u64::from_le_bytes(header.try_into().unwrap());
+        let tampered = [(declared + 1).to_le_bytes().to_vec(), frames.concat()].concat();
+
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(tampered), &mut decrypted, &config, None);
+        assert!(matches!(result, Err(Error::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_streaming_frame_truncation_fails() {
+        let (key, _) = get_test_key_and_config();
+        let (config, encrypted) = encrypt_bound(&key, b"0123456789abcdef-second-frame-data", None);
+
+        let (header, frames) = split_frames(&encrypted);
+        let truncated = [header, frames[0].clone()].concat();
+
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(truncated), &mut decrypted, &config, None);
+        assert!(matches!(result, Err(Error::Format(_))));
+    }
+
+    fn encrypt_with_footer(
+        key: &<AesGcmSystem as SymmetricCryptographicSystem>::Key,
+        data: &[u8],
+    ) -> (StreamingConfig, Vec<u8>, Vec<u8>) {
+        // 只启用尾部，以便单独检验尾部本身能发现的篡改
+        let config = StreamingConfig::default()
+            .with_buffer_size(16)
+            .with_frame_metadata_binding(false)
+            .with_integrity_footer(true);
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(key, Cursor::new(data), &mut encrypted, &config, None).unwrap();
+        // 尾部：标记 (4) + 帧数 (8) + CRC (4)
+        let footer = encrypted.split_off(encrypted.len() - 4 - crate::common::streaming::FOOTER_SIZE);
+        (config, encrypted, footer)
+    }
+
+    #[test]
+    fn test_streaming_integrity_footer_roundtrip() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"0123456789abcdef-second-frame-0123456789abcdef-third";
+        let (config, frames, footer) = encrypt_with_footer(&key, original_data);
+        let encrypted = [frames, footer].concat();
+
+        assert_eq!(verify_stream_integrity(Cursor::new(&encrypted), &config).unwrap(), 4);
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &config, None).unwrap();
+        assert_eq!(decrypted, original_data);
+
+        // 未启用尾部的配置无法解析带尾部的流
+        let mut decrypted = Vec::new();
+        let plain_config = StreamingConfig::default().with_buffer_size(16).with_frame_metadata_binding(false);
+        assert!(AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &plain_config, None).is_err());
+    }
+
+    #[test]
+    fn test_streaming_truncation_detected_via_footer() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"0123456789abcdef-second-frame-0123456789abcdef-third";
+        let (config, frames, footer) = encrypt_with_footer(&key, original_data);
+        let frame_list = frame_list(&frames);
+
+        // 在帧边界截断（尾部一并丢失）：不需要密钥即可发现
+        let truncated = frame_list[..2].concat();
+        assert!(matches!(verify_stream_integrity(Cursor::new(&truncated), &config), Err(Error::StreamIntegrity(_))));
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(&truncated), &mut decrypted, &config, None);
+        assert!(matches!(result, Err(Error::StreamIntegrity(_))));
+
+        // 在帧中间截断
+        let cut = &frames[..frames.len() - 5];
+        assert!(matches!(verify_stream_integrity(Cursor::new(cut), &config), Err(Error::StreamIntegrity(_))));
+
+        // 丢失中间的帧但保留尾部：帧数与 CRC 不再吻合
+        let missing_middle = [frame_list[0].clone(), frame_list[2].clone(), frame_list[3].clone(), footer.clone()].concat();
+        assert!(matches!(verify_stream_integrity(Cursor::new(&missing_middle), &config), Err(Error::StreamIntegrity(_))));
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(&missing_middle), &mut decrypted, &config, None);
+        assert!(result.is_err());
+
+        // 尾部之后的多余数据同样被拒绝
+        let trailing = [frames, footer, vec![0u8]].concat();
+        assert!(matches!(verify_stream_integrity(Cursor::new(&trailing), &config), Err(Error::StreamIntegrity(_))));
+    }
+
+    #[test]
+    fn test_streaming_footer_crc_covers_whole_frames() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"0123456789abcdef-second-frame-0123456789abcdef-third";
+        let (config, frames, footer) = encrypt_with_footer(&key, original_data);
+        let frame_list = frame_list(&frames);
+
+        let crc = frame_list.iter().fold(0, |crc, frame| crate::common::utils::crc32_update(crc, &frame[4..]));
+        assert_eq!(footer[12..16], crc.to_le_bytes());
+
+        // 改动帧开头的编码字符（远离帧尾）同样会被尾部发现
+        let mut tampered = frame_list.clone();
+        tampered[1][4] = if tampered[1][4] == b'A' { b'B' } else { b'A' };
+        let tampered = [tampered.concat(), footer].concat();
+        assert!(matches!(verify_stream_integrity(Cursor::new(&tampered), &config), Err(Error::StreamIntegrity(_))));
+    }
+
+    #[test]
+    fn test_streaming_malformed_frames_return_error() {
+        let (key, config) = get_test_key_and_config();
+        let config = config.with_frame_metadata_binding(false);
+
+        // 伪造的超大长度前缀不会预先分配对应大小的缓冲区
+        let bogus_length = [(u32::MAX - 1).to_le_bytes().as_slice(), b"short"].concat();
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(bogus_length), &mut decrypted, &config, None);
+        assert!(matches!(result, Err(Error::Io(_))));
+
+        // 帧内容不是 UTF-8
+        let invalid_utf8 = [4u32.to_le_bytes().as_slice(), &[0xff, 0xfe, 0xfd, 0xfc]].concat();
+        let mut decrypted = Vec::new();
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(invalid_utf8), &mut decrypted, &config, None);
+        assert!(matches!(result, Err(Error::Format(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_stream_matches_serial() {
+        let (key, _) = get_test_key_and_config();
+        let original_data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let pool = std::sync::Arc::new(rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap());
+
+        for bind in [false, true] {
+            let config = StreamingConfig::default()
+                .with_buffer_size(100)
+                .with_total_bytes(original_data.len() as u64)
+                .with_frame_metadata_binding(bind)
+                .with_thread_pool(pool.clone());
+            let aad = Some(b"parallel aad".as_ref());
+
+            // 并行加密 -> 串行解密
+            let mut parallel_encrypted = Vec::new();
+            let result = encrypt_stream_parallel::<AesGcmSystem, _, _>(
+                &key, Cursor::new(&original_data), &mut parallel_encrypted, &config, aad,
+            ).unwrap();
+            assert_eq!(result.bytes_processed, original_data.len() as u64);
+            let mut decrypted = Vec::new();
+            AesGcmSystem::decrypt_stream(&key, Cursor::new(&parallel_encrypted), &mut decrypted, &config, aad).unwrap();
+            assert_eq!(decrypted, original_data);
+
+            // 串行加密 -> 并行解密
+            let mut serial_encrypted = Vec::new();
+            AesGcmSystem::encrypt_stream(&key, Cursor::new(&original_data), &mut serial_encrypted, &config, aad).unwrap();
+            let mut decrypted = Vec::new();
+            decrypt_stream_parallel::<AesGcmSystem, _, _>(
+                &key, Cursor::new(&serial_encrypted), &mut decrypted, &config, aad,
+            ).unwrap();
+            assert_eq!(decrypted, original_data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_stream_nonces_unique_and_order_preserved() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = vec![0u8; 64 * 200];
+        let config = StreamingConfig::default()
+            .with_buffer_size(64)
+            .with_total_bytes(original_data.len() as u64)
+            .with_frame_metadata_binding(true);
+
+        let mut encrypted = Vec::new();
+        encrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(&original_data), &mut encrypted, &config, None).unwrap();
+        let (_, frames) = split_frames(&encrypted);
+        assert_eq!(frames.len(), 200);
+
+        let nonces: std::collections::HashSet<Vec<u8>> = frames.iter()
+            .map(|frame| crate::common::utils::from_base64(std::str::from_utf8(&frame[4..]).unwrap()).unwrap()[..12].to_vec())
+            .collect();
+        assert_eq!(nonces.len(), frames.len());
+
+        // 帧序号绑定在AAD中，逐帧解密即可验证输出顺序与输入一致
+        for (index, frame) in frames.iter().enumerate() {
+            let aad = frame_aad(index as u64, 200, None);
+            let ciphertext = std::str::from_utf8(&frame[4..]).unwrap();
+            assert!(AesGcmSystem::decrypt(&key, ciphertext, Some(&aad)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_streaming_frame_metadata_bound_by_default() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"Frames are bound to their position even without total_bytes or user AAD.";
+        let config = StreamingConfig::default().with_buffer_size(16);
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(original_data), &mut encrypted, &config, None).unwrap();
+        let (header, frames) = split_frames(&encrypted);
+        assert_eq!(header, OPEN_ENDED_FRAME_COUNT.to_le_bytes());
+        // 数据帧之后是一个带标记的空末帧
+        assert_eq!(frames.len(), original_data.len().div_ceil(16) + 1);
+        assert!(frames.last().unwrap().starts_with(&FINAL_FRAME_MARKER.to_le_bytes()));
+
+        let decrypt = |stream: Vec<u8>| AesGcmSystem::decrypt_stream(&key, Cursor::new(stream), &mut Vec::new(), &config, None);
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &config, None).unwrap();
+        assert_eq!(decrypted, original_data);
+
+        // 交换两个数据帧
+        let mut swapped = frames.clone();
+        swapped.swap(0, 1);
+        assert!(decrypt([header.clone(), swapped.concat()].concat()).is_err());
+
+        // 丢弃末帧
+        let truncated = frames[..frames.len() - 1].concat();
+        assert!(matches!(decrypt([header.clone(), truncated].concat()), Err(Error::StreamIntegrity(_))));
+
+        // 把普通帧标记为末帧、或去掉末帧标记
+        let mut early_final = frames[..2].to_vec();
+        early_final[1] = [FINAL_FRAME_MARKER.to_le_bytes().as_slice(), &frames[1]].concat();
+        assert!(decrypt([header.clone(), early_final.concat()].concat()).is_err());
+        let mut unmarked = frames.clone();
+        let last = unmarked.pop().unwrap();
+        unmarked.push(last[4..].to_vec());
+        assert!(decrypt([header.clone(), unmarked.concat()].concat()).is_err());
+
+        // 改写头部声明的总帧数
+        let declared = (frames.len() as u64).to_le_bytes().to_vec();
+        assert!(decrypt([declared, frames.concat()].concat()).is_err());
+
+        // 空输入同样以末帧结束
+        let mut empty = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(b""), &mut empty, &config, None).unwrap();
+        assert_eq!(split_frames(&empty).1.len(), 1);
+        assert!(decrypt(empty).unwrap().bytes_processed == 0);
+        assert!(matches!(decrypt(header), Err(Error::StreamIntegrity(_))));
+    }
+
+    type ReceivedMetadata = std::sync::Arc<std::sync::Mutex<Vec<(u64, Vec<u8>)>>>;
+
+    fn metadata_config() -> (StreamingConfig, ReceivedMetadata) {
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let config = StreamingConfig::default()
+            .with_buffer_size(16)
+            .with_chunk_metadata(std::sync::Arc::new(|index, plaintext| {
+                format!("ts={};len={}", 1_700_000_000 + index, plaintext.len()).into_bytes()
+            }))
+            .with_chunk_metadata_callback(std::sync::Arc::new(move |index, metadata| {
+                sink.lock().unwrap().push((index, metadata.to_vec()));
+            }));
+        (config, received)
+    }
+
+    #[test]
+    fn test_streaming_chunk_metadata_roundtrip() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"Each sixteen byte chunk carries its own timestamp.";
+        let (config, received) = metadata_config();
+        // 流末尾的空末帧同样带有元数据
+        let expected: Vec<(u64, Vec<u8>)> = original_data.chunks(16).chain([&[][..]]).enumerate()
+            .map(|(index, chunk)| (index as u64, format!("ts={};len={}", 1_700_000_000 + index, chunk.len()).into_bytes()))
+            .collect();
+
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(original_data), &mut encrypted, &config, Some(b"aad")).unwrap();
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &config, Some(b"aad")).unwrap();
+        assert_eq!(decrypted, original_data);
+        assert_eq!(*received.lock().unwrap(), expected);
+
+        // 元数据以明文出现在帧中
+        let first_frame = &split_frames(&encrypted).1[0];
+        let metadata_len = u16::from_le_bytes([first_frame[4], first_frame[5]]) as usize;
+        assert_eq!(&first_frame[6..6 + metadata_len], expected[0].1.as_slice());
+
+        // 未设置回调时无法解析带元数据的帧
+        let plain_config = StreamingConfig::default().with_buffer_size(16);
+        assert!(AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut Vec::new(), &plain_config, Some(b"aad")).is_err());
+
+        #[cfg(feature = "parallel")]
+        {
+            received.lock().unwrap().clear();
+            let mut parallel_encrypted = Vec::new();
+            encrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(original_data), &mut parallel_encrypted, &config, Some(b"aad")).unwrap();
+            let mut decrypted = Vec::new();
+            decrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(&parallel_encrypted), &mut decrypted, &config, Some(b"aad")).unwrap();
+            assert_eq!(decrypted, original_data);
+            assert_eq!(*received.lock().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_streaming_tampered_chunk_metadata_fails() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"Tampering with any frame's metadata must break that frame.";
+        let (config, received) = metadata_config();
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(original_data), &mut encrypted, &config, None).unwrap();
+
+        // 修改第二帧元数据中的一个字节
+        let first_frame_len = split_frames(&encrypted).1[0].len();
+        let mut tampered = encrypted.clone();
+        tampered[FRAME_HEADER_SIZE + first_frame_len + 4 + 2 + 3] ^= 1;
+        let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(&tampered), &mut Vec::new(), &config, None);
+        assert!(result.is_err());
+        // 第一帧已通过认证，篡改帧的元数据不会交给回调
+        assert_eq!(received.lock().unwrap().iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0]);
+
+        // 交换两帧的元数据同样无法通过认证
+        let (header, frames) = split_frames(&encrypted);
+        let swap = |frame: &[u8], other: &[u8]| -> Vec<u8> {
+            let len = u16::from_le_bytes([other[4], other[5]]) as usize;
+            let own_len = u16::from_le_bytes([frame[4], frame[5]]) as usize;
+            let mut body = other[4..6 + len].to_vec();
+            body.extend_from_slice(&frame[6 + own_len..]);
+            [&(body.len() as u32).to_le_bytes()[..], &body].concat()
+        };
+        let swapped = [header, swap(&frames[0], &frames[1]), swap(&frames[1], &frames[0]), frames[2..].concat()].concat();
+        assert!(AesGcmSystem::decrypt_stream(&key, Cursor::new(&swapped), &mut Vec::new(), &config, None).is_err());
+    }
+
+    /// 模拟攻击者：按改动后的帧重新计算无密钥的帧数与CRC，保留原有的全局认证标签
+    fn forge_footer(frames: &[Vec<u8>], global_tag: &[u8]) -> Vec<u8> {
+        let mut tally = FrameTally::default();
+        for frame in frames {
+            tally.record(&frame[4..]);
+        }
+        [&tally.footer()[..], global_tag].concat()
+    }
+
+    #[test]
+    fn test_stream_mac_detects_dropped_and_reordered_frames() {
+        let (key, _) = get_test_key_and_config();
+        let original_data = b"0123456789abcdef-second-frame-0123456789abcdef-third";
+        let config = StreamingConfig::default()
+            .with_buffer_size(16)
+            .with_frame_metadata_binding(false)
+            .with_stream_mac_key(b"stream mac key");
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(original_data), &mut encrypted, &config, None).unwrap();
+        let footer_start = encrypted.len() - 4 - crate::common::streaming::FOOTER_SIZE - crate::common::streaming::STREAM_MAC_SIZE;
+        let (body, footer) = encrypted.split_at(footer_start);
+        let frames = frame_list(body);
+        assert_eq!(frames.len(), 4);
+
+        assert_eq!(verify_stream_integrity(Cursor::new(&encrypted), &config).unwrap(), 4);
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &config, None).unwrap();
+        assert_eq!(decrypted, original_data);
+
+        let dropped = vec![frames[0].clone(), frames[1].clone(), frames[3].clone()];
+        let reordered = vec![frames[1].clone(), frames[0].clone(), frames[2].clone(), frames[3].clone()];
+        for tampered_frames in [dropped, reordered] {
+            // 未绑定帧序号，每一帧单独都能通过认证
+            for frame in &tampered_frames {
+                assert!(AesGcmSystem::decrypt(&key, std::str::from_utf8(&frame[4..]).unwrap(), None).is_ok());
+            }
+            let global_tag = &footer[4 + crate::common::streaming::FOOTER_SIZE..];
+            let tampered = [tampered_frames.concat(), forge_footer(&tampered_frames, global_tag)].concat();
+            // 仅有无密钥的尾部时伪造可以通过
+            let crc_only = StreamingConfig::default().with_buffer_size(16).with_frame_metadata_binding(false).with_integrity_footer(true);
+            let crc_only_stream = [tampered_frames.concat(), forge_footer(&tampered_frames, &[])].concat();
+            assert!(verify_stream_integrity(Cursor::new(&crc_only_stream), &crc_only).is_ok());
+
+            assert!(matches!(verify_stream_integrity(Cursor::new(&tampered), &config), Err(Error::StreamIntegrity(_))));
+            let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(&tampered), &mut Vec::new(), &config, None);
+            assert!(matches!(result, Err(Error::StreamIntegrity(_))));
+        }
+
+        // 全局认证密钥不符同样失败
+        let wrong_key = config.clone().with_stream_mac_key(b"another mac key");
+        assert!(matches!(verify_stream_integrity(Cursor::new(&encrypted), &wrong_key), Err(Error::StreamIntegrity(_))));
+    }
+
+    #[test]
+    fn test_streaming_content_defined_chunking() {
+        use crate::symmetric::chunking::ContentDefinedChunker;
+
+        let (key, _) = get_test_key_and_config();
+        let chunker = ContentDefinedChunker::new(256, 1024, 4096).unwrap();
+        let original_data: Vec<u8> = (0..40_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+        let config = StreamingConfig::default().with_content_defined_chunking(chunker);
+
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(&original_data), &mut encrypted, &config, None).unwrap();
+        // 每帧对应一个内容定义的块，之后是空末帧；解密无需分块设置
+        assert_eq!(split_frames(&encrypted).1.len(), chunker.chunks(&original_data).count() + 1);
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &StreamingConfig::default(), None).unwrap();
+        assert_eq!(decrypted, original_data);
+
+        #[cfg(feature = "parallel")]
+        {
+            let mut parallel_encrypted = Vec::new();
+            encrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(&original_data), &mut parallel_encrypted, &config, None).unwrap();
+            assert_eq!(split_frames(&parallel_encrypted).1.len(), split_frames(&encrypted).1.len());
+            let mut decrypted = Vec::new();
+            AesGcmSystem::decrypt_stream(&key, Cursor::new(&parallel_encrypted), &mut decrypted, &config, None).unwrap();
+            assert_eq!(decrypted, original_data);
+        }
+
+        // 总帧数无法预先确定，即使设置了 total_bytes 也按总帧数未知绑定
+        let declared = config.with_total_bytes(original_data.len() as u64);
+        let mut encrypted = Vec::new();
+        AesGcmSystem::encrypt_stream(&key, Cursor::new(&original_data), &mut encrypted, &declared, None).unwrap();
+        assert_eq!(split_frames(&encrypted).0, OPEN_ENDED_FRAME_COUNT.to_le_bytes());
+        let mut decrypted = Vec::new();
+        AesGcmSystem::decrypt_stream(&key, Cursor::new(&encrypted), &mut decrypted, &declared, None).unwrap();
+        assert_eq!(decrypted, original_data);
+    }
+
+    #[test]
+    fn test_encrypt_to_sink_matches_returned_ciphertext() {
+        let (key, _) = get_test_key_and_config();
+        let mut sink = Vec::new();
+        let written = AesGcmSystem::encrypt_to(&key, b"pipeline payload", Some(b"aad"), &mut sink).unwrap();
+        assert_eq!(written, sink.len());
+        let returned = AesGcmSystem::encrypt(&key, b"pipeline payload", Some(b"aad")).unwrap().to_string();
+        assert_eq!(sink.len(), returned.len());
+        assert_eq!(AesGcmSystem::decrypt(&key, std::str::from_utf8(&sink).unwrap(), Some(b"aad")).unwrap(), b"pipeline payload");
+
+        // 使用相同的确定性RNG时，写入的字节与返回值逐字节相同
+        #[cfg(feature = "test-determinism")]
+        {
+            let config = CryptoConfig { deterministic_seed: Some(42), ..CryptoConfig::default() };
+            let rng = || crate::common::rng::EngineRng::from_config(&config);
+            let returned = rng().run(|| AesGcmSystem::encrypt(&key, b"pipeline payload", None).unwrap().to_string());
+            let mut sink = Vec::new();
+            rng().run(|| AesGcmSystem::encrypt_to(&key, b"pipeline payload", None, &mut sink)).unwrap();
+            assert_eq!(sink, returned.as_bytes());
+        }
+
+        // 追加写入已有内容的缓冲区
+        let mut sink = b"prefix:".to_vec();
+        AesGcmSystem::encrypt_to(&key, b"", None, &mut sink).unwrap();
+        assert_eq!(AesGcmSystem::decrypt(&key, std::str::from_utf8(&sink[7..]).unwrap(), None).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_encrypt_to_sink_write_error() {
+        struct FailingSink;
+
+        impl Write for FailingSink {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "closed"))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (key, _) = get_test_key_and_config();
+        let result = AesGcmSystem::encrypt_to(&key, b"payload", None, &mut FailingSink);
+        assert!(matches!(result, Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe));
+    }
+
+    #[test]
+    fn test_frame_chaining_detects_splicing() {
+        let (key, _) = get_test_key_and_config();
+        let stream_a = b"stream A: 0123456789abcdefghijklmnopqrstuvwxyz-ABCDEFGHIJKLMNOPQ";
+        let stream_b = b"stream B: the second stream shares key, AAD and frame indices!!";
+
+        for chaining in [false, true] {
+            let config = StreamingConfig::default().with_buffer_size(16).with_frame_chaining(chaining);
+            let encrypt = |data: &[u8]| {
+                let mut encrypted = Vec::new();
+                AesGcmSystem::encrypt_stream(&key, Cursor::new(data), &mut encrypted, &config, Some(b"aad")).unwrap();
+                encrypted
+            };
+            let (encrypted_a, encrypted_b) = (encrypt(stream_a), encrypt(stream_b));
+            for (encrypted, original) in [(&encrypted_a, &stream_a[..]), (&encrypted_b, &stream_b[..])] {
+                let mut decrypted = Vec::new();
+                AesGcmSystem::decrypt_stream(&key, Cursor::new(encrypted), &mut decrypted, &config, Some(b"aad")).unwrap();
+                assert_eq!(decrypted, original);
+            }
+
+            // 把流A的第2帧换入流B的同一位置
+            let (header, mut frames) = split_frames(&encrypted_b);
+            frames[2] = split_frames(&encrypted_a).1[2].clone();
+            let spliced = [header, frames.concat()].concat();
+            let mut decrypted = Vec::new();
+            let result = AesGcmSystem::decrypt_stream(&key, Cursor::new(&spliced), &mut decrypted, &config, Some(b"aad"));
+            if chaining {
+                assert!(matches!(result, Err(Error::DecryptionFailed(_))));
+            } else {
+                // 未启用帧链接时，序号相同的帧可以被无声地拼接
+                result.unwrap();
+                assert_eq!(&decrypted[32..48], &stream_a[32..48]);
+            }
+
+            #[cfg(feature = "parallel")]
+            if chaining {
+                let mut decrypted = Vec::new();
+                decrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(&encrypted_a), &mut decrypted, &config, Some(b"aad")).unwrap();
+                assert_eq!(decrypted, stream_a);
+                let result = decrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(&spliced), &mut Vec::new(), &config, Some(b"aad"));
+                assert!(matches!(result, Err(Error::DecryptionFailed(_))));
+                let result = encrypt_stream_parallel::<AesGcmSystem, _, _>(&key, Cursor::new(stream_a), &mut Vec::new(), &config, None);
+                assert!(matches!(result, Err(Error::Format(_))));
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! 提供前向保密的对称棘轮
+//!
+//! 双方从同一个根密钥出发，每处理一条消息就推进一次链密钥：
+//! `消息密钥 = HKDF-Expand(链密钥, MESSAGE_KEY_LABEL)`，`下一链密钥 = HKDF-Expand(链密钥, CHAIN_KEY_LABEL)`。
+//! 旧的链密钥在推进后立即清零，HKDF 单向不可逆，因此泄露当前状态或某条消息密钥都无法推出之前的消息密钥。
+//! 消息的棘轮位置同时作为附加认证数据，解密方必须处于与发送方相同的位置。
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::common::errors::Error;
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 由根密钥导出初始链密钥时使用的 HKDF salt
+const ROOT_SALT: &[u8] = b"seal-kit-ratchet-root-v1";
+/// 派生消息密钥的 HKDF `info` 标签
+const MESSAGE_KEY_LABEL: &[u8] = b"seal-kit-ratchet-message-key-v1";
+/// 派生下一链密钥的 HKDF `info` 标签
+const CHAIN_KEY_LABEL: &[u8] = b"seal-kit-ratchet-chain-key-v1";
+/// 棘轮消息附加认证数据的域分隔标签
+const RATCHET_AAD_LABEL: &[u8] = b"seal-kit-ratchet-v1";
+
+/// 对称棘轮状态，发送方与接收方各持有一份并按相同顺序推进
+pub struct RatchetState {
+    chain_key: Zeroizing<[u8; 32]>,
+    position: u64,
+}
+
+impl core::fmt::Debug for RatchetState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RatchetState")
+            .field("chain_key", &"[REDACTED]")
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl RatchetState {
+    /// 由双方共享的根密钥（如密钥协商得到的共享密钥）创建棘轮，位置从 0 开始
+    pub fn new(root_key: &[u8]) -> Self {
+        let mut chain_key = Zeroizing::new([0u8; 32]);
+        Hkdf::<Sha256>::new(Some(ROOT_SALT), root_key)
+            .expand(CHAIN_KEY_LABEL, chain_key.as_mut())
+            .expect("32 字节输出远小于 HKDF-SHA256 的上限");
+        Self { chain_key, position: 0 }
+    }
+
+    /// 下一条消息的棘轮位置
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 取出当前位置的消息密钥并推进链密钥，返回 (位置, 消息密钥)
+    pub fn next_message_key(&mut self) -> Result<(u64, AesGcmKey), Error> {
+        let (message_key, next_chain_key) = self.derive()?;
+        let position = self.position;
+        self.advance(next_chain_key);
+        Ok((position, message_key))
+    }
+
+    /// 以下一条消息密钥加密，位置绑定到附加认证数据中
+    pub fn encrypt_next(&mut self, plaintext: &[u8]) -> Result<String, Error> {
+        let (message_key, next_chain_key) = self.derive()?;
+        let ciphertext = AesGcmSystem::encrypt(&message_key, plaintext, Some(&Self::aad(self.position)))?;
+        self.advance(next_chain_key);
+        Ok(ciphertext.to_string())
+    }
+
+    /// 以下一条消息密钥解密；认证失败时不推进棘轮，因此消息必须按发送顺序逐条解密
+    pub fn decrypt_next(&mut self, ciphertext: &str) -> Result<Vec<u8>, Error> {
+        let (message_key, next_chain_key) = self.derive()?;
+        let plaintext = AesGcmSystem::decrypt(&message_key, ciphertext, Some(&Self::aad(self.position)))?;
+        self.advance(next_chain_key);
+        Ok(plaintext)
+    }
+
+    /// 由当前链密钥派生 (消息密钥, 下一链密钥)，不修改状态
+    fn derive(&self) -> Result<(AesGcmKey, Zeroizing<[u8; 32]>), Error> {
+        if self.position == u64::MAX {
+            return Err(Error::NonceExhausted);
+        }
+        let hkdf = Hkdf::<Sha256>::from_prk(self.chain_key.as_ref())
+            .expect("链密钥长度等于 SHA-256 输出长度");
+        let mut message_key = Zeroizing::new([0u8; 32]);
+        let mut next_chain_key = Zeroizing::new([0u8; 32]);
+        hkdf.expand(MESSAGE_KEY_LABEL, message_key.as_mut())
+            .and_then(|_| hkdf.expand(CHAIN_KEY_LABEL, next_chain_key.as_mut()))
+            .expect("32 字节输出远小于 HKDF-SHA256 的上限");
+        Ok((AesGcmKey::from_derived(*message_key), next_chain_key))
+    }
+
+    fn advance(&mut self, next_chain_key: Zeroizing<[u8; 32]>) {
+        self.chain_key = next_chain_key;
+        self.position += 1;
+    }
+
+    fn aad(position: u64) -> Vec<u8> {
+        let mut aad = RATCHET_AAD_LABEL.to_vec();
+        aad.extend_from_slice(&position.to_be_bytes());
+        aad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(key: &AesGcmKey) -> String {
+        AesGcmSystem::export_key(key).unwrap()
+    }
+
+    #[test]
+    fn test_each_message_uses_distinct_key() {
+        let mut ratchet = RatchetState::new(b"shared root key");
+        let keys: Vec<String> = (0..16).map(|i| {
+            let (position, key) = ratchet.next_message_key().unwrap();
+            assert_eq!(position, i);
+            export(&key)
+        }).collect();
+        let unique: std::collections::HashSet<_> = keys.iter().collect();
+        assert_eq!(unique.len(), keys.len());
+        assert_eq!(ratchet.position(), 16);
+    }
+
+    #[test]
+    fn test_ratchet_advances_deterministically() {
+        let mut a = RatchetState::new(b"shared root key");
+        let mut b = RatchetState::new(b"shared root key");
+        for _ in 0..8 {
+            assert_eq!(export(&a.next_message_key().unwrap().1), export(&b.next_message_key().unwrap().1));
+        }
+        let mut other = RatchetState::new(b"another root key");
+        assert_ne!(export(&RatchetState::new(b"shared root key").next_message_key().unwrap().1), export(&other.next_message_key().unwrap().1));
+        assert!(format!("{:?}", a).contains("REDACTED"));
+    }
+
+    #[test]
+    fn test_decryption_requires_matching_position() {
+        let mut sender = RatchetState::new(b"shared root key");
+        let mut receiver = RatchetState::new(b"shared root key");
+        let first = sender.encrypt_next(b"one").unwrap();
+        let second = sender.encrypt_next(b"two").unwrap();
+
+        // 乱序到达的消息无法解密，且不会推进接收方的棘轮
+        assert!(receiver.decrypt_next(&second).is_err());
+        assert_eq!(receiver.position(), 0);
+        assert_eq!(receiver.decrypt_next(&first).unwrap(), b"one");
+        assert_eq!(receiver.decrypt_next(&second).unwrap(), b"two");
+        // 重放旧消息失败
+        assert!(receiver.decrypt_next(&first).is_err());
+
+        // 已推进的状态无法再解密之前的消息
+        let mut lagging = RatchetState::new(b"shared root key");
+        lagging.next_message_key().unwrap();
+        assert!(lagging.decrypt_next(&first).is_err());
+        assert_eq!(lagging.decrypt_next(&second).unwrap(), b"two");
+    }
+}
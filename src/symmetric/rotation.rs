@@ -65,13 +65,13 @@ where
         }
         
         if let Some(name) = primary_key_name {
-            self.load_primary_key(&name)?;
+            self.load_primary_key(&name, config)?;
         } else {
             self.create_new_primary_key(config)?;
         }
         
         for name in secondary_key_names {
-            self.load_secondary_key(&name)?;
+            self.load_secondary_key(&name, config)?;
         }
         
         Ok(())
@@ -192,6 +192,40 @@ where
         self.primary_key.as_ref().map(|(_, metadata)| metadata)
     }
 
+    /// 获取为解密旧数据而保留的次要密钥的元数据
+    pub fn get_secondary_key_metadata(&self) -> Vec<&KeyMetadata> {
+        self.secondary_keys.iter().map(|(_, metadata)| metadata).collect()
+    }
+
+    /// 删除创建时间早于 `cutoff` 的次要密钥，返回删除的数量
+    ///
+    /// 密钥先从存储后端删除，再从内存中移除并立即丢弃（内置密钥类型在丢弃时清零）；
+    /// 存储删除失败时返回错误，该密钥及其后的密钥保持不变。创建时间无法解析的密钥不会被删除。
+    pub fn prune_secondary_keys_before(&mut self, cutoff: DateTime<Utc>) -> Result<usize, Error> {
+        let mut pruned = 0;
+        let mut index = 0;
+        while let Some((_, metadata)) = self.secondary_keys.get(index) {
+            let expired = DateTime::parse_from_rfc3339(&metadata.created_at)
+                .is_ok_and(|created_at| created_at < cutoff);
+            if !expired {
+                index += 1;
+                continue;
+            }
+            let key_name = format!("{}-{}", self.key_prefix, metadata.id);
+            self.key_storage.delete_key(&key_name)?;
+            drop(self.secondary_keys.remove(index));
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_secondary_created_at(&mut self, id: &str, created_at: DateTime<Utc>) {
+        if let Some((_, metadata)) = self.secondary_keys.iter_mut().find(|(_, metadata)| metadata.id == id) {
+            metadata.created_at = created_at.to_rfc3339();
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn set_usage_count(&mut self, count: u64) {
         if let Some((_, metadata)) = &mut self.primary_key {
@@ -217,11 +251,11 @@ where
         Ok(key_str.into_bytes())
     }
 
-    /// 反序列化单个密钥
-    fn deserialize_key(&self, data: &[u8]) -> Result<T::Key, Error> {
+    /// 反序列化单个密钥，按配置拒绝弱密钥
+    fn deserialize_key(&self, data: &[u8], config: &CryptoConfig) -> Result<T::Key, Error> {
         let key_str = String::from_utf8(data.to_vec())
             .map_err(|e| Error::Serialization(e.to_string()))?;
-        T::import_key(&key_str).map_err(|e| Error::Operation(e.to_string()))
+        T::import_key_with_config(&key_str, config).map_err(|e| Error::Operation(e.to_string()))
     }
     
     /// 创建新的主密钥
@@ -255,17 +289,17 @@ where
     }
     
     /// 加载主密钥
-    fn load_primary_key(&mut self, name: &str) -> Result<(), Error> {
+    fn load_primary_key(&mut self, name: &str, config: &CryptoConfig) -> Result<(), Error> {
         let (metadata, key_data) = self.key_storage.load_key(name)?;
-        let key = self.deserialize_key(&key_data)?;
+        let key = self.deserialize_key(&key_data, config)?;
         self.primary_key = Some((key, metadata));
         Ok(())
     }
 
     /// 加载次要密钥
-    fn load_secondary_key(&mut self, name: &str) -> Result<(), Error> {
+    fn load_secondary_key(&mut self, name: &str, config: &CryptoConfig) -> Result<(), Error> {
         let (metadata, key_data) = self.key_storage.load_key(name)?;
-        let key = self.deserialize_key(&key_data)?;
+        let key = self.deserialize_key(&key_data, config)?;
         self.secondary_keys.push((key, metadata));
         Ok(())
     }
@@ -0,0 +1,378 @@
+//! 无需引擎的轻量级流式加解密函数
+//!
+//! 适用于命令行工具等不需要密钥存储与轮换的场景：直接提供原始密钥字节与算法，
+//! 以流式帧格式（`u32 LE 长度 || Base64 密文`）在 `Read`/`Write` 之间加解密。
+//! 流末尾总是写入带全局认证标签的完整性尾部，认证密钥由 HKDF-SHA256 从原始密钥派生，
+//! 因此截断在帧边界上的密文也会被发现。
+//! 另提供信封加密辅助函数：为每条消息生成一次性的数据加密密钥（DEK），由调用方交给外部 KMS 封装。
+
+use std::io::{Read, Write};
+#[cfg(feature = "mmap")]
+use std::fs::File;
+#[cfg(feature = "mmap")]
+use std::path::Path;
+
+use zeroize::Zeroizing;
+
+use crate::common::errors::Error;
+use crate::common::key_derivation::{HkdfSha256, KeyDerivation};
+use crate::common::streaming::StreamingConfig;
+use crate::common::utils::CryptoConfig;
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+#[cfg(feature = "chacha")]
+use crate::symmetric::systems::chacha20_poly1305::{ChaCha20Poly1305Key, ChaCha20Poly1305System};
+use crate::symmetric::traits::{SymmetricCryptographicSystem, SymmetricSyncStreamingSystem};
+
+pub use crate::symmetric::systems::SymmetricAlgorithm;
+
+/// 派生流全局认证密钥时使用的 HKDF `info`
+const STREAM_MAC_LABEL: &[u8] = b"seal-kit/seal/stream-mac";
+
+/// 流式加解密使用的配置：启用完整性尾部，全局认证密钥由 `key` 派生
+fn stream_config(key: &[u8]) -> Result<StreamingConfig, Error> {
+    let mac_key = HkdfSha256.derive(key, &[], STREAM_MAC_LABEL, 32)?;
+    Ok(StreamingConfig::default().with_integrity_footer(true).with_stream_mac_key(&mac_key))
+}
+
+/// 统计写入字节数的包装器
+struct CountingWriter<W: Write> {
+    inner: W,
+    written: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 将 `reader` 中的明文加密后写入 `writer`，返回写入 `writer` 的字节数（含完整性尾部）
+pub fn seal_reader_to_writer<R: Read, W: Write>(
+    key: &[u8],
+    algorithm: SymmetricAlgorithm,
+    reader: R,
+    writer: W,
+) -> Result<u64, Error> {
+    let mut counter = CountingWriter { inner: writer, written: 0 };
+    match algorithm {
+        SymmetricAlgorithm::Aes256Gcm => {
+            let config = stream_config(key)?;
+            let key = AesGcmKey::from_slice(key)?;
+            AesGcmSystem::encrypt_stream(&key, reader, &mut counter, &config, None)?;
+        }
+        #[cfg(feature = "chacha")]
+        SymmetricAlgorithm::ChaCha20Poly1305 => {
+            let config = stream_config(key)?;
+            let key = ChaCha20Poly1305Key::from_slice(key)?;
+            ChaCha20Poly1305System::encrypt_stream(&key, reader, &mut counter, &config, None)?;
+        }
+    }
+    Ok(counter.written)
+}
+
+/// 将 `reader` 中由 [`seal_reader_to_writer`] 生成的密文解密后写入 `writer`，返回写入的明文字节数
+///
+/// 密文被截断、缺少尾部或帧被重排时返回 `Error::StreamIntegrity`；此前已解密的帧可能已写入 `writer`。
+pub fn open_reader_to_writer<R: Read, W: Write>(
+    key: &[u8],
+    algorithm: SymmetricAlgorithm,
+    reader: R,
+    writer: W,
+) -> Result<u64, Error> {
+    let mut counter = CountingWriter { inner: writer, written: 0 };
+    match algorithm {
+        SymmetricAlgorithm::Aes256Gcm => {
+            let config = stream_config(key)?;
+            let key = AesGcmKey::from_slice(key)?;
+            AesGcmSystem::decrypt_stream(&key, reader, &mut counter, &config, None)?;
+        }
+        #[cfg(feature = "chacha")]
+        SymmetricAlgorithm::ChaCha20Poly1305 => {
+            let config = stream_config(key)?;
+            let key = ChaCha20Poly1305Key::from_slice(key)?;
+            ChaCha20Poly1305System::decrypt_stream(&key, reader, &mut counter, &config, None)?;
+        }
+    }
+    Ok(counter.written)
+}
+
+/// 生成随机 DEK 加密 `plaintext`，返回 (Base64 密文, 原始 DEK 字节)
+///
+/// DEK 只用于这一条消息；调用方应立即用外部 KMS 封装 DEK 并与密文一起保存，
+/// 返回的明文 DEK 在离开作用域时自动清零。
+pub fn encrypt_enveloped(
+    plaintext: &[u8],
+    algorithm: SymmetricAlgorithm,
+    additional_data: Option<&[u8]>,
+) -> Result<(String, Zeroizing<Vec<u8>>), Error> {
+    match algorithm {
+        SymmetricAlgorithm::Aes256Gcm => {
+            let key = AesGcmSystem::generate_key(&CryptoConfig::default())?;
+            let ciphertext = AesGcmSystem::encrypt(&key, plaintext, additional_data)?.to_string();
+            Ok((ciphertext, Zeroizing::new(key.as_bytes().to_vec())))
+        }
+        #[cfg(feature = "chacha")]
+        SymmetricAlgorithm::ChaCha20Poly1305 => {
+            let key = ChaCha20Poly1305System::generate_key(&CryptoConfig::default())?;
+            let ciphertext = ChaCha20Poly1305System::encrypt(&key, plaintext, additional_data)?.to_string();
+            Ok((ciphertext, Zeroizing::new(key.as_bytes().to_vec())))
+        }
+    }
+}
+
+/// 使用 KMS 解封得到的 DEK 解密 [`encrypt_enveloped`] 生成的密文
+///
+/// DEK 长度不符时返回 `Error::KeyImportFailed`，DEK 错误或密文被篡改时返回 `Error::DecryptionFailed`。
+pub fn decrypt_enveloped(
+    ciphertext: &str,
+    dek: &[u8],
+    algorithm: SymmetricAlgorithm,
+    additional_data: Option<&[u8]>,
+) -> Result<Vec<u8>, Error> {
+    match algorithm {
+        SymmetricAlgorithm::Aes256Gcm => {
+            let key = AesGcmKey::from_slice(dek)?;
+            AesGcmSystem::decrypt(&key, ciphertext, additional_data)
+        }
+        #[cfg(feature = "chacha")]
+        SymmetricAlgorithm::ChaCha20Poly1305 => {
+            let key = ChaCha20Poly1305Key::from_slice(dek)?;
+            ChaCha20Poly1305System::decrypt(&key, ciphertext, additional_data)
+        }
+    }
+}
+
+/// 按块读取内存映射区域的读取器，每次读取前检查源文件大小
+///
+/// 该检查只能尽早发现多数意外修改，不能防止检查与读取之间的并发截断，安全性依赖 [`encrypt_mmap`] 的调用约定。
+#[cfg(feature = "mmap")]
+struct MappedReader<'a> {
+    mapped: &'a [u8],
+    position: usize,
+    file: &'a File,
+}
+
+#[cfg(feature = "mmap")]
+impl Read for MappedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.file.metadata()?.len() != self.mapped.len() as u64 {
+            return Err(std::io::Error::other("输入文件在加密过程中大小发生变化"));
+        }
+        let remaining = &self.mapped[self.position..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// 以内存映射方式读取 `path_in` 并按块流式加密到 `path_out`，返回写入的密文字节数
+///
+/// 输出格式与 [`seal_reader_to_writer`] 相同，可用 [`open_reader_to_writer`] 解密。
+/// 每读取一块前会检查输入文件的大小，尽力发现处理期间的截断或追加，但这只是诊断手段。
+///
+/// # Safety
+///
+/// 调用方必须保证在函数返回前，没有任何进程或线程截断、改写或追加 `path_in`。
+/// 映射期间文件被截断时，访问映射区域会触发 `SIGBUS` 使进程终止；内容被改写时加密的是不确定的数据。
+/// 大小检查与读取之间存在竞态，无法替代这一约定；无法保证时请改用 [`seal_reader_to_writer`] 读取普通文件句柄。
+#[cfg(feature = "mmap")]
+pub unsafe fn encrypt_mmap<P: AsRef<Path>, Q: AsRef<Path>>(
+    path_in: P,
+    path_out: Q,
+    key: &[u8],
+    algorithm: SymmetricAlgorithm,
+) -> Result<u64, Error> {
+    let file = File::open(path_in)?;
+    let expected_len = file.metadata()?.len();
+    // 长度为0的文件无法映射，直接按空输入处理
+    let mapping = if expected_len == 0 {
+        None
+    } else {
+        // SAFETY: 只读映射；调用方保证处理期间文件不被修改（见本函数的 Safety 说明）
+        Some(unsafe { memmap2::Mmap::map(&file)? })
+    };
+    let mapped = mapping.as_deref().unwrap_or_default();
+    if mapped.len() as u64 != expected_len {
+        return Err(Error::Operation("输入文件在映射过程中大小发生变化".to_string()));
+    }
+
+    let reader = MappedReader { mapped, position: 0, file: &file };
+    let mut writer = std::io::BufWriter::new(File::create(path_out)?);
+    let written = seal_reader_to_writer(key, algorithm, reader, &mut writer)?;
+    writer.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::streaming::{FOOTER_SIZE, FRAME_HEADER_SIZE, STREAM_MAC_SIZE};
+    use std::io::Cursor;
+
+    const KEY: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let plaintext = vec![42u8; 200_000];
+
+        let mut sealed = Vec::new();
+        let written = seal_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&plaintext), &mut sealed).unwrap();
+        assert_eq!(written, sealed.len() as u64);
+        assert_ne!(sealed, plaintext);
+
+        let mut opened = Vec::new();
+        let written = open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&sealed), &mut opened).unwrap();
+        assert_eq!(written, plaintext.len() as u64);
+        assert_eq!(opened, plaintext);
+    }
+
+    #[cfg(feature = "chacha")]
+    #[test]
+    fn test_chacha_seal_and_envelope_roundtrip() {
+        let plaintext = vec![42u8; 200_000];
+        let mut sealed = Vec::new();
+        seal_reader_to_writer(&KEY, SymmetricAlgorithm::ChaCha20Poly1305, Cursor::new(&plaintext), &mut sealed).unwrap();
+        let mut opened = Vec::new();
+        open_reader_to_writer(&KEY, SymmetricAlgorithm::ChaCha20Poly1305, Cursor::new(&sealed), &mut opened).unwrap();
+        assert_eq!(opened, plaintext);
+        // 以另一种算法打开同一密文失败
+        assert!(open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&sealed), &mut Vec::new()).is_err());
+
+        let (ciphertext, dek) = encrypt_enveloped(b"kms payload", SymmetricAlgorithm::ChaCha20Poly1305, Some(b"tenant-1")).unwrap();
+        assert_eq!(dek.len(), 32);
+        assert_eq!(
+            decrypt_enveloped(&ciphertext, &dek, SymmetricAlgorithm::ChaCha20Poly1305, Some(b"tenant-1")).unwrap(),
+            b"kms payload"
+        );
+        assert!(decrypt_enveloped(&ciphertext, &dek, SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-1")).is_err());
+    }
+
+    #[test]
+    fn test_seal_empty_input() {
+        let mut sealed = Vec::new();
+        let written = seal_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(b""), &mut sealed).unwrap();
+        // 空输入只有完整性尾部
+        assert_eq!(written, sealed.len() as u64);
+
+        let mut opened = Vec::new();
+        assert_eq!(open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&sealed), &mut opened).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_open_with_wrong_key_fails() {
+        let mut sealed = Vec::new();
+        seal_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(b"secret"), &mut sealed).unwrap();
+
+        let mut opened = Vec::new();
+        let result = open_reader_to_writer(&[8u8; 32], SymmetricAlgorithm::Aes256Gcm, Cursor::new(&sealed), &mut opened);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_encrypt_mmap_large_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("large.bin");
+        let output_path = dir.path().join("large.sealed");
+        let plaintext: Vec<u8> = (0..8 * 1024 * 1024 + 123).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&input_path, &plaintext).unwrap();
+
+        // SAFETY: 临时目录中的文件只由本测试访问
+        let written = unsafe { encrypt_mmap(&input_path, &output_path, &KEY, SymmetricAlgorithm::Aes256Gcm) }.unwrap();
+        let sealed = std::fs::read(&output_path).unwrap();
+        assert_eq!(written, sealed.len() as u64);
+
+        let mut opened = Vec::new();
+        open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&sealed), &mut opened).unwrap();
+        assert_eq!(opened, plaintext);
+
+        let empty_path = dir.path().join("empty.bin");
+        std::fs::write(&empty_path, b"").unwrap();
+        let empty_sealed = dir.path().join("empty.sealed");
+        let written = unsafe { encrypt_mmap(&empty_path, &empty_sealed, &KEY, SymmetricAlgorithm::Aes256Gcm) }.unwrap();
+        let sealed = std::fs::read(&empty_sealed).unwrap();
+        assert_eq!(written, sealed.len() as u64);
+        assert_eq!(open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&sealed), &mut Vec::new()).unwrap(), 0);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_encrypt_mmap_detects_size_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("shrinking.bin");
+        std::fs::write(&input_path, vec![1u8; 4096]).unwrap();
+
+        let file = File::open(&input_path).unwrap();
+        let mapping = unsafe { memmap2::Mmap::map(&file).unwrap() };
+        std::fs::OpenOptions::new().append(true).open(&input_path).unwrap().write_all(b"more").unwrap();
+
+        let mut reader = MappedReader { mapped: &mapping, position: 0, file: &file };
+        let mut buf = [0u8; 16];
+        assert!(reader.read(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_length() {
+        let mut sealed = Vec::new();
+        let result = seal_reader_to_writer(&[0u8; 16], SymmetricAlgorithm::Aes256Gcm, Cursor::new(b"data"), &mut sealed);
+        assert!(matches!(result, Err(Error::KeyImportFailed(_))));
+    }
+
+    #[test]
+    fn test_enveloped_roundtrip_and_wrong_dek() {
+        let (ciphertext, dek) = encrypt_enveloped(b"kms payload", SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-1")).unwrap();
+        assert_eq!(dek.len(), 32);
+        assert_eq!(
+            decrypt_enveloped(&ciphertext, &dek, SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-1")).unwrap(),
+            b"kms payload"
+        );
+        assert!(decrypt_enveloped(&ciphertext, &dek, SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-2")).is_err());
+
+        // 每次加密都生成新的 DEK
+        let (_, other_dek) = encrypt_enveloped(b"kms payload", SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-1")).unwrap();
+        assert_ne!(*dek, *other_dek);
+        assert!(matches!(
+            decrypt_enveloped(&ciphertext, &other_dek, SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-1")),
+            Err(Error::DecryptionFailed(_))
+        ));
+        assert!(matches!(
+            decrypt_enveloped(&ciphertext, &dek[..16], SymmetricAlgorithm::Aes256Gcm, Some(b"tenant-1")),
+            Err(Error::KeyImportFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_open_detects_truncation_at_frame_boundary() {
+        let plaintext = vec![42u8; 200_000];
+        let mut sealed = Vec::new();
+        seal_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(&plaintext), &mut sealed).unwrap();
+
+        // 只保留头部与第一帧：长度前缀 || 帧内容
+        let first_frame_end = FRAME_HEADER_SIZE + 4
+            + u32::from_le_bytes(sealed[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + 4].try_into().unwrap()) as usize;
+        let truncated = &sealed[..first_frame_end];
+        assert!(truncated.len() < sealed.len());
+        let result = open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(truncated), &mut Vec::new());
+        assert!(matches!(result, Err(Error::StreamIntegrity(_))));
+
+        // 去掉尾部（标记 || 总帧数 || CRC32 || 全局认证标签）同样被拒绝
+        let without_footer = &sealed[..sealed.len() - 4 - FOOTER_SIZE - STREAM_MAC_SIZE];
+        let result = open_reader_to_writer(&KEY, SymmetricAlgorithm::Aes256Gcm, Cursor::new(without_footer), &mut Vec::new());
+        assert!(matches!(result, Err(Error::StreamIntegrity(_))));
+    }
+
+    #[test]
+    fn test_envelope_rejects_wrong_dek_length() {
+        let (ciphertext, dek) = encrypt_enveloped(b"payload", SymmetricAlgorithm::Aes256Gcm, None).unwrap();
+        assert_eq!(decrypt_enveloped(&ciphertext, &dek, SymmetricAlgorithm::Aes256Gcm, None).unwrap(), b"payload");
+        let result = decrypt_enveloped(&ciphertext, &dek[..16], SymmetricAlgorithm::Aes256Gcm, None);
+        assert!(matches!(result, Err(Error::KeyImportFailed(_))));
+    }
+}
@@ -0,0 +1,164 @@
+//! 双人控制的拆分密钥
+//!
+//! 实际使用的 AES-256-GCM 密钥是两份等长份额的异或：`密钥 = 份额A ⊕ 份额B`。两份份额分别交给不同的操作员保管，
+//! 任何一份单独都是均匀随机的 32 字节，不泄露关于密钥的任何信息，因此必须两人同时提供份额才能加解密。
+//! [`SplitKey`] 在内存中分别持有两份份额，只在每次加解密时临时合成密钥，用后即清零。
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::{engine::general_purpose, Engine as _};
+use zeroize::Zeroizing;
+
+use crate::common::errors::Error;
+use crate::common::utils::{Base64String, CryptoConfig};
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 份额长度，与 AES-256 密钥相同
+const SHARE_SIZE: usize = 32;
+
+/// 拆分密钥的一份份额，由一名操作员单独保管
+#[derive(Clone)]
+pub struct KeyShare(Zeroizing<[u8; SHARE_SIZE]>);
+
+impl core::fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("KeyShare").field(&"[REDACTED]").finish()
+    }
+}
+
+impl KeyShare {
+    /// 生成随机份额
+    pub fn random() -> Result<Self, Error> {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default())?;
+        Self::from_bytes(key.as_bytes())
+    }
+
+    /// 由 32 字节构造份额
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let share: [u8; SHARE_SIZE] = bytes
+            .try_into()
+            .map_err(|_| Error::KeyImportFailed(format!("份额长度应为 {} 字节，实际 {}", SHARE_SIZE, bytes.len())))?;
+        Ok(Self(Zeroizing::new(share)))
+    }
+
+    /// 导出为 Base64 字符串，交给保管该份额的操作员
+    pub fn export(&self) -> String {
+        general_purpose::STANDARD.encode(self.0.as_ref())
+    }
+
+    /// 从 Base64 字符串导入份额
+    pub fn import(encoded: &str) -> Result<Self, Error> {
+        let bytes = Zeroizing::new(
+            general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| Error::KeyImportFailed(format!("Base64 decoding failed: {}", e)))?,
+        );
+        Self::from_bytes(&bytes)
+    }
+
+    /// 与另一份额逐字节异或
+    fn xor(&self, other: &[u8]) -> Zeroizing<[u8; SHARE_SIZE]> {
+        let mut combined = Zeroizing::new([0u8; SHARE_SIZE]);
+        for (out, (a, b)) in combined.iter_mut().zip(self.0.iter().zip(other)) {
+            *out = a ^ b;
+        }
+        combined
+    }
+}
+
+/// 由两份份额异或得到有效密钥的拆分密钥
+#[derive(Clone, Debug)]
+pub struct SplitKey {
+    first: KeyShare,
+    second: KeyShare,
+}
+
+impl SplitKey {
+    /// 生成两份相互独立的随机份额，有效密钥随之确定
+    pub fn generate() -> Result<Self, Error> {
+        Ok(Self { first: KeyShare::random()?, second: KeyShare::random()? })
+    }
+
+    /// 把已有密钥拆分为两份份额：第一份随机，第二份为 `密钥 ⊕ 第一份`
+    pub fn split(key: &AesGcmKey) -> Result<Self, Error> {
+        if key.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
+        let first = KeyShare::random()?;
+        let second = KeyShare(first.xor(key.as_bytes()));
+        Ok(Self { first, second })
+    }
+
+    /// 由两名操作员各自提供的份额组合
+    pub fn from_halves(first: KeyShare, second: KeyShare) -> Self {
+        Self { first, second }
+    }
+
+    /// 拆回两份份额，分别交给不同的操作员保管
+    pub fn into_halves(self) -> (KeyShare, KeyShare) {
+        (self.first, self.second)
+    }
+
+    /// 以合成的有效密钥加密
+    pub fn encrypt(&self, plaintext: &[u8], additional_data: Option<&[u8]>) -> Result<Base64String, Error> {
+        AesGcmSystem::encrypt(&self.effective_key(), plaintext, additional_data)
+    }
+
+    /// 以合成的有效密钥解密；任一份额错误时得到的是另一把密钥，认证失败
+    pub fn decrypt(&self, ciphertext: &str, additional_data: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        AesGcmSystem::decrypt(&self.effective_key(), ciphertext, additional_data)
+    }
+
+    /// 临时合成有效密钥，调用方用后丢弃即清零
+    pub fn effective_key(&self) -> AesGcmKey {
+        AesGcmKey::from_derived(*self.first.xor(self.second.0.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_key_requires_both_halves() {
+        let split = SplitKey::generate().unwrap();
+        let ciphertext = split.encrypt(b"dual control", Some(b"aad")).unwrap().to_string();
+
+        // 份额分别导出、保管后再组合
+        let (first, second) = split.into_halves();
+        let (first_export, second_export) = (first.export(), second.export());
+        let rejoined = SplitKey::from_halves(KeyShare::import(&first_export).unwrap(), KeyShare::import(&second_export).unwrap());
+        assert_eq!(rejoined.decrypt(&ciphertext, Some(b"aad")).unwrap(), b"dual control");
+
+        // 只有一份真实份额时合成的是另一把密钥
+        let stranger = KeyShare::random().unwrap();
+        for partial in [
+            SplitKey::from_halves(first.clone(), stranger.clone()),
+            SplitKey::from_halves(stranger.clone(), second.clone()),
+        ] {
+            assert!(partial.decrypt(&ciphertext, Some(b"aad")).is_err());
+        }
+        // 单独一份份额直接当作密钥同样无法解密
+        for share in [&first_export, &second_export] {
+            let key = AesGcmSystem::import_key(share).unwrap();
+            assert!(AesGcmSystem::decrypt(&key, &ciphertext, Some(b"aad")).is_err());
+        }
+    }
+
+    #[test]
+    fn test_split_existing_key() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let ciphertext = AesGcmSystem::encrypt(&key, b"existing", None).unwrap().to_string();
+        let split = SplitKey::split(&key).unwrap();
+        assert_eq!(split.decrypt(&ciphertext, None).unwrap(), b"existing");
+        assert_eq!(split.effective_key().as_bytes(), key.as_bytes());
+
+        let (first, second) = split.into_halves();
+        assert_ne!(first.export(), AesGcmSystem::export_key(&key).unwrap());
+        assert_ne!(second.export(), AesGcmSystem::export_key(&key).unwrap());
+        assert!(KeyShare::from_bytes(&[0u8; 16]).is_err());
+        assert!(format!("{:?}", first).contains("REDACTED"));
+    }
+}
@@ -1,2 +1,129 @@
 #[cfg(feature = "aes-gcm-feature")]
-pub mod aes_gcm;
\ No newline at end of file
+pub mod aes_gcm;
+#[cfg(feature = "aes-xts")]
+pub mod aes_xts;
+#[cfg(all(feature = "chacha", feature = "aes-gcm-feature"))]
+pub mod chacha20_poly1305;
+
+#[cfg(feature = "aes-gcm-feature")]
+use crate::common::utils::KeyProtection;
+
+/// 数据加密可选用的对称 AEAD 算法
+///
+/// 引擎的算法由类型参数在编译期确定，本类型用于在运行时做出选择后据此创建对应的引擎：
+///
+/// ```ignore
+/// match SymmetricAlgorithm::auto() {
+///     SymmetricAlgorithm::Aes256Gcm => { SymmetricQSealEngine::<AesGcmSystem>::new(config, "data")?; }
+///     SymmetricAlgorithm::ChaCha20Poly1305 => { SymmetricQSealEngine::<ChaCha20Poly1305System>::new(config, "data")?; }
+/// }
+/// ```
+///
+/// `symmetric::seal` 中的流式与信封加密辅助函数也直接接受本类型。
+///
+/// 两种算法的密文互不兼容，选择结果应随数据一起记录。ChaCha20-Poly1305 需要启用 `chacha` 特性。
+#[cfg(feature = "aes-gcm-feature")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymmetricAlgorithm {
+    /// AES-256-GCM，对应 [`aes_gcm::AesGcmSystem`]
+    Aes256Gcm,
+    /// ChaCha20-Poly1305，对应 [`chacha20_poly1305::ChaCha20Poly1305System`]
+    #[cfg(feature = "chacha")]
+    ChaCha20Poly1305,
+}
+
+#[cfg(feature = "aes-gcm-feature")]
+impl SymmetricAlgorithm {
+    /// 当前编译配置下可用的全部算法
+    pub fn available() -> alloc::vec::Vec<Self> {
+        alloc::vec![
+            SymmetricAlgorithm::Aes256Gcm,
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305,
+        ]
+    }
+
+    /// 按 CPU 是否具备 AES 硬件加速选择数据加密算法：有加速时用 AES-256-GCM，否则用 ChaCha20-Poly1305
+    ///
+    /// 未启用 `chacha` 特性时总是返回 AES-256-GCM。
+    pub fn select(aes_accelerated: bool) -> Self {
+        #[cfg(feature = "chacha")]
+        if !aes_accelerated {
+            return SymmetricAlgorithm::ChaCha20Poly1305;
+        }
+        let _ = aes_accelerated;
+        SymmetricAlgorithm::Aes256Gcm
+    }
+
+    /// 检测当前 CPU 后调用 [`SymmetricAlgorithm::select`]
+    #[cfg(feature = "std")]
+    pub fn auto() -> Self {
+        Self::select(crate::cpu::has_aes_acceleration())
+    }
+
+    /// 同一算法对应的密钥容器保护算法
+    pub fn key_protection(&self) -> KeyProtection {
+        match self {
+            SymmetricAlgorithm::Aes256Gcm => KeyProtection::Aes256Gcm,
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => KeyProtection::ChaCha20Poly1305,
+        }
+    }
+
+    /// 算法名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymmetricAlgorithm::Aes256Gcm => "AES-256-GCM",
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+        }
+    }
+}
+
+#[cfg(all(test, feature = "aes-gcm-feature"))]
+mod tests {
+    use super::*;
+    use crate::common::errors::Error;
+    use crate::common::utils::CryptoConfig;
+    use crate::symmetric::traits::SymmetricCryptographicSystem;
+
+    /// 以 `algorithm` 对应的系统加密后解密
+    fn roundtrip(algorithm: SymmetricAlgorithm, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        fn run<S: SymmetricCryptographicSystem<Error = Error>>(plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+            let key = S::generate_key(&CryptoConfig::default())?;
+            let ciphertext = S::encrypt(&key, plaintext, Some(b"aad"))?.to_string();
+            S::decrypt(&key, &ciphertext, Some(b"aad"))
+        }
+        match algorithm {
+            SymmetricAlgorithm::Aes256Gcm => run::<aes_gcm::AesGcmSystem>(plaintext),
+            #[cfg(feature = "chacha")]
+            SymmetricAlgorithm::ChaCha20Poly1305 => run::<chacha20_poly1305::ChaCha20Poly1305System>(plaintext),
+        }
+    }
+
+    #[test]
+    fn test_select_with_acceleration_uses_aes_gcm() {
+        let algorithm = SymmetricAlgorithm::select(true);
+        assert_eq!(algorithm, SymmetricAlgorithm::Aes256Gcm);
+        assert_eq!(roundtrip(algorithm, b"accelerated").unwrap(), b"accelerated");
+    }
+
+    #[test]
+    fn test_select_without_acceleration() {
+        let algorithm = SymmetricAlgorithm::select(false);
+        #[cfg(feature = "chacha")]
+        assert_eq!(algorithm, SymmetricAlgorithm::ChaCha20Poly1305);
+        #[cfg(not(feature = "chacha"))]
+        assert_eq!(algorithm, SymmetricAlgorithm::Aes256Gcm);
+        assert_eq!(roundtrip(algorithm, b"software only").unwrap(), b"software only");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_auto_follows_cpu_detection() {
+        let algorithm = SymmetricAlgorithm::auto();
+        assert_eq!(algorithm, SymmetricAlgorithm::select(crate::cpu::has_aes_acceleration()));
+        assert_eq!(roundtrip(algorithm, b"auto").unwrap(), b"auto");
+        assert!(SymmetricAlgorithm::available().contains(&algorithm));
+    }
+}
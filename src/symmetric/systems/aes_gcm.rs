@@ -1,58 +1,131 @@
 //! AES-GCM 对称加密实现
-use rand_core::{OsRng, TryRngCore};
-use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
-use aes_gcm::aead::{Aead, Payload};
+use rand_core::TryRngCore;
+use aes_gcm::{AesGcm, Aes256Gcm, Key, KeyInit, Nonce as GcmNonce, TagSize};
+use aes_gcm::aead::{Aead, AeadInPlace, Payload};
+use aes_gcm::aead::consts::{U12, U13, U14, U15, U16};
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aes::Aes256;
 use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
-use crate::common::errors::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use crate::common::errors::{DecryptDiagnostics, DecryptFailureCause, Error};
+use crate::common::nonce::Nonce;
+use crate::common::rng::SystemRng;
 use crate::symmetric::traits::SymmetricCryptographicSystem;
-use std::fmt::Debug;
-use crate::common::utils::{Base64String, CryptoConfig};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use crate::common::utils::{check_weak_key, from_base64, Base64String, CryptoConfig, NonceStrategy};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 
 const KEY_SIZE: usize = 32; // AES-256 需要 32 字节的密钥
 const NONCE_SIZE: usize = 12; // GCM 标准的 Nonce 大小是 12 字节
+const TAG_SIZE: usize = 16; // GCM 认证标签大小
+/// 由密钥派生合成 Nonce 所用 MAC 子密钥时的 HKDF info
+const SYNTHETIC_NONCE_INFO: &[u8] = b"seal-kit synthetic nonce v1";
+/// 由密钥派生截断标签格式所用子密钥时的 HKDF info，其后追加 1 字节的标签长度
+const TRUNCATED_TAG_INFO: &[u8] = b"seal-kit truncated tag v1";
+/// 截断标签的安全下限（NIST SP 800-38D 对通用场景的最低要求）
+pub const MIN_TAG_SIZE: usize = 12;
+
+/// AES-GCM 使用的 Nonce 类型
+pub type AesGcmNonce = Nonce<NONCE_SIZE>;
 
 /// AES-GCM 对称加密系统
 pub struct AesGcmSystem;
 
-/// AES-GCM 密钥的包装，以支持序列化和调试
-#[derive(Clone, Serialize, Deserialize)]
+/// AES-GCM 密钥的包装，以支持序列化和调试；丢弃时清零密钥字节
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct AesGcmKey(Vec<u8>);
 
 impl Debug for AesGcmKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("AesGcmKey").finish_non_exhaustive()
     }
 }
 
-impl SymmetricCryptographicSystem for AesGcmSystem {
-    type Key = AesGcmKey;
-    type CiphertextOutput = Base64String;
-    type Error = Error;
+impl AesGcmKey {
+    /// 由已派生好的密钥字节构造
+    pub(crate) fn from_derived(bytes: [u8; KEY_SIZE]) -> Self {
+        AesGcmKey(bytes.to_vec())
+    }
 
-    /// 生成一个随机的 AES-256 密钥
-    fn generate_key(_config: &CryptoConfig) -> Result<Self::Key, Self::Error> {
+    /// 由原始密钥字节构造，不经过 Base64 等中间表示；长度不是32字节时返回 `Error::KeyImportFailed`
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != KEY_SIZE {
+            return Err(Error::KeyImportFailed(format!("Invalid key size: expected {}, got {}", KEY_SIZE, bytes.len())));
+        }
+        Ok(AesGcmKey(bytes.to_vec()))
+    }
+
+    /// 原始密钥字节
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// 立即清零并丢弃密钥，不必等到离开作用域
+    ///
+    /// 只清零这一份；此前 `clone` 出的副本不受影响。需要让仍被其他结构持有的密钥失效时，
+    /// 对其调用 [`Zeroize::zeroize`]，之后任何运算都返回 `Error::KeyZeroized`。
+    pub fn zeroize_now(mut self) {
+        self.zeroize();
+    }
+
+    /// 密钥是否已被清零
+    pub fn is_zeroized(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 供 AEAD 使用的密钥，已清零时返回 `Error::KeyZeroized`
+    fn cipher_key(&self) -> Result<&Key<Aes256Gcm>, Error> {
+        if self.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
+        Ok(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+}
+
+/// 使用给定的随机数生成器填充缓冲区，失败时返回 `Error::RngFailure`
+fn fill_random<R: TryRngCore + ?Sized>(rng: &mut R, buf: &mut [u8]) -> Result<(), Error> {
+    rng.try_fill_bytes(buf)
+        .map_err(|e| Error::RngFailure(e.to_string()))
+}
+
+impl AesGcmSystem {
+    /// 使用指定的随机数生成器生成密钥
+    pub(crate) fn generate_key_with_rng<R: TryRngCore + ?Sized>(rng: &mut R) -> Result<AesGcmKey, Error> {
         let mut key_bytes = vec![0u8; KEY_SIZE];
-        OsRng.try_fill_bytes(&mut key_bytes)
-            .map_err(|e| Error::Operation(e.to_string()))?;
+        fill_random(rng, &mut key_bytes)?;
         Ok(AesGcmKey(key_bytes))
+    }
 
+    /// 使用指定的随机数生成器生成 Nonce 并加密数据
+    pub(crate) fn encrypt_with_rng<R: TryRngCore + ?Sized>(
+        rng: &mut R,
+        key: &AesGcmKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<Base64String, Error> {
+        let nonce = AesGcmNonce::random_from(rng)?;
+        Self::encrypt_with_nonce(key, &nonce, plaintext, additional_data)
     }
 
-    /// 使用 AES-256-GCM 加密数据
-    /// Nonce 会被预置在密文前，然后整体进行 Base64 编码
-    fn encrypt(
-        key: &Self::Key,
+    /// 使用调用方提供的 Nonce 加密数据
+    ///
+    /// 调用方必须保证同一密钥下 Nonce 绝不重复，否则 GCM 的机密性与完整性都会被破坏。
+    pub fn encrypt_with_nonce(
+        key: &AesGcmKey,
+        nonce: &AesGcmNonce,
         plaintext: &[u8],
         additional_data: Option<&[u8]>,
-    ) -> Result<Self::CiphertextOutput, Self::Error> {
-        let key = Key::<Aes256Gcm>::from_slice(&key.0);
+    ) -> Result<Base64String, Error> {
+        let key = key.cipher_key()?;
         let cipher = Aes256Gcm::new(key);
-
-        let mut nonce_bytes = vec![0u8; NONCE_SIZE];
-        OsRng.try_fill_bytes(&mut nonce_bytes)
-            .map_err(|e| Error::Operation(e.to_string()))?;
-        let nonce = Nonce::from_slice(&nonce_bytes);
+        let nonce = GcmNonce::from_slice(nonce.as_ref());
         
         let aad = additional_data.unwrap_or_default();
 
@@ -66,34 +139,503 @@ impl SymmetricCryptographicSystem for AesGcmSystem {
         Ok(Base64String::from(result))
     }
 
+    /// 以指定的认证标签长度加密数据，`tag_length` 必须在 [`MIN_TAG_SIZE`] 到 16 字节之间
+    ///
+    /// 16 字节时输出与 [`encrypt`](SymmetricCryptographicSystem::encrypt) 相同；
+    /// 更短的标签输出 `标签长度(1字节) || Nonce || 密文 || 截断标签`，解密时据此确定标签长度。
+    /// 截断格式以按标签长度派生的子密钥加密，改写首字节或把完整标签截短都无法通过认证。
+    /// 低于安全下限时返回 `Error::InsecureParameter`。
+    pub fn encrypt_with_tag_length(
+        key: &AesGcmKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        tag_length: usize,
+    ) -> Result<Base64String, Error> {
+        check_tag_length(tag_length)?;
+        let nonce = AesGcmNonce::random_from(&mut SystemRng)?;
+        Self::seal_with_tag_length(key, &nonce, plaintext, additional_data, tag_length)
+    }
+
+    /// 以合成 Nonce 加密数据，相同的密钥、明文与附加认证数据总是得到相同的密文
+    ///
+    /// Nonce 的构造与安全性说明见 [`NonceStrategy::SyntheticFromPlaintext`]。
+    pub fn encrypt_synthetic(
+        key: &AesGcmKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<Base64String, Error> {
+        let nonce = Self::synthetic_nonce(key, plaintext, additional_data);
+        Self::encrypt_with_nonce(key, &nonce, plaintext, additional_data)
+    }
+
+    /// 计算 [`NonceStrategy::SyntheticFromPlaintext`] 下加密 `plaintext` 使用的 Nonce
+    ///
+    /// 先以 HKDF 从密钥派生独立的 MAC 子密钥，再计算 `HMAC-SHA256(子密钥, AAD长度(8字节大端) || AAD || 明文)`
+    /// 并取前 12 字节。AAD 参与计算，因此同一明文搭配不同 AAD 也不会重用 Nonce。
+    pub fn synthetic_nonce(key: &AesGcmKey, plaintext: &[u8], additional_data: Option<&[u8]>) -> AesGcmNonce {
+        let aad = additional_data.unwrap_or_default();
+        let mut mac_key = zeroize::Zeroizing::new([0u8; KEY_SIZE]);
+        Hkdf::<Sha256>::new(None, &key.0)
+            .expand(SYNTHETIC_NONCE_INFO, mac_key.as_mut())
+            .expect("32 字节在 HKDF-SHA256 的输出上限之内");
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(mac_key.as_ref()).expect("HMAC 接受任意长度的密钥");
+        mac.update(&(aad.len() as u64).to_be_bytes());
+        mac.update(aad);
+        mac.update(plaintext);
+        let digest = mac.finalize().into_bytes();
+        AesGcmNonce::from_slice(&digest[..NONCE_SIZE]).expect("截取的长度等于 Nonce 长度")
+    }
+
+    /// 按 `strategy` 生成加密 `plaintext` 所用的 Nonce
+    fn nonce_for(
+        strategy: NonceStrategy,
+        key: &AesGcmKey,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<AesGcmNonce, Error> {
+        match strategy {
+            NonceStrategy::Random => AesGcmNonce::random_from(&mut SystemRng),
+            NonceStrategy::SyntheticFromPlaintext => Ok(Self::synthetic_nonce(key, plaintext, additional_data)),
+        }
+    }
+
+    /// 以给定的 Nonce 与标签长度加密，输出格式同 [`encrypt_with_tag_length`](Self::encrypt_with_tag_length)
+    fn seal_with_tag_length(
+        key: &AesGcmKey,
+        nonce: &AesGcmNonce,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        tag_length: usize,
+    ) -> Result<Base64String, Error> {
+        if tag_length == TAG_SIZE {
+            return Self::encrypt_with_nonce(key, nonce, plaintext, additional_data);
+        }
+
+        let key = truncated_tag_key(key, tag_length)?;
+        let payload = Payload { msg: plaintext, aad: additional_data.unwrap_or_default() };
+        let ciphertext = match tag_length {
+            12 => seal_truncated::<U12>(&key, nonce, payload),
+            13 => seal_truncated::<U13>(&key, nonce, payload),
+            14 => seal_truncated::<U14>(&key, nonce, payload),
+            _ => seal_truncated::<U15>(&key, nonce, payload),
+        }?;
+
+        let mut result = Vec::with_capacity(1 + NONCE_SIZE + ciphertext.len());
+        result.push(tag_length as u8);
+        result.extend_from_slice(nonce.as_ref());
+        result.extend_from_slice(&ciphertext);
+        Ok(Base64String::from(result))
+    }
+
+    /// 以 `tag_length` 字节的标签加密 `plaintext_len` 字节明文时的密文字节数（Base64 编码前）
+    #[cfg(feature = "std")]
+    pub(crate) fn ciphertext_len(plaintext_len: usize, tag_length: usize) -> Result<usize, Error> {
+        check_tag_length(tag_length)?;
+        let header = if tag_length == TAG_SIZE { 0 } else { 1 };
+        Ok(header + NONCE_SIZE + plaintext_len + tag_length)
+    }
+
+    /// 解码后的密文是否足以容纳 Nonce 与认证标签（标准格式，或首字节为有效截断标签长度的截断格式）
+    pub(crate) fn has_valid_layout(decoded: &[u8]) -> bool {
+        decoded.len() >= NONCE_SIZE + TAG_SIZE
+            || decoded.split_first().is_some_and(|(&tag_length, rest)| {
+                (MIN_TAG_SIZE..TAG_SIZE).contains(&(tag_length as usize)) && rest.len() >= NONCE_SIZE + tag_length as usize
+            })
+    }
+
+    /// 解密截断标签格式的密文，首字节不是有效的截断标签长度时返回 `None`
+    fn decrypt_truncated(key: &AesGcmKey, decoded: &[u8], aad: &[u8]) -> Option<Result<Vec<u8>, Error>> {
+        let (&tag_length, rest) = decoded.split_first()?;
+        if !(MIN_TAG_SIZE..TAG_SIZE).contains(&(tag_length as usize)) || rest.len() < NONCE_SIZE + tag_length as usize {
+            return None;
+        }
+        let key = match truncated_tag_key(key, tag_length as usize) {
+            Ok(key) => key,
+            Err(e) => return Some(Err(e)),
+        };
+        let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+        let payload = Payload { msg: ciphertext, aad };
+        Some(match tag_length {
+            12 => open_truncated::<U12>(&key, nonce, payload),
+            13 => open_truncated::<U13>(&key, nonce, payload),
+            14 => open_truncated::<U14>(&key, nonce, payload),
+            _ => open_truncated::<U15>(&key, nonce, payload),
+        })
+    }
+
+    /// 按 16 字节标签的标准格式解密已解码的密文
+    fn decrypt_standard(key: &AesGcmKey, decoded: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        if decoded.len() < NONCE_SIZE {
+            return Err(Error::DecryptionFailed("Ciphertext is too short to contain a nonce".to_string()));
+        }
+        let (nonce, ciphertext) = decoded.split_at(NONCE_SIZE);
+        Aes256Gcm::new(key.cipher_key()?)
+            .decrypt(GcmNonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+            .map_err(|e| Error::DecryptionFailed(e.to_string()))
+    }
+
+    /// 与 [`decrypt`](SymmetricCryptographicSystem::decrypt) 相同，但失败时返回带诊断信息的 `Error::AuthenticationFailed`
+    ///
+    /// Base64 解码失败或长度不足以容纳 Nonce 与标签时，可能原因只有 `CorruptFormat`。
+    /// 认证失败时列出错误的密钥、被篡改与附加认证数据不一致三种原因；若提供了附加认证数据，
+    /// 还会不带附加认证数据重试一次：成功说明密文加密时未绑定附加认证数据，原因只剩 `WrongAad`（试探得到的明文立即清零丢弃）。
+    pub fn decrypt_diagnosed(
+        key: &AesGcmKey,
+        ciphertext_b64: &str,
+        additional_data: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        let corrupt = |detail: String| Error::AuthenticationFailed(DecryptDiagnostics {
+            format_valid: false,
+            possible_causes: vec![DecryptFailureCause::CorruptFormat],
+            detail,
+        });
+        let decoded = from_base64(ciphertext_b64).map_err(|e| corrupt(format!("Base64 decoding failed: {}", e)))?;
+        if decoded.len() < NONCE_SIZE + MIN_TAG_SIZE {
+            return Err(corrupt("Ciphertext is too short to contain a nonce and tag".to_string()));
+        }
+
+        let error = match <Self as SymmetricCryptographicSystem>::decrypt(key, ciphertext_b64, additional_data) {
+            Ok(plaintext) => return Ok(plaintext),
+            Err(error) => error,
+        };
+        let aad_only = additional_data.is_some_and(|aad| !aad.is_empty())
+            && <Self as SymmetricCryptographicSystem>::decrypt(key, ciphertext_b64, None)
+                .map(zeroize::Zeroizing::new)
+                .is_ok();
+        let possible_causes = if aad_only {
+            vec![DecryptFailureCause::WrongAad]
+        } else {
+            vec![DecryptFailureCause::WrongKey, DecryptFailureCause::Tampered, DecryptFailureCause::WrongAad]
+        };
+        let detail = match error {
+            Error::DecryptionFailed(msg) => msg,
+            other => other.to_string(),
+        };
+        Err(Error::AuthenticationFailed(DecryptDiagnostics { format_valid: true, possible_causes, detail }))
+    }
+
+    /// 将密文解密到调用方提供的缓冲区，返回明文长度，整个过程不分配堆内存
+    ///
+    /// 缓冲区小于明文长度时返回 `Error::BufferTooSmall`，其中给出所需的字节数；解密失败时缓冲区内容无意义。
+    /// 支持标准与截断标签两种格式，但只接受不含空白的标准或 URL 安全 Base64（填充可省略）。
+    pub fn decrypt_into(
+        key: &AesGcmKey,
+        ciphertext: &str,
+        out: &mut [u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<usize, Error> {
+        let aad = additional_data.unwrap_or_default();
+        let decoded_len = base64_decoded_len(ciphertext)?;
+        let too_short = || Error::DecryptionFailed("Ciphertext is too short to contain a nonce and tag".to_string());
+        if decoded_len < 1 + NONCE_SIZE + MIN_TAG_SIZE {
+            return Err(too_short());
+        }
+
+        let mut header = 0u8;
+        let mut nonce = [0u8; NONCE_SIZE];
+        let mut tag = [0u8; TAG_SIZE];
+        // 两种格式的最小长度不同：短于标准格式下限的密文只可能是截断标签格式
+        let standard = decoded_len >= NONCE_SIZE + TAG_SIZE;
+        if standard {
+            let required = decoded_len - NONCE_SIZE - TAG_SIZE;
+            if out.len() < required {
+                return Err(Error::BufferTooSmall { required, provided: out.len() });
+            }
+            decode_base64_with(ciphertext, |index, byte| {
+                if index == 0 {
+                    header = byte;
+                }
+                match index {
+                    i if i < NONCE_SIZE => nonce[i] = byte,
+                    i if i < NONCE_SIZE + required => out[i - NONCE_SIZE] = byte,
+                    i => tag[i - NONCE_SIZE - required] = byte,
+                }
+            })?;
+            if open_in_place::<U16>(key, &nonce, aad, &mut out[..required], &tag).is_ok() {
+                return Ok(required);
+            }
+        } else {
+            decode_base64_with(ciphertext, |index, byte| {
+                if index == 0 {
+                    header = byte;
+                }
+            })?;
+        }
+
+        // 首字节是有效的截断标签长度时，再按截断标签格式尝试
+        let tag_length = header as usize;
+        let Some(required) = decoded_len.checked_sub(1 + NONCE_SIZE + tag_length)
+            .filter(|_| (MIN_TAG_SIZE..TAG_SIZE).contains(&tag_length))
+        else {
+            return Err(if standard { Error::DecryptionFailed("aead::Error".to_string()) } else { too_short() });
+        };
+        if out.len() < required {
+            return Err(Error::BufferTooSmall { required, provided: out.len() });
+        }
+        decode_base64_with(ciphertext, |index, byte| match index {
+            0 => {}
+            i if i <= NONCE_SIZE => nonce[i - 1] = byte,
+            i if i <= NONCE_SIZE + required => out[i - 1 - NONCE_SIZE] = byte,
+            i => tag[i - 1 - NONCE_SIZE - required] = byte,
+        })?;
+        let key = truncated_tag_key(key, tag_length)?;
+        let buffer = &mut out[..required];
+        let tag = &tag[..tag_length];
+        match tag_length {
+            12 => open_in_place::<U12>(&key, &nonce, aad, buffer, tag),
+            13 => open_in_place::<U13>(&key, &nonce, aad, buffer, tag),
+            14 => open_in_place::<U14>(&key, &nonce, aad, buffer, tag),
+            _ => open_in_place::<U15>(&key, &nonce, aad, buffer, tag),
+        }?;
+        Ok(required)
+    }
+
+    /// 仅认证不加密：以空明文、`data` 作为附加认证数据执行 AES-GCM，返回 `Nonce || 标签`
+    ///
+    /// 适用于必须保持明文的数据（如头部），可与加密使用同一密钥。
+    pub fn authenticate(key: &AesGcmKey, data: &[u8]) -> Result<Base64String, Error> {
+        <Self as SymmetricCryptographicSystem>::encrypt(key, &[], Some(data))
+    }
+
+    /// 校验 [`authenticate`](Self::authenticate) 生成的认证标签
+    ///
+    /// 标签与数据匹配时返回 `Ok(true)`，数据被篡改或密钥不符时返回 `Ok(false)`；
+    /// 标签本身格式错误时返回 `Error::Format`。
+    pub fn verify_authentication(key: &AesGcmKey, data: &[u8], tag: &str) -> Result<bool, Error> {
+        let decoded = from_base64(tag)?;
+        if decoded.len() != NONCE_SIZE + TAG_SIZE {
+            return Err(Error::Format(format!(
+                "无效的认证标签长度: 期望 {} 字节, 实际 {} 字节", NONCE_SIZE + TAG_SIZE, decoded.len()
+            )));
+        }
+        Ok(<Self as SymmetricCryptographicSystem>::decrypt(key, tag, Some(data)).is_ok())
+    }
+}
+
+/// 校验标签长度：低于安全下限返回 `Error::InsecureParameter`，超过 16 字节返回 `Error::Operation`
+fn check_tag_length(tag_length: usize) -> Result<(), Error> {
+    if tag_length < MIN_TAG_SIZE {
+        return Err(Error::InsecureParameter(vec![format!(
+            "tag_length = {}，低于 AES-GCM 认证标签的安全下限 {} 字节", tag_length, MIN_TAG_SIZE
+        )]));
+    }
+    if tag_length > TAG_SIZE {
+        return Err(Error::Operation(format!("AES-GCM 认证标签最长为 {} 字节，实际 {}", TAG_SIZE, tag_length)));
+    }
+    Ok(())
+}
+
+/// 截断标签格式使用的子密钥：以 HKDF 从密钥派生，`info` 含标签长度，不同标签长度的子密钥互不相同
+fn truncated_tag_key(key: &AesGcmKey, tag_length: usize) -> Result<AesGcmKey, Error> {
+    key.cipher_key()?;
+    let mut okm = zeroize::Zeroizing::new([0u8; KEY_SIZE]);
+    Hkdf::<Sha256>::new(None, &key.0)
+        .expand_multi_info(&[TRUNCATED_TAG_INFO, &[tag_length as u8]], okm.as_mut())
+        .expect("32 字节在 HKDF-SHA256 的输出上限之内");
+    Ok(AesGcmKey::from_derived(*okm))
+}
+
+/// 密文的标签格式与配置的标签长度不符时返回的错误
+fn tag_length_mismatch(tag_length: usize) -> Error {
+    Error::DecryptionFailed(format!("密文的认证标签格式与配置的 {} 字节标签长度不符", tag_length))
+}
+
+fn seal_truncated<T: TagSize>(key: &AesGcmKey, nonce: &AesGcmNonce, payload: Payload) -> Result<Vec<u8>, Error> {
+    AesGcm::<Aes256, U12, T>::new(key.cipher_key()?)
+        .encrypt(GcmNonce::from_slice(nonce.as_ref()), payload)
+        .map_err(|e| Error::EncryptionFailed(e.to_string()))
+}
+
+fn open_truncated<T: TagSize>(key: &AesGcmKey, nonce: &[u8], payload: Payload) -> Result<Vec<u8>, Error> {
+    AesGcm::<Aes256, U12, T>::new(key.cipher_key()?)
+        .decrypt(GcmNonce::from_slice(nonce), payload)
+        .map_err(|e| Error::DecryptionFailed(e.to_string()))
+}
+
+fn open_in_place<T: TagSize>(key: &AesGcmKey, nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), Error> {
+    AesGcm::<Aes256, U12, T>::new(key.cipher_key()?)
+        .decrypt_in_place_detached(GcmNonce::from_slice(nonce), aad, buffer, GenericArray::from_slice(tag))
+        .map_err(|e| Error::DecryptionFailed(e.to_string()))
+}
+
+/// 去掉末尾填充后的 Base64 正文
+fn base64_body(text: &str) -> &str {
+    let body = text.strip_suffix('=').unwrap_or(text);
+    body.strip_suffix('=').unwrap_or(body)
+}
+
+/// Base64 文本解码后的字节数，长度不可能合法时返回 `Error::DecryptionFailed`
+fn base64_decoded_len(text: &str) -> Result<usize, Error> {
+    let body = base64_body(text);
+    let partial = match body.len() % 4 {
+        0 => 0,
+        2 => 1,
+        3 => 2,
+        _ => return Err(Error::DecryptionFailed("Base64 decoding failed: invalid length".to_string())),
+    };
+    Ok(body.len() / 4 * 3 + partial)
+}
+
+/// 不分配内存地解码标准或 URL 安全 Base64，按顺序把每个字节及其序号交给 `sink`
+fn decode_base64_with(text: &str, mut sink: impl FnMut(usize, u8)) -> Result<(), Error> {
+    let (mut buffer, mut bits, mut index) = (0u32, 0u32, 0usize);
+    for c in base64_body(text).bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return Err(Error::DecryptionFailed(format!("Base64 decoding failed: invalid byte {:?}", c as char))),
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            sink(index, (buffer >> bits) as u8);
+            index += 1;
+        }
+    }
+    Ok(())
+}
+
+impl SymmetricCryptographicSystem for AesGcmSystem {
+    type Key = AesGcmKey;
+    type CiphertextOutput = Base64String;
+    type Error = Error;
+
+    /// 生成一个随机的 AES-256 密钥
+    fn generate_key(_config: &CryptoConfig) -> Result<Self::Key, Self::Error> {
+        Self::generate_key_with_rng(&mut SystemRng)
+    }
+
+    /// 使用 AES-256-GCM 加密数据
+    /// Nonce 会被预置在密文前，然后整体进行 Base64 编码
+    fn encrypt(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        Self::encrypt_with_rng(&mut SystemRng, key, plaintext, additional_data)
+    }
+
+    /// 按 `config.tag_length` 指定的认证标签长度、`config.nonce_strategy` 指定的 Nonce 生成方式加密
+    fn encrypt_with_config(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        check_tag_length(config.tag_length)?;
+        let nonce = Self::nonce_for(config.nonce_strategy, key, plaintext, additional_data)?;
+        Self::seal_with_tag_length(key, &nonce, plaintext, additional_data, config.tag_length)
+    }
+
     /// 解密 AES-256-GCM 加密的数据
     /// 输入是 Base64 编码的字符串，其中包含了 Nonce 和密文
+    ///
+    /// 16 字节标签的标准格式解密失败时，若首字节是有效的截断标签长度，再按截断标签格式尝试解密。
     fn decrypt(
         key: &Self::Key,
         ciphertext_b64: &str,
         additional_data: Option<&[u8]>,
     ) -> Result<Vec<u8>, Self::Error> {
-        let key = Key::<Aes256Gcm>::from_slice(&key.0);
-        let cipher = Aes256Gcm::new(key);
-        
-        let decoded_data = general_purpose::STANDARD.decode(ciphertext_b64)
+        let decoded_data = from_base64(ciphertext_b64)
             .map_err(|e| Error::DecryptionFailed(format!("Base64 decoding failed: {}", e)))?;
+        let aad = additional_data.unwrap_or_default();
 
-        if decoded_data.len() < NONCE_SIZE {
-            return Err(Error::DecryptionFailed("Ciphertext is too short to contain a nonce".to_string()));
+        Self::decrypt_standard(key, &decoded_data, aad)
+            .or_else(|e| Self::decrypt_truncated(key, &decoded_data, aad).unwrap_or(Err(e)))
+    }
+
+    /// 只接受 `config.tag_length` 对应格式的密文：16 字节时只按标准格式解密，
+    /// 更短时只接受首字节等于该长度的截断格式，其他格式返回 `Error::DecryptionFailed`
+    fn decrypt_with_config(
+        key: &Self::Key,
+        ciphertext_b64: &str,
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let decoded = from_base64(ciphertext_b64)
+            .map_err(|e| Error::DecryptionFailed(format!("Base64 decoding failed: {}", e)))?;
+        let mut plaintext = Vec::new();
+        Self::decrypt_from_bytes(key, &decoded, additional_data, config, &mut plaintext)?;
+        Ok(plaintext)
+    }
+
+    /// 16 字节标签时直接在 `out` 中拼接 `Nonce || 密文 || 标签` 并原地加密，不分配额外的堆内存
+    fn encrypt_to_buffer(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Self::Error> {
+        if config.tag_length != TAG_SIZE {
+            let ciphertext = Self::encrypt_with_config(key, plaintext, additional_data, config)?;
+            out.extend_from_slice(ciphertext.as_ref());
+            return Ok(());
         }
 
-        let (nonce_bytes, ciphertext) = decoded_data.split_at(NONCE_SIZE);
-        let nonce = Nonce::from_slice(nonce_bytes);
+        let cipher = Aes256Gcm::new(key.cipher_key()?);
+        let nonce = Self::nonce_for(config.nonce_strategy, key, plaintext, additional_data)?;
+        let start = out.len();
+        out.reserve(NONCE_SIZE + plaintext.len() + TAG_SIZE);
+        out.extend_from_slice(nonce.as_ref());
+        out.extend_from_slice(plaintext);
+        let tag = cipher
+            .encrypt_in_place_detached(
+                GcmNonce::from_slice(nonce.as_ref()),
+                additional_data.unwrap_or_default(),
+                &mut out[start + NONCE_SIZE..],
+            )
+            .map_err(|e| {
+                out[start..].zeroize();
+                out.truncate(start);
+                Error::EncryptionFailed(e.to_string())
+            })?;
+        out.extend_from_slice(&tag);
+        Ok(())
+    }
 
+    /// 与 [`decrypt_with_config`](SymmetricCryptographicSystem::decrypt_with_config) 一样只接受 `config.tag_length` 对应的格式；
+    /// 标准格式直接在 `out` 中原地解密
+    fn decrypt_from_bytes(
+        key: &Self::Key,
+        ciphertext: &[u8],
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Self::Error> {
+        check_tag_length(config.tag_length)?;
         let aad = additional_data.unwrap_or_default();
+        if config.tag_length != TAG_SIZE {
+            if ciphertext.first() != Some(&(config.tag_length as u8)) {
+                return Err(tag_length_mismatch(config.tag_length));
+            }
+            let plaintext = zeroize::Zeroizing::new(Self::decrypt_truncated(key, ciphertext, aad).unwrap_or_else(|| {
+                Err(Error::DecryptionFailed("Ciphertext is too short to contain a nonce and tag".to_string()))
+            })?);
+            out.extend_from_slice(&plaintext);
+            return Ok(());
+        }
+        if ciphertext.len() < NONCE_SIZE + TAG_SIZE {
+            return Err(Error::DecryptionFailed("Ciphertext is too short to contain a nonce and tag".to_string()));
+        }
 
-        cipher.decrypt(nonce, Payload { msg: ciphertext, aad })
-            .map_err(|e| Error::DecryptionFailed(e.to_string()))
+        let (nonce, rest) = ciphertext.split_at(NONCE_SIZE);
+        let (body, tag) = rest.split_at(rest.len() - TAG_SIZE);
+        let start = out.len();
+        out.extend_from_slice(body);
+        // 认证先于解密完成，失败时缓冲区中仍是密文，截断即可
+        open_in_place::<U16>(key, nonce, aad, &mut out[start..], tag).inspect_err(|_| out.truncate(start))
     }
 
     /// 将密钥导出为 Base64 字符串
     fn export_key(key: &Self::Key) -> Result<String, Self::Error> {
+        if key.is_zeroized() {
+            return Err(Error::KeyZeroized);
+        }
         Ok(general_purpose::STANDARD.encode(&key.0))
     }
 
@@ -108,12 +650,21 @@ impl SymmetricCryptographicSystem for AesGcmSystem {
 
         Ok(AesGcmKey(key_bytes))
     }
+
+    /// `config.reject_weak_keys` 开启时拒绝全零或熵过低的密钥，返回 `Error::InsecureParameter`
+    fn import_key_with_config(key_data: &str, config: &CryptoConfig) -> Result<Self::Key, Self::Error> {
+        let key = Self::import_key(key_data)?;
+        if config.reject_weak_keys {
+            check_weak_key(&key.0)?;
+        }
+        Ok(key)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::utils::CryptoConfig;
+    use crate::common::utils::{key_fingerprint, CryptoConfig};
 
     #[test]
     fn test_generate_key() {
@@ -228,6 +779,181 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_owned_and_borrowed_aad_are_equivalent() {
+        let config = CryptoConfig::default();
+        let key = AesGcmSystem::generate_key(&config).unwrap();
+        let plaintext = b"owned vs borrowed aad";
+        let owned_aad: Vec<u8> = format!("request-{}", 42).into_bytes();
+
+        // 借用AAD加密，所有权AAD解密；反之亦然
+        let borrowed_ct = AesGcmSystem::encrypt(&key, plaintext, Some(&owned_aad)).unwrap();
+        let owned_ct = AesGcmSystem::encrypt_owned_aad(&key, plaintext, Some(owned_aad.clone())).unwrap();
+
+        let from_borrowed = AesGcmSystem::decrypt_owned_aad(&key, &borrowed_ct.to_string(), Some(owned_aad.clone())).unwrap();
+        let from_owned = AesGcmSystem::decrypt(&key, &owned_ct.to_string(), Some(&owned_aad)).unwrap();
+        assert_eq!(from_borrowed, plaintext);
+        assert_eq!(from_owned, from_borrowed);
+
+        assert!(AesGcmSystem::decrypt_owned_aad(&key, &owned_ct.to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_authenticate_verify() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let header = b"content-type: application/octet-stream";
+
+        let tag = AesGcmSystem::authenticate(&key, header).unwrap();
+        assert_eq!(tag.as_bytes().len(), NONCE_SIZE + TAG_SIZE);
+        assert!(AesGcmSystem::verify_authentication(&key, header, &tag.to_string()).unwrap());
+        assert!(AesGcmSystem::verify_authentication(&key, b"", &AesGcmSystem::authenticate(&key, b"").unwrap().to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_authenticate_rejects_tampered_data_and_wrong_key() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let other_key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let tag = AesGcmSystem::authenticate(&key, b"header").unwrap().to_string();
+
+        assert!(!AesGcmSystem::verify_authentication(&key, b"headeR", &tag).unwrap());
+        assert!(!AesGcmSystem::verify_authentication(&other_key, b"header", &tag).unwrap());
+
+        // 带明文的普通密文不能当作认证标签
+        let ciphertext = AesGcmSystem::encrypt(&key, b"x", Some(b"header")).unwrap().to_string();
+        assert!(matches!(AesGcmSystem::verify_authentication(&key, b"header", &ciphertext), Err(Error::Format(_))));
+    }
+
+    #[test]
+    fn test_encrypt_with_nonce() {
+        let config = CryptoConfig::default();
+        let key = AesGcmSystem::generate_key(&config).unwrap();
+        let nonce = AesGcmNonce::from_slice(&[9u8; NONCE_SIZE]).unwrap();
+
+        let first = AesGcmSystem::encrypt_with_nonce(&key, &nonce, b"fixed nonce", None).unwrap();
+        let second = AesGcmSystem::encrypt_with_nonce(&key, &nonce, b"fixed nonce", None).unwrap();
+        // 相同的密钥与 Nonce 产生相同的密文，且 Nonce 被前置在密文中
+        assert_eq!(first.0, second.0);
+        assert_eq!(&first.0[..NONCE_SIZE], nonce.as_bytes());
+
+        let decrypted = AesGcmSystem::decrypt(&key, &first.to_string(), None).unwrap();
+        assert_eq!(decrypted, b"fixed nonce");
+    }
+
+    #[test]
+    fn test_decrypt_diagnosed_reports_possible_causes() {
+        use DecryptFailureCause::*;
+
+        let config = CryptoConfig::default();
+        let key = AesGcmSystem::generate_key(&config).unwrap();
+        let other_key = AesGcmSystem::generate_key(&config).unwrap();
+        let ciphertext = AesGcmSystem::encrypt(&key, b"diagnose me", Some(b"aad")).unwrap().to_string();
+        let diagnose = |key: &AesGcmKey, ciphertext: &str, aad: Option<&[u8]>| {
+            let error = AesGcmSystem::decrypt_diagnosed(key, ciphertext, aad).unwrap_err();
+            error.diagnostics().cloned().unwrap()
+        };
+
+        assert_eq!(AesGcmSystem::decrypt_diagnosed(&key, &ciphertext, Some(b"aad")).unwrap(), b"diagnose me");
+
+        // 格式无效
+        for corrupt in ["not base64!!", "AAAA"] {
+            let diagnostics = diagnose(&key, corrupt, Some(b"aad"));
+            assert!(!diagnostics.format_valid);
+            assert_eq!(diagnostics.cause_codes(), ["corrupt_format"]);
+        }
+
+        // 错误的密钥
+        let diagnostics = diagnose(&other_key, &ciphertext, Some(b"aad"));
+        assert!(diagnostics.format_valid);
+        assert_eq!(diagnostics.possible_causes, [WrongKey, Tampered, WrongAad]);
+
+        // 被篡改
+        let mut bytes = from_base64(&ciphertext).unwrap();
+        bytes[NONCE_SIZE] ^= 1;
+        let diagnostics = diagnose(&key, &Base64String::from(bytes).to_string(), Some(b"aad"));
+        assert!(diagnostics.possible_causes.contains(&Tampered));
+
+        // 附加认证数据不一致
+        assert!(diagnose(&key, &ciphertext, Some(b"other")).possible_causes.contains(&WrongAad));
+        assert!(diagnose(&key, &ciphertext, None).possible_causes.contains(&WrongAad));
+        // 密文未绑定附加认证数据时可以确定原因
+        let unbound = AesGcmSystem::encrypt(&key, b"no aad", None).unwrap().to_string();
+        let error = AesGcmSystem::decrypt_diagnosed(&key, &unbound, Some(b"aad")).unwrap_err();
+        assert_eq!(error.diagnostics().unwrap().cause_codes(), ["wrong_aad"]);
+        assert!(error.to_string().contains("wrong_aad"));
+    }
+
+    #[test]
+    fn test_synthetic_nonce_is_deterministic_per_plaintext() {
+        let config = CryptoConfig { nonce_strategy: NonceStrategy::SyntheticFromPlaintext, ..Default::default() };
+        let key = AesGcmSystem::generate_key(&config).unwrap();
+
+        // 相同明文得到相同密文，且可按常规方式解密
+        let first = AesGcmSystem::encrypt_with_config(&key, b"dedup me", Some(b"ctx"), &config).unwrap();
+        let second = AesGcmSystem::encrypt_with_config(&key, b"dedup me", Some(b"ctx"), &config).unwrap();
+        assert_eq!(first.0, second.0);
+        assert_eq!(&first.0[..NONCE_SIZE], AesGcmSystem::synthetic_nonce(&key, b"dedup me", Some(b"ctx")).as_bytes());
+        assert_eq!(AesGcmSystem::decrypt(&key, &first.to_string(), Some(b"ctx")).unwrap(), b"dedup me");
+
+        let mut buffer = Vec::new();
+        AesGcmSystem::encrypt_to_buffer(&key, b"dedup me", Some(b"ctx"), &config, &mut buffer).unwrap();
+        assert_eq!(buffer, first.0);
+        assert_eq!(AesGcmSystem::encrypt_synthetic(&key, b"dedup me", Some(b"ctx")).unwrap().0, first.0);
+
+        // 明文、附加认证数据或密钥不同时 Nonce 不同
+        let nonce = AesGcmSystem::synthetic_nonce(&key, b"dedup me", Some(b"ctx"));
+        assert_ne!(nonce, AesGcmSystem::synthetic_nonce(&key, b"dedup mf", Some(b"ctx")));
+        assert_ne!(nonce, AesGcmSystem::synthetic_nonce(&key, b"dedup me", Some(b"other")));
+        assert_ne!(nonce, AesGcmSystem::synthetic_nonce(&key, b"dedup me", None));
+        let other_key = AesGcmSystem::generate_key(&config).unwrap();
+        assert_ne!(nonce, AesGcmSystem::synthetic_nonce(&other_key, b"dedup me", Some(b"ctx")));
+
+        // 截断标签同样适用
+        let truncated = CryptoConfig { tag_length: 12, ..config.clone() };
+        let a = AesGcmSystem::encrypt_with_config(&key, b"dedup me", None, &truncated).unwrap();
+        let b = AesGcmSystem::encrypt_with_config(&key, b"dedup me", None, &truncated).unwrap();
+        assert_eq!(a.0, b.0);
+        assert_eq!(AesGcmSystem::decrypt(&key, &a.to_string(), None).unwrap(), b"dedup me");
+
+        // 默认的随机策略不受影响
+        let random = CryptoConfig::default();
+        let x = AesGcmSystem::encrypt_with_config(&key, b"dedup me", None, &random).unwrap();
+        let y = AesGcmSystem::encrypt_with_config(&key, b"dedup me", None, &random).unwrap();
+        assert_ne!(x.0, y.0);
+    }
+
+    /// 始终失败的随机数生成器，用于模拟熵源不可用
+    struct FailingRng;
+
+    impl TryRngCore for FailingRng {
+        type Error = std::io::Error;
+
+        fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+            Err(std::io::Error::other("entropy unavailable"))
+        }
+
+        fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+            Err(std::io::Error::other("entropy unavailable"))
+        }
+
+        fn try_fill_bytes(&mut self, _dst: &mut [u8]) -> Result<(), Self::Error> {
+            Err(std::io::Error::other("entropy unavailable"))
+        }
+    }
+
+    #[test]
+    fn test_rng_failure_on_generate_key() {
+        let result = AesGcmSystem::generate_key_with_rng(&mut FailingRng);
+        assert!(matches!(result, Err(Error::RngFailure(_))));
+    }
+
+    #[test]
+    fn test_rng_failure_on_encrypt() {
+        let config = CryptoConfig::default();
+        let key = AesGcmSystem::generate_key(&config).unwrap();
+        let result = AesGcmSystem::encrypt_with_rng(&mut FailingRng, &key, b"data", None);
+        assert!(matches!(result, Err(Error::RngFailure(_))));
+    }
+
     #[test]
     fn test_decrypt_invalid_ciphertext() {
         let config = CryptoConfig::default();
@@ -242,4 +968,283 @@ mod tests {
         let result = AesGcmSystem::decrypt(&key, &short_ciphertext, None);
         assert!(result.is_err());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_decrypt_malformed_input_never_panics() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let valid = from_base64(&AesGcmSystem::encrypt(&key, b"fuzz target", Some(b"aad")).unwrap().to_string()).unwrap();
+
+        // 随机字节（按长度覆盖 Nonce/标签边界）
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        for len in 0..=valid.len() + 8 {
+            let random: Vec<u8> = (0..len).map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            }).collect();
+            assert!(AesGcmSystem::decrypt(&key, &general_purpose::STANDARD.encode(&random), None).is_err());
+        }
+
+        // 合法密文的每个前缀与逐字节篡改
+        for len in 0..valid.len() {
+            assert!(AesGcmSystem::decrypt(&key, &general_purpose::STANDARD.encode(&valid[..len]), Some(b"aad")).is_err());
+        }
+        for i in 0..valid.len() {
+            let mut tampered = valid.clone();
+            tampered[i] ^= 0x80;
+            assert!(AesGcmSystem::decrypt(&key, &general_purpose::STANDARD.encode(&tampered), Some(b"aad")).is_err());
+        }
+
+        // 非 Base64 文本
+        for text in ["", "=", "====", "\u{0}", "😀😀😀😀", "A", "AB=C", "kid:abc.def"] {
+            assert!(AesGcmSystem::decrypt(&key, text, None).is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_truncated_tag_roundtrip() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let plaintext = b"iot sensor reading";
+
+        for tag_length in [12, 13, 15, 16] {
+            let ciphertext = AesGcmSystem::encrypt_with_tag_length(&key, plaintext, Some(b"aad"), tag_length).unwrap();
+            let decoded = from_base64(&ciphertext.to_string()).unwrap();
+            let header = if tag_length == TAG_SIZE { 0 } else { 1 };
+            assert_eq!(decoded.len(), header + NONCE_SIZE + plaintext.len() + tag_length);
+            assert_eq!(AesGcmSystem::decrypt(&key, &ciphertext.to_string(), Some(b"aad")).unwrap(), plaintext);
+            assert!(AesGcmSystem::decrypt(&key, &ciphertext.to_string(), Some(b"other")).is_err());
+
+            // 篡改记录的标签长度或截断标签都会导致解密失败
+            for i in [0, decoded.len() - 1] {
+                let mut tampered = decoded.clone();
+                tampered[i] ^= 0x01;
+                assert!(AesGcmSystem::decrypt(&key, &general_purpose::STANDARD.encode(&tampered), Some(b"aad")).is_err());
+            }
+        }
+
+        // 16 字节标签与默认格式一致
+        let full = AesGcmSystem::encrypt_with_tag_length(&key, plaintext, None, 16).unwrap();
+        let config = CryptoConfig { tag_length: 16, ..Default::default() };
+        let via_config = AesGcmSystem::encrypt_with_config(&key, plaintext, None, &config).unwrap();
+        assert_eq!(from_base64(&full.to_string()).unwrap().len(), from_base64(&via_config.to_string()).unwrap().len());
+    }
+
+    #[test]
+    fn test_tag_length_below_floor_rejected() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let config = CryptoConfig { tag_length: 8, ..Default::default() };
+        assert!(matches!(
+            AesGcmSystem::encrypt_with_config(&key, b"data", None, &config),
+            Err(Error::InsecureParameter(findings)) if findings[0].contains("tag_length = 8")
+        ));
+        assert!(matches!(AesGcmSystem::encrypt_with_tag_length(&key, b"data", None, 11), Err(Error::InsecureParameter(_))));
+        assert!(matches!(AesGcmSystem::encrypt_with_tag_length(&key, b"data", None, 17), Err(Error::Operation(_))));
+    }
+
+    #[test]
+    fn test_decrypt_into_matches_allocating_api() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        for len in [0usize, 1, 2, 3, 17, 1000] {
+            let plaintext: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+            let ciphertext = AesGcmSystem::encrypt(&key, &plaintext, Some(b"aad")).unwrap().to_string();
+            let expected = AesGcmSystem::decrypt(&key, &ciphertext, Some(b"aad")).unwrap();
+
+            let mut exact = vec![0u8; len];
+            assert_eq!(AesGcmSystem::decrypt_into(&key, &ciphertext, &mut exact, Some(b"aad")).unwrap(), len);
+            assert_eq!(exact, expected);
+
+            let mut larger = vec![0xeeu8; len + 8];
+            assert_eq!(AesGcmSystem::decrypt_into(&key, &ciphertext, &mut larger, Some(b"aad")).unwrap(), len);
+            assert_eq!(&larger[..len], expected.as_slice());
+
+            // URL 安全且无填充的编码同样可以解密
+            let url_safe = crate::common::utils::Base64Variant::UrlSafeNoPad.encode(&from_base64(&ciphertext).unwrap());
+            assert_eq!(AesGcmSystem::decrypt_into(&key, &url_safe, &mut exact, Some(b"aad")).unwrap(), len);
+            assert_eq!(exact, expected);
+
+            assert!(AesGcmSystem::decrypt_into(&key, &ciphertext, &mut larger, Some(b"other")).is_err());
+        }
+
+        for tag_length in [12, 15] {
+            let ciphertext = AesGcmSystem::encrypt_with_tag_length(&key, b"truncated", None, tag_length).unwrap().to_string();
+            let mut out = [0u8; 9];
+            assert_eq!(AesGcmSystem::decrypt_into(&key, &ciphertext, &mut out, None).unwrap(), 9);
+            assert_eq!(&out, b"truncated");
+        }
+    }
+
+    #[test]
+    fn test_decrypt_into_accepts_truncated_ciphertext_shorter_than_standard_minimum() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        for len in 0..3usize {
+            let plaintext = vec![0x5au8; len];
+            let ciphertext = AesGcmSystem::encrypt_with_tag_length(&key, &plaintext, None, 12).unwrap().to_string();
+            assert!(from_base64(&ciphertext).unwrap().len() < NONCE_SIZE + TAG_SIZE);
+
+            let mut out = [0u8; 8];
+            assert_eq!(AesGcmSystem::decrypt_into(&key, &ciphertext, &mut out, None).unwrap(), len);
+            assert_eq!(&out[..len], plaintext.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_decrypt_into_rejects_small_buffer_and_bad_input() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let ciphertext = AesGcmSystem::encrypt(&key, b"sixteen byte msg", None).unwrap().to_string();
+
+        let mut small = [0u8; 15];
+        assert!(matches!(
+            AesGcmSystem::decrypt_into(&key, &ciphertext, &mut small, None),
+            Err(Error::BufferTooSmall { required: 16, provided: 15 })
+        ));
+        assert!(matches!(
+            AesGcmSystem::decrypt_into(&key, &ciphertext, &mut [], None),
+            Err(Error::BufferTooSmall { required: 16, provided: 0 })
+        ));
+
+        let mut out = [0u8; 64];
+        for text in ["", "A", "AAAAA", "not base64!", &ciphertext[..ciphertext.len() - 4], &format!(" {}", ciphertext)] {
+            assert!(AesGcmSystem::decrypt_into(&key, text, &mut out, None).is_err(), "{:?}", text);
+        }
+        let other_key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        assert!(matches!(
+            AesGcmSystem::decrypt_into(&other_key, &ciphertext, &mut out, None),
+            Err(Error::DecryptionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_multi_aad_segments_authenticated() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let ciphertext = AesGcmSystem::encrypt_multi_aad(&key, b"payload", &[&b"ab"[..], b"c"]).unwrap().to_string();
+        assert_eq!(AesGcmSystem::decrypt_multi_aad(&key, &ciphertext, &[&b"ab"[..], b"c"]).unwrap(), b"payload");
+        // 字段边界不同但拼接结果相同的 AAD 不能通过认证
+        assert!(AesGcmSystem::decrypt_multi_aad(&key, &ciphertext, &[&b"a"[..], b"bc"]).is_err());
+        assert!(AesGcmSystem::decrypt(&key, &ciphertext, Some(b"abc")).is_err());
+    }
+
+    #[test]
+    fn test_buffer_apis_match_string_apis() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        for tag_length in [16, 12] {
+            let config = CryptoConfig { tag_length, ..Default::default() };
+            let mut buffer = b"prefix".to_vec();
+            AesGcmSystem::encrypt_to_buffer(&key, b"pooled", Some(b"aad"), &config, &mut buffer).unwrap();
+            assert_eq!(&buffer[..6], b"prefix");
+            let ciphertext = &buffer[6..];
+            assert_eq!(ciphertext.len(), usize::from(tag_length != TAG_SIZE) + NONCE_SIZE + 6 + tag_length);
+            assert_eq!(AesGcmSystem::decrypt(&key, &crate::common::utils::to_base64(ciphertext), Some(b"aad")).unwrap(), b"pooled");
+
+            let mut plaintext = b"xy".to_vec();
+            AesGcmSystem::decrypt_from_bytes(&key, ciphertext, Some(b"aad"), &config, &mut plaintext).unwrap();
+            assert_eq!(plaintext, b"xypooled");
+
+            // 认证失败时输出缓冲区保持原长度
+            let mut plaintext = b"xy".to_vec();
+            assert!(AesGcmSystem::decrypt_from_bytes(&key, ciphertext, Some(b"other"), &config, &mut plaintext).is_err());
+            assert_eq!(plaintext, b"xy");
+        }
+        let mut plaintext = Vec::new();
+        assert!(AesGcmSystem::decrypt_from_bytes(&key, &[0u8; 27], None, &CryptoConfig::default(), &mut plaintext).is_err());
+
+        // 短于标准格式下限的截断标签密文
+        let config = CryptoConfig { tag_length: 12, ..Default::default() };
+        let mut ciphertext = Vec::new();
+        AesGcmSystem::encrypt_to_buffer(&key, b"ab", None, &config, &mut ciphertext).unwrap();
+        assert!(ciphertext.len() < NONCE_SIZE + TAG_SIZE);
+        AesGcmSystem::decrypt_from_bytes(&key, &ciphertext, None, &config, &mut plaintext).unwrap();
+        assert_eq!(plaintext, b"ab");
+    }
+
+    #[test]
+    fn test_reject_weak_keys_on_import() {
+        let all_zero = general_purpose::STANDARD.encode([0u8; KEY_SIZE]);
+        let strict = CryptoConfig { reject_weak_keys: true, ..Default::default() };
+
+        assert!(matches!(
+            AesGcmSystem::import_key_with_config(&all_zero, &strict),
+            Err(Error::InsecureParameter(_))
+        ));
+        let low_entropy = general_purpose::STANDARD.encode([1u8, 2].repeat(KEY_SIZE / 2));
+        assert!(matches!(
+            AesGcmSystem::import_key_with_config(&low_entropy, &strict),
+            Err(Error::InsecureParameter(_))
+        ));
+
+        // 默认关闭检查，全零密钥照常导入
+        assert!(AesGcmSystem::import_key_with_config(&all_zero, &CryptoConfig::default()).is_ok());
+        assert!(AesGcmSystem::import_key(&all_zero).is_ok());
+
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let exported = AesGcmSystem::export_key(&key).unwrap();
+        assert!(AesGcmSystem::import_key_with_config(&exported, &strict).is_ok());
+    }
+
+    #[test]
+    fn test_generate_vanity_key() {
+        let config = CryptoConfig::default();
+        let key = AesGcmSystem::generate_vanity_key(&config, "A", 2048).unwrap();
+        assert!(key_fingerprint(&AesGcmSystem::export_key(&key).unwrap()).starts_with('a'));
+
+        // 12 位前缀在 8 次尝试内几乎不可能找到
+        assert!(matches!(AesGcmSystem::generate_vanity_key(&config, "0123456789ab", 8), Err(Error::Operation(_))));
+        assert!(matches!(AesGcmSystem::generate_vanity_key(&config, "xyz", 8), Err(Error::Operation(_))));
+        assert!(matches!(AesGcmSystem::generate_vanity_key(&config, &"0".repeat(17), 8), Err(Error::Operation(_))));
+    }
+
+    #[test]
+    fn test_zeroized_key_is_rejected() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let ciphertext = AesGcmSystem::encrypt(&key, b"session", None).unwrap().to_string();
+
+        let mut wiped = key.clone();
+        assert!(!wiped.is_zeroized());
+        wiped.zeroize();
+        assert!(wiped.is_zeroized());
+        assert!(matches!(AesGcmSystem::encrypt(&wiped, b"session", None), Err(Error::KeyZeroized)));
+        assert!(matches!(AesGcmSystem::decrypt(&wiped, &ciphertext, None), Err(Error::KeyZeroized)));
+        assert!(matches!(AesGcmSystem::encrypt_with_tag_length(&wiped, b"session", None, 12), Err(Error::KeyZeroized)));
+        assert!(matches!(AesGcmSystem::export_key(&wiped), Err(Error::KeyZeroized)));
+        let mut out = b"prefix".to_vec();
+        let result = AesGcmSystem::encrypt_to_buffer(&wiped, b"session", None, &CryptoConfig::default(), &mut out);
+        assert!(matches!(result, Err(Error::KeyZeroized)));
+        assert_eq!(out, b"prefix");
+
+        // 清零只影响这一份，原密钥仍可用，随后显式丢弃
+        assert_eq!(AesGcmSystem::decrypt(&key, &ciphertext, None).unwrap(), b"session");
+        key.zeroize_now();
+    }
+
+    #[test]
+    fn test_truncated_tag_length_is_authenticated() {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default()).unwrap();
+        let plaintext = b"downgrade";
+
+        // 把完整标签截短并加上长度首字节，不能冒充 12 字节标签的密文
+        let full = from_base64(&AesGcmSystem::encrypt(&key, plaintext, None).unwrap().to_string()).unwrap();
+        let mut forged = vec![12u8];
+        forged.extend_from_slice(&full[..full.len() - 4]);
+        let forged = general_purpose::STANDARD.encode(&forged);
+        assert!(AesGcmSystem::decrypt(&key, &forged, None).is_err());
+        let mut out = [0u8; 32];
+        assert!(AesGcmSystem::decrypt_into(&key, &forged, &mut out, None).is_err());
+
+        // 改写首字节并截掉多余的标签字节同样无效
+        let long = from_base64(&AesGcmSystem::encrypt_with_tag_length(&key, plaintext, None, 15).unwrap().to_string()).unwrap();
+        let mut relabeled = long[..long.len() - 3].to_vec();
+        relabeled[0] = 12;
+        assert!(AesGcmSystem::decrypt(&key, &general_purpose::STANDARD.encode(&relabeled), None).is_err());
+
+        // 按配置解密只接受配置的标签长度
+        let truncated = AesGcmSystem::encrypt_with_tag_length(&key, plaintext, None, 12).unwrap().to_string();
+        let standard = general_purpose::STANDARD.encode(&full);
+        let tag_12 = CryptoConfig { tag_length: 12, ..Default::default() };
+        let tag_13 = CryptoConfig { tag_length: 13, ..Default::default() };
+        assert_eq!(AesGcmSystem::decrypt_with_config(&key, &truncated, None, &tag_12).unwrap(), plaintext);
+        assert!(AesGcmSystem::decrypt_with_config(&key, &truncated, None, &tag_13).is_err());
+        assert!(AesGcmSystem::decrypt_with_config(&key, &truncated, None, &CryptoConfig::default()).is_err());
+        assert!(AesGcmSystem::decrypt_with_config(&key, &standard, None, &tag_12).is_err());
+        assert_eq!(AesGcmSystem::decrypt_with_config(&key, &standard, None, &CryptoConfig::default()).unwrap(), plaintext);
+    }
+}
@@ -0,0 +1,189 @@
+//! AES-256-XTS 扇区加密实现
+//!
+//! XTS 是长度保持的分组密码工作模式（IEEE P1619），适用于固定大小的磁盘扇区：
+//! 密文与明文等长，每个扇区的 tweak 由扇区索引派生，相同明文在不同扇区得到不同密文。
+//!
+//! **警告：XTS 不提供认证。** 攻击者可以篡改、回滚或在扇区间交换密文而不会被检测到，
+//! 被篡改的扇区会解密为不可预测的数据而不是返回错误。需要完整性保护的场景请使用 AES-GCM，
+//! 或在更高层额外维护扇区的 MAC。
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::KeyInit;
+use aes::Aes256;
+use rand_core::TryRngCore;
+use alloc::format;
+use alloc::string::ToString;
+use core::fmt::Debug;
+use xts_mode::{get_tweak_default, Xts128};
+use zeroize::Zeroizing;
+use crate::common::errors::Error;
+use crate::common::rng::SystemRng;
+
+/// AES-256-XTS 密钥长度：两个独立的 AES-256 密钥（数据密钥与 tweak 密钥）
+pub const KEY_SIZE: usize = 64;
+/// XTS 处理的最小扇区大小（一个 AES 分组）
+const MIN_SECTOR_SIZE: usize = 16;
+
+/// AES-256-XTS 密钥，离开作用域时自动擦除
+#[derive(Clone)]
+pub struct AesXtsKey(Zeroizing<[u8; KEY_SIZE]>);
+
+impl Debug for AesXtsKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AesXtsKey").finish_non_exhaustive()
+    }
+}
+
+impl AesXtsKey {
+    /// 使用系统RNG生成新密钥
+    pub fn generate() -> Result<Self, Error> {
+        let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+        SystemRng.try_fill_bytes(key.as_mut())
+            .map_err(|e| Error::RngFailure(e.to_string()))?;
+        Self::from_bytes(key.as_ref())
+    }
+
+    /// 从64字节原始密钥导入
+    ///
+    /// 按 IEEE P1619 要求，两半密钥不能相同。
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let key: [u8; KEY_SIZE] = bytes.try_into()
+            .map_err(|_| Error::KeyImportFailed(format!("AES-XTS 密钥必须为{}字节，实际为{}字节", KEY_SIZE, bytes.len())))?;
+        let key = Zeroizing::new(key);
+        if key[..KEY_SIZE / 2] == key[KEY_SIZE / 2..] {
+            return Err(Error::KeyImportFailed("AES-XTS 的数据密钥与tweak密钥不能相同".to_string()));
+        }
+        Ok(Self(key))
+    }
+
+    /// 原始密钥字节
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+/// AES-256-XTS 扇区加密系统，绑定固定的扇区大小
+pub struct AesXtsSystem {
+    cipher: Xts128<Aes256>,
+    sector_size: usize,
+}
+
+impl AesXtsSystem {
+    /// 使用密钥与扇区大小（如512或4096字节，至少16字节）创建实例
+    pub fn new(key: &AesXtsKey, sector_size: usize) -> Result<Self, Error> {
+        if sector_size < MIN_SECTOR_SIZE {
+            return Err(Error::Operation(format!("扇区大小至少为{}字节，实际为{}字节", MIN_SECTOR_SIZE, sector_size)));
+        }
+        let (data_key, tweak_key) = key.as_bytes().split_at(KEY_SIZE / 2);
+        let cipher = Xts128::new(
+            Aes256::new(GenericArray::from_slice(data_key)),
+            Aes256::new(GenericArray::from_slice(tweak_key)),
+        );
+        Ok(Self { cipher, sector_size })
+    }
+
+    /// 扇区大小（字节）
+    pub fn sector_size(&self) -> usize {
+        self.sector_size
+    }
+
+    /// 原地加密单个扇区，`sector` 长度必须等于扇区大小
+    pub fn encrypt_sector(&self, sector_index: u128, sector: &mut [u8]) -> Result<(), Error> {
+        self.check_len(sector.len(), 1)?;
+        self.cipher.encrypt_sector(sector, get_tweak_default(sector_index));
+        Ok(())
+    }
+
+    /// 原地解密单个扇区，`sector` 长度必须等于扇区大小
+    ///
+    /// XTS 无认证，错误的密钥或被篡改的密文同样会“成功”解密为随机数据。
+    pub fn decrypt_sector(&self, sector_index: u128, sector: &mut [u8]) -> Result<(), Error> {
+        self.check_len(sector.len(), 1)?;
+        self.cipher.decrypt_sector(sector, get_tweak_default(sector_index));
+        Ok(())
+    }
+
+    /// 原地加密从 `first_sector_index` 开始的连续扇区，`data` 长度必须为扇区大小的整数倍
+    pub fn encrypt_sectors(&self, first_sector_index: u128, data: &mut [u8]) -> Result<(), Error> {
+        self.check_len(data.len(), data.len() / self.sector_size)?;
+        self.cipher.encrypt_area(data, self.sector_size, first_sector_index, get_tweak_default);
+        Ok(())
+    }
+
+    /// 原地解密从 `first_sector_index` 开始的连续扇区，`data` 长度必须为扇区大小的整数倍
+    pub fn decrypt_sectors(&self, first_sector_index: u128, data: &mut [u8]) -> Result<(), Error> {
+        self.check_len(data.len(), data.len() / self.sector_size)?;
+        self.cipher.decrypt_area(data, self.sector_size, first_sector_index, get_tweak_default);
+        Ok(())
+    }
+
+    fn check_len(&self, len: usize, sectors: usize) -> Result<(), Error> {
+        if sectors == 0 || len != sectors * self.sector_size {
+            return Err(Error::Format(format!(
+                "数据长度{}字节不是扇区大小{}字节的正整数倍",
+                len, self.sector_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 253) as u8).collect()
+    }
+
+    #[test]
+    fn test_xts_sector_roundtrip_preserves_length() {
+        let key = AesXtsKey::generate().unwrap();
+        for sector_size in [512, 4096] {
+            let xts = AesXtsSystem::new(&key, sector_size).unwrap();
+            let plaintext = sample_data(sector_size);
+            let mut sector = plaintext.clone();
+
+            xts.encrypt_sector(7, &mut sector).unwrap();
+            assert_eq!(sector.len(), sector_size);
+            assert_ne!(sector, plaintext);
+
+            xts.decrypt_sector(7, &mut sector).unwrap();
+            assert_eq!(sector, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_xts_tweak_depends_on_sector_index() {
+        let key = AesXtsKey::generate().unwrap();
+        let xts = AesXtsSystem::new(&key, 512).unwrap();
+        let plaintext = vec![0u8; 512];
+
+        let mut sector_a = plaintext.clone();
+        let mut sector_b = plaintext.clone();
+        xts.encrypt_sector(0, &mut sector_a).unwrap();
+        xts.encrypt_sector(1, &mut sector_b).unwrap();
+        assert_ne!(sector_a, sector_b);
+
+        // 多扇区区域加密与逐扇区加密结果一致
+        let mut area = [plaintext.clone(), plaintext].concat();
+        xts.encrypt_sectors(0, &mut area).unwrap();
+        assert_eq!(&area[..512], sector_a.as_slice());
+        assert_eq!(&area[512..], sector_b.as_slice());
+        xts.decrypt_sectors(0, &mut area).unwrap();
+        assert!(area.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_xts_rejects_invalid_inputs() {
+        assert!(matches!(AesXtsKey::from_bytes(&[1u8; 32]), Err(Error::KeyImportFailed(_))));
+        assert!(matches!(AesXtsKey::from_bytes(&[1u8; 64]), Err(Error::KeyImportFailed(_))));
+
+        let key = AesXtsKey::generate().unwrap();
+        assert!(AesXtsSystem::new(&key, 8).is_err());
+        let xts = AesXtsSystem::new(&key, 512).unwrap();
+        assert!(matches!(xts.encrypt_sector(0, &mut [0u8; 511]), Err(Error::Format(_))));
+        assert!(matches!(xts.encrypt_sectors(0, &mut [0u8; 1000]), Err(Error::Format(_))));
+        assert!(matches!(xts.decrypt_sectors(0, &mut []), Err(Error::Format(_))));
+    }
+}
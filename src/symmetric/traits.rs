@@ -1,7 +1,12 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+#[cfg(feature = "std")]
 use std::io::{Read, Write};
-use std::fmt::Debug;
-use crate::common::utils::CryptoConfig;
+use crate::common::utils::{aad_segments, to_base64, CryptoConfig};
+#[cfg(feature = "std")]
 use crate::common::errors::Error;
+#[cfg(feature = "std")]
 use crate::common::streaming::{StreamingConfig, StreamingResult};
 
 #[cfg(feature = "async-engine")]
@@ -16,7 +21,7 @@ pub trait SymmetricCryptographicSystem: Sized {
     type CiphertextOutput: AsRef<[u8]> + From<Vec<u8>> + ToString + Send + Sync;
     
     /// 该系统的错误类型。
-    type Error: std::error::Error;
+    type Error: core::error::Error;
     
     /// 生成一个新的密钥。
     fn generate_key(config: &CryptoConfig) -> Result<Self::Key, Self::Error>;
@@ -28,6 +33,16 @@ pub trait SymmetricCryptographicSystem: Sized {
         additional_data: Option<&[u8]>
     ) -> Result<Self::CiphertextOutput, Self::Error>;
     
+    /// 按配置加密数据，供引擎使用；默认忽略配置，直接调用 [`encrypt`](Self::encrypt)。
+    fn encrypt_with_config(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        _config: &CryptoConfig,
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        Self::encrypt(key, plaintext, additional_data)
+    }
+
     /// 使用密钥解密数据。
     fn decrypt(
         key: &Self::Key,
@@ -35,14 +50,112 @@ pub trait SymmetricCryptographicSystem: Sized {
         additional_data: Option<&[u8]>
     ) -> Result<Vec<u8>, Self::Error>;
 
+    /// 按配置解密数据，供引擎使用；默认忽略配置，直接调用 [`decrypt`](Self::decrypt)。
+    /// 密文格式取决于配置的实现（如认证标签长度）应只接受与配置一致的密文。
+    fn decrypt_with_config(
+        key: &Self::Key,
+        ciphertext: &str,
+        additional_data: Option<&[u8]>,
+        _config: &CryptoConfig,
+    ) -> Result<Vec<u8>, Self::Error> {
+        Self::decrypt(key, ciphertext, additional_data)
+    }
+
+    /// 按配置加密，把未经 Base64 编码的密文追加到调用方提供的暂存缓冲区（如从
+    /// [`BufferPool`](crate::common::buffer_pool::BufferPool) 借出的缓冲区）；
+    /// 默认调用 [`encrypt_with_config`](Self::encrypt_with_config) 后复制结果。
+    fn encrypt_to_buffer(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Self::Error> {
+        let ciphertext = Self::encrypt_with_config(key, plaintext, additional_data, config)?;
+        out.extend_from_slice(ciphertext.as_ref());
+        Ok(())
+    }
+
+    /// 按配置解密已完成 Base64 解码的密文，把明文追加到 `out`；失败时 `out` 保持原长度。
+    /// 默认重新编码后调用 [`decrypt_with_config`](Self::decrypt_with_config)。
+    fn decrypt_from_bytes(
+        key: &Self::Key,
+        ciphertext: &[u8],
+        additional_data: Option<&[u8]>,
+        config: &CryptoConfig,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Self::Error> {
+        let plaintext = zeroize::Zeroizing::new(Self::decrypt_with_config(key, &to_base64(ciphertext), additional_data, config)?);
+        out.extend_from_slice(&plaintext);
+        Ok(())
+    }
+
+    /// 使用密钥加密数据，附加认证数据以所有权形式传入。
+    fn encrypt_owned_aad(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<Vec<u8>>
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        Self::encrypt(key, plaintext, additional_data.as_deref())
+    }
+
+    /// 使用密钥解密数据，附加认证数据以所有权形式传入。
+    fn decrypt_owned_aad(
+        key: &Self::Key,
+        ciphertext: &str,
+        additional_data: Option<Vec<u8>>
+    ) -> Result<Vec<u8>, Self::Error> {
+        Self::decrypt(key, ciphertext, additional_data.as_deref())
+    }
+
+    /// 以多个字段作为附加认证数据加密，字段经 [`aad_segments`](crate::common::utils::aad_segments) 规范拼接
+    fn encrypt_multi_aad(
+        key: &Self::Key,
+        plaintext: &[u8],
+        segments: &[&[u8]],
+    ) -> Result<Self::CiphertextOutput, Self::Error> {
+        Self::encrypt_owned_aad(key, plaintext, Some(aad_segments(segments)))
+    }
+
+    /// 解密 [`encrypt_multi_aad`](Self::encrypt_multi_aad) 生成的密文，字段及其顺序必须与加密时一致
+    fn decrypt_multi_aad(
+        key: &Self::Key,
+        ciphertext: &str,
+        segments: &[&[u8]],
+    ) -> Result<Vec<u8>, Self::Error> {
+        Self::decrypt_owned_aad(key, ciphertext, Some(aad_segments(segments)))
+    }
+
     /// 导出密钥为字符串
     fn export_key(key: &Self::Key) -> Result<String, Self::Error>;
 
     /// 从字符串导入密钥
     fn import_key(key_data: &str) -> Result<Self::Key, Self::Error>;
+
+    /// 按配置导入密钥；默认忽略配置，直接调用 [`import_key`](Self::import_key)。
+    /// 能访问原始密钥字节的实现应在 `config.reject_weak_keys` 开启时拒绝弱密钥。
+    fn import_key_with_config(key_data: &str, _config: &CryptoConfig) -> Result<Self::Key, Self::Error> {
+        Self::import_key(key_data)
+    }
+
+    /// 反复生成密钥，直到导出密钥的指纹（见 [`key_fingerprint`](crate::common::utils::key_fingerprint)）以十六进制 `prefix` 开头
+    ///
+    /// 期望尝试次数为 16 的前缀长度次方；`max_attempts` 次内未找到或前缀无效时返回 `Error::Operation`。
+    /// 已知前缀的人可据此把密钥空间缩小 `4 × 前缀长度` 位，前缀应保持很短。
+    fn generate_vanity_key(config: &CryptoConfig, prefix: &str, max_attempts: u64) -> Result<Self::Key, crate::common::errors::Error>
+    where
+        crate::common::errors::Error: From<Self::Error>,
+    {
+        crate::common::utils::search_vanity_key(prefix, max_attempts, || {
+            let key = Self::generate_key(config)?;
+            let fingerprint = crate::common::utils::key_fingerprint(&Self::export_key(&key)?);
+            Ok((key, fingerprint))
+        })
+    }
 }
 
 /// 同步对称流式加密系统扩展
+#[cfg(feature = "std")]
 pub trait SymmetricSyncStreamingSystem: SymmetricCryptographicSystem
 where
     Error: From<Self::Error>,
@@ -64,6 +177,21 @@ where
         config: &StreamingConfig,
         additional_data: Option<&[u8]>,
     ) -> Result<StreamingResult, Error>;
+
+    /// 加密并把序列化后的密文直接写入 `sink`，返回写入的字节数
+    ///
+    /// 写出的内容与 `encrypt(...).to_string()` 相同，但原始密文经 [`encrypt_to_buffer`](SymmetricCryptographicSystem::encrypt_to_buffer)
+    /// 生成后边编码边写出，不分配中间的 Base64 字符串。`sink` 写入失败时返回 `Error::Io`，此时可能已写出部分内容。
+    fn encrypt_to(
+        key: &Self::Key,
+        plaintext: &[u8],
+        additional_data: Option<&[u8]>,
+        sink: &mut dyn Write,
+    ) -> Result<usize, Error> {
+        let mut ciphertext = Vec::new();
+        Self::encrypt_to_buffer(key, plaintext, additional_data, &CryptoConfig::default(), &mut ciphertext)?;
+        Ok(crate::common::utils::write_base64(&ciphertext, sink)?)
+    }
 }
 
 /// 异步对称流式加密系统扩展
@@ -97,4 +225,36 @@ where
     where
         R: AsyncRead + Unpin + Send,
         W: AsyncWrite + Unpin + Send;
+
+    /// 异步流式加密，附加认证数据以所有权形式传入，便于跨 `.await` 传递
+    async fn encrypt_stream_async_owned_aad<R, W>(
+        key: &Self::Key,
+        reader: R,
+        writer: W,
+        config: &StreamingConfig,
+        additional_data: Option<Vec<u8>>,
+    ) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        Self::Key: Sync,
+    {
+        Self::encrypt_stream_async(key, reader, writer, config, additional_data.as_deref()).await
+    }
+
+    /// 异步流式解密，附加认证数据以所有权形式传入，便于跨 `.await` 传递
+    async fn decrypt_stream_async_owned_aad<R, W>(
+        key: &Self::Key,
+        reader: R,
+        writer: W,
+        config: &StreamingConfig,
+        additional_data: Option<Vec<u8>>,
+    ) -> Result<StreamingResult, Error>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+        Self::Key: Sync,
+    {
+        Self::decrypt_stream_async(key, reader, writer, config, additional_data.as_deref()).await
+    }
 }
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! 确定性测试向量的生成与校验
+//!
+//! 在固定种子的确定性RNG下生成规范的测试向量（输入、密钥与期望密文），用于发现密文格式的意外变化。
+//! 向量文件为纯文本，每行一个向量，字段以制表符分隔：
+//!
+//! `名称  种子  明文(十六进制)  附加数据(十六进制, "-" 表示无)  密钥(Base64)  期望输出(Base64)`
+//!
+//! 空行与 `#` 开头的注释行会被忽略。校验时按每行的种子与输入重新计算密钥和输出，并确认期望输出可以解密回明文。
+
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::common::errors::Error;
+use crate::common::rng::EngineRng;
+use crate::common::streaming::StreamingConfig;
+use crate::common::utils::{to_base64, CryptoConfig};
+use crate::symmetric::systems::aes_gcm::{AesGcmKey, AesGcmSystem};
+use crate::symmetric::traits::{SymmetricCryptographicSystem, SymmetricSyncStreamingSystem};
+
+/// 一次性 AES-256-GCM 加密
+const KIND_AES_GCM: &str = "aes-256-gcm";
+/// 启用帧元数据绑定与完整性尾部的 AES-256-GCM 流式加密
+const KIND_AES_GCM_STREAM: &str = "aes-256-gcm-stream";
+/// 流式向量使用的分块大小，保证多数输入被拆分为多帧
+const STREAM_BUFFER_SIZE: usize = 16;
+
+/// 规范向量的输入：(种类, 种子, 明文, 附加数据)
+type VectorInput = (&'static str, u64, Vec<u8>, Option<Vec<u8>>);
+
+fn canonical_inputs() -> Vec<VectorInput> {
+    let mut inputs = Vec::new();
+    for len in [0usize, 1, 15, 16, 17, 64, 1000] {
+        let plaintext: Vec<u8> = (0..len).map(|i| (i * 31 + 7) as u8).collect();
+        inputs.push((KIND_AES_GCM, plaintext.clone(), None));
+        inputs.push((KIND_AES_GCM, plaintext.clone(), Some(b"seal-kit test vector".to_vec())));
+        inputs.push((KIND_AES_GCM_STREAM, plaintext, Some(b"stream".to_vec())));
+    }
+    // 每个向量使用不同的种子，避免同一密钥下重复使用 Nonce
+    inputs.into_iter()
+        .enumerate()
+        .map(|(index, (kind, plaintext, aad))| (kind, 1000 + index as u64, plaintext, aad))
+        .collect()
+}
+
+/// 生成规范测试向量文件的内容
+pub fn generate() -> Result<String, Error> {
+    let mut output = String::from("# seal-kit 测试向量；由 seal_kit::testvectors::generate() 生成，请勿手工编辑\n");
+    for (index, (kind, seed, plaintext, aad)) in canonical_inputs().into_iter().enumerate() {
+        let (key, expected) = compute(kind, seed, &plaintext, aad.as_deref())?;
+        output.push_str(&format!(
+            "{}-{:02}\t{}\t{}\t{}\t{}\t{}\n",
+            kind, index, seed, to_hex(&plaintext), aad.as_deref().map_or_else(|| "-".to_string(), to_hex),
+            AesGcmSystem::export_key(&key)?, expected
+        ));
+    }
+    Ok(output)
+}
+
+/// 校验向量文件，返回通过校验的向量数量
+///
+/// 任意一行的密钥或期望输出与重新计算的结果不一致、或期望输出无法解密回明文时返回 `Error::Format`。
+pub fn verify<P: AsRef<Path>>(path: P) -> Result<usize, Error> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut verified = 0;
+    for (line_number, line) in contents.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mismatch = |what: &str| Error::Format(format!("测试向量第 {} 行{}", line_number, what));
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, seed, plaintext, aad, key, expected] = fields[..] else {
+            return Err(mismatch("字段数量错误"));
+        };
+        let kind = name.rsplit_once('-').map_or(name, |(kind, _)| kind);
+        let seed = seed.parse::<u64>().map_err(|_| mismatch("种子无效"))?;
+        let plaintext = from_hex(plaintext).ok_or_else(|| mismatch("明文不是有效的十六进制"))?;
+        let aad = match aad {
+            "-" => None,
+            aad => Some(from_hex(aad).ok_or_else(|| mismatch("附加数据不是有效的十六进制"))?),
+        };
+
+        let (computed_key, computed) = compute(kind, seed, &plaintext, aad.as_deref())?;
+        if AesGcmSystem::export_key(&computed_key)? != key {
+            return Err(mismatch("的密钥与重新生成的结果不一致"));
+        }
+        if computed != expected {
+            return Err(mismatch("的期望输出与重新生成的结果不一致"));
+        }
+        if decrypt(kind, &computed_key, expected, aad.as_deref())? != plaintext {
+            return Err(mismatch("的期望输出无法解密回明文"));
+        }
+        verified += 1;
+    }
+    Ok(verified)
+}
+
+/// 在以 `seed` 初始化的确定性RNG下生成密钥并加密，返回 (密钥, Base64输出)
+fn compute(kind: &str, seed: u64, plaintext: &[u8], aad: Option<&[u8]>) -> Result<(AesGcmKey, String), Error> {
+    EngineRng::from_config(&CryptoConfig::test_deterministic(seed)).run(|| {
+        let key = AesGcmSystem::generate_key(&CryptoConfig::default())?;
+        let output = match kind {
+            KIND_AES_GCM => AesGcmSystem::encrypt(&key, plaintext, aad)?.to_string(),
+            KIND_AES_GCM_STREAM => {
+                let mut encrypted = Vec::new();
+                AesGcmSystem::encrypt_stream(&key, Cursor::new(plaintext), &mut encrypted, &stream_config(plaintext.len()), aad)?;
+                to_base64(&encrypted)
+            }
+            other => return Err(Error::Format(format!("未知的测试向量种类: {}", other))),
+        };
+        Ok((key, output))
+    })
+}
+
+fn decrypt(kind: &str, key: &AesGcmKey, expected: &str, aad: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+    match kind {
+        KIND_AES_GCM_STREAM => {
+            let encrypted = crate::common::utils::from_base64(expected)?;
+            let mut decrypted = Vec::new();
+            let config = stream_config(0);
+            AesGcmSystem::decrypt_stream(key, Cursor::new(encrypted), &mut decrypted, &config, aad)?;
+            Ok(decrypted)
+        }
+        _ => AesGcmSystem::decrypt(key, expected, aad),
+    }
+}
+
+fn stream_config(total_bytes: usize) -> StreamingConfig {
+    StreamingConfig::default()
+        .with_buffer_size(STREAM_BUFFER_SIZE)
+        .with_total_bytes(total_bytes as u64)
+        .with_frame_metadata_binding(true)
+        .with_integrity_footer(true)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generated_vectors_verify() {
+        let vectors = generate().unwrap();
+        assert_eq!(vectors, generate().unwrap());
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("vectors.txt");
+        std::fs::write(&path, &vectors).unwrap();
+        assert_eq!(verify(&path).unwrap(), canonical_inputs().len());
+    }
+
+    #[test]
+    fn test_modified_expected_output_fails() {
+        let vectors = generate().unwrap();
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("vectors.txt");
+
+        // 修改第一条向量期望输出的最后一个 Base64 字符
+        let mut lines: Vec<String> = vectors.lines().map(str::to_string).collect();
+        let line = &mut lines[1];
+        let last = line.pop().unwrap();
+        line.push(if last == 'A' { 'B' } else { 'A' });
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        let err = verify(&path).unwrap_err();
+        assert!(matches!(&err, Error::Format(message) if message.contains("第 2 行")), "{}", err);
+
+        std::fs::write(&path, "aes-256-gcm-00\t1\t00\n").unwrap();
+        assert!(matches!(verify(&path), Err(Error::Format(_))));
+    }
+}
@@ -0,0 +1,96 @@
+//! 确定性测试模式（`test-determinism` 特性）的集成测试
+use std::path::Path;
+use std::sync::Arc;
+
+use seal_kit::common::config::{ConfigFile, ConfigManager, StorageConfig};
+use seal_kit::common::utils::CryptoConfig;
+use seal_kit::rotation::RotationPolicy;
+use seal_kit::symmetric::systems::aes_gcm::AesGcmSystem;
+use seal_kit::{AsymmetricQSealEngine, SymmetricQSealEngine, TraditionalRsa};
+use tempfile::tempdir;
+
+fn config_manager(dir: &Path, crypto: CryptoConfig) -> Arc<ConfigManager> {
+    let config = ConfigFile {
+        storage: StorageConfig {
+            key_storage_dir: dir.to_str().unwrap().to_string(),
+            ..Default::default()
+        },
+        rotation: RotationPolicy::default(),
+        crypto,
+    };
+    Arc::new(ConfigManager::from_config_file(config))
+}
+
+#[test]
+fn symmetric_engines_with_same_seed_are_byte_identical() {
+    let (dir_a, dir_b) = (tempdir().unwrap(), tempdir().unwrap());
+    let mut engine_a = SymmetricQSealEngine::<AesGcmSystem>::new(
+        config_manager(dir_a.path(), CryptoConfig::test_deterministic(2024)), "golden",
+    ).unwrap();
+    let mut engine_b = SymmetricQSealEngine::<AesGcmSystem>::new(
+        config_manager(dir_b.path(), CryptoConfig::test_deterministic(2024)), "golden",
+    ).unwrap();
+
+    for message in [b"first".as_ref(), b"second", b""] {
+        let ct_a = engine_a.encrypt(message, Some(b"aad")).unwrap();
+        let ct_b = engine_b.encrypt(message, Some(b"aad")).unwrap();
+        assert_eq!(ct_a, ct_b);
+        assert_eq!(engine_b.decrypt(&ct_a, Some(b"aad")).unwrap(), message);
+    }
+}
+
+#[test]
+fn symmetric_engines_with_different_seeds_differ() {
+    let (dir_a, dir_b) = (tempdir().unwrap(), tempdir().unwrap());
+    let mut engine_a = SymmetricQSealEngine::<AesGcmSystem>::new(
+        config_manager(dir_a.path(), CryptoConfig::test_deterministic(1)), "golden",
+    ).unwrap();
+    let mut engine_b = SymmetricQSealEngine::<AesGcmSystem>::new(
+        config_manager(dir_b.path(), CryptoConfig::test_deterministic(2)), "golden",
+    ).unwrap();
+
+    assert_ne!(engine_a.encrypt(b"data", None).unwrap(), engine_b.encrypt(b"data", None).unwrap());
+}
+
+#[test]
+fn rsa_engines_with_same_seed_are_byte_identical() {
+    let crypto = CryptoConfig { rsa_key_bits: 2048, ..CryptoConfig::test_deterministic(99) };
+    let (dir_a, dir_b) = (tempdir().unwrap(), tempdir().unwrap());
+    let mut engine_a = AsymmetricQSealEngine::<TraditionalRsa>::new(
+        config_manager(dir_a.path(), crypto.clone()), "golden",
+    ).unwrap();
+    let mut engine_b = AsymmetricQSealEngine::<TraditionalRsa>::new(
+        config_manager(dir_b.path(), crypto), "golden",
+    ).unwrap();
+
+    let ct_a = engine_a.encrypt(b"golden file").unwrap();
+    let ct_b = engine_b.encrypt(b"golden file").unwrap();
+    assert_eq!(ct_a, ct_b);
+    assert_eq!(engine_a.decrypt(&ct_b).unwrap(), b"golden file");
+}
+
+#[cfg(feature = "post-quantum")]
+#[test]
+fn kyber_engines_refuse_deterministic_seed() {
+    use seal_kit::{HybridRsaKyber, PostQuantumKyber};
+
+    let dir = tempdir().unwrap();
+    let crypto = CryptoConfig { rsa_key_bits: 2048, ..CryptoConfig::test_deterministic(5) };
+    let result = AsymmetricQSealEngine::<PostQuantumKyber>::new(config_manager(dir.path(), crypto.clone()), "golden");
+    assert!(matches!(result, Err(seal_kit::Error::Operation(_))));
+    let result = AsymmetricQSealEngine::<HybridRsaKyber>::new(config_manager(dir.path(), crypto), "golden");
+    assert!(matches!(result, Err(seal_kit::Error::Operation(_))));
+
+    // 未设置种子时照常创建
+    let crypto = CryptoConfig { rsa_key_bits: 2048, ..CryptoConfig::default() };
+    assert!(AsymmetricQSealEngine::<PostQuantumKyber>::new(config_manager(dir.path(), crypto), "random").is_ok());
+}
+
+#[cfg(feature = "async-engine")]
+#[test]
+fn async_engine_refuses_deterministic_seed() {
+    let dir = tempdir().unwrap();
+    let crypto = CryptoConfig { rsa_key_bits: 2048, ..CryptoConfig::test_deterministic(5) };
+    let result = seal_kit::AsymmetricQSealEngineAsync::<TraditionalRsa>::new(config_manager(dir.path(), crypto), "golden");
+    assert!(matches!(result, Err(seal_kit::Error::Operation(_))));
+}
@@ -0,0 +1,31 @@
+//! 以 `#![no_std]` 编译并调用 seal-kit 的对称加密核心
+//!
+//! 库本身不链接 `std`，若 seal-kit 在关闭 `std` 特性后仍引用了标准库，这里会编译失败。
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use seal_kit::common::errors::Error;
+use seal_kit::common::utils::CryptoConfig;
+use seal_kit::symmetric::systems::aes_gcm::AesGcmSystem;
+use seal_kit::symmetric::traits::SymmetricCryptographicSystem;
+
+/// 生成 AES-GCM 密钥并完成一次加解密往返
+pub fn aes_gcm_roundtrip(plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+    let key = AesGcmSystem::generate_key(&CryptoConfig::default())?;
+    let ciphertext = AesGcmSystem::encrypt(&key, plaintext, Some(aad))?;
+    AesGcmSystem::decrypt(&key, &ciphertext.to_string(), Some(aad))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_without_std() {
+        assert_eq!(aes_gcm_roundtrip(b"no_std payload", b"aad").unwrap(), b"no_std payload");
+    }
+}
@@ -0,0 +1,14 @@
+//! 校验仓库中保存的规范测试向量，密文格式发生变化时失败
+use seal_kit::testvectors;
+
+#[test]
+fn stored_test_vectors_still_verify() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_vectors.txt");
+    assert_eq!(testvectors::verify(path).unwrap(), 21);
+}
+
+#[test]
+fn stored_test_vectors_match_generator() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_vectors.txt");
+    assert_eq!(std::fs::read_to_string(path).unwrap(), testvectors::generate().unwrap());
+}